@@ -8,28 +8,75 @@
 mod analyze;
 mod assignment;
 mod association;
+mod binding;
+mod case_statement;
+mod clock_domain;
+mod combinational_loop;
 mod concurrent;
+mod connectivity;
+mod dataflow;
 mod declarative;
 mod design_unit;
+mod drivers;
+mod explain;
 mod expression;
+mod external_name;
 mod formal_region;
+mod generic_doc;
+mod generic_folding;
+mod generic_override;
+mod hierarchy;
 mod literals;
 mod lock;
 mod named_entity;
 mod names;
 mod overloaded;
 mod package_instance;
+mod port_record;
+mod predefined_attribute;
+mod process;
+mod purity;
 mod range;
 mod region;
+mod return_path;
 mod root;
 mod semantic;
 mod sequential;
+mod spelling;
 mod standard;
+mod state_machine;
+mod static_expression;
+mod structural_search;
+mod synthesis;
 mod target;
+mod testbench;
+mod unused;
+mod use_scope;
 mod visibility;
+mod visible_names;
+mod wait_graph;
 
 #[cfg(test)]
 mod tests;
 
 pub use self::root::DesignRoot;
-pub use named_entity::{AnyEnt, AnyEntKind, EntRef, EntityId, HasEntityId, Related};
+pub use binding::ArchitectureSelection;
+pub use clock_domain::{ClockDomainCrossing, ClockDomainCrossingReport};
+pub use connectivity::{
+    Connection, ConnectivityReport, PortConnection, UnusedPort, UnusedPortReport,
+};
+pub use dataflow::{DataflowEdge, DataflowNode, DataflowNodeKind, ProcessDataflow};
+pub use explain::{ExplainedEntity, OverloadCandidate, ResolutionExplanation, UseClauseCandidate};
+pub use generic_doc::GenericDocumentation;
+pub use named_entity::{
+    AnyEnt, AnyEntKind, Design, EntRef, EntityId, HasEntityId, PrettyEnt, Related, StaticRange,
+    StaticValue,
+};
+pub use port_record::PortGrouping;
+pub use process::{
+    classify_process, ActiveLevel, ClassifiedProcess, ClockEdge, ClockedProcess, ProcessKind,
+    ProcessReset, ResetConvention, ResetStyle,
+};
+pub use state_machine::{StateMachine, StateMachineReport, StateTransition};
+pub use structural_search::{StructuralMatch, StructuralPattern};
+pub use visible_names::{VisibleName, VisibleOrigin};
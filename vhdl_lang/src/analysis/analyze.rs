@@ -4,13 +4,16 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
+use super::explain::ResolutionTrace;
 use super::named_entity::*;
 use super::region::*;
 use super::root::*;
+use super::visible_names::ScopeProbe;
+use super::wait_graph::Caller;
 use crate::ast::*;
 use crate::data::*;
-use fnv::FnvHashSet;
-use std::cell::RefCell;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -133,7 +136,7 @@ pub(super) struct AnalyzeContext<'a> {
     pub(super) root: &'a DesignRoot,
 
     pub work_sym: Symbol,
-    std_sym: Symbol,
+    pub std_sym: Symbol,
     standard_sym: Symbol,
 
     // Record dependencies and sensitivies when
@@ -154,6 +157,18 @@ pub(super) struct AnalyzeContext<'a> {
     uses: RefCell<FnvHashSet<UnitId>>,
     missing_primary: RefCell<FnvHashSet<(Symbol, Symbol)>>,
     uses_library_all: RefCell<FnvHashSet<Symbol>>,
+    /// Assumed values of generics which are evaluated as static
+    pub(super) generics: FnvHashMap<EntityId, StaticValue>,
+    /// Captures the names visible at a position
+    pub(super) probe: Option<ScopeProbe>,
+    /// Captures how the name at a position is resolved
+    pub(super) trace: Option<ResolutionTrace>,
+    /// Whether the design unit contains external names, their paths are resolved
+    /// against the design hierarchy once all design units have been analyzed
+    pub(super) has_external_names: Cell<bool>,
+    /// The wait statements and procedure calls of subprogram bodies and processes which are
+    /// checked against the call graph once all design units have been analyzed
+    pub(super) callers: RefCell<Vec<Caller>>,
 }
 
 impl<'a> AnalyzeContext<'a> {
@@ -172,9 +187,32 @@ impl<'a> AnalyzeContext<'a> {
             uses: RefCell::new(FnvHashSet::default()),
             missing_primary: RefCell::new(FnvHashSet::default()),
             uses_library_all: RefCell::new(FnvHashSet::default()),
+            generics: FnvHashMap::default(),
+            probe: None,
+            trace: None,
+            has_external_names: Cell::new(false),
+            callers: RefCell::new(Vec::new()),
         }
     }
 
+    /// Evaluate names of generics to the given values
+    pub fn with_generics(mut self, generics: FnvHashMap<EntityId, StaticValue>) -> Self {
+        self.generics = generics;
+        self
+    }
+
+    /// Capture the names visible at the position
+    pub fn with_probe(mut self, pos: SrcPos) -> Self {
+        self.probe = Some(ScopeProbe::new(pos));
+        self
+    }
+
+    /// Capture how the name at the position is resolved
+    pub fn with_trace(mut self, pos: SrcPos) -> Self {
+        self.trace = Some(ResolutionTrace::new(pos));
+        self
+    }
+
     pub fn work_library_name(&self) -> &Symbol {
         self.current_unit.library_name()
     }
@@ -306,6 +344,33 @@ impl<'a> AnalyzeContext<'a> {
         self.get_package_body().is_some()
     }
 
+    /// Check if an architecture of the entity exists within the library
+    /// The architecture itself is not analyzed
+    pub fn has_architecture(
+        &self,
+        library_name: &Symbol,
+        entity_name: &Symbol,
+        architecture_name: &Symbol,
+    ) -> bool {
+        let exists = self
+            .root
+            .get_library_units(library_name)
+            .and_then(|units| {
+                units.get(&UnitKey::Secondary(
+                    entity_name.clone(),
+                    architecture_name.clone(),
+                ))
+            })
+            .map(|unit| unit.kind() == AnyKind::Secondary(SecondaryKind::Architecture))
+            .unwrap_or(false);
+
+        if !exists {
+            // Ensure re-analysis when the architecture is added
+            self.make_use_of_missing_primary(library_name, entity_name);
+        }
+        exists
+    }
+
     fn get_analysis(
         &self,
         use_pos: Option<&SrcPos>,
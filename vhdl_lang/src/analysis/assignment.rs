@@ -24,9 +24,23 @@ impl<'a> AnalyzeContext<'a> {
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         let ttyp = as_fatal(self.resolve_target(scope, target, assignment_type, diagnostics))?;
+        let range = self.target_range(target, ttyp);
+        self.analyze_expr_right_hand(scope, ttyp, range.as_ref(), rhs, diagnostics)
+    }
+
+    /// Analyze the expressions of a right hand side with the type of its target
+    /// The conditions of a conditional right hand side are boolean
+    pub fn analyze_expr_right_hand(
+        &self,
+        scope: &Scope<'a>,
+        ttyp: Option<TypeEnt<'a>>,
+        range: Option<&StaticRange>,
+        rhs: &mut AssignmentRightHand<WithPos<Expression>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
         match rhs {
             AssignmentRightHand::Simple(expr) => {
-                self.analyze_expression_for_target(scope, ttyp, expr, diagnostics)?;
+                self.analyze_expression_for_target(scope, ttyp, range, expr, diagnostics)?;
             }
             AssignmentRightHand::Conditional(conditionals) => {
                 let Conditionals {
@@ -35,11 +49,11 @@ impl<'a> AnalyzeContext<'a> {
                 } = conditionals;
                 for conditional in conditionals {
                     let Conditional { condition, item } = conditional;
-                    self.analyze_expression_for_target(scope, ttyp, item, diagnostics)?;
+                    self.analyze_expression_for_target(scope, ttyp, range, item, diagnostics)?;
                     self.boolean_expr(scope, condition, diagnostics)?;
                 }
                 if let Some(expr) = else_item {
-                    self.analyze_expression_for_target(scope, ttyp, expr, diagnostics)?;
+                    self.analyze_expression_for_target(scope, ttyp, range, expr, diagnostics)?;
                 }
             }
             AssignmentRightHand::Selected(selection) => {
@@ -49,7 +63,7 @@ impl<'a> AnalyzeContext<'a> {
                 } = selection;
                 let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
                 for Alternative { choices, item } in alternatives.iter_mut() {
-                    self.analyze_expression_for_target(scope, ttyp, item, diagnostics)?;
+                    self.analyze_expression_for_target(scope, ttyp, range, item, diagnostics)?;
                     self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
                 }
             }
@@ -57,6 +71,41 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    /// LRM 10.5.3, 10.5.4, 10.6.2 Conditional and selected assignments are only
+    /// sequential statements since VHDL-2008
+    pub fn check_sequential_right_hand<T>(
+        &self,
+        rhs: &AssignmentRightHand<T>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.root.standard() >= VHDLStandard::VHDL2008 {
+            return;
+        }
+        match rhs {
+            AssignmentRightHand::Simple(..) => {}
+            AssignmentRightHand::Conditional(conditionals) => {
+                if let Some(first) = conditionals.conditionals.first() {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            &first.condition.pos,
+                            "Conditional assignment cannot be a sequential statement before VHDL-2008",
+                        )
+                        .with_code(ErrorCode::LaterRevision),
+                    );
+                }
+            }
+            AssignmentRightHand::Selected(selection) => {
+                diagnostics.push(
+                    Diagnostic::error(
+                        &selection.expression.pos,
+                        "Selected assignment cannot be a sequential statement before VHDL-2008",
+                    )
+                    .with_code(ErrorCode::LaterRevision),
+                );
+            }
+        }
+    }
+
     pub fn analyze_waveform_assignment(
         &self,
         scope: &Scope<'a>,
@@ -66,9 +115,11 @@ impl<'a> AnalyzeContext<'a> {
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         let ttyp = as_fatal(self.resolve_target(scope, target, assignment_type, diagnostics))?;
+        let range = self.target_range(target, ttyp);
+        let range = range.as_ref();
         match rhs {
             AssignmentRightHand::Simple(wavf) => {
-                self.analyze_waveform(scope, ttyp, wavf, diagnostics)?;
+                self.analyze_waveform(scope, ttyp, range, wavf, diagnostics)?;
             }
             AssignmentRightHand::Conditional(conditionals) => {
                 let Conditionals {
@@ -77,11 +128,11 @@ impl<'a> AnalyzeContext<'a> {
                 } = conditionals;
                 for conditional in conditionals {
                     let Conditional { condition, item } = conditional;
-                    self.analyze_waveform(scope, ttyp, item, diagnostics)?;
+                    self.analyze_waveform(scope, ttyp, range, item, diagnostics)?;
                     self.boolean_expr(scope, condition, diagnostics)?;
                 }
                 if let Some(wavf) = else_item {
-                    self.analyze_waveform(scope, ttyp, wavf, diagnostics)?;
+                    self.analyze_waveform(scope, ttyp, range, wavf, diagnostics)?;
                 }
             }
             AssignmentRightHand::Selected(selection) => {
@@ -91,7 +142,7 @@ impl<'a> AnalyzeContext<'a> {
                 } = selection;
                 let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
                 for Alternative { choices, item } in alternatives.iter_mut() {
-                    self.analyze_waveform(scope, ttyp, item, diagnostics)?;
+                    self.analyze_waveform(scope, ttyp, range, item, diagnostics)?;
                     self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
                 }
             }
@@ -103,6 +154,7 @@ impl<'a> AnalyzeContext<'a> {
         &self,
         scope: &Scope<'a>,
         ttyp: Option<TypeEnt<'a>>,
+        range: Option<&StaticRange>,
         wavf: &mut Waveform,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
@@ -110,7 +162,7 @@ impl<'a> AnalyzeContext<'a> {
             Waveform::Elements(ref mut elems) => {
                 for elem in elems.iter_mut() {
                     let WaveformElement { value, after } = elem;
-                    self.analyze_expression_for_target(scope, ttyp, value, diagnostics)?;
+                    self.analyze_expression_for_target(scope, ttyp, range, value, diagnostics)?;
                     if let Some(expr) = after {
                         self.expr_with_ttyp(scope, self.time(), expr, diagnostics)?;
                     }
@@ -125,14 +177,33 @@ impl<'a> AnalyzeContext<'a> {
         &self,
         scope: &Scope<'a>,
         ttyp: Option<TypeEnt<'a>>,
+        range: Option<&StaticRange>,
         expr: &mut WithPos<Expression>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         if let Some(ttyp) = ttyp {
             self.expr_with_ttyp(scope, ttyp, expr, diagnostics)?;
+            if let Some(range) = range {
+                self.check_aggregate_length(scope, ttyp, range, expr, diagnostics);
+            }
         } else {
             self.expr_unknown_ttyp(scope, expr, diagnostics)?;
         }
         Ok(())
     }
+
+    /// The static index range of a one-dimensional array target
+    fn target_range(
+        &self,
+        target: &WithPos<Target>,
+        ttyp: Option<TypeEnt<'a>>,
+    ) -> Option<StaticRange> {
+        let Target::Name(ref name) = target.item else {
+            return None;
+        };
+        match self.name_ranges(name, ttyp)?.as_slice() {
+            [range] => Some(*range),
+            _ => None,
+        }
+    }
 }
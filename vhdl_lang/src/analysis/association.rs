@@ -1,6 +1,6 @@
 #![allow(clippy::only_used_in_recursion)]
 
-use fnv::FnvHashSet;
+use fnv::{FnvHashMap, FnvHashSet};
 
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this file,
@@ -59,6 +59,14 @@ impl<'a> ResolvedFormal<'a> {
         }
     }
 
+    fn iface(&self) -> InterfaceEnt<'a> {
+        *match self {
+            ResolvedFormal::Basic(_, ent) => ent,
+            ResolvedFormal::Selected(_, ent, _) => ent,
+            ResolvedFormal::Converted(_, ent, _) => ent,
+        }
+    }
+
     // The position of the formal in the formal region
     fn idx(&self) -> usize {
         *match self {
@@ -67,6 +75,55 @@ impl<'a> ResolvedFormal<'a> {
             ResolvedFormal::Converted(idx, _, _) => idx,
         }
     }
+
+    /// The mode of the formal, which is the mode of the selected element
+    /// for a port with a mode view
+    fn mode(&self, name: Option<&Name>) -> Option<Mode> {
+        let iface = self.iface();
+        let (
+            AnyEntKind::Object(Object {
+                view: Some(view), ..
+            }),
+            Some(name),
+        ) = (iface.kind(), name)
+        else {
+            return iface.mode();
+        };
+
+        // The formal designator of a converted formal is the argument of the conversion
+        let name = match (self, name) {
+            (ResolvedFormal::Converted(..), Name::CallOrIndexed(fcall)) => {
+                match fcall.parameters.first().map(|param| &param.actual.item) {
+                    Some(ActualPart::Expression(Expression::Name(name))) => name.as_ref(),
+                    _ => return iface.mode(),
+                }
+            }
+            _ => name,
+        };
+
+        match view_element_mode(*view, name) {
+            Some(ViewElementMode::Simple(mode)) => Some(mode),
+            Some(ViewElementMode::Record(view) | ViewElementMode::Array(view)) => Some(view.mode()),
+            None => iface.mode(),
+        }
+    }
+}
+
+/// The mode of the (sub)element of a port with a mode view that is denoted by the name.
+/// Indexes and slices of an array with a mode view keep the view of its elements.
+fn view_element_mode<'a>(view: ViewEnt<'a>, name: &Name) -> Option<ViewElementMode<'a>> {
+    match name {
+        Name::Designator(_) => Some(ViewElementMode::Record(view)),
+        Name::Selected(prefix, suffix) => match view_element_mode(view, &prefix.item)? {
+            ViewElementMode::Record(view) | ViewElementMode::Array(view) => {
+                view.element_mode(suffix.item.designator())
+            }
+            mode @ ViewElementMode::Simple(_) => Some(mode),
+        },
+        Name::Slice(prefix, _) => view_element_mode(view, &prefix.item),
+        Name::CallOrIndexed(fcall) => view_element_mode(view, &fcall.name.item),
+        Name::SelectedAll(_) | Name::Attribute(_) | Name::External(_) => None,
+    }
 }
 
 impl<'a> AnalyzeContext<'a> {
@@ -153,24 +210,20 @@ impl<'a> AnalyzeContext<'a> {
                         diagnostics,
                     ))? {
                         Some(ResolvedName::Type(typ)) => {
-                            if let Some((expr_pos, expr)) =
-                                as_type_conversion(&mut fcall.parameters)
-                            {
-                                self.check_type_conversion(
-                                    scope,
-                                    typ,
-                                    expr_pos,
-                                    expr,
-                                    diagnostics,
-                                )?;
-                                typ
-                            } else {
+                            // The formal is not visible in the scope so the
+                            // conversion is checked using its type
+                            if !typ.base().is_closely_related(formal_ent.base_type().base()) {
                                 return Err(Diagnostic::error(
                                     name_pos,
-                                    "Invalid formal type conversion",
+                                    format!(
+                                        "{} cannot be converted to {}",
+                                        formal_ent.type_mark().describe(),
+                                        typ.describe()
+                                    ),
                                 )
                                 .into());
                             }
+                            typ
                         }
                         Some(ResolvedName::Overloaded(des, overloaded)) => {
                             let mut candidates = Vec::with_capacity(overloaded.len());
@@ -263,9 +316,11 @@ impl<'a> AnalyzeContext<'a> {
         let mut missing = false;
         let mut associated_indexes: FnvHashSet<usize> = Default::default();
         let mut extra_associations: Vec<SrcPos> = Default::default();
+        // The first association of each formal and whether it associates the whole formal
+        let mut first_associations: FnvHashMap<usize, (bool, SrcPos)> = Default::default();
 
         for (idx, AssociationElement { formal, actual }) in elems.iter_mut().enumerate() {
-            if let Some(ref mut formal) = formal {
+            let (resolved, pos) = if let Some(ref mut formal) = formal {
                 // Call by name using formal
                 match self.resolve_formal(
                     formal_region,
@@ -277,18 +332,41 @@ impl<'a> AnalyzeContext<'a> {
                     Err(err) => {
                         missing = true;
                         diagnostics.push(err.into_non_fatal()?);
+                        continue;
                     }
-                    Ok(formal) => {
-                        associated_indexes.insert(formal.idx());
-                        result.push(formal);
-                    }
+                    Ok(resolved) => (resolved, &formal.pos),
                 }
             } else if let Some(formal) = formal_region.nth(idx) {
-                associated_indexes.insert(idx);
-                result.push(ResolvedFormal::Basic(idx, formal));
+                (ResolvedFormal::Basic(idx, formal), &actual.pos)
             } else {
                 extra_associations.push(actual.pos.clone());
+                continue;
             };
+
+            // Individual associations of parts of the same formal are legal
+            // but a formal associated as a whole may not be associated again
+            let whole = matches!(resolved, ResolvedFormal::Basic(..));
+            match first_associations.get(&resolved.idx()) {
+                Some((prev_whole, prev_pos)) if whole || *prev_whole => {
+                    missing = true;
+                    let mut diagnostic = Diagnostic::error(
+                        pos,
+                        format!(
+                            "{} is associated more than once",
+                            capitalize(&resolved.iface().describe())
+                        ),
+                    );
+                    diagnostic.add_related(prev_pos, "Previously associated here");
+                    diagnostics.push(diagnostic);
+                }
+                Some(_) => {}
+                None => {
+                    first_associations.insert(resolved.idx(), (whole, pos.clone()));
+                }
+            }
+
+            associated_indexes.insert(resolved.idx());
+            result.push(resolved);
         }
 
         let mut not_associated = Vec::new();
@@ -343,19 +421,53 @@ impl<'a> AnalyzeContext<'a> {
             elems,
             diagnostics,
         ))? {
-            for (formal, actual) in formals
-                .iter()
-                .zip(elems.iter_mut().map(|assoc| &mut assoc.actual))
+            for (
+                formal,
+                AssociationElement {
+                    formal: name,
+                    actual,
+                },
+            ) in formals.iter().zip(elems.iter_mut())
             {
+                let mode = formal.mode(name.as_ref().map(|name| &name.item));
+                if let (ResolvedFormal::Converted(..), Some(name)) = (formal, name) {
+                    if mode == Some(Mode::In) {
+                        diagnostics.error(&name.pos, "A formal of mode in cannot be converted");
+                    }
+                }
+
                 match &mut actual.item {
                     ActualPart::Expression(expr) => {
-                        self.expr_pos_with_ttyp(
+                        if !self.analyze_actual_conversion(
                             scope,
-                            formal.type_mark(),
+                            formal,
+                            mode,
                             &actual.pos,
                             expr,
                             diagnostics,
-                        )?;
+                        )? {
+                            match expr {
+                                // The actual of a formal of mode out is only written
+                                Expression::Name(name) if mode == Some(Mode::Out) => {
+                                    self.written_name_with_ttyp(
+                                        scope,
+                                        &actual.pos,
+                                        name.as_mut(),
+                                        formal.type_mark(),
+                                        diagnostics,
+                                    )?;
+                                }
+                                _ => {
+                                    self.expr_pos_with_ttyp(
+                                        scope,
+                                        formal.type_mark(),
+                                        &actual.pos,
+                                        expr,
+                                        diagnostics,
+                                    )?;
+                                }
+                            }
+                        }
                     }
                     ActualPart::Open => {}
                 }
@@ -363,6 +475,145 @@ impl<'a> AnalyzeContext<'a> {
         }
         Ok(())
     }
+
+    /// Analyze an actual that is a conversion such as `function_name(actual)` or `type_mark(actual)`.
+    /// The conversion returns the type of the formal while the actual designator has the type
+    /// of the formal after its own conversion.
+    /// Returns false if the actual is not a conversion and shall be analyzed as an expression.
+    fn analyze_actual_conversion(
+        &self,
+        scope: &Scope<'a>,
+        formal: &ResolvedFormal<'a>,
+        mode: Option<Mode>,
+        actual_pos: &SrcPos,
+        expr: &mut Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<bool> {
+        let Expression::Name(ref mut name) = expr else {
+            return Ok(false);
+        };
+        let Name::CallOrIndexed(ref mut fcall) = name.as_mut() else {
+            return Ok(false);
+        };
+        if !is_actual_conversion_argument(&fcall.parameters) {
+            return Ok(false);
+        }
+
+        let Some(resolved) = as_fatal(self.name_resolve(
+            scope,
+            &fcall.name.pos,
+            &mut fcall.name.item,
+            &mut NullDiagnostics,
+        ))?
+        else {
+            return Ok(false);
+        };
+
+        let is_conversion = match resolved {
+            ResolvedName::Type(_) => true,
+            ResolvedName::Overloaded(_, ref overloaded) => {
+                overloaded.entities().any(|ent| ent.is_function())
+            }
+            _ => false,
+        };
+
+        if !is_conversion {
+            return Ok(false);
+        }
+
+        let ResolvedFormal::Converted(_, iface, converted_typ) = *formal else {
+            if let Some(mode @ (Mode::Out | Mode::Buffer)) = mode {
+                diagnostics.error(
+                    actual_pos,
+                    format!("The actual of a formal of mode {mode} cannot be converted"),
+                );
+            }
+            // A conversion of the actual of the unconverted formal is an ordinary expression
+            return Ok(false);
+        };
+
+        let formal_typ = iface.type_mark();
+        match resolved {
+            ResolvedName::Type(typ) => {
+                if typ.base() != formal_typ.base() {
+                    diagnostics.error(
+                        &fcall.name.pos,
+                        format!(
+                            "{} does not match {}",
+                            typ.describe(),
+                            formal_typ.describe()
+                        ),
+                    );
+                } else if !typ.base().is_closely_related(converted_typ.base()) {
+                    diagnostics.error(
+                        &fcall.name.pos,
+                        format!(
+                            "{} cannot be converted to {}",
+                            converted_typ.describe(),
+                            typ.describe()
+                        ),
+                    );
+                }
+                if let Some(reference) = fcall.name.item.suffix_reference_mut() {
+                    reference.set_unique_reference(&typ);
+                }
+            }
+            ResolvedName::Overloaded(des, overloaded) => {
+                let mut candidates: Vec<_> = overloaded
+                    .entities()
+                    .filter(|ent| {
+                        ent.is_function()
+                            && ent
+                                .signature()
+                                .can_be_called_with_single_parameter(converted_typ)
+                            && ent.signature().match_return_type(Some(formal_typ))
+                    })
+                    .collect();
+
+                if candidates.len() > 1 {
+                    let mut diagnostic = Diagnostic::error(
+                        &fcall.name.pos,
+                        format!("Ambiguous call to function '{des}'"),
+                    );
+                    diagnostic.add_subprogram_candidates("might be", candidates);
+                    diagnostics.push(diagnostic);
+                } else if let Some(ent) = candidates.pop() {
+                    fcall.name.set_unique_reference(&ent);
+                } else {
+                    diagnostics.error(
+                        &fcall.name.pos,
+                        format!(
+                            "No function '{}' converting {} to {}",
+                            fcall.name,
+                            converted_typ.describe(),
+                            formal_typ.describe()
+                        ),
+                    );
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        // The actual designator has the type of the converted formal
+        if let Some((arg_pos, arg)) = as_type_conversion(&mut fcall.parameters) {
+            self.expr_pos_with_ttyp(scope, converted_typ, arg_pos, arg, diagnostics)?;
+        }
+        Ok(true)
+    }
+}
+
+/// True if the parameters of an actual conversion are a single positional actual designator
+fn is_actual_conversion_argument(parameters: &[AssociationElement]) -> bool {
+    matches!(
+        parameters,
+        [AssociationElement {
+            formal: None,
+            actual: WithPos {
+                item: ActualPart::Expression(Expression::Name(_)),
+                ..
+            },
+        }]
+    )
 }
 
 fn to_formal_conversion_argument(
@@ -0,0 +1,305 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Selection of the architecture of an entity by the default binding, LRM 7.3.3
+
+use super::connectivity::visit_instances;
+use super::named_entity::*;
+use super::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+use std::cmp::Ordering;
+use std::path::Path;
+
+/// How the default binding selects one of several architectures of an entity
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ArchitectureSelection {
+    /// The most recently analyzed architecture as defined by the LRM.
+    /// The files of a library are analyzed in the order of the configuration
+    /// and the architectures of a file in the order in which they occur.
+    #[default]
+    LastAnalyzed,
+    /// The first architecture in alphabetical order, independent of the order of analysis
+    Alphabetical,
+}
+
+impl ArchitectureSelection {
+    pub fn from_name(name: &str) -> Option<ArchitectureSelection> {
+        match name {
+            "last_analyzed" => Some(ArchitectureSelection::LastAnalyzed),
+            "alphabetical" => Some(ArchitectureSelection::Alphabetical),
+            _ => None,
+        }
+    }
+}
+
+/// The component instances within an architecture that a configuration declaration
+/// binds to an explicit architecture or configuration
+struct PinnedInstances {
+    /// The labels of the enclosing block and generate statements separated by dots
+    path: String,
+    component: Designator,
+    /// None for all instances of the component
+    labels: Option<Vec<Symbol>>,
+}
+
+impl PinnedInstances {
+    fn applies_to(&self, path: &str, component_name: &SelectedName) -> bool {
+        let (prefix, label) = path.rsplit_once('.').unwrap_or(("", path));
+        self.path == prefix
+            && &self.component == suffix_designator(component_name)
+            && self
+                .labels
+                .as_ref()
+                .is_none_or(|labels| labels.iter().any(|sym| sym.to_string() == label))
+    }
+}
+
+impl DesignRoot {
+    /// Select the architecture that the default binding denotes among the architectures
+    /// of an entity given by their name and position
+    pub(super) fn select_architecture<'t, T>(
+        &self,
+        architectures: &'t [T],
+        key: impl Fn(&T) -> (&Symbol, &SrcPos),
+    ) -> Option<&'t T> {
+        architectures
+            .iter()
+            .max_by(|lhs, rhs| self.compare_architectures(key(lhs), key(rhs)))
+    }
+
+    /// The selected architecture compares greater than the others
+    fn compare_architectures(&self, lhs: (&Symbol, &SrcPos), rhs: (&Symbol, &SrcPos)) -> Ordering {
+        match self.architecture_selection {
+            ArchitectureSelection::LastAnalyzed => {
+                self.analysis_order(lhs.1).cmp(&self.analysis_order(rhs.1))
+            }
+            ArchitectureSelection::Alphabetical => rhs
+                .0
+                .name_utf8()
+                .to_lowercase()
+                .cmp(&lhs.0.name_utf8().to_lowercase()),
+        }
+    }
+
+    /// Files that are not part of the configuration are considered analyzed after
+    /// those that are, in the order of their names
+    fn analysis_order<'p>(&self, pos: &'p SrcPos) -> (usize, &'p Path, Position) {
+        let file_name = pos.file_name();
+        let order = self
+            .compile_order
+            .get(file_name)
+            .copied()
+            .unwrap_or(usize::MAX);
+        (order, file_name, pos.start())
+    }
+
+    /// The name and position of the architectures of each entity
+    fn architectures_by_entity(&self) -> FnvHashMap<EntityId, Vec<(Symbol, SrcPos)>> {
+        let mut architectures: FnvHashMap<EntityId, Vec<(Symbol, SrcPos)>> = FnvHashMap::default();
+
+        for locked_unit in self.locked_units() {
+            let Some(unit) = locked_unit.unit.get() else {
+                continue;
+            };
+            if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref arch)) = *unit {
+                if let Some(entity) = arch.entity_name.reference {
+                    architectures
+                        .entry(entity)
+                        .or_default()
+                        .push((arch.ident.item.clone(), arch.ident.pos.clone()));
+                }
+            }
+        }
+        architectures
+    }
+
+    /// The component instances bound to an explicit architecture or configuration by the
+    /// configuration declarations of the design by the entity and architecture they are within
+    fn pinned_instances(&self) -> FnvHashMap<(EntityId, Symbol), Vec<PinnedInstances>> {
+        let mut pinned: FnvHashMap<(EntityId, Symbol), Vec<PinnedInstances>> =
+            FnvHashMap::default();
+
+        for locked_unit in self.locked_units() {
+            let Some(unit) = locked_unit.unit.get() else {
+                continue;
+            };
+            let AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(ref config)) = *unit else {
+                continue;
+            };
+            let entity = config
+                .ident
+                .decl
+                .and_then(|id| match self.get_ent(id).kind() {
+                    AnyEntKind::Design(Design::Configuration(Some(entity))) => Some(entity.id()),
+                    _ => None,
+                });
+
+            if let (Some(entity), Some(arch)) =
+                (entity, block_label(&config.block_config.block_spec.item))
+            {
+                add_pinned_instances(
+                    "",
+                    &config.block_config,
+                    pinned.entry((entity, arch.clone())).or_default(),
+                );
+            }
+        }
+        pinned
+    }
+
+    /// Warn about instances that the default binding binds to an entity with several
+    /// architectures, since the architecture that is elaborated then depends on the order
+    /// of analysis. Instances within the simulation libraries are not checked.
+    pub(super) fn check_default_architectures(&self, diagnostics: &mut dyn DiagnosticHandler) {
+        if !self.is_lint_enabled(ErrorCode::AmbiguousDefaultBinding) {
+            return;
+        }
+
+        let architectures = self.architectures_by_entity();
+        let pinned = self.pinned_instances();
+
+        for locked_unit in self.locked_units() {
+            if self
+                .simulation_libraries
+                .contains(locked_unit.unit_id().library_name())
+            {
+                continue;
+            }
+            let Some(unit) = locked_unit.unit.get() else {
+                continue;
+            };
+            let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref arch)) = *unit else {
+                continue;
+            };
+            let pins = arch
+                .entity_name
+                .reference
+                .and_then(|entity| pinned.get(&(entity, arch.ident.item.clone())));
+
+            visit_instances("", &arch.statements, &mut |path, instance| {
+                let (name_pos, entity) = match instance.unit {
+                    InstantiatedUnit::Entity(ref name, None) => {
+                        (&name.pos, name.item.get_suffix_reference())
+                    }
+                    InstantiatedUnit::Component(ref name) => {
+                        if pins.is_some_and(|pins| {
+                            pins.iter().any(|pin| pin.applies_to(path, &name.item))
+                        }) {
+                            return;
+                        }
+                        (&name.pos, instance.default_binding)
+                    }
+                    InstantiatedUnit::Entity(_, Some(_)) | InstantiatedUnit::Configuration(_) => {
+                        return;
+                    }
+                };
+
+                let Some(entity) = entity else {
+                    return;
+                };
+                let Some(candidates) = architectures.get(&entity) else {
+                    return;
+                };
+                if candidates.len() < 2 {
+                    return;
+                }
+                let Some((selected, selected_pos)) =
+                    self.select_architecture(candidates, |(name, pos)| (name, pos))
+                else {
+                    return;
+                };
+
+                let mut diagnostic = Diagnostic::warning(
+                    name_pos,
+                    format!(
+                        "{} has {} architectures, the default binding selects '{}'",
+                        capitalize(&self.get_ent(entity).describe()),
+                        candidates.len(),
+                        selected
+                    ),
+                )
+                .with_code(ErrorCode::AmbiguousDefaultBinding);
+                diagnostic.add_related(selected_pos, format!("Selected architecture '{selected}'"));
+                for (name, pos) in candidates.iter() {
+                    if pos != selected_pos {
+                        diagnostic.add_related(pos, format!("Other architecture '{name}'"));
+                    }
+                }
+                diagnostics.push(diagnostic);
+            });
+        }
+    }
+}
+
+fn add_pinned_instances(
+    path: &str,
+    block_config: &BlockConfiguration,
+    pins: &mut Vec<PinnedInstances>,
+) {
+    for item in block_config.items.iter() {
+        match item {
+            ConfigurationItem::Block(block) => {
+                if let Some(label) = block_label(&block.block_spec.item) {
+                    let path = if path.is_empty() {
+                        label.to_string()
+                    } else {
+                        format!("{path}.{label}")
+                    };
+                    add_pinned_instances(&path, block, pins);
+                }
+            }
+            ConfigurationItem::Component(component) => {
+                let is_pinned = matches!(
+                    component
+                        .bind_ind
+                        .as_ref()
+                        .and_then(|bind_ind| bind_ind.entity_aspect.as_ref()),
+                    Some(
+                        EntityAspect::Entity(_, Some(_))
+                            | EntityAspect::Configuration(_)
+                            | EntityAspect::Open
+                    )
+                );
+
+                if is_pinned {
+                    pins.push(PinnedInstances {
+                        path: path.to_owned(),
+                        component: suffix_designator(&component.spec.component_name.item).clone(),
+                        labels: match component.spec.instantiation_list {
+                            InstantiationList::Labels(ref labels) => {
+                                Some(labels.iter().map(|label| label.item.clone()).collect())
+                            }
+                            InstantiationList::All | InstantiationList::Others => None,
+                        },
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// The architecture or statement label that a block specification denotes,
+/// ignoring the index of a generate statement
+fn block_label(name: &Name) -> Option<&Symbol> {
+    match name {
+        Name::Designator(designator) => match designator.item {
+            Designator::Identifier(ref sym) => Some(sym),
+            _ => None,
+        },
+        Name::CallOrIndexed(call) => block_label(&call.name.item),
+        Name::Slice(prefix, _) => block_label(&prefix.item),
+        _ => None,
+    }
+}
+
+fn suffix_designator(name: &SelectedName) -> &Designator {
+    match name {
+        SelectedName::Designator(designator) => &designator.item,
+        SelectedName::Selected(_, suffix) => &suffix.item.item,
+    }
+}
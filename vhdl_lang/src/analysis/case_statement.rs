@@ -0,0 +1,255 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Checks of the choices of case statements
+//! LRM 10.9 Case statement
+
+use super::analyze::*;
+use super::literals::{expand_bit_string, string_literal_length};
+use super::named_entity::*;
+use super::static_expression::non_static_reference;
+use crate::ast::*;
+use crate::data::*;
+
+impl<'a> AnalyzeContext<'a> {
+    /// Check the selector and choices of a case statement or case generate statement
+    /// The selector of a matching case statement must be of type BIT or STD_ULOGIC or a
+    /// one-dimensional array of them. For ordinary case statements a '-' choice is reported
+    /// since it only matches the '-' value rather than acting as a don't care.
+    pub fn check_case_choices<T>(
+        &self,
+        is_matching: bool,
+        selector: &WithPos<Expression>,
+        ctyp: Option<TypeEnt<'a>>,
+        alternatives: &[Alternative<T>],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let choices = || {
+            alternatives
+                .iter()
+                .flat_map(|alternative| alternative.choices.iter())
+                .filter_map(|choice| {
+                    if let Choice::Expression(ref expr) = choice {
+                        Some(expr)
+                    } else {
+                        None
+                    }
+                })
+        };
+
+        let Some(ctyp) = ctyp else {
+            return;
+        };
+
+        let (is_array, has_dont_care) = self.case_selector_kind(ctyp);
+
+        if is_matching {
+            if has_dont_care {
+                let selector_length = if is_array {
+                    self.selector_length(selector)
+                } else {
+                    Some(1)
+                };
+                check_matching_choices(
+                    self.arena,
+                    is_array,
+                    selector_length,
+                    choices(),
+                    diagnostics,
+                );
+            } else {
+                diagnostics.error(
+                    &selector.pos,
+                    format!(
+                        "Selector of a matching case statement must be of type BIT or STD_ULOGIC or a one-dimensional array of them, got {}",
+                        ctyp.describe()
+                    ),
+                );
+            }
+        } else if has_dont_care {
+            for expr in choices() {
+                if contains_dont_care(&expr.item) {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            &expr.pos,
+                            "Choice '-' only matches the '-' value in an ordinary case statement, use case? to treat it as a don't care",
+                        )
+                        .with_code(ErrorCode::DontCareChoice),
+                    );
+                }
+            }
+        }
+    }
+
+    /// The number of elements of an array selector with a static index range
+    fn selector_length(&self, selector: &WithPos<Expression>) -> Option<usize> {
+        let Expression::Name(ref name) = selector.item else {
+            return None;
+        };
+        let ranges = self.name_ranges(name, None)?;
+        usize::try_from(ranges.first()?.length()?).ok()
+    }
+
+    /// Whether the selector is an array and whether its elements have don't care values
+    fn case_selector_kind(&self, ctyp: TypeEnt<'a>) -> (bool, bool) {
+        match ctyp.base().kind() {
+            Type::Array {
+                indexes, elem_type, ..
+            } => (
+                true,
+                indexes.len() == 1 && self.has_matching_operators(*elem_type),
+            ),
+            _ => (false, self.has_matching_operators(ctyp)),
+        }
+    }
+}
+
+/// The choices of a matching case statement must be locally static and of equal length.
+/// When the length of the selector is known the choices must have that length.
+/// Two choices must not match the same value of the selector.
+fn check_matching_choices<'e>(
+    arena: &Arena,
+    is_array: bool,
+    selector_length: Option<usize>,
+    choices: impl Iterator<Item = &'e WithPos<Expression>> + Clone,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    for expr in choices.clone() {
+        if let Some(ent) = non_static_reference(arena, &expr.item) {
+            diagnostics.error(
+                &expr.pos,
+                format!(
+                    "Choice must be locally static, {} is not static",
+                    ent.describe()
+                ),
+            );
+        }
+    }
+
+    if is_array {
+        let mut first_length: Option<(usize, &SrcPos)> = None;
+        for expr in choices.clone() {
+            let Some(length) = choice_length(&expr.item) else {
+                continue;
+            };
+            if let Some(selector_length) = selector_length {
+                if length != selector_length {
+                    diagnostics.error(
+                        &expr.pos,
+                        format!(
+                            "Choice of length {length} does not match the length {selector_length} of the selector"
+                        ),
+                    );
+                }
+                continue;
+            }
+            match first_length {
+                Some((first, first_pos)) if first != length => {
+                    diagnostics.push(
+                        Diagnostic::error(
+                            &expr.pos,
+                            format!(
+                                "Choice of length {length} does not match the length {first} of the other choices"
+                            ),
+                        )
+                        .related(first_pos, format!("Choice of length {first}")),
+                    );
+                }
+                Some(_) => {}
+                None => first_length = Some((length, &expr.pos)),
+            }
+        }
+    }
+
+    let mut previous: Vec<(Vec<u8>, &SrcPos)> = Vec::new();
+    for expr in choices {
+        let Some(value) = choice_value(&expr.item) else {
+            continue;
+        };
+        let overlap = previous.iter().find_map(|(prev_value, prev_pos)| {
+            Some((common_match(prev_value, &value)?, *prev_pos))
+        });
+        if let Some((common, prev_pos)) = overlap {
+            let common = String::from_utf8_lossy(&common);
+            let common = if is_array {
+                format!("\"{common}\"")
+            } else {
+                format!("'{common}'")
+            };
+            diagnostics.push(
+                Diagnostic::error(
+                    &expr.pos,
+                    format!(
+                        "Choice overlaps with a previous choice, both match the value {common}"
+                    ),
+                )
+                .related(prev_pos, "Previous choice"),
+            );
+        }
+        previous.push((value, &expr.pos));
+    }
+}
+
+/// The number of elements of a choice given by a string or bit string literal
+fn choice_length(expr: &Expression) -> Option<usize> {
+    match expr {
+        Expression::Literal(literal) => string_literal_length(literal),
+        Expression::Qualified(qexpr) => choice_length(&qexpr.expr.item),
+        _ => None,
+    }
+}
+
+/// The elements of a choice given by a character, string or bit string literal
+fn choice_value(expr: &Expression) -> Option<Vec<u8>> {
+    match expr {
+        Expression::Literal(Literal::Character(chr)) => Some(vec![*chr]),
+        Expression::Literal(Literal::String(value)) => Some(value.bytes.clone()),
+        Expression::Literal(Literal::BitString(bitstring)) => expand_bit_string(bitstring).ok(),
+        Expression::Qualified(qexpr) => choice_value(&qexpr.expr.item),
+        _ => None,
+    }
+}
+
+/// A value that both choices match using the predefined matching equality, if any.
+/// Choices containing metavalues such as 'X' never match.
+fn common_match(left: &[u8], right: &[u8]) -> Option<Vec<u8>> {
+    if left.len() != right.len() {
+        return None;
+    }
+    left.iter()
+        .zip(right.iter())
+        .map(
+            |(left, right)| match (matching_value(*left)?, matching_value(*right)?) {
+                (b'-', b'-') => Some(b'0'),
+                (b'-', value) | (value, b'-') => Some(value),
+                (left, right) if left == right => Some(left),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// The value of an element under matching equality where 'L' and 'H' are the weak '0' and '1'
+fn matching_value(chr: u8) -> Option<u8> {
+    match chr {
+        b'0' | b'L' => Some(b'0'),
+        b'1' | b'H' => Some(b'1'),
+        b'-' => Some(b'-'),
+        _ => None,
+    }
+}
+
+fn contains_dont_care(expr: &Expression) -> bool {
+    match expr {
+        // A single '-' choice deliberately matches the '-' value such as in is_X of std_logic_1164
+        Expression::Literal(Literal::String(value)) => value.bytes.contains(&b'-'),
+        Expression::Literal(Literal::BitString(bitstring)) => expand_bit_string(bitstring)
+            .map(|value| value.contains(&b'-'))
+            .unwrap_or(false),
+        Expression::Qualified(qexpr) => contains_dont_care(&qexpr.expr.item),
+        _ => false,
+    }
+}
@@ -0,0 +1,284 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Signals that cross between clock domains within an architecture.
+//! A register belongs to the domain of the clock of the process that assigns it and a signal
+//! assigned by combinational logic belongs to the domains of the signals it depends on.
+//! A register that reads a signal of another domain is a crossing, unless the signal is a register
+//! that is copied by two consecutive registers of the destination domain.
+
+use super::combinational_loop::{
+    concurrent_assignment_sources, process_assignment_sources, SignalSources,
+};
+use super::connectivity::{csv_table, html_table};
+use super::named_entity::*;
+use super::process::{classify_process, ProcessKind};
+use super::root::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// A register that reads a signal of another clock domain without a synchronizer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClockDomainCrossing {
+    /// The architecture as library.entity(architecture)
+    pub architecture: String,
+    /// The signal of the source domain
+    pub signal: String,
+    pub source_clock: String,
+    /// The register of the destination domain that reads the signal
+    pub register: String,
+    pub destination_clock: String,
+    /// The position where the register reads the signal
+    pub pos: SrcPos,
+}
+
+/// The unsynchronized clock domain crossings of all architectures of the design
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClockDomainCrossingReport {
+    pub crossings: Vec<ClockDomainCrossing>,
+}
+
+const COLUMNS: [&str; 5] = [
+    "architecture",
+    "signal",
+    "source clock",
+    "register",
+    "destination clock",
+];
+
+impl ClockDomainCrossing {
+    fn columns(&self) -> [String; 5] {
+        [
+            self.architecture.clone(),
+            self.signal.clone(),
+            self.source_clock.clone(),
+            self.register.clone(),
+            self.destination_clock.clone(),
+        ]
+    }
+}
+
+impl ClockDomainCrossingReport {
+    /// A table with a header row and one row per crossing
+    pub fn to_csv(&self) -> String {
+        csv_table(
+            &COLUMNS,
+            self.crossings.iter().map(ClockDomainCrossing::columns),
+        )
+    }
+
+    /// A standalone page with a table of the crossings
+    pub fn to_html(&self) -> String {
+        html_table(
+            "Clock domain crossings",
+            &COLUMNS,
+            self.crossings
+                .iter()
+                .map(|crossing| ("crossing", crossing.columns())),
+        )
+    }
+}
+
+/// The signal assignments of the registers of a clock
+struct ClockedAssignments {
+    clock: EntityId,
+    reset: Option<EntityId>,
+    assignments: Vec<SignalSources>,
+}
+
+#[derive(Default)]
+struct ArchitectureLogic {
+    clocked: Vec<ClockedAssignments>,
+    combinational: Vec<SignalSources>,
+}
+
+impl DesignRoot {
+    /// The signals that are read by a register of another clock domain than their own without
+    /// a two register synchronizer, within all architectures of the design.
+    /// Processes within generate statements are included regardless of the generate conditions.
+    pub fn clock_domain_crossings(&self) -> ClockDomainCrossingReport {
+        let is_signal = |id| {
+            ObjectEnt::from_any(self.get_ent(id))
+                .is_some_and(|object| object.class() == ObjectClass::Signal)
+        };
+
+        let mut crossings = Vec::new();
+        for (unit_id, mut arch) in self.analyzed_architectures() {
+            let mut logic = ArchitectureLogic::default();
+            collect_logic(&mut arch.statements, &is_signal, &mut logic);
+            let architecture = format!(
+                "{}.{}({})",
+                unit_id.library_name(),
+                arch.entity_name.item,
+                arch.ident.item
+            );
+            self.add_crossings(&architecture, &logic, &mut crossings);
+        }
+
+        crossings.sort_by(|x, y| x.architecture.cmp(&y.architecture));
+        ClockDomainCrossingReport { crossings }
+    }
+
+    fn add_crossings(
+        &self,
+        architecture: &str,
+        logic: &ArchitectureLogic,
+        crossings: &mut Vec<ClockDomainCrossing>,
+    ) {
+        let domains = clock_domains(logic);
+        let registers: FnvHashMap<EntityId, EntityId> = logic
+            .clocked
+            .iter()
+            .rev()
+            .flat_map(|clocked| {
+                clocked
+                    .assignments
+                    .iter()
+                    .map(|assignment| (assignment.target, clocked.clock))
+            })
+            .collect();
+        let name = |id| self.get_ent(id).designator().to_string();
+
+        let mut reported = FnvHashSet::default();
+        for clocked in logic.clocked.iter() {
+            for assignment in clocked.assignments.iter() {
+                for (source, pos) in assignment.sources.iter() {
+                    if *source == clocked.clock || Some(*source) == clocked.reset {
+                        continue;
+                    }
+                    for source_clock in domains.get(source).into_iter().flatten() {
+                        if *source_clock == clocked.clock {
+                            continue;
+                        }
+                        let synchronized = assignment.copied == Some(*source)
+                            && registers.get(source) == Some(source_clock)
+                            && is_copied_by_register(logic, clocked.clock, assignment.target);
+                        if synchronized
+                            || !reported.insert((*source, *source_clock, assignment.target))
+                        {
+                            continue;
+                        }
+                        crossings.push(ClockDomainCrossing {
+                            architecture: architecture.to_owned(),
+                            signal: name(*source),
+                            source_clock: name(*source_clock),
+                            register: name(assignment.target),
+                            destination_clock: name(clocked.clock),
+                            pos: pos.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn collect_logic(
+    statements: &mut [LabeledConcurrentStatement],
+    is_signal: &dyn Fn(EntityId) -> bool,
+    logic: &mut ArchitectureLogic,
+) {
+    for statement in statements.iter_mut() {
+        match statement.statement {
+            ConcurrentStatement::Assignment(ref mut assign) => {
+                logic
+                    .combinational
+                    .extend(concurrent_assignment_sources(assign, is_signal));
+            }
+            ConcurrentStatement::Process(ref mut process) => match classify_process(process) {
+                ProcessKind::Combinational => {
+                    process_assignment_sources(
+                        &mut process.statements,
+                        is_signal,
+                        &mut logic.combinational,
+                    );
+                }
+                ProcessKind::Clocked(clocked) => {
+                    let Some(clock) = clocked.clock else {
+                        continue;
+                    };
+                    let mut assignments = Vec::new();
+                    process_assignment_sources(
+                        &mut process.statements,
+                        is_signal,
+                        &mut assignments,
+                    );
+                    logic.clocked.push(ClockedAssignments {
+                        clock,
+                        reset: clocked.reset.and_then(|reset| reset.signal),
+                        assignments,
+                    });
+                }
+                ProcessKind::Other => {}
+            },
+            ConcurrentStatement::Block(ref mut block) => {
+                collect_logic(&mut block.statements, is_signal, logic);
+            }
+            ConcurrentStatement::ForGenerate(ref mut gen) => {
+                collect_logic(&mut gen.body.statements, is_signal, logic);
+            }
+            ConcurrentStatement::IfGenerate(ref mut gen) => {
+                for conditional in gen.conditionals.iter_mut() {
+                    collect_logic(&mut conditional.item.statements, is_signal, logic);
+                }
+                if let Some(ref mut else_item) = gen.else_item {
+                    collect_logic(&mut else_item.statements, is_signal, logic);
+                }
+            }
+            ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                for alternative in gen.alternatives.iter_mut() {
+                    collect_logic(&mut alternative.item.statements, is_signal, logic);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The clocks of the registers that each signal depends on without passing through another
+/// register, signals such as input ports that do not depend on any register have no domain
+fn clock_domains(logic: &ArchitectureLogic) -> FnvHashMap<EntityId, FnvHashSet<EntityId>> {
+    let mut domains: FnvHashMap<EntityId, FnvHashSet<EntityId>> = FnvHashMap::default();
+    for clocked in logic.clocked.iter() {
+        for assignment in clocked.assignments.iter() {
+            domains
+                .entry(assignment.target)
+                .or_default()
+                .insert(clocked.clock);
+        }
+    }
+
+    // Propagate through the combinational logic until no domain changes
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for assignment in logic.combinational.iter() {
+            let clocks: Vec<EntityId> = assignment
+                .sources
+                .iter()
+                .filter_map(|(source, _)| domains.get(source))
+                .flatten()
+                .cloned()
+                .collect();
+            let domain = domains.entry(assignment.target).or_default();
+            for clock in clocks {
+                changed |= domain.insert(clock);
+            }
+        }
+    }
+    domains
+}
+
+/// The signal is copied without any logic by a register of the clock
+fn is_copied_by_register(logic: &ArchitectureLogic, clock: EntityId, signal: EntityId) -> bool {
+    logic
+        .clocked
+        .iter()
+        .filter(|clocked| clocked.clock == clock)
+        .flat_map(|clocked| clocked.assignments.iter())
+        .any(|assignment| assignment.copied == Some(signal))
+}
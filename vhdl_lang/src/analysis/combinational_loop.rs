@@ -0,0 +1,315 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Detection of combinational loops within an architecture.
+//! The target of a concurrent signal assignment or of an assignment within a combinational
+//! process depends on the signals read by the assignment and by the conditions it is nested in.
+//! Clocked processes break such dependencies, and so do variables which are not followed.
+
+use super::analyze::*;
+use super::named_entity::*;
+use super::process::{classify_process, signal_reads, ProcessKind};
+use crate::ast::*;
+use crate::data::*;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
+
+/// A signal assignment together with the signals that the assigned value depends on
+pub(super) struct SignalSources {
+    pub target: EntityId,
+    /// The position of the target of the assignment
+    pub pos: SrcPos,
+    /// The signals read by the assignment and by the conditions it is nested in
+    pub sources: Vec<(EntityId, SrcPos)>,
+    /// The signal whose value is assigned without any logic, such as `a` in `b <= a;`
+    pub copied: Option<EntityId>,
+    /// The assignment has an after clause
+    pub delayed: bool,
+}
+
+/// The target of an assignment depends on a signal read by it
+struct Dependency<'s> {
+    source: EntityId,
+    target: EntityId,
+    /// The position of the target of the assignment
+    pos: &'s SrcPos,
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// Warn about signals that depend on themselves through concurrent signal assignments
+    /// and combinational processes. Statements within generate statements are not considered
+    /// since whether they are elaborated depends on the generate conditions.
+    pub fn check_combinational_loops(
+        &self,
+        statements: &mut [LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if !self.root.is_lint_enabled(ErrorCode::CombinationalLoop) {
+            return;
+        }
+
+        let mut assignments = Vec::new();
+        combinational_assignments(statements, &|id| self.is_signal(id), &mut assignments);
+
+        // Assignments with an after clause are delayed and do not form combinational logic
+        let dependencies: Vec<Dependency> = assignments
+            .iter()
+            .filter(|assignment| !assignment.delayed)
+            .flat_map(|assignment| {
+                assignment.sources.iter().map(|(source, _)| Dependency {
+                    source: *source,
+                    target: assignment.target,
+                    pos: &assignment.pos,
+                })
+            })
+            .collect();
+
+        let mut graph: FnvHashMap<EntityId, Vec<&Dependency>> = FnvHashMap::default();
+        for dependency in dependencies.iter() {
+            graph.entry(dependency.source).or_default().push(dependency);
+        }
+
+        // Each loop is reported once at the assignment that occurs first
+        let mut reported = FnvHashSet::default();
+        for dependency in dependencies.iter() {
+            if reported.contains(&dependency.target) {
+                continue;
+            }
+            let Some(cycle) = find_cycle(&graph, dependency) else {
+                continue;
+            };
+            reported.extend(cycle.iter().map(|dependency| dependency.target));
+
+            let mut diagnostic = Diagnostic::warning(
+                dependency.pos,
+                format!(
+                    "Signal '{}' depends on itself through a combinational loop",
+                    self.arena.get(dependency.target).designator()
+                ),
+            )
+            .with_code(ErrorCode::CombinationalLoop);
+            for dependency in cycle.iter().skip(1) {
+                diagnostic.add_related(
+                    dependency.pos,
+                    format!(
+                        "'{}' depends on '{}'",
+                        self.arena.get(dependency.target).designator(),
+                        self.arena.get(dependency.source).designator()
+                    ),
+                );
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+/// The signal assignments of the concurrent statements and combinational processes
+fn combinational_assignments(
+    statements: &mut [LabeledConcurrentStatement],
+    is_signal: &dyn Fn(EntityId) -> bool,
+    assignments: &mut Vec<SignalSources>,
+) {
+    for statement in statements.iter_mut() {
+        match statement.statement {
+            ConcurrentStatement::Assignment(ref mut assign) => {
+                assignments.extend(concurrent_assignment_sources(assign, is_signal));
+            }
+            ConcurrentStatement::Process(ref mut process)
+                if classify_process(process) == ProcessKind::Combinational =>
+            {
+                process_assignment_sources(&mut process.statements, is_signal, assignments);
+            }
+            ConcurrentStatement::Block(ref mut block) => {
+                combinational_assignments(&mut block.statements, is_signal, assignments);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(super) fn concurrent_assignment_sources(
+    assign: &mut ConcurrentSignalAssignment,
+    is_signal: &dyn Fn(EntityId) -> bool,
+) -> Option<SignalSources> {
+    assignment_sources(&assign.target, &mut assign.rhs, is_signal, &[])
+}
+
+/// The signal assignments within the statements of a process
+pub(super) fn process_assignment_sources(
+    statements: &mut [LabeledSequentialStatement],
+    is_signal: &dyn Fn(EntityId) -> bool,
+    assignments: &mut Vec<SignalSources>,
+) {
+    sequential_sources(statements, is_signal, &mut Vec::new(), assignments);
+}
+
+/// The conditions are the signals read by the conditions of the enclosing if, case
+/// and loop statements
+fn sequential_sources(
+    statements: &mut [LabeledSequentialStatement],
+    is_signal: &dyn Fn(EntityId) -> bool,
+    conditions: &mut Vec<(EntityId, SrcPos)>,
+    assignments: &mut Vec<SignalSources>,
+) {
+    for statement in statements.iter_mut() {
+        let outer = conditions.len();
+        match statement.statement {
+            SequentialStatement::SignalAssignment(ref mut assign) => {
+                assignments.extend(assignment_sources(
+                    &assign.target,
+                    &mut assign.rhs,
+                    is_signal,
+                    conditions,
+                ));
+            }
+            SequentialStatement::If(ref mut ifstmt) => {
+                // A branch also depends on the conditions of the branches before it
+                for conditional in ifstmt.conditionals.iter_mut() {
+                    conditions.extend(signal_reads(&mut conditional.condition, is_signal));
+                    sequential_sources(&mut conditional.item, is_signal, conditions, assignments);
+                }
+                if let Some(ref mut else_item) = ifstmt.else_item {
+                    sequential_sources(else_item, is_signal, conditions, assignments);
+                }
+            }
+            SequentialStatement::Case(ref mut case_stmt) => {
+                conditions.extend(signal_reads(&mut case_stmt.expression, is_signal));
+                for alternative in case_stmt.alternatives.iter_mut() {
+                    sequential_sources(&mut alternative.item, is_signal, conditions, assignments);
+                }
+            }
+            SequentialStatement::Loop(ref mut loop_stmt) => {
+                if let Some(IterationScheme::While(ref mut condition)) = loop_stmt.iteration_scheme
+                {
+                    conditions.extend(signal_reads(condition, is_signal));
+                }
+                sequential_sources(
+                    &mut loop_stmt.statements,
+                    is_signal,
+                    conditions,
+                    assignments,
+                );
+            }
+            _ => {}
+        }
+        conditions.truncate(outer);
+    }
+}
+
+fn assignment_sources(
+    target: &WithPos<Target>,
+    rhs: &mut AssignmentRightHand<Waveform>,
+    is_signal: &dyn Fn(EntityId) -> bool,
+    conditions: &[(EntityId, SrcPos)],
+) -> Option<SignalSources> {
+    let Target::Name(ref name) = target.item else {
+        return None;
+    };
+    let signal = base_signal(name, is_signal)?;
+
+    let sources = signal_reads(rhs, is_signal)
+        .into_iter()
+        .chain(conditions.iter().cloned())
+        // An element or slice assigned from another part of the same signal
+        .filter(|(source, _)| *source != signal || matches!(name, Name::Designator(_)))
+        .collect();
+
+    Some(SignalSources {
+        target: signal,
+        pos: target.pos.clone(),
+        sources,
+        copied: copied_signal(rhs).filter(|id| is_signal(*id)),
+        delayed: is_delayed(rhs),
+    })
+}
+
+/// The signal of which the name denotes the whole or a part
+pub(super) fn base_signal(name: &Name, is_signal: &dyn Fn(EntityId) -> bool) -> Option<EntityId> {
+    match name {
+        Name::Designator(designator) => designator.reference.filter(|id| is_signal(*id)),
+        Name::Selected(prefix, suffix) => suffix
+            .item
+            .reference
+            .filter(|id| is_signal(*id))
+            .or_else(|| base_signal(&prefix.item, is_signal)),
+        Name::Slice(prefix, ..) => base_signal(&prefix.item, is_signal),
+        Name::CallOrIndexed(fcall) => base_signal(&fcall.name.item, is_signal),
+        Name::SelectedAll(..) | Name::Attribute(..) | Name::External(..) => None,
+    }
+}
+
+/// The signal that is the whole value of a simple assignment
+fn copied_signal(rhs: &AssignmentRightHand<Waveform>) -> Option<EntityId> {
+    let AssignmentRightHand::Simple(Waveform::Elements(ref elements)) = rhs else {
+        return None;
+    };
+    let [element] = elements.as_slice() else {
+        return None;
+    };
+    match element.value.item {
+        Expression::Name(ref name) => match name.as_ref() {
+            Name::Designator(designator) => designator.reference,
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The shortest cycle starting with the dependency, which is found by a breadth first search
+/// from the target of the dependency back to its source
+fn find_cycle<'d, 's>(
+    graph: &FnvHashMap<EntityId, Vec<&'d Dependency<'s>>>,
+    first: &'d Dependency<'s>,
+) -> Option<Vec<&'d Dependency<'s>>> {
+    let mut reached_by: FnvHashMap<EntityId, Option<&'d Dependency>> = FnvHashMap::default();
+    reached_by.insert(first.target, None);
+    let mut queue = VecDeque::from([first.target]);
+
+    while let Some(signal) = queue.pop_front() {
+        if signal == first.source {
+            let mut cycle = Vec::new();
+            let mut current = signal;
+            while let Some(Some(dependency)) = reached_by.get(&current) {
+                cycle.push(*dependency);
+                current = dependency.source;
+            }
+            cycle.push(first);
+            cycle.reverse();
+            return Some(cycle);
+        }
+
+        for dependency in graph.get(&signal).into_iter().flatten() {
+            if let Entry::Vacant(entry) = reached_by.entry(dependency.target) {
+                entry.insert(Some(dependency));
+                queue.push_back(dependency.target);
+            }
+        }
+    }
+    None
+}
+
+fn is_delayed(rhs: &AssignmentRightHand<Waveform>) -> bool {
+    let has_after = |waveform: &Waveform| match waveform {
+        Waveform::Elements(elements) => elements.iter().any(|element| element.after.is_some()),
+        Waveform::Unaffected => false,
+    };
+
+    match rhs {
+        AssignmentRightHand::Simple(waveform) => has_after(waveform),
+        AssignmentRightHand::Conditional(conditionals) => {
+            conditionals
+                .conditionals
+                .iter()
+                .any(|conditional| has_after(&conditional.item))
+                || conditionals.else_item.as_ref().is_some_and(has_after)
+        }
+        AssignmentRightHand::Selected(selection) => selection
+            .alternatives
+            .iter()
+            .any(|alternative| has_after(&alternative.item)),
+    }
+}
@@ -9,6 +9,7 @@
 
 use super::named_entity::*;
 use super::sequential::SequentialRoot;
+use super::wait_graph::CallerKind;
 use super::*;
 use crate::ast::*;
 use crate::data::*;
@@ -23,9 +24,12 @@ impl<'a> AnalyzeContext<'a> {
         statements: &mut [LabeledConcurrentStatement],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
-        for statement in statements.iter_mut() {
+        let mut probe = self.probe_list(statements);
+        for (idx, statement) in statements.iter_mut().enumerate() {
+            self.probe_item(scope, &mut probe, idx);
             self.analyze_concurrent_statement(scope, statement, diagnostics)?;
         }
+        self.probe_end(scope, probe);
 
         Ok(())
     }
@@ -80,10 +84,23 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_declarative_part(&nested, decl, diagnostics)?;
                 self.analyze_sequential_part(
                     &nested,
-                    &SequentialRoot::Process,
+                    &SequentialRoot::Process {
+                        has_sensitivity_list: sensitivity_list.is_some(),
+                    },
                     statements,
                     diagnostics,
                 )?;
+                if sensitivity_list.is_some() {
+                    self.record_caller(CallerKind::SensitiveProcess, Vec::new(), statements);
+                }
+                self.check_latch_inference(process, diagnostics);
+                self.check_sensitivity_completeness(process, diagnostics);
+                self.check_reset_convention(process, diagnostics);
+                self.check_write_only_variables(
+                    &mut process.decl,
+                    &mut process.statements,
+                    diagnostics,
+                );
             }
             ConcurrentStatement::ForGenerate(ref mut gen) => {
                 let ForGenerateStatement {
@@ -92,11 +109,11 @@ impl<'a> AnalyzeContext<'a> {
                     body,
                 } = gen;
                 let typ = as_fatal(self.drange_type(scope, discrete_range, diagnostics))?;
+                self.check_null_drange(discrete_range, Some("nothing is generated"), diagnostics);
                 let nested = scope.nested();
-                nested.add(
-                    index_name.define(self.arena, AnyEntKind::LoopParameter(typ)),
-                    diagnostics,
-                );
+                let index = index_name.define(self.arena, AnyEntKind::LoopParameter(typ));
+                self.set_loop_parameter_range(index, typ, discrete_range);
+                nested.add(index, diagnostics);
                 self.analyze_generate_body(&nested, body, diagnostics)?;
             }
             ConcurrentStatement::IfGenerate(ref mut gen) => {
@@ -116,10 +133,18 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             ConcurrentStatement::CaseGenerate(ref mut gen) => {
-                for alternative in gen.alternatives.iter_mut() {
+                let Selection {
+                    expression,
+                    alternatives,
+                } = gen;
+                let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+                for alternative in alternatives.iter_mut() {
+                    let Alternative { choices, item } = alternative;
+                    self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
                     let nested = scope.nested();
-                    self.analyze_generate_body(&nested, &mut alternative.item, diagnostics)?;
+                    self.analyze_generate_body(&nested, item, diagnostics)?;
                 }
+                self.check_case_choices(false, expression, ctyp, alternatives, diagnostics);
             }
             ConcurrentStatement::Instance(ref mut instance) => {
                 self.analyze_instance(scope, instance, diagnostics)?;
@@ -157,10 +182,51 @@ impl<'a> AnalyzeContext<'a> {
                     self.expr_with_ttyp(scope, self.severity_level(), expr, diagnostics)?;
                 }
             }
+            ConcurrentStatement::PslDirective(ref mut directive) => {
+                let PslDirective {
+                    kind: _,
+                    property,
+                    report,
+                    severity,
+                } = directive;
+                self.analyze_psl_property(scope, property, diagnostics)?;
+                if let Some(expr) = report {
+                    self.expr_with_ttyp(scope, self.string(), expr, diagnostics)?;
+                }
+                if let Some(expr) = severity {
+                    self.expr_with_ttyp(scope, self.severity_level(), expr, diagnostics)?;
+                }
+            }
         };
         Ok(())
     }
 
+    /// LRM 11.1 The names within a PSL property refer to the declarations of the
+    /// enclosing design unit
+    fn analyze_psl_property(
+        &self,
+        scope: &Scope<'a>,
+        property: &mut PslProperty,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let PslProperty {
+            booleans,
+            values,
+            counts,
+            clocks,
+        } = property;
+        for expr in booleans.iter_mut().chain(clocks.iter_mut()) {
+            self.boolean_expr(scope, expr, diagnostics)?;
+        }
+        for expr in values.iter_mut() {
+            self.expr_unknown_ttyp(scope, expr, diagnostics)?;
+        }
+        for expr in counts.iter_mut() {
+            self.integer_expr(scope, expr, diagnostics)?;
+        }
+        Ok(())
+    }
+
     fn analyze_generate_body(
         &self,
         scope: &Scope<'a>,
@@ -219,6 +285,14 @@ impl<'a> AnalyzeContext<'a> {
                                     &mut instance.port_map,
                                     diagnostics,
                                 )?;
+                                self.check_port_map_with_generics(
+                                    &entity_name.pos,
+                                    &generic_region,
+                                    &instance.generic_map,
+                                    &port_region,
+                                    &instance.port_map,
+                                    diagnostics,
+                                );
                                 Ok(())
                             } else {
                                 Err(AnalysisError::NotFatal(
@@ -257,6 +331,14 @@ impl<'a> AnalyzeContext<'a> {
                                     &mut instance.port_map,
                                     diagnostics,
                                 )?;
+                                self.check_port_map_with_generics(
+                                    &component_name.pos,
+                                    &generic_region,
+                                    &instance.generic_map,
+                                    &port_region,
+                                    &instance.port_map,
+                                    diagnostics,
+                                );
                                 Ok(())
                             } else {
                                 Err(AnalysisError::NotFatal(
@@ -270,11 +352,12 @@ impl<'a> AnalyzeContext<'a> {
             }
             InstantiatedUnit::Configuration(ref mut config_name) => {
                 fn is_configuration(kind: &AnyEntKind) -> bool {
-                    matches!(kind, AnyEntKind::Design(Design::Configuration))
+                    matches!(kind, AnyEntKind::Design(Design::Configuration(..)))
                 }
 
-                if let Err(err) =
-                    self.resolve_selected_name(scope, config_name)
+                let config =
+                    match self
+                        .resolve_selected_name(scope, config_name)
                         .and_then(|entities| {
                             self.resolve_non_overloaded_with_kind(
                                 entities,
@@ -282,16 +365,208 @@ impl<'a> AnalyzeContext<'a> {
                                 &is_configuration,
                                 "configuration",
                             )
-                        })
-                {
-                    err.add_to(diagnostics)?;
+                        }) {
+                        Ok(config) => Some(config),
+                        Err(err) => {
+                            err.add_to(diagnostics)?;
+                            None
+                        }
+                    };
+
+                // The formals are those of the entity that is configured
+                let entity_region = config.and_then(|config| match config.kind() {
+                    AnyEntKind::Design(Design::Configuration(Some(entity))) => {
+                        match entity.kind() {
+                            Design::Entity(_, region) => Some(region),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                });
+
+                if let Some(ent_region) = entity_region {
+                    let (generic_region, port_region) = ent_region.to_entity_formal();
+                    self.analyze_assoc_elems_with_formal_region(
+                        &config_name.pos,
+                        &generic_region,
+                        scope,
+                        &mut instance.generic_map,
+                        diagnostics,
+                    )?;
+                    self.analyze_assoc_elems_with_formal_region(
+                        &config_name.pos,
+                        &port_region,
+                        scope,
+                        &mut instance.port_map,
+                        diagnostics,
+                    )?;
+                    self.check_port_map_with_generics(
+                        &config_name.pos,
+                        &generic_region,
+                        &instance.generic_map,
+                        &port_region,
+                        &instance.port_map,
+                        diagnostics,
+                    );
+                } else {
+                    self.analyze_assoc_elems(scope, &mut instance.generic_map, diagnostics)?;
+                    self.analyze_assoc_elems(scope, &mut instance.port_map, diagnostics)?;
+                }
+            }
+        };
+
+        Ok(())
+    }
+
+    /// LRM 7.3.3 Default binding indication
+    ///
+    /// Component instances that are not bound by a configuration specification are bound to the
+    /// entity with the same simple name within the working library.
+    /// Must be called after the declarations and statements have been analyzed.
+    pub fn analyze_default_bindings(
+        &self,
+        decls: &[Declaration],
+        statements: &mut [LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let specs: Vec<&ComponentSpecification> = decls
+            .iter()
+            .filter_map(|decl| {
+                if let Declaration::Configuration(config) = decl {
+                    Some(&config.spec)
+                } else {
+                    None
                 }
+            })
+            .collect();
 
-                self.analyze_assoc_elems(scope, &mut instance.generic_map, diagnostics)?;
-                self.analyze_assoc_elems(scope, &mut instance.port_map, diagnostics)?;
+        for statement in statements.iter_mut() {
+            match statement.statement {
+                ConcurrentStatement::Instance(ref mut instance) => {
+                    let label = statement.label.as_ref().map(|label| label.tree.name());
+                    self.analyze_default_binding(&specs, label, instance, diagnostics)?;
+                }
+                ConcurrentStatement::Block(ref mut block) => {
+                    self.analyze_default_bindings(&block.decl, &mut block.statements, diagnostics)?;
+                }
+                ConcurrentStatement::ForGenerate(ref mut gen) => {
+                    self.analyze_generate_body_default_bindings(&mut gen.body, diagnostics)?;
+                }
+                ConcurrentStatement::IfGenerate(ref mut gen) => {
+                    for conditional in gen.conditionals.iter_mut() {
+                        self.analyze_generate_body_default_bindings(
+                            &mut conditional.item,
+                            diagnostics,
+                        )?;
+                    }
+                    if let Some(ref mut else_item) = gen.else_item {
+                        self.analyze_generate_body_default_bindings(else_item, diagnostics)?;
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                    for alternative in gen.alternatives.iter_mut() {
+                        self.analyze_generate_body_default_bindings(
+                            &mut alternative.item,
+                            diagnostics,
+                        )?;
+                    }
+                }
+                ConcurrentStatement::ProcedureCall(..)
+                | ConcurrentStatement::Process(..)
+                | ConcurrentStatement::Assert(..)
+                | ConcurrentStatement::PslDirective(..)
+                | ConcurrentStatement::Assignment(..) => {}
             }
+        }
+        Ok(())
+    }
+
+    fn analyze_generate_body_default_bindings(
+        &self,
+        body: &mut GenerateBody,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let GenerateBody {
+            alternative_label: _,
+            decl,
+            statements,
+        } = body;
+        let decl = decl.as_deref().unwrap_or_default();
+        self.analyze_default_bindings(decl, statements, diagnostics)
+    }
+
+    fn analyze_default_binding(
+        &self,
+        specs: &[&ComponentSpecification],
+        label: Option<&Symbol>,
+        instance: &mut InstantiationStatement,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let component_name = if let InstantiatedUnit::Component(ref component_name) = instance.unit
+        {
+            component_name
+        } else {
+            return Ok(());
+        };
+
+        let component = if let Some(id) = component_name.item.get_suffix_reference() {
+            self.arena.get(id)
+        } else {
+            return Ok(());
+        };
+
+        let component_region = if let AnyEntKind::Component(region) = component.kind() {
+            region
+        } else {
+            return Ok(());
         };
 
+        let is_explicitly_bound = specs.iter().any(|spec| {
+            spec.component_name.item.get_suffix_reference() == Some(component.id())
+                && match spec.instantiation_list {
+                    InstantiationList::All | InstantiationList::Others => true,
+                    InstantiationList::Labels(ref labels) => labels
+                        .iter()
+                        .any(|spec_label| Some(&spec_label.item) == label),
+                }
+        });
+
+        if is_explicitly_bound {
+            return Ok(());
+        }
+
+        let mut reference = None;
+        let entity = match self.lookup_in_library(
+            self.work_library_name(),
+            &component_name.pos,
+            component.designator(),
+            &mut reference,
+        ) {
+            Ok(design) => design,
+            // A component without a matching entity is left unbound
+            Err(AnalysisError::NotFatal(_)) => return Ok(()),
+            Err(AnalysisError::Fatal(err)) => return Err(err),
+        };
+
+        if let Design::Entity(_, entity_region) = entity.kind() {
+            instance.default_binding = reference;
+
+            let (local_generics, local_ports) = component_region.to_entity_formal();
+            let (generic_region, port_region) = entity_region.to_entity_formal();
+            self.check_default_binding(
+                &component_name.pos,
+                &local_generics,
+                &generic_region,
+                diagnostics,
+            );
+            self.check_default_binding(
+                &component_name.pos,
+                &local_ports,
+                &port_region,
+                diagnostics,
+            );
+        }
+
         Ok(())
     }
 
@@ -301,6 +576,8 @@ impl<'a> AnalyzeContext<'a> {
         names: &mut [WithPos<Name>],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
+        let mut signals: Vec<(&WithPos<Name>, Vec<SensitivityPathElement>)> = Vec::new();
+
         for name in names.iter_mut() {
             if let Some(object_name) = as_fatal(self.resolve_object_name(
                 scope,
@@ -310,12 +587,15 @@ impl<'a> AnalyzeContext<'a> {
                 diagnostics,
             ))? {
                 if object_name.base.class() != ObjectClass::Signal {
-                    diagnostics.error(
-                        &name.pos,
-                        format!(
-                            "{} is not a signal and cannot be in a sensitivity list",
-                            object_name.base.describe_class()
-                        ),
+                    diagnostics.push(
+                        Diagnostic::error(
+                            &name.pos,
+                            format!(
+                                "{} is not a signal and cannot be in a sensitivity list",
+                                object_name.base.describe_class()
+                            ),
+                        )
+                        .with_code(ErrorCode::NonSignalSensitivity),
                     )
                 } else if object_name.base.mode() == Some(Mode::Out) {
                     diagnostics.error(
@@ -325,9 +605,87 @@ impl<'a> AnalyzeContext<'a> {
                             object_name.base.describe_class()
                         ),
                     )
+                } else if let Some(path) = self.sensitivity_path(&name.item) {
+                    signals.push((&*name, path));
                 }
             }
         }
+
+        for (idx, (name, path)) in signals.iter().enumerate() {
+            if let Some((prev_name, _)) = signals[..idx].iter().find(|(_, prev)| prev == path) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        &name.pos,
+                        format!("Duplicate '{}' in sensitivity list", name.item),
+                    )
+                    .related(&prev_name.pos, "Previously listed here")
+                    .with_code(ErrorCode::DuplicateSensitivity),
+                );
+            } else if let Some((whole, _)) = signals
+                .iter()
+                .find(|(_, other)| other.len() < path.len() && path.starts_with(other))
+            {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        &name.pos,
+                        format!(
+                            "'{}' is redundant since '{}' is also in the sensitivity list",
+                            name.item, whole.item
+                        ),
+                    )
+                    .related(&whole.pos, "Also listed here")
+                    .with_code(ErrorCode::RedundantSensitivity),
+                );
+            }
+        }
         Ok(())
     }
+
+    /// The object a name in a sensitivity list refers to followed by the
+    /// selected elements, indexes and slices applied to it
+    fn sensitivity_path(&self, name: &Name) -> Option<Vec<SensitivityPathElement>> {
+        match name {
+            Name::Designator(designator) => {
+                Some(vec![SensitivityPathElement::Object(designator.reference?)])
+            }
+            Name::Selected(prefix, suffix) => {
+                let id = suffix.item.reference?;
+                if matches!(
+                    self.arena.get(id).kind(),
+                    AnyEntKind::ElementDeclaration(..)
+                ) {
+                    let mut path = self.sensitivity_path(&prefix.item)?;
+                    path.push(SensitivityPathElement::Element(id));
+                    Some(path)
+                } else {
+                    // An object selected from a package
+                    Some(vec![SensitivityPathElement::Object(id)])
+                }
+            }
+            Name::CallOrIndexed(fcall) => {
+                let mut path = self.sensitivity_path(&fcall.name.item)?;
+                let indexes: Vec<String> = fcall
+                    .parameters
+                    .iter()
+                    .map(|param| param.to_string())
+                    .collect();
+                path.push(SensitivityPathElement::Index(indexes.join(", ")));
+                Some(path)
+            }
+            Name::Slice(prefix, drange) => {
+                let mut path = self.sensitivity_path(&prefix.item)?;
+                path.push(SensitivityPathElement::Index(drange.to_string()));
+                Some(path)
+            }
+            Name::SelectedAll(..) | Name::Attribute(..) | Name::External(..) => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq)]
+enum SensitivityPathElement {
+    Object(EntityId),
+    Element(EntityId),
+    // The textual index or range since only equal expressions are compared
+    Index(String),
 }
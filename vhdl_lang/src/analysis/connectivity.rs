@@ -0,0 +1,704 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Connectivity of the ports of the instances of an architecture for integration reviews
+
+use super::formal_region::InterfaceEnt;
+use super::named_entity::*;
+use super::root::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+/// How a port of an instance is connected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connection {
+    /// A signal or port, or an element of a record signal
+    Signal,
+    /// A slice or an indexed element of a signal, a conversion function or type conversion
+    /// of a signal, or a port that is associated in parts or through a conversion
+    Adapted,
+    /// A literal or an expression of constants and generics
+    Constant,
+    /// Associated with open or not associated at all
+    Open,
+    /// Any other expression
+    Expression,
+}
+
+impl Connection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Connection::Signal => "signal",
+            Connection::Adapted => "adapted",
+            Connection::Constant => "constant",
+            Connection::Open => "open",
+            Connection::Expression => "expression",
+        }
+    }
+
+    /// The connection of a slice, element or conversion of an actual
+    fn adapted(self) -> Connection {
+        match self {
+            Connection::Signal => Connection::Adapted,
+            other => other,
+        }
+    }
+
+    /// The connection of an operator or function applied to several actuals
+    fn combined(self, other: Connection) -> Connection {
+        if self == Connection::Constant && other == Connection::Constant {
+            Connection::Constant
+        } else {
+            Connection::Expression
+        }
+    }
+}
+
+/// A port of an instance and what it is connected to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PortConnection {
+    /// The labels of the enclosing blocks and generate statements and of the instance
+    /// separated by dots
+    pub instance: String,
+    /// The instantiated entity, component or configuration
+    pub unit: String,
+    pub port: String,
+    /// The mode of the port, empty when the ports of the instantiated unit are not known
+    pub mode: String,
+    /// The actual as VHDL, empty when the port is not associated
+    pub actual: String,
+    pub connection: Connection,
+}
+
+/// The port connections of all instances within an architecture
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectivityReport {
+    /// The architecture as library.entity(architecture)
+    pub architecture: String,
+    pub connections: Vec<PortConnection>,
+}
+
+/// A port that is left open or tied to a constant at every instance of its entity
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnusedPort {
+    /// The entity as library.entity
+    pub entity: String,
+    pub port: String,
+    pub mode: String,
+    /// The number of instances of the entity
+    pub instances: usize,
+    /// The number of instances that leave the port open, the others tie it to a constant
+    pub open: usize,
+    pub decl_pos: Option<SrcPos>,
+}
+
+/// The ports of instantiated entities that could be removed from their interface
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnusedPortReport {
+    pub ports: Vec<UnusedPort>,
+}
+
+const COLUMNS: [&str; 6] = ["instance", "unit", "port", "mode", "actual", "connection"];
+const UNUSED_PORT_COLUMNS: [&str; 6] = ["entity", "port", "mode", "instances", "open", "constant"];
+
+impl PortConnection {
+    fn columns(&self) -> [&str; 6] {
+        [
+            &self.instance,
+            &self.unit,
+            &self.port,
+            &self.mode,
+            &self.actual,
+            self.connection.as_str(),
+        ]
+    }
+}
+
+impl ConnectivityReport {
+    /// A table with a header row and one row per port
+    pub fn to_csv(&self) -> String {
+        csv_table(
+            &COLUMNS,
+            self.connections
+                .iter()
+                .map(|connection| connection.columns().map(str::to_owned)),
+        )
+    }
+
+    /// A standalone page with a table where ports connected to constants, left open or
+    /// connected through an adaptation are highlighted
+    pub fn to_html(&self) -> String {
+        html_table(
+            &format!("Connectivity of {}", self.architecture),
+            &COLUMNS,
+            self.connections.iter().map(|connection| {
+                (
+                    connection.connection.as_str(),
+                    connection.columns().map(str::to_owned),
+                )
+            }),
+        )
+    }
+}
+
+impl UnusedPort {
+    fn columns(&self) -> [String; 6] {
+        [
+            self.entity.clone(),
+            self.port.clone(),
+            self.mode.clone(),
+            self.instances.to_string(),
+            self.open.to_string(),
+            (self.instances - self.open).to_string(),
+        ]
+    }
+}
+
+impl UnusedPortReport {
+    /// A table with a header row and one row per port
+    pub fn to_csv(&self) -> String {
+        csv_table(
+            &UNUSED_PORT_COLUMNS,
+            self.ports.iter().map(UnusedPort::columns),
+        )
+    }
+
+    /// A standalone page with a table where ports that are always left open are highlighted
+    pub fn to_html(&self) -> String {
+        html_table(
+            "Unused ports",
+            &UNUSED_PORT_COLUMNS,
+            self.ports.iter().map(|port| {
+                let class = if port.open == port.instances {
+                    "open"
+                } else {
+                    "constant"
+                };
+                (class, port.columns())
+            }),
+        )
+    }
+}
+
+pub(super) fn csv_table<const N: usize>(
+    columns: &[&str; N],
+    rows: impl Iterator<Item = [String; N]>,
+) -> String {
+    let mut csv = columns.join(",");
+    csv.push('\n');
+    for row in rows {
+        let row: Vec<_> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// A standalone page with a table where each row has a class for highlighting
+pub(super) fn html_table<'s, const N: usize>(
+    title: &str,
+    columns: &[&str; N],
+    rows: impl Iterator<Item = (&'s str, [String; N])>,
+) -> String {
+    let title = html_escape(title);
+    let mut html = format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+<style>
+table {{ border-collapse: collapse; font-family: monospace; }}
+th, td {{ border: 1px solid #999; padding: 2px 8px; text-align: left; }}
+tr.constant {{ background: #dde9ff; }}
+tr.open {{ background: #ffe0e0; }}
+tr.adapted {{ background: #fff3c4; }}
+tr.crossing {{ background: #ffe0e0; }}
+tr.reset {{ background: #e0f5e0; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<table>
+<tr>"
+    );
+    for column in columns {
+        html.push_str(&format!("<th>{column}</th>"));
+    }
+    html.push_str("</tr>\n");
+    for (class, row) in rows {
+        html.push_str(&format!("<tr class=\"{class}\">"));
+        for column in row.iter() {
+            html.push_str(&format!("<td>{}</td>", html_escape(column)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl DesignRoot {
+    /// The port connections of all instances within an architecture, including those
+    /// within blocks and generate statements
+    pub fn connectivity(
+        &self,
+        library_name: &str,
+        entity_name: &str,
+        architecture_name: &str,
+    ) -> Result<ConnectivityReport, String> {
+        let library_sym = self.symbol_utf8(library_name);
+        let entity_sym = self.symbol_utf8(entity_name);
+        let architecture_sym = self.symbol_utf8(architecture_name);
+        let unit_id = UnitId::secondary(
+            &library_sym,
+            SecondaryKind::Architecture,
+            &entity_sym,
+            &architecture_sym,
+        );
+
+        let unit = self
+            .get_unit(&unit_id)
+            .and_then(|locked_unit| locked_unit.unit.get());
+        let Some(AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref architecture))) =
+            unit.as_deref()
+        else {
+            return Err(format!(
+                "No such architecture '{architecture_sym}' of entity '{library_sym}.{entity_sym}'"
+            ));
+        };
+
+        let mut connections = Vec::new();
+        visit_instances("", &architecture.statements, &mut |path, instance| {
+            self.add_instance_connections(path, instance, &mut connections)
+        });
+        Ok(ConnectivityReport {
+            architecture: format!("{library_sym}.{entity_sym}({architecture_sym})"),
+            connections,
+        })
+    }
+
+    /// The ports of entities that are left open or tied to a constant at every instance
+    /// within all architectures of the design, entities without instances are not included
+    pub fn unused_ports(&self) -> UnusedPortReport {
+        let mut entities: FnvHashMap<EntityId, EntityPorts> = FnvHashMap::default();
+        for locked_unit in self.locked_units() {
+            let Some(unit) = locked_unit.unit.get() else {
+                continue;
+            };
+            if let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity)) = *unit {
+                if let Some(id) = entity.ident.decl {
+                    let ports = self
+                        .unit_ports(Some(id))
+                        .into_iter()
+                        .map(|port| PortUsage {
+                            port: port.designator().clone(),
+                            mode: port.mode().map(|mode| mode.to_string()).unwrap_or_default(),
+                            decl_pos: port.decl_pos().cloned(),
+                            open: 0,
+                            used: false,
+                        })
+                        .collect();
+                    entities.insert(
+                        id,
+                        EntityPorts {
+                            name: format!(
+                                "{}.{}",
+                                locked_unit.unit_id().library_name(),
+                                entity.ident.tree.item
+                            ),
+                            instances: 0,
+                            ports,
+                        },
+                    );
+                }
+            }
+        }
+
+        for locked_unit in self.locked_units() {
+            let Some(unit) = locked_unit.unit.get() else {
+                continue;
+            };
+            if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref arch)) = *unit {
+                visit_instances("", &arch.statements, &mut |_, instance| {
+                    self.add_port_usage(instance, &mut entities)
+                });
+            }
+        }
+
+        let mut entities: Vec<_> = entities
+            .into_values()
+            .filter(|entity| entity.instances > 0)
+            .collect();
+        entities.sort_by(|x, y| x.name.cmp(&y.name));
+
+        let mut ports = Vec::new();
+        for entity in entities {
+            for port in entity.ports.into_iter().filter(|port| !port.used) {
+                ports.push(UnusedPort {
+                    entity: entity.name.clone(),
+                    port: port.port.to_string(),
+                    mode: port.mode,
+                    instances: entity.instances,
+                    open: port.open,
+                    decl_pos: port.decl_pos,
+                });
+            }
+        }
+        UnusedPortReport { ports }
+    }
+
+    fn add_instance_connections(
+        &self,
+        path: &str,
+        instance: &InstantiationStatement,
+        connections: &mut Vec<PortConnection>,
+    ) {
+        let (unit, reference) = instantiated_unit(instance);
+        let ports = self.unit_ports(reference);
+        let (actuals, unmatched) = self.port_actuals(&ports, &instance.port_map);
+
+        for (port, actual, connection) in unmatched {
+            connections.push(PortConnection {
+                instance: path.to_owned(),
+                unit: unit.clone(),
+                port,
+                mode: String::new(),
+                actual,
+                connection,
+            });
+        }
+
+        for (port, actual) in ports.iter().zip(actuals) {
+            let (actual, connection) = actual.unwrap_or((String::new(), Connection::Open));
+            connections.push(PortConnection {
+                instance: path.to_owned(),
+                unit: unit.clone(),
+                port: port.designator().to_string(),
+                mode: port.mode().map(|mode| mode.to_string()).unwrap_or_default(),
+                actual,
+                connection,
+            });
+        }
+    }
+
+    /// Count how the ports of the entity of an instance are connected, the ports of a
+    /// component are matched by name to those of its default binding
+    fn add_port_usage(
+        &self,
+        instance: &InstantiationStatement,
+        entities: &mut FnvHashMap<EntityId, EntityPorts>,
+    ) {
+        let entity = match instance.unit {
+            InstantiatedUnit::Entity(ref name, _) => name.item.get_suffix_reference(),
+            InstantiatedUnit::Component(..) => instance.default_binding,
+            InstantiatedUnit::Configuration(..) => None,
+        };
+        let Some(entity) = entity.and_then(|id| entities.get_mut(&id)) else {
+            return;
+        };
+
+        let ports = self.unit_ports(instantiated_unit(instance).1);
+        let (actuals, _) = self.port_actuals(&ports, &instance.port_map);
+
+        entity.instances += 1;
+        for usage in entity.ports.iter_mut() {
+            let connection = ports
+                .iter()
+                .zip(actuals.iter())
+                .find(|(port, _)| port.designator() == &usage.port)
+                .and_then(|(_, actual)| actual.as_ref())
+                .map_or(Connection::Open, |(_, connection)| *connection);
+
+            match connection {
+                Connection::Open => usage.open += 1,
+                Connection::Constant => {}
+                Connection::Signal | Connection::Adapted | Connection::Expression => {
+                    usage.used = true
+                }
+            }
+        }
+    }
+
+    /// The ports of an entity or component
+    fn unit_ports(&self, reference: Option<EntityId>) -> Vec<InterfaceEnt<'_>> {
+        match reference.map(|id| self.get_ent(id).kind()) {
+            Some(AnyEntKind::Design(Design::Entity(_, region)))
+            | Some(AnyEntKind::Component(region)) => region.to_entity_formal().1.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The actual and connection of each port, and the formal, actual and connection of
+    /// the associations that do not designate a known port
+    #[allow(clippy::type_complexity)]
+    fn port_actuals(
+        &self,
+        ports: &[InterfaceEnt],
+        port_map: &[AssociationElement],
+    ) -> (
+        Vec<Option<(String, Connection)>>,
+        Vec<(String, String, Connection)>,
+    ) {
+        let mut actuals: Vec<Option<(String, Connection)>> = vec![None; ports.len()];
+        let mut unmatched = Vec::new();
+        for (idx, assoc) in port_map.iter().enumerate() {
+            let actual = assoc.actual.item.to_string();
+            let connection = self.actual_connection(&assoc.actual.item);
+            let port = match assoc.formal {
+                None => (idx < ports.len()).then_some((idx, false)),
+                Some(ref formal) => formal_port(ports, &formal.item),
+            };
+
+            match port {
+                Some((port_idx, partial)) => {
+                    let entry = &mut actuals[port_idx];
+                    *entry = match entry.take() {
+                        // A port associated in parts
+                        Some((previous, _)) => {
+                            Some((format!("{previous}, {actual}"), Connection::Adapted))
+                        }
+                        None if partial => Some((actual, connection.adapted())),
+                        None => Some((actual, connection)),
+                    };
+                }
+                None => unmatched.push((
+                    assoc
+                        .formal
+                        .as_ref()
+                        .map(|formal| formal.item.to_string())
+                        .unwrap_or_default(),
+                    actual,
+                    connection,
+                )),
+            }
+        }
+        (actuals, unmatched)
+    }
+
+    fn actual_connection(&self, actual: &ActualPart) -> Connection {
+        match actual {
+            ActualPart::Open => Connection::Open,
+            ActualPart::Expression(expr) => self.expr_connection(expr),
+        }
+    }
+
+    fn expr_connection(&self, expr: &Expression) -> Connection {
+        match expr {
+            Expression::Literal(..) => Connection::Constant,
+            Expression::Name(name) => self.name_connection(name),
+            Expression::Qualified(qexpr) => self.expr_connection(&qexpr.expr.item).adapted(),
+            Expression::Unary(_, operand) => {
+                Connection::Constant.combined(self.expr_connection(&operand.item))
+            }
+            Expression::Binary(_, left, right) => self
+                .expr_connection(&left.item)
+                .combined(self.expr_connection(&right.item)),
+            Expression::Aggregate(assocs) => {
+                assocs
+                    .iter()
+                    .fold(Connection::Constant, |connection, assoc| match assoc {
+                        ElementAssociation::Positional(expr)
+                        | ElementAssociation::Named(_, expr) => {
+                            connection.combined(self.expr_connection(&expr.item))
+                        }
+                    })
+            }
+            Expression::New(..) => Connection::Expression,
+        }
+    }
+
+    fn name_connection(&self, name: &Name) -> Connection {
+        match name {
+            Name::Designator(designator) => self
+                .reference_connection(designator.reference)
+                .unwrap_or(Connection::Expression),
+            Name::Selected(prefix, suffix) => {
+                let is_element = suffix.item.reference.is_some_and(|id| {
+                    matches!(self.get_ent(id).kind(), AnyEntKind::ElementDeclaration(..))
+                });
+                if is_element {
+                    self.name_connection(&prefix.item)
+                } else {
+                    self.reference_connection(suffix.item.reference)
+                        .unwrap_or(Connection::Expression)
+                }
+            }
+            Name::Slice(prefix, _) => self.name_connection(&prefix.item).adapted(),
+            Name::CallOrIndexed(call) => {
+                let prefix = match call.name.item {
+                    Name::Designator(ref designator) => designator.reference,
+                    Name::Selected(_, ref suffix) => suffix.item.reference,
+                    _ => None,
+                };
+                if self.reference_connection(prefix).is_some() {
+                    // An indexed element of an object
+                    return self.name_connection(&call.name.item).adapted();
+                }
+
+                // A function call or type conversion
+                let arguments: Vec<_> = call
+                    .parameters
+                    .iter()
+                    .map(|assoc| self.actual_connection(&assoc.actual.item))
+                    .filter(|connection| *connection != Connection::Constant)
+                    .collect();
+                match arguments.as_slice() {
+                    [] => Connection::Constant,
+                    [Connection::Signal | Connection::Adapted] => Connection::Adapted,
+                    _ => Connection::Expression,
+                }
+            }
+            Name::External(external) => match external.class {
+                ExternalObjectClass::Constant => Connection::Constant,
+                _ => Connection::Signal,
+            },
+            Name::SelectedAll(..) | Name::Attribute(..) => Connection::Expression,
+        }
+    }
+
+    /// The connection of a reference to an object or enumeration literal
+    fn reference_connection(&self, reference: Option<EntityId>) -> Option<Connection> {
+        let ent = self.get_ent(reference?).as_actual();
+        let class = match ent.kind() {
+            AnyEntKind::Object(object) => object.class,
+            AnyEntKind::ObjectAlias { base_object, .. } => base_object.class(),
+            AnyEntKind::Overloaded(Overloaded::EnumLiteral(..)) => {
+                return Some(Connection::Constant)
+            }
+            _ => return None,
+        };
+        Some(match class {
+            ObjectClass::Signal => Connection::Signal,
+            ObjectClass::Constant => Connection::Constant,
+            ObjectClass::Variable | ObjectClass::SharedVariable => Connection::Expression,
+        })
+    }
+}
+
+/// How the ports of an entity are connected across its instances
+struct EntityPorts {
+    /// The entity as library.entity
+    name: String,
+    instances: usize,
+    ports: Vec<PortUsage>,
+}
+
+struct PortUsage {
+    port: Designator,
+    mode: String,
+    decl_pos: Option<SrcPos>,
+    /// The number of instances that leave the port open
+    open: usize,
+    /// True if any instance connects the port to something other than a constant
+    used: bool,
+}
+
+/// Visit the instances within the statements, including those within blocks and generate
+/// statements, with the labels of the enclosing statements and the instance separated by dots
+pub(super) fn visit_instances(
+    path: &str,
+    statements: &[LabeledConcurrentStatement],
+    visitor: &mut dyn FnMut(&str, &InstantiationStatement),
+) {
+    for statement in statements.iter() {
+        let path = match statement.label {
+            Some(ref label) if path.is_empty() => label.tree.item.to_string(),
+            Some(ref label) => format!("{path}.{}", label.tree.item),
+            None => path.to_owned(),
+        };
+
+        match statement.statement {
+            ConcurrentStatement::Instance(ref instance) => visitor(&path, instance),
+            ConcurrentStatement::Block(ref block) => {
+                visit_instances(&path, &block.statements, visitor)
+            }
+            ConcurrentStatement::ForGenerate(ref gen) => {
+                visit_instances(&path, &gen.body.statements, visitor)
+            }
+            ConcurrentStatement::IfGenerate(ref gen) => {
+                for conditional in gen.conditionals.iter() {
+                    visit_instances(&path, &conditional.item.statements, visitor);
+                }
+                if let Some(ref else_item) = gen.else_item {
+                    visit_instances(&path, &else_item.statements, visitor);
+                }
+            }
+            ConcurrentStatement::CaseGenerate(ref gen) => {
+                for alternative in gen.alternatives.iter() {
+                    visit_instances(&path, &alternative.item.statements, visitor);
+                }
+            }
+            ConcurrentStatement::ProcedureCall(..)
+            | ConcurrentStatement::Process(..)
+            | ConcurrentStatement::Assert(..)
+            | ConcurrentStatement::PslDirective(..)
+            | ConcurrentStatement::Assignment(..) => {}
+        }
+    }
+}
+
+/// A description of the instantiated unit and a reference to its entity or component
+fn instantiated_unit(instance: &InstantiationStatement) -> (String, Option<EntityId>) {
+    match instance.unit {
+        InstantiatedUnit::Entity(ref name, None) => (
+            format!("entity {}", name.item),
+            name.item.get_suffix_reference(),
+        ),
+        InstantiatedUnit::Entity(ref name, Some(ref arch)) => (
+            format!("entity {}({})", name.item, arch.item),
+            name.item.get_suffix_reference(),
+        ),
+        InstantiatedUnit::Component(ref name) => (
+            format!("component {}", name.item),
+            name.item.get_suffix_reference(),
+        ),
+        InstantiatedUnit::Configuration(ref name) => (format!("configuration {}", name.item), None),
+    }
+}
+
+/// The index of the port that a formal designates and whether the port is associated
+/// in parts or through a conversion
+fn formal_port(ports: &[InterfaceEnt], formal: &Name) -> Option<(usize, bool)> {
+    match formal {
+        Name::Designator(designator) => ports
+            .iter()
+            .position(|port| port.designator() == designator.designator())
+            .map(|idx| (idx, false)),
+        Name::Selected(prefix, _) | Name::Slice(prefix, _) => {
+            formal_port(ports, &prefix.item).map(|(idx, _)| (idx, true))
+        }
+        Name::CallOrIndexed(call) => formal_port(ports, &call.name.item)
+            .or_else(|| match call.parameters.as_slice() {
+                [AssociationElement {
+                    actual:
+                        WithPos {
+                            item: ActualPart::Expression(Expression::Name(name)),
+                            ..
+                        },
+                    ..
+                }] => formal_port(ports, name),
+                _ => None,
+            })
+            .map(|(idx, _)| (idx, true)),
+        _ => None,
+    }
+}
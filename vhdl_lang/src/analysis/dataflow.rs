@@ -0,0 +1,360 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! The dataflow of a single process as a graph from the signals it reads through its
+//! variables to the signals it assigns. An assignment adds an edge from each object read
+//! by its right hand side, and a dashed edge from each object read by the conditions of
+//! the enclosing if, case and loop statements.
+
+use super::combinational_loop::base_signal;
+use super::named_entity::*;
+use super::process::signal_reads;
+use super::root::DesignRoot;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+
+/// The role of an object within the dataflow of a process
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataflowNodeKind {
+    /// A signal that is read but not assigned by the process
+    Input,
+    Variable,
+    /// A signal that is assigned by the process
+    Output,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataflowNode {
+    pub name: String,
+    pub kind: DataflowNodeKind,
+    pub decl_pos: Option<SrcPos>,
+}
+
+/// An assignment of an object that reads another object, the indexes are those of the nodes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataflowEdge {
+    pub from: usize,
+    pub to: usize,
+    /// The object is read by a condition of the assignment rather than its value
+    pub control: bool,
+    /// The position of the first read of the object for the assignment
+    pub pos: SrcPos,
+}
+
+/// The objects read and assigned by a process in the order of their first occurrence
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessDataflow {
+    /// The architecture as library.entity(architecture)
+    pub architecture: String,
+    pub label: Option<String>,
+    pub nodes: Vec<DataflowNode>,
+    pub edges: Vec<DataflowEdge>,
+}
+
+impl ProcessDataflow {
+    /// The graph in the DOT language of Graphviz from left to right, where the outputs are bold
+    /// and the edges of conditions are dashed
+    pub fn to_dot(&self) -> String {
+        let name = match self.label {
+            Some(ref label) => format!("{}.{}", self.architecture, label),
+            None => self.architecture.clone(),
+        };
+        let mut dot = format!("digraph {} {{\n  rankdir=LR;\n", dot_id(&name));
+        for (index, node) in self.nodes.iter().enumerate() {
+            let attributes = match node.kind {
+                DataflowNodeKind::Input => "shape=box",
+                DataflowNodeKind::Variable => "shape=ellipse",
+                DataflowNodeKind::Output => "shape=box, style=bold",
+            };
+            dot.push_str(&format!(
+                "  n{index} [label={}, {attributes}];\n",
+                dot_id(&node.name)
+            ));
+        }
+        for edge in self.edges.iter() {
+            let style = if edge.control { " [style=dashed]" } else { "" };
+            dot.push_str(&format!("  n{} -> n{}{style};\n", edge.from, edge.to));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// An object assigned by a statement of the process with the objects read for it
+struct Assignment {
+    target: EntityId,
+    sources: Vec<(EntityId, SrcPos)>,
+    conditions: Vec<(EntityId, SrcPos)>,
+}
+
+impl DesignRoot {
+    /// The dataflow of the process of an architecture in the source at the cursor.
+    /// The cursor selects the process when it is on a line from its label or first
+    /// referenced name up to its last referenced name.
+    pub fn process_dataflow(&self, source: &Source, cursor: Position) -> Option<ProcessDataflow> {
+        for (unit_id, mut arch) in self.analyzed_architectures() {
+            if arch.source() != source {
+                continue;
+            }
+
+            let Some(statement) = process_at_cursor(&mut arch.statements, cursor) else {
+                continue;
+            };
+            let label = statement.label.as_ref().map(|label| label.tree.to_string());
+            let ConcurrentStatement::Process(ref mut process) = statement.statement else {
+                continue;
+            };
+            let is_object = |id| {
+                ObjectEnt::from_any(self.get_ent(id)).is_some_and(|object| {
+                    matches!(object.class(), ObjectClass::Signal | ObjectClass::Variable)
+                })
+            };
+            let mut assignments = Vec::new();
+            dataflow_assignments(
+                &mut process.statements,
+                &is_object,
+                &mut Vec::new(),
+                &mut assignments,
+            );
+
+            return Some(ProcessDataflow {
+                architecture: format!(
+                    "{}.{}({})",
+                    unit_id.library_name(),
+                    arch.entity_name.item,
+                    arch.ident.item
+                ),
+                label,
+                ..self.dataflow_graph(assignments)
+            });
+        }
+        None
+    }
+
+    fn dataflow_graph(&self, assignments: Vec<Assignment>) -> ProcessDataflow {
+        let mut ids: Vec<EntityId> = Vec::new();
+        let mut graph = ProcessDataflow {
+            architecture: String::new(),
+            label: None,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+        let mut node = |id: EntityId, graph: &mut ProcessDataflow| -> usize {
+            if let Some(index) = ids.iter().position(|other| *other == id) {
+                return index;
+            }
+            let ent = self.get_ent(id);
+            let is_variable = ObjectEnt::from_any(ent)
+                .is_some_and(|object| object.class() == ObjectClass::Variable);
+            let is_assigned = assignments.iter().any(|assignment| assignment.target == id);
+            ids.push(id);
+            graph.nodes.push(DataflowNode {
+                name: ent.designator().to_string(),
+                kind: if is_variable {
+                    DataflowNodeKind::Variable
+                } else if is_assigned {
+                    DataflowNodeKind::Output
+                } else {
+                    DataflowNodeKind::Input
+                },
+                decl_pos: ent.decl_pos().cloned(),
+            });
+            ids.len() - 1
+        };
+
+        for assignment in assignments.iter() {
+            let sources = assignment.sources.iter().map(|source| (source, false));
+            let conditions = assignment.conditions.iter().map(|source| (source, true));
+            for ((source, pos), control) in sources.chain(conditions) {
+                let from = node(*source, &mut graph);
+                let to = node(assignment.target, &mut graph);
+                let is_duplicate = graph
+                    .edges
+                    .iter()
+                    .any(|edge| edge.from == from && edge.to == to && edge.control == control);
+                if !is_duplicate {
+                    graph.edges.push(DataflowEdge {
+                        from,
+                        to,
+                        control,
+                        pos: pos.clone(),
+                    });
+                }
+            }
+            // An object that is only assigned constants is still part of the graph
+            node(assignment.target, &mut graph);
+        }
+        graph
+    }
+}
+
+/// The process whose lines include the cursor including those within blocks and generate statements
+fn process_at_cursor(
+    statements: &mut [LabeledConcurrentStatement],
+    cursor: Position,
+) -> Option<&mut LabeledConcurrentStatement> {
+    for statement in statements.iter_mut() {
+        let is_selected = match statement.statement {
+            ConcurrentStatement::Process(_) => {
+                let mut extent = ProcessExtent::default();
+                if let Some(ref label) = statement.label {
+                    extent.include(&label.tree.pos);
+                }
+                let _ = statement.search(&mut extent);
+                extent
+                    .lines
+                    .is_some_and(|(first, last)| first <= cursor.line && cursor.line <= last)
+            }
+            _ => false,
+        };
+        if is_selected {
+            return Some(statement);
+        }
+        let found = match statement.statement {
+            ConcurrentStatement::Block(ref mut block) => {
+                process_at_cursor(&mut block.statements, cursor)
+            }
+            ConcurrentStatement::ForGenerate(ref mut gen) => {
+                process_at_cursor(&mut gen.body.statements, cursor)
+            }
+            ConcurrentStatement::IfGenerate(ref mut gen) => gen
+                .conditionals
+                .iter_mut()
+                .map(|conditional| &mut conditional.item.statements)
+                .chain(
+                    gen.else_item
+                        .as_mut()
+                        .map(|else_item| &mut else_item.statements),
+                )
+                .find_map(|statements| process_at_cursor(statements, cursor)),
+            ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                gen.alternatives.iter_mut().find_map(|alternative| {
+                    process_at_cursor(&mut alternative.item.statements, cursor)
+                })
+            }
+            _ => None,
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// The first and last line of the names within a statement
+#[derive(Default)]
+struct ProcessExtent {
+    lines: Option<(u32, u32)>,
+}
+
+impl ProcessExtent {
+    fn include(&mut self, pos: &SrcPos) {
+        let (first, last) = (pos.start().line, pos.end().line);
+        self.lines = Some(match self.lines {
+            Some((start, end)) => (start.min(first), end.max(last)),
+            None => (first, last),
+        });
+    }
+}
+
+impl Searcher for ProcessExtent {
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, _ref: &mut Reference) -> SearchState {
+        self.include(pos);
+        NotFinished
+    }
+
+    fn search_with_pos(&mut self, pos: &SrcPos) -> SearchState {
+        self.include(pos);
+        NotFinished
+    }
+}
+
+/// The assignments of signals and variables within the statements of a process,
+/// the conditions are the objects read by the conditions of the enclosing statements
+fn dataflow_assignments(
+    statements: &mut [LabeledSequentialStatement],
+    is_object: &dyn Fn(EntityId) -> bool,
+    conditions: &mut Vec<(EntityId, SrcPos)>,
+    assignments: &mut Vec<Assignment>,
+) {
+    for statement in statements.iter_mut() {
+        let outer = conditions.len();
+        match statement.statement {
+            SequentialStatement::SignalAssignment(ref mut assign) => {
+                assignments.extend(assignment(
+                    &assign.target.item,
+                    signal_reads(&mut assign.rhs, is_object),
+                    is_object,
+                    conditions,
+                ));
+            }
+            SequentialStatement::VariableAssignment(ref mut assign) => {
+                assignments.extend(assignment(
+                    &assign.target.item,
+                    signal_reads(&mut assign.rhs, is_object),
+                    is_object,
+                    conditions,
+                ));
+            }
+            SequentialStatement::If(ref mut ifstmt) => {
+                // A branch also depends on the conditions of the branches before it
+                for conditional in ifstmt.conditionals.iter_mut() {
+                    conditions.extend(signal_reads(&mut conditional.condition, is_object));
+                    dataflow_assignments(&mut conditional.item, is_object, conditions, assignments);
+                }
+                if let Some(ref mut else_item) = ifstmt.else_item {
+                    dataflow_assignments(else_item, is_object, conditions, assignments);
+                }
+            }
+            SequentialStatement::Case(ref mut case_stmt) => {
+                conditions.extend(signal_reads(&mut case_stmt.expression, is_object));
+                for alternative in case_stmt.alternatives.iter_mut() {
+                    dataflow_assignments(&mut alternative.item, is_object, conditions, assignments);
+                }
+            }
+            SequentialStatement::Loop(ref mut loop_stmt) => {
+                match loop_stmt.iteration_scheme {
+                    Some(IterationScheme::While(ref mut condition)) => {
+                        conditions.extend(signal_reads(condition, is_object));
+                    }
+                    Some(IterationScheme::For(_, ref mut range)) => {
+                        conditions.extend(signal_reads(range, is_object));
+                    }
+                    None => {}
+                }
+                dataflow_assignments(
+                    &mut loop_stmt.statements,
+                    is_object,
+                    conditions,
+                    assignments,
+                );
+            }
+            _ => {}
+        }
+        conditions.truncate(outer);
+    }
+}
+
+fn assignment(
+    target: &Target,
+    sources: Vec<(EntityId, SrcPos)>,
+    is_object: &dyn Fn(EntityId) -> bool,
+    conditions: &[(EntityId, SrcPos)],
+) -> Option<Assignment> {
+    let Target::Name(ref name) = target else {
+        return None;
+    };
+    Some(Assignment {
+        target: base_signal(name, is_object)?,
+        sources,
+        conditions: conditions.to_vec(),
+    })
+}
@@ -5,10 +5,14 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::formal_region::FormalRegion;
+use super::formal_region::GpkgInterfaceEnt;
+use super::formal_region::GpkgRegion;
+use super::formal_region::RecordElement;
 use super::formal_region::RecordRegion;
 use super::named_entity::*;
 use super::names::*;
 use super::sequential::SequentialRoot;
+use super::wait_graph::CallerKind;
 use super::*;
 use crate::ast;
 use crate::ast::*;
@@ -30,7 +34,10 @@ impl<'a> AnalyzeContext<'a> {
     ) -> FatalResult {
         let mut incomplete_types: FnvHashMap<Symbol, (EntRef<'a>, SrcPos)> = FnvHashMap::default();
 
+        let mut probe = self.probe_list(declarations);
         for i in 0..declarations.len() {
+            self.probe_item(scope, &mut probe, i);
+
             // Handle incomplete types
 
             let (decl, remaining) = declarations[i..].split_first_mut().unwrap();
@@ -103,6 +110,7 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
         }
+        self.probe_end(scope, probe);
         Ok(())
     }
 
@@ -121,10 +129,18 @@ impl<'a> AnalyzeContext<'a> {
 
         let resolved_name = self.name_resolve(scope, &name.pos, &mut name.item, diagnostics);
 
-        if let Some(ref mut subtype_indication) = subtype_indication {
+        let subtype = if let Some(ref mut subtype_indication) = subtype_indication {
             // Object alias
-            self.analyze_subtype_indication(scope, subtype_indication, diagnostics)?;
-        }
+            match self.resolve_subtype_indication(scope, subtype_indication, diagnostics) {
+                Ok(subtype) => Some(subtype),
+                Err(err) => {
+                    err.add_to(diagnostics)?;
+                    None
+                }
+            }
+        } else {
+            None
+        };
 
         let resolved_name = resolved_name?;
 
@@ -134,19 +150,39 @@ impl<'a> AnalyzeContext<'a> {
                     if let Some(ref signature) = signature {
                         diagnostics.push(Diagnostic::should_not_have_signature("Alias", signature));
                     }
+                    // The subtype of the alias is the subtype indication when given
+                    // which must have the base type of the object, element or slice
+                    let type_mark = match subtype {
+                        Some(subtype) => {
+                            let type_mark = subtype.type_mark();
+                            if type_mark.base_type() != oname.type_mark().base_type() {
+                                let pos = &subtype_indication.as_ref().unwrap().type_mark.pos;
+                                diagnostics.error(
+                                    pos,
+                                    format!(
+                                        "Subtype '{}' of alias does not match {}",
+                                        type_mark.designator(),
+                                        oname.describe_type()
+                                    ),
+                                );
+                                return Err(EvalError::Unknown);
+                            }
+                            type_mark
+                        }
+                        None => oname.type_mark(),
+                    };
                     match oname.base {
                         ObjectBase::Object(base_object) => AnyEntKind::ObjectAlias {
                             base_object,
-                            type_mark: oname.type_mark(),
+                            type_mark,
                         },
                         ObjectBase::ObjectAlias(base_object, _) => AnyEntKind::ObjectAlias {
                             base_object,
-                            type_mark: oname.type_mark(),
-                        },
-                        ObjectBase::ExternalName(class) => AnyEntKind::ExternalAlias {
-                            class,
-                            type_mark: oname.type_mark(),
+                            type_mark,
                         },
+                        ObjectBase::ExternalName(class) => {
+                            AnyEntKind::ExternalAlias { class, type_mark }
+                        }
                         ObjectBase::DeferredConstant(_) => {
                             // @TODO handle
                             return Err(EvalError::Unknown);
@@ -179,6 +215,15 @@ impl<'a> AnalyzeContext<'a> {
                                     if let Some(reference) = name.item.suffix_reference_mut() {
                                         reference.set_unique_reference(&ent);
                                     }
+                                    if let Designator::OperatorSymbol(op) = designator.tree.item {
+                                        // LRM 6.6.3 An operator alias denotes a function with
+                                        // a signature that may overload the operator
+                                        if let Err(diagnostic) =
+                                            check_operator_alias(&designator.tree, op, ent)
+                                        {
+                                            diagnostics.push(diagnostic);
+                                        }
+                                    }
                                     AnyEntKind::Overloaded(Overloaded::Alias(ent))
                                 } else {
                                     diagnostics.push(Diagnostic::no_overloaded_with_signature(
@@ -199,14 +244,27 @@ impl<'a> AnalyzeContext<'a> {
                         return Err(EvalError::Unknown);
                     }
                 }
-                ResolvedName::Final(_) => {
-                    // @TODO some of these can probably be aliased
+                ResolvedName::Final(ent) => {
+                    // LRM 6.6.3 Labels and loop parameters may not be aliased
+                    if matches!(ent.kind(), AnyEntKind::Label | AnyEntKind::LoopParameter(_)) {
+                        diagnostics.error(
+                            &name.pos,
+                            format!("{} cannot be aliased", capitalize(&ent.describe())),
+                        );
+                    }
+                    // @TODO files, components and physical units can be aliased
                     return Err(EvalError::Unknown);
                 }
             }
         };
 
-        Ok(designator.define(self.arena, kind))
+        let ent = designator.define(self.arena, kind);
+        if let AnyEntKind::ObjectAlias { type_mark, .. } = ent.kind() {
+            let ranges =
+                self.object_alias_ranges(*type_mark, &name.item, subtype_indication.as_ref());
+            self.set_static_property(ent, ranges.map(StaticProperty::Ranges));
+        }
+        Ok(ent)
     }
 
     pub(crate) fn analyze_declaration(
@@ -259,6 +317,25 @@ impl<'a> AnalyzeContext<'a> {
                             &mut expr.item,
                             diagnostics,
                         )?;
+                        let ranges = self
+                            .subtype_indication_ranges(subtype, &object_decl.subtype_indication);
+                        if object_decl.class == ObjectClass::Signal {
+                            self.check_static_default(
+                                subtype.type_mark(),
+                                ranges.as_deref(),
+                                expr,
+                                diagnostics,
+                            );
+                        }
+                        if let Some([range]) = ranges.as_deref() {
+                            self.check_aggregate_length(
+                                scope,
+                                subtype.type_mark(),
+                                range,
+                                expr,
+                                diagnostics,
+                            );
+                        }
                     } else {
                         self.expr_unknown_ttyp(scope, expr, diagnostics)?;
                     }
@@ -266,6 +343,36 @@ impl<'a> AnalyzeContext<'a> {
 
                 match subtype {
                     Ok(subtype) => {
+                        if object_decl.class == ObjectClass::SharedVariable {
+                            self.check_shared_variable_type(
+                                &object_decl.ident.tree,
+                                &subtype,
+                                diagnostics,
+                            );
+                        }
+                        self.check_protected_object(
+                            object_decl.class,
+                            &object_decl.ident.tree,
+                            &subtype,
+                            diagnostics,
+                        );
+
+                        let static_property = if let (ObjectClass::Constant, Some(expr)) =
+                            (object_decl.class, &object_decl.expression)
+                        {
+                            self.eval_static(Some(subtype.type_mark()), &expr.item)
+                                .map(StaticProperty::Value)
+                        } else {
+                            None
+                        }
+                        .or_else(|| {
+                            self.subtype_indication_ranges(
+                                &subtype,
+                                &object_decl.subtype_indication,
+                            )
+                            .map(StaticProperty::Ranges)
+                        });
+
                         let kind = if object_decl.class == ObjectClass::Constant
                             && object_decl.expression.is_none()
                         {
@@ -275,10 +382,13 @@ impl<'a> AnalyzeContext<'a> {
                                 class: object_decl.class,
                                 mode: None,
                                 has_default: object_decl.expression.is_some(),
+                                view: None,
                                 subtype,
                             })
                         };
-                        scope.add(self.arena.define(&mut object_decl.ident, kind), diagnostics);
+                        let ent = self.arena.define(&mut object_decl.ident, kind);
+                        self.set_static_property(ent, static_property);
+                        scope.add(ent, diagnostics);
                     }
                     Err(err) => err.add_to(diagnostics)?,
                 }
@@ -342,17 +452,15 @@ impl<'a> AnalyzeContext<'a> {
                         }
                     }
                 }
-                // @TODO Ignored for now
                 Attribute::Specification(ref mut attr_spec) => {
                     let AttributeSpecification {
                         ident,
                         entity_name,
-                        // @TODO also check the entity class
-                        entity_class: _,
+                        entity_class,
                         expr,
                     } = attr_spec;
 
-                    match scope.lookup(
+                    let attribute = match scope.lookup(
                         &ident.item.pos,
                         &Designator::Identifier(ident.item.name().clone()),
                     ) {
@@ -366,11 +474,13 @@ impl<'a> AnalyzeContext<'a> {
                                     &mut expr.item,
                                     diagnostics,
                                 )?;
+                                Some(ent)
                             } else {
                                 diagnostics.error(
                                     &ident.item.pos,
                                     format!("{} is not an attribute", ent.describe()),
                                 );
+                                None
                             }
                         }
                         Ok(NamedEntities::Overloaded(_)) => {
@@ -378,54 +488,96 @@ impl<'a> AnalyzeContext<'a> {
                                 &ident.item.pos,
                                 format!("Overloaded name '{}' is not an attribute", ident.item),
                             );
+                            None
                         }
                         Err(err) => {
                             diagnostics.push(err);
+                            None
                         }
-                    }
+                    };
 
-                    if let EntityName::Name(EntityTag {
-                        designator,
-                        signature,
-                    }) = entity_name
-                    {
-                        match scope.lookup(&designator.pos, &designator.item.item) {
-                            Ok(NamedEntities::Single(ent)) => {
-                                designator.set_unique_reference(ent);
-
-                                if let Some(signature) = signature {
-                                    diagnostics.push(Diagnostic::should_not_have_signature(
-                                        "Attribute specification",
-                                        &signature.pos,
-                                    ));
+                    match entity_name {
+                        EntityName::Name(EntityTag {
+                            designator,
+                            signature,
+                        }) => {
+                            let named = match scope.lookup(&designator.pos, &designator.item.item) {
+                                Ok(NamedEntities::Single(ent)) => {
+                                    designator.set_unique_reference(ent);
+
+                                    if let Some(signature) = signature {
+                                        diagnostics.push(Diagnostic::should_not_have_signature(
+                                            "Attribute specification",
+                                            &signature.pos,
+                                        ));
+                                    }
+                                    Some(ent)
                                 }
-                            }
-                            Ok(NamedEntities::Overloaded(overloaded)) => {
-                                if let Some(signature) = signature {
-                                    match self.resolve_signature(scope, signature) {
-                                        Ok(signature_key) => {
-                                            if let Some(ent) = overloaded.get(&signature_key) {
-                                                designator.set_unique_reference(&ent);
-                                            } else {
-                                                diagnostics.push(
-                                                    Diagnostic::no_overloaded_with_signature(
-                                                        &designator.pos,
-                                                        &designator.item.item,
-                                                        &overloaded,
-                                                    ),
-                                                );
+                                Ok(NamedEntities::Overloaded(overloaded)) => {
+                                    if let Some(signature) = signature {
+                                        match self.resolve_signature(scope, signature) {
+                                            Ok(signature_key) => {
+                                                if let Some(ent) = overloaded.get(&signature_key) {
+                                                    designator.set_unique_reference(&ent);
+                                                    Some(ent.into())
+                                                } else {
+                                                    diagnostics.push(
+                                                        Diagnostic::no_overloaded_with_signature(
+                                                            &designator.pos,
+                                                            &designator.item.item,
+                                                            &overloaded,
+                                                        ),
+                                                    );
+                                                    None
+                                                }
+                                            }
+                                            Err(err) => {
+                                                err.add_to(diagnostics)?;
+                                                None
                                             }
                                         }
-                                        Err(err) => {
-                                            err.add_to(diagnostics)?;
-                                        }
+                                    } else {
+                                        diagnostics
+                                            .push(Diagnostic::signature_required(&designator.pos));
+                                        None
                                     }
-                                } else {
-                                    diagnostics.push(Diagnostic::signature_required(designator));
                                 }
+                                Err(err) => {
+                                    diagnostics.push(err);
+                                    None
+                                }
+                            };
+
+                            if let (Some(attribute), Some(ent)) = (attribute, named) {
+                                self.add_attribute_value(
+                                    ent,
+                                    attribute,
+                                    &designator.pos,
+                                    &expr.pos,
+                                    diagnostics,
+                                );
                             }
-                            Err(err) => {
-                                diagnostics.push(err);
+                        }
+                        EntityName::All | EntityName::Others => {
+                            if let Some(attribute) = attribute {
+                                let others = matches!(entity_name, EntityName::Others);
+
+                                for ent in scope.immediates() {
+                                    if !ent.is_explicit()
+                                        || !is_of_entity_class(ent, *entity_class)
+                                        || (others && ent.attribute_value(attribute).is_some())
+                                    {
+                                        continue;
+                                    }
+
+                                    self.add_attribute_value(
+                                        ent,
+                                        attribute,
+                                        &expr.pos,
+                                        &expr.pos,
+                                        diagnostics,
+                                    );
+                                }
                             }
                         }
                     }
@@ -434,6 +586,11 @@ impl<'a> AnalyzeContext<'a> {
             Declaration::SubprogramBody(ref mut body) => {
                 let subpgm_region = scope.nested();
 
+                let generics = self.analyze_subprogram_header(
+                    &subpgm_region,
+                    &mut body.specification,
+                    diagnostics,
+                )?;
                 let signature = self.analyze_subprogram_declaration(
                     &subpgm_region,
                     &mut body.specification,
@@ -444,7 +601,7 @@ impl<'a> AnalyzeContext<'a> {
                 let subpgm_region = Scope::new(subpgm_region.into_region());
 
                 // Overwrite subprogram definition with full signature
-                let sroot = match signature {
+                let (sroot, ids) = match signature {
                     Ok(signature) => {
                         let sroot = if let Some(return_type) = signature.return_type() {
                             SequentialRoot::Function(return_type)
@@ -452,17 +609,22 @@ impl<'a> AnalyzeContext<'a> {
                             SequentialRoot::Procedure
                         };
 
-                        let subpgm_ent = body.specification.define(
-                            self.arena,
-                            AnyEntKind::Overloaded(Overloaded::Subprogram(signature)),
-                        );
+                        let kind = match generics {
+                            Some(generics) => Overloaded::UninstSubprogram(signature, generics),
+                            None => Overloaded::Subprogram(signature),
+                        };
+                        let subpgm_ent = body
+                            .specification
+                            .define(self.arena, AnyEntKind::Overloaded(kind));
+                        let mut ids = vec![subpgm_ent.id()];
+                        ids.extend(self.subprogram_declaration(scope, subpgm_ent));
                         scope.add(subpgm_ent, diagnostics);
 
-                        sroot
+                        (sroot, ids)
                     }
                     Err(err) => {
                         err.add_to(diagnostics)?;
-                        SequentialRoot::Unknown
+                        (SequentialRoot::Unknown, Vec::new())
                     }
                 };
                 let subpgm_region = subpgm_region.with_parent(scope);
@@ -476,9 +638,24 @@ impl<'a> AnalyzeContext<'a> {
                     &mut body.statements,
                     diagnostics,
                 )?;
+                let kind = match body.specification {
+                    SubprogramDeclaration::Function(..) => CallerKind::Function,
+                    SubprogramDeclaration::Procedure(..) => CallerKind::Procedure,
+                };
+                self.record_caller(kind, ids, &body.statements);
+                self.check_function_purity(body, diagnostics);
+                self.check_return_paths(body, diagnostics);
+                self.check_unused_parameters(body, diagnostics);
+                self.check_write_only_variables(
+                    &mut body.declarations,
+                    &mut body.statements,
+                    diagnostics,
+                );
             }
             Declaration::SubprogramDeclaration(ref mut subdecl) => {
                 let subpgm_region = scope.nested();
+                let generics =
+                    self.analyze_subprogram_header(&subpgm_region, subdecl, diagnostics)?;
                 let signature =
                     self.analyze_subprogram_declaration(&subpgm_region, subdecl, diagnostics);
                 subpgm_region.close(diagnostics);
@@ -486,11 +663,12 @@ impl<'a> AnalyzeContext<'a> {
 
                 match signature {
                     Ok(signature) => {
+                        let kind = match generics {
+                            Some(generics) => Overloaded::UninstSubprogramDecl(signature, generics),
+                            None => Overloaded::SubprogramDecl(signature),
+                        };
                         scope.add(
-                            subdecl.define(
-                                self.arena,
-                                AnyEntKind::Overloaded(Overloaded::SubprogramDecl(signature)),
-                            ),
+                            subdecl.define(self.arena, AnyEntKind::Overloaded(kind)),
                             diagnostics,
                         );
                     }
@@ -502,6 +680,13 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_use_clause(scope, &mut use_clause.item, diagnostics)?;
             }
 
+            Declaration::SubprogramInstantiation(ref mut instance) => {
+                if let Some(ent) =
+                    as_fatal(self.generic_subprogram_instance(scope, instance, diagnostics))?
+                {
+                    scope.add(ent, diagnostics);
+                }
+            }
             Declaration::Package(ref mut instance) => {
                 if let Some(pkg_region) =
                     as_fatal(self.generic_package_instance(scope, instance, diagnostics))?
@@ -515,13 +700,632 @@ impl<'a> AnalyzeContext<'a> {
                     );
                 }
             }
-            Declaration::Configuration(..) => {}
+            Declaration::Configuration(ref mut config) => {
+                self.analyze_configuration_specification(scope, config, diagnostics)?;
+            }
+            Declaration::GroupTemplate(ref mut template) => {
+                let GroupTemplateDeclaration {
+                    ident,
+                    entity_class_entries,
+                } = template;
+
+                if let Some((_, init)) = entity_class_entries.split_last() {
+                    for entry in init.iter().filter(|entry| entry.item.is_box) {
+                        diagnostics.error(
+                            &entry.pos,
+                            "Only the last entity class entry of a group template may contain a box (<>)",
+                        );
+                    }
+                }
+
+                let entries = entity_class_entries
+                    .iter()
+                    .map(|entry| entry.item)
+                    .collect();
+                scope.add(
+                    self.arena.define(ident, AnyEntKind::GroupTemplate(entries)),
+                    diagnostics,
+                );
+            }
+            Declaration::Group(ref mut group) => {
+                self.analyze_group_declaration(scope, group, diagnostics)?;
+            }
+            Declaration::View(ref mut view) => {
+                match self.analyze_mode_view_declaration(scope, view, diagnostics) {
+                    Ok(ent) => scope.add(ent, diagnostics),
+                    Err(err) => err.add_to(diagnostics)?,
+                }
+            }
+            Declaration::PslClock(ref mut clock) => {
+                self.boolean_expr(scope, clock, diagnostics)?;
+            }
             Declaration::Type(..) => unreachable!("Handled elsewhere"),
         };
 
         Ok(())
     }
 
+    /// LRM 6.5.2 Mode view declarations
+    fn analyze_mode_view_declaration(
+        &self,
+        scope: &Scope<'a>,
+        view: &mut ModeViewDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> AnalysisResult<EntRef<'a>> {
+        let ModeViewDeclaration {
+            ident,
+            typ,
+            elements,
+        } = view;
+
+        if self.root.standard() < VHDLStandard::VHDL2019 {
+            diagnostics.push(
+                Diagnostic::error(
+                    ident.pos(),
+                    "Mode view declarations are not allowed before VHDL-2019",
+                )
+                .with_code(ErrorCode::LaterRevision),
+            );
+        }
+
+        let type_mark = self
+            .resolve_subtype_indication(scope, typ, diagnostics)?
+            .type_mark();
+        let Type::Record(region) = type_mark.base_type().kind() else {
+            return Err(AnalysisError::not_fatal_error(
+                &typ.type_mark.pos,
+                format!(
+                    "The subtype of a mode view must be a record type, got {}",
+                    type_mark.describe()
+                ),
+            ));
+        };
+
+        let mut modes = Vec::with_capacity(region.len());
+        let mut given: FnvHashMap<EntityId, SrcPos> = FnvHashMap::default();
+
+        for element in elements.iter_mut() {
+            let mode = match element.mode {
+                ElementMode::Simple(ref mode) => Some(ViewElementMode::Simple(mode.item)),
+                ElementMode::View(kind, ref mut name) => {
+                    match self.resolve_view_name(scope, name) {
+                        Ok(view) => Some(match kind {
+                            ModeViewKind::Record => ViewElementMode::Record(view),
+                            ModeViewKind::Array => ViewElementMode::Array(view),
+                        }),
+                        Err(err) => {
+                            err.add_to(diagnostics)?;
+                            None
+                        }
+                    }
+                }
+            };
+
+            for name in element.names.iter_mut() {
+                let designator = Designator::Identifier(name.item.item.clone());
+                let Some(elem) = region.lookup(&designator) else {
+                    diagnostics.error(
+                        &name.item.pos,
+                        format!(
+                            "No declaration of '{}' within {}",
+                            designator,
+                            type_mark.describe()
+                        ),
+                    );
+                    continue;
+                };
+                name.set_unique_reference(&elem);
+
+                match given.entry(elem.id()) {
+                    Entry::Occupied(entry) => {
+                        diagnostics.push(
+                            Diagnostic::error(
+                                &name.item.pos,
+                                format!("Duplicate mode of element '{designator}'"),
+                            )
+                            .related(entry.get(), "Previously given here"),
+                        );
+                        continue;
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(name.item.pos.clone());
+                    }
+                }
+
+                if let Some(mode) = mode {
+                    self.check_element_view(&name.item.pos, &elem, mode, diagnostics);
+                    modes.push((elem.into(), mode));
+                }
+            }
+        }
+
+        for elem in region.iter() {
+            if !given.contains_key(&elem.id()) {
+                diagnostics.error(
+                    ident.pos(),
+                    format!(
+                        "Element '{}' of {} is not given a mode in view '{}'",
+                        elem.designator(),
+                        type_mark.describe(),
+                        ident.tree.item
+                    ),
+                );
+            }
+        }
+
+        Ok(self.arena.define(
+            ident,
+            AnyEntKind::View(View {
+                typ: type_mark,
+                elements: modes,
+            }),
+        ))
+    }
+
+    fn resolve_view_name(
+        &self,
+        scope: &Scope<'a>,
+        name: &mut WithPos<SelectedName>,
+    ) -> AnalysisResult<ViewEnt<'a>> {
+        let ent = self.resolve_non_overloaded_with_kind(
+            self.resolve_selected_name(scope, name)?,
+            name.suffix_pos(),
+            &|kind| matches!(kind, AnyEntKind::View(..)),
+            "view",
+        )?;
+        Ok(ViewEnt::from_any(ent).unwrap())
+    }
+
+    /// The view of a record element must be of the type of the element,
+    /// or of the element type of an array element
+    fn check_element_view(
+        &self,
+        pos: &SrcPos,
+        elem: &RecordElement<'a>,
+        mode: ViewElementMode<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let (view, typ) = match mode {
+            ViewElementMode::Simple(_) => return,
+            ViewElementMode::Record(view) => (view, Some(elem.type_mark())),
+            ViewElementMode::Array(view) => (
+                view,
+                elem.type_mark()
+                    .array_type()
+                    .map(|(elem_type, _)| elem_type),
+            ),
+        };
+
+        if typ.map(|typ| typ.base_type()) != Some(view.type_mark().base_type()) {
+            diagnostics.error(
+                pos,
+                format!(
+                    "{} of {} cannot be the view of element '{}' of {}",
+                    capitalize(&view.describe()),
+                    view.type_mark().describe(),
+                    elem.designator(),
+                    elem.type_mark().describe()
+                ),
+            );
+        }
+    }
+
+    /// LRM 6.5.2 The subtype of a port with a mode view indication
+    fn analyze_mode_view_indication(
+        &self,
+        scope: &Scope<'a>,
+        indication: &mut ModeViewIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> AnalysisResult<(ViewEnt<'a>, Subtype<'a>)> {
+        let ModeViewIndication {
+            kind,
+            name,
+            subtype_indication,
+        } = indication;
+
+        let view = self.resolve_view_name(scope, name)?;
+        let Some(subtype_indication) = subtype_indication else {
+            return Ok((view, Subtype::new(view.type_mark())));
+        };
+
+        let subtype = self.resolve_subtype_indication(scope, subtype_indication, diagnostics)?;
+        let typ = match kind {
+            ModeViewKind::Record => Some(subtype.type_mark()),
+            ModeViewKind::Array => subtype
+                .type_mark()
+                .array_type()
+                .map(|(elem_type, _)| elem_type),
+        };
+
+        if typ.map(|typ| typ.base_type()) != Some(view.type_mark().base_type()) {
+            let expected = match kind {
+                ModeViewKind::Record => view.type_mark().describe(),
+                ModeViewKind::Array => format!("an array of {}", view.type_mark().describe()),
+            };
+            diagnostics.error(
+                &subtype_indication.type_mark.pos,
+                format!(
+                    "The subtype of a port with {} must be {}, got {}",
+                    view.describe(),
+                    expected,
+                    subtype.type_mark().describe()
+                ),
+            );
+        }
+
+        Ok((view, subtype))
+    }
+
+    /// LRM 6.10 Group declarations
+    fn analyze_group_declaration(
+        &self,
+        scope: &Scope<'a>,
+        group: &mut GroupDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let GroupDeclaration {
+            ident,
+            group_template_name,
+            group_constituent_list,
+        } = group;
+
+        let template = match self
+            .resolve_selected_name(scope, group_template_name)
+            .and_then(|entities| {
+                self.resolve_non_overloaded_with_kind(
+                    entities,
+                    group_template_name.suffix_pos(),
+                    &|kind| matches!(kind, AnyEntKind::GroupTemplate(..)),
+                    "group template",
+                )
+            }) {
+            Ok(template) => Some(template),
+            Err(err) => {
+                err.add_to(diagnostics)?;
+                None
+            }
+        };
+
+        let entries = if let Some(AnyEntKind::GroupTemplate(entries)) =
+            template.map(|template| template.actual_kind())
+        {
+            Some(entries.as_slice())
+        } else {
+            None
+        };
+
+        for (idx, constituent) in group_constituent_list.iter_mut().enumerate() {
+            let Some(named) = as_fatal(self.resolve_name(
+                scope,
+                &constituent.pos,
+                &mut constituent.item,
+                diagnostics,
+            ))?
+            else {
+                continue;
+            };
+
+            let Some(entry) = entries.and_then(|entries| entity_class_entry_at(entries, idx))
+            else {
+                continue;
+            };
+
+            let (is_of_class, describe) = match named {
+                NamedEntities::Single(ent) => {
+                    (is_of_entity_class(ent, entry.entity_class), ent.describe())
+                }
+                NamedEntities::Overloaded(overloaded) => (
+                    overloaded
+                        .entities()
+                        .any(|ent| is_of_entity_class(ent.into(), entry.entity_class)),
+                    format!("'{}'", overloaded.designator()),
+                ),
+            };
+
+            if !is_of_class {
+                diagnostics.error(
+                    &constituent.pos,
+                    format!(
+                        "{} does not belong to entity class '{}' of the group template",
+                        describe, entry.entity_class
+                    ),
+                );
+            }
+        }
+
+        if let (Some(template), Some(entries)) = (template, entries) {
+            let has_box = entries.last().is_some_and(|entry| entry.is_box);
+            let min_count = if has_box {
+                entries.len() - 1
+            } else {
+                entries.len()
+            };
+            let count = group_constituent_list.len();
+
+            if count < min_count || (!has_box && count > min_count) {
+                diagnostics.error(
+                    &group_template_name.pos,
+                    format!(
+                        "{} expects {}{} constituent{}, got {}",
+                        template.describe(),
+                        if has_box { "at least " } else { "" },
+                        min_count,
+                        if min_count == 1 { "" } else { "s" },
+                        count
+                    ),
+                );
+            }
+        }
+
+        scope.add(self.arena.define(ident, AnyEntKind::Group), diagnostics);
+        Ok(())
+    }
+
+    /// Associate the value of a user defined attribute with a named entity
+    fn add_attribute_value(
+        &self,
+        ent: EntRef<'a>,
+        attribute: EntRef<'a>,
+        pos: &SrcPos,
+        value_pos: &SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        // Entities of other design units are not attributed here
+        if !self.arena.is_local(ent.id()) {
+            return;
+        }
+
+        if let Some(prev_pos) = ent.attribute_value(attribute) {
+            diagnostics.push(
+                Diagnostic::error(
+                    pos,
+                    format!(
+                        "Duplicate specification of attribute '{}' for {}",
+                        attribute.designator(),
+                        ent.describe()
+                    ),
+                )
+                .related(prev_pos, "Previously specified here"),
+            );
+        } else {
+            unsafe {
+                self.arena
+                    .add_attribute(ent.id(), attribute, value_pos.clone());
+            }
+        }
+    }
+
+    fn analyze_configuration_specification(
+        &self,
+        scope: &Scope<'a>,
+        config: &mut ConfigurationSpecification,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let ConfigurationSpecification { spec, bind_ind, .. } = config;
+
+        let component_region = match self.resolve_component_name(scope, &mut spec.component_name) {
+            Ok(region) => Some(region),
+            Err(err) => {
+                err.add_to(diagnostics)?;
+                None
+            }
+        };
+
+        let entity = match bind_ind.entity_aspect {
+            Some(EntityAspect::Entity(ref mut entity_name, ref architecture_name)) => {
+                match self.resolve_bound_entity(scope, entity_name, architecture_name.as_ref()) {
+                    Ok(entity_region) => Some((entity_name.pos.clone(), entity_region)),
+                    Err(err) => {
+                        err.add_to(diagnostics)?;
+                        None
+                    }
+                }
+            }
+            Some(EntityAspect::Configuration(ref mut config_name)) => {
+                fn is_configuration(kind: &AnyEntKind) -> bool {
+                    matches!(kind, AnyEntKind::Design(Design::Configuration(..)))
+                }
+
+                if let Err(err) =
+                    self.resolve_selected_name(scope, config_name)
+                        .and_then(|entities| {
+                            self.resolve_non_overloaded_with_kind(
+                                entities,
+                                config_name.suffix_pos(),
+                                &is_configuration,
+                                "configuration",
+                            )
+                        })
+                {
+                    err.add_to(diagnostics)?;
+                }
+                None
+            }
+            Some(EntityAspect::Open) | None => None,
+        };
+
+        // The local generics and ports of the component are visible within the binding indication
+        let local_scope = component_region.map(|region| Scope::extend(region, Some(scope)));
+        let actual_scope = local_scope.as_ref().unwrap_or(scope);
+
+        if let Some((entity_pos, entity_region)) = entity {
+            let (generic_region, port_region) = entity_region.to_entity_formal();
+            let local_regions = component_region.map(|region| region.to_entity_formal());
+
+            if let Some(ref mut generic_map) = bind_ind.generic_map {
+                self.analyze_assoc_elems_with_formal_region(
+                    &entity_pos,
+                    &generic_region,
+                    actual_scope,
+                    generic_map,
+                    diagnostics,
+                )?;
+            } else if let Some((ref local_generics, _)) = local_regions {
+                self.check_default_binding(
+                    &entity_pos,
+                    local_generics,
+                    &generic_region,
+                    diagnostics,
+                );
+            }
+
+            if let Some(ref mut port_map) = bind_ind.port_map {
+                self.analyze_assoc_elems_with_formal_region(
+                    &entity_pos,
+                    &port_region,
+                    actual_scope,
+                    port_map,
+                    diagnostics,
+                )?;
+            } else if let Some((_, ref local_ports)) = local_regions {
+                self.check_default_binding(&entity_pos, local_ports, &port_region, diagnostics);
+            }
+        } else {
+            if let Some(ref mut generic_map) = bind_ind.generic_map {
+                self.analyze_assoc_elems(actual_scope, generic_map, diagnostics)?;
+            }
+            if let Some(ref mut port_map) = bind_ind.port_map {
+                self.analyze_assoc_elems(actual_scope, port_map, diagnostics)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve_component_name(
+        &self,
+        scope: &Scope<'a>,
+        component_name: &mut WithPos<SelectedName>,
+    ) -> AnalysisResult<&'a Region<'a>> {
+        let expected = "component";
+        let entities = self.resolve_selected_name(scope, component_name)?;
+        let ent = self.resolve_non_overloaded(entities, component_name.suffix_pos(), expected)?;
+
+        if let AnyEntKind::Component(region) = ent.kind() {
+            Ok(region)
+        } else {
+            Err(AnalysisError::NotFatal(
+                ent.kind_error(component_name.suffix_pos(), expected),
+            ))
+        }
+    }
+
+    /// Resolve the entity and optional architecture of an entity aspect
+    fn resolve_bound_entity(
+        &self,
+        scope: &Scope<'a>,
+        entity_name: &mut WithPos<SelectedName>,
+        architecture_name: Option<&Ident>,
+    ) -> AnalysisResult<&'a Region<'a>> {
+        let expected = "entity";
+        let entities = self.resolve_selected_name(scope, entity_name)?;
+        let ent = self.resolve_non_overloaded(entities, entity_name.suffix_pos(), expected)?;
+
+        let region = if let AnyEntKind::Design(Design::Entity(_, region)) = ent.kind() {
+            region
+        } else {
+            return Err(AnalysisError::NotFatal(
+                ent.kind_error(entity_name.suffix_pos(), expected),
+            ));
+        };
+
+        if let Some(architecture_name) = architecture_name {
+            // The library is only known when the entity name is selected from a library
+            if let SelectedName::Selected(ref prefix, _) = entity_name.item {
+                if let SelectedName::Designator(ref library) = prefix.item {
+                    let library_ent = library
+                        .reference
+                        .map(|id| self.arena.get(id))
+                        .filter(|ent| matches!(ent.kind(), AnyEntKind::Library));
+                    if let Some(library_ent) = library_ent {
+                        let library_name = library_ent.designator().expect_identifier();
+                        let entity_sym = ent.designator().expect_identifier();
+                        if !self.has_architecture(library_name, entity_sym, &architecture_name.item)
+                        {
+                            return Err(AnalysisError::not_fatal_error(
+                                architecture_name,
+                                format!(
+                                    "No architecture '{}' for {}",
+                                    architecture_name.item,
+                                    ent.describe()
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(region)
+    }
+
+    /// Check the implicit association of formals to locals with the same name
+    /// when a binding indication has no generic or port map
+    pub(crate) fn check_default_binding(
+        &self,
+        pos: &SrcPos,
+        locals: &FormalRegion<'a>,
+        formals: &FormalRegion<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for local in locals.iter() {
+            if let Ok((_, formal)) = formals.lookup(pos, local.designator()) {
+                if local.base_type() != formal.base_type() {
+                    let mut diagnostic = Diagnostic::error(
+                        pos,
+                        format!(
+                            "{} of type {} does not match {} of type {}",
+                            capitalize(&local.describe()),
+                            local.type_mark().designator(),
+                            formal.describe(),
+                            formal.type_mark().designator()
+                        ),
+                    );
+                    if let Some(decl_pos) = formal.decl_pos() {
+                        diagnostic.add_related(decl_pos, "Defined here");
+                    }
+                    diagnostics.push(diagnostic);
+                } else if !is_mode_compatible(local.inner(), formal.inner()) {
+                    let mut diagnostic = Diagnostic::error(
+                        pos,
+                        format!(
+                            "{} cannot be associated with {}",
+                            capitalize(&local.describe()),
+                            formal.describe()
+                        ),
+                    );
+                    if let Some(decl_pos) = formal.decl_pos() {
+                        diagnostic.add_related(decl_pos, "Defined here");
+                    }
+                    diagnostics.push(diagnostic);
+                }
+            } else {
+                let mut diagnostic = Diagnostic::error(
+                    pos,
+                    format!("No formal matching {} in default binding", local.describe()),
+                );
+                if let Some(decl_pos) = local.decl_pos() {
+                    diagnostic.add_related(decl_pos, "Defined here");
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        for formal in formals.iter() {
+            if locals.lookup(pos, formal.designator()).is_err()
+                && !formal.has_default()
+                && !(formals.typ == InterfaceListType::Port && formal.is_output_signal())
+            {
+                let mut diagnostic =
+                    Diagnostic::error(pos, format!("No association of {}", formal.describe()));
+                if let Some(decl_pos) = formal.decl_pos() {
+                    diagnostic.add_related(decl_pos, "Defined here");
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
     pub(crate) fn analyze_type_declaration(
         &self,
         scope: &Scope<'a>,
@@ -550,13 +1354,17 @@ impl<'a> AnalyzeContext<'a> {
                     Some(enum_type),
                 );
 
-                for literal in enumeration.iter_mut() {
+                for (position, literal) in enumeration.iter_mut().enumerate() {
                     let literal_ent = self.arena.explicit(
                         literal.tree.item.clone().into_designator(),
                         AnyEntKind::Overloaded(Overloaded::EnumLiteral(signature.clone())),
                         Some(&literal.tree.pos),
                     );
                     literal.decl = Some(literal_ent.id());
+                    self.set_static_property(
+                        literal_ent,
+                        Some(StaticProperty::Value(StaticValue::Enum(position as i64))),
+                    );
 
                     unsafe {
                         self.arena.add_implicit(enum_type.id(), literal_ent);
@@ -565,6 +1373,17 @@ impl<'a> AnalyzeContext<'a> {
                     scope.add(literal_ent, diagnostics);
                 }
 
+                if !enumeration.is_empty() {
+                    self.set_static_property(
+                        enum_type.into(),
+                        Some(StaticProperty::Ranges(vec![StaticRange::new(
+                            StaticValue::Enum(0),
+                            StaticValue::Enum(enumeration.len() as i64 - 1),
+                            Direction::Ascending,
+                        )])),
+                    );
+                }
+
                 scope.add(enum_type.into(), diagnostics);
 
                 for ent in self.enum_implicits(enum_type) {
@@ -698,6 +1517,11 @@ impl<'a> AnalyzeContext<'a> {
                                 &mut elem_decl.ident,
                                 AnyEntKind::ElementDeclaration(subtype),
                             );
+                            self.set_static_property(
+                                elem,
+                                self.subtype_indication_ranges(&subtype, &elem_decl.subtype)
+                                    .map(StaticProperty::Ranges),
+                            );
                             region.add(elem, diagnostics);
                             elems.add(elem);
                         }
@@ -728,6 +1552,16 @@ impl<'a> AnalyzeContext<'a> {
                     self.resolve_subtype_indication(scope, subtype_indication, diagnostics);
                 match subtype {
                     Ok(subtype) => {
+                        // Access types designating protected types are allowed since VHDL-2019
+                        if self.root.standard() < VHDLStandard::VHDL2019 {
+                            self.check_not_protected(
+                                "An access type cannot designate",
+                                &subtype_indication.type_mark.pos,
+                                subtype.base_type(),
+                                diagnostics,
+                            );
+                        }
+
                         let type_ent = TypeEnt::define_with_opt_id(
                             self.arena,
                             overwrite_id,
@@ -773,6 +1607,22 @@ impl<'a> AnalyzeContext<'a> {
                     Type::Array { indexes, elem_type },
                 );
 
+                let evaluator = self.static_evaluator();
+                let index_ranges: Option<Vec<_>> = array_indexes
+                    .iter()
+                    .zip(array_ent.array_type().map(|(_, indexes)| indexes).unwrap())
+                    .map(|(index, index_typ)| match index {
+                        ArrayIndex::Discrete(range) => {
+                            evaluator.eval_discrete_range(index_typ.map(TypeEnt::from), range)
+                        }
+                        ArrayIndex::IndexSubtypeDefintion(..) => None,
+                    })
+                    .collect();
+                self.set_static_property(
+                    array_ent.into(),
+                    index_ranges.map(StaticProperty::Ranges),
+                );
+
                 scope.add(array_ent.into(), diagnostics);
 
                 for ent in self.array_implicits(array_ent) {
@@ -785,12 +1635,20 @@ impl<'a> AnalyzeContext<'a> {
             TypeDefinition::Subtype(ref mut subtype_indication) => {
                 match self.resolve_subtype_indication(scope, subtype_indication, diagnostics) {
                     Ok(subtype) => {
+                        if let Some(ref constraint) = subtype_indication.constraint {
+                            self.check_null_constraint(&constraint.item, diagnostics);
+                        }
                         let type_ent = TypeEnt::define_with_opt_id(
                             self.arena,
                             overwrite_id,
                             &mut type_decl.ident,
                             Type::Subtype(subtype),
                         );
+                        self.set_static_property(
+                            type_ent.into(),
+                            self.subtype_indication_ranges(&subtype, subtype_indication)
+                                .map(StaticProperty::Ranges),
+                        );
                         scope.add(type_ent.into(), diagnostics);
                     }
                     Err(err) => {
@@ -812,12 +1670,23 @@ impl<'a> AnalyzeContext<'a> {
                     &mut type_decl.ident,
                     Type::Physical,
                 );
+                let evaluator = self.static_evaluator();
+                self.set_static_property(
+                    phys_type.into(),
+                    evaluator.eval_range(None, &physical.range).map(|range| {
+                        StaticProperty::Ranges(vec![evaluator.as_type_range(phys_type, range)])
+                    }),
+                );
                 scope.add(phys_type.into(), diagnostics);
 
                 let primary = self.arena.define(
                     &mut physical.primary_unit,
                     AnyEntKind::PhysicalLiteral(phys_type),
                 );
+                self.set_static_property(
+                    primary,
+                    Some(StaticProperty::Value(StaticValue::Physical(1))),
+                );
 
                 unsafe {
                     self.arena.add_implicit(phys_type.id(), primary);
@@ -841,9 +1710,28 @@ impl<'a> AnalyzeContext<'a> {
                         Err(err) => diagnostics.push(err),
                     }
 
+                    // LRM 5.2.4.1 A secondary unit is an integral multiple of a previously declared unit
+                    if !matches!(value.value, AbstractLiteral::Integer(multiplier) if multiplier > 0)
+                    {
+                        diagnostics.error(
+                            &secondary_unit_name.tree.pos,
+                            format!(
+                                "Secondary unit '{}' must be a positive integer multiple of a previously declared unit, got {}",
+                                secondary_unit_name.tree.item, value
+                            ),
+                        );
+                    }
+
+                    let unit_value = self.physical_literal_value(
+                        phys_type,
+                        &secondary_unit_name.tree.pos,
+                        value,
+                        diagnostics,
+                    );
                     let secondary_unit = self
                         .arena
                         .define(secondary_unit_name, AnyEntKind::PhysicalLiteral(phys_type));
+                    self.set_static_property(secondary_unit, unit_value.map(StaticProperty::Value));
                     unsafe {
                         self.arena.add_implicit(phys_type.id(), secondary_unit);
                     }
@@ -888,6 +1776,12 @@ impl<'a> AnalyzeContext<'a> {
                         UniversalType::Real => Type::Real,
                     },
                 );
+                self.set_static_property(
+                    type_ent.into(),
+                    self.static_evaluator()
+                        .eval_range(None, range)
+                        .map(|range| StaticProperty::Ranges(vec![range])),
+                );
                 scope.add(type_ent.into(), diagnostics);
 
                 for ent in self.numeric_implicits(universal_type, type_ent) {
@@ -906,8 +1800,15 @@ impl<'a> AnalyzeContext<'a> {
                     Type::File,
                 );
 
+                let type_mark_pos = type_mark.pos.clone();
                 match self.resolve_type_mark(scope, type_mark) {
                     Ok(type_mark) => {
+                        self.check_not_protected(
+                            "A file type cannot contain values of",
+                            &type_mark_pos,
+                            type_mark.base_type(),
+                            diagnostics,
+                        );
                         for ent in self.create_implicit_file_type_subprograms(file_type, type_mark)
                         {
                             unsafe {
@@ -1000,21 +1901,98 @@ impl<'a> AnalyzeContext<'a> {
                             &mut expression.item,
                             diagnostics,
                         )?;
+                        let ranges = self
+                            .subtype_indication_ranges(subtype, &object_decl.subtype_indication);
+                        self.check_static_default(
+                            subtype.type_mark(),
+                            ranges.as_deref(),
+                            expression,
+                            diagnostics,
+                        );
+                        if let Some([range]) = ranges.as_deref() {
+                            self.check_aggregate_length(
+                                scope,
+                                subtype.type_mark(),
+                                range,
+                                expression,
+                                diagnostics,
+                            );
+                        }
                     } else {
                         self.expr_unknown_ttyp(scope, expression, diagnostics)?
                     }
                 }
 
                 let subtype = subtype?;
-                self.arena.define(
+                self.check_protected_object(
+                    object_decl.class,
+                    &object_decl.ident.tree,
+                    &subtype,
+                    diagnostics,
+                );
+                let static_default = if object_decl.class == ObjectClass::Constant {
+                    object_decl
+                        .expression
+                        .as_ref()
+                        .and_then(|expr| self.eval_static(Some(subtype.type_mark()), &expr.item))
+                } else {
+                    None
+                };
+                let ranges =
+                    self.subtype_indication_ranges(&subtype, &object_decl.subtype_indication);
+                let constraint = object_decl.subtype_indication.constraint.as_ref();
+                let default = object_decl.expression.as_ref();
+                // The constraint or default of a port may depend on generics
+                let depends_on_generics = object_decl.class == ObjectClass::Signal
+                    && ((constraint.is_some() && ranges.is_none())
+                        || default.is_some_and(|expr| {
+                            self.eval_static(Some(subtype.type_mark()), &expr.item)
+                                .is_none()
+                        }));
+
+                let static_property = if let Some(value) = static_default {
+                    Some(StaticProperty::Default(value))
+                } else if depends_on_generics {
+                    Some(StaticProperty::Generic {
+                        constraint: constraint.map(|constraint| Box::new(constraint.item.clone())),
+                        default: default.map(|expr| Box::new(expr.clone())),
+                    })
+                } else {
+                    ranges.map(StaticProperty::Ranges)
+                };
+                let ent = self.arena.define(
                     &mut object_decl.ident,
                     AnyEntKind::Object(Object {
                         class: object_decl.class,
                         mode: Some(object_decl.mode),
                         subtype,
                         has_default: object_decl.expression.is_some(),
+                        view: None,
                     }),
-                )
+                );
+                self.set_static_property(ent, static_property);
+                ent
+            }
+            InterfaceDeclaration::View(ref mut view_decl) => {
+                let (view, subtype) =
+                    self.analyze_mode_view_indication(scope, &mut view_decl.view, diagnostics)?;
+                let ranges = view_decl
+                    .view
+                    .subtype_indication
+                    .as_ref()
+                    .and_then(|indication| self.subtype_indication_ranges(&subtype, indication));
+                let ent = self.arena.define(
+                    &mut view_decl.ident,
+                    AnyEntKind::Object(Object {
+                        class: ObjectClass::Signal,
+                        mode: Some(view.mode()),
+                        subtype,
+                        has_default: false,
+                        view: Some(view),
+                    }),
+                );
+                self.set_static_property(ent, ranges.map(StaticProperty::Ranges));
+                ent
             }
             InterfaceDeclaration::Type(ref mut ident) => {
                 let typ =
@@ -1036,16 +2014,22 @@ impl<'a> AnalyzeContext<'a> {
 
                 typ.into()
             }
-            InterfaceDeclaration::Subprogram(ref mut subpgm, ..) => {
+            InterfaceDeclaration::Subprogram(ref mut subpgm, ref default) => {
                 let subpgm_region = scope.nested();
                 let signature =
                     self.analyze_subprogram_declaration(&subpgm_region, subpgm, diagnostics);
                 subpgm_region.close(diagnostics);
                 drop(subpgm_region);
 
+                let default = match default {
+                    None => SubprogramDefaultKind::None,
+                    Some(SubprogramDefault::Name(_)) => SubprogramDefaultKind::Name,
+                    Some(SubprogramDefault::Box) => SubprogramDefaultKind::Box,
+                };
+
                 subpgm.define(
                     self.arena,
-                    AnyEntKind::Overloaded(Overloaded::InterfaceSubprogram(signature?)),
+                    AnyEntKind::Overloaded(Overloaded::InterfaceSubprogram(signature?, default)),
                 )
             }
             InterfaceDeclaration::Package(ref mut instance) => {
@@ -1102,6 +2086,114 @@ impl<'a> AnalyzeContext<'a> {
         Ok(params)
     }
 
+    /// LRM 5.6.1 Only variables, including shared variables and variable parameters,
+    /// may be of a protected type
+    fn check_protected_object(
+        &self,
+        class: ObjectClass,
+        ident: &Ident,
+        subtype: &Subtype,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if matches!(class, ObjectClass::Variable | ObjectClass::SharedVariable) {
+            return;
+        }
+
+        let typ = subtype.base_type();
+        if matches!(typ.kind(), Type::Protected(..)) {
+            diagnostics.error(
+                &ident.pos,
+                format!(
+                    "{} '{}' cannot be of {}, only variables may be of a protected type",
+                    capitalize(&class.to_string()),
+                    ident.item,
+                    typ.describe()
+                ),
+            );
+        }
+    }
+
+    /// LRM 5.4.1 and 5.5 The designated type of an access type and the type of the values
+    /// of a file type may not be a protected type
+    fn check_not_protected(
+        &self,
+        restriction: &str,
+        pos: &SrcPos,
+        typ: TypeEnt,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if matches!(typ.kind(), Type::Protected(..)) {
+            diagnostics.error(pos, format!("{restriction} {}", typ.describe()));
+        }
+    }
+
+    /// LRM 6.4.2.4 Since VHDL-2002 shared variables must be of a protected type.
+    /// VHDL-1993 allows shared variables of any type.
+    fn check_shared_variable_type(
+        &self,
+        ident: &Ident,
+        subtype: &Subtype,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.root.standard() < VHDLStandard::VHDL2002 {
+            return;
+        }
+
+        // The actual of an interface type may be a protected type
+        if !matches!(
+            subtype.base_type().kind(),
+            Type::Protected(..) | Type::Interface
+        ) {
+            diagnostics.push(
+                Diagnostic::error(
+                    &ident.pos,
+                    format!(
+                        "Shared variable '{}' must be of a protected type, got {}",
+                        ident.item,
+                        subtype.type_mark().describe()
+                    ),
+                )
+                .with_code(ErrorCode::SharedVariableType),
+            );
+        }
+    }
+
+    /// LRM 4.2.2.1 The parameters of a function are of mode in and of class constant, signal
+    /// or file. Since VHDL-2019 impure functions may have parameters of any mode and class.
+    fn check_function_parameters(
+        &self,
+        fun: &FunctionSpecification,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if !fun.pure && self.root.standard() >= VHDLStandard::VHDL2019 {
+            return;
+        }
+
+        for param in fun.parameter_list.iter() {
+            let InterfaceDeclaration::Object(ref object) = param else {
+                continue;
+            };
+            let ident = &object.ident.tree;
+            if object.mode != Mode::In {
+                diagnostics.error(
+                    &ident.pos,
+                    format!(
+                        "Parameter '{}' of function '{}' must be of mode in, got {}",
+                        ident.item, fun.designator.tree.item, object.mode
+                    ),
+                );
+            } else if object.class == ObjectClass::Variable {
+                diagnostics.error(
+                    &ident.pos,
+                    format!(
+                        "Parameter '{}' of function '{}' must be a constant, signal or file, got variable",
+                        ident.item, fun.designator.tree.item
+                    ),
+                );
+            }
+        }
+    }
+
     fn analyze_array_index(
         &self,
         scope: &Scope<'a>,
@@ -1118,7 +2210,15 @@ impl<'a> AnalyzeContext<'a> {
                     }
                 }
             }
-            ArrayIndex::Discrete(ref mut drange) => self.drange_type(scope, drange, diagnostics),
+            ArrayIndex::Discrete(ref mut drange) => {
+                let typ = self.drange_type(scope, drange, diagnostics)?;
+                // LRM 5.3.2.2 The index subtype of a range of universal_integer bounds is INTEGER
+                if typ == self.universal_integer() && self.root.standard_types.is_some() {
+                    Ok(self.integer().base())
+                } else {
+                    Ok(typ)
+                }
+            }
         }
     }
 
@@ -1239,12 +2339,13 @@ impl<'a> AnalyzeContext<'a> {
     ) -> AnalysisResult<Subtype<'a>> {
         // @TODO more
         let SubtypeIndication {
+            resolution,
             type_mark,
             constraint,
-            ..
         } = subtype_indication;
 
         let base_type = self.resolve_type_mark(scope, type_mark)?;
+        self.analyze_resolution_indication(scope, base_type, resolution, diagnostics)?;
 
         if let Some(constraint) = constraint {
             self.analyze_subtype_constraint(
@@ -1256,7 +2357,135 @@ impl<'a> AnalyzeContext<'a> {
             )?;
         }
 
-        Ok(Subtype::new(base_type))
+        let resolved = !matches!(resolution, ResolutionIndication::Unresolved);
+        Ok(Subtype::new(base_type).with_resolved(resolved))
+    }
+
+    /// Resolve the resolution functions of a subtype of the type and its elements
+    fn analyze_resolution_indication(
+        &self,
+        scope: &Scope<'a>,
+        typ: TypeEnt<'a>,
+        resolution: &mut ResolutionIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        match resolution {
+            ResolutionIndication::FunctionName(name) => {
+                if let Err(err) = self.resolve_resolution_function(scope, typ, name) {
+                    err.add_to(diagnostics)?;
+                }
+            }
+            ResolutionIndication::ArrayElement(resolution) => {
+                if let Some((elem_type, _)) = typ.array_type() {
+                    self.analyze_resolution_indication(scope, elem_type, resolution, diagnostics)?;
+                } else if let Some(pos) = resolution_pos(resolution) {
+                    diagnostics.error(
+                        pos,
+                        format!("Element resolution of non-array {}", typ.describe()),
+                    );
+                }
+            }
+            ResolutionIndication::Record(elements) => {
+                let Type::Record(region) = typ.base_type().kind() else {
+                    if let Some(pos) = resolution_pos(resolution) {
+                        diagnostics.error(
+                            pos,
+                            format!("Record element resolution of non-record {}", typ.describe()),
+                        );
+                    }
+                    return Ok(());
+                };
+
+                for element in elements.iter_mut() {
+                    let designator = Designator::Identifier(element.ident.item.clone());
+                    if let Some(elem) = region.lookup(&designator) {
+                        self.analyze_resolution_indication(
+                            scope,
+                            elem.type_mark(),
+                            &mut element.resolution,
+                            diagnostics,
+                        )?;
+                    } else {
+                        diagnostics.push(Diagnostic::no_declaration_within(
+                            &typ,
+                            &element.ident.pos,
+                            &designator,
+                        ));
+                    }
+                }
+            }
+            ResolutionIndication::Unresolved => {}
+        }
+        Ok(())
+    }
+
+    /// Resolve the function with a single parameter of an unconstrained one-dimensional array
+    /// of the type which returns the type
+    fn resolve_resolution_function(
+        &self,
+        scope: &Scope<'a>,
+        typ: TypeEnt<'a>,
+        name: &mut WithPos<SelectedName>,
+    ) -> AnalysisResult<()> {
+        let overloaded = match self.resolve_selected_name(scope, name)? {
+            NamedEntities::Overloaded(overloaded) => overloaded,
+            NamedEntities::Single(ent) => {
+                return Err(Diagnostic::error(
+                    &name.pos,
+                    format!("{} cannot be used as a resolution function", ent.describe()),
+                )
+                .into());
+            }
+        };
+
+        let is_resolution_function = |ent: &OverloadedEnt<'a>| {
+            if !ent.signature().match_return_type(Some(typ)) {
+                return false;
+            }
+            let mut formals = ent.formals().iter();
+            let (Some(formal), None) = (formals.next(), formals.next()) else {
+                return false;
+            };
+            let formal_type = formal.type_mark();
+            match formal_type.array_type() {
+                Some((elem_type, indexes)) => {
+                    elem_type.base_type() == typ.base_type()
+                        && indexes.len() == 1
+                        && formal_type.static_ranges().is_none()
+                }
+                None => false,
+            }
+        };
+
+        let candidates: Vec<_> = overloaded
+            .entities()
+            .filter(|ent| is_resolution_function(ent))
+            .collect();
+
+        match candidates.as_slice() {
+            [ent] => {
+                name.set_unique_reference(ent);
+                Ok(())
+            }
+            [] => Err(Diagnostic::error(
+                &name.pos,
+                format!(
+                    "No resolution function '{}' for {}",
+                    overloaded.designator(),
+                    typ.describe()
+                ),
+            )
+            .into()),
+            _ => Err(Diagnostic::error(
+                &name.pos,
+                format!(
+                    "Ambiguous resolution function '{}' for {}",
+                    overloaded.designator(),
+                    typ.describe()
+                ),
+            )
+            .into()),
+        }
     }
 
     pub fn analyze_subtype_indication(
@@ -1271,6 +2500,37 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    /// LRM 4.2.1 The generics of an uninstantiated subprogram,
+    /// None if the subprogram has no generic clause
+    fn analyze_subprogram_header(
+        &self,
+        scope: &Scope<'a>,
+        subprogram: &mut SubprogramDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<GpkgRegion<'a>>> {
+        let header = match subprogram {
+            SubprogramDeclaration::Function(fun) => fun.header.as_mut(),
+            SubprogramDeclaration::Procedure(procedure) => procedure.header.as_mut(),
+        };
+        let Some(header) = header else {
+            return Ok(None);
+        };
+
+        let mut generics = Vec::with_capacity(header.generic_list.len());
+        for decl in header.generic_list.iter_mut() {
+            match self.analyze_interface_declaration(scope, decl, diagnostics) {
+                Ok(ent) => {
+                    scope.add(ent, diagnostics);
+                    generics.extend(GpkgInterfaceEnt::from_any(ent));
+                }
+                Err(err) => {
+                    err.add_to(diagnostics)?;
+                }
+            }
+        }
+        Ok(Some(GpkgRegion::new(generics)))
+    }
+
     fn analyze_subprogram_declaration(
         &self,
         scope: &Scope<'a>,
@@ -1281,8 +2541,9 @@ impl<'a> AnalyzeContext<'a> {
             SubprogramDeclaration::Function(fun) => {
                 let params =
                     self.analyze_parameter_list(scope, &mut fun.parameter_list, diagnostics);
+                self.check_function_parameters(fun, diagnostics);
                 let return_type = self.resolve_type_mark(scope, &mut fun.return_type);
-                Ok(Signature::new(params?, Some(return_type?)))
+                Ok(Signature::new(params?, Some(return_type?)).with_impure(!fun.pure))
             }
             SubprogramDeclaration::Procedure(procedure) => {
                 let params =
@@ -1293,6 +2554,124 @@ impl<'a> AnalyzeContext<'a> {
     }
 }
 
+/// The position of the first name within a resolution indication
+fn resolution_pos(resolution: &ResolutionIndication) -> Option<&SrcPos> {
+    match resolution {
+        ResolutionIndication::FunctionName(name) => Some(&name.pos),
+        ResolutionIndication::ArrayElement(resolution) => resolution_pos(resolution),
+        ResolutionIndication::Record(elements) => {
+            elements.first().map(|element| &element.ident.pos)
+        }
+        ResolutionIndication::Unresolved => None,
+    }
+}
+
+/// The entity class entry of a group template that applies to the constituent at the given index
+fn entity_class_entry_at(entries: &[EntityClassEntry], idx: usize) -> Option<&EntityClassEntry> {
+    match entries.get(idx) {
+        Some(entry) => Some(entry),
+        None => entries.last().filter(|entry| entry.is_box),
+    }
+}
+
+/// Returns true if the named entity belongs to the entity class of an attribute specification
+/// or a group template
+fn is_of_entity_class(ent: &AnyEnt, class: EntityClass) -> bool {
+    match class {
+        EntityClass::Signal => matches!(
+            ent.kind(),
+            AnyEntKind::Object(Object {
+                class: ObjectClass::Signal,
+                ..
+            })
+        ),
+        EntityClass::Variable => matches!(
+            ent.kind(),
+            AnyEntKind::Object(Object {
+                class: ObjectClass::Variable | ObjectClass::SharedVariable,
+                ..
+            })
+        ),
+        EntityClass::Constant => matches!(
+            ent.kind(),
+            AnyEntKind::Object(Object {
+                class: ObjectClass::Constant,
+                ..
+            }) | AnyEntKind::DeferredConstant(..)
+        ),
+        EntityClass::Type => matches!(
+            ent.kind(),
+            AnyEntKind::Type(typ) if !matches!(typ, Type::Subtype(..) | Type::Alias(..))
+        ),
+        EntityClass::Subtype => matches!(ent.kind(), AnyEntKind::Type(Type::Subtype(..))),
+        EntityClass::Procedure | EntityClass::Function => {
+            if ent.is_subprogram()
+                || ent.is_subprogram_decl()
+                || ent.is_uninst_subprogram()
+                || ent.is_uninst_subprogram_decl()
+            {
+                let is_function = ent
+                    .signature()
+                    .map(|signature| signature.return_type().is_some())
+                    .unwrap_or(false);
+                is_function == (class == EntityClass::Function)
+            } else {
+                false
+            }
+        }
+        EntityClass::Component => matches!(ent.kind(), AnyEntKind::Component(..)),
+        EntityClass::Label => matches!(ent.kind(), AnyEntKind::Label),
+        EntityClass::Literal => matches!(
+            ent.kind(),
+            AnyEntKind::Overloaded(Overloaded::EnumLiteral(..))
+        ),
+        EntityClass::Units => matches!(ent.kind(), AnyEntKind::PhysicalLiteral(..)),
+        EntityClass::Group => matches!(ent.kind(), AnyEntKind::Group),
+        EntityClass::File => matches!(
+            ent.kind(),
+            AnyEntKind::File(..) | AnyEntKind::InterfaceFile(..)
+        ),
+        EntityClass::Entity => matches!(ent.kind(), AnyEntKind::Design(Design::Entity(..))),
+        EntityClass::Configuration => {
+            matches!(ent.kind(), AnyEntKind::Design(Design::Configuration(..)))
+        }
+        EntityClass::Package => matches!(
+            ent.kind(),
+            AnyEntKind::Design(
+                Design::Package(..) | Design::UninstPackage(..) | Design::PackageInstance(..)
+            )
+        ),
+        // Architectures are not named entities that can be referenced by name
+        EntityClass::Architecture => false,
+    }
+}
+
+/// LRM 6.5.6.3 Port clauses
+/// A local of the given mode may be associated with a formal of the given mode
+fn is_mode_compatible(local: EntRef, formal: EntRef) -> bool {
+    let (local_mode, formal_mode) = match (local.kind(), formal.kind()) {
+        (
+            AnyEntKind::Object(Object {
+                mode: Some(local_mode),
+                ..
+            }),
+            AnyEntKind::Object(Object {
+                mode: Some(formal_mode),
+                ..
+            }),
+        ) => (*local_mode, *formal_mode),
+        _ => return true,
+    };
+
+    match formal_mode {
+        Mode::In => matches!(local_mode, Mode::In | Mode::InOut | Mode::Buffer),
+        Mode::Out => matches!(local_mode, Mode::Out | Mode::InOut | Mode::Buffer),
+        Mode::InOut => matches!(local_mode, Mode::InOut | Mode::Buffer),
+        Mode::Buffer => matches!(local_mode, Mode::Out | Mode::InOut | Mode::Buffer),
+        Mode::Linkage => true,
+    }
+}
+
 fn find_full_type_definition<'a>(
     name: &Symbol,
     decls: &'a [Declaration],
@@ -1314,8 +2693,60 @@ fn find_full_type_definition<'a>(
     None
 }
 
+/// LRM 4.5.2 The number of parameters of a function that overloads an operator
+fn operator_arity(op: Operator) -> &'static [usize] {
+    match op {
+        Operator::Abs | Operator::Not | Operator::QueQue => &[1],
+        // The logical operators are also unary reduction operators in VHDL-2008
+        Operator::Plus
+        | Operator::Minus
+        | Operator::And
+        | Operator::Or
+        | Operator::Nand
+        | Operator::Nor
+        | Operator::Xor
+        | Operator::Xnor => &[1, 2],
+        _ => &[2],
+    }
+}
+
+fn check_operator_alias(
+    designator: &WithPos<Designator>,
+    op: Operator,
+    ent: OverloadedEnt,
+) -> Result<(), Diagnostic> {
+    if !ent.is_function() {
+        return Err(Diagnostic::error(
+            designator,
+            format!(
+                "Alias of {} must denote a function, got procedure {}",
+                designator.item.describe(),
+                ent.describe()
+            ),
+        ));
+    }
+
+    let arity = operator_arity(op);
+    let num_formals = ent.formals().len();
+    if !arity.contains(&num_formals) {
+        let expected = match arity {
+            [1] => "one parameter",
+            [2] => "two parameters",
+            _ => "one or two parameters",
+        };
+        return Err(Diagnostic::error(
+            designator,
+            format!(
+                "Alias of {} must denote a function with {expected}, got {num_formals}",
+                designator.item.describe(),
+            ),
+        ));
+    }
+    Ok(())
+}
+
 impl Diagnostic {
-    fn no_overloaded_with_signature(
+    pub(super) fn no_overloaded_with_signature(
         pos: &SrcPos,
         des: &Designator,
         overloaded: &OverloadedName,
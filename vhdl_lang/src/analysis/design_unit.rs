@@ -67,6 +67,10 @@ impl<'a> AnalyzeContext<'a> {
         let region = primary_scope.into_region();
         let visibility = root_scope.into_visibility();
 
+        if !self.is_testbench(&unit.ident.tree.item, &region) {
+            self.check_synthesizable(&unit.decl, &unit.statements, diagnostics);
+        }
+
         self.redefine(
             id,
             &mut unit.ident,
@@ -85,7 +89,7 @@ impl<'a> AnalyzeContext<'a> {
         self.add_implicit_context_clause(&root_region)?;
         self.analyze_context_clause(&root_region, &mut unit.context_clause, diagnostics)?;
 
-        match self.lookup_entity_for_configuration(&root_region, unit) {
+        let entity = match self.lookup_entity_for_configuration(&root_region, unit) {
             Ok(named_entity) => {
                 if let Some(primary_pos) = named_entity.decl_pos() {
                     let secondary_pos = unit.pos();
@@ -102,14 +106,18 @@ impl<'a> AnalyzeContext<'a> {
                         ));
                     }
                 }
+                Some(named_entity)
             }
             Err(err) => {
                 err.add_to(diagnostics)?;
+                None
             }
         };
 
-        self.arena
-            .define(&mut unit.ident, AnyEntKind::Design(Design::Configuration));
+        self.arena.define(
+            &mut unit.ident,
+            AnyEntKind::Design(Design::Configuration(entity)),
+        );
 
         Ok(())
     }
@@ -133,6 +141,7 @@ impl<'a> AnalyzeContext<'a> {
             self.analyze_interface_list(&scope, list, diagnostics)?;
         }
         self.analyze_declarative_part(&scope, &mut unit.decl, diagnostics)?;
+        self.check_synthesizable(&unit.decl, &[], diagnostics);
 
         if !self.has_package_body() {
             scope.close(diagnostics);
@@ -241,7 +250,15 @@ impl<'a> AnalyzeContext<'a> {
 
         self.analyze_declarative_part(&scope, &mut unit.decl, diagnostics)?;
         self.analyze_concurrent_part(&scope, &mut unit.statements, diagnostics)?;
+        self.check_multiple_drivers(&unit.statements, diagnostics);
+        self.check_combinational_loops(&mut unit.statements, diagnostics);
+        if !self.is_testbench(&unit.entity_name.item.item, region) {
+            self.check_synthesizable(&unit.decl, &unit.statements, diagnostics);
+        }
+        self.check_unused_signals(&mut unit.decl, &mut unit.statements, diagnostics);
+        self.analyze_default_bindings(&unit.decl, &mut unit.statements, diagnostics)?;
         scope.close(diagnostics);
+        self.check_testbench_termination(unit, region, diagnostics)?;
         Ok(())
     }
 
@@ -286,6 +303,7 @@ impl<'a> AnalyzeContext<'a> {
         let scope = Scope::extend(region, Some(&root_scope));
 
         self.analyze_declarative_part(&scope, &mut unit.decl, diagnostics)?;
+        self.check_synthesizable(&unit.decl, &[], diagnostics);
         scope.close(diagnostics);
         Ok(())
     }
@@ -0,0 +1,238 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Detection of signals of unresolved types that have more than one driver.
+//! Each process and concurrent signal assignment of an architecture has its own drivers.
+
+use super::analyze::*;
+use super::named_entity::*;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+/// A signal or a part of it that is driven by a process or concurrent signal assignment
+struct Driver<'a> {
+    /// The index of the process or concurrent signal assignment
+    source: usize,
+    signal: ObjectEnt<'a>,
+    /// The formatted indexes, slices and record elements selected from the signal
+    part: Vec<String>,
+    pos: SrcPos,
+}
+
+impl<'a> Driver<'a> {
+    /// Two drivers overlap when one part contains the other
+    fn overlaps(&self, other: &Driver<'a>) -> bool {
+        self.part
+            .iter()
+            .zip(other.part.iter())
+            .all(|(part, other_part)| part == other_part)
+    }
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// Error on signals of unresolved types that are driven by more than one process
+    /// or concurrent signal assignment. Statements within generate statements are not considered
+    /// since whether they are elaborated depends on the generate conditions.
+    pub fn check_multiple_drivers(
+        &self,
+        statements: &[LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut drivers = Vec::new();
+        let mut sources = 0;
+        self.concurrent_drivers(statements, &mut sources, &mut drivers);
+
+        let mut by_signal: FnvHashMap<EntityId, Vec<&Driver<'a>>> = FnvHashMap::default();
+        let mut order = Vec::new();
+        for driver in drivers.iter() {
+            let id = driver.signal.id();
+            if !by_signal.contains_key(&id) {
+                order.push(id);
+            }
+            by_signal.entry(id).or_default().push(driver);
+        }
+
+        for id in order {
+            let drivers = &by_signal[&id];
+            let signal = drivers[0].signal;
+            if signal.object().subtype.is_resolved() {
+                continue;
+            }
+
+            let conflicting: Vec<_> = drivers
+                .iter()
+                .filter(|driver| {
+                    drivers
+                        .iter()
+                        .any(|other| other.source != driver.source && driver.overlaps(other))
+                })
+                .collect();
+
+            if let Some(second) = conflicting.get(1) {
+                let mut diagnostic = Diagnostic::error(
+                    &second.pos,
+                    format!(
+                        "{} of unresolved type '{}' has multiple drivers",
+                        capitalize(&signal.describe_name()),
+                        signal.type_mark().designator()
+                    ),
+                );
+                for driver in conflicting.iter() {
+                    diagnostic.add_related(&driver.pos, "Driven here");
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    fn concurrent_drivers(
+        &self,
+        statements: &[LabeledConcurrentStatement],
+        sources: &mut usize,
+        drivers: &mut Vec<Driver<'a>>,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                ConcurrentStatement::Process(ref process) => {
+                    let mut process_drivers = Vec::new();
+                    self.sequential_drivers(&process.statements, *sources, &mut process_drivers);
+                    drivers.extend(process_drivers);
+                    *sources += 1;
+                }
+                ConcurrentStatement::Assignment(ref assign) => {
+                    drivers.extend(self.target_driver(&assign.target, *sources));
+                    *sources += 1;
+                }
+                ConcurrentStatement::Block(ref block) => {
+                    self.concurrent_drivers(&block.statements, sources, drivers);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The drivers of the statements of a process. Assignments to the same part of a signal
+    /// within the process share a single driver.
+    fn sequential_drivers(
+        &self,
+        statements: &[LabeledSequentialStatement],
+        source: usize,
+        drivers: &mut Vec<Driver<'a>>,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    if let Some(driver) = self.target_driver(&assign.target, source) {
+                        if !drivers.iter().any(|other| {
+                            other.signal.id() == driver.signal.id() && other.part == driver.part
+                        }) {
+                            drivers.push(driver);
+                        }
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.sequential_drivers(&conditional.item, source, drivers);
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.sequential_drivers(else_item, source, drivers);
+                    }
+                }
+                SequentialStatement::Case(ref case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter() {
+                        self.sequential_drivers(&alternative.item, source, drivers);
+                    }
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    self.sequential_drivers(&loop_stmt.statements, source, drivers);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The driver of the target of a signal assignment.
+    /// Targets whose part is not known such as an element with a non-literal index are ignored.
+    fn target_driver(&self, target: &WithPos<Target>, source: usize) -> Option<Driver<'a>> {
+        let Target::Name(ref name) = target.item else {
+            return None;
+        };
+
+        let mut part = Vec::new();
+        let signal = self.driven_signal(name, &mut part)?;
+        if signal.class() != ObjectClass::Signal
+            || matches!(signal.ent.kind(), AnyEntKind::ObjectAlias { .. })
+        {
+            return None;
+        }
+
+        Some(Driver {
+            source,
+            signal,
+            part,
+            pos: target.pos.clone(),
+        })
+    }
+
+    fn driven_signal(&self, name: &Name, part: &mut Vec<String>) -> Option<ObjectEnt<'a>> {
+        match name {
+            Name::Designator(designator) => designator
+                .reference
+                .and_then(|id| ObjectEnt::from_any(self.arena.get(id))),
+            Name::Selected(prefix, suffix) => {
+                // An object selected from a package rather than an element of a record
+                if let Some(object) = suffix
+                    .item
+                    .reference
+                    .and_then(|id| ObjectEnt::from_any(self.arena.get(id)))
+                {
+                    return Some(object);
+                }
+                let signal = self.driven_signal(&prefix.item, part)?;
+                part.push(suffix.item.item.to_string());
+                Some(signal)
+            }
+            Name::Slice(prefix, drange) => {
+                let signal = self.driven_signal(&prefix.item, part)?;
+                match drange.as_ref() {
+                    DiscreteRange::Range(crate::ast::Range::Range(constraint))
+                        if is_literal(&constraint.left_expr.item)
+                            && is_literal(&constraint.right_expr.item) =>
+                    {
+                        part.push(drange.to_string());
+                        Some(signal)
+                    }
+                    _ => None,
+                }
+            }
+            Name::CallOrIndexed(fcall) => {
+                let signal = self.driven_signal(&fcall.name.item, part)?;
+                if fcall.parameters.iter().all(|param| {
+                    param.formal.is_none()
+                        && matches!(param.actual.item, ActualPart::Expression(ref expr) if is_literal(expr))
+                }) {
+                    part.push(
+                        fcall
+                            .parameters
+                            .iter()
+                            .map(|param| param.actual.item.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    );
+                    Some(signal)
+                } else {
+                    None
+                }
+            }
+            Name::SelectedAll(..) | Name::Attribute(..) | Name::External(..) => None,
+        }
+    }
+}
+
+fn is_literal(expr: &Expression) -> bool {
+    matches!(expr, Expression::Literal(_))
+}
@@ -0,0 +1,393 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Explains how the name at a position was resolved
+
+use super::analyze::*;
+use super::expression::ExpressionType;
+use super::named_entity::*;
+use super::region::*;
+use super::root::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+
+/// A named entity that was considered when resolving a name
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExplainedEntity {
+    pub description: String,
+    pub decl_pos: Option<SrcPos>,
+}
+
+/// A named entity made visible by use clauses
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UseClauseCandidate {
+    pub entity: ExplainedEntity,
+    /// The positions leading from the use clause to the context references that made it visible
+    pub visible_pos: Vec<SrcPos>,
+}
+
+/// A candidate of an overloaded name
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverloadCandidate {
+    pub entity: ExplainedEntity,
+    /// The reason the candidate was rejected or None if it remained a candidate
+    pub rejection: Option<String>,
+}
+
+/// How a name was resolved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolutionExplanation {
+    pub designator: Designator,
+    /// The named entities with the designator declared in each scope that was searched
+    /// from the innermost scope outwards. The search stops at the first scope declaring
+    /// a name that is not overloaded. Empty for selected names.
+    pub scopes: Vec<Vec<ExplainedEntity>>,
+    /// The named entities made visible by use clauses
+    pub use_clauses: Vec<UseClauseCandidate>,
+    /// The candidates when the name is overloaded
+    pub overloads: Vec<OverloadCandidate>,
+    /// The named entity the name resolved to if it is unique
+    pub resolved: Option<ExplainedEntity>,
+    /// The error when the name could not be looked up
+    pub error: Option<String>,
+}
+
+impl ExplainedEntity {
+    fn new(ent: EntRef) -> ExplainedEntity {
+        ExplainedEntity {
+            description: ent.describe(),
+            decl_pos: ent.decl_pos().cloned(),
+        }
+    }
+}
+
+impl Display for ExplainedEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.description)?;
+        if let Some(ref decl_pos) = self.decl_pos {
+            write!(f, " at {}", location(decl_pos))?;
+        }
+        Ok(())
+    }
+}
+
+fn location(pos: &SrcPos) -> String {
+    format!(
+        "{}:{}",
+        pos.file_name().to_string_lossy(),
+        pos.range().start.line + 1
+    )
+}
+
+impl Display for ResolutionExplanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Resolution of '{}'", self.designator)?;
+
+        if !self.scopes.is_empty() {
+            writeln!(f, "Searched scopes from the innermost outwards:")?;
+            for (idx, declared) in self.scopes.iter().enumerate() {
+                if declared.is_empty() {
+                    writeln!(f, "  {}: no declaration", idx + 1)?;
+                }
+                for ent in declared.iter() {
+                    writeln!(f, "  {}: {}", idx + 1, ent)?;
+                }
+            }
+        }
+
+        if !self.use_clauses.is_empty() {
+            writeln!(f, "Made visible by use clauses:")?;
+            for candidate in self.use_clauses.iter() {
+                write!(f, "  {}", candidate.entity)?;
+                if let Some(use_pos) = candidate.visible_pos.first() {
+                    write!(f, " by use clause at {}", location(use_pos))?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        if !self.overloads.is_empty() {
+            writeln!(f, "Overload candidates:")?;
+            for candidate in self.overloads.iter() {
+                match candidate.rejection {
+                    Some(ref rejection) => {
+                        writeln!(f, "  {}: rejected, {}", candidate.entity, rejection)?
+                    }
+                    None => writeln!(f, "  {}: accepted", candidate.entity)?,
+                }
+            }
+        }
+
+        if let Some(ref error) = self.error {
+            write!(f, "Error: {error}")
+        } else if let Some(ref resolved) = self.resolved {
+            write!(f, "Resolved to {resolved}")
+        } else if self.overloads.is_empty() {
+            write!(f, "Not resolved")
+        } else {
+            write!(f, "Ambiguous between the accepted candidates")
+        }
+    }
+}
+
+/// Captures how the name at a position is resolved while a design unit is analyzed.
+/// A name may be resolved several times such as when its type is first inferred and then checked,
+/// each candidate keeps the reason it was last rejected.
+pub(super) struct ResolutionTrace {
+    pos: SrcPos,
+    state: RefCell<Option<Traced>>,
+}
+
+struct Traced {
+    /// The position of the traced name
+    pos: SrcPos,
+    explanation: ResolutionExplanation,
+    /// The ids of the overload candidates of the explanation
+    ids: Vec<EntityId>,
+}
+
+impl ResolutionTrace {
+    pub fn new(pos: SrcPos) -> ResolutionTrace {
+        ResolutionTrace {
+            pos,
+            state: RefCell::new(None),
+        }
+    }
+
+    fn into_explanation(self) -> Option<ResolutionExplanation> {
+        self.state.into_inner().map(|traced| traced.explanation)
+    }
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// True if the resolution of the name at the position is traced
+    pub(super) fn explains(&self, pos: &SrcPos) -> bool {
+        let Some(ref trace) = self.trace else {
+            return false;
+        };
+        let cursor = trace.pos.start();
+        pos.source == trace.pos.source && pos.start() <= cursor && cursor <= pos.end()
+    }
+
+    /// Called when the designator of a name is looked up within a scope
+    pub(super) fn explain_lookup(
+        &self,
+        scope: &Scope<'a>,
+        pos: &SrcPos,
+        designator: &Designator,
+        result: &Result<NamedEntities<'a>, Diagnostic>,
+    ) {
+        if !self.explains(pos) {
+            return;
+        }
+
+        let trace = self.trace.as_ref().unwrap();
+        if trace
+            .state
+            .borrow()
+            .as_ref()
+            .is_some_and(|traced| traced.pos == *pos)
+        {
+            // Looked up again when the name is analyzed once more
+            return;
+        }
+
+        let (searched, visible) = scope.searched_scopes(designator);
+        let mut use_clauses: Vec<_> = visible
+            .into_iter()
+            .map(|(ent, visible_pos)| UseClauseCandidate {
+                entity: ExplainedEntity::new(ent),
+                visible_pos: visible_pos.into_iter().flatten().collect(),
+            })
+            .collect();
+        use_clauses.sort_by(|a, b| a.entity.decl_pos.cmp(&b.entity.decl_pos));
+
+        let (candidates, resolved, error) = match result {
+            Ok(NamedEntities::Single(ent)) => (Vec::new(), Some(ExplainedEntity::new(ent)), None),
+            Ok(NamedEntities::Overloaded(overloaded)) => (overloaded.sorted_entities(), None, None),
+            Err(err) => (Vec::new(), None, Some(err.message.clone())),
+        };
+        let overloads = candidates
+            .iter()
+            .map(|ent| OverloadCandidate {
+                entity: ExplainedEntity::new(ent),
+                rejection: None,
+            })
+            .collect();
+
+        let explanation = ResolutionExplanation {
+            designator: designator.clone(),
+            scopes: searched
+                .into_iter()
+                .map(|mut declared| {
+                    declared.sort_by(|a, b| a.decl_pos().cmp(&b.decl_pos()));
+                    declared.into_iter().map(ExplainedEntity::new).collect()
+                })
+                .collect(),
+            use_clauses,
+            overloads,
+            resolved,
+            error,
+        };
+
+        *trace.state.borrow_mut() = Some(Traced {
+            pos: pos.clone(),
+            explanation,
+            ids: candidates.iter().map(|ent| ent.id()).collect(),
+        });
+    }
+
+    /// Called when the candidates of an overloaded name have been disambiguated.
+    /// Each rejected candidate is paired with the reason it was rejected.
+    pub(super) fn explain_overloaded(
+        &self,
+        pos: &SrcPos,
+        designator: &Designator,
+        candidates: &[OverloadedEnt<'a>],
+        rejected: &[(OverloadedEnt<'a>, String)],
+        resolved: Option<OverloadedEnt<'a>>,
+    ) {
+        if !self.explains(pos) {
+            return;
+        }
+
+        let rejection = |ent: &OverloadedEnt<'a>| {
+            rejected
+                .iter()
+                .find(|(other, _)| other.id() == ent.id())
+                .map(|(_, reason)| reason.clone())
+        };
+        let resolved = resolved.map(|ent| ExplainedEntity::new(&ent));
+
+        let mut state = self.trace.as_ref().unwrap().state.borrow_mut();
+        match *state {
+            // The name was looked up within a scope
+            Some(ref mut traced) if traced.pos == *pos => {
+                for ent in candidates.iter() {
+                    if let Some(idx) = traced.ids.iter().position(|id| *id == ent.id()) {
+                        traced.explanation.overloads[idx].rejection = rejection(ent);
+                    }
+                }
+                traced.explanation.resolved = resolved;
+            }
+            // The name was selected from a prefix
+            _ => {
+                let mut candidates = candidates.to_vec();
+                candidates.sort_by(|a, b| a.decl_pos().cmp(&b.decl_pos()));
+
+                *state = Some(Traced {
+                    pos: pos.clone(),
+                    explanation: ResolutionExplanation {
+                        designator: designator.clone(),
+                        scopes: Vec::new(),
+                        use_clauses: Vec::new(),
+                        overloads: candidates
+                            .iter()
+                            .map(|ent| OverloadCandidate {
+                                entity: ExplainedEntity::new(ent),
+                                rejection: rejection(ent),
+                            })
+                            .collect(),
+                        resolved,
+                        error: None,
+                    },
+                    ids: candidates.iter().map(|ent| ent.id()).collect(),
+                })
+            }
+        }
+    }
+
+    /// Explain the disambiguation of an operator by replaying the steps of disambiguate_op
+    pub(super) fn explain_op(
+        &self,
+        pos: &SrcPos,
+        designator: &Designator,
+        overloaded: &[OverloadedEnt<'a>],
+        operand_types: &[ExpressionType<'a>],
+        ttyp: Option<TypeEnt<'a>>,
+        remaining: &[OverloadedEnt<'a>],
+    ) {
+        if !self.explains(pos) {
+            return;
+        }
+
+        let tbase = ttyp.map(|ttyp| ttyp.base());
+        let rejected: Vec<_> = overloaded
+            .iter()
+            .filter(|ent| !remaining.contains(ent))
+            .map(|ent| {
+                let mismatch = |implicit: bool| {
+                    operand_types
+                        .iter()
+                        .enumerate()
+                        .find(|(idx, operand_type)| {
+                            let formal_type = ent.nth_base(*idx).unwrap();
+                            if implicit {
+                                !self.matcher().is_possible(operand_type, formal_type)
+                            } else {
+                                !self
+                                    .matcher_no_implicit()
+                                    .is_possible(operand_type, formal_type)
+                            }
+                        })
+                };
+                let return_type = ent.return_type().unwrap();
+
+                let reason = if let Some((idx, operand_type)) = mismatch(true) {
+                    format!(
+                        "Operand {} does not match {}, got {}",
+                        idx + 1,
+                        ent.nth_base(idx).unwrap().describe(),
+                        operand_type.describe()
+                    )
+                } else if tbase.is_some_and(|tbase| !self.can_be_target_type(return_type, tbase)) {
+                    format!(
+                        "Return {} does not match {}",
+                        return_type.describe(),
+                        ttyp.unwrap().describe()
+                    )
+                } else if let Some((idx, _)) = mismatch(false) {
+                    format!(
+                        "Operand {} requires an implicit conversion from a universal type",
+                        idx + 1
+                    )
+                } else {
+                    "Another candidate matches without implicit conversion".to_owned()
+                };
+                (*ent, reason)
+            })
+            .collect();
+
+        let resolved = if let [ent] = remaining {
+            Some(*ent)
+        } else {
+            None
+        };
+        self.explain_overloaded(pos, designator, overloaded, &rejected, resolved);
+    }
+}
+
+impl DesignRoot {
+    /// Explain how the name at the cursor was resolved: the scopes that were searched,
+    /// the use clauses that made candidates visible and why overloaded candidates were rejected.
+    /// Returns None if the cursor is not at a name that is looked up.
+    pub fn explain_resolution(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<ResolutionExplanation> {
+        let pos = SrcPos::new(source.clone(), crate::data::Range::new(cursor, cursor));
+        self.reanalyze_containing(
+            source,
+            cursor,
+            |context| context.with_trace(pos),
+            |context| context.trace.and_then(ResolutionTrace::into_explanation),
+        )
+        .flatten()
+    }
+}
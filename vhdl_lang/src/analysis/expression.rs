@@ -7,7 +7,7 @@
 use fnv::FnvHashSet;
 
 use super::analyze::*;
-use super::formal_region::RecordRegion;
+use super::formal_region::{RecordElement, RecordRegion};
 use super::named_entity::*;
 use super::overloaded::Disambiguated;
 use super::overloaded::DisambiguatedType;
@@ -207,7 +207,9 @@ impl<'a> AnalyzeContext<'a> {
         arity: usize,
     ) -> AnalysisResult<Vec<OverloadedEnt<'a>>> {
         let designator = Designator::OperatorSymbol(op);
-        match scope.lookup(op_pos, &designator)? {
+        let visible = scope.lookup(op_pos, &designator);
+        self.explain_lookup(scope, op_pos, &designator, &visible);
+        match visible? {
             NamedEntities::Single(ent) => {
                 // Should never happen but better know if it does
                 Err(Diagnostic::error(
@@ -221,10 +223,18 @@ impl<'a> AnalyzeContext<'a> {
             }
             NamedEntities::Overloaded(overloaded) => {
                 // Candidates that match arity of operator
-                let op_candidates: Vec<_> = overloaded
+                let (op_candidates, rejected): (Vec<_>, Vec<_>) = overloaded
                     .entities()
-                    .filter(|ent| ent.formals().len() == arity && ent.return_type().is_some())
-                    .collect();
+                    .partition(|ent| ent.formals().len() == arity && ent.return_type().is_some());
+
+                if self.explains(op_pos) {
+                    let rejected: Vec<_> = rejected
+                        .into_iter()
+                        .map(|ent| (ent, format!("Does not take {arity} operand(s)")))
+                        .collect();
+                    let all: Vec<_> = overloaded.entities().collect();
+                    self.explain_overloaded(op_pos, &designator, &all, &rejected, None);
+                }
 
                 if op_candidates.is_empty() {
                     Err(Diagnostic::error(
@@ -354,6 +364,15 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
+        self.explain_op(
+            &op.pos,
+            &designator,
+            &overloaded,
+            &operand_types,
+            ttyp,
+            &candidates,
+        );
+
         if candidates.is_empty() {
             diagnostics.error(
                 &op.pos,
@@ -385,10 +404,6 @@ impl<'a> AnalyzeContext<'a> {
         exprs: &mut [&mut WithPos<Expression>],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<ExpressionType<'a>> {
-        if !can_handle(op.item.item) {
-            return Err(EvalError::Unknown);
-        }
-
         let op_candidates = match self.lookup_operator(scope, &op.pos, op.item.item, exprs.len()) {
             Ok(candidates) => candidates,
             Err(err) => {
@@ -750,47 +765,41 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             Expression::Binary(ref mut op, ref mut left, ref mut right) => {
-                if can_handle(op.item.item) {
-                    let op_candidates = match self.lookup_operator(scope, &op.pos, op.item.item, 2)
-                    {
-                        Ok(candidates) => candidates,
-                        Err(err) => {
-                            diagnostics.push(err.into_non_fatal()?);
-                            return Ok(());
-                        }
-                    };
+                let op_candidates = match self.lookup_operator(scope, &op.pos, op.item.item, 2) {
+                    Ok(candidates) => candidates,
+                    Err(err) => {
+                        diagnostics.push(err.into_non_fatal()?);
+                        return Ok(());
+                    }
+                };
 
-                    match as_fatal(self.disambiguate_op(
-                        scope,
-                        Some(target_type),
-                        op,
-                        op_candidates,
-                        &mut [left.as_mut(), right.as_mut()],
-                        diagnostics,
-                    ))? {
-                        Some(Disambiguated::Unambiguous(overloaded)) => {
-                            let op_type = overloaded.return_type().unwrap();
-
-                            if !self.can_be_target_type(op_type, target_type.base()) {
-                                diagnostics.push(Diagnostic::type_mismatch(
-                                    expr_pos,
-                                    &op_type.describe(),
-                                    target_type,
-                                ));
-                            }
-                        }
-                        Some(Disambiguated::Ambiguous(candidates)) => {
-                            diagnostics.push(Diagnostic::ambiguous_op(
-                                &op.pos,
-                                op.item.item,
-                                candidates,
+                match as_fatal(self.disambiguate_op(
+                    scope,
+                    Some(target_type),
+                    op,
+                    op_candidates,
+                    &mut [left.as_mut(), right.as_mut()],
+                    diagnostics,
+                ))? {
+                    Some(Disambiguated::Unambiguous(overloaded)) => {
+                        let op_type = overloaded.return_type().unwrap();
+
+                        if !self.can_be_target_type(op_type, target_type.base()) {
+                            diagnostics.push(Diagnostic::type_mismatch(
+                                expr_pos,
+                                &op_type.describe(),
+                                target_type,
                             ));
                         }
-                        None => {}
                     }
-                } else {
-                    self.expr_unknown_ttyp(scope, left, diagnostics)?;
-                    self.expr_unknown_ttyp(scope, right, diagnostics)?;
+                    Some(Disambiguated::Ambiguous(candidates)) => {
+                        diagnostics.push(Diagnostic::ambiguous_op(
+                            &op.pos,
+                            op.item.item,
+                            candidates,
+                        ));
+                    }
+                    None => {}
                 }
             }
             Expression::Unary(ref mut op, ref mut expr) => {
@@ -856,6 +865,7 @@ impl<'a> AnalyzeContext<'a> {
                         scope,
                         target_base,
                         record_scope,
+                        expr_pos,
                         assocs,
                         diagnostics,
                     )?;
@@ -912,13 +922,21 @@ impl<'a> AnalyzeContext<'a> {
         scope: &Scope<'a>,
         record_type: TypeEnt<'a>,
         elems: &RecordRegion<'a>,
+        aggregate_pos: &SrcPos,
         assocs: &mut [ElementAssociation],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
-        for assoc in assocs.iter_mut() {
+        // The elements that have been associated together with the position of the choice
+        let mut associated: Vec<(RecordElement<'a>, SrcPos)> = Vec::new();
+        // Missing elements are only reported when all choices could be resolved
+        let mut all_resolved = true;
+
+        for (idx, assoc) in assocs.iter_mut().enumerate() {
             match assoc {
                 ElementAssociation::Named(ref mut choices, ref mut actual_expr) => {
-                    let elem = if let [choice] = choices.as_mut_slice() {
+                    let mut choice_elems: Vec<(RecordElement<'a>, SrcPos)> = Vec::new();
+
+                    for choice in choices.iter_mut() {
                         match choice {
                             Choice::Expression(choice_expr) => {
                                 if let Some(simple_name) =
@@ -926,55 +944,148 @@ impl<'a> AnalyzeContext<'a> {
                                 {
                                     if let Some(elem) = elems.lookup(&simple_name.item) {
                                         simple_name.set_unique_reference(&elem);
-                                        Some(elem)
+                                        choice_elems.push((elem, choice_expr.pos.clone()));
                                     } else {
                                         diagnostics.push(Diagnostic::no_declaration_within(
                                             &record_type,
                                             &choice_expr.pos,
                                             &simple_name.item,
                                         ));
-                                        None
+                                        all_resolved = false;
                                     }
                                 } else {
                                     diagnostics.error(
                                         &choice_expr.pos,
                                         "Record aggregate choice must be a simple name",
                                     );
-                                    None
+                                    all_resolved = false;
                                 }
                             }
-                            Choice::DiscreteRange(_decl) => {
-                                // @TODO not allowed for enum
-                                None
+                            Choice::DiscreteRange(ref mut drange) => {
+                                self.drange_unknown_type(scope, drange, diagnostics)?;
+                                diagnostics.error(
+                                    drange.pos(),
+                                    "Record aggregate choice must be a simple name",
+                                );
+                                all_resolved = false;
                             }
                             Choice::Others => {
-                                // @TODO handle specially
-                                None
+                                let remaining: Vec<_> = elems
+                                    .iter()
+                                    .filter(|elem| {
+                                        !associated
+                                            .iter()
+                                            .chain(choice_elems.iter())
+                                            .any(|(other, _)| other.id() == elem.id())
+                                    })
+                                    .collect();
+
+                                if remaining.is_empty() {
+                                    diagnostics.error(
+                                        &actual_expr.pos,
+                                        format!(
+                                            "Others choice does not represent any element of {}",
+                                            record_type.describe()
+                                        ),
+                                    );
+                                }
+
+                                for elem in remaining {
+                                    choice_elems.push((elem, actual_expr.pos.clone()));
+                                }
                             }
                         }
-                    } else {
-                        // @TODO not allowed for num
-                        // Record aggregate can only have a single choice
-                        None
-                    };
+                    }
+
+                    for (elem, pos) in choice_elems.iter() {
+                        if let Some((_, prev_pos)) =
+                            associated.iter().find(|(other, _)| other.id() == elem.id())
+                        {
+                            diagnostics.push(
+                                Diagnostic::error(
+                                    pos,
+                                    format!(
+                                        "Element '{}' is associated more than once",
+                                        elem.designator()
+                                    ),
+                                )
+                                .related(prev_pos, "Previously associated here"),
+                            );
+                        } else {
+                            associated.push((*elem, pos.clone()));
+                        }
+                    }
+
+                    let mut same_type = true;
+                    if let Some((first, _)) = choice_elems.first() {
+                        for (elem, pos) in choice_elems.iter().skip(1) {
+                            if elem.type_mark().base_type() != first.type_mark().base_type() {
+                                same_type = false;
+                                diagnostics.error(
+                                    pos,
+                                    format!(
+                                        "Elements '{}' and '{}' of the same choice must have the same type, got {} and {}",
+                                        first.designator(),
+                                        elem.designator(),
+                                        first.type_mark().describe(),
+                                        elem.type_mark().describe()
+                                    ),
+                                );
+                            }
+                        }
+                    }
 
-                    if let Some(elem) = elem {
+                    match choice_elems.first() {
+                        Some((first, _)) if same_type => {
+                            self.expr_pos_with_ttyp(
+                                scope,
+                                first.type_mark(),
+                                &actual_expr.pos,
+                                &mut actual_expr.item,
+                                diagnostics,
+                            )?;
+                        }
+                        _ => {
+                            self.expr_unknown_ttyp(scope, actual_expr, diagnostics)?;
+                        }
+                    }
+                }
+                ElementAssociation::Positional(ref mut expr) => {
+                    if let Some(elem) = elems.nth(idx) {
+                        associated.push((*elem, expr.pos.clone()));
                         self.expr_pos_with_ttyp(
                             scope,
                             elem.type_mark(),
-                            &actual_expr.pos,
-                            &mut actual_expr.item,
+                            &expr.pos,
+                            &mut expr.item,
                             diagnostics,
                         )?;
                     } else {
-                        self.expr_unknown_ttyp(scope, actual_expr, diagnostics)?;
+                        diagnostics.error(
+                            &expr.pos,
+                            format!(
+                                "Too many elements in aggregate of {}",
+                                record_type.describe()
+                            ),
+                        );
+                        all_resolved = false;
+                        self.expr_unknown_ttyp(scope, expr, diagnostics)?;
                     }
                 }
-                ElementAssociation::Positional(ref mut expr) => {
-                    self.expr_unknown_ttyp(scope, expr, diagnostics)?;
+            }
+        }
+
+        if all_resolved {
+            for elem in elems.iter() {
+                if !associated.iter().any(|(other, _)| other.id() == elem.id()) {
+                    diagnostics.error(
+                        aggregate_pos,
+                        format!("Missing association of element '{}'", elem.designator()),
+                    );
                 }
             }
         }
+
         Ok(())
     }
 
@@ -1065,19 +1176,6 @@ impl<'a> AnalyzeContext<'a> {
     }
 }
 
-// @TODO skip operators we do not handle yet
-fn can_handle(op: Operator) -> bool {
-    !matches!(
-        op,
-        Operator::QueEQ
-            | Operator::QueNE
-            | Operator::QueGT
-            | Operator::QueGTE
-            | Operator::QueLT
-            | Operator::QueLTE
-    )
-}
-
 impl Diagnostic {
     fn ambiguous_op<'a>(
         pos: &SrcPos,
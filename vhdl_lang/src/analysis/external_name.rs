@@ -0,0 +1,433 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Resolution of the paths of external names against the design hierarchy.
+//! The paths are resolved once all design units have been analyzed since they may refer to
+//! instances of the architecture in which they occur or to design units that depend on it.
+
+use super::named_entity::*;
+use super::DesignRoot;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+/// The declarations of an architecture that an external name may denote
+struct ArchitectureIndex {
+    name: Symbol,
+    pos: SrcPos,
+    /// The labels of the concurrent statements and the entity instantiated by each statement.
+    /// Labels of block and generate statements do not instantiate an entity.
+    labels: FnvHashMap<Symbol, (EntityId, Option<EntityId>)>,
+    objects: FnvHashMap<Symbol, EntityId>,
+}
+
+/// The libraries, primary units and architectures of the design
+#[derive(Default)]
+pub(super) struct ExternalNameIndex {
+    libraries: FnvHashMap<Symbol, EntityId>,
+    primaries: FnvHashMap<(Symbol, Symbol), EntityId>,
+    architectures: FnvHashMap<EntityId, Vec<ArchitectureIndex>>,
+}
+
+impl ExternalNameIndex {
+    pub fn add_library(&mut self, library_name: &Symbol, id: EntityId) {
+        self.libraries.insert(library_name.clone(), id);
+    }
+
+    pub fn add_unit(&mut self, library_name: &Symbol, unit: &AnyDesignUnit) {
+        match unit {
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) => {
+                self.add_primary(library_name, &entity.ident);
+            }
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package)) => {
+                self.add_primary(library_name, &package.ident);
+            }
+            AnyDesignUnit::Primary(AnyPrimaryUnit::PackageInstance(instance)) => {
+                self.add_primary(library_name, &instance.ident);
+            }
+            AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) => {
+                if let Some(entity) = arch.entity_name.reference {
+                    self.architectures
+                        .entry(entity)
+                        .or_default()
+                        .push(ArchitectureIndex::new(arch));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_primary(&mut self, library_name: &Symbol, ident: &WithDecl<Ident>) {
+        if let Some(id) = ident.decl {
+            self.primaries
+                .insert((library_name.clone(), ident.tree.item.clone()), id);
+        }
+    }
+
+    /// The architecture of an entity selected by the default binding
+    fn architecture_of(&self, root: &DesignRoot, entity: EntityId) -> Option<&ArchitectureIndex> {
+        root.select_architecture(self.architectures.get(&entity)?, |arch| {
+            (&arch.name, &arch.pos)
+        })
+    }
+
+    /// Resolve the paths of the external names of a design unit of the library
+    pub fn resolve(
+        &self,
+        root: &DesignRoot,
+        library_name: &Symbol,
+        unit: &mut AnyDesignUnit,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut resolver = ExternalNameResolver {
+            root,
+            index: self,
+            library_name,
+            local: None,
+            diagnostics,
+        };
+
+        if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = unit {
+            let local = arch.entity_name.reference.and_then(|entity| {
+                self.architectures
+                    .get(&entity)?
+                    .iter()
+                    .find(|index| index.name == arch.ident.item)
+                    .map(|index| (entity, index))
+            });
+
+            resolver.local = local;
+            let _ = arch.decl.search(&mut resolver);
+            for statement in arch.statements.iter_mut() {
+                // Relative paths within blocks and generate statements start at those statements
+                resolver.local = match statement.statement {
+                    ConcurrentStatement::Block(..)
+                    | ConcurrentStatement::ForGenerate(..)
+                    | ConcurrentStatement::IfGenerate(..)
+                    | ConcurrentStatement::CaseGenerate(..) => None,
+                    _ => local,
+                };
+                let _ = statement.search(&mut resolver);
+            }
+        } else {
+            let _ = unit.search(&mut resolver);
+        }
+    }
+}
+
+impl ArchitectureIndex {
+    fn new(arch: &ArchitectureBody) -> ArchitectureIndex {
+        let mut labels = FnvHashMap::default();
+        for statement in arch.statements.iter() {
+            let Some(ref label) = statement.label else {
+                continue;
+            };
+            let Some(id) = label.decl else {
+                continue;
+            };
+
+            let instance = match statement.statement {
+                ConcurrentStatement::Instance(ref instance) => match instance.unit {
+                    InstantiatedUnit::Entity(ref name, _) => name.item.get_suffix_reference(),
+                    InstantiatedUnit::Component(_) => instance.default_binding,
+                    InstantiatedUnit::Configuration(..) => None,
+                },
+                _ => None,
+            };
+            labels.insert(label.tree.item.clone(), (id, instance));
+        }
+
+        let mut objects = FnvHashMap::default();
+        for decl in arch.decl.iter() {
+            if let Declaration::Object(ref object) = decl {
+                if let Some(id) = object.ident.decl {
+                    objects.insert(object.ident.tree.item.clone(), id);
+                }
+            }
+        }
+
+        ArchitectureIndex {
+            name: arch.ident.item.clone(),
+            pos: arch.ident.pos.clone(),
+            labels,
+            objects,
+        }
+    }
+}
+
+struct ExternalNameResolver<'r> {
+    root: &'r DesignRoot,
+    index: &'r ExternalNameIndex,
+    library_name: &'r Symbol,
+    /// The entity and architecture at which relative paths start
+    local: Option<(EntityId, &'r ArchitectureIndex)>,
+    diagnostics: &'r mut dyn DiagnosticHandler,
+}
+
+type Segment<'n> = (&'n SrcPos, &'n mut WithRef<Designator>);
+
+impl<'r> Searcher for ExternalNameResolver<'r> {
+    fn search_external_name(&mut self, ename: &mut ExternalName) -> SearchState {
+        self.resolve(ename);
+        NotFinished
+    }
+}
+
+impl<'r> ExternalNameResolver<'r> {
+    fn resolve(&mut self, ename: &mut ExternalName) {
+        let ExternalName {
+            class,
+            path,
+            subtype,
+        } = ename;
+
+        let object = match path.item {
+            ExternalPath::Package(ref mut name) => path_segments(name)
+                .and_then(|mut segments| self.resolve_package_path(&mut segments)),
+            ExternalPath::Absolute(ref mut name) => path_segments(name)
+                .and_then(|mut segments| self.resolve_absolute_path(&mut segments)),
+            ExternalPath::Relative(ref mut name, 0) => {
+                let local = self.local;
+                path_segments(name).and_then(|mut segments| {
+                    let (entity, arch) = local?;
+                    self.resolve_in_entity(entity, Some(arch), &mut segments)
+                })
+            }
+            // Paths that go up the hierarchy depend on where the design unit is instantiated
+            ExternalPath::Relative(..) => None,
+        };
+
+        let Some(object) = object else {
+            return;
+        };
+
+        let Some(object) = ObjectEnt::from_any(object) else {
+            self.diagnostics.push(Diagnostic::error(
+                &path.pos,
+                format!("{} is not an object", capitalize(&object.describe())),
+            ));
+            return;
+        };
+
+        let class = ObjectClass::from(*class);
+        let matches_class = match object.class() {
+            ObjectClass::SharedVariable => class == ObjectClass::Variable,
+            object_class => object_class == class,
+        };
+        if !matches_class {
+            self.diagnostics.push(Diagnostic::error(
+                &path.pos,
+                format!("External {} name denotes {}", class, object.describe_name()),
+            ));
+            return;
+        }
+
+        if subtype.type_mark.item.attr.is_some() {
+            return;
+        }
+        let Some(type_mark) = subtype
+            .type_mark
+            .item
+            .name
+            .item
+            .get_suffix_reference()
+            .and_then(|id| TypeEnt::from_any(self.root.get_ent(id)))
+        else {
+            return;
+        };
+
+        if type_mark.base_type() != object.type_mark().base_type() {
+            self.diagnostics.push(Diagnostic::error(
+                &subtype.type_mark.pos,
+                format!(
+                    "External name of type '{}' does not match {} of type '{}'",
+                    type_mark.designator(),
+                    object.describe_name(),
+                    object.type_mark().designator()
+                ),
+            ));
+        }
+    }
+
+    /// A path of the form @library.package.object
+    fn resolve_package_path(&mut self, segments: &mut [Segment]) -> Option<EntRef<'r>> {
+        let [(library_pos, library), (package_pos, package), (object_pos, object)] = segments
+        else {
+            return None;
+        };
+
+        let library_name = identifier(library)?.clone();
+        let Some(library_id) = self.index.libraries.get(&library_name) else {
+            self.diagnostics.push(Diagnostic::error(
+                *library_pos,
+                format!("No such library '{library_name}'"),
+            ));
+            return None;
+        };
+        library.reference = Some(*library_id);
+
+        let package_ent = self.lookup_primary(&library_name, package_pos, package)?;
+        let region = match package_ent.kind() {
+            AnyEntKind::Design(Design::Package(_, region))
+            | AnyEntKind::Design(Design::PackageInstance(region)) => region,
+            _ => {
+                self.diagnostics.push(Diagnostic::error(
+                    *package_pos,
+                    format!("{} is not a package", capitalize(&package_ent.describe())),
+                ));
+                return None;
+            }
+        };
+
+        match region
+            .lookup_immediate(&object.item)
+            .and_then(|ents| ents.as_non_overloaded())
+        {
+            Some(ent) => {
+                object.reference = Some(ent.id());
+                Some(ent)
+            }
+            None => {
+                self.diagnostics.push(Diagnostic::error(
+                    *object_pos,
+                    format!(
+                        "No declaration of '{}' within {}",
+                        object.item,
+                        package_ent.describe()
+                    ),
+                ));
+                None
+            }
+        }
+    }
+
+    /// A path of the form .entity.label.object where the entity is the root of the design
+    fn resolve_absolute_path(&mut self, segments: &mut [Segment]) -> Option<EntRef<'r>> {
+        let ((entity_pos, entity), rest) = segments.split_first_mut()?;
+        let entity_ent = self.lookup_primary(self.library_name, entity_pos, entity)?;
+        if !matches!(entity_ent.kind(), AnyEntKind::Design(Design::Entity(..))) {
+            self.diagnostics.push(Diagnostic::error(
+                *entity_pos,
+                format!("{} is not an entity", capitalize(&entity_ent.describe())),
+            ));
+            return None;
+        }
+        self.resolve_in_entity(entity_ent.id(), None, rest)
+    }
+
+    /// Resolve the remaining segments of a path within the architecture of an entity.
+    /// The architecture is given for the design unit of the external name and is
+    /// otherwise the architecture selected by the default binding.
+    fn resolve_in_entity(
+        &mut self,
+        entity: EntityId,
+        arch: Option<&'r ArchitectureIndex>,
+        segments: &mut [Segment],
+    ) -> Option<EntRef<'r>> {
+        let entity = self.root.get_ent(entity);
+        let AnyEntKind::Design(Design::Entity(_, region)) = entity.kind() else {
+            return None;
+        };
+        let arch = arch.or_else(|| self.index.architecture_of(self.root, entity.id()));
+
+        let ((pos, designator), rest) = segments.split_first_mut()?;
+        let name = identifier(designator)?;
+
+        if rest.is_empty() {
+            let object = arch
+                .and_then(|arch| arch.objects.get(name))
+                .map(|id| self.root.get_ent(*id))
+                .or_else(|| {
+                    region
+                        .lookup_immediate(&designator.item)
+                        .and_then(|ents| ents.as_non_overloaded())
+                });
+
+            if let Some(object) = object {
+                designator.reference = Some(object.id());
+            } else if arch.is_some() {
+                self.diagnostics
+                    .push(no_declaration_error(pos, designator, entity));
+            }
+            object
+        } else {
+            let arch = arch?;
+            if let Some((label, instance)) = arch.labels.get(name) {
+                designator.reference = Some(*label);
+                self.resolve_in_entity((*instance)?, None, rest)
+            } else {
+                self.diagnostics
+                    .push(no_declaration_error(pos, designator, entity));
+                None
+            }
+        }
+    }
+
+    fn lookup_primary(
+        &mut self,
+        library_name: &Symbol,
+        pos: &SrcPos,
+        designator: &mut WithRef<Designator>,
+    ) -> Option<EntRef<'r>> {
+        let name = identifier(designator)?;
+        let Some(id) = self
+            .index
+            .primaries
+            .get(&(library_name.clone(), name.clone()))
+        else {
+            self.diagnostics.push(Diagnostic::error(
+                pos,
+                format!("No primary unit '{name}' within library '{library_name}'"),
+            ));
+            return None;
+        };
+        designator.reference = Some(*id);
+        Some(self.root.get_ent(*id))
+    }
+}
+
+/// The segments of a path and their references with all references cleared
+fn path_segments(name: &mut WithPos<Name>) -> Option<Vec<Segment<'_>>> {
+    let WithPos { item, pos } = name;
+    match item {
+        Name::Designator(designator) => {
+            designator.reference = None;
+            Some(vec![(pos, designator)])
+        }
+        Name::Selected(prefix, suffix) => {
+            let mut segments = path_segments(prefix)?;
+            suffix.item.reference = None;
+            segments.push((&suffix.pos, &mut suffix.item));
+            Some(segments)
+        }
+        // Indexes of for generate statements
+        _ => None,
+    }
+}
+
+fn identifier(designator: &WithRef<Designator>) -> Option<&Symbol> {
+    if let Designator::Identifier(ref name) = designator.item {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+fn no_declaration_error(
+    pos: &SrcPos,
+    designator: &WithRef<Designator>,
+    entity: EntRef,
+) -> Diagnostic {
+    Diagnostic::error(
+        pos,
+        format!(
+            "No declaration of '{}' within {}",
+            designator.item,
+            entity.describe()
+        ),
+    )
+}
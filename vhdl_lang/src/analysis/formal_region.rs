@@ -55,6 +55,13 @@ impl<'a> InterfaceEnt<'a> {
         }
     }
 
+    pub fn mode(&self) -> Option<Mode> {
+        match self.ent.kind() {
+            AnyEntKind::Object(obj) => obj.mode,
+            _ => None,
+        }
+    }
+
     pub fn type_mark(&self) -> TypeEnt<'a> {
         match self.ent.kind() {
             AnyEntKind::Object(obj) => obj.subtype.type_mark(),
@@ -247,7 +254,7 @@ impl<'a> GpkgInterfaceEnt<'a> {
                     ObjectEnt::from_any(ent).unwrap(),
                 ))
             }
-            AnyEntKind::Overloaded(Overloaded::InterfaceSubprogram(_)) => Some(
+            AnyEntKind::Overloaded(Overloaded::InterfaceSubprogram(..)) => Some(
                 GpkgInterfaceEnt::Subprogram(OverloadedEnt::from_any(ent).unwrap()),
             ),
             AnyEntKind::Design(Design::PackageInstance(_)) => Some(GpkgInterfaceEnt::Package(ent)),
@@ -297,4 +304,8 @@ impl<'a> GpkgRegion<'a> {
     pub fn nth(&self, idx: usize) -> Option<GpkgInterfaceEnt<'a>> {
         self.entities.get(idx).cloned()
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = GpkgInterfaceEnt<'a>> + '_ {
+        self.entities.iter().cloned()
+    }
 }
@@ -0,0 +1,169 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Documentation of the generics of entities and components
+
+use super::named_entity::{AnyEntKind, Design, EntRef, EntityId, Object};
+use super::root::DesignRoot;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+
+/// A row of the generic documentation table of an entity or component
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenericDocumentation {
+    pub name: String,
+    /// The subtype indication of the generic
+    pub subtype: String,
+    pub default: Option<String>,
+    /// The comment lines directly above the generic followed by its trailing comment
+    pub doc: Option<String>,
+}
+
+/// Which generics to document
+enum Target {
+    /// All generics of the entity or component with this id
+    Owner(EntityId),
+    /// The generic with this id
+    Generic(EntityId),
+}
+
+struct FindGenerics {
+    target: Target,
+    result: Vec<GenericDocumentation>,
+}
+
+impl FindGenerics {
+    fn document(&mut self, owner: &WithDecl<Ident>, generics: &[InterfaceDeclaration]) {
+        for generic in generics.iter() {
+            // Only interface constants have a subtype and a default
+            let InterfaceDeclaration::Object(object) = generic else {
+                continue;
+            };
+
+            let is_target = match self.target {
+                Target::Owner(id) => owner.decl == Some(id),
+                Target::Generic(id) => object.ident.decl == Some(id),
+            };
+
+            if is_target {
+                self.result.push(GenericDocumentation {
+                    name: object.ident.tree.item.to_string(),
+                    subtype: object.subtype_indication.to_string(),
+                    default: object.expression.as_ref().map(|expr| expr.item.to_string()),
+                    doc: doc_comment(&object.ident.tree.pos),
+                });
+            }
+        }
+    }
+}
+
+impl Searcher for FindGenerics {
+    fn search_decl(&mut self, decl: FoundDeclaration) -> SearchState {
+        match decl {
+            FoundDeclaration::Entity(entity) => {
+                if let Some(ref generics) = entity.generic_clause {
+                    self.document(&entity.ident, generics);
+                }
+            }
+            FoundDeclaration::Component(component) => {
+                self.document(&component.ident, &component.generic_list);
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// The text of a comment line without the leading dashes
+fn comment_text(comment: &str) -> &str {
+    let comment = comment.trim_start_matches('-');
+    comment.strip_prefix(' ').unwrap_or(comment).trim_end()
+}
+
+/// The trailing comment of a line, ignoring dashes within string literals
+fn trailing_comment(line: &str) -> Option<&str> {
+    let mut in_string = false;
+    let mut prev = None;
+    for (idx, chr) in line.char_indices() {
+        match chr {
+            '"' => in_string = !in_string,
+            '-' if !in_string && prev == Some('-') => return Some(&line[idx - 1..]),
+            _ => {}
+        }
+        prev = Some(chr);
+    }
+    None
+}
+
+/// The comment lines directly above the declaration at the position followed by its trailing comment
+fn doc_comment(pos: &SrcPos) -> Option<String> {
+    let contents = pos.source.contents();
+    let line = pos.start().line as usize;
+
+    let mut lines = Vec::new();
+    for lineno in (0..line).rev() {
+        match contents.get_line(lineno).map(str::trim) {
+            Some(text) if text.starts_with("--") => lines.push(comment_text(text).to_owned()),
+            _ => break,
+        }
+    }
+    lines.reverse();
+
+    if let Some(text) = contents.get_line(line) {
+        let rest: String = text.chars().skip(pos.end().character as usize).collect();
+        if let Some(comment) = trailing_comment(&rest) {
+            lines.push(comment_text(comment).to_owned());
+        }
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+impl DesignRoot {
+    /// The documentation table of the generics of an entity or component.
+    /// Only interface constants are documented.
+    pub fn generic_documentation(&self, ent: EntRef) -> Vec<GenericDocumentation> {
+        if !matches!(
+            ent.kind(),
+            AnyEntKind::Design(Design::Entity(..)) | AnyEntKind::Component(..)
+        ) {
+            return Vec::new();
+        }
+
+        let mut searcher = FindGenerics {
+            target: Target::Owner(ent.id()),
+            result: Vec::new(),
+        };
+        let _ = self.search(&mut searcher);
+        searcher.result
+    }
+
+    /// The documentation of a generic of an entity or component
+    pub fn documentation_of_generic(&self, ent: EntRef) -> Option<GenericDocumentation> {
+        if !matches!(
+            ent.kind(),
+            AnyEntKind::Object(Object {
+                class: ObjectClass::Constant,
+                mode: Some(_),
+                ..
+            })
+        ) {
+            return None;
+        }
+
+        let mut searcher = FindGenerics {
+            target: Target::Generic(ent.id()),
+            result: Vec::new(),
+        };
+        let _ = self.search(&mut searcher);
+        searcher.result.into_iter().next()
+    }
+}
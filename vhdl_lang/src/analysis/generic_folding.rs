@@ -0,0 +1,204 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Propagation of static generic actuals into the constraints of ports at instantiation sites
+
+use super::analyze::*;
+use super::formal_region::{FormalRegion, InterfaceEnt};
+use super::named_entity::*;
+use super::static_expression::{static_default_error, static_length, StaticEvaluator};
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+impl<'a> AnalyzeContext<'a> {
+    /// Check that the lengths of the actuals of a port map match the lengths of the ports
+    /// and that the defaults of unassociated ports are within their subtypes.
+    /// Constraints of ports that depend on generics such as `bit_vector(width - 1 downto 0)`
+    /// are evaluated with the static actuals of the generic map or the static defaults of the generics.
+    pub fn check_port_map_with_generics(
+        &self,
+        instance_pos: &SrcPos,
+        generic_region: &FormalRegion<'a>,
+        generic_map: &[AssociationElement],
+        port_region: &FormalRegion<'a>,
+        port_map: &[AssociationElement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut generics = FnvHashMap::default();
+        for generic in generic_region.iter() {
+            if let Some(value) = generic.static_default() {
+                generics.insert(generic.id(), value);
+            }
+        }
+
+        for (generic, actual) in associated_formals(generic_region, generic_map) {
+            if let ActualPart::Expression(ref expr) = actual.item {
+                match self.eval_static(Some(generic.type_mark()), expr) {
+                    Some(value) => generics.insert(generic.id(), value),
+                    None => generics.remove(&generic.id()),
+                };
+            }
+        }
+
+        let formal_evaluator = self.static_evaluator().with_generics(generics);
+        let actual_evaluator = self.static_evaluator();
+
+        let associated_ports = associated_formals(port_region, port_map);
+
+        for port in port_region.iter() {
+            let is_associated = associated_ports.iter().any(|(formal, actual)| {
+                formal.id() == port.id() && actual.item != ActualPart::Open
+            });
+            if !is_associated {
+                self.check_generic_default(&formal_evaluator, instance_pos, port, diagnostics);
+            }
+        }
+
+        for (port, actual) in associated_ports {
+            let ActualPart::Expression(ref expr) = actual.item else {
+                continue;
+            };
+
+            if port.type_mark().array_type().is_none() {
+                continue;
+            }
+
+            let Some(port_lengths) = formal_evaluator
+                .interface_ranges(port.inner())
+                .and_then(|ranges| lengths(&ranges))
+            else {
+                continue;
+            };
+
+            let Some(actual_lengths) = self.actual_lengths(&actual_evaluator, expr) else {
+                continue;
+            };
+
+            if port_lengths.len() != actual_lengths.len() {
+                continue;
+            }
+
+            if let Some((port_length, actual_length)) = port_lengths
+                .iter()
+                .zip(actual_lengths.iter())
+                .find(|(port_length, actual_length)| port_length != actual_length)
+            {
+                let mut diagnostic = Diagnostic::error(
+                    &actual.pos,
+                    format!(
+                        "Actual of length {} does not match the length {} of port '{}'",
+                        actual_length,
+                        port_length,
+                        port.designator()
+                    ),
+                );
+                if let Some(decl_pos) = port.decl_pos() {
+                    diagnostic.add_related(decl_pos, "Defined here");
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    /// Check a default of a port that depends on generics
+    fn check_generic_default(
+        &self,
+        evaluator: &StaticEvaluator<impl Fn(EntityId) -> EntRef<'a>>,
+        instance_pos: &SrcPos,
+        port: InterfaceEnt<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(StaticProperty::Generic {
+            default: Some(ref default),
+            ..
+        }) = port.static_property
+        else {
+            return;
+        };
+
+        let ranges = evaluator.interface_ranges(port.inner());
+        if let Some(message) = static_default_error(
+            evaluator,
+            port.type_mark(),
+            ranges.as_deref(),
+            &default.item,
+        ) {
+            diagnostics.push(
+                Diagnostic::error(
+                    instance_pos,
+                    format!(
+                        "Default value of port '{}' is invalid with the generics of this instance",
+                        port.designator()
+                    ),
+                )
+                .related(&default.pos, message),
+            );
+        }
+    }
+
+    /// The static lengths of each dimension of an array actual
+    fn actual_lengths(
+        &self,
+        evaluator: &StaticEvaluator<impl Fn(EntityId) -> EntRef<'a>>,
+        expr: &Expression,
+    ) -> Option<Vec<i64>> {
+        match expr {
+            Expression::Name(name) => match name.as_ref() {
+                Name::Designator(..) | Name::Selected(..) => {
+                    let ent = self.arena.get(name.get_suffix_reference()?);
+                    let is_array_object = match ent.kind() {
+                        AnyEntKind::Object(object) => object.subtype.type_mark(),
+                        AnyEntKind::ObjectAlias { type_mark, .. } => *type_mark,
+                        AnyEntKind::DeferredConstant(subtype) => subtype.type_mark(),
+                        _ => return None,
+                    }
+                    .array_type()
+                    .is_some();
+
+                    if is_array_object {
+                        lengths(&evaluator.interface_ranges(ent)?)
+                    } else {
+                        None
+                    }
+                }
+                Name::Slice(_, range) => Some(vec![evaluator
+                    .eval_discrete_range(None, range)?
+                    .length()?]),
+                _ => None,
+            },
+            _ => Some(vec![static_length(expr)?]),
+        }
+    }
+}
+
+fn lengths(ranges: &[StaticRange]) -> Option<Vec<i64>> {
+    ranges.iter().map(|range| range.length()).collect()
+}
+
+/// The formals of an association list that are associated as a whole together with their actuals
+fn associated_formals<'a, 'e>(
+    formal_region: &FormalRegion<'a>,
+    elems: &'e [AssociationElement],
+) -> Vec<(InterfaceEnt<'a>, &'e WithPos<ActualPart>)> {
+    elems
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, elem)| {
+            let formal = if let Some(ref formal) = elem.formal {
+                if let Name::Designator(ref designator) = formal.item {
+                    let id = designator.reference?;
+                    formal_region.iter().find(|formal| formal.id() == id)?
+                } else {
+                    return None;
+                }
+            } else {
+                formal_region.nth(idx)?
+            };
+            Some((formal, &elem.actual))
+        })
+        .collect()
+}
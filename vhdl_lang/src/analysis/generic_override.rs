@@ -0,0 +1,128 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Analysis of an architecture with assumed values of the generics of its entity
+
+use super::analyze::*;
+use super::named_entity::*;
+use super::region::{NamedEntities, Region, Scope};
+use super::root::DesignRoot;
+use crate::ast::search::clear_references;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+impl DesignRoot {
+    /// Analyze an architecture as if the generics of its entity had the given values,
+    /// like the generic overrides of a simulator. Constraints and expressions that depend
+    /// on the generics are evaluated as static such that their widths and ranges are checked.
+    /// Returns the diagnostics of the architecture or an error if a value is invalid.
+    pub fn analyze_with_generics(
+        &self,
+        library_name: &str,
+        entity_name: &str,
+        architecture_name: &str,
+        mut generics: Vec<(Symbol, WithPos<Expression>)>,
+    ) -> Result<Vec<Diagnostic>, String> {
+        let library_name = self.symbol_utf8(library_name);
+        let entity_sym = self.symbol_utf8(entity_name);
+        let architecture_sym = self.symbol_utf8(architecture_name);
+
+        let entity_unit_id = UnitId::primary(&library_name, PrimaryKind::Entity, &entity_sym);
+        let entity_id = {
+            let unit = self
+                .get_unit(&entity_unit_id)
+                .and_then(|locked_unit| locked_unit.unit.get());
+            let Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity))) = unit.as_deref()
+            else {
+                return Err(format!("No such entity '{library_name}.{entity_sym}'"));
+            };
+            entity
+                .ident
+                .decl
+                .ok_or_else(|| format!("The entity '{entity_sym}' is not analyzed"))?
+        };
+
+        let architecture_unit_id = UnitId::secondary(
+            &library_name,
+            SecondaryKind::Architecture,
+            &entity_sym,
+            &architecture_sym,
+        );
+        let mut architecture = {
+            let unit = self
+                .get_unit(&architecture_unit_id)
+                .and_then(|locked_unit| locked_unit.unit.get());
+            let Some(AnyDesignUnit::Secondary(ref architecture)) = unit.as_deref() else {
+                return Err(format!(
+                    "No such architecture '{architecture_sym}' of entity '{library_name}.{entity_sym}'"
+                ));
+            };
+            architecture.clone()
+        };
+
+        let entity = self.get_ent(entity_id);
+        let AnyEntKind::Design(Design::Entity(ref visibility, ref region)) = entity.kind() else {
+            return Err(format!("The entity '{entity_sym}' is not analyzed"));
+        };
+
+        // The architecture is analyzed into a separate arena which references all analyzed units
+        let arena = Arena::new(ArenaId::default());
+        arena.link(&self.arenas);
+        let context = AnalyzeContext::new(self, &architecture_unit_id, &arena);
+
+        let root_scope = Scope::new(Region::with_visibility(visibility.clone()));
+        let scope = Scope::extend(region, Some(&root_scope));
+
+        let mut values = FnvHashMap::default();
+        for (name, value) in generics.iter_mut() {
+            let Some(NamedEntities::Single(generic)) =
+                region.lookup_immediate(&name.clone().into())
+            else {
+                return Err(format!("Entity '{entity_sym}' has no generic '{name}'"));
+            };
+            let AnyEntKind::Object(
+                object @ Object {
+                    class: ObjectClass::Constant,
+                    mode: Some(_),
+                    ..
+                },
+            ) = generic.kind()
+            else {
+                return Err(format!(
+                    "Entity '{entity_sym}' has no generic constant '{name}'"
+                ));
+            };
+
+            let mut diagnostics = Vec::new();
+            if let Err(err) =
+                context.expr_with_ttyp(&scope, object.subtype.type_mark(), value, &mut diagnostics)
+            {
+                err.push_into(&mut diagnostics);
+            }
+            if let Some(diagnostic) = diagnostics.first() {
+                return Err(format!(
+                    "Invalid value of generic '{name}': {}",
+                    diagnostic.message
+                ));
+            }
+
+            let value = context
+                .eval_static(Some(object.subtype.type_mark()), &value.item)
+                .ok_or_else(|| format!("The value of generic '{name}' is not static"))?;
+            values.insert(generic.id(), value);
+        }
+
+        let context =
+            AnalyzeContext::new(self, &architecture_unit_id, &arena).with_generics(values);
+        let mut diagnostics = Vec::new();
+        clear_references(&mut architecture);
+        if let Err(err) = context.analyze_secondary_unit(&mut architecture, &mut diagnostics) {
+            err.push_into(&mut diagnostics);
+        }
+        Ok(diagnostics)
+    }
+}
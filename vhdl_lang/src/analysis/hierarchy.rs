@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Detection of entities that recursively instantiate themselves
+
+use super::named_entity::EntityId;
+use super::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+/// An architecture given by its entity and name
+type Architecture = (EntityId, Symbol);
+
+/// An instantiated entity with the architecture named by the instance, if any
+type Instance = (EntityId, Option<Symbol>);
+
+/// The entities instantiated by each architecture.
+/// Instances within generate statements are excluded since the generate condition
+/// may terminate the recursion.
+#[derive(Default)]
+pub(super) struct InstantiationGraph {
+    architectures: Vec<Architecture>,
+    /// The name and position of the architectures of each entity for the default binding
+    names: FnvHashMap<EntityId, Vec<(Symbol, SrcPos)>>,
+    instances: FnvHashMap<Architecture, Vec<(Instance, SrcPos)>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    OnPath,
+    Done,
+}
+
+impl InstantiationGraph {
+    pub fn add_architecture(&mut self, arch: &ArchitectureBody) {
+        if let Some(entity) = arch.entity_name.reference {
+            let key = (entity, arch.ident.item.clone());
+            if self.instances.contains_key(&key) {
+                return;
+            }
+            self.names
+                .entry(entity)
+                .or_default()
+                .push((arch.ident.item.clone(), arch.ident.pos.clone()));
+            let mut instances = Vec::new();
+            add_instances(&arch.statements, &mut instances);
+            self.architectures.push(key.clone());
+            self.instances.insert(key, instances);
+        }
+    }
+
+    /// Report every instantiation that closes a cycle in the hierarchy
+    pub fn check_cycles(&self, root: &DesignRoot, diagnostics: &mut dyn DiagnosticHandler) {
+        let mut state = FnvHashMap::default();
+        let mut path = Vec::new();
+
+        for arch in self.architectures.iter() {
+            if !state.contains_key(arch) {
+                self.visit(arch.clone(), root, &mut state, &mut path, diagnostics);
+            }
+        }
+    }
+
+    /// The architecture that an instance is bound to, either the one it names
+    /// or the one selected by the default binding
+    fn bound_architecture(&self, root: &DesignRoot, instance: &Instance) -> Option<Architecture> {
+        let (entity, ref arch) = *instance;
+        let arch = match arch {
+            Some(arch) => arch.clone(),
+            None => {
+                let (name, _) =
+                    root.select_architecture(self.names.get(&entity)?, |(name, pos)| (name, pos))?;
+                name.clone()
+            }
+        };
+        Some((entity, arch))
+    }
+
+    fn visit<'p>(
+        &'p self,
+        arch: Architecture,
+        root: &DesignRoot,
+        state: &mut FnvHashMap<Architecture, VisitState>,
+        path: &mut Vec<(Architecture, &'p SrcPos)>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        state.insert(arch.clone(), VisitState::OnPath);
+
+        for (instance, pos) in self.instances.get(&arch).into_iter().flatten() {
+            let Some(bound) = self.bound_architecture(root, instance) else {
+                continue;
+            };
+            match state.get(&bound) {
+                Some(VisitState::OnPath) => {
+                    let start = path
+                        .iter()
+                        .position(|(other, _)| *other == bound)
+                        .unwrap_or(path.len());
+                    let cycle: Vec<_> = path[start..]
+                        .iter()
+                        .map(|((entity, _), pos)| (*entity, *pos))
+                        .collect();
+                    diagnostics.push(cycle_error(root, &cycle, arch.0, bound.0, pos));
+                }
+                Some(VisitState::Done) => {}
+                None => {
+                    path.push((arch.clone(), pos));
+                    self.visit(bound, root, state, path, diagnostics);
+                    path.pop();
+                }
+            }
+        }
+
+        state.insert(arch, VisitState::Done);
+    }
+}
+
+fn add_instances(
+    statements: &[LabeledConcurrentStatement],
+    instances: &mut Vec<(Instance, SrcPos)>,
+) {
+    for statement in statements.iter() {
+        match statement.statement {
+            ConcurrentStatement::Instance(ref instance) => {
+                let entity = match instance.unit {
+                    InstantiatedUnit::Entity(ref name, ref arch) => name
+                        .item
+                        .get_suffix_reference()
+                        .map(|id| ((id, arch.as_ref().map(|arch| arch.item.clone())), &name.pos)),
+                    InstantiatedUnit::Component(ref name) => {
+                        instance.default_binding.map(|id| ((id, None), &name.pos))
+                    }
+                    InstantiatedUnit::Configuration(..) => None,
+                };
+
+                if let Some((instance, pos)) = entity {
+                    instances.push((instance, pos.clone()));
+                }
+            }
+            ConcurrentStatement::Block(ref block) => {
+                add_instances(&block.statements, instances);
+            }
+            ConcurrentStatement::ForGenerate(..)
+            | ConcurrentStatement::IfGenerate(..)
+            | ConcurrentStatement::CaseGenerate(..)
+            | ConcurrentStatement::ProcedureCall(..)
+            | ConcurrentStatement::Process(..)
+            | ConcurrentStatement::Assert(..)
+            | ConcurrentStatement::PslDirective(..)
+            | ConcurrentStatement::Assignment(..) => {}
+        }
+    }
+}
+
+fn cycle_error(
+    root: &DesignRoot,
+    path: &[(EntityId, &SrcPos)],
+    entity: EntityId,
+    instance: EntityId,
+    pos: &SrcPos,
+) -> Diagnostic {
+    let name = |id: EntityId| root.get_ent(id).designator().to_string();
+
+    let mut names: Vec<String> = path.iter().map(|(id, _)| name(*id)).collect();
+    names.push(name(entity));
+    names.push(name(instance));
+
+    let mut diagnostic = Diagnostic::error(
+        pos,
+        format!(
+            "Recursive instantiation of entity '{}' without a terminating generate condition: {}",
+            name(instance),
+            names.join(" -> ")
+        ),
+    );
+
+    for (idx, (_, pos)) in path.iter().enumerate() {
+        let instantiated = path.get(idx + 1).map(|(id, _)| *id).unwrap_or(entity);
+        diagnostic.add_related(*pos, format!("Instantiation of '{}'", name(instantiated)));
+    }
+    diagnostic
+}
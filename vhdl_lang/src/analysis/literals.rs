@@ -68,11 +68,11 @@ impl<'a> AnalyzeContext<'a> {
             },
             Literal::String(string_lit) => {
                 if let Some((elem_type, literals)) = as_single_index_enum_array(target_base) {
-                    for chr in string_lit.chars() {
+                    for (idx, chr) in string_lit.chars().enumerate() {
                         let chr = Designator::Character(*chr);
                         if !literals.contains(&chr) {
                             diagnostics.push(Diagnostic::error(
-                                pos,
+                                string_char_pos(pos, string_lit, idx),
                                 format!(
                                     "{} does not define character {}",
                                     elem_type.describe(),
@@ -88,8 +88,8 @@ impl<'a> AnalyzeContext<'a> {
                     ));
                 }
             }
-            Literal::Physical(PhysicalLiteral { ref mut unit, .. }) => {
-                match self.resolve_physical_unit(scope, unit) {
+            Literal::Physical(ref mut physical) => {
+                match self.resolve_physical_unit(scope, &mut physical.unit) {
                     Ok(physical_type) => {
                         if physical_type.base_type() != target_base {
                             diagnostics.push(Diagnostic::type_mismatch(
@@ -97,6 +97,8 @@ impl<'a> AnalyzeContext<'a> {
                                 &physical_type.describe(),
                                 target_type,
                             ))
+                        } else {
+                            self.check_physical_literal(physical_type, pos, physical, diagnostics);
                         }
                     }
                     Err(diagnostic) => {
@@ -106,32 +108,30 @@ impl<'a> AnalyzeContext<'a> {
             }
             Literal::BitString(bitstring) => {
                 if let Some((elem_type, literals)) = as_single_index_enum_array(target_base) {
-                    let needs_1 = bitstring.value.chars().any(|c| *c != b'0');
-                    let c0 = Designator::Character(b'0');
-                    let c1 = Designator::Character(b'1');
-
-                    if !literals.contains(&c0) {
-                        diagnostics.push(Diagnostic::error(
-                            pos,
-                            format!(
-                                "element {} of {} does not define character {}",
-                                elem_type.describe(),
-                                target_type.describe(),
-                                c0
-                            ),
-                        ))
-                    }
+                    match expand_bit_string(bitstring) {
+                        Ok(value) => {
+                            let mut chars: Vec<u8> = value;
+                            chars.sort_unstable();
+                            chars.dedup();
 
-                    if needs_1 && !literals.contains(&c1) {
-                        diagnostics.push(Diagnostic::error(
-                            pos,
-                            format!(
-                                "element {} of {} does not define character {}",
-                                elem_type.describe(),
-                                target_type.describe(),
-                                c1
-                            ),
-                        ))
+                            for chr in chars {
+                                let chr = Designator::Character(chr);
+                                if !literals.contains(&chr) {
+                                    diagnostics.push(Diagnostic::error(
+                                        pos,
+                                        format!(
+                                            "element {} of {} does not define character {}",
+                                            elem_type.describe(),
+                                            target_type.describe(),
+                                            chr
+                                        ),
+                                    ))
+                                }
+                            }
+                        }
+                        Err(msg) => {
+                            diagnostics.error(pos, msg);
+                        }
                     }
                 } else {
                     diagnostics.push(Diagnostic::error(
@@ -183,6 +183,30 @@ impl<'a> AnalyzeContext<'a> {
     }
 }
 
+/// The position of the character at idx within a string literal at pos
+/// Falls back to the position of the whole literal if it does not span the string
+fn string_char_pos(pos: &SrcPos, string: &Latin1String, idx: usize) -> SrcPos {
+    let range = pos.range();
+    // Quotes within the string are doubled in the source
+    let num_quotes = |chars: &[u8]| chars.iter().filter(|chr| **chr == b'"').count() as u32;
+    let source_len = string.len() as u32 + num_quotes(&string.bytes) + 2;
+
+    if range.start.line != range.end.line
+        || range.end.character - range.start.character != source_len
+    {
+        return pos.clone();
+    }
+
+    let chr = string.bytes[idx];
+    let start = Position::new(
+        range.start.line,
+        range.start.character + 1 + idx as u32 + num_quotes(&string.bytes[..idx]),
+    );
+    let end = start.after_char(chr as char);
+    let end = if chr == b'"' { end.next_char() } else { end };
+    pos.source.pos(start, end)
+}
+
 /// Must be an array type with a single index of enum type
 fn as_single_index_enum_array(typ: TypeEnt) -> Option<(TypeEnt, &FnvHashSet<Designator>)> {
     if let Type::Array {
@@ -197,3 +221,126 @@ fn as_single_index_enum_array(typ: TypeEnt) -> Option<(TypeEnt, &FnvHashSet<Desi
     }
     None
 }
+
+/// Expand a bit string literal into the characters of its string value
+/// LRM 15.8 Bit string literals
+pub(crate) fn expand_bit_string(bitstring: &BitString) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = bitstring
+        .value
+        .bytes
+        .iter()
+        .copied()
+        .filter(|chr| *chr != b'_')
+        .collect();
+
+    let (bits_per_digit, base_name) = match bitstring.base {
+        BaseSpecifier::B | BaseSpecifier::UB | BaseSpecifier::SB => (1, "binary"),
+        BaseSpecifier::O | BaseSpecifier::UO | BaseSpecifier::SO => (3, "octal"),
+        BaseSpecifier::X | BaseSpecifier::UX | BaseSpecifier::SX => (4, "hexadecimal"),
+        BaseSpecifier::D => return expand_decimal_bit_string(&digits, bitstring.length),
+    };
+
+    let mut value = Vec::with_capacity(digits.len() * bits_per_digit);
+    for chr in digits {
+        if let Some(digit) = (chr as char).to_digit(16) {
+            if digit >= 1 << bits_per_digit {
+                return Err(format!(
+                    "Illegal digit '{}' in {} bit string literal",
+                    chr as char, base_name
+                ));
+            }
+            for bit in (0..bits_per_digit).rev() {
+                value.push(if digit & (1 << bit) != 0 { b'1' } else { b'0' });
+            }
+        } else {
+            // Other graphic characters such as 'Z' or '-' are replicated
+            value.resize(value.len() + bits_per_digit, chr);
+        }
+    }
+
+    if let Some(length) = bitstring.length {
+        let length = length as usize;
+        let is_signed = matches!(
+            bitstring.base,
+            BaseSpecifier::SB | BaseSpecifier::SO | BaseSpecifier::SX
+        );
+
+        if length > value.len() {
+            let fill = if is_signed {
+                value.first().copied().unwrap_or(b'0')
+            } else {
+                b'0'
+            };
+            let mut expanded = vec![fill; length - value.len()];
+            expanded.extend(value);
+            value = expanded;
+        } else if length < value.len() {
+            let (dropped, kept) = value.split_at(value.len() - length);
+            let fill = if is_signed {
+                kept.first().copied().unwrap_or(b'0')
+            } else {
+                b'0'
+            };
+
+            if dropped.iter().any(|chr| *chr != fill) {
+                return Err(format!(
+                    "Truncating bit string literal to {length} bits would lose significant bits"
+                ));
+            }
+            value = kept.to_vec();
+        }
+    }
+
+    Ok(value)
+}
+
+/// The number of elements of the value of a string or bit string literal
+pub(crate) fn string_literal_length(literal: &Literal) -> Option<usize> {
+    match literal {
+        Literal::String(value) => Some(value.len()),
+        Literal::BitString(bitstring) => expand_bit_string(bitstring).ok().map(|value| value.len()),
+        _ => None,
+    }
+}
+
+/// A decimal bit string literal is the binary representation of its value
+fn expand_decimal_bit_string(digits: &[u8], length: Option<u32>) -> Result<Vec<u8>, String> {
+    let mut decimal = Vec::with_capacity(digits.len());
+    for chr in digits.iter() {
+        if let Some(digit) = (*chr as char).to_digit(10) {
+            decimal.push(digit);
+        } else {
+            return Err(format!(
+                "Illegal digit '{}' in decimal bit string literal",
+                *chr as char
+            ));
+        }
+    }
+
+    // Repeated division by two of the decimal digits yields the bits from least significant
+    let mut value = Vec::new();
+    while decimal.iter().any(|digit| *digit != 0) {
+        let mut remainder = 0;
+        for digit in decimal.iter_mut() {
+            let current = remainder * 10 + *digit;
+            *digit = current / 2;
+            remainder = current % 2;
+        }
+        value.push(if remainder == 1 { b'1' } else { b'0' });
+    }
+    value.reverse();
+
+    if let Some(length) = length {
+        let length = length as usize;
+        if value.len() > length {
+            return Err(format!(
+                "Truncating bit string literal to {length} bits would lose significant bits"
+            ));
+        }
+        let mut expanded = vec![b'0'; length - value.len()];
+        expanded.extend(value);
+        value = expanded;
+    }
+
+    Ok(value)
+}
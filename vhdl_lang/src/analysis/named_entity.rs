@@ -8,8 +8,8 @@ use super::formal_region::FormalRegion;
 use super::region::Region;
 use crate::ast::ExternalObjectClass;
 use crate::ast::{
-    AnyPrimaryUnit, Designator, HasIdent, Ident, ObjectClass, SubprogramDeclaration,
-    SubprogramDesignator, WithDecl,
+    AnyPrimaryUnit, Designator, EntityClassEntry, HasIdent, Ident, ObjectClass,
+    SubprogramDeclaration, SubprogramDesignator, WithDecl,
 };
 use crate::data::*;
 
@@ -17,7 +17,7 @@ mod types;
 pub use types::{BaseType, Subtype, Type, TypeEnt, TypedSelection, UniversalType};
 
 mod overloaded;
-pub use overloaded::{Overloaded, OverloadedEnt, Signature, SignatureKey};
+pub use overloaded::{Overloaded, OverloadedEnt, Signature, SignatureKey, SubprogramDefaultKind};
 
 mod object;
 pub use object::{Object, ObjectEnt};
@@ -25,9 +25,18 @@ pub use object::{Object, ObjectEnt};
 mod design;
 pub use design::{Design, DesignEnt};
 
+mod view;
+pub use view::{View, ViewElementMode, ViewEnt};
+
 mod arena;
 pub use arena::{Arena, ArenaId, EntityId, FinalArena};
 
+mod static_value;
+pub use static_value::{StaticProperty, StaticRange, StaticValue};
+
+mod pretty;
+pub use pretty::PrettyEnt;
+
 pub enum AnyEntKind<'a> {
     ExternalAlias {
         class: ExternalObjectClass,
@@ -41,6 +50,8 @@ pub enum AnyEntKind<'a> {
     InterfaceFile(TypeEnt<'a>),
     Component(Region<'a>),
     Attribute(TypeEnt<'a>),
+    GroupTemplate(Vec<EntityClassEntry>),
+    Group,
     Overloaded(Overloaded<'a>),
     Type(Type<'a>),
     ElementDeclaration(Subtype<'a>),
@@ -51,6 +62,7 @@ pub enum AnyEntKind<'a> {
     DeferredConstant(Subtype<'a>),
     Library,
     Design(Design<'a>),
+    View(View<'a>),
 }
 
 impl<'a> AnyEntKind<'a> {
@@ -101,6 +113,8 @@ impl<'a> AnyEntKind<'a> {
             ElementDeclaration(..) => "element declaration",
             Component(..) => "component",
             Attribute(..) => "attribute",
+            GroupTemplate(..) => "group template",
+            Group => "group",
             Overloaded(overloaded) => overloaded.describe(),
             Label => "label",
             LoopParameter(_) => "loop parameter",
@@ -110,6 +124,7 @@ impl<'a> AnyEntKind<'a> {
             Library => "library",
             Design(design) => design.describe(),
             Type(typ) => typ.describe(),
+            View(..) => "view",
         }
     }
 }
@@ -127,6 +142,8 @@ impl<'a> std::fmt::Debug for AnyEnt<'a> {
             id,
             related,
             implicits,
+            attributes,
+            static_property,
             designator,
             kind,
             decl_pos,
@@ -136,6 +153,8 @@ impl<'a> std::fmt::Debug for AnyEnt<'a> {
         s.field(stringify!(id), id);
         s.field(stringify!(related), related);
         s.field(stringify!(implicits), &implicits.len());
+        s.field(stringify!(attributes), &attributes.len());
+        s.field(stringify!(static_property), static_property);
         s.field(stringify!(designator), designator);
         s.field(stringify!(kind), kind);
         s.field(stringify!(decl_pos), decl_pos);
@@ -161,6 +180,11 @@ pub struct AnyEnt<'a> {
     pub id: EntityId,
     pub related: Related<'a>,
     pub implicits: Vec<EntRef<'a>>,
+    /// User defined attributes given a value by an attribute specification
+    /// together with the position of the value expression
+    pub attributes: Vec<(EntRef<'a>, SrcPos)>,
+    /// The locally static value or ranges of the declaration if they could be evaluated
+    pub static_property: Option<StaticProperty>,
     /// The location where the declaration was made.
     /// Builtin and implicit declaration will not have a source position.
     pub designator: Designator,
@@ -231,6 +255,24 @@ impl<'a> AnyEnt<'a> {
         )
     }
 
+    pub fn is_uninst_subprogram(&self) -> bool {
+        matches!(
+            self.kind,
+            AnyEntKind::Overloaded(Overloaded::UninstSubprogram(..))
+        )
+    }
+
+    pub fn is_uninst_subprogram_decl(&self) -> bool {
+        matches!(
+            self.kind,
+            AnyEntKind::Overloaded(Overloaded::UninstSubprogramDecl(..))
+        )
+    }
+
+    pub fn is_library(&self) -> bool {
+        matches!(self.kind, AnyEntKind::Library)
+    }
+
     pub fn is_explicit(&self) -> bool {
         !self.is_implicit()
     }
@@ -276,6 +318,49 @@ impl<'a> AnyEnt<'a> {
         self.implicits.push(ent);
     }
 
+    pub(crate) fn add_attribute(&mut self, attribute: EntRef<'a>, pos: SrcPos) {
+        self.attributes.push((attribute, pos));
+    }
+
+    pub(crate) fn set_static_property(&mut self, property: StaticProperty) {
+        self.static_property = Some(property);
+    }
+
+    /// The static value of a constant, enumeration literal or physical unit
+    pub fn static_value(&self) -> Option<StaticValue> {
+        if let Some(StaticProperty::Value(value)) = self.static_property {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// The static range of a scalar subtype or the index ranges of a constrained array subtype
+    pub fn static_ranges(&self) -> Option<&[StaticRange]> {
+        if let Some(StaticProperty::Ranges(ref ranges)) = self.static_property {
+            Some(ranges)
+        } else {
+            None
+        }
+    }
+
+    /// The static default value of a generic
+    pub fn static_default(&self) -> Option<StaticValue> {
+        if let Some(StaticProperty::Default(value)) = self.static_property {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// The position of the value given to a user defined attribute of this entity
+    pub fn attribute_value(&self, attribute: &AnyEnt) -> Option<&SrcPos> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| attr.id() == attribute.id())
+            .map(|(_, pos)| pos)
+    }
+
     /// Strip aliases and return reference to actual entity kind
     pub fn actual_kind(&self) -> &AnyEntKind {
         self.as_actual().kind()
@@ -19,6 +19,7 @@ use super::AnyEnt;
 use super::AnyEntKind;
 use super::EntRef;
 use super::Related;
+use super::StaticProperty;
 use super::TypeEnt;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -160,6 +161,8 @@ impl Arena {
             id: EntityId::undefined(),
             related,
             implicits: Vec::new(),
+            attributes: Vec::new(),
+            static_property: None,
             designator,
             kind,
             decl_pos,
@@ -188,6 +191,8 @@ impl Arena {
                 id,
                 related,
                 implicits: Vec::new(),
+                attributes: Vec::new(),
+                static_property: None,
                 designator,
                 kind,
                 decl_pos,
@@ -207,6 +212,38 @@ impl Arena {
         }
     }
 
+    pub(crate) unsafe fn add_attribute<'a>(
+        &'a self,
+        id: EntityId,
+        attribute: EntRef<'a>,
+        pos: SrcPos,
+    ) {
+        let local = self.local.borrow_mut();
+        assert_eq!(id.arena_id(), local.id);
+        let p = &mut *self.local.as_ptr() as &mut LocalArena;
+        let eref = p.get_mut(id.local_id());
+        unsafe {
+            let eref: &mut AnyEnt = &mut *eref as &mut AnyEnt;
+            eref.add_attribute(attribute, pos);
+        }
+    }
+
+    pub(crate) unsafe fn set_static_property(&self, id: EntityId, property: StaticProperty) {
+        let local = self.local.borrow_mut();
+        assert_eq!(id.arena_id(), local.id);
+        let p = &mut *self.local.as_ptr() as &mut LocalArena;
+        let eref = p.get_mut(id.local_id());
+        unsafe {
+            let eref: &mut AnyEnt = &mut *eref as &mut AnyEnt;
+            eref.set_static_property(property);
+        }
+    }
+
+    /// Returns true if the entity was allocated in the local arena
+    pub fn is_local(&self, id: EntityId) -> bool {
+        self.local.borrow().id == id.arena_id()
+    }
+
     pub fn get<'a>(&'a self, id: EntityId) -> EntRef<'a> {
         // Since local uses PinnedVec we do not have to worry about
         // returning a pure reference here since allocating new
@@ -21,7 +21,8 @@ use crate::SrcPos;
 
 pub enum Design<'a> {
     Entity(Visibility<'a>, Region<'a>),
-    Configuration,
+    /// The configured entity, if it could be resolved
+    Configuration(Option<DesignEnt<'a>>),
     Package(Visibility<'a>, Region<'a>),
     UninstPackage(Visibility<'a>, Region<'a>),
     PackageInstance(Region<'a>),
@@ -33,7 +34,7 @@ impl<'a> Design<'a> {
         use Design::*;
         match self {
             Entity(..) => "entity",
-            Configuration => "configuration",
+            Configuration(..) => "configuration",
             Package(..) => "package",
             UninstPackage(..) => "uninstantiated package",
             PackageInstance(..) => "package instance",
@@ -14,6 +14,7 @@ use super::AnyEnt;
 use super::EntRef;
 use super::Subtype;
 use super::TypeEnt;
+use super::ViewEnt;
 
 // A named entity that is known to be an object
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -84,6 +85,9 @@ pub struct Object<'a> {
     pub mode: Option<Mode>,
     pub subtype: Subtype<'a>,
     pub has_default: bool,
+    /// The mode view of a port with a mode view indication, the mode is then
+    /// the mode of the record as a whole
+    pub view: Option<ViewEnt<'a>>,
 }
 
 impl ObjectClass {
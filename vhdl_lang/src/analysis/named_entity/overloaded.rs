@@ -10,28 +10,50 @@ use super::EntRef;
 use super::EntityId;
 use super::TypeEnt;
 use crate::analysis::formal_region::FormalRegion;
+use crate::analysis::formal_region::GpkgRegion;
 use crate::analysis::formal_region::InterfaceEnt;
 use crate::ast::Designator;
 
 pub enum Overloaded<'a> {
     SubprogramDecl(Signature<'a>),
     Subprogram(Signature<'a>),
-    InterfaceSubprogram(Signature<'a>),
+    InterfaceSubprogram(Signature<'a>, SubprogramDefaultKind),
     EnumLiteral(Signature<'a>),
     Alias(OverloadedEnt<'a>),
+    /// Subprogram declaration with a generic clause, LRM 4.2.1
+    UninstSubprogramDecl(Signature<'a>, GpkgRegion<'a>),
+    /// Subprogram body with a generic clause, LRM 4.2.1
+    UninstSubprogram(Signature<'a>, GpkgRegion<'a>),
+}
+
+/// The kind of default of an interface subprogram, LRM 6.5.4
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubprogramDefaultKind {
+    None,
+    Name,
+    Box,
 }
 
 impl<'a> Overloaded<'a> {
     pub fn describe(&self) -> &'static str {
         use Overloaded::*;
         match self {
-            SubprogramDecl(signature) | Subprogram(signature) | InterfaceSubprogram(signature) => {
+            SubprogramDecl(signature)
+            | Subprogram(signature)
+            | InterfaceSubprogram(signature, _) => {
                 if signature.return_type().is_some() {
                     "function"
                 } else {
                     "procedure"
                 }
             }
+            UninstSubprogramDecl(signature, _) | UninstSubprogram(signature, _) => {
+                if signature.return_type().is_some() {
+                    "uninstantiated function"
+                } else {
+                    "uninstantiated procedure"
+                }
+            }
             EnumLiteral(..) => "enum literal",
             Alias(..) => "alias",
         }
@@ -39,13 +61,25 @@ impl<'a> Overloaded<'a> {
 
     pub fn signature(&'a self) -> &'a Signature<'a> {
         match self {
-            Overloaded::InterfaceSubprogram(ref signature)
+            Overloaded::InterfaceSubprogram(ref signature, _)
             | Overloaded::Subprogram(ref signature)
             | Overloaded::SubprogramDecl(ref signature)
-            | Overloaded::EnumLiteral(ref signature) => signature,
+            | Overloaded::EnumLiteral(ref signature)
+            | Overloaded::UninstSubprogramDecl(ref signature, _)
+            | Overloaded::UninstSubprogram(ref signature, _) => signature,
             Overloaded::Alias(ref overloaded) => overloaded.signature(),
         }
     }
+
+    /// The generics of an uninstantiated subprogram
+    pub fn generics(&'a self) -> Option<&'a GpkgRegion<'a>> {
+        match self {
+            Overloaded::UninstSubprogramDecl(_, ref generics)
+            | Overloaded::UninstSubprogram(_, ref generics) => Some(generics),
+            Overloaded::Alias(ref overloaded) => overloaded.kind().generics(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -53,6 +87,8 @@ pub struct Signature<'a> {
     /// Vector of InterfaceObject or InterfaceFile
     pub(crate) formals: FormalRegion<'a>,
     pub(crate) return_type: Option<TypeEnt<'a>>,
+    /// True for functions declared impure
+    pub(crate) impure: bool,
 }
 
 impl<'a> Signature<'a> {
@@ -60,9 +96,19 @@ impl<'a> Signature<'a> {
         Signature {
             formals,
             return_type: return_type.as_ref().map(TypeEnt::to_owned),
+            impure: false,
         }
     }
 
+    pub fn with_impure(self, impure: bool) -> Signature<'a> {
+        Signature { impure, ..self }
+    }
+
+    /// Returns true if the signature is of a function declared impure
+    pub fn is_impure(&self) -> bool {
+        self.impure
+    }
+
     pub fn key(&self) -> SignatureKey {
         let formals = self
             .formals
@@ -187,6 +233,11 @@ impl<'a> OverloadedEnt<'a> {
         self.return_type().is_some()
     }
 
+    /// Returns true for subprograms with a generic clause that must be instantiated before use
+    pub fn is_uninstantiated(&self) -> bool {
+        self.kind().generics().is_some()
+    }
+
     pub fn formals(&self) -> &'a FormalRegion<'a> {
         &self.signature().formals
     }
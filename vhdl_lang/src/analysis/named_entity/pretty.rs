@@ -0,0 +1,425 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Rendering of analyzed named entities back into VHDL declarations
+
+use std::fmt::{Display, Formatter, Result};
+
+use super::*;
+use crate::analysis::static_expression::{format_static_range, format_static_value};
+use crate::ast::{InterfaceListType, Mode};
+
+/// Renders a named entity as a VHDL declaration from the information known after analysis
+/// Entities that have no declaration in VHDL such as labels are rendered as a comment
+pub struct PrettyEnt<'a>(EntRef<'a>);
+
+impl<'a> AnyEnt<'a> {
+    pub fn pretty(&'a self) -> PrettyEnt<'a> {
+        PrettyEnt(self)
+    }
+
+    /// An instantiation of an entity or component with all generics and ports associated by name
+    /// to actuals of the same name
+    pub fn instantiation_template(&'a self, label: &str) -> Option<String> {
+        let (unit, region) = match self.kind() {
+            AnyEntKind::Design(Design::Entity(_, region)) => {
+                (format!("entity work.{}", self.designator), region)
+            }
+            AnyEntKind::Component(region) => (self.designator.to_string(), region),
+            _ => return None,
+        };
+
+        let (generics, ports) = region.to_entity_formal();
+        let mut result = format!("{label} : {unit}");
+        for (list, map) in [(&generics, "generic map"), (&ports, "port map")] {
+            if list.is_empty() {
+                continue;
+            }
+            result.push_str(&format!("\n  {map} ("));
+            for (i, formal) in list.iter().enumerate() {
+                let separator = if i + 1 < list.len() { "," } else { "" };
+                result.push_str(&format!(
+                    "\n    {} => {}{}",
+                    formal.designator(),
+                    formal.designator(),
+                    separator
+                ));
+            }
+            result.push_str("\n  )");
+        }
+        result.push(';');
+        Some(result)
+    }
+}
+
+impl<'a> Display for PrettyEnt<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let ent = self.0;
+        let designator = ent.designator();
+        match ent.kind() {
+            AnyEntKind::Library => write!(f, "library {designator};"),
+            AnyEntKind::Object(object) => {
+                if object.mode.is_some() {
+                    write!(f, "{};", interface(ent, None))
+                } else {
+                    write!(
+                        f,
+                        "{} {} : {}{};",
+                        object.class,
+                        designator,
+                        subtype(ent, object.subtype.type_mark()),
+                        default(ent, object.subtype.type_mark())
+                    )
+                }
+            }
+            AnyEntKind::InterfaceFile(..) => write!(f, "{};", interface(ent, None)),
+            AnyEntKind::DeferredConstant(sub) => {
+                write!(
+                    f,
+                    "constant {designator} : {};",
+                    subtype(ent, sub.type_mark())
+                )
+            }
+            AnyEntKind::File(sub) => {
+                write!(f, "file {designator} : {};", subtype(ent, sub.type_mark()))
+            }
+            AnyEntKind::ElementDeclaration(sub) => {
+                write!(f, "{designator} : {};", subtype(ent, sub.type_mark()))
+            }
+            AnyEntKind::ObjectAlias {
+                base_object,
+                type_mark,
+            } => write!(
+                f,
+                "alias {designator} : {} is {};",
+                type_mark.designator(),
+                base_object.designator()
+            ),
+            AnyEntKind::Attribute(typ) => {
+                write!(f, "attribute {designator} : {};", typ.designator())
+            }
+            AnyEntKind::GroupTemplate(entries) => {
+                write!(f, "group {designator} is (")?;
+                for (i, entry) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{entry}")?;
+                }
+                write!(f, ");")
+            }
+            AnyEntKind::Overloaded(overloaded) => match overloaded {
+                Overloaded::SubprogramDecl(signature)
+                | Overloaded::Subprogram(signature)
+                | Overloaded::InterfaceSubprogram(signature, _)
+                | Overloaded::UninstSubprogramDecl(signature, _)
+                | Overloaded::UninstSubprogram(signature, _) => {
+                    write!(f, "{};", subprogram(designator, signature))
+                }
+                Overloaded::Alias(aliased) => write!(
+                    f,
+                    "alias {designator} is {}{};",
+                    aliased.designator(),
+                    aliased.signature().describe()
+                ),
+                Overloaded::EnumLiteral(..) => write!(f, "{designator}"),
+            },
+            AnyEntKind::PhysicalLiteral(typ) => write!(f, "{};", unit(ent, *typ)),
+            AnyEntKind::Component(region) => {
+                write!(f, "component {designator} is")?;
+                formals(f, region)?;
+                write!(f, "\nend component;")
+            }
+            AnyEntKind::Design(Design::Entity(_, region)) => {
+                write!(f, "entity {designator} is")?;
+                formals(f, region)?;
+                write!(f, "\nend entity;")
+            }
+            AnyEntKind::Type(..) => match TypeEnt::from_any(ent) {
+                Some(typ) => type_declaration(f, typ),
+                None => unreachable!(),
+            },
+            AnyEntKind::LoopParameter(Some(typ)) => match ent.static_property {
+                Some(StaticProperty::Ranges(ref ranges)) if ranges.len() == 1 => write!(
+                    f,
+                    "{designator} in {}",
+                    format_static_range(TypeEnt::from(*typ), &ranges[0])
+                ),
+                _ => write!(f, "-- {}", ent.describe()),
+            },
+            AnyEntKind::View(view) => {
+                write!(f, "view {designator} of {} is", view.typ.designator())?;
+                for (elem, mode) in view.elements.iter() {
+                    write!(f, "\n  {} : ", elem.designator())?;
+                    match mode {
+                        ViewElementMode::Simple(mode) => write!(f, "{mode};")?,
+                        ViewElementMode::Record(view) => write!(f, "view {};", view.designator())?,
+                        ViewElementMode::Array(view) => write!(f, "view ({});", view.designator())?,
+                    }
+                }
+                write!(f, "\nend view;")
+            }
+            AnyEntKind::ExternalAlias { .. }
+            | AnyEntKind::Label
+            | AnyEntKind::Group
+            | AnyEntKind::LoopParameter(None)
+            | AnyEntKind::Design(..) => write!(f, "-- {}", ent.describe()),
+        }
+    }
+}
+
+fn type_declaration(f: &mut Formatter<'_>, ent: TypeEnt) -> Result {
+    let designator = ent.designator();
+    match ent.kind() {
+        Type::Enum(..) => {
+            let mut literals: Vec<_> = ent
+                .implicits
+                .iter()
+                .filter_map(|literal| Some((literal.static_value()?, literal.designator())))
+                .collect();
+            literals.sort_by_key(|(value, _)| value.as_discrete());
+            write!(f, "type {designator} is (")?;
+            for (i, (_, literal)) in literals.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{literal}")?;
+            }
+            write!(f, ");")
+        }
+        Type::Integer | Type::Real | Type::Physical => {
+            let Some([range]) = ent.static_ranges() else {
+                return write!(f, "-- {}", ent.describe());
+            };
+            if let Type::Physical = ent.kind() {
+                // The range of a physical type is written as integers
+                let bound = |value: StaticValue| physical(&value).unwrap_or_default();
+                write!(
+                    f,
+                    "type {designator} is range {} {} {}",
+                    bound(range.left),
+                    range.direction,
+                    bound(range.right)
+                )?;
+                let mut units: Vec<_> = ent
+                    .implicits
+                    .iter()
+                    .filter(|unit| matches!(unit.kind(), AnyEntKind::PhysicalLiteral(..)))
+                    .collect();
+                units.sort_by_key(|unit| unit.static_value().and_then(|value| physical(&value)));
+                write!(f, "\n  units")?;
+                for literal in units {
+                    write!(f, "\n    {};", unit(literal, ent))?;
+                }
+                write!(f, "\n  end units;")
+            } else {
+                write!(
+                    f,
+                    "type {designator} is range {};",
+                    format_static_range(ent, range)
+                )
+            }
+        }
+        Type::Array { indexes, elem_type } => {
+            write!(f, "type {designator} is array (")?;
+            let ranges = ent.static_ranges();
+            for (i, index) in indexes.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                let Some(index) = index else {
+                    write!(f, "<>")?;
+                    continue;
+                };
+                match ranges.and_then(|ranges| ranges.get(i)) {
+                    Some(range) => {
+                        write!(f, "{}", format_static_range(TypeEnt::from(*index), range))?
+                    }
+                    None => write!(f, "{} range <>", index.designator())?,
+                }
+            }
+            write!(f, ") of {};", elem_type.designator())
+        }
+        Type::Record(elements) => {
+            write!(f, "type {designator} is record")?;
+            for elem in elements.iter() {
+                write!(f, "\n  {}", PrettyEnt(elem.into()))?;
+            }
+            write!(f, "\nend record;")
+        }
+        Type::Subtype(sub) => {
+            write!(
+                f,
+                "subtype {designator} is {};",
+                subtype(&ent, sub.type_mark())
+            )
+        }
+        Type::Access(sub) => write!(
+            f,
+            "type {designator} is access {};",
+            sub.type_mark().designator()
+        ),
+        Type::Alias(aliased) => write!(f, "alias {designator} is {};", aliased.designator()),
+        Type::Incomplete => write!(f, "type {designator};"),
+        Type::Interface => write!(f, "type {designator}"),
+        Type::Protected(..) | Type::File | Type::Universal(..) => {
+            write!(f, "-- {}", ent.describe())
+        }
+    }
+}
+
+/// A function or procedure specification
+fn subprogram(designator: &Designator, signature: &Signature) -> String {
+    let mut result = if signature.return_type().is_some() {
+        format!("function {designator}")
+    } else {
+        format!("procedure {designator}")
+    };
+    for (i, formal) in signature.formals.iter().enumerate() {
+        result.push_str(if i == 0 { "(\n  " } else { ";\n  " });
+        result.push_str(&interface(
+            formal.inner(),
+            Some(InterfaceListType::Parameter),
+        ));
+    }
+    if !signature.formals.is_empty() {
+        result.push_str("\n)");
+    }
+    if let Some(return_type) = signature.return_type() {
+        result.push_str(&format!(" return {}", return_type.designator()));
+    }
+    result
+}
+
+/// The generic and port clauses of an entity or component
+fn formals(f: &mut Formatter<'_>, region: &Region) -> Result {
+    let (generics, ports) = region.to_entity_formal();
+    for (list, clause) in [(&generics, "generic"), (&ports, "port")] {
+        for (i, formal) in list.iter().enumerate() {
+            if i == 0 {
+                write!(f, "\n  {clause} (\n    ")?;
+            } else {
+                write!(f, ";\n    ")?;
+            }
+            write!(f, "{}", interface(formal.inner(), Some(list.typ)))?;
+        }
+        if !list.is_empty() {
+            write!(f, "\n  );")?;
+        }
+    }
+    Ok(())
+}
+
+/// An interface declaration as written in a list of the given type
+/// Without a list type the type is guessed from the class of the object
+fn interface(ent: EntRef, list_type: Option<InterfaceListType>) -> String {
+    let designator = ent.designator();
+    let object = match ent.kind() {
+        AnyEntKind::Object(object) => object,
+        AnyEntKind::InterfaceFile(typ) => {
+            return format!("file {designator} : {}", typ.designator())
+        }
+        _ => return designator.to_string(),
+    };
+
+    if let Some(view) = object.view {
+        let type_mark = object.subtype.type_mark();
+        return if view.type_mark() == type_mark {
+            format!("{designator} : view {}", view.designator())
+        } else {
+            format!(
+                "{designator} : view ({}) of {}",
+                view.designator(),
+                subtype(ent, type_mark)
+            )
+        };
+    }
+
+    let list_type = list_type.unwrap_or(match object.class {
+        ObjectClass::Signal => InterfaceListType::Port,
+        ObjectClass::Constant => InterfaceListType::Generic,
+        ObjectClass::Variable | ObjectClass::SharedVariable => InterfaceListType::Parameter,
+    });
+    let mode = object.mode.unwrap_or(Mode::In);
+    let type_mark = object.subtype.type_mark();
+    let subtype = subtype(ent, type_mark);
+    let default = default(ent, type_mark);
+
+    match list_type {
+        InterfaceListType::Port => format!("{designator} : {mode} {subtype}{default}"),
+        InterfaceListType::Generic => format!("{designator} : {subtype}{default}"),
+        InterfaceListType::Parameter => {
+            format!("{} {designator} : {mode} {subtype}{default}", object.class)
+        }
+    }
+}
+
+/// The subtype indication of a declaration with the type mark and its constraint if any
+fn subtype(ent: EntRef, type_mark: TypeEnt) -> String {
+    let mut result = type_mark.designator().to_string();
+    match ent.static_property {
+        Some(StaticProperty::Generic {
+            constraint: Some(ref constraint),
+            ..
+        }) => result.push_str(&constraint.to_string()),
+        Some(StaticProperty::Ranges(ref ranges))
+            if type_mark.static_ranges() != Some(ranges.as_slice()) =>
+        {
+            if let Type::Array { indexes, .. } = type_mark.base_type().kind() {
+                result.push('(');
+                for (i, range) in ranges.iter().enumerate() {
+                    if i > 0 {
+                        result.push_str(", ");
+                    }
+                    let index_type = indexes
+                        .get(i)
+                        .copied()
+                        .flatten()
+                        .map(TypeEnt::from)
+                        .unwrap_or(type_mark);
+                    result.push_str(&format_static_range(index_type, range));
+                }
+                result.push(')');
+            } else if let [range] = ranges.as_slice() {
+                result.push_str(&format!(" range {}", format_static_range(type_mark, range)));
+            }
+        }
+        _ => {}
+    }
+    result
+}
+
+/// The default value of an object when it is known
+fn default(ent: EntRef, type_mark: TypeEnt) -> String {
+    match ent.static_property {
+        Some(StaticProperty::Value(value)) | Some(StaticProperty::Default(value)) => {
+            format!(" := {}", format_static_value(type_mark, value))
+        }
+        Some(StaticProperty::Generic {
+            default: Some(ref default),
+            ..
+        }) => format!(" := {}", default.item),
+        _ => String::new(),
+    }
+}
+
+/// A unit declaration of a physical type
+fn unit(ent: EntRef, typ: TypeEnt) -> String {
+    match ent.static_value() {
+        Some(value) if physical(&value) != Some(1) => {
+            format!("{} = {}", ent.designator(), format_static_value(typ, value))
+        }
+        _ => ent.designator().to_string(),
+    }
+}
+
+fn physical(value: &StaticValue) -> Option<i64> {
+    if let StaticValue::Physical(value) = value {
+        Some(*value)
+    } else {
+        None
+    }
+}
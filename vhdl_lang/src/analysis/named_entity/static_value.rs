@@ -0,0 +1,136 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::ast::{Direction, Expression, SubtypeConstraint};
+use crate::data::WithPos;
+
+/// The value of a locally static expression
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StaticValue {
+    Integer(i64),
+    Real(f64),
+    /// The position number of an enumeration literal
+    Enum(i64),
+    /// The value in multiples of the primary unit of the physical type
+    Physical(i64),
+}
+
+impl StaticValue {
+    /// The position number of a discrete value
+    pub fn as_discrete(&self) -> Option<i64> {
+        match self {
+            StaticValue::Integer(value) | StaticValue::Enum(value) => Some(*value),
+            StaticValue::Real(_) | StaticValue::Physical(_) => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            StaticValue::Enum(0) => Some(false),
+            StaticValue::Enum(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_bool(value: bool) -> StaticValue {
+        StaticValue::Enum(i64::from(value))
+    }
+
+    /// A value of the same kind with another position number or magnitude
+    pub(crate) fn with_integer(&self, value: i64) -> Option<StaticValue> {
+        match self {
+            StaticValue::Integer(_) => Some(StaticValue::Integer(value)),
+            StaticValue::Enum(_) => Some(StaticValue::Enum(value)),
+            StaticValue::Physical(_) => Some(StaticValue::Physical(value)),
+            StaticValue::Real(_) => None,
+        }
+    }
+
+    /// Values of different kinds are not ordered
+    pub(crate) fn compare(&self, other: &StaticValue) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (StaticValue::Integer(left), StaticValue::Integer(right))
+            | (StaticValue::Enum(left), StaticValue::Enum(right))
+            | (StaticValue::Physical(left), StaticValue::Physical(right)) => Some(left.cmp(right)),
+            (StaticValue::Real(left), StaticValue::Real(right)) => left.partial_cmp(right),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn less_than(&self, other: &StaticValue) -> Option<bool> {
+        Some(self.compare(other)? == std::cmp::Ordering::Less)
+    }
+}
+
+/// A range with locally static bounds
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StaticRange {
+    pub left: StaticValue,
+    pub right: StaticValue,
+    pub direction: Direction,
+}
+
+impl StaticRange {
+    pub fn new(left: StaticValue, right: StaticValue, direction: Direction) -> StaticRange {
+        StaticRange {
+            left,
+            right,
+            direction,
+        }
+    }
+
+    pub fn low(&self) -> StaticValue {
+        match self.direction {
+            Direction::Ascending => self.left,
+            Direction::Descending => self.right,
+        }
+    }
+
+    pub fn high(&self) -> StaticValue {
+        match self.direction {
+            Direction::Ascending => self.right,
+            Direction::Descending => self.left,
+        }
+    }
+
+    /// A null range is a range where the bounds are in the opposite order of the direction
+    pub fn is_null(&self) -> bool {
+        self.high().less_than(&self.low()).unwrap_or(false)
+    }
+
+    /// The number of values of a discrete range
+    pub fn length(&self) -> Option<i64> {
+        if self.is_null() {
+            return Some(0);
+        }
+        let low = self.low().as_discrete()?;
+        let high = self.high().as_discrete()?;
+        high.checked_sub(low)?.checked_add(1)
+    }
+
+    /// Returns None when the value is not of the same kind as the bounds of the range
+    pub fn contains(&self, value: &StaticValue) -> Option<bool> {
+        Some(!value.less_than(&self.low())? && !self.high().less_than(value)?)
+    }
+}
+
+/// The locally static information known about a declaration
+#[derive(Clone, PartialEq, Debug)]
+pub enum StaticProperty {
+    /// The value of a constant, enumeration literal or physical unit
+    Value(StaticValue),
+    /// The range of a scalar subtype or the index ranges of a constrained array subtype
+    Ranges(Vec<StaticRange>),
+    /// The static default value of a generic, which is not static itself since
+    /// it may be given another value by the generic map of an instance
+    Default(StaticValue),
+    /// The constraint and default value of a port when either of them depends on generics
+    /// They are evaluated at instantiation sites where the values of the generics are known
+    Generic {
+        constraint: Option<Box<SubtypeConstraint>>,
+        default: Option<Box<WithPos<Expression>>>,
+    },
+}
@@ -231,6 +231,23 @@ impl<'a> TypeEnt<'a> {
         }
     }
 
+    /// The type is a resolved subtype or all of its elements are resolved
+    pub fn is_resolved(&self) -> bool {
+        match self.kind() {
+            Type::Subtype(subtype) => subtype.is_resolved(),
+            Type::Alias(alias) => alias.is_resolved(),
+            Type::Array { elem_type, .. } => elem_type.is_resolved(),
+            Type::Record(region) => {
+                region.iter().next().is_some()
+                    && region.iter().all(|elem| match elem.kind() {
+                        AnyEntKind::ElementDeclaration(subtype) => subtype.is_resolved(),
+                        _ => false,
+                    })
+            }
+            _ => false,
+        }
+    }
+
     // @TODO used to skip things from instantiated packages which we cannot handle yet
     pub fn is_generic(&self) -> bool {
         matches!(self.base_type().kind(), Type::Interface)
@@ -407,11 +424,26 @@ impl<'a> Deref for BaseType<'a> {
 #[derive(Clone, Copy)]
 pub struct Subtype<'a> {
     pub(crate) type_mark: TypeEnt<'a>,
+    /// The subtype indication has a resolution indication
+    pub(crate) resolved: bool,
 }
 
 impl<'a> Subtype<'a> {
     pub fn new(type_mark: TypeEnt<'a>) -> Subtype<'a> {
-        Subtype { type_mark }
+        Subtype {
+            type_mark,
+            resolved: false,
+        }
+    }
+
+    pub fn with_resolved(mut self, resolved: bool) -> Subtype<'a> {
+        self.resolved = resolved;
+        self
+    }
+
+    /// The subtype or all of its elements have a resolution function
+    pub fn is_resolved(&self) -> bool {
+        self.resolved || self.type_mark.is_resolved()
     }
 
     pub fn type_mark(&self) -> TypeEnt<'a> {
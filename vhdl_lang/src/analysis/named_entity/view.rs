@@ -0,0 +1,94 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use std::ops::Deref;
+
+use crate::ast::{Designator, Mode};
+
+use super::AnyEnt;
+use super::AnyEntKind;
+use super::EntRef;
+use super::TypeEnt;
+
+/// A mode view of a record type, LRM 6.5.2
+#[derive(Clone)]
+pub struct View<'a> {
+    /// The record type
+    pub typ: TypeEnt<'a>,
+    /// The mode of each element declaration of the record type
+    pub elements: Vec<(EntRef<'a>, ViewElementMode<'a>)>,
+}
+
+/// The mode of a record element within a mode view
+#[derive(Clone, Copy)]
+pub enum ViewElementMode<'a> {
+    Simple(Mode),
+    /// A record element with a mode view of its own
+    Record(ViewEnt<'a>),
+    /// An array element whose elements have a mode view
+    Array(ViewEnt<'a>),
+}
+
+// A named entity that is known to be a mode view
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewEnt<'a> {
+    pub ent: EntRef<'a>,
+}
+
+impl<'a> ViewEnt<'a> {
+    pub fn from_any(ent: &'a AnyEnt) -> Option<Self> {
+        if matches!(ent.actual_kind(), AnyEntKind::View(..)) {
+            Some(Self { ent })
+        } else {
+            None
+        }
+    }
+
+    pub fn view(&self) -> &'a View<'a> {
+        if let AnyEntKind::View(view) = self.ent.actual_kind() {
+            view
+        } else {
+            unreachable!("ViewEnt type invariant broken")
+        }
+    }
+
+    pub fn type_mark(&self) -> TypeEnt<'a> {
+        self.view().typ
+    }
+
+    pub fn element_mode(&self, designator: &Designator) -> Option<ViewElementMode<'a>> {
+        self.view()
+            .elements
+            .iter()
+            .find(|(elem, _)| elem.designator() == designator)
+            .map(|(_, mode)| *mode)
+    }
+
+    /// The mode of the record as a whole: in or out when all elements are of that mode
+    /// and inout otherwise
+    pub fn mode(&self) -> Mode {
+        let mut modes = self.view().elements.iter().map(|(_, mode)| match mode {
+            ViewElementMode::Simple(Mode::Buffer) => Mode::Out,
+            ViewElementMode::Simple(mode) => *mode,
+            ViewElementMode::Record(view) | ViewElementMode::Array(view) => view.mode(),
+        });
+        let Some(first) = modes.next() else {
+            return Mode::InOut;
+        };
+        if matches!(first, Mode::In | Mode::Out) && modes.all(|mode| mode == first) {
+            first
+        } else {
+            Mode::InOut
+        }
+    }
+}
+
+impl<'a> Deref for ViewEnt<'a> {
+    type Target = EntRef<'a>;
+    fn deref(&self) -> &Self::Target {
+        &self.ent
+    }
+}
@@ -152,6 +152,9 @@ impl<'a> ResolvedName<'a> {
             AnyEntKind::File(_)
             | AnyEntKind::InterfaceFile(_)
             | AnyEntKind::Component(_)
+            | AnyEntKind::GroupTemplate(_)
+            | AnyEntKind::Group
+            | AnyEntKind::View(_)
             | AnyEntKind::PhysicalLiteral(_) => ResolvedName::Final(ent),
             AnyEntKind::Design(_)
             | AnyEntKind::Library
@@ -207,6 +210,9 @@ impl<'a> ResolvedName<'a> {
             AnyEntKind::File(_)
             | AnyEntKind::InterfaceFile(_)
             | AnyEntKind::Component(_)
+            | AnyEntKind::GroupTemplate(_)
+            | AnyEntKind::Group
+            | AnyEntKind::View(_)
             | AnyEntKind::Label
             | AnyEntKind::LoopParameter(_)
             | AnyEntKind::PhysicalLiteral(_) => ResolvedName::Final(ent),
@@ -334,12 +340,11 @@ impl<'a> AnalyzeContext<'a> {
         name: ResolvedName<'a>,
     ) -> Result<Option<DisambiguatedType<'a>>, Diagnostic> {
         match name {
-            ResolvedName::Library(_) | ResolvedName::Design(_) | ResolvedName::Type(_) => {
-                Err(Diagnostic::error(
-                    pos,
-                    format!("{} cannot be used in an expression", name.describe_type()),
-                ))
-            }
+            ResolvedName::Type(typ) => Err(Diagnostic::type_as_value(pos, typ)),
+            ResolvedName::Library(_) | ResolvedName::Design(_) => Err(Diagnostic::error(
+                pos,
+                format!("{} cannot be used in an expression", name.describe_type()),
+            )),
             ResolvedName::Final(ent) => match ent.actual_kind() {
                 AnyEntKind::LoopParameter(typ) => {
                     Ok(typ.map(|typ| DisambiguatedType::Unambiguous(typ.into())))
@@ -382,12 +387,11 @@ impl<'a> AnalyzeContext<'a> {
         suffix_ref: Option<&mut Reference>,
     ) -> Result<Option<TypeEnt<'a>>, Diagnostic> {
         match name {
-            ResolvedName::Library(_) | ResolvedName::Design(_) | ResolvedName::Type(_) => {
-                Err(Diagnostic::error(
-                    pos,
-                    format!("{} cannot be used in an expression", name.describe_type()),
-                ))
-            }
+            ResolvedName::Type(typ) => Err(Diagnostic::type_as_value(pos, *typ)),
+            ResolvedName::Library(_) | ResolvedName::Design(_) => Err(Diagnostic::error(
+                pos,
+                format!("{} cannot be used in an expression", name.describe_type()),
+            )),
             ResolvedName::Final(ent) => match ent.actual_kind() {
                 AnyEntKind::LoopParameter(typ) => Ok(typ.map(|typ| typ.into())),
                 AnyEntKind::PhysicalLiteral(typ) => Ok(Some(*typ)),
@@ -528,6 +532,7 @@ impl<'a> AnalyzeContext<'a> {
                         } else {
                             self.drange_unknown_type(scope, drange, diagnostics)?;
                         }
+                        self.check_null_drange(drange, Some("the slice is empty"), diagnostics);
                     } else {
                         diagnostics.error(
                             name_pos,
@@ -605,116 +610,6 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
-    pub fn attribute_suffix(
-        &self,
-        pos: &SrcPos,
-        scope: &Scope<'a>,
-        typ: TypeEnt<'a>,
-        attr: &mut AttributeSuffix,
-        diagnostics: &mut dyn DiagnosticHandler,
-    ) -> EvalResult<Option<BaseType<'a>>> {
-        match attr.attr.item {
-            AttributeDesignator::Left
-            | AttributeDesignator::Right
-            | AttributeDesignator::High
-            | AttributeDesignator::Low => {
-                if let Some((_, indexes)) = typ.array_type() {
-                    if attr.expr.is_none() {
-                        // @TODO could also be 'left(2) for different dimensions
-                        Ok(Some(indexes.first().unwrap().unwrap()))
-                    } else {
-                        Err(EvalError::Unknown)
-                    }
-                } else if typ.is_scalar() {
-                    check_no_attr_argument(attr, diagnostics);
-                    Ok(Some(typ.into()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::Ascending | AttributeDesignator::Descending => {
-                if typ.array_type().is_some() {
-                    Ok(Some(self.boolean().base()))
-                } else if typ.is_scalar() {
-                    check_no_attr_argument(attr, diagnostics);
-                    Ok(Some(self.boolean().base()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::Image => {
-                if let Some(ref mut expr) = check_single_argument(pos, attr, diagnostics) {
-                    self.expr_with_ttyp(scope, typ, expr, diagnostics)?;
-                }
-
-                if typ.is_scalar() {
-                    Ok(Some(self.string().base()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::Value => {
-                if let Some(ref mut expr) = check_single_argument(pos, attr, diagnostics) {
-                    self.expr_with_ttyp(scope, self.string(), expr, diagnostics)?;
-                }
-
-                if typ.is_scalar() {
-                    Ok(Some(typ.base()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::Pos => {
-                if typ.base().is_discrete() {
-                    if let Some(ref mut expr) = check_single_argument(pos, attr, diagnostics) {
-                        self.expr_with_ttyp(scope, typ, expr, diagnostics)?;
-                    }
-                    Ok(Some(self.universal_integer()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::Val => {
-                if typ.base().is_discrete() {
-                    if let Some(ref mut expr) = check_single_argument(pos, attr, diagnostics) {
-                        self.integer_expr(scope, expr, diagnostics)?;
-                    }
-                    Ok(Some(typ.base()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::Succ
-            | AttributeDesignator::Pred
-            | AttributeDesignator::LeftOf
-            | AttributeDesignator::RightOf => {
-                if typ.base().is_discrete() {
-                    if let Some(ref mut expr) = check_single_argument(pos, attr, diagnostics) {
-                        self.expr_with_ttyp(scope, typ, expr, diagnostics)?;
-                    }
-                    Ok(Some(typ.base()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::Length => {
-                if typ.array_type().is_some() {
-                    Ok(Some(self.universal_integer()))
-                } else {
-                    Ok(None)
-                }
-            }
-            AttributeDesignator::SimpleName
-            | AttributeDesignator::InstanceName
-            | AttributeDesignator::PathName => {
-                check_no_attr_argument(attr, diagnostics);
-                Ok(Some(self.string().base()))
-            }
-
-            _ => Err(EvalError::Unknown),
-        }
-    }
-
     pub fn signal_attribute_suffix(
         &self,
         scope: &Scope<'a>,
@@ -781,6 +676,69 @@ impl<'a> AnalyzeContext<'a> {
         self.name_resolve_with_suffixes(scope, name_pos, name, None, false, diagnostics)
     }
 
+    /// LRM 9.3.4 A function call with a generic map aspect calls an anonymous instance
+    /// of the uninstantiated function (VHDL-2019)
+    fn generic_call_resolve(
+        &self,
+        scope: &Scope<'a>,
+        call_pos: &SrcPos,
+        fcall: &mut CallOrIndexed,
+        ttyp: Option<TypeEnt<'a>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<ResolvedName<'a>> {
+        let CallOrIndexed {
+            name,
+            generic_map,
+            parameters,
+        } = fcall;
+
+        let resolved = self.name_resolve(scope, &name.pos, &mut name.item, diagnostics)?;
+        let ResolvedName::Overloaded(des, overloaded) = resolved else {
+            diagnostics.error(
+                &name.pos,
+                format!(
+                    "{} is not an uninstantiated subprogram",
+                    capitalize(&resolved.describe())
+                ),
+            );
+            return Err(EvalError::Unknown);
+        };
+
+        let instance = self.call_site_instance(
+            scope,
+            &des,
+            overloaded.entities().collect(),
+            generic_map.as_deref_mut().unwrap_or_default(),
+            diagnostics,
+        )?;
+
+        match self.disambiguate(
+            scope,
+            call_pos,
+            &des,
+            parameters,
+            ttyp,
+            vec![instance],
+            diagnostics,
+        )? {
+            Disambiguated::Unambiguous(ent) => {
+                name.set_unique_reference(&ent);
+                if let Some(return_type) = ent.return_type() {
+                    Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                        return_type,
+                    )))
+                } else {
+                    diagnostics.error(
+                        &name.pos,
+                        "Procedure calls are not valid in names and expressions",
+                    );
+                    Err(EvalError::Unknown)
+                }
+            }
+            Disambiguated::Ambiguous(_) => Err(EvalError::Unknown),
+        }
+    }
+
     fn name_resolve_with_suffixes(
         &self,
         scope: &Scope<'a>,
@@ -790,12 +748,26 @@ impl<'a> AnalyzeContext<'a> {
         has_suffix: bool,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<ResolvedName<'a>> {
+        if let Name::CallOrIndexed(ref mut fcall) = name {
+            if fcall.generic_map.is_some() {
+                return self.generic_call_resolve(
+                    scope,
+                    name_pos,
+                    fcall,
+                    // @TODO disambiguate based on suffixes
+                    if has_suffix { None } else { ttyp },
+                    diagnostics,
+                );
+            }
+        }
+
         let mut suffix;
         let prefix;
         let mut resolved = match SplitName::from_name(name) {
             SplitName::Designator(designator) => {
-                let name =
-                    catch_diagnostic(scope.lookup(name_pos, designator.designator()), diagnostics)?;
+                let name = scope.lookup(name_pos, designator.designator());
+                self.explain_lookup(scope, name_pos, designator.designator(), &name);
+                let name = catch_diagnostic(name, diagnostics)?;
                 return Ok(match name {
                     NamedEntities::Single(ent) => {
                         designator.set_unique_reference(ent);
@@ -814,6 +786,7 @@ impl<'a> AnalyzeContext<'a> {
             }
             SplitName::External(ename) => {
                 let ExternalName { subtype, class, .. } = ename;
+                self.has_external_names.set(true);
                 let subtype = catch_analysis_err(
                     self.resolve_subtype_indication(scope, subtype, diagnostics),
                     diagnostics,
@@ -924,7 +897,15 @@ impl<'a> AnalyzeContext<'a> {
                     }
                 };
 
-                return match self.attribute_suffix(name_pos, scope, typ, attr, diagnostics) {
+                return match self.attribute_suffix(
+                    name_pos,
+                    &prefix.pos,
+                    scope,
+                    &resolved,
+                    typ,
+                    attr,
+                    diagnostics,
+                ) {
                     Ok(Some(typ)) => Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
                         typ.into(),
                     ))),
@@ -1039,6 +1020,21 @@ impl<'a> AnalyzeContext<'a> {
                     diagnostics,
                 )? {
                     Some(TypeOrMethod::Type(typ)) => {
+                        if let (Suffix::Slice(drange), ObjectBase::Object(obj), None) =
+                            (&suffix, oname.base, oname.type_mark)
+                        {
+                            self.check_slice_direction(obj, drange, diagnostics);
+                        }
+                        if let Suffix::CallOrIndexed(ref assocs) = suffix {
+                            if could_be_indexed_name(assocs) {
+                                self.check_static_index(
+                                    &prefix.item,
+                                    oname.type_mark(),
+                                    assocs,
+                                    diagnostics,
+                                );
+                            }
+                        }
                         resolved = ResolvedName::ObjectName(oname.with_suffix(typ));
                     }
                     Some(TypeOrMethod::Method(des, name)) => {
@@ -1246,6 +1242,7 @@ impl<'a> AnalyzeContext<'a> {
     ) -> EvalResult<DisambiguatedType<'a>> {
         let resolved =
             self.name_resolve_with_suffixes(scope, expr_pos, name, None, false, diagnostics)?;
+        self.check_read_mode(expr_pos, &resolved, diagnostics);
         match self.name_to_type(expr_pos, name.suffix_reference_mut(), resolved) {
             Ok(Some(typ)) => Ok(typ),
             Ok(None) => Err(EvalError::Unknown),
@@ -1264,6 +1261,30 @@ impl<'a> AnalyzeContext<'a> {
         name: &mut Name,
         ttyp: TypeEnt<'a>,
         diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        self.name_with_ttyp(scope, expr_pos, name, ttyp, true, diagnostics)
+    }
+
+    /// Analyze a name whose value is written but not read such as the actual of a formal of mode out
+    pub fn written_name_with_ttyp(
+        &self,
+        scope: &Scope<'a>,
+        expr_pos: &SrcPos,
+        name: &mut Name,
+        ttyp: TypeEnt<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        self.name_with_ttyp(scope, expr_pos, name, ttyp, false, diagnostics)
+    }
+
+    fn name_with_ttyp(
+        &self,
+        scope: &Scope<'a>,
+        expr_pos: &SrcPos,
+        name: &mut Name,
+        ttyp: TypeEnt<'a>,
+        is_read: bool,
+        diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         if let Some(resolved) = as_fatal(self.name_resolve_with_suffixes(
             scope,
@@ -1273,6 +1294,9 @@ impl<'a> AnalyzeContext<'a> {
             false,
             diagnostics,
         ))? {
+            if is_read {
+                self.check_read_mode(expr_pos, &resolved, diagnostics);
+            }
             // @TODO target_type already used above, functions could probably be simplified
             match self.name_to_unambiguous_type(
                 expr_pos,
@@ -1298,6 +1322,34 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    /// LRM 6.5.2 Interface object declarations
+    /// Before VHDL-2008 the value of a port or parameter of mode out cannot be read
+    fn check_read_mode(
+        &self,
+        pos: &SrcPos,
+        resolved: &ResolvedName<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.root.standard() >= VHDLStandard::VHDL2008 {
+            return;
+        }
+
+        if let ResolvedName::ObjectName(oname) = resolved {
+            if oname.base.mode() == Some(Mode::Out) {
+                diagnostics.push(
+                    Diagnostic::error(
+                        pos,
+                        format!(
+                            "{} cannot be read before VHDL-2008",
+                            oname.base.describe_class()
+                        ),
+                    )
+                    .with_code(ErrorCode::LaterRevision),
+                );
+            }
+        }
+    }
+
     /// Fallback solution that just lookups names
     pub fn resolve_name(
         &self,
@@ -1355,6 +1407,7 @@ impl<'a> AnalyzeContext<'a> {
             }
             Name::External(ref mut ename) => {
                 let ExternalName { subtype, .. } = ename.as_mut();
+                self.has_external_names.set(true);
                 self.analyze_subtype_indication(scope, subtype, diagnostics)?;
                 Err(EvalError::Unknown)
             }
@@ -1517,7 +1570,7 @@ impl<'a> AnalyzeContext<'a> {
     }
 }
 
-fn plural(singular: &'static str, plural: &'static str, count: usize) -> &'static str {
+pub(super) fn plural(singular: &'static str, plural: &'static str, count: usize) -> &'static str {
     if count == 1 {
         singular
     } else {
@@ -1610,15 +1663,6 @@ impl Diagnostic {
     }
 }
 
-fn check_no_attr_argument(suffix: &AttributeSuffix, diagnostics: &mut dyn DiagnosticHandler) {
-    if let Some(ref expr) = suffix.expr {
-        diagnostics.error(
-            &expr.pos,
-            format!("'{} attribute does not take an argument", suffix.attr),
-        )
-    }
-}
-
 fn check_no_sattr_argument(
     attr: SignalAttribute,
     expr: Option<&mut WithPos<Expression>>,
@@ -1632,22 +1676,6 @@ fn check_no_sattr_argument(
     }
 }
 
-fn check_single_argument<'a>(
-    pos: &SrcPos,
-    suffix: &'a mut AttributeSuffix,
-    diagnostics: &mut dyn DiagnosticHandler,
-) -> Option<&'a mut WithPos<Expression>> {
-    if let Some(ref mut expr) = suffix.expr {
-        Some(expr)
-    } else {
-        diagnostics.error(
-            pos,
-            format!("'{} attribute requires a single argument", suffix.attr),
-        );
-        None
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2324,6 +2352,86 @@ constant c0 : arr_t := (others => 0);
         );
     }
 
+    #[test]
+    fn discrete_attributes_require_type_prefix() {
+        let test = TestSetup::new();
+        test.declarative_part(
+            "
+constant c0 : character := 'a';
+        ",
+        );
+
+        let code = test.snippet("c0'pos('a')");
+        let mut diagnostics = Vec::new();
+        assert_eq!(
+            test.name_resolve(&code, None, &mut diagnostics),
+            Err(EvalError::Unknown)
+        );
+        check_diagnostics(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s1("c0"),
+                "Expected type prefix for 'pos attribute, got constant 'c0'",
+            )],
+        )
+    }
+
+    #[test]
+    fn physical_type_attributes() {
+        let test = TestSetup::new();
+
+        let code = test.snippet("time'val(0)");
+        assert_eq!(
+            test.name_resolve(&code, None, &mut NoDiagnostics),
+            Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                test.lookup_type("time")
+            )))
+        );
+
+        let code = test.snippet("time'pos(1 ns)");
+        assert_eq!(
+            test.name_resolve(&code, None, &mut NoDiagnostics),
+            Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                test.ctx().universal_integer().into()
+            )))
+        );
+    }
+
+    #[test]
+    fn array_attributes_select_dimension() {
+        let test = TestSetup::new();
+        test.declarative_part(
+            "
+type arr_t is array (natural range 0 to 3, character range 'a' to 'c') of integer;
+constant c0 : arr_t := (others => (others => 0));
+        ",
+        );
+
+        let code = test.snippet("c0'left(2)");
+        assert_eq!(
+            test.name_resolve(&code, None, &mut NoDiagnostics),
+            Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                test.ctx().character()
+            )))
+        );
+
+        let code = test.snippet("arr_t'high(1)");
+        assert_eq!(
+            test.name_resolve(&code, None, &mut NoDiagnostics),
+            Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                test.lookup_type("integer")
+            )))
+        );
+
+        let code = test.snippet("c0'length(2)");
+        assert_eq!(
+            test.name_resolve(&code, None, &mut NoDiagnostics),
+            Ok(ResolvedName::Expression(DisambiguatedType::Unambiguous(
+                test.ctx().universal_integer().into()
+            )))
+        );
+    }
+
     #[test]
     fn signal_attributes_on_non_signal() {
         let test = TestSetup::new();
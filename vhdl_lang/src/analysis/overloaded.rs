@@ -37,6 +37,27 @@ enum Rejection<'a> {
 
     // The amount of actuals or named actuals do not match formals
     MissingFormals(Vec<InterfaceEnt<'a>>),
+
+    // The subprogram has a generic clause and must be instantiated before it is called
+    Uninstantiated,
+}
+
+impl<'a> Rejection<'a> {
+    fn reason(&self) -> String {
+        match self {
+            Rejection::ReturnType => "Does not match the return type".to_owned(),
+            Rejection::Procedure => "Procedure cannot be used as a function".to_owned(),
+            Rejection::MissingFormals(missing) => format!(
+                "Missing association of {}",
+                missing
+                    .iter()
+                    .map(|formal| formal.describe())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Rejection::Uninstantiated => "Uninstantiated subprogram cannot be called".to_owned(),
+        }
+    }
 }
 
 struct Candidate<'a> {
@@ -134,6 +155,13 @@ impl<'a> Candidates<'a> {
                                 )
                             }
                         }
+                        Rejection::Uninstantiated => diag.add_related(
+                            decl_pos,
+                            format!(
+                                "Uninstantiated subprogram {} cannot be called",
+                                cand.ent.describe()
+                            ),
+                        ),
                     };
                 }
             }
@@ -189,21 +217,109 @@ impl<'a> AnalyzeContext<'a> {
         all_overloaded: Vec<OverloadedEnt<'a>>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<Disambiguated<'a>> {
+        let mut rejected = Vec::new();
+        let result = self.disambiguate_call(
+            scope,
+            call_pos,
+            call_name,
+            parameters,
+            ttyp,
+            &all_overloaded,
+            &mut rejected,
+            diagnostics,
+        );
+
+        let resolved = match result {
+            Ok(Disambiguated::Unambiguous(ent)) => Some(ent),
+            _ => None,
+        };
+        self.explain_overloaded(
+            &call_name.pos,
+            call_name.designator(),
+            &all_overloaded,
+            &rejected,
+            resolved,
+        );
+        result
+    }
+
+    /// Disambiguate the call, the reasons candidates were rejected are added to `rejected`
+    /// when the resolution of the name is explained
+    #[allow(clippy::too_many_arguments)]
+    fn disambiguate_call(
+        &self,
+        scope: &Scope<'a>,
+        call_pos: &SrcPos,
+        call_name: &WithPos<Designator>,
+        parameters: &mut [AssociationElement],
+        ttyp: Option<TypeEnt<'a>>,
+        all_overloaded: &[OverloadedEnt<'a>],
+        rejected: &mut Vec<(OverloadedEnt<'a>, String)>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<Disambiguated<'a>> {
+        let explaining = self.explains(&call_name.pos);
+
+        // LRM 4.2.1 An uninstantiated subprogram cannot be called
+        let callable: Vec<_> = all_overloaded
+            .iter()
+            .copied()
+            .filter(|ent| {
+                if ent.is_uninstantiated() {
+                    if explaining {
+                        rejected.push((
+                            *ent,
+                            "Uninstantiated subprogram cannot be called".to_owned(),
+                        ));
+                    }
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect();
+
+        if callable.is_empty() && !all_overloaded.is_empty() {
+            let mut diag = Diagnostic::error(
+                &call_name.pos,
+                format!(
+                    "Uninstantiated subprogram '{}' cannot be called",
+                    call_name.designator()
+                ),
+            );
+            diag.add_subprogram_candidates("Must be instantiated", all_overloaded.to_vec());
+            diagnostics.push(diag);
+            return Err(EvalError::Unknown);
+        }
+
         // Apply target type constraint if it exists
         let overloaded = if let Some(ttyp) = ttyp {
-            let mut overloaded = all_overloaded.clone();
+            let mut overloaded = callable;
             let tbase = ttyp.base();
             overloaded.retain(|ent| {
                 if let Some(return_type) = ent.return_type() {
-                    self.can_be_target_type(return_type, tbase)
+                    let matches = self.can_be_target_type(return_type, tbase);
+                    if !matches && explaining {
+                        rejected.push((
+                            *ent,
+                            format!(
+                                "Return {} does not match {}",
+                                return_type.describe(),
+                                ttyp.describe()
+                            ),
+                        ));
+                    }
+                    matches
                 } else {
+                    if explaining {
+                        rejected.push((*ent, "Procedure cannot be used as a function".to_owned()));
+                    }
                     false
                 }
             });
 
             overloaded
         } else {
-            all_overloaded.clone()
+            callable
         };
 
         // Does not need disambiguation
@@ -217,21 +333,28 @@ impl<'a> AnalyzeContext<'a> {
                 &call_name.pos,
                 format!("Could not resolve call to '{}'", call_name.designator()),
             );
-            diag.add_subprogram_candidates("Does not match", all_overloaded);
+            diag.add_subprogram_candidates("Does not match", all_overloaded.to_vec());
             diagnostics.push(diag);
             return Err(EvalError::Unknown);
         }
 
         let mut candidates = Vec::with_capacity(overloaded.len());
         for ent in overloaded.iter() {
+            let mut formal_diagnostics = Vec::new();
             if let Some(resolved) = as_fatal(self.resolve_association_formals(
                 call_pos,
                 ent.formals(),
                 scope,
                 parameters,
-                &mut NullDiagnostics,
+                &mut formal_diagnostics,
             ))? {
                 candidates.push((ent, resolved));
+            } else if explaining {
+                let reason = formal_diagnostics.first().map_or_else(
+                    || "Associations do not match the formals".to_owned(),
+                    |diag| diag.message.clone(),
+                );
+                rejected.push((*ent, reason));
             }
 
             for elem in parameters.iter_mut() {
@@ -281,14 +404,31 @@ impl<'a> AnalyzeContext<'a> {
         let type_candidates: Vec<_> = candidates
             .iter()
             .cloned()
-            .filter(|(_, formals)| {
-                actual_types.iter().enumerate().all(|(idx, actual_type)| {
+            .filter(|(ent, formals)| {
+                let mismatch = actual_types.iter().enumerate().find(|(idx, actual_type)| {
                     if let Some(actual_type) = actual_type {
-                        self.is_possible(actual_type, formals[idx].type_mark().base())
+                        !self.is_possible(actual_type, formals[*idx].type_mark().base())
                     } else {
-                        true
+                        false
                     }
-                })
+                });
+
+                if let Some((idx, Some(actual_type))) = mismatch {
+                    if explaining {
+                        rejected.push((
+                            **ent,
+                            format!(
+                                "Actual {} does not match {}, got {}",
+                                idx + 1,
+                                formals[idx].type_mark().describe(),
+                                actual_type.describe()
+                            ),
+                        ));
+                    }
+                    false
+                } else {
+                    true
+                }
             })
             .map(|(ent, _)| *ent)
             .collect();
@@ -322,7 +462,9 @@ impl<'a> AnalyzeContext<'a> {
         let tbase = ttyp.map(|ttyp| ttyp.base());
 
         for cand in candidates.remaining() {
-            if !cand.ent.signature().can_be_called_without_actuals() {
+            if cand.ent.is_uninstantiated() {
+                cand.rejection = Some(Rejection::Uninstantiated);
+            } else if !cand.ent.signature().can_be_called_without_actuals() {
                 cand.rejection = Some(Rejection::MissingFormals(
                     cand.ent.signature().formals_without_defaults().collect(),
                 ));
@@ -337,6 +479,26 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
+        if self.explains(&name.pos) {
+            let all: Vec<_> = candidates.0.iter().map(|cand| cand.ent).collect();
+            let rejected: Vec<_> = candidates
+                .0
+                .iter()
+                .filter_map(|cand| Some((cand.ent, cand.rejection.as_ref()?.reason())))
+                .collect();
+            let remaining: Vec<_> = candidates
+                .0
+                .iter()
+                .filter(|cand| cand.rejection.is_none())
+                .collect();
+            let resolved = if let [single] = remaining.as_slice() {
+                Some(single.ent)
+            } else {
+                None
+            };
+            self.explain_overloaded(&name.pos, &name.item, &all, &rejected, resolved);
+        }
+
         Ok(Some(candidates.finish(name, ttyp)?))
     }
 }
@@ -6,6 +6,7 @@
 
 use arc_swap::ArcSwapOption;
 use fnv::FnvHashMap;
+use std::collections::hash_map::Entry;
 
 use super::analyze::*;
 use super::formal_region::FormalRegion;
@@ -20,22 +21,37 @@ use super::named_entity::ObjectEnt;
 use super::named_entity::Overloaded;
 use super::named_entity::OverloadedEnt;
 use super::named_entity::Signature;
+use super::named_entity::StaticProperty;
+use super::named_entity::StaticRange;
+use super::named_entity::SubprogramDefaultKind;
 use super::named_entity::Subtype;
 use super::named_entity::Type;
 use super::named_entity::TypeEnt;
+use super::named_entity::View;
+use super::named_entity::ViewElementMode;
+use super::named_entity::ViewEnt;
 use super::region::*;
 use super::AnyEntKind;
 use super::EntRef;
 use super::EntityId;
 use super::Related;
+use crate::ast::capitalize;
 use crate::ast::ActualPart;
 use crate::ast::AssociationElement;
+use crate::ast::Designator;
+use crate::ast::DiscreteRange;
 use crate::ast::Expression;
 use crate::ast::Literal;
 use crate::ast::Name;
 use crate::ast::Operator;
 use crate::ast::PackageInstantiation;
+use crate::ast::SubprogramInstantiation;
+use crate::ast::SubprogramKind;
 use crate::data::DiagnosticHandler;
+use crate::data::ErrorCode;
+use crate::data::SrcPos;
+use crate::data::VHDLStandard;
+use crate::data::WithPos;
 use crate::Diagnostic;
 use crate::NullDiagnostics;
 
@@ -43,27 +59,38 @@ impl<'a> AnalyzeContext<'a> {
     fn package_generic_map(
         &self,
         scope: &Scope<'a>,
+        error_pos: &SrcPos, // The position of the instantiated package name
         generics: GpkgRegion<'a>,
         generic_map: &mut [AssociationElement],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<FnvHashMap<EntityId, EntRef<'a>>> {
         let mut mapping = FnvHashMap::default();
+        // The position of the association of each formal and whether it is open
+        let mut associated: FnvHashMap<usize, (SrcPos, bool)> = FnvHashMap::default();
+        let mut unresolved = false;
 
-        // @TODO check missing associations
         for (idx, assoc) in generic_map.iter_mut().enumerate() {
-            let formal = if let Some(formal) = &mut assoc.formal {
+            let formal_pos = assoc
+                .formal
+                .as_ref()
+                .map_or(&assoc.actual.pos, |formal| &formal.pos)
+                .clone();
+
+            let (formal_idx, formal) = if let Some(formal) = &mut assoc.formal {
                 if let Name::Designator(des) = &mut formal.item {
                     match generics.lookup(&formal.pos, &des.item) {
-                        Ok((_, ent)) => {
+                        Ok((idx, ent)) => {
                             des.set_unique_reference(&ent);
-                            ent
+                            (idx, ent)
                         }
                         Err(err) => {
+                            unresolved = true;
                             diagnostics.push(err);
                             continue;
                         }
                     }
                 } else {
+                    unresolved = true;
                     diagnostics.error(
                         &formal.pos,
                         "Expected simple name for package generic formal",
@@ -71,36 +98,36 @@ impl<'a> AnalyzeContext<'a> {
                     continue;
                 }
             } else if let Some(ent) = generics.nth(idx) {
-                ent
+                (idx, ent)
             } else {
+                unresolved = true;
                 diagnostics.error(&assoc.actual.pos, "Extra actual for generic map");
                 continue;
             };
 
+            match associated.entry(formal_idx) {
+                Entry::Occupied(entry) => {
+                    let mut diagnostic = Diagnostic::error(
+                        formal_pos,
+                        format!(
+                            "{} is associated more than once",
+                            capitalize(&formal.describe())
+                        ),
+                    );
+                    diagnostic.add_related(&entry.get().0, "Previously associated here");
+                    diagnostics.push(diagnostic);
+                    continue;
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((formal_pos, matches!(assoc.actual.item, ActualPart::Open)));
+                }
+            }
+
             match &mut assoc.actual.item {
                 ActualPart::Expression(expr) => match formal {
                     GpkgInterfaceEnt::Type(uninst_typ) => {
                         let typ = if let Expression::Name(name) = expr {
-                            match name.as_mut() {
-                                // Could be an array constraint such as integer_vector(0 to 3)
-                                // @TODO we ignore the suffix for now
-                                Name::Slice(prefix, _) => self.type_name(
-                                    scope,
-                                    &prefix.pos,
-                                    &mut prefix.item,
-                                    diagnostics,
-                                )?,
-                                // Could be a record constraint such as rec_t(field(0 to 3))
-                                // @TODO we ignore the suffix for now
-                                Name::CallOrIndexed(call) if call.could_be_indexed_name() => self
-                                    .type_name(
-                                    scope,
-                                    &call.name.pos,
-                                    &mut call.name.item,
-                                    diagnostics,
-                                )?,
-                                _ => self.type_name(scope, &assoc.actual.pos, name, diagnostics)?,
-                            }
+                            self.type_actual(scope, &assoc.actual.pos, name, diagnostics)?
                         } else {
                             diagnostics
                                 .error(&assoc.actual.pos, "Cannot map expression to type generic");
@@ -155,9 +182,211 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
         }
+
+        // Only complain about missing associations if all formals could be resolved
+        if !unresolved {
+            for (idx, formal) in generics.iter().enumerate() {
+                if associated.get(&idx).is_some_and(|(_, is_open)| !is_open) {
+                    continue;
+                }
+
+                let has_default = match formal {
+                    GpkgInterfaceEnt::Constant(obj) => obj.kind().has_default,
+                    GpkgInterfaceEnt::Subprogram(subpgm) => match subpgm.kind() {
+                        Overloaded::InterfaceSubprogram(_, SubprogramDefaultKind::Box) => {
+                            self.resolve_box_default(
+                                scope,
+                                error_pos,
+                                &mapping,
+                                subpgm,
+                                diagnostics,
+                            );
+                            true
+                        }
+                        Overloaded::InterfaceSubprogram(_, SubprogramDefaultKind::None) => false,
+                        _ => true,
+                    },
+                    GpkgInterfaceEnt::Type(_) | GpkgInterfaceEnt::Package(_) => false,
+                };
+
+                if !has_default {
+                    let mut diagnostic = Diagnostic::error(
+                        error_pos,
+                        format!("No association of {}", formal.describe()),
+                    );
+                    if let Some(decl_pos) = formal.decl_pos() {
+                        diagnostic.add_related(decl_pos, "Defined here");
+                    }
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
         Ok(mapping)
     }
 
+    /// The type denoted by the actual of a type generic. An actual with a constraint such as
+    /// integer_vector(0 to 3) or rec_t(field(0 to 3)) is parsed as a name and denotes
+    /// a subtype of the type mark with the static ranges of the constraint
+    fn type_actual(
+        &self,
+        scope: &Scope<'a>,
+        pos: &SrcPos,
+        name: &mut Name,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<TypeEnt<'a>> {
+        let (typ, ranges) = match name {
+            Name::Slice(prefix, drange) => {
+                let typ = self.type_name(scope, &prefix.pos, &mut prefix.item, diagnostics)?;
+                let range = self.index_constraint(scope, &prefix.pos, typ, drange, diagnostics)?;
+                (typ, range.map(|range| vec![range]))
+            }
+            Name::CallOrIndexed(call) if call.could_be_indexed_name() => {
+                let typ =
+                    self.type_name(scope, &call.name.pos, &mut call.name.item, diagnostics)?;
+                let Type::Record(region) = typ.base_type().kind() else {
+                    // @TODO index constraints given by subtype names such as arr_t(idx_t)
+                    return Ok(typ);
+                };
+                for assoc in call.parameters.iter_mut() {
+                    self.record_element_constraint(scope, typ, region, assoc, diagnostics)?;
+                }
+                // The constraints of the elements are not part of the static ranges
+                (typ, None)
+            }
+            _ => return self.type_name(scope, pos, name, diagnostics),
+        };
+
+        let subtype = self.arena.explicit(
+            typ.designator().clone(),
+            AnyEntKind::Type(Type::Subtype(Subtype::new(typ))),
+            Some(pos),
+        );
+        self.set_static_property(subtype, ranges.map(StaticProperty::Ranges));
+        Ok(TypeEnt::from_any(subtype).unwrap())
+    }
+
+    /// The static range of the index constraint of a one-dimensional array type
+    fn index_constraint(
+        &self,
+        scope: &Scope<'a>,
+        pos: &SrcPos, // The position of the type mark
+        typ: TypeEnt<'a>,
+        drange: &mut DiscreteRange,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<StaticRange>> {
+        let Some((_, indexes)) = typ.array_type() else {
+            diagnostics.error(
+                pos,
+                format!("Array constraint cannot be used for {}", typ.describe()),
+            );
+            return Ok(None);
+        };
+        let [index_typ] = indexes.as_slice() else {
+            diagnostics.error(
+                pos,
+                format!(
+                    "Too few index constraints for {}. Got 1 but expected {}",
+                    typ.describe(),
+                    indexes.len()
+                ),
+            );
+            return Ok(None);
+        };
+
+        if let Some(index_typ) = index_typ {
+            self.drange_with_ttyp(scope, (*index_typ).into(), drange, diagnostics)?;
+        } else {
+            self.drange_unknown_type(scope, drange, diagnostics)?;
+        }
+        self.check_null_drange(drange, None, diagnostics);
+        Ok(self
+            .static_evaluator()
+            .eval_discrete_range(index_typ.map(TypeEnt::from), drange))
+    }
+
+    /// An element constraint such as field(0 to 3) of a record type actual
+    fn record_element_constraint(
+        &self,
+        scope: &Scope<'a>,
+        typ: TypeEnt<'a>,
+        region: &RecordRegion<'a>,
+        assoc: &mut AssociationElement,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let pos = &assoc.actual.pos;
+        let ActualPart::Expression(Expression::Name(ref mut name)) = assoc.actual.item else {
+            diagnostics.error(pos, "Expected record element constraint");
+            return Ok(());
+        };
+        let Name::Slice(ref mut prefix, ref mut drange) = name.as_mut() else {
+            diagnostics.error(pos, "Expected record element constraint");
+            return Ok(());
+        };
+        let Name::Designator(ref mut designator) = prefix.item else {
+            diagnostics.error(&prefix.pos, "Expected record element name");
+            return Ok(());
+        };
+
+        let Some(elem) = region.lookup(&designator.item) else {
+            diagnostics.push(Diagnostic::no_declaration_within(
+                &typ,
+                &prefix.pos,
+                &designator.item,
+            ));
+            return Ok(());
+        };
+        designator.set_unique_reference(&elem);
+        self.index_constraint(scope, &prefix.pos, elem.type_mark(), drange, diagnostics)?;
+        Ok(())
+    }
+
+    /// LRM 6.5.6.2 An unassociated formal subprogram with a box default denotes
+    /// the subprogram with the same designator and a conforming profile
+    /// that is directly visible at the place of the instantiation
+    fn resolve_box_default(
+        &self,
+        scope: &Scope<'a>,
+        error_pos: &SrcPos,
+        mapping: &FnvHashMap<EntityId, EntRef<'a>>,
+        formal: OverloadedEnt<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Ok(signature) = self.map_signature(mapping, formal.signature()) else {
+            return;
+        };
+        let key = signature.key();
+
+        let candidates: Vec<_> = match scope.lookup(error_pos, formal.designator()) {
+            Ok(NamedEntities::Overloaded(overloaded)) => overloaded
+                .entities()
+                .filter(|ent| ent.id() != formal.id() && !ent.is_uninstantiated())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        if candidates.iter().any(|ent| ent.signature().key() == key) {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::error(
+            error_pos,
+            format!(
+                "No visible subprogram matches the default <> of {}",
+                formal.describe()
+            ),
+        );
+        if let Some(decl_pos) = formal.decl_pos() {
+            diagnostic.add_related(decl_pos, "Defined here");
+        }
+        // Implicit declarations such as the predefined operators are not listed
+        // since there are too many of them to be of any help
+        diagnostic.add_subprogram_candidates(
+            "Does not match",
+            candidates.into_iter().filter(|ent| !ent.is_implicit()),
+        );
+        diagnostics.push(diagnostic);
+    }
+
     pub fn generic_package_instance(
         &self,
         scope: &Scope<'a>,
@@ -175,15 +404,18 @@ impl<'a> AnalyzeContext<'a> {
                 let nested = scope.nested().in_package_declaration();
                 let (generics, other) = package_region.to_package_generic();
 
-                let mapping = if let Some(generic_map) = generic_map {
-                    self.package_generic_map(&nested, generics, generic_map, diagnostics)?
-                } else {
-                    FnvHashMap::default()
-                };
+                let mut mapping = self.package_generic_map(
+                    &nested,
+                    &package_name.pos,
+                    generics,
+                    generic_map.as_deref_mut().unwrap_or_default(),
+                    diagnostics,
+                )?;
 
                 for uninst in other {
                     match self.instantiate(&mapping, uninst) {
                         Ok(inst) => {
+                            mapping.insert(uninst.id(), inst);
                             // We ignore diagnostics here, for example when adding implicit operators EQ and NE for interface types
                             // They can collide if there are more than one interface type that map to the same actual type
                             nested.add(inst, &mut NullDiagnostics);
@@ -207,6 +439,222 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
+    /// LRM 4.4 Subprogram instantiation declarations
+    pub fn generic_subprogram_instance(
+        &self,
+        scope: &Scope<'a>,
+        instance: &mut SubprogramInstantiation,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<EntRef<'a>> {
+        let SubprogramInstantiation {
+            kind,
+            designator,
+            subprogram_name,
+            signature,
+            generic_map,
+        } = instance;
+
+        let overloaded = match self.resolve_selected_name(scope, subprogram_name) {
+            Ok(NamedEntities::Overloaded(overloaded)) => overloaded,
+            Ok(NamedEntities::Single(ent)) => {
+                diagnostics.error(
+                    &subprogram_name.pos,
+                    format!(
+                        "{} is not an uninstantiated subprogram",
+                        capitalize(&ent.describe())
+                    ),
+                );
+                return Err(EvalError::Unknown);
+            }
+            Err(err) => {
+                diagnostics.push(err.into_non_fatal()?);
+                return Err(EvalError::Unknown);
+            }
+        };
+
+        let is_function = *kind == SubprogramKind::Function;
+        let uninst = if let Some(signature) = signature {
+            let key = match self.resolve_signature(scope, signature) {
+                Ok(key) => key,
+                Err(err) => {
+                    err.add_to(diagnostics)?;
+                    return Err(EvalError::Unknown);
+                }
+            };
+            if let Some(uninst) = overloaded.get(&key) {
+                uninst
+            } else {
+                diagnostics.push(Diagnostic::no_overloaded_with_signature(
+                    &subprogram_name.pos,
+                    overloaded.designator(),
+                    &overloaded,
+                ));
+                return Err(EvalError::Unknown);
+            }
+        } else {
+            let candidates: Vec<_> = overloaded
+                .entities()
+                .filter(|ent| ent.is_uninstantiated() && ent.is_function() == is_function)
+                .collect();
+            match candidates.as_slice() {
+                [uninst] => *uninst,
+                [] => {
+                    let mut diagnostic = Diagnostic::error(
+                        &subprogram_name.pos,
+                        format!(
+                            "'{}' does not denote an uninstantiated {}",
+                            subprogram_name,
+                            if is_function { "function" } else { "procedure" }
+                        ),
+                    );
+                    diagnostic.add_subprogram_candidates("Found", overloaded.entities());
+                    diagnostics.push(diagnostic);
+                    return Err(EvalError::Unknown);
+                }
+                _ => {
+                    let mut diagnostic = Diagnostic::error(
+                        &subprogram_name.pos,
+                        format!(
+                            "Ambiguous instantiation of '{subprogram_name}', a signature is required"
+                        ),
+                    );
+                    diagnostic.add_subprogram_candidates("Might be", candidates);
+                    diagnostics.push(diagnostic);
+                    return Err(EvalError::Unknown);
+                }
+            }
+        };
+
+        let Some(generics) = uninst.kind().generics() else {
+            diagnostics.error(
+                &subprogram_name.pos,
+                format!(
+                    "{} is not an uninstantiated subprogram",
+                    capitalize(&uninst.describe())
+                ),
+            );
+            return Err(EvalError::Unknown);
+        };
+        if uninst.is_function() != is_function {
+            diagnostics.error(
+                &subprogram_name.pos,
+                format!(
+                    "Expected uninstantiated {}, got {}",
+                    if is_function { "function" } else { "procedure" },
+                    uninst.describe()
+                ),
+            );
+            return Err(EvalError::Unknown);
+        }
+        subprogram_name
+            .item
+            .suffix_reference_mut()
+            .set_unique_reference(&uninst);
+
+        let mapping = self.package_generic_map(
+            scope,
+            &subprogram_name.pos,
+            generics.clone(),
+            generic_map.as_deref_mut().unwrap_or_default(),
+            diagnostics,
+        )?;
+
+        match self.map_signature(&mapping, uninst.signature()) {
+            Ok(signature) => Ok(designator.define(
+                self.arena,
+                AnyEntKind::Overloaded(Overloaded::Subprogram(signature)),
+            )),
+            Err(err) => {
+                let mut diag = Diagnostic::error(&designator.tree.pos, err);
+                if let Some(pos) = uninst.decl_pos() {
+                    diag.add_related(pos, "When instantiating this declaration");
+                }
+                diagnostics.push(diag);
+                Err(EvalError::Unknown)
+            }
+        }
+    }
+
+    /// LRM 9.3.4 A call of an uninstantiated subprogram with a generic map aspect calls
+    /// an anonymous instance of the subprogram (VHDL-2019)
+    pub fn call_site_instance(
+        &self,
+        scope: &Scope<'a>,
+        name: &WithPos<Designator>,
+        candidates: Vec<OverloadedEnt<'a>>,
+        generic_map: &mut [AssociationElement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<OverloadedEnt<'a>> {
+        if self.root.standard() < VHDLStandard::VHDL2019 {
+            diagnostics.push(
+                Diagnostic::error(
+                    &name.pos,
+                    "Generic map aspects of subprogram calls are not allowed before VHDL-2019",
+                )
+                .with_code(ErrorCode::LaterRevision),
+            );
+        }
+
+        let uninstantiated: Vec<_> = candidates
+            .iter()
+            .copied()
+            .filter(|ent| ent.is_uninstantiated())
+            .collect();
+        let uninst = match uninstantiated.as_slice() {
+            [uninst] => *uninst,
+            [] => {
+                let mut diagnostic = Diagnostic::error(
+                    &name.pos,
+                    format!(
+                        "'{}' does not denote an uninstantiated subprogram",
+                        name.item
+                    ),
+                );
+                diagnostic.add_subprogram_candidates("Found", candidates);
+                diagnostics.push(diagnostic);
+                return Err(EvalError::Unknown);
+            }
+            _ => {
+                let mut diagnostic = Diagnostic::error(
+                    &name.pos,
+                    format!(
+                        "Ambiguous call of uninstantiated subprogram '{}', an instantiation with a signature is required",
+                        name.item
+                    ),
+                );
+                diagnostic.add_subprogram_candidates("Might be", uninstantiated);
+                diagnostics.push(diagnostic);
+                return Err(EvalError::Unknown);
+            }
+        };
+
+        let Some(generics) = uninst.kind().generics() else {
+            return Err(EvalError::Unknown);
+        };
+        let mapping =
+            self.package_generic_map(scope, &name.pos, generics.clone(), generic_map, diagnostics)?;
+
+        match self.map_signature(&mapping, uninst.signature()) {
+            Ok(signature) => {
+                let inst = self.arena.alloc(
+                    uninst.designator().clone(),
+                    Related::InstanceOf(uninst.into()),
+                    AnyEntKind::Overloaded(Overloaded::Subprogram(signature)),
+                    uninst.decl_pos().cloned(),
+                );
+                OverloadedEnt::from_any(inst).map_err(|_| EvalError::Unknown)
+            }
+            Err(err) => {
+                let mut diag = Diagnostic::error(&name.pos, err);
+                if let Some(pos) = uninst.decl_pos() {
+                    diag.add_related(pos, "When instantiating this declaration");
+                }
+                diagnostics.push(diag);
+                Err(EvalError::Unknown)
+            }
+        }
+    }
+
     fn instantiate(
         &self,
         mapping: &FnvHashMap<EntityId, EntRef<'a>>,
@@ -216,12 +664,49 @@ impl<'a> AnalyzeContext<'a> {
 
         let decl_pos = uninst.decl_pos().cloned();
 
+        if matches!(uninst.kind(), AnyEntKind::Type(_)) {
+            // The methods of a protected type and the implicit operations of a type
+            // refer to the type itself, which must therefore be mapped to the instance
+            // before its kind is instantiated
+            let inst = self.arena.alloc(
+                designator.clone(),
+                Related::InstanceOf(uninst),
+                AnyEntKind::Type(Type::Incomplete),
+                decl_pos.clone(),
+            );
+            let mut mapping = mapping.clone();
+            mapping.insert(uninst.id(), inst);
+
+            let kind = self.map_kind(&mapping, uninst.kind())?;
+            let inst = unsafe {
+                self.arena.update(
+                    inst.id(),
+                    designator,
+                    Related::InstanceOf(uninst),
+                    kind,
+                    decl_pos,
+                )
+            };
+            self.instantiate_implicits(&mapping, uninst, inst)?;
+            return Ok(inst);
+        }
+
         let kind = self.map_kind(mapping, uninst.kind())?;
 
         let inst = self
             .arena
             .alloc(designator, Related::InstanceOf(uninst), kind, decl_pos);
+        self.instantiate_implicits(mapping, uninst, inst)?;
+
+        Ok(inst)
+    }
 
+    fn instantiate_implicits(
+        &self,
+        mapping: &FnvHashMap<EntityId, EntRef<'a>>,
+        uninst: EntRef<'a>,
+        inst: EntRef<'a>,
+    ) -> Result<(), String> {
         for implicit_uninst in uninst.implicits.iter() {
             unsafe {
                 self.arena
@@ -229,7 +714,7 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
-        Ok(inst)
+        Ok(())
     }
 
     fn map_kind(
@@ -265,6 +750,8 @@ impl<'a> AnalyzeContext<'a> {
                 AnyEntKind::Component(self.map_region(mapping, region)?)
             }
             AnyEntKind::Attribute(typ) => AnyEntKind::Attribute(self.map_type_ent(mapping, *typ)?),
+            AnyEntKind::GroupTemplate(entries) => AnyEntKind::GroupTemplate(entries.clone()),
+            AnyEntKind::Group => AnyEntKind::Group,
             AnyEntKind::Overloaded(overloaded) => {
                 AnyEntKind::Overloaded(self.map_overloaded(mapping, overloaded)?)
             }
@@ -273,6 +760,7 @@ impl<'a> AnalyzeContext<'a> {
                 AnyEntKind::ElementDeclaration(self.map_subtype(mapping, *subtype)?)
             }
             AnyEntKind::Label => AnyEntKind::Label,
+            AnyEntKind::View(view) => AnyEntKind::View(self.map_view(mapping, view)?),
             AnyEntKind::Object(obj) => AnyEntKind::Object(self.map_object(mapping, obj)?),
             AnyEntKind::LoopParameter(typ) => AnyEntKind::LoopParameter(if let Some(typ) = typ {
                 Some(self.map_type_ent(mapping, (*typ).into())?.base())
@@ -312,12 +800,22 @@ impl<'a> AnalyzeContext<'a> {
             Overloaded::Subprogram(signature) => {
                 Overloaded::Subprogram(self.map_signature(mapping, signature)?)
             }
-            Overloaded::InterfaceSubprogram(signature) => {
-                Overloaded::InterfaceSubprogram(self.map_signature(mapping, signature)?)
+            Overloaded::InterfaceSubprogram(signature, default) => {
+                Overloaded::InterfaceSubprogram(self.map_signature(mapping, signature)?, *default)
             }
             Overloaded::EnumLiteral(signature) => {
                 Overloaded::EnumLiteral(self.map_signature(mapping, signature)?)
             }
+            Overloaded::UninstSubprogramDecl(signature, generics) => {
+                Overloaded::UninstSubprogramDecl(
+                    self.map_signature(mapping, signature)?,
+                    generics.clone(),
+                )
+            }
+            Overloaded::UninstSubprogram(signature, generics) => Overloaded::UninstSubprogram(
+                self.map_signature(mapping, signature)?,
+                generics.clone(),
+            ),
             Overloaded::Alias(alias) => {
                 let alias_inst = self.instantiate(mapping, alias)?;
 
@@ -341,6 +839,7 @@ impl<'a> AnalyzeContext<'a> {
         let Signature {
             formals,
             return_type,
+            impure,
         } = signature;
 
         let FormalRegion {
@@ -373,6 +872,7 @@ impl<'a> AnalyzeContext<'a> {
                 entities: inst_entities,
             },
             return_type,
+            impure: *impure,
         })
     }
 
@@ -390,15 +890,20 @@ impl<'a> AnalyzeContext<'a> {
         let mut inst_region = Region::default();
         inst_region.kind = *kind;
 
+        // Later declarations of the region refer to the instances of earlier ones
+        let mut mapping = mapping.clone();
+
         for (_, uninst) in uninst_entities.iter() {
             match uninst {
                 NamedEntities::Single(uninst) => {
-                    let inst = self.instantiate(mapping, uninst)?;
+                    let inst = self.instantiate(&mapping, uninst)?;
+                    mapping.insert(uninst.id(), inst);
                     inst_region.add(inst, &mut NullDiagnostics);
                 }
                 NamedEntities::Overloaded(overloaded) => {
                     for uninst in overloaded.entities() {
-                        let inst = self.instantiate(mapping, uninst.into())?;
+                        let inst = self.instantiate(&mapping, uninst.into())?;
+                        mapping.insert(uninst.id(), inst);
                         inst_region.add(inst, &mut NullDiagnostics);
                     }
                 }
@@ -470,6 +975,7 @@ impl<'a> AnalyzeContext<'a> {
             mode,
             subtype,
             has_default,
+            view,
         } = obj;
 
         Ok(Object {
@@ -477,9 +983,59 @@ impl<'a> AnalyzeContext<'a> {
             mode: *mode,
             subtype: self.map_subtype(mapping, *subtype)?,
             has_default: *has_default,
+            view: if let Some(view) = view {
+                Some(self.map_view_ent(mapping, *view)?)
+            } else {
+                None
+            },
         })
     }
 
+    fn map_view(
+        &self,
+        mapping: &FnvHashMap<EntityId, EntRef<'a>>,
+        view: &View<'a>,
+    ) -> Result<View<'a>, String> {
+        let mut elements = Vec::with_capacity(view.elements.len());
+        for (elem, mode) in view.elements.iter() {
+            let elem = mapping.get(&elem.id()).copied().unwrap_or(*elem);
+            let mode = match mode {
+                ViewElementMode::Simple(mode) => ViewElementMode::Simple(*mode),
+                ViewElementMode::Record(view) => {
+                    ViewElementMode::Record(self.map_view_ent(mapping, *view)?)
+                }
+                ViewElementMode::Array(view) => {
+                    ViewElementMode::Array(self.map_view_ent(mapping, *view)?)
+                }
+            };
+            elements.push((elem, mode));
+        }
+
+        Ok(View {
+            typ: self.map_type_ent(mapping, view.typ)?,
+            elements,
+        })
+    }
+
+    fn map_view_ent(
+        &self,
+        mapping: &FnvHashMap<EntityId, EntRef<'a>>,
+        view: ViewEnt<'a>,
+    ) -> Result<ViewEnt<'a>, String> {
+        if let Some(ent) = mapping.get(&view.id()) {
+            if let Some(view) = ViewEnt::from_any(ent) {
+                return Ok(view);
+            } else {
+                return Err(format!(
+                    "Internal error when mapping view, expected view got {}",
+                    ent.describe()
+                ));
+            }
+        }
+
+        Ok(view)
+    }
+
     fn map_type_ent(
         &self,
         mapping: &FnvHashMap<EntityId, EntRef<'a>>,
@@ -504,10 +1060,14 @@ impl<'a> AnalyzeContext<'a> {
         mapping: &FnvHashMap<EntityId, EntRef<'a>>,
         subtype: Subtype<'a>,
     ) -> Result<Subtype<'a>, String> {
-        let Subtype { type_mark } = subtype;
+        let Subtype {
+            type_mark,
+            resolved,
+        } = subtype;
 
         Ok(Subtype {
             type_mark: self.map_type_ent(mapping, type_mark)?,
+            resolved,
         })
     }
 }
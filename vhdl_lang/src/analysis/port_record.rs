@@ -0,0 +1,641 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Refactoring that groups ports of an entity into a record type
+
+use super::named_entity::EntityId;
+use super::root::DesignRoot;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashSet;
+
+/// Ports of an entity to group into a new record type declared in a package
+#[derive(Clone, Debug)]
+pub struct PortGrouping {
+    pub library_name: String,
+    pub entity_name: String,
+    pub ports: Vec<String>,
+    /// The name of the port of the record type that replaces the grouped ports
+    pub port_name: String,
+    pub record_name: String,
+    pub package_library: String,
+    pub package_name: String,
+}
+
+/// A declaration in a port list that declares one or more ports such as `a, b : in bit`
+struct PortDeclaration {
+    idents: Vec<WithDecl<Ident>>,
+    mode: Mode,
+    has_default: bool,
+    end: Position,
+}
+
+impl PortDeclaration {
+    fn start(&self) -> Position {
+        self.idents[0].tree.pos.start()
+    }
+
+    fn source(&self) -> &Source {
+        &self.idents[0].tree.pos.source
+    }
+
+    /// The position of the mode and subtype indication following the identifiers
+    fn subtype_pos(&self) -> SrcPos {
+        let last = &self.idents[self.idents.len() - 1].tree.pos;
+        self.source().pos(last.end(), self.end)
+    }
+
+    /// The text of the subtype indication without the colon and mode
+    fn subtype_text(&self) -> String {
+        let text = text_of(&self.subtype_pos());
+        let text = text.trim_start().trim_start_matches(':').trim_start();
+        let mode = self.mode.to_string();
+        match text.get(..mode.len()) {
+            Some(prefix)
+                if prefix.eq_ignore_ascii_case(&mode)
+                    && text[mode.len()..].starts_with(char::is_whitespace) =>
+            {
+                text[mode.len()..].trim().to_owned()
+            }
+            _ => text.trim().to_owned(),
+        }
+    }
+}
+
+/// Group the interface objects of a port list by the declaration they were declared by
+fn port_declarations(port_list: &[InterfaceDeclaration]) -> Vec<PortDeclaration> {
+    let mut declarations: Vec<PortDeclaration> = Vec::new();
+    let mut previous_type_mark: Option<&SrcPos> = None;
+
+    for decl in port_list.iter() {
+        let InterfaceDeclaration::Object(object) = decl else {
+            continue;
+        };
+        let type_mark = &object.subtype_indication.type_mark.pos;
+
+        if previous_type_mark == Some(type_mark) {
+            if let Some(last) = declarations.last_mut() {
+                last.idents.push(object.ident.clone());
+                continue;
+            }
+        }
+        previous_type_mark = Some(type_mark);
+
+        let end = if let Some(ref expr) = object.expression {
+            expr.pos.end()
+        } else if let Some(ref constraint) = object.subtype_indication.constraint {
+            constraint.pos.end()
+        } else {
+            type_mark.end()
+        };
+
+        declarations.push(PortDeclaration {
+            idents: vec![object.ident.clone()],
+            mode: object.mode,
+            has_default: object.expression.is_some(),
+            end,
+        });
+    }
+    declarations
+}
+
+fn text_of(pos: &SrcPos) -> String {
+    pos.source.contents().text(pos.range())
+}
+
+/// Rewrite a port list such that the selected ports are replaced by a declaration of the record port.
+/// The text between the remaining declarations such as comments is kept.
+fn rewrite_port_list(
+    declarations: &[PortDeclaration],
+    selected: &FnvHashSet<Symbol>,
+    record_port: &str,
+) -> Option<Fix> {
+    let first = declarations.first()?;
+    let last = declarations.last()?;
+
+    let separators: Vec<String> = declarations
+        .windows(2)
+        .map(|pair| text_of(&pair[0].source().pos(pair[0].end, pair[1].start())))
+        .collect();
+
+    let mut items: Vec<(usize, String)> = Vec::new();
+    let mut record_port_added = false;
+
+    for (idx, decl) in declarations.iter().enumerate() {
+        let kept: Vec<&WithDecl<Ident>> = decl
+            .idents
+            .iter()
+            .filter(|ident| !selected.contains(&ident.tree.item))
+            .collect();
+
+        if kept.is_empty() {
+            if !record_port_added {
+                items.push((idx, record_port.to_owned()));
+                record_port_added = true;
+            }
+        } else if kept.len() == decl.idents.len() {
+            items.push((idx, text_of(&decl.source().pos(decl.start(), decl.end))));
+        } else {
+            let names: Vec<String> = kept.iter().map(|ident| text_of(&ident.tree.pos)).collect();
+            items.push((
+                idx,
+                format!("{}{}", names.join(", "), text_of(&decl.subtype_pos())),
+            ));
+        }
+    }
+
+    if !record_port_added {
+        items.push((declarations.len() - 1, record_port.to_owned()));
+    }
+
+    let mut replacement = String::new();
+    let mut previous: Option<usize> = None;
+    for (idx, text) in items {
+        if let Some(previous) = previous {
+            // Use the separator after the previous item to keep its trailing comments
+            match separators.get(previous) {
+                Some(separator) => replacement.push_str(separator),
+                None => replacement.push_str("; "),
+            }
+        }
+        replacement.push_str(&text);
+        previous = Some(idx);
+    }
+
+    Some(Fix {
+        pos: first.source().pos(first.start(), last.end),
+        replacement,
+    })
+}
+
+/// Finds the instances of an entity and the components that are bound to it
+struct FindInstances {
+    entity: EntityId,
+    components: FnvHashSet<EntityId>,
+    positional: Option<SrcPos>,
+}
+
+impl Searcher for FindInstances {
+    fn search_instantiation(&mut self, instance: &InstantiationStatement) -> SearchState {
+        let is_instance = match instance.unit {
+            InstantiatedUnit::Entity(ref name, _) => {
+                name.item.get_suffix_reference() == Some(self.entity)
+            }
+            InstantiatedUnit::Component(ref name) => {
+                if instance.default_binding == Some(self.entity) {
+                    if let Some(id) = name.item.get_suffix_reference() {
+                        self.components.insert(id);
+                    }
+                    true
+                } else {
+                    false
+                }
+            }
+            InstantiatedUnit::Configuration(_) => false,
+        };
+
+        if is_instance {
+            if let Some(elem) = instance.port_map.iter().find(|elem| elem.formal.is_none()) {
+                self.positional.get_or_insert(elem.actual.pos.clone());
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Finds the declarations of components
+struct FindComponents<'a> {
+    components: &'a FnvHashSet<EntityId>,
+    found: Vec<(Ident, Vec<InterfaceDeclaration>)>,
+}
+
+impl<'a> Searcher for FindComponents<'a> {
+    fn search_decl(&mut self, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::Component(component) = decl {
+            if component
+                .ident
+                .decl
+                .is_some_and(|id| self.components.contains(&id))
+            {
+                self.found
+                    .push((component.ident.tree.clone(), component.port_list.clone()));
+            }
+        }
+        NotFinished
+    }
+}
+
+/// The context of a design unit that needs to see the record type
+struct UnitContext {
+    library_name: Symbol,
+    ident: Ident,
+    context_clause: ContextClause,
+}
+
+impl UnitContext {
+    fn new(library_name: &Symbol, unit: &AnyDesignUnit) -> UnitContext {
+        UnitContext {
+            library_name: library_name.clone(),
+            ident: unit.ident().clone(),
+            context_clause: unit.context_clause().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Add a use clause of the package unless the package is already used
+    fn use_package(&self, package_library: &Symbol, package_name: &Symbol) -> Option<Fix> {
+        let same_library = package_library == &self.library_name;
+
+        let mut has_library_clause = same_library;
+        for item in self.context_clause.iter() {
+            match item.item {
+                ContextItem::Use(ref use_clause) => {
+                    if use_clause.name_list.iter().any(|name| {
+                        uses_all_of(&name.item, package_library, package_name, same_library)
+                    }) {
+                        return None;
+                    }
+                }
+                ContextItem::Library(ref library_clause) => {
+                    if library_clause
+                        .name_list
+                        .iter()
+                        .any(|name| &name.item.item == package_library)
+                    {
+                        has_library_clause = true;
+                    }
+                }
+                ContextItem::Context(..) => {}
+            }
+        }
+
+        let mut lines = Vec::new();
+        if !has_library_clause {
+            lines.push(format!("library {package_library};"));
+        }
+        if same_library {
+            lines.push(format!("use work.{package_name}.all;"));
+        } else {
+            lines.push(format!("use {package_library}.{package_name}.all;"));
+        }
+
+        let source = &self.ident.pos.source;
+        Some(if let Some(last) = self.context_clause.last() {
+            let end = last.pos.end();
+            Fix {
+                pos: source.pos(end, end),
+                replacement: format!("\n{}", lines.join("\n")),
+            }
+        } else {
+            let start = Position::new(self.ident.pos.start().line, 0);
+            Fix {
+                pos: source.pos(start, start),
+                replacement: format!("{}\n\n", lines.join("\n")),
+            }
+        })
+    }
+}
+
+/// Returns true if the name is `library.package.all`, or `work.package.all` within the same library
+fn uses_all_of(name: &Name, library: &Symbol, package: &Symbol, same_library: bool) -> bool {
+    let Name::SelectedAll(ref prefix) = name else {
+        return false;
+    };
+    let Name::Selected(ref prefix, ref suffix) = prefix.item else {
+        return false;
+    };
+    let Name::Designator(ref prefix) = prefix.item else {
+        return false;
+    };
+    let (Designator::Identifier(prefix), Designator::Identifier(suffix)) =
+        (&prefix.item, &suffix.item.item)
+    else {
+        return false;
+    };
+    suffix == package && (prefix == library || (same_library && prefix.eq_basic("work")))
+}
+
+/// The line of the `end` of a package declaration
+fn package_end_line(package: &Ident) -> Option<u32> {
+    let contents = package.pos.source.contents();
+    let name = package.item.to_string();
+
+    (package.pos.start().line as usize + 1..contents.num_lines())
+        .find(|&lineno| {
+            let line = contents.get_line(lineno).unwrap_or_default().trim();
+            let Some(rest) = line
+                .get(..3)
+                .filter(|end| end.eq_ignore_ascii_case("end"))
+                .map(|_| line[3..].trim_start())
+            else {
+                return false;
+            };
+            let word = rest
+                .split(|chr: char| !(chr.is_alphanumeric() || chr == '_'))
+                .next()
+                .unwrap_or_default();
+            let is_name = if package.item.is_extended() {
+                // Extended identifiers are case-sensitive
+                rest.starts_with(&name)
+            } else {
+                word.eq_ignore_ascii_case(&name)
+            };
+            rest.starts_with(';') || word.eq_ignore_ascii_case("package") || is_name
+        })
+        .map(|lineno| lineno as u32)
+}
+
+fn indentation_of(source: &Source, line: u32) -> String {
+    let contents = source.contents();
+    contents
+        .get_line(line as usize)
+        .unwrap_or_default()
+        .chars()
+        .take_while(|chr| *chr == ' ' || *chr == '\t')
+        .collect()
+}
+
+impl DesignRoot {
+    /// The text edits to group ports of an entity into a new record type declared in a package.
+    /// The grouped ports are replaced by a single port of the record type in the entity and in
+    /// the components bound to it, and all references to the grouped ports, such as in
+    /// architectures and in the port maps of instances, are rewritten to select the record element.
+    /// The package must make the subtypes of the grouped ports visible.
+    pub fn group_ports_into_record(&self, grouping: &PortGrouping) -> Result<Vec<Fix>, String> {
+        let library_name = self.symbol_utf8(&grouping.library_name);
+        let entity_name = self.symbol_utf8(&grouping.entity_name);
+        let package_library = self.symbol_utf8(&grouping.package_library);
+        let package_name = self.symbol_utf8(&grouping.package_name);
+        let port_name = self.symbol_utf8(&grouping.port_name);
+        let record_name = self.symbol_utf8(&grouping.record_name);
+        let selected: FnvHashSet<Symbol> = grouping
+            .ports
+            .iter()
+            .map(|name| self.symbol_utf8(name))
+            .collect();
+
+        if selected.is_empty() {
+            return Err("No ports to group into a record".to_owned());
+        }
+
+        let entity_unit_id = UnitId::primary(&library_name, PrimaryKind::Entity, &entity_name);
+        let (entity_id, entity_context, generic_ids, mut names, declarations) = {
+            let unit = self
+                .get_unit(&entity_unit_id)
+                .and_then(|locked_unit| locked_unit.unit.get());
+            let Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity))) = unit.as_deref()
+            else {
+                return Err(format!(
+                    "No such entity '{}.{}'",
+                    grouping.library_name, grouping.entity_name
+                ));
+            };
+            let entity_id = entity
+                .ident
+                .decl
+                .ok_or_else(|| format!("The entity '{}' is not analyzed", entity.name()))?;
+
+            let mut generic_ids = Vec::new();
+            let mut names = Vec::new();
+            for decl in entity.generic_clause.iter().flatten() {
+                if let InterfaceDeclaration::Object(object) = decl {
+                    names.push(object.ident.tree.item.clone());
+                    generic_ids.extend(object.ident.decl);
+                }
+            }
+            let port_list = entity.port_clause.clone().unwrap_or_default();
+            (
+                entity_id,
+                UnitContext::new(&library_name, unit.as_deref().unwrap()),
+                generic_ids,
+                names,
+                port_declarations(&port_list),
+            )
+        };
+
+        let (package_ident, package_unit_id) = {
+            let package_unit_id = UnitId::package(&package_library, &package_name);
+            let unit = self
+                .get_unit(&package_unit_id)
+                .and_then(|locked_unit| locked_unit.unit.get());
+            let Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Package(ref package))) =
+                unit.as_deref()
+            else {
+                return Err(format!(
+                    "No such package '{}.{}'",
+                    grouping.package_library, grouping.package_name
+                ));
+            };
+
+            let declares_record = package.decl.iter().any(|decl| match decl {
+                Declaration::Type(typ) => typ.ident.tree.item == record_name,
+                _ => false,
+            });
+            if declares_record {
+                return Err(format!(
+                    "Package '{}' already declares '{}'",
+                    grouping.package_name, grouping.record_name
+                ));
+            }
+            (package.ident.tree.clone(), package_unit_id)
+        };
+
+        // Check the grouped ports
+        let mut grouped: Vec<(&PortDeclaration, &WithDecl<Ident>)> = Vec::new();
+        for decl in declarations.iter() {
+            for ident in decl.idents.iter() {
+                if selected.contains(&ident.tree.item) {
+                    grouped.push((decl, ident));
+                } else {
+                    names.push(ident.tree.item.clone());
+                }
+            }
+        }
+
+        for name in grouping.ports.iter() {
+            let sym = self.symbol_utf8(name);
+            if !grouped.iter().any(|(_, ident)| ident.tree.item == sym) {
+                return Err(format!(
+                    "Entity '{}' has no port '{}'",
+                    grouping.entity_name, name
+                ));
+            }
+        }
+
+        let mode = grouped[0].0.mode;
+        for (decl, ident) in grouped.iter() {
+            if decl.has_default {
+                return Err(format!(
+                    "Cannot group port '{}' since it has a default value",
+                    ident.tree.item
+                ));
+            }
+            if decl.mode != mode {
+                return Err("Cannot group ports of different modes into a record".to_owned());
+            }
+        }
+
+        if names.contains(&port_name) {
+            return Err(format!(
+                "Entity '{}' already declares '{}'",
+                grouping.entity_name, grouping.port_name
+            ));
+        }
+
+        // The record is declared outside of the entity where its generics are not visible
+        for generic_id in generic_ids {
+            let generic = self.get_ent(generic_id);
+            for pos in self.find_all_references(generic) {
+                for (decl, ident) in grouped.iter() {
+                    let subtype_pos = decl.subtype_pos();
+                    if pos.source == subtype_pos.source
+                        && pos.start() >= subtype_pos.start()
+                        && pos.end() <= subtype_pos.end()
+                    {
+                        return Err(format!(
+                            "The subtype of port '{}' depends on the generic '{}'",
+                            ident.tree.item,
+                            generic.designator()
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Instances with positional port maps cannot be rewritten
+        let mut instances = FindInstances {
+            entity: entity_id,
+            components: FnvHashSet::default(),
+            positional: None,
+        };
+        let _ = self.search(&mut instances);
+        if let Some(pos) = instances.positional {
+            return Err(format!(
+                "Cannot rewrite the positional port map at {}:{}",
+                pos.file_name().display(),
+                pos.start().line + 1
+            ));
+        }
+
+        let record_port = format!("{} : {} {}", grouping.port_name, mode, grouping.record_name);
+        let mut fixes = Vec::new();
+        let mut port_ids = Vec::new();
+        let mut unit_contexts = vec![entity_context];
+
+        fixes.extend(rewrite_port_list(&declarations, &selected, &record_port));
+        port_ids.extend(grouped.iter().filter_map(|(_, ident)| ident.decl));
+
+        // Rewrite the components bound to the entity
+        let mut record_line = None;
+        if !instances.components.is_empty() {
+            for locked_unit in self.locked_units() {
+                let mut unit = locked_unit.unit.write();
+                let mut components = FindComponents {
+                    components: &instances.components,
+                    found: Vec::new(),
+                };
+                let _ = unit.search(&mut components);
+                if components.found.is_empty() {
+                    continue;
+                }
+
+                let unit_id = locked_unit.unit_id();
+                for (ident, port_list) in components.found {
+                    let declarations = port_declarations(&port_list);
+                    for name in grouping.ports.iter() {
+                        let sym = self.symbol_utf8(name);
+                        let declared = declarations
+                            .iter()
+                            .any(|decl| decl.idents.iter().any(|ident| ident.tree.item == sym));
+                        if !declared {
+                            return Err(format!(
+                                "Component '{}' does not declare port '{}'",
+                                ident.item, name
+                            ));
+                        }
+                    }
+
+                    fixes.extend(rewrite_port_list(&declarations, &selected, &record_port));
+                    for decl in declarations.iter() {
+                        port_ids.extend(
+                            decl.idents
+                                .iter()
+                                .filter(|ident| selected.contains(&ident.tree.item))
+                                .filter_map(|ident| ident.decl),
+                        );
+                    }
+
+                    // The record must be declared before components of the package that use it
+                    if unit_id == &package_unit_id {
+                        let line = ident.pos.start().line;
+                        record_line = Some(record_line.map_or(line, |other: u32| other.min(line)));
+                    }
+                }
+
+                if unit_id != &package_unit_id {
+                    unit_contexts.push(UnitContext::new(unit_id.library_name(), &unit));
+                }
+            }
+        }
+
+        for port_id in port_ids {
+            let port = self.get_ent(port_id);
+            for pos in self.find_all_references(port) {
+                if Some(&pos) != port.decl_pos() {
+                    let replacement = format!("{}.{}", grouping.port_name, text_of(&pos));
+                    fixes.push(Fix { pos, replacement });
+                }
+            }
+        }
+
+        for context in unit_contexts.iter() {
+            fixes.extend(context.use_package(&package_library, &package_name));
+        }
+
+        // Declare the record type in the package
+        let source = &package_ident.pos.source;
+        let (line, at_end) = if let Some(line) = record_line {
+            (line, false)
+        } else {
+            let line = package_end_line(&package_ident).ok_or_else(|| {
+                format!(
+                    "Cannot find the end of the package '{}'",
+                    grouping.package_name
+                )
+            })?;
+            (line, true)
+        };
+        let indent = if at_end {
+            format!("{}  ", indentation_of(source, line))
+        } else {
+            indentation_of(source, line)
+        };
+
+        let mut record = format!("{indent}type {} is record\n", grouping.record_name);
+        for (decl, ident) in grouped.iter() {
+            record.push_str(&format!(
+                "{indent}  {} : {};\n",
+                text_of(&ident.tree.pos),
+                decl.subtype_text()
+            ));
+        }
+        record.push_str(&format!("{indent}end record;\n"));
+
+        let start = Position::new(line, 0);
+        fixes.push(Fix {
+            pos: source.pos(start, start),
+            replacement: if at_end {
+                format!("\n{record}")
+            } else {
+                format!("{record}\n")
+            },
+        });
+
+        fixes.sort_by(|left, right| left.pos.cmp(&right.pos));
+        fixes.dedup();
+        Ok(fixes)
+    }
+}
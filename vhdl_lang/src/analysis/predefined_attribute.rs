@@ -0,0 +1,268 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Typing of the predefined attributes of types and objects
+//!
+//! Each predefined attribute is described by the kind of prefix it is defined for,
+//! the parameter it takes and the type of its result. Signal, range and type attributes
+//! are not values of the prefix type and are analyzed elsewhere.
+
+use super::analyze::*;
+use super::named_entity::*;
+use super::names::{AttributeSuffix, ResolvedName};
+use super::region::*;
+use crate::ast::*;
+use crate::data::*;
+
+/// LRM 16.2 The prefix a predefined attribute is defined for
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum AttributePrefix {
+    /// A scalar type or an array type or object
+    ScalarOrArray,
+    /// An array type or object
+    Array,
+    /// A scalar type
+    ScalarType,
+    /// A discrete or physical type
+    DiscreteOrPhysicalType,
+    /// Any named entity
+    Any,
+}
+
+/// LRM 16.2 The parameter of a predefined attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum AttributeParameter {
+    /// No parameter
+    None,
+    /// An optional dimension when the prefix is an array, no parameter otherwise
+    Dimension,
+    /// A value of the prefix type
+    Value,
+    /// A value of type STRING
+    String,
+    /// A value of any integer type
+    Integer,
+}
+
+/// LRM 16.2 The result type of a predefined attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum AttributeResult {
+    /// The index type of the dimension when the prefix is an array, the prefix type otherwise
+    Bound,
+    /// The base type of the prefix
+    PrefixBase,
+    Boolean,
+    String,
+    UniversalInteger,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct PredefinedAttribute {
+    prefix: AttributePrefix,
+    parameter: AttributeParameter,
+    result: AttributeResult,
+}
+
+impl PredefinedAttribute {
+    const fn new(
+        prefix: AttributePrefix,
+        parameter: AttributeParameter,
+        result: AttributeResult,
+    ) -> PredefinedAttribute {
+        PredefinedAttribute {
+            prefix,
+            parameter,
+            result,
+        }
+    }
+
+    /// LRM 16.2 Predefined attributes that denote values of types and objects
+    fn of(designator: &AttributeDesignator) -> Option<PredefinedAttribute> {
+        use AttributePrefix::*;
+        use AttributeResult::*;
+
+        Some(match designator {
+            AttributeDesignator::Left
+            | AttributeDesignator::Right
+            | AttributeDesignator::High
+            | AttributeDesignator::Low => {
+                Self::new(ScalarOrArray, AttributeParameter::Dimension, Bound)
+            }
+            AttributeDesignator::Ascending | AttributeDesignator::Descending => {
+                Self::new(ScalarOrArray, AttributeParameter::Dimension, Boolean)
+            }
+            AttributeDesignator::Length => {
+                Self::new(Array, AttributeParameter::Dimension, UniversalInteger)
+            }
+            AttributeDesignator::Image => Self::new(ScalarType, AttributeParameter::Value, String),
+            AttributeDesignator::Value => {
+                Self::new(ScalarType, AttributeParameter::String, PrefixBase)
+            }
+            AttributeDesignator::Pos => Self::new(
+                DiscreteOrPhysicalType,
+                AttributeParameter::Value,
+                UniversalInteger,
+            ),
+            AttributeDesignator::Val => Self::new(
+                DiscreteOrPhysicalType,
+                AttributeParameter::Integer,
+                PrefixBase,
+            ),
+            AttributeDesignator::Succ
+            | AttributeDesignator::Pred
+            | AttributeDesignator::LeftOf
+            | AttributeDesignator::RightOf => Self::new(
+                DiscreteOrPhysicalType,
+                AttributeParameter::Value,
+                PrefixBase,
+            ),
+            AttributeDesignator::SimpleName
+            | AttributeDesignator::InstanceName
+            | AttributeDesignator::PathName => Self::new(Any, AttributeParameter::None, String),
+            AttributeDesignator::Type(_)
+            | AttributeDesignator::Range(_)
+            | AttributeDesignator::Signal(_)
+            | AttributeDesignator::Ident(_) => {
+                return None;
+            }
+        })
+    }
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// The type of a predefined attribute of a type or object
+    /// Returns None if the prefix is of the wrong kind for the attribute
+    #[allow(clippy::too_many_arguments)]
+    pub fn attribute_suffix(
+        &self,
+        pos: &SrcPos,
+        prefix_pos: &SrcPos,
+        scope: &Scope<'a>,
+        prefix: &ResolvedName<'a>,
+        typ: TypeEnt<'a>,
+        attr: &mut AttributeSuffix,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<Option<BaseType<'a>>> {
+        let Some(predefined) = PredefinedAttribute::of(&attr.attr.item) else {
+            return Err(EvalError::Unknown);
+        };
+
+        let is_type_prefix = matches!(prefix, ResolvedName::Type(_));
+        let array_indexes = typ.array_type().map(|(_, indexes)| indexes);
+
+        let is_valid_prefix = match predefined.prefix {
+            AttributePrefix::ScalarOrArray => array_indexes.is_some() || typ.is_scalar(),
+            AttributePrefix::Array => array_indexes.is_some(),
+            AttributePrefix::ScalarType => typ.is_scalar(),
+            AttributePrefix::DiscreteOrPhysicalType => {
+                typ.base().is_discrete() || typ.base().is_physical()
+            }
+            AttributePrefix::Any => true,
+        };
+
+        if !is_valid_prefix {
+            return Ok(None);
+        }
+
+        // LRM 16.2.2 Since VHDL-2019 the 'image attribute is also defined for objects
+        let is_object_image = attr.attr.item == AttributeDesignator::Image
+            && !is_type_prefix
+            && attr.expr.is_none()
+            && self.root.standard() >= VHDLStandard::VHDL2019;
+
+        let requires_type_prefix = matches!(
+            predefined.prefix,
+            AttributePrefix::ScalarType | AttributePrefix::DiscreteOrPhysicalType
+        );
+
+        if requires_type_prefix && !is_type_prefix && !is_object_image {
+            diagnostics.error(
+                prefix_pos,
+                format!(
+                    "Expected type prefix for '{} attribute, got {}",
+                    attr.attr.item,
+                    prefix.describe()
+                ),
+            );
+            return Err(EvalError::Unknown);
+        }
+
+        let mut dimension = 0;
+        match predefined.parameter {
+            AttributeParameter::None => check_no_attr_argument(attr, diagnostics),
+            AttributeParameter::Dimension => {
+                if let Some(indexes) = array_indexes {
+                    dimension = as_fatal(self.attribute_dimension(
+                        scope,
+                        &attr.attr.item,
+                        attr.expr.as_deref_mut(),
+                        indexes.len(),
+                        diagnostics,
+                    ))?
+                    .unwrap_or(0);
+                } else {
+                    check_no_attr_argument(attr, diagnostics);
+                }
+            }
+            AttributeParameter::Value => {
+                if !is_object_image {
+                    if let Some(expr) = check_single_argument(pos, attr, diagnostics) {
+                        self.expr_with_ttyp(scope, typ, expr, diagnostics)?;
+                    }
+                }
+            }
+            AttributeParameter::String => {
+                if let Some(expr) = check_single_argument(pos, attr, diagnostics) {
+                    self.expr_with_ttyp(scope, self.string(), expr, diagnostics)?;
+                }
+            }
+            AttributeParameter::Integer => {
+                if let Some(expr) = check_single_argument(pos, attr, diagnostics) {
+                    self.integer_expr(scope, expr, diagnostics)?;
+                }
+            }
+        }
+
+        Ok(Some(match predefined.result {
+            AttributeResult::Bound => {
+                if let Some(indexes) = array_indexes {
+                    indexes[dimension].ok_or(EvalError::Unknown)?
+                } else {
+                    typ.base()
+                }
+            }
+            AttributeResult::PrefixBase => typ.base(),
+            AttributeResult::Boolean => self.boolean().base(),
+            AttributeResult::String => self.string().base(),
+            AttributeResult::UniversalInteger => self.universal_integer(),
+        }))
+    }
+}
+
+fn check_no_attr_argument(suffix: &AttributeSuffix, diagnostics: &mut dyn DiagnosticHandler) {
+    if let Some(ref expr) = suffix.expr {
+        diagnostics.error(
+            &expr.pos,
+            format!("'{} attribute does not take an argument", suffix.attr),
+        )
+    }
+}
+
+fn check_single_argument<'a>(
+    pos: &SrcPos,
+    suffix: &'a mut AttributeSuffix,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> Option<&'a mut WithPos<Expression>> {
+    if let Some(ref mut expr) = suffix.expr {
+        Some(expr)
+    } else {
+        diagnostics.error(
+            pos,
+            format!("'{} attribute requires a single argument", suffix.attr),
+        );
+        None
+    }
+}
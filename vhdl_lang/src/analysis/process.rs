@@ -0,0 +1,1014 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Checks of process statements that are performed after the process has been analyzed
+//! and all names within it have been resolved
+
+use super::analyze::*;
+use super::named_entity::*;
+use super::unused::written_object;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashSet;
+
+impl<'a> AnalyzeContext<'a> {
+    /// Warn about signals that are not assigned on every path through a combinational process.
+    /// Such signals must keep their previous value which infers a latch.
+    pub fn check_latch_inference(
+        &self,
+        process: &ProcessStatement,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if classify_process(process) != ProcessKind::Combinational {
+            return;
+        }
+
+        let mut assigned = Vec::new();
+        self.assigned_signals(&process.statements, &mut assigned);
+        if assigned.is_empty() {
+            return;
+        }
+
+        let always_assigned = self.always_assigned_signals(&process.statements);
+        for (signal, pos) in assigned {
+            if !always_assigned.contains(&signal.id()) {
+                let mut diagnostic = Diagnostic::warning(
+                    pos,
+                    format!(
+                        "Latch inferred for {} which is not assigned in every branch of the process",
+                        signal.describe_name()
+                    ),
+                );
+                if let Some((branch_pos, message)) =
+                    self.missing_branch(&process.statements, signal.id())
+                {
+                    diagnostic.add_related(branch_pos, message);
+                }
+                diagnostics.push(diagnostic.with_code(ErrorCode::LatchInference));
+            }
+        }
+    }
+
+    /// Compare the sensitivity list of a combinational process with the signals it reads and
+    /// warn about read signals that are missing and listed signals that are never read.
+    /// A process that ends with its only wait statement `wait on ...;` is sensitive to the
+    /// signals of that statement. A process with the sensitivity list `all` is sensitive to
+    /// every signal it reads.
+    pub fn check_sensitivity_completeness(
+        &self,
+        process: &mut ProcessStatement,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let check_missing = self.root.is_lint_enabled(ErrorCode::IncompleteSensitivity);
+        let check_superfluous = self.root.is_lint_enabled(ErrorCode::SuperfluousSensitivity);
+        if !(check_missing || check_superfluous) || has_clock_edge(&process.statements) {
+            return;
+        }
+
+        let ProcessStatement {
+            sensitivity_list,
+            statements,
+            ..
+        } = process;
+        let (names, statements) = match sensitivity_list {
+            Some(SensitivityList::Names(names)) => (&*names, statements.as_mut_slice()),
+            Some(SensitivityList::All) => return,
+            None => {
+                let Some((last, rest)) = statements.split_last_mut() else {
+                    return;
+                };
+                match last.statement {
+                    SequentialStatement::Wait(WithPos {
+                        item:
+                            WaitStatement {
+                                ref sensitivity_clause,
+                                condition_clause: None,
+                                timeout_clause: None,
+                            },
+                        ..
+                    }) if !sensitivity_clause.is_empty() && !rest.iter().any(is_wait) => {
+                        (sensitivity_clause, rest)
+                    }
+                    _ => return,
+                }
+            }
+        };
+        let Some((first, last)) = names.first().zip(names.last()) else {
+            return;
+        };
+
+        let listed: Vec<(&WithPos<Name>, EntityId)> = names
+            .iter()
+            .filter_map(|name| Some((name, written_object(&name.pos, &name.item)?.1)))
+            .collect();
+        let reads = statement_read_signals(statements, &|id| self.is_signal(id));
+
+        let superfluous: Vec<_> = listed
+            .iter()
+            .filter(|(_, id)| !reads.iter().any(|(read, _)| read == id))
+            .collect();
+        let missing: Vec<_> = reads
+            .iter()
+            .filter(|(id, _)| !listed.iter().any(|(_, listed)| listed == id))
+            .filter_map(|(id, pos)| Some((ObjectEnt::from_any(self.arena.get(*id))?, pos)))
+            .collect();
+
+        // The names that are read in the order of the list followed by the missing signals
+        let expected = listed
+            .iter()
+            .filter(|(_, id)| reads.iter().any(|(read, _)| read == id))
+            .map(|(name, _)| name.item.to_string())
+            .chain(
+                missing
+                    .iter()
+                    .map(|(signal, _)| signal.designator().to_string()),
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+        let list_pos = first.pos.combine(&last.pos);
+
+        if check_missing {
+            for (signal, pos) in missing.iter() {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        pos,
+                        format!(
+                            "{} is read by the process but is not in the sensitivity list",
+                            capitalize(&signal.describe_name())
+                        ),
+                    )
+                    .with_code(ErrorCode::IncompleteSensitivity)
+                    .with_fix(&list_pos, &expected),
+                );
+            }
+        }
+
+        // An empty list is not valid
+        if check_superfluous && !expected.is_empty() {
+            for (name, _) in superfluous {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        &name.pos,
+                        format!(
+                            "'{}' is in the sensitivity list but is not read by the process",
+                            name.item
+                        ),
+                    )
+                    .with_code(ErrorCode::SuperfluousSensitivity)
+                    .with_fix(&list_pos, &expected),
+                );
+            }
+        }
+    }
+
+    pub(super) fn is_signal(&self, id: EntityId) -> bool {
+        ObjectEnt::from_any(self.arena.get(id))
+            .is_some_and(|object| object.class() == ObjectClass::Signal)
+    }
+
+    /// Warn about clocked processes whose reset does not follow the reset convention of the project
+    pub fn check_reset_convention(
+        &self,
+        process: &ProcessStatement,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(convention) = self.root.reset_convention() else {
+            return;
+        };
+
+        let ProcessKind::Clocked(clocked) = classify_process(process) else {
+            return;
+        };
+
+        if let Some(reset) = clocked.reset {
+            if let Some(style) = convention.style {
+                if reset.style != style {
+                    diagnostics.warning(
+                        &reset.pos,
+                        format!(
+                            "{} reset does not follow the {} reset convention of the project",
+                            capitalize(reset.style.describe()),
+                            style.describe()
+                        ),
+                    );
+                }
+            }
+
+            if let Some(level) = convention.level {
+                if reset.level != level {
+                    diagnostics.warning(
+                        &reset.pos,
+                        format!(
+                            "Reset is {} but the reset convention of the project is {}",
+                            reset.level.describe(),
+                            level.describe()
+                        ),
+                    );
+                }
+            }
+        } else if convention.required {
+            diagnostics.warning(
+                &clocked.clock_pos,
+                "Clocked process has no reset but the reset convention of the project requires one",
+            );
+        }
+    }
+
+    /// The signals assigned within the statements together with the position of the first assignment
+    pub(super) fn assigned_signals(
+        &self,
+        statements: &[LabeledSequentialStatement],
+        assigned: &mut Vec<(ObjectEnt<'a>, SrcPos)>,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    if let Some(signal) = self.target_signal(&assign.target.item) {
+                        if !assigned.iter().any(|(ent, _)| ent.id() == signal.id()) {
+                            assigned.push((signal, assign.target.pos.clone()));
+                        }
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.assigned_signals(&conditional.item, assigned);
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.assigned_signals(else_item, assigned);
+                    }
+                }
+                SequentialStatement::Case(ref case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter() {
+                        self.assigned_signals(&alternative.item, assigned);
+                    }
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    self.assigned_signals(&loop_stmt.statements, assigned);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The signals that are assigned on every path through the statements
+    fn always_assigned_signals(
+        &self,
+        statements: &[LabeledSequentialStatement],
+    ) -> FnvHashSet<EntityId> {
+        let mut result = FnvHashSet::default();
+
+        for statement in statements.iter() {
+            match statement.statement {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    let is_complete = match assign.rhs {
+                        AssignmentRightHand::Simple(..) | AssignmentRightHand::Selected(..) => true,
+                        AssignmentRightHand::Conditional(ref conditionals) => {
+                            conditionals.else_item.is_some()
+                        }
+                    };
+
+                    if is_complete {
+                        if let Some(signal) = self.target_signal(&assign.target.item) {
+                            result.insert(signal.id());
+                        }
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        let branches = ifstmt
+                            .conditionals
+                            .iter()
+                            .map(|conditional| &conditional.item)
+                            .chain(std::iter::once(else_item));
+                        result.extend(self.assigned_in_all_branches(branches));
+                    }
+                }
+                SequentialStatement::Case(ref case_stmt) => {
+                    let branches = case_stmt
+                        .alternatives
+                        .iter()
+                        .map(|alternative| &alternative.item);
+                    result.extend(self.assigned_in_all_branches(branches));
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    if let Some(body) = self.always_executed_body(loop_stmt) {
+                        result.extend(self.always_assigned_signals(body));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        result
+    }
+
+    fn assigned_in_all_branches<'s>(
+        &self,
+        mut branches: impl Iterator<Item = &'s Vec<LabeledSequentialStatement>>,
+    ) -> FnvHashSet<EntityId> {
+        let mut result = if let Some(first) = branches.next() {
+            self.always_assigned_signals(first)
+        } else {
+            return FnvHashSet::default();
+        };
+
+        for branch in branches {
+            let assigned = self.always_assigned_signals(branch);
+            result.retain(|id| assigned.contains(id));
+        }
+        result
+    }
+
+    /// The position of the first branch within the statements that does not assign the signal
+    /// although it is assigned within the statements
+    fn missing_branch(
+        &self,
+        statements: &[LabeledSequentialStatement],
+        signal: EntityId,
+    ) -> Option<(SrcPos, &'static str)> {
+        let statement = statements
+            .iter()
+            .find(|statement| self.assigns_signal(std::slice::from_ref(*statement), signal))?;
+
+        match statement.statement {
+            SequentialStatement::SignalAssignment(ref assign) => match assign.rhs {
+                AssignmentRightHand::Conditional(ref conditionals)
+                    if conditionals.else_item.is_none() =>
+                {
+                    let last = conditionals.conditionals.last()?;
+                    Some((
+                        last.condition.pos.clone(),
+                        "Not assigned when no condition holds",
+                    ))
+                }
+                _ => None,
+            },
+            SequentialStatement::If(ref ifstmt) => {
+                for conditional in ifstmt.conditionals.iter() {
+                    if let Some(missing) = self.missing_in_branch(
+                        &conditional.item,
+                        signal,
+                        &conditional.condition.pos,
+                    ) {
+                        return Some(missing);
+                    }
+                }
+                let last = ifstmt.conditionals.last()?;
+                match ifstmt.else_item {
+                    Some(ref else_item) if self.assigns_signal(else_item, signal) => {
+                        self.missing_branch(else_item, signal)
+                    }
+                    _ => Some((
+                        last.condition.pos.clone(),
+                        "Not assigned when no condition holds",
+                    )),
+                }
+            }
+            SequentialStatement::Case(ref case_stmt) => {
+                for alternative in case_stmt.alternatives.iter() {
+                    let choice_pos = match alternative.choices.first() {
+                        Some(Choice::Expression(ref expr)) => &expr.pos,
+                        _ => &case_stmt.expression.pos,
+                    };
+                    if let Some(missing) =
+                        self.missing_in_branch(&alternative.item, signal, choice_pos)
+                    {
+                        return Some(missing);
+                    }
+                }
+                None
+            }
+            SequentialStatement::Loop(ref loop_stmt) => {
+                let body = self.always_executed_body(loop_stmt)?;
+                self.missing_branch(body, signal)
+            }
+            _ => None,
+        }
+    }
+
+    /// The statements of a loop when they are executed at least once in full.
+    /// That is the case for a for loop with a static range that is not null and a body without
+    /// next or exit statements. Other loops might not execute at all.
+    fn always_executed_body<'s>(
+        &self,
+        loop_stmt: &'s LoopStatement,
+    ) -> Option<&'s [LabeledSequentialStatement]> {
+        let Some(IterationScheme::For(_, ref drange)) = loop_stmt.iteration_scheme else {
+            return None;
+        };
+        let range = self.static_evaluator().eval_discrete_range(None, drange)?;
+        if range.is_null() || leaves_iteration(&loop_stmt.statements) {
+            return None;
+        }
+        Some(&loop_stmt.statements)
+    }
+
+    fn missing_in_branch(
+        &self,
+        branch: &[LabeledSequentialStatement],
+        signal: EntityId,
+        branch_pos: &SrcPos,
+    ) -> Option<(SrcPos, &'static str)> {
+        if self.always_assigned_signals(branch).contains(&signal) {
+            None
+        } else if self.assigns_signal(branch, signal) {
+            self.missing_branch(branch, signal)
+        } else {
+            Some((branch_pos.clone(), "Not assigned in this branch"))
+        }
+    }
+
+    fn assigns_signal(&self, statements: &[LabeledSequentialStatement], signal: EntityId) -> bool {
+        let mut assigned = Vec::new();
+        self.assigned_signals(statements, &mut assigned);
+        assigned.iter().any(|(ent, _)| ent.id() == signal)
+    }
+
+    /// The signal that is the target of an assignment
+    /// Assignments to an element or slice of a signal are considered assignments of the whole signal
+    pub(super) fn target_signal(&self, target: &Target) -> Option<ObjectEnt<'a>> {
+        if let Target::Name(ref name) = target {
+            self.name_base_object(name)
+                .filter(|object| object.class() == ObjectClass::Signal)
+        } else {
+            None
+        }
+    }
+
+    fn name_base_object(&self, name: &Name) -> Option<ObjectEnt<'a>> {
+        match name {
+            Name::Designator(designator) => designator
+                .reference
+                .and_then(|id| ObjectEnt::from_any(self.arena.get(id))),
+            Name::Selected(prefix, suffix) => suffix
+                .item
+                .reference
+                .and_then(|id| ObjectEnt::from_any(self.arena.get(id)))
+                .or_else(|| self.name_base_object(&prefix.item)),
+            Name::Slice(prefix, ..) => self.name_base_object(&prefix.item),
+            Name::CallOrIndexed(fcall) => self.name_base_object(&fcall.name.item),
+            Name::SelectedAll(..) | Name::Attribute(..) | Name::External(..) => None,
+        }
+    }
+}
+
+/// LRM 11.3 The signals read by the statements of a process together with the position of
+/// their first read. This is the set of signals a process with the sensitivity list `all`
+/// is sensitive to. Signals that are only the target of assignments are not read.
+pub(crate) fn read_signals(
+    process: &mut ProcessStatement,
+    is_signal: &dyn Fn(EntityId) -> bool,
+) -> Vec<(EntityId, SrcPos)> {
+    statement_read_signals(&mut process.statements, is_signal)
+}
+
+fn statement_read_signals(
+    statements: &mut [LabeledSequentialStatement],
+    is_signal: &dyn Fn(EntityId) -> bool,
+) -> Vec<(EntityId, SrcPos)> {
+    let mut searcher = FindSignalReads {
+        writes: Vec::new(),
+        is_signal,
+        reads: Vec::new(),
+    };
+    assignment_targets(statements, &mut searcher.writes);
+    for statement in statements.iter_mut() {
+        let _ = statement.search(&mut searcher);
+    }
+    searcher.reads
+}
+
+/// The signals referenced within an expression or the right hand side of an assignment
+/// together with the position of their first reference
+pub(super) fn signal_reads(
+    item: &mut impl Search,
+    is_signal: &dyn Fn(EntityId) -> bool,
+) -> Vec<(EntityId, SrcPos)> {
+    let mut searcher = FindSignalReads {
+        writes: Vec::new(),
+        is_signal,
+        reads: Vec::new(),
+    };
+    let _ = item.search(&mut searcher);
+    searcher.reads
+}
+
+/// The positions of the objects that are the targets of signal assignments within the statements
+fn assignment_targets(statements: &[LabeledSequentialStatement], writes: &mut Vec<SrcPos>) {
+    for statement in statements.iter() {
+        let target = match statement.statement {
+            SequentialStatement::SignalAssignment(ref assign) => &assign.target,
+            SequentialStatement::SignalForceAssignment(ref assign) => &assign.target,
+            SequentialStatement::SignalReleaseAssignment(ref assign) => &assign.target,
+            SequentialStatement::If(ref ifstmt) => {
+                for conditional in ifstmt.conditionals.iter() {
+                    assignment_targets(&conditional.item, writes);
+                }
+                if let Some(ref else_item) = ifstmt.else_item {
+                    assignment_targets(else_item, writes);
+                }
+                continue;
+            }
+            SequentialStatement::Case(ref case_stmt) => {
+                for alternative in case_stmt.alternatives.iter() {
+                    assignment_targets(&alternative.item, writes);
+                }
+                continue;
+            }
+            SequentialStatement::Loop(ref loop_stmt) => {
+                assignment_targets(&loop_stmt.statements, writes);
+                continue;
+            }
+            _ => continue,
+        };
+        if let Target::Name(ref name) = target.item {
+            if let Some((pos, _)) = written_object(&target.pos, name) {
+                writes.push(pos);
+            }
+        }
+    }
+}
+
+/// Whether the statements contain a next or exit statement that can end an iteration early
+fn leaves_iteration(statements: &[LabeledSequentialStatement]) -> bool {
+    statements
+        .iter()
+        .any(|statement| match statement.statement {
+            SequentialStatement::Next(..) | SequentialStatement::Exit(..) => true,
+            SequentialStatement::If(ref ifstmt) => {
+                ifstmt
+                    .conditionals
+                    .iter()
+                    .any(|conditional| leaves_iteration(&conditional.item))
+                    || ifstmt.else_item.as_deref().is_some_and(leaves_iteration)
+            }
+            SequentialStatement::Case(ref case_stmt) => case_stmt
+                .alternatives
+                .iter()
+                .any(|alternative| leaves_iteration(&alternative.item)),
+            SequentialStatement::Loop(ref loop_stmt) => leaves_iteration(&loop_stmt.statements),
+            _ => false,
+        })
+}
+
+/// Finds the signals that are referenced other than as the target of an assignment
+struct FindSignalReads<'b> {
+    writes: Vec<SrcPos>,
+    is_signal: &'b dyn Fn(EntityId) -> bool,
+    reads: Vec<(EntityId, SrcPos)>,
+}
+
+impl<'b> Searcher for FindSignalReads<'b> {
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        if let Some(id) = *reference {
+            if (self.is_signal)(id)
+                && !self.writes.contains(pos)
+                && !self.reads.iter().any(|(read, _)| *read == id)
+            {
+                self.reads.push((id, pos.clone()));
+            }
+        }
+        NotFinished
+    }
+}
+
+/// The edge of a clock that a clocked process is sensitive to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockEdge {
+    Rising,
+    Falling,
+}
+
+/// If the reset of a clocked process is checked before or after the clock edge
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResetStyle {
+    Synchronous,
+    Asynchronous,
+}
+
+/// The level of a signal that activates the reset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActiveLevel {
+    High,
+    Low,
+}
+
+impl ResetStyle {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ResetStyle::Synchronous => "synchronous",
+            ResetStyle::Asynchronous => "asynchronous",
+        }
+    }
+}
+
+impl ActiveLevel {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ActiveLevel::High => "active high",
+            ActiveLevel::Low => "active low",
+        }
+    }
+}
+
+/// The reset that clocked processes of a project are expected to use
+///
+/// A style or level of None accepts any style or level
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResetConvention {
+    pub style: Option<ResetStyle>,
+    pub level: Option<ActiveLevel>,
+    /// Clocked processes without a reset are reported
+    pub required: bool,
+}
+
+/// The reset of a clocked process
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessReset {
+    pub signal: Reference,
+    pub style: ResetStyle,
+    pub level: ActiveLevel,
+    /// The position of the reset condition
+    pub pos: SrcPos,
+}
+
+/// A process that follows the template of a register
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClockedProcess {
+    pub clock: Reference,
+    pub edge: ClockEdge,
+    /// The position of the clock edge condition
+    pub clock_pos: SrcPos,
+    pub reset: Option<ProcessReset>,
+}
+
+/// The synthesis template that a process follows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProcessKind {
+    /// A process with a sensitivity list and no clock edge
+    Combinational,
+    /// A process that only updates on a clock edge, with an optional reset
+    Clocked(ClockedProcess),
+    /// A process that does not follow any template such as a test bench process
+    Other,
+}
+
+/// A process of an architecture together with its classification
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassifiedProcess {
+    pub label: Option<Ident>,
+    pub kind: ProcessKind,
+    /// The signals read by the process in the order of their first read,
+    /// a process with the sensitivity list `all` is sensitive to them
+    pub read_signals: Vec<EntityId>,
+}
+
+/// Classify all processes of the concurrent statements
+/// including processes within block and generate statements
+pub fn classify_processes(
+    statements: &mut [LabeledConcurrentStatement],
+    is_signal: &dyn Fn(EntityId) -> bool,
+) -> Vec<ClassifiedProcess> {
+    let mut processes = Vec::new();
+    collect_processes(statements, is_signal, &mut processes);
+    processes
+}
+
+fn collect_processes(
+    statements: &mut [LabeledConcurrentStatement],
+    is_signal: &dyn Fn(EntityId) -> bool,
+    processes: &mut Vec<ClassifiedProcess>,
+) {
+    for statement in statements.iter_mut() {
+        match statement.statement {
+            ConcurrentStatement::Process(ref mut process) => {
+                processes.push(ClassifiedProcess {
+                    label: statement.label.as_ref().map(|label| label.tree.clone()),
+                    kind: classify_process(process),
+                    read_signals: read_signals(process, is_signal)
+                        .into_iter()
+                        .map(|(id, _)| id)
+                        .collect(),
+                });
+            }
+            ConcurrentStatement::Block(ref mut block) => {
+                collect_processes(&mut block.statements, is_signal, processes);
+            }
+            ConcurrentStatement::ForGenerate(ref mut gen) => {
+                collect_processes(&mut gen.body.statements, is_signal, processes);
+            }
+            ConcurrentStatement::IfGenerate(ref mut gen) => {
+                for conditional in gen.conditionals.iter_mut() {
+                    collect_processes(&mut conditional.item.statements, is_signal, processes);
+                }
+                if let Some(ref mut else_item) = gen.else_item {
+                    collect_processes(&mut else_item.statements, is_signal, processes);
+                }
+            }
+            ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                for alternative in gen.alternatives.iter_mut() {
+                    collect_processes(&mut alternative.item.statements, is_signal, processes);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Classify a process by recognizing the common synthesis templates
+///
+/// Clocked processes are recognized in the following forms:
+///
+/// process (clk) begin if rising_edge(clk) then ... end if; end process;
+/// process (clk, rst) begin if rst = '1' then ... elsif rising_edge(clk) then ... end if; end process;
+/// process begin wait until rising_edge(clk); ... end process;
+///
+/// A synchronous reset is an if-statement with an else branch directly within the clocked part
+/// which tests a single signal.
+pub fn classify_process(process: &ProcessStatement) -> ProcessKind {
+    let statements: Vec<&LabeledSequentialStatement> = process
+        .statements
+        .iter()
+        .filter(|statement| !matches!(statement.statement, SequentialStatement::Null))
+        .collect();
+
+    if process.sensitivity_list.is_some() {
+        if let [statement] = statements.as_slice() {
+            if let SequentialStatement::If(ref ifstmt) = statement.statement {
+                if let Some(clocked) = classify_clocked_if(ifstmt) {
+                    return ProcessKind::Clocked(clocked);
+                }
+            }
+        }
+
+        if has_clock_edge(&process.statements) {
+            ProcessKind::Other
+        } else {
+            ProcessKind::Combinational
+        }
+    } else {
+        if let Some((first, rest)) = statements.split_first() {
+            if let SequentialStatement::Wait(WithPos {
+                item:
+                    WaitStatement {
+                        ref sensitivity_clause,
+                        condition_clause: Some(ref condition),
+                        timeout_clause: None,
+                    },
+                ..
+            }) = first.statement
+            {
+                if sensitivity_clause.is_empty() && rest.iter().all(|stmt| !is_wait(stmt)) {
+                    if let Some((clock, edge)) = clock_edge(&condition.item) {
+                        return ProcessKind::Clocked(ClockedProcess {
+                            clock,
+                            edge,
+                            clock_pos: condition.pos.clone(),
+                            reset: synchronous_reset(rest.iter().copied()),
+                        });
+                    }
+                }
+            }
+        }
+        ProcessKind::Other
+    }
+}
+
+fn classify_clocked_if(ifstmt: &IfStatement) -> Option<ClockedProcess> {
+    match ifstmt.conditionals.as_slice() {
+        [clocked] if ifstmt.else_item.is_none() => {
+            let (clock, edge) = clock_edge(&clocked.condition.item)?;
+            Some(ClockedProcess {
+                clock,
+                edge,
+                clock_pos: clocked.condition.pos.clone(),
+                reset: synchronous_reset(clocked.item.iter()),
+            })
+        }
+        [reset, clocked] if ifstmt.else_item.is_none() => {
+            let (clock, edge) = clock_edge(&clocked.condition.item)?;
+            let (signal, level) = reset_condition(&reset.condition.item)?;
+            Some(ClockedProcess {
+                clock,
+                edge,
+                clock_pos: clocked.condition.pos.clone(),
+                reset: Some(ProcessReset {
+                    signal,
+                    style: ResetStyle::Asynchronous,
+                    level,
+                    pos: reset.condition.pos.clone(),
+                }),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Find a synchronous reset in the statements of the clocked part of a process
+fn synchronous_reset<'s>(
+    mut statements: impl Iterator<Item = &'s LabeledSequentialStatement>,
+) -> Option<ProcessReset> {
+    let statement = statements.find(|stmt| !matches!(stmt.statement, SequentialStatement::Null))?;
+
+    if let SequentialStatement::If(ref ifstmt) = statement.statement {
+        if let ([reset], Some(_)) = (ifstmt.conditionals.as_slice(), &ifstmt.else_item) {
+            let (signal, level) = reset_condition(&reset.condition.item)?;
+            return Some(ProcessReset {
+                signal,
+                style: ResetStyle::Synchronous,
+                level,
+                pos: reset.condition.pos.clone(),
+            });
+        }
+    }
+    None
+}
+
+fn is_wait(statement: &LabeledSequentialStatement) -> bool {
+    match statement.statement {
+        SequentialStatement::Wait(..) => true,
+        SequentialStatement::If(ref ifstmt) => {
+            ifstmt
+                .conditionals
+                .iter()
+                .any(|conditional| conditional.item.iter().any(is_wait))
+                || ifstmt
+                    .else_item
+                    .as_ref()
+                    .map(|else_item| else_item.iter().any(is_wait))
+                    .unwrap_or(false)
+        }
+        SequentialStatement::Case(ref case_stmt) => case_stmt
+            .alternatives
+            .iter()
+            .any(|alternative| alternative.item.iter().any(is_wait)),
+        SequentialStatement::Loop(ref loop_stmt) => loop_stmt.statements.iter().any(is_wait),
+        _ => false,
+    }
+}
+
+/// Returns true if any if-statement condition contains a clock edge
+fn has_clock_edge(statements: &[LabeledSequentialStatement]) -> bool {
+    statements
+        .iter()
+        .any(|statement| match statement.statement {
+            SequentialStatement::If(ref ifstmt) => {
+                ifstmt.conditionals.iter().any(|conditional| {
+                    clock_edge(&conditional.condition.item).is_some()
+                        || has_clock_edge(&conditional.item)
+                }) || ifstmt
+                    .else_item
+                    .as_ref()
+                    .map(|else_item| has_clock_edge(else_item))
+                    .unwrap_or(false)
+            }
+            SequentialStatement::Case(ref case_stmt) => case_stmt
+                .alternatives
+                .iter()
+                .any(|alternative| has_clock_edge(&alternative.item)),
+            SequentialStatement::Loop(ref loop_stmt) => has_clock_edge(&loop_stmt.statements),
+            _ => false,
+        })
+}
+
+/// The clock signal and edge of a condition such as
+/// rising_edge(clk), falling_edge(clk) or clk'event and clk = '1'
+/// The clock edge may be combined with other conditions using 'and'
+fn clock_edge(expr: &Expression) -> Option<(Reference, ClockEdge)> {
+    match expr {
+        Expression::Binary(op, left, right) if op.item.item == Operator::And => {
+            if let Some(clock) = event_attribute(&left.item) {
+                if let Some((level_clock, level)) = level_test(&right.item) {
+                    if clock == level_clock {
+                        return Some((clock, edge_of_level(level)));
+                    }
+                }
+            } else if let Some(clock) = event_attribute(&right.item) {
+                if let Some((level_clock, level)) = level_test(&left.item) {
+                    if clock == level_clock {
+                        return Some((clock, edge_of_level(level)));
+                    }
+                }
+            }
+
+            clock_edge(&left.item).or_else(|| clock_edge(&right.item))
+        }
+        Expression::Name(name) => {
+            if let Name::CallOrIndexed(ref fcall) = name.as_ref() {
+                let edge = match suffix_designator(&fcall.name.item) {
+                    Some(Designator::Identifier(sym)) => {
+                        if sym.eq_basic("rising_edge") {
+                            ClockEdge::Rising
+                        } else if sym.eq_basic("falling_edge") {
+                            ClockEdge::Falling
+                        } else {
+                            return None;
+                        }
+                    }
+                    _ => return None,
+                };
+
+                if let [AssociationElement {
+                    formal: None,
+                    actual:
+                        WithPos {
+                            item: ActualPart::Expression(ref actual),
+                            ..
+                        },
+                }] = fcall.parameters.as_slice()
+                {
+                    return Some((signal_reference(actual)?, edge));
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+fn edge_of_level(level: ActiveLevel) -> ClockEdge {
+    match level {
+        ActiveLevel::High => ClockEdge::Rising,
+        ActiveLevel::Low => ClockEdge::Falling,
+    }
+}
+
+/// The signal of clk'event
+fn event_attribute(expr: &Expression) -> Option<Reference> {
+    if let Expression::Name(ref name) = expr {
+        if let Name::Attribute(ref attr) = name.as_ref() {
+            if attr.attr.item == AttributeDesignator::Signal(SignalAttribute::Event) {
+                return name_reference(&attr.name.item);
+            }
+        }
+    }
+    None
+}
+
+/// The signal and level of a reset condition such as
+/// rst = '1', rst = '0', rst, not rst or rst = true
+fn reset_condition(expr: &Expression) -> Option<(Reference, ActiveLevel)> {
+    match expr {
+        Expression::Unary(op, operand) if op.item.item == Operator::Not => {
+            Some((signal_reference(&operand.item)?, ActiveLevel::Low))
+        }
+        Expression::Name(..) => Some((signal_reference(expr)?, ActiveLevel::High)),
+        _ => level_test(expr),
+    }
+}
+
+/// A comparison of a signal with a constant level such as clk = '1' or rst = false
+fn level_test(expr: &Expression) -> Option<(Reference, ActiveLevel)> {
+    if let Expression::Binary(op, left, right) = expr {
+        if op.item.item == Operator::EQ {
+            if let Some(level) = constant_level(&right.item) {
+                return Some((signal_reference(&left.item)?, level));
+            } else if let Some(level) = constant_level(&left.item) {
+                return Some((signal_reference(&right.item)?, level));
+            }
+        }
+    }
+    None
+}
+
+fn constant_level(expr: &Expression) -> Option<ActiveLevel> {
+    match expr {
+        Expression::Literal(Literal::Character(b'1')) => Some(ActiveLevel::High),
+        Expression::Literal(Literal::Character(b'0')) => Some(ActiveLevel::Low),
+        Expression::Name(name) => match suffix_designator(name)? {
+            Designator::Identifier(sym) if sym.eq_basic("true") => Some(ActiveLevel::High),
+            Designator::Identifier(sym) if sym.eq_basic("false") => Some(ActiveLevel::Low),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn signal_reference(expr: &Expression) -> Option<Reference> {
+    if let Expression::Name(ref name) = expr {
+        name_reference(name)
+    } else {
+        None
+    }
+}
+
+/// The reference of a simple or selected name
+fn name_reference(name: &Name) -> Option<Reference> {
+    match name {
+        Name::Designator(designator) => Some(designator.reference),
+        Name::Selected(_, suffix) => Some(suffix.item.reference),
+        _ => None,
+    }
+}
+
+fn suffix_designator(name: &Name) -> Option<&Designator> {
+    match name {
+        Name::Designator(designator) => Some(designator.designator()),
+        Name::Selected(_, suffix) => Some(suffix.designator()),
+        _ => None,
+    }
+}
@@ -0,0 +1,149 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Checks of pure function bodies that are performed after all names within them have been resolved
+
+use super::analyze::*;
+use super::named_entity::*;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashSet;
+
+impl<'a> AnalyzeContext<'a> {
+    /// LRM 4.3 Subprogram bodies
+    /// A pure function shall not reference a signal, variable or file declared outside of it
+    /// nor call an impure function
+    /// The installed std and ieee libraries are not checked.
+    pub fn check_function_purity(
+        &self,
+        body: &mut SubprogramBody,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let SubprogramDeclaration::Function(ref fun) = body.specification else {
+            return;
+        };
+        let library_name = self.work_library_name();
+        if !fun.pure || *library_name == self.std_sym || library_name.name_utf8() == "ieee" {
+            return;
+        }
+        let name = fun.designator.tree.item.to_string();
+
+        let mut searcher = FindImpurity {
+            arena: self.arena,
+            locals: FnvHashSet::default(),
+            formals: FnvHashSet::default(),
+            impurities: Vec::new(),
+        };
+        let _ = body.specification.search(&mut searcher);
+        let _ = body.declarations.search(&mut searcher);
+        let _ = body.statements.search(&mut searcher);
+
+        for (pos, ent) in searcher.impurities {
+            if self.is_now(ent) {
+                continue;
+            }
+            let message = if let AnyEntKind::Overloaded(..) = ent.kind() {
+                format!(
+                    "Pure function '{name}' cannot call impure function '{}'",
+                    ent.designator()
+                )
+            } else {
+                format!(
+                    "Pure function '{name}' cannot reference {} '{}' declared outside of it",
+                    describe_impure_object(ent),
+                    ent.designator()
+                )
+            };
+            diagnostics.push(Diagnostic::error(pos, message).with_code(ErrorCode::FunctionPurity));
+        }
+    }
+
+    /// Whether the entity is the function NOW of the standard package.
+    /// Tools commonly accept calls of NOW within pure functions such as those of VITAL.
+    fn is_now(&self, ent: EntRef) -> bool {
+        let Some(standard_pkg) = self.root.standard_pkg_id else {
+            return false;
+        };
+        let standard_file = self
+            .arena
+            .get(standard_pkg)
+            .decl_pos()
+            .map(|pos| pos.file_name());
+        matches!(ent.kind(), AnyEntKind::Overloaded(..))
+            && *ent.designator() == Designator::Identifier(self.root.symbol_utf8("now"))
+            && ent.decl_pos().map(|pos| pos.file_name()) == standard_file
+    }
+}
+
+/// The class of an object that a pure function may not reference
+fn describe_impure_object(ent: EntRef) -> &'static str {
+    match ent.actual_kind() {
+        AnyEntKind::Object(object) => match object.class {
+            ObjectClass::Signal => "signal",
+            ObjectClass::Variable => "variable",
+            ObjectClass::SharedVariable => "shared variable",
+            ObjectClass::Constant => "constant",
+        },
+        _ => "file",
+    }
+}
+
+struct FindImpurity<'b> {
+    arena: &'b Arena,
+    /// The declarations within the function including its parameters
+    locals: FnvHashSet<EntityId>,
+    /// The formals of the called subprograms which are referenced by named association
+    formals: FnvHashSet<EntityId>,
+    /// The references to objects declared outside of the function and calls of impure functions
+    impurities: Vec<(SrcPos, EntRef<'b>)>,
+}
+
+impl<'b> FindImpurity<'b> {
+    fn is_impure(&self, ent: EntRef) -> bool {
+        match ent.kind() {
+            AnyEntKind::Overloaded(overloaded) => overloaded.signature().is_impure(),
+            _ if self.locals.contains(&ent.id()) || self.formals.contains(&ent.id()) => false,
+            _ => match ent.actual_kind() {
+                AnyEntKind::Object(object) => matches!(
+                    object.class,
+                    ObjectClass::Signal | ObjectClass::Variable | ObjectClass::SharedVariable
+                ),
+                AnyEntKind::File(..) | AnyEntKind::InterfaceFile(..) => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl<'b> Searcher for FindImpurity<'b> {
+    fn search_decl(&mut self, decl: FoundDeclaration) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            self.locals.insert(id);
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        if let Some(id) = reference {
+            let ent = self.arena.get(*id);
+            // The name of a call is searched before its association list
+            if let AnyEntKind::Overloaded(overloaded) = ent.kind() {
+                self.formals.extend(
+                    overloaded
+                        .signature()
+                        .formals
+                        .iter()
+                        .map(|formal| formal.id()),
+                );
+            }
+            if self.is_impure(ent) {
+                self.impurities.push((pos.clone(), ent));
+            }
+        }
+        NotFinished
+    }
+}
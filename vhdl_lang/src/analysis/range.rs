@@ -7,10 +7,11 @@
 use super::analyze::*;
 use super::expression::ExpressionType;
 use super::named_entity::*;
-use super::names::ResolvedName;
+use super::names::{plural, ResolvedName};
 use super::overloaded::Disambiguated;
 use super::overloaded::DisambiguatedType;
 use super::region::*;
+use super::static_expression::non_static_reference;
 use crate::ast::Range;
 use crate::ast::*;
 use crate::data::*;
@@ -116,7 +117,14 @@ impl<'a> AnalyzeContext<'a> {
         };
 
         if let Some((_, indexes)) = typ.array_type() {
-            Ok(indexes.first().unwrap().unwrap())
+            let dimension = self.attribute_dimension(
+                scope,
+                &attr.attr.item,
+                attr.expr.as_deref_mut(),
+                indexes.len(),
+                diagnostics,
+            )?;
+            indexes[dimension].ok_or(EvalError::Unknown)
         } else {
             diagnostics.error(
                 &attr.name.pos,
@@ -129,6 +137,59 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
+    /// The zero-based dimension given by the expression of an array attribute such as 'range(2)
+    /// LRM 16.2 The dimension shall be a locally static expression of type universal_integer.
+    /// Dimensions that cannot be evaluated are assumed to be the first dimension
+    pub fn attribute_dimension(
+        &self,
+        scope: &Scope<'a>,
+        designator: &AttributeDesignator,
+        expr: Option<&mut WithPos<Expression>>,
+        dimensions: usize,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<usize> {
+        let Some(expr) = expr else {
+            return Ok(0);
+        };
+        self.expr_with_ttyp(scope, self.universal_integer().into(), expr, diagnostics)?;
+
+        if let Some(ent) = non_static_reference(self.arena, &expr.item) {
+            diagnostics.error(
+                &expr.pos,
+                format!(
+                    "Dimension of '{} must be locally static, {} is not static",
+                    designator,
+                    ent.describe()
+                ),
+            );
+            return Err(EvalError::Unknown);
+        }
+
+        let Some(dimension) = self
+            .static_evaluator()
+            .eval(None, &expr.item)
+            .and_then(|value| value.as_discrete())
+        else {
+            return Ok(0);
+        };
+
+        if (1..=dimensions as i64).contains(&dimension) {
+            Ok(dimension as usize - 1)
+        } else {
+            diagnostics.error(
+                &expr.pos,
+                format!(
+                    "Dimension {} is out of range, the prefix of '{} has {} {}",
+                    dimension,
+                    designator,
+                    dimensions,
+                    plural("dimension", "dimensions", dimensions)
+                ),
+            );
+            Err(EvalError::Unknown)
+        }
+    }
+
     pub fn range_type(
         &self,
         scope: &Scope<'a>,
@@ -274,7 +335,26 @@ impl<'a> AnalyzeContext<'a> {
                 )?;
             }
             Range::Attribute(ref mut attr) => {
-                self.analyze_attribute_name(scope, attr, diagnostics)?;
+                self.range_attribute_with_ttyp(scope, target_type, attr, diagnostics)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn range_attribute_with_ttyp(
+        &self,
+        scope: &Scope<'a>,
+        target_type: TypeEnt<'a>,
+        attr: &mut AttributeName,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        if let Some(typ) = as_fatal(self.range_attribute_type(scope, attr, diagnostics))? {
+            if typ != target_type.base() {
+                diagnostics.push(Diagnostic::type_mismatch(
+                    &attr.name.pos,
+                    &typ.describe(),
+                    target_type,
+                ));
             }
         }
         Ok(())
@@ -8,11 +8,12 @@ use super::formal_region::InterfaceEnt;
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 use super::named_entity::*;
+use super::spelling::most_similar;
 use super::{named_entity, visibility::*};
 use crate::ast::*;
 use crate::data::*;
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use std::cell::RefCell;
 use std::collections::hash_map::Entry;
 use std::rc::Rc;
@@ -72,6 +73,16 @@ impl<'a> OverloadedName<'a> {
 
     #[allow(clippy::if_same_then_else)]
     fn insert(&mut self, ent: OverloadedEnt<'a>) -> Result<(), Diagnostic> {
+        if ent.is_uninst_subprogram() {
+            // The generic types of an uninstantiated subprogram declaration and its body
+            // are different entities so they cannot be paired by the signature key
+            let signature = ent.signature().describe();
+            self.entities.retain(|_, old_ent| {
+                !(old_ent.is_uninst_subprogram_decl()
+                    && old_ent.signature().describe() == signature)
+            });
+        }
+
         match self.entities.entry(ent.signature().key()) {
             Entry::Occupied(mut entry) => {
                 let old_ent = entry.get();
@@ -172,6 +183,14 @@ impl<'a> NamedEntities<'a> {
         }
     }
 
+    /// All named entities, which are more than one if overloaded
+    pub fn entities(&self) -> Vec<EntRef<'a>> {
+        match self {
+            Self::Single(ent) => vec![*ent],
+            Self::Overloaded(overloaded) => overloaded.entities().map(EntRef::from).collect(),
+        }
+    }
+
     pub fn as_non_overloaded(&self) -> Option<EntRef<'a>> {
         match self {
             Self::Single(ent) => Some(ent),
@@ -317,6 +336,18 @@ impl<'a> ScopeInner<'a> {
         }
     }
 
+    /// Add the named entities declared in each region searched by lookup_enclosing
+    fn searched_regions(&self, designator: &Designator, searched: &mut Vec<Vec<EntRef<'a>>>) {
+        let immediate = self.lookup_immediate(designator);
+        searched.push(immediate.map(NamedEntities::entities).unwrap_or_default());
+
+        if !matches!(immediate, Some(NamedEntities::Single(..))) {
+            if let Some(ref parent) = self.parent {
+                parent.0.borrow().searched_regions(designator, searched);
+            }
+        }
+    }
+
     fn lookup_visiblity_into(&self, designator: &Designator, visible: &mut Visible<'a>) {
         self.region.visibility.lookup_into(designator, visible);
         if let Some(ref parent) = self.parent {
@@ -365,23 +396,62 @@ impl<'a> ScopeInner<'a> {
 
         match result {
             Some(visible) => Ok(visible),
-            None => Err(Diagnostic::error(
-                pos,
-                match designator {
-                    Designator::Identifier(ident) => {
-                        format!("No declaration of '{ident}'")
-                    }
-                    Designator::OperatorSymbol(operator) => {
-                        format!("No declaration of operator '{operator}'")
-                    }
-                    Designator::Character(chr) => {
-                        format!("No declaration of '{chr}'")
+            None => Err(match designator {
+                Designator::Identifier(ident) => {
+                    if let Some(similar) = self.similar_identifier(ident) {
+                        Diagnostic::error(
+                            pos,
+                            format!("No declaration of '{ident}', did you mean '{similar}'?"),
+                        )
+                        .with_fix(pos, similar)
+                    } else {
+                        Diagnostic::error(pos, format!("No declaration of '{ident}'"))
                     }
-                },
-            )),
+                }
+                Designator::OperatorSymbol(operator) => {
+                    Diagnostic::error(pos, format!("No declaration of operator '{operator}'"))
+                }
+                Designator::Character(chr) => {
+                    Diagnostic::error(pos, format!("No declaration of '{chr}'"))
+                }
+            }),
+        }
+    }
+
+    /// Add the identifiers declared in this region or an enclosing region or made visible to them
+    fn visible_identifiers(&self, identifiers: &mut FnvHashSet<Symbol>) {
+        identifiers.extend(
+            self.region
+                .entities
+                .keys()
+                .filter_map(Designator::as_identifier)
+                .cloned(),
+        );
+        self.region.visibility.visible_identifiers(identifiers);
+
+        if let Some(ref parent) = self.parent {
+            parent.0.borrow().visible_identifiers(identifiers);
+        }
+    }
+
+    /// Add the designators declared in this region or an enclosing region or made visible to them
+    fn visible_designators(&self, designators: &mut FnvHashSet<Designator>) {
+        designators.extend(self.region.entities.keys().cloned());
+        self.region.visibility.visible_designators(designators);
+
+        if let Some(ref parent) = self.parent {
+            parent.0.borrow().visible_designators(designators);
         }
     }
 
+    /// A visible identifier that is a likely misspelling of the identifier
+    fn similar_identifier(&self, ident: &Symbol) -> Option<String> {
+        let mut identifiers = FnvHashSet::default();
+        self.visible_identifiers(&mut identifiers);
+        let names: Vec<String> = identifiers.iter().map(|sym| sym.name_utf8()).collect();
+        most_similar(&ident.name_utf8(), names.iter().map(|name| name.as_str())).map(str::to_owned)
+    }
+
     fn lookup(
         &mut self,
         pos: &SrcPos,
@@ -516,6 +586,23 @@ impl<'a> Scope<'a> {
         Some(names.clone())
     }
 
+    /// The named entities declared immediately within this scope in order of declaration
+    pub fn immediates(&self) -> Vec<EntRef<'a>> {
+        let inner = self.0.as_ref().borrow();
+        let mut entities: Vec<EntRef<'a>> = inner
+            .region
+            .immediates()
+            .flat_map(|named| match named {
+                NamedEntities::Single(ent) => vec![*ent],
+                NamedEntities::Overloaded(overloaded) => {
+                    overloaded.entities().map(|ent| ent.into()).collect()
+                }
+            })
+            .collect();
+        entities.sort_by(|a, b| a.decl_pos().cmp(&b.decl_pos()));
+        entities
+    }
+
     pub fn lookup(
         &self,
         pos: &SrcPos,
@@ -524,6 +611,75 @@ impl<'a> Scope<'a> {
         self.0.as_ref().borrow_mut().lookup(pos, designator)
     }
 
+    /// All named entities that are visible within this scope by their visible designator.
+    /// Each is paired with the positions where it was made visible or None if it is declared
+    /// in this scope or an enclosing scope. Names hidden by a conflicting use clause are not visible.
+    #[allow(clippy::type_complexity)]
+    pub fn visible_names(
+        &self,
+        pos: &SrcPos,
+    ) -> Vec<(Designator, EntRef<'a>, Option<Vec<Option<SrcPos>>>)> {
+        let inner = self.0.as_ref().borrow();
+        let mut designators = FnvHashSet::default();
+        inner.visible_designators(&mut designators);
+
+        let mut result = Vec::new();
+        for designator in designators {
+            let Ok(named_entities) = inner.lookup_uncached(pos, &designator) else {
+                continue;
+            };
+            let enclosing = inner.lookup_enclosing(&designator);
+            let mut visible = Visible::default();
+            inner.lookup_visiblity_into(&designator, &mut visible);
+
+            for ent in named_entities.entities() {
+                let is_enclosing = enclosing.as_ref().is_some_and(|enclosing| {
+                    enclosing
+                        .entities()
+                        .iter()
+                        .any(|other| other.id() == ent.id())
+                });
+                let visible_pos = if is_enclosing {
+                    None
+                } else {
+                    visible
+                        .visible_pos(ent)
+                        .map(|visible_pos| visible_pos.to_vec())
+                };
+                result.push((designator.clone(), ent, visible_pos));
+            }
+        }
+        result
+    }
+
+    /// The named entities with the designator declared in each scope searched by a lookup
+    /// from the innermost scope outwards, followed by the named entities made visible by use clauses
+    /// paired with the positions where they were made visible.
+    /// Use clauses are not searched when an enclosing scope declares a name that is not overloaded.
+    #[allow(clippy::type_complexity)]
+    pub fn searched_scopes(
+        &self,
+        designator: &Designator,
+    ) -> (Vec<Vec<EntRef<'a>>>, Vec<(EntRef<'a>, Vec<Option<SrcPos>>)>) {
+        let inner = self.0.as_ref().borrow();
+        let mut searched = Vec::new();
+        inner.searched_regions(designator, &mut searched);
+
+        let mut visible_names = Vec::new();
+        if !matches!(
+            inner.lookup_enclosing(designator),
+            Some(NamedEntities::Single(..))
+        ) {
+            let mut visible = Visible::default();
+            inner.lookup_visiblity_into(designator, &mut visible);
+            visible_names = visible
+                .entities()
+                .map(|(ent, visible_pos)| (ent, visible_pos.to_vec()))
+                .collect();
+        }
+        (searched, visible_names)
+    }
+
     /// Used when using context clauses
     pub fn add_context_visibility(&self, visible_pos: Option<&SrcPos>, region: &Region<'a>) {
         self.0
@@ -674,6 +830,7 @@ impl<'a> Region<'a> {
                             && ent.kind().is_non_deferred_constant()
                         {
                             if self.kind == RegionKind::PackageBody {
+                                check_deferred_constant_subtype(prev_ent, ent, diagnostics);
                                 // Overwrite deferred constant
                                 *prev_ent = ent;
                             } else {
@@ -786,6 +943,44 @@ impl SetReference for Name {
     }
 }
 
+impl SetReference for SelectedName {
+    fn set_unique_reference(&mut self, ent: &AnyEnt) {
+        self.suffix_reference_mut().set_unique_reference(ent);
+    }
+}
+
+/// The full declaration of a deferred constant must have the same subtype
+fn check_deferred_constant_subtype(
+    deferred: EntRef,
+    full: EntRef,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    if let (AnyEntKind::DeferredConstant(deferred_subtype), AnyEntKind::Object(object)) =
+        (deferred.kind(), full.kind())
+    {
+        let deferred_type = deferred_subtype.type_mark();
+        let full_type = object.subtype.type_mark();
+
+        if deferred_type.id() != full_type.id() {
+            if let Some(pos) = full.decl_pos() {
+                let mut diagnostic = Diagnostic::error(
+                    pos,
+                    format!(
+                        "Full declaration of deferred constant '{}' has {} which does not match {} of the deferred constant",
+                        full.designator(),
+                        full_type.describe(),
+                        deferred_type.describe()
+                    ),
+                );
+                if let Some(prev_pos) = deferred.decl_pos() {
+                    diagnostic.add_related(prev_pos, "Deferred constant declared here");
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+}
+
 pub(super) fn duplicate_error(
     name: &impl std::fmt::Display,
     pos: &SrcPos,
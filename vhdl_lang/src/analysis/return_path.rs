@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Detection of function bodies whose execution can complete without a return statement
+
+use super::analyze::*;
+use crate::ast::*;
+use crate::data::*;
+
+impl AnalyzeContext<'_> {
+    /// LRM 4.3 It is an error if the execution of a function completes
+    /// by any other means than the execution of a return statement
+    pub fn check_return_paths(
+        &self,
+        body: &SubprogramBody,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let SubprogramDeclaration::Function(ref fun) = body.specification else {
+            return;
+        };
+        if !self.can_complete(&body.statements) {
+            return;
+        }
+
+        diagnostics.push(
+            Diagnostic::warning(
+                &body.end_pos,
+                format!(
+                    "Function '{}' can reach the end of its body without executing a return statement",
+                    fun.designator.tree.item
+                ),
+            )
+            .related(&fun.designator.tree.pos, "Function declared here")
+            .with_code(ErrorCode::MissingReturn),
+        );
+    }
+
+    /// Whether the execution of a sequence of statements can continue after its last statement
+    fn can_complete(&self, statements: &[LabeledSequentialStatement]) -> bool {
+        statements
+            .iter()
+            .all(|statement| self.statement_can_complete(statement))
+    }
+
+    fn statement_can_complete(&self, statement: &LabeledSequentialStatement) -> bool {
+        match statement.statement {
+            SequentialStatement::Return(ref ret) => match ret.item.expression {
+                // A conditional return without an else branch returns only when a condition is true
+                Some(AssignmentRightHand::Conditional(ref conditionals)) => {
+                    conditionals.else_item.is_none()
+                }
+                _ => false,
+            },
+            SequentialStatement::If(ref conditionals) => {
+                conditionals
+                    .conditionals
+                    .iter()
+                    .any(|conditional| self.can_complete(&conditional.item))
+                    || conditionals
+                        .else_item
+                        .as_ref()
+                        .is_none_or(|statements| self.can_complete(statements))
+            }
+            // The choices of a case statement cover all values of the expression
+            SequentialStatement::Case(ref case) => case
+                .alternatives
+                .iter()
+                .any(|alternative| self.can_complete(&alternative.item)),
+            // A loop with an iteration scheme completes when the condition is false or the
+            // range is exhausted, a loop without one or with a condition that is always true
+            // only by an exit statement
+            SequentialStatement::Loop(ref loop_statement) => {
+                let completes_by_scheme = match loop_statement.iteration_scheme {
+                    Some(IterationScheme::While(ref condition)) => {
+                        !self.is_standard_literal(&condition.item, "true")
+                    }
+                    Some(IterationScheme::For(..)) => true,
+                    None => false,
+                };
+                completes_by_scheme
+                    || exits_loop(
+                        statement.label.as_ref().map(|label| &label.tree.item),
+                        &loop_statement.statements,
+                        false,
+                    )
+            }
+            SequentialStatement::Next(ref next) => next.condition.is_some(),
+            SequentialStatement::Exit(ref exit) => exit.condition.is_some(),
+            // An assertion violation or report of severity failure stops the simulation
+            SequentialStatement::Report(ref report) => !self.is_failure(&report.severity),
+            SequentialStatement::Assert(ref assert) => {
+                !(self.is_standard_literal(&assert.condition.item, "false")
+                    && self.is_failure(&assert.severity))
+            }
+            _ => true,
+        }
+    }
+
+    fn is_failure(&self, severity: &Option<WithPos<Expression>>) -> bool {
+        severity
+            .as_ref()
+            .is_some_and(|severity| self.is_standard_literal(&severity.item, "failure"))
+    }
+
+    /// Whether the expression is a name of the enumeration literal of package standard
+    fn is_standard_literal(&self, expr: &Expression, literal: &str) -> bool {
+        let Expression::Name(ref name) = expr else {
+            return false;
+        };
+        let reference = match name.as_ref() {
+            Name::Designator(designator) => designator.reference,
+            Name::Selected(_, suffix) => suffix.item.reference,
+            _ => None,
+        };
+        reference.is_some_and(|id| {
+            let ent = self.arena.get(id);
+            matches!(ent.designator(), Designator::Identifier(sym) if sym.eq_basic(literal))
+                && Some(id.arena_id()) == self.root.standard_pkg_id.map(|id| id.arena_id())
+        })
+    }
+}
+
+/// Whether the statements of a loop contain an exit statement that leaves it.
+/// An exit statement without a label within a nested loop leaves the nested loop.
+/// Statements that are not reachable are not excluded.
+fn exits_loop(
+    label: Option<&Symbol>,
+    statements: &[LabeledSequentialStatement],
+    nested: bool,
+) -> bool {
+    statements
+        .iter()
+        .any(|statement| match statement.statement {
+            SequentialStatement::Exit(ref exit) => match exit.loop_label {
+                Some(ref loop_label) => Some(&loop_label.item) == label,
+                None => !nested,
+            },
+            SequentialStatement::If(ref conditionals) => {
+                conditionals
+                    .conditionals
+                    .iter()
+                    .any(|conditional| exits_loop(label, &conditional.item, nested))
+                    || conditionals
+                        .else_item
+                        .as_ref()
+                        .is_some_and(|statements| exits_loop(label, statements, nested))
+            }
+            SequentialStatement::Case(ref case) => case
+                .alternatives
+                .iter()
+                .any(|alternative| exits_loop(label, &alternative.item, nested)),
+            SequentialStatement::Loop(ref loop_statement) => {
+                exits_loop(label, &loop_statement.statements, true)
+            }
+            _ => false,
+        })
+}
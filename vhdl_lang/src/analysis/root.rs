@@ -5,30 +5,42 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::analyze::*;
+use super::binding::ArchitectureSelection;
+use super::external_name::ExternalNameIndex;
+use super::hierarchy::InstantiationGraph;
 use super::lock::*;
 use super::named_entity::*;
-use super::region::Scope;
+use super::process::{classify_processes, ClassifiedProcess, ResetConvention};
+use super::region::{NamedEntities, Scope};
 use super::standard::StandardTypes;
 use super::standard::UniversalTypes;
+use super::static_expression::FindStaticValue;
+use super::use_scope::{search_use_scope, UseScope};
+use super::wait_graph::{Caller, WaitGraph};
 
 use crate::ast::search::*;
 use crate::ast::*;
 use crate::data::*;
 use crate::syntax::Symbols;
 use fnv::{FnvHashMap, FnvHashSet};
-use parking_lot::RwLock;
+use parking_lot::{MappedRwLockWriteGuard, RwLock};
 use std::collections::hash_map::Entry;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
 
 /// A design unit with design unit data
 pub(super) struct AnalysisData {
     pub diagnostics: Vec<Diagnostic>,
     pub has_circular_dependency: bool,
+    pub has_external_names: bool,
+    pub callers: Vec<Caller>,
     pub arena: FinalArena,
 }
 
 pub(super) type UnitReadGuard<'a> = ReadGuard<'a, AnyDesignUnit, AnalysisData>;
 pub(super) type UnitWriteGuard<'a> = WriteGuard<'a, AnyDesignUnit, AnalysisData>;
+pub(super) type ArchitectureWriteGuard<'a> = MappedRwLockWriteGuard<'a, ArchitectureBody>;
 
 /// Wraps the AST of a [design unit](../../ast/enum.AnyDesignUnit.html) in a thread-safe
 /// r/w-lock for analysis.
@@ -66,6 +78,17 @@ impl LockedUnit {
             unit: AnalysisLock::new(unit),
         }
     }
+
+    /// The architecture of the unit if it is an architecture that has been analyzed,
+    /// the references of an architecture are only resolved once it has been analyzed
+    pub(super) fn analyzed_architecture(&self) -> Option<ArchitectureWriteGuard<'_>> {
+        drop(self.unit.get()?);
+        MappedRwLockWriteGuard::try_map(self.unit.write(), |unit| match unit {
+            AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) => Some(arch),
+            _ => None,
+        })
+        .ok()
+    }
 }
 
 impl HasIdent for LockedUnit {
@@ -246,6 +269,10 @@ impl Library {
     }
 }
 
+/// The declarations made visible by a `use prefix.all` clause
+/// each paired with true if it is referenced within the scope of the clause
+pub type UseAllExpansion<'a> = Vec<(EntRef<'a>, bool)>;
+
 /// Contains the entire design state.
 ///
 /// Besides all loaded libraries and design units, `DesignRoot` also keeps track of
@@ -271,6 +298,24 @@ pub struct DesignRoot {
     // Tracks which units have a "use library.all;" clause.
     // library name  =>  set(affected)
     users_of_library_all: RwLock<FnvHashMap<Symbol, FnvHashSet<UnitId>>>,
+
+    // The reset that clocked processes are expected to use
+    reset_convention: Option<ResetConvention>,
+
+    // The revision of the language that the design is analyzed against
+    standard: VHDLStandard,
+
+    // The opt-in lints that are performed
+    enabled_lints: FnvHashSet<ErrorCode>,
+
+    // How the default binding selects one of several architectures of an entity
+    pub(super) architecture_selection: ArchitectureSelection,
+
+    // The position of each file within the files of its library in the configuration
+    pub(super) compile_order: FnvHashMap<PathBuf, usize>,
+
+    // Libraries of testbenches and simulation models that are not part of the production design
+    pub(super) simulation_libraries: FnvHashSet<Symbol>,
 }
 
 impl DesignRoot {
@@ -286,9 +331,88 @@ impl DesignRoot {
             users_of: RwLock::new(FnvHashMap::default()),
             missing_primary: RwLock::new(FnvHashMap::default()),
             users_of_library_all: RwLock::new(FnvHashMap::default()),
+            reset_convention: None,
+            standard: VHDLStandard::default(),
+            enabled_lints: FnvHashSet::default(),
+            architecture_selection: ArchitectureSelection::default(),
+            compile_order: FnvHashMap::default(),
+            simulation_libraries: FnvHashSet::default(),
+        }
+    }
+
+    /// Set the reset convention that clocked processes are checked against
+    /// Takes effect on the next analysis
+    pub fn set_reset_convention(&mut self, convention: Option<ResetConvention>) {
+        if self.reset_convention != convention {
+            self.reset_convention = convention;
+
+            // The convention affects the diagnostics of every architecture
+            for library in self.libraries.values() {
+                for unit in library.units.values() {
+                    unit.unit.reset();
+                }
+            }
+        }
+    }
+
+    pub fn reset_convention(&self) -> Option<&ResetConvention> {
+        self.reset_convention.as_ref()
+    }
+
+    /// Set the revision of the language that the design is analyzed against
+    /// Takes effect on the next analysis
+    pub fn set_standard(&mut self, standard: VHDLStandard) {
+        if self.standard != standard {
+            self.standard = standard;
+
+            for library in self.libraries.values() {
+                for unit in library.units.values() {
+                    unit.unit.reset();
+                }
+            }
         }
     }
 
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
+    /// Set the opt-in lints that are performed
+    /// Takes effect on the next analysis
+    pub fn set_enabled_lints(&mut self, lints: FnvHashSet<ErrorCode>) {
+        if self.enabled_lints != lints {
+            self.enabled_lints = lints;
+
+            for library in self.libraries.values() {
+                for unit in library.units.values() {
+                    unit.unit.reset();
+                }
+            }
+        }
+    }
+
+    /// Set how the default binding selects one of several architectures of an entity
+    /// The selection is made after the design units are analyzed and takes effect on the next analysis
+    pub fn set_architecture_selection(&mut self, selection: ArchitectureSelection) {
+        self.architecture_selection = selection;
+    }
+
+    /// Set the position of each file within the files of its library in the configuration,
+    /// which is the order in which the default binding considers the architectures analyzed
+    pub fn set_compile_order(&mut self, compile_order: FnvHashMap<PathBuf, usize>) {
+        self.compile_order = compile_order;
+    }
+
+    /// Set the libraries of testbenches and simulation models
+    pub fn set_simulation_libraries(&mut self, libraries: FnvHashSet<Symbol>) {
+        self.simulation_libraries = libraries;
+    }
+
+    /// Lints that are not opt-in are always performed
+    pub fn is_lint_enabled(&self, code: ErrorCode) -> bool {
+        !code.is_opt_in() || self.enabled_lints.contains(&code)
+    }
+
     /// Create library if it does not exist or return existing
     fn get_or_create_library(&mut self, name: Symbol) -> &mut Library {
         match self.libraries.entry(name) {
@@ -348,15 +472,32 @@ impl DesignRoot {
         Some(self.get_ent(id))
     }
 
+    /// Search for the references at many positions of a source in a single traversal,
+    /// such as all identifiers of a viewport. The results are in the order of the cursors.
+    pub fn search_references<'a>(
+        &'a self,
+        source: &Source,
+        cursors: &[Position],
+    ) -> Vec<Option<EntRef<'a>>> {
+        let mut searcher = ItemsAtCursors::new(source, cursors);
+        let _ = self.search(&mut searcher);
+        searcher
+            .result
+            .into_iter()
+            .map(|id| id.map(|id| self.get_ent(id)))
+            .collect()
+    }
+
     #[cfg(test)]
     pub fn search_reference_pos(&self, source: &Source, cursor: Position) -> Option<SrcPos> {
         self.search_reference(source, cursor)
             .and_then(|ent| ent.decl_pos().cloned())
     }
     /// Search for the declaration at decl_pos and format it
+    /// Entities without a declaration in the source are rendered from their analyzed form
     pub fn format_declaration(&self, ent: &AnyEnt) -> Option<String> {
         if let AnyEntKind::Library = ent.kind() {
-            Some(format!("library {};", ent.designator()))
+            Some(ent.pretty().to_string())
         } else {
             let ent = if let Related::InstanceOf(ent) = ent.related {
                 ent
@@ -366,7 +507,7 @@ impl DesignRoot {
 
             let mut searcher = FormatDeclaration::new(ent);
             let _ = self.search(&mut searcher);
-            searcher.result
+            searcher.result.or_else(|| Some(ent.pretty().to_string()))
         }
     }
 
@@ -377,12 +518,276 @@ impl DesignRoot {
         searcher.references
     }
 
+    /// Search for all references to the declaration until the deadline has passed
+    /// Returns the references found so far and true if the search was truncated
+    pub fn find_all_references_until(&self, ent: EntRef, deadline: Instant) -> (Vec<SrcPos>, bool) {
+        let mut searcher = FindAllReferences::new(self, ent).with_deadline(deadline);
+        let _ = self.search(&mut searcher);
+        (searcher.references, searcher.truncated)
+    }
+
+    /// The declarations made visible by the `use prefix.all` clause at the cursor
+    /// Each declaration is paired with true if it is referenced within the scope of the clause
+    /// Returns None if there is no such clause at the cursor or the prefix is not a package
+    pub fn expand_use_all<'a>(
+        &'a self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<UseAllExpansion<'a>> {
+        self.use_all_at(source, cursor).map(|(visible, _)| visible)
+    }
+
+    /// Replace the `prefix.all` name of the use clause at the cursor with the selected names
+    /// of the declarations referenced within the scope of the clause
+    /// Returns the position of the name and its replacement, or None if nothing is referenced
+    pub fn use_all_to_explicit_imports(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<(SrcPos, String)> {
+        let (visible, name) = self.use_all_at(source, cursor)?;
+        let Name::SelectedAll(ref prefix) = name.item else {
+            return None;
+        };
+
+        let mut designators: Vec<String> = Vec::new();
+        for (ent, referenced) in visible {
+            let designator = ent.designator().to_string();
+            if referenced && !designators.contains(&designator) {
+                designators.push(designator);
+            }
+        }
+        if designators.is_empty() {
+            return None;
+        }
+
+        let replacement = designators
+            .iter()
+            .map(|designator| format!("{prefix}.{designator}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some((name.pos, replacement))
+    }
+
+    /// The declarations made visible by the `use prefix.all` clause at the cursor
+    /// together with the `prefix.all` name of the clause
+    fn use_all_at<'a>(
+        &'a self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<(UseAllExpansion<'a>, WithPos<Name>)> {
+        for library in self.libraries.values() {
+            for unit_id in library.sorted_unit_ids() {
+                let unit = library.units.get(unit_id.key()).unwrap();
+                let mut unit = unit.unit.write();
+
+                let mut finder = FindUseAllPrefix::new(source, cursor);
+                if let NotFound = unit.search(&mut finder) {
+                    continue;
+                }
+
+                let region = match self.get_ent(finder.result?).kind() {
+                    AnyEntKind::Design(
+                        Design::Package(_, region)
+                        | Design::UninstPackage(_, region)
+                        | Design::PackageInstance(region),
+                    ) => region,
+                    _ => return None,
+                };
+
+                let mut searcher = FindAllReferenced::new(self);
+                let scope = search_use_scope(&mut unit, finder.clause_pos.as_ref()?, &mut searcher);
+                drop(unit);
+
+                // The secondary units are within the declarative region of their primary unit
+                if scope == UseScope::SecondaryUnits {
+                    for (key, secondary) in library.units.iter() {
+                        if matches!(key, UnitKey::Secondary(name, _) if name == unit_id.primary_name())
+                        {
+                            let _ = secondary.unit.write().search(&mut searcher);
+                        }
+                    }
+                }
+
+                let mut visible: Vec<_> = region
+                    .immediates()
+                    .flat_map(|named| match named {
+                        NamedEntities::Single(ent) => vec![*ent],
+                        NamedEntities::Overloaded(overloaded) => {
+                            overloaded.entities().map(|ent| ent.into()).collect()
+                        }
+                    })
+                    .filter(|ent| ent.is_explicit())
+                    .map(|ent| (ent, searcher.referenced.contains(&ent.id())))
+                    .collect();
+                visible.sort_by(|(a, _), (b, _)| a.decl_pos().cmp(&b.decl_pos()));
+                return Some((visible, finder.name?));
+            }
+        }
+        None
+    }
+
+    /// The value of the innermost expression at the cursor if it is locally static
+    /// Returns the position of the expression and its value if it could be evaluated
+    pub fn static_value_at(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<(SrcPos, Option<StaticValue>)> {
+        let mut searcher = FindStaticValue::new(self, source, cursor);
+        let _ = self.search(&mut searcher);
+        searcher.result
+    }
+
+    /// Search for all references to a library by its own name such as in library clauses,
+    /// use clauses and selected names. References through `work` are not included.
+    /// Returns None if there is no such library
+    pub fn find_library_references(&self, library_name: &Symbol) -> Option<Vec<SrcPos>> {
+        let (arena, id) = self.get_library_arena(library_name)?;
+        let name = library_name.name_utf8();
+        let mut references = self.find_all_references(arena.get(id));
+        references.retain(|pos| {
+            let (start, end) = (pos.start(), pos.end());
+            let contents = pos.source.contents();
+            let Some(line) = contents.get_line(start.line as usize) else {
+                return false;
+            };
+            let text: String = line
+                .chars()
+                .skip(start.character as usize)
+                .take(end.character.saturating_sub(start.character) as usize)
+                .collect();
+            start.line == end.line && text.eq_ignore_ascii_case(&name)
+        });
+        references.sort();
+        Some(references)
+    }
+
+    /// Returns true if the library exists
+    pub fn has_library(&self, library_name: &Symbol) -> bool {
+        self.libraries.contains_key(library_name)
+    }
+
+    /// Remove the diagnostics within ignored design units given as pairs of library name and
+    /// primary unit name, the secondary units of an ignored primary unit are also ignored.
+    /// A unit extends from the start of its context clause to the start of the next unit
+    /// of the same file.
+    pub fn remove_ignored_unit_diagnostics(
+        &self,
+        ignored: &[(Symbol, Symbol)],
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        if ignored.is_empty() {
+            return diagnostics;
+        }
+
+        let mut units_by_file: FnvHashMap<&Path, Vec<(Position, bool)>> = FnvHashMap::default();
+        for unit in self.locked_units() {
+            let unit_id = unit.unit_id();
+            let is_ignored = ignored.iter().any(|(library_name, primary_name)| {
+                unit_id.library_name() == library_name && unit_id.primary_name() == primary_name
+            });
+
+            units_by_file
+                .entry(unit.ident().pos.source.file_name())
+                .or_default()
+                .push((unit_start(unit), is_ignored));
+        }
+
+        for units in units_by_file.values_mut() {
+            units.sort_by_key(|(start, _)| *start);
+        }
+
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let Some(units) = units_by_file.get(diagnostic.pos.source.file_name()) else {
+                    return true;
+                };
+                // Diagnostics before the first unit of the file belong to the first unit
+                let idx = units
+                    .partition_point(|(start, _)| *start <= diagnostic.pos.start())
+                    .saturating_sub(1);
+                !units[idx].1
+            })
+            .collect()
+    }
+
+    /// The source text of the entities, package declarations, contexts and package instances
+    /// of a library by their name, which is the interface other projects may be analyzed against.
+    /// A unit extends from the start of its context clause to the start of the next unit of the
+    /// same file. Returns None if the library does not exist.
+    pub fn interface_units(&self, library_name: &Symbol) -> Option<Vec<(Symbol, String)>> {
+        let library = self.libraries.get(library_name)?;
+
+        let mut units_by_file: FnvHashMap<&Path, Vec<(Position, &LockedUnit)>> =
+            FnvHashMap::default();
+        for unit in library.units.values() {
+            units_by_file
+                .entry(unit.ident().pos.source.file_name())
+                .or_default()
+                .push((unit_start(unit), unit));
+        }
+
+        let mut file_names: Vec<&Path> = units_by_file.keys().copied().collect();
+        file_names.sort();
+
+        let mut interface = Vec::new();
+        for file_name in file_names {
+            let units = units_by_file.get_mut(file_name).unwrap();
+            units.sort_by_key(|(start, _)| *start);
+
+            for (idx, (start, unit)) in units.iter().enumerate() {
+                let AnyKind::Primary(kind) = unit.kind() else {
+                    continue;
+                };
+                if kind == PrimaryKind::Configuration {
+                    continue;
+                }
+
+                let contents = unit.ident().pos.source.contents();
+                let end = units
+                    .get(idx + 1)
+                    .map(|(next, _)| *next)
+                    .unwrap_or_else(|| contents.range().end);
+                let text = contents.text(crate::data::Range::new(*start, end));
+                interface.push((unit.ident().item.clone(), text.trim_end().to_owned()));
+            }
+        }
+        Some(interface)
+    }
+
     pub fn find_all_unresolved(&self) -> (usize, Vec<SrcPos>) {
         let mut searcher = FindAllUnresolved::default();
         let _ = self.search(&mut searcher);
         (searcher.count, searcher.unresolved)
     }
 
+    /// Classify the processes of an architecture by the synthesis template they follow
+    /// Returns None if the architecture does not exist or has not been analyzed
+    pub fn classify_processes(
+        &self,
+        library_name: &Symbol,
+        entity_name: &Symbol,
+        architecture_name: &Symbol,
+    ) -> Option<Vec<ClassifiedProcess>> {
+        let locked_unit = self
+            .libraries
+            .get(library_name)?
+            .units
+            .get(&UnitKey::Secondary(
+                entity_name.clone(),
+                architecture_name.clone(),
+            ))?;
+        let mut arch = locked_unit.analyzed_architecture()?;
+
+        let is_signal = |id| {
+            ObjectEnt::from_any(self.get_ent(id))
+                .is_some_and(|object| object.class() == ObjectClass::Signal)
+        };
+        Some(classify_processes(&mut arch.statements, &is_signal))
+    }
+
     #[cfg(test)]
     pub fn find_all_references_pos(&self, decl_pos: &SrcPos) -> Vec<SrcPos> {
         if let Some(ent) = self.search_reference(decl_pos.source(), decl_pos.start()) {
@@ -457,6 +862,8 @@ impl DesignRoot {
                 };
 
                 AnalysisData {
+                    has_external_names: context.has_external_names.get(),
+                    callers: context.callers.take(),
                     arena: arena.finalize(),
                     diagnostics,
                     has_circular_dependency,
@@ -472,6 +879,8 @@ impl DesignRoot {
                 };
 
                 AnalysisData {
+                    has_external_names: context.has_external_names.get(),
+                    callers: context.callers.take(),
                     arena: arena.finalize(),
                     diagnostics,
                     has_circular_dependency,
@@ -492,6 +901,73 @@ impl DesignRoot {
         }
     }
 
+    /// Analyze the design unit containing the cursor again into a separate arena.
+    /// The context is prepared by `prepare` to capture state at the cursor
+    /// which is extracted by `extract` after the analysis.
+    /// Returns None if the cursor is not within an analyzed design unit.
+    pub(super) fn reanalyze_containing<T>(
+        &self,
+        source: &Source,
+        cursor: Position,
+        prepare: impl FnOnce(AnalyzeContext<'_>) -> AnalyzeContext<'_>,
+        extract: impl FnOnce(AnalyzeContext<'_>) -> T,
+    ) -> Option<T> {
+        // The design unit containing the cursor is the last one starting before it
+        let mut containing = None;
+        for locked_unit in self.locked_units() {
+            if locked_unit.ident().pos.source != *source {
+                continue;
+            }
+            let Some(unit) = locked_unit.unit.get() else {
+                continue;
+            };
+            let start = unit
+                .context_clause()
+                .and_then(|items| items.first())
+                .map_or_else(|| unit.ident().pos.start(), |item| item.pos.start());
+
+            if start <= cursor && containing.as_ref().is_none_or(|(prev, _)| start > *prev) {
+                containing = Some((start, locked_unit));
+            }
+        }
+
+        let (_, locked_unit) = containing?;
+        let mut unit = locked_unit.unit.get().map(|unit| (*unit).clone())?;
+
+        let arena = Arena::new(ArenaId::default());
+        arena.link(&self.arenas);
+        let context = prepare(AnalyzeContext::new(self, locked_unit.unit_id(), &arena));
+
+        let mut diagnostics = Vec::new();
+        clear_references(&mut unit);
+        let _ = match unit {
+            AnyDesignUnit::Primary(ref mut unit) => {
+                let ent = arena.explicit(unit.name().clone(), AnyEntKind::Label, Some(unit.pos()));
+                context.analyze_primary_unit(ent.id(), unit, &mut diagnostics)
+            }
+            AnyDesignUnit::Secondary(ref mut unit) => {
+                context.analyze_secondary_unit(unit, &mut diagnostics)
+            }
+        };
+
+        Some(extract(context))
+    }
+
+    pub(super) fn locked_units(&self) -> impl Iterator<Item = &LockedUnit> {
+        self.libraries
+            .values()
+            .flat_map(|library| library.units.values())
+    }
+
+    /// The architectures of the design that have been analyzed with their unit ids
+    pub(super) fn analyzed_architectures(
+        &self,
+    ) -> impl Iterator<Item = (&UnitId, ArchitectureWriteGuard<'_>)> {
+        self.locked_units().filter_map(|locked_unit| {
+            Some((locked_unit.unit_id(), locked_unit.analyzed_architecture()?))
+        })
+    }
+
     pub(super) fn get_unit<'a>(&'a self, unit_id: &UnitId) -> Option<&'a LockedUnit> {
         self.libraries
             .get(unit_id.library_name())
@@ -755,6 +1231,8 @@ impl DesignRoot {
                         arena,
                         diagnostics,
                         has_circular_dependency: false,
+                        has_external_names: false,
+                        callers: Vec::new(),
                     };
 
                     unit.finish(result);
@@ -808,6 +1286,72 @@ impl DesignRoot {
                 diagnostics.append(unit.unit.expect_analyzed().result().diagnostics.clone());
             }
         }
+
+        self.check_instantiation_cycles(diagnostics);
+        self.check_wait_calls(diagnostics);
+        self.resolve_external_names(diagnostics);
+        self.check_default_architectures(diagnostics);
+    }
+
+    fn check_instantiation_cycles(&self, diagnostics: &mut dyn DiagnosticHandler) {
+        let mut graph = InstantiationGraph::default();
+
+        for library in self.libraries.values() {
+            for unit_id in library.sorted_unit_ids() {
+                let unit = library.units.get(unit_id.key()).unwrap();
+                if let Some(unit) = unit.unit.get() {
+                    if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref arch)) =
+                        *unit
+                    {
+                        graph.add_architecture(arch);
+                    }
+                }
+            }
+        }
+
+        graph.check_cycles(self, diagnostics);
+    }
+
+    fn check_wait_calls(&self, diagnostics: &mut dyn DiagnosticHandler) {
+        let mut graph = WaitGraph::default();
+
+        for library in self.libraries.values() {
+            for unit_id in library.sorted_unit_ids() {
+                let unit = library.units.get(unit_id.key()).unwrap();
+                if let Some(unit) = unit.unit.get() {
+                    graph.add_callers(&unit.result().callers);
+                }
+            }
+        }
+
+        graph.check_waits(self, diagnostics);
+    }
+
+    fn resolve_external_names(&self, diagnostics: &mut dyn DiagnosticHandler) {
+        let mut index = ExternalNameIndex::default();
+        let mut units = Vec::new();
+
+        for library in self.libraries.values() {
+            index.add_library(&library.name, library.id);
+            for unit_id in library.sorted_unit_ids() {
+                let locked_unit = library.units.get(unit_id.key()).unwrap();
+                if let Some(unit) = locked_unit.unit.get() {
+                    index.add_unit(&library.name, &unit);
+                    if unit.result().has_external_names {
+                        units.push((&library.name, locked_unit));
+                    }
+                }
+            }
+        }
+
+        for (library_name, locked_unit) in units {
+            index.resolve(
+                self,
+                library_name,
+                &mut locked_unit.unit.write(),
+                diagnostics,
+            );
+        }
     }
 
     /// Get the named entity
@@ -841,6 +1385,20 @@ fn get_all_affected(
     all_affected
 }
 
+/// The start of the context clause of a design unit, or the start of the line of its name
+fn unit_start(unit: &LockedUnit) -> Position {
+    let mut start = Position::new(unit.ident().pos.start().line, 0);
+    if let Some(design_unit) = unit.unit.get() {
+        if let Some(item) = design_unit
+            .context_clause()
+            .and_then(|context_clause| context_clause.first())
+        {
+            start = start.min(item.pos.start());
+        }
+    }
+    start
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
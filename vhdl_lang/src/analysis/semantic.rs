@@ -47,7 +47,20 @@ impl<'a> AnalyzeContext<'a> {
         let pos = type_mark.suffix_pos();
         let expected = "type";
         let ent = self.resolve_non_overloaded(entities, pos, expected)?;
-        TypeEnt::from_any(ent).ok_or_else(|| AnalysisError::NotFatal(ent.kind_error(pos, expected)))
+        TypeEnt::from_any(ent).ok_or_else(|| {
+            let is_object = matches!(
+                ent.kind(),
+                AnyEntKind::Object(_) | AnyEntKind::ObjectAlias { .. }
+            );
+            // LRM 16.2.1 The 'subtype attribute is available since VHDL-2008
+            AnalysisError::NotFatal(
+                if is_object && self.root.standard() >= VHDLStandard::VHDL2008 {
+                    Diagnostic::object_as_type_mark(pos, ent)
+                } else {
+                    ent.kind_error(pos, expected)
+                },
+            )
+        })
     }
 
     pub fn resolve_type_mark(
@@ -111,6 +124,20 @@ impl<'a> AnalyzeContext<'a> {
         for choice in choices.iter_mut() {
             match choice {
                 Choice::Expression(ref mut expr) => {
+                    // A range attribute name is a discrete range rather than an expression
+                    if let Expression::Name(ref mut name) = expr.item {
+                        if let Name::Attribute(ref mut attr) = name.as_mut() {
+                            if attr.as_range().is_some() {
+                                if let Some(ttyp) = ttyp {
+                                    self.range_attribute_with_ttyp(scope, ttyp, attr, diagnostics)?;
+                                } else {
+                                    as_fatal(self.range_attribute_type(scope, attr, diagnostics))?;
+                                }
+                                continue;
+                            }
+                        }
+                    }
+
                     if let Some(ttyp) = ttyp {
                         self.expr_with_ttyp(scope, ttyp, expr, diagnostics)?;
                     } else {
@@ -153,7 +180,11 @@ impl<'a> AnalyzeContext<'a> {
         fcall: &mut WithPos<CallOrIndexed>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
-        let CallOrIndexed { name, parameters } = &mut fcall.item;
+        let CallOrIndexed {
+            name,
+            generic_map,
+            parameters,
+        } = &mut fcall.item;
 
         let resolved =
             match as_fatal(self.name_resolve(scope, &name.pos, &mut name.item, diagnostics))? {
@@ -173,11 +204,29 @@ impl<'a> AnalyzeContext<'a> {
                     .collect();
 
                 if !procedures.is_empty() {
+                    let procedures = if let Some(generic_map) = generic_map {
+                        match as_fatal(self.call_site_instance(
+                            scope,
+                            des,
+                            procedures,
+                            generic_map,
+                            diagnostics,
+                        ))? {
+                            Some(instance) => vec![instance],
+                            None => {
+                                self.analyze_assoc_elems(scope, parameters, diagnostics)?;
+                                return Ok(());
+                            }
+                        }
+                    } else {
+                        procedures
+                    };
+
                     match as_fatal(self.disambiguate(
                         scope,
                         &fcall.pos,
                         des,
-                        &mut fcall.item.parameters,
+                        parameters,
                         None,
                         procedures,
                         diagnostics,
@@ -263,6 +312,34 @@ impl<'a> AnyEnt<'a> {
 }
 
 impl Diagnostic {
+    /// A type or subtype name that is used where a value is expected
+    pub(super) fn type_as_value(pos: &SrcPos, typ: TypeEnt) -> Diagnostic {
+        Diagnostic::error(
+            pos,
+            format!(
+                "Expected value, got {}, did you mean a qualified expression {}'(...) or a constant?",
+                typ.describe(),
+                typ.designator()
+            ),
+        )
+    }
+
+    /// An object name that is used as a type mark, the subtype of the object is denoted by 'subtype
+    pub(super) fn object_as_type_mark(pos: &SrcPos, ent: &AnyEnt) -> Diagnostic {
+        let mut error = Diagnostic::error(
+            pos,
+            format!(
+                "Expected type, got {}, did you mean {}'subtype?",
+                ent.describe(),
+                ent.designator()
+            ),
+        );
+        if let Some(decl_pos) = ent.decl_pos() {
+            error.add_related(decl_pos, "Defined here");
+        }
+        error.with_fix(pos, format!("{}'subtype", ent.designator()))
+    }
+
     pub(super) fn type_mismatch(pos: &SrcPos, desc: &str, expected_type: TypeEnt) -> Diagnostic {
         Diagnostic::error(
             pos,
@@ -31,10 +31,30 @@ impl<'a> AnalyzeContext<'a> {
             SequentialStatement::Return(ref mut ret) => {
                 let ReturnStatement { ref mut expression } = ret.item;
 
+                if let Some(AssignmentRightHand::Conditional(ref conditionals)) = expression {
+                    if self.root.standard() < VHDLStandard::VHDL2019 {
+                        if let Some(first) = conditionals.conditionals.first() {
+                            diagnostics.push(
+                                Diagnostic::error(
+                                    &first.condition.pos,
+                                    "Conditional return statement is not allowed before VHDL-2019",
+                                )
+                                .with_code(ErrorCode::LaterRevision),
+                            );
+                        }
+                    }
+                }
+
                 match sroot {
                     SequentialRoot::Function(ttyp) => {
                         if let Some(ref mut expression) = expression {
-                            self.expr_with_ttyp(scope, *ttyp, expression, diagnostics)?;
+                            self.analyze_expr_right_hand(
+                                scope,
+                                Some(*ttyp),
+                                None,
+                                expression,
+                                diagnostics,
+                            )?;
                         } else {
                             diagnostics.error(&ret.pos, "Functions cannot return without a value");
                         }
@@ -44,22 +64,45 @@ impl<'a> AnalyzeContext<'a> {
                             diagnostics.error(&ret.pos, "Procedures cannot return a value");
                         }
                     }
-                    SequentialRoot::Process => {
+                    SequentialRoot::Process { .. } => {
                         diagnostics.error(&ret.pos, "Cannot return from a process");
                     }
                     SequentialRoot::Unknown => {
                         if let Some(ref mut expression) = expression {
-                            self.expr_unknown_ttyp(scope, expression, diagnostics)?;
+                            self.analyze_expr_right_hand(
+                                scope,
+                                None,
+                                None,
+                                expression,
+                                diagnostics,
+                            )?;
                         }
                     }
                 }
             }
             SequentialStatement::Wait(ref mut wait_stmt) => {
+                // LRM 10.2 Procedures called by functions and processes with a sensitivity list
+                // are checked once all design units have been analyzed
+                match sroot {
+                    SequentialRoot::Function(_) => {
+                        diagnostics
+                            .error(&wait_stmt.pos, "A function cannot contain a wait statement");
+                    }
+                    SequentialRoot::Process {
+                        has_sensitivity_list: true,
+                    } => {
+                        diagnostics.error(
+                            &wait_stmt.pos,
+                            "A process with a sensitivity list cannot contain a wait statement",
+                        );
+                    }
+                    _ => {}
+                }
                 let WaitStatement {
                     sensitivity_clause,
                     condition_clause,
                     timeout_clause,
-                } = wait_stmt;
+                } = &mut wait_stmt.item;
                 self.sensitivity_list_check(scope, sensitivity_clause, diagnostics)?;
                 if let Some(expr) = condition_clause {
                     self.boolean_expr(scope, expr, diagnostics)?;
@@ -129,7 +172,7 @@ impl<'a> AnalyzeContext<'a> {
             }
             SequentialStatement::Case(ref mut case_stmt) => {
                 let CaseStatement {
-                    is_matching: _,
+                    is_matching,
                     expression,
                     alternatives,
                 } = case_stmt;
@@ -139,6 +182,7 @@ impl<'a> AnalyzeContext<'a> {
                     self.choice_with_ttyp(scope, ctyp, choices, diagnostics)?;
                     self.analyze_sequential_part(scope, sroot, item, diagnostics)?;
                 }
+                self.check_case_choices(*is_matching, expression, ctyp, alternatives, diagnostics);
             }
             SequentialStatement::Loop(ref mut loop_stmt) => {
                 let LoopStatement {
@@ -148,11 +192,15 @@ impl<'a> AnalyzeContext<'a> {
                 match iteration_scheme {
                     Some(IterationScheme::For(ref mut index, ref mut drange)) => {
                         let typ = as_fatal(self.drange_type(scope, drange, diagnostics))?;
-                        let region = scope.nested();
-                        region.add(
-                            self.arena.define(index, AnyEntKind::LoopParameter(typ)),
+                        self.check_null_drange(
+                            drange,
+                            Some("the loop is never executed"),
                             diagnostics,
                         );
+                        let region = scope.nested();
+                        let index = self.arena.define(index, AnyEntKind::LoopParameter(typ));
+                        self.set_loop_parameter_range(index, typ, drange);
+                        region.add(index, diagnostics);
                         self.analyze_sequential_part(&region, sroot, statements, diagnostics)?;
                     }
                     Some(IterationScheme::While(ref mut expr)) => {
@@ -170,6 +218,7 @@ impl<'a> AnalyzeContext<'a> {
             SequentialStatement::SignalAssignment(ref mut assign) => {
                 // @TODO more
                 let SignalAssignment { target, rhs, .. } = assign;
+                self.check_sequential_right_hand(rhs, diagnostics);
                 self.analyze_waveform_assignment(
                     scope,
                     target,
@@ -180,6 +229,7 @@ impl<'a> AnalyzeContext<'a> {
             }
             SequentialStatement::VariableAssignment(ref mut assign) => {
                 let VariableAssignment { target, rhs } = assign;
+                self.check_sequential_right_hand(rhs, diagnostics);
                 self.analyze_expr_assignment(
                     scope,
                     target,
@@ -221,16 +271,19 @@ impl<'a> AnalyzeContext<'a> {
         statements: &mut [LabeledSequentialStatement],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
-        for statement in statements.iter_mut() {
+        let mut probe = self.probe_list(statements);
+        for (idx, statement) in statements.iter_mut().enumerate() {
+            self.probe_item(scope, &mut probe, idx);
             self.analyze_sequential_statement(scope, sroot, statement, diagnostics)?;
         }
+        self.probe_end(scope, probe);
 
         Ok(())
     }
 }
 
 pub enum SequentialRoot<'a> {
-    Process,
+    Process { has_sensitivity_list: bool },
     Procedure,
     Function(TypeEnt<'a>),
     Unknown,
@@ -0,0 +1,118 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Suggestions of similar names when a name could not be resolved
+
+/// Find the candidate most similar to the name, if any is similar enough to be a likely misspelling
+/// Names are compared ignoring case, a candidate only differing by underscores is the best match
+pub fn most_similar<'c>(name: &str, candidates: impl Iterator<Item = &'c str>) -> Option<&'c str> {
+    let name = name.to_lowercase();
+    let max_distance = match name.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        6..=8 => 2,
+        _ => 3,
+    };
+
+    let mut best: Option<(usize, &str)> = None;
+    for candidate in candidates {
+        let lower = candidate.to_lowercase();
+        if lower == name {
+            continue;
+        }
+
+        let distance = if without_underscores(&lower) == without_underscores(&name) {
+            0
+        } else {
+            edit_distance(&lower, &name)
+        };
+
+        if distance > max_distance {
+            continue;
+        }
+
+        // Prefer the smallest distance and then the alphabetically first name for determinism
+        let is_better = match best {
+            Some((best_distance, best_name)) => (distance, candidate) < (best_distance, best_name),
+            None => true,
+        };
+        if is_better {
+            best = Some((distance, candidate));
+        }
+    }
+
+    best.map(|(_, candidate)| candidate)
+}
+
+fn without_underscores(name: &str) -> String {
+    name.chars().filter(|chr| *chr != '_').collect()
+}
+
+/// The number of insertions, deletions, substitutions and transpositions of adjacent characters
+/// needed to turn one string into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("wr_en", "wr_en"), 0);
+        assert_eq!(edit_distance("wr_en", "wr_em"), 1);
+        assert_eq!(edit_distance("wr_en", "wren"), 1);
+        assert_eq!(edit_distance("wr_en", "rw_en"), 1);
+        assert_eq!(edit_distance("clk", "reset"), 5);
+    }
+
+    #[test]
+    fn suggests_most_similar() {
+        let candidates = ["wr_en", "rd_en", "wr_data", "WrEnable"];
+        assert_eq!(
+            most_similar("wr_enn", candidates.into_iter()),
+            Some("wr_en")
+        );
+        assert_eq!(most_similar("WR_EM", candidates.into_iter()), Some("wr_en"));
+        assert_eq!(
+            most_similar("wr_enable", candidates.into_iter()),
+            Some("WrEnable")
+        );
+        assert_eq!(most_similar("clk", candidates.into_iter()), None);
+    }
+
+    #[test]
+    fn short_names_need_an_exact_underscore_match() {
+        assert_eq!(most_similar("a", ["b"].into_iter()), None);
+        assert_eq!(most_similar("a_", ["a"].into_iter()), Some("a"));
+    }
+}
@@ -243,6 +243,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(type_ent),
                 has_default: false,
+                view: None,
             }),
             type_ent.decl_pos(),
         ));
@@ -254,6 +255,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(type_ent),
                 has_default: false,
+                view: None,
             }),
             type_ent.decl_pos(),
         ));
@@ -280,6 +282,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(arr_typ),
                 has_default: false,
+                view: None,
             }),
             arr_typ.decl_pos(),
         ));
@@ -302,6 +305,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(typ.to_owned()),
                 has_default: false,
+                view: None,
             }),
             typ.decl_pos(),
         ));
@@ -336,6 +340,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(left),
                 has_default: false,
+                view: None,
             }),
             implicit_of.decl_pos(),
         ));
@@ -348,6 +353,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(right),
                 has_default: false,
+                view: None,
             }),
             implicit_of.decl_pos(),
         ));
@@ -399,6 +405,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(string),
                     has_default: false,
+                    view: None,
                 }),
                 file_type.decl_pos(),
             ));
@@ -410,6 +417,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(file_open_kind),
                     has_default: true,
+                    view: None,
                 }),
                 file_type.decl_pos(),
             ));
@@ -431,6 +439,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::Out),
                     subtype: Subtype::new(file_open_status),
                     has_default: false,
+                    view: None,
                 }),
                 file_type.decl_pos(),
             ));
@@ -446,6 +455,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(string),
                     has_default: false,
+                    view: None,
                 }),
                 file_type.decl_pos(),
             ));
@@ -457,6 +467,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(file_open_kind),
                     has_default: true,
+                    view: None,
                 }),
                 file_type.decl_pos(),
             ));
@@ -501,6 +512,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::Out),
                     subtype: Subtype::new(type_mark),
                     has_default: false,
+                    view: None,
                 }),
                 file_type.decl_pos(),
             ));
@@ -529,6 +541,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(type_mark),
                     has_default: false,
+                    view: None,
                 }),
                 file_type.decl_pos(),
             ));
@@ -589,6 +602,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(type_ent),
                 has_default: false,
+                view: None,
             }),
             type_ent.decl_pos(),
         ));
@@ -612,6 +626,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::In),
                 subtype: Subtype::new(type_ent),
                 has_default: false,
+                view: None,
             }),
             type_ent.decl_pos(),
         ));
@@ -635,6 +650,7 @@ impl<'a> AnalyzeContext<'a> {
                 mode: Some(Mode::InOut),
                 subtype: Subtype::new(type_ent.to_owned()),
                 has_default: false,
+                view: None,
             }),
             type_ent.decl_pos(),
         ));
@@ -737,6 +753,54 @@ impl<'a> AnalyzeContext<'a> {
         .chain(self.comparators(typ).into_iter())
     }
 
+    /// LRM 9.2.3 The matching relational operators of BIT and STD_ULOGIC
+    pub fn matching_comparators(&self, typ: TypeEnt<'a>) -> impl Iterator<Item = EntRef<'a>> {
+        [
+            self.symmetric_binary(Operator::QueEQ, typ),
+            self.symmetric_binary(Operator::QueNE, typ),
+            self.symmetric_binary(Operator::QueLT, typ),
+            self.symmetric_binary(Operator::QueLTE, typ),
+            self.symmetric_binary(Operator::QueGT, typ),
+            self.symmetric_binary(Operator::QueGTE, typ),
+        ]
+        .into_iter()
+    }
+
+    /// LRM 9.2.3 The matching equality operators of one-dimensional arrays of BIT and STD_ULOGIC
+    fn matching_array_equality(
+        &self,
+        array_type: TypeEnt<'a>,
+        elem_type: TypeEnt<'a>,
+    ) -> impl Iterator<Item = EntRef<'a>> {
+        [
+            self.binary(
+                Operator::QueEQ,
+                array_type,
+                array_type,
+                array_type,
+                elem_type,
+            ),
+            self.binary(
+                Operator::QueNE,
+                array_type,
+                array_type,
+                array_type,
+                elem_type,
+            ),
+        ]
+        .into_iter()
+    }
+
+    /// True for the BIT type and the STD_ULOGIC type of the ieee.std_logic_1164 package
+    pub(crate) fn has_matching_operators(&self, typ: TypeEnt<'a>) -> bool {
+        let base = typ.base_type();
+        if base.designator() == &Designator::Identifier(self.symbol("STD_ULOGIC")) {
+            return true;
+        }
+
+        self.root.standard_types.is_some() && base.id() == self.bit().id()
+    }
+
     pub fn enum_implicits(&self, typ: TypeEnt<'a>) -> impl Iterator<Item = EntRef<'a>> {
         [
             self.create_to_string(typ),
@@ -745,6 +809,15 @@ impl<'a> AnalyzeContext<'a> {
         ]
         .into_iter()
         .chain(self.comparators(typ).into_iter())
+        .chain(
+            (if self.has_matching_operators(typ) {
+                Some(self.matching_comparators(typ))
+            } else {
+                None
+            })
+            .into_iter()
+            .flatten(),
+        )
     }
 
     pub fn record_implicits(&self, typ: TypeEnt<'a>) -> impl Iterator<Item = EntRef<'a>> {
@@ -814,6 +887,15 @@ impl<'a> AnalyzeContext<'a> {
             .into_iter()
             .flatten(),
         )
+        .chain(
+            (if is_one_dimensional && self.has_matching_operators(*elem_type) {
+                Some(self.matching_array_equality(typ, *elem_type))
+            } else {
+                None
+            })
+            .into_iter()
+            .flatten(),
+        )
         .chain(
             (if is_scalar {
                 Some(
@@ -938,6 +1020,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(real.to_owned()),
                     has_default: false,
+                    view: None,
                 }),
                 real.decl_pos(),
             ));
@@ -949,6 +1032,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(natural),
                     has_default: false,
+                    view: None,
                 }),
                 real.decl_pos(),
             ));
@@ -980,6 +1064,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(real.to_owned()),
                     has_default: false,
+                    view: None,
                 }),
                 real.decl_pos(),
             ));
@@ -991,6 +1076,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(string.to_owned()),
                     has_default: false,
+                    view: None,
                 }),
                 real.decl_pos(),
             ));
@@ -1021,6 +1107,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(time.to_owned()),
                     has_default: false,
+                    view: None,
                 }),
                 time.decl_pos(),
             ));
@@ -1032,6 +1119,7 @@ impl<'a> AnalyzeContext<'a> {
                     mode: Some(Mode::In),
                     subtype: Subtype::new(time.to_owned()),
                     has_default: false,
+                    view: None,
                 }),
                 time.decl_pos(),
             ));
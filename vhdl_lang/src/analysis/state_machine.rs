@@ -0,0 +1,535 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Finite state machines of an architecture in the common pattern of a register of an
+//! enumeration type and a case statement on it. The case statement assigns the next state
+//! either to the register itself within a clocked process, or to a signal within a
+//! combinational process that the register copies.
+
+use super::combinational_loop::process_assignment_sources;
+use super::connectivity::{csv_table, html_table};
+use super::named_entity::*;
+use super::process::{classify_process, signal_reads, ProcessKind};
+use super::root::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// A transition between two states of a state machine
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateTransition {
+    /// The state of the case alternative, `others` for the states without an alternative
+    pub from: String,
+    pub to: String,
+    /// The conditions of the if and case statements within the case alternative that
+    /// lead to the assignment, empty when the transition is unconditional
+    pub condition: String,
+    /// The position of the next state in the assignment
+    pub pos: SrcPos,
+}
+
+/// A register of an enumeration type whose next state is assigned within a case statement on it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateMachine {
+    /// The architecture as library.entity(architecture)
+    pub architecture: String,
+    /// The state register
+    pub signal: String,
+    /// The literals of the type of the state register in the order of their declaration
+    pub states: Vec<String>,
+    /// The state that the register is assigned when the reset of its process is active
+    pub reset_state: Option<String>,
+    pub transitions: Vec<StateTransition>,
+    pub decl_pos: Option<SrcPos>,
+}
+
+/// The state machines of all architectures of the design
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateMachineReport {
+    pub state_machines: Vec<StateMachine>,
+}
+
+const COLUMNS: [&str; 6] = [
+    "architecture",
+    "signal",
+    "reset state",
+    "from",
+    "to",
+    "condition",
+];
+
+impl StateMachine {
+    fn rows(&self) -> impl Iterator<Item = [String; 6]> + '_ {
+        self.transitions.iter().map(|transition| {
+            [
+                self.architecture.clone(),
+                self.signal.clone(),
+                self.reset_state.clone().unwrap_or_default(),
+                transition.from.clone(),
+                transition.to.clone(),
+                transition.condition.clone(),
+            ]
+        })
+    }
+}
+
+impl StateMachineReport {
+    /// A table with a header row and one row per transition
+    pub fn to_csv(&self) -> String {
+        csv_table(
+            &COLUMNS,
+            self.state_machines.iter().flat_map(StateMachine::rows),
+        )
+    }
+
+    /// A standalone page with a table where the transitions from the reset state are highlighted
+    pub fn to_html(&self) -> String {
+        html_table(
+            "State machines",
+            &COLUMNS,
+            self.state_machines.iter().flat_map(|state_machine| {
+                state_machine.rows().map(|row| {
+                    let class = if state_machine.reset_state.as_ref() == Some(&row[3]) {
+                        "reset"
+                    } else {
+                        "transition"
+                    };
+                    (class, row)
+                })
+            }),
+        )
+    }
+}
+
+/// A next state assigned within a case statement on a state register
+struct CaseTransition {
+    state: EntityId,
+    /// The signal that is assigned the next state
+    target: EntityId,
+    transition: StateTransition,
+}
+
+#[derive(Default)]
+struct ArchitectureStates {
+    transitions: Vec<CaseTransition>,
+    registers: FnvHashSet<EntityId>,
+    /// The signals that each signal is copied to by a register
+    copied_to: FnvHashMap<EntityId, Vec<EntityId>>,
+    /// The state assigned to a register when the reset of its process is active
+    reset_states: FnvHashMap<EntityId, String>,
+}
+
+impl DesignRoot {
+    /// The state machines within all architectures of the design, including those within
+    /// blocks and generate statements
+    pub fn state_machines(&self) -> StateMachineReport {
+        let mut state_machines = Vec::new();
+        for (unit_id, mut arch) in self.analyzed_architectures() {
+            let mut states = ArchitectureStates::default();
+            self.collect_states(&mut arch.statements, &mut states);
+            let architecture = format!(
+                "{}.{}({})",
+                unit_id.library_name(),
+                arch.entity_name.item,
+                arch.ident.item
+            );
+            self.add_state_machines(&architecture, states, &mut state_machines);
+        }
+
+        state_machines.sort_by(|x, y| x.architecture.cmp(&y.architecture));
+        StateMachineReport { state_machines }
+    }
+
+    fn add_state_machines(
+        &self,
+        architecture: &str,
+        states: ArchitectureStates,
+        state_machines: &mut Vec<StateMachine>,
+    ) {
+        let mut by_state: Vec<(EntityId, Vec<StateTransition>)> = Vec::new();
+        for case_transition in states.transitions {
+            let CaseTransition {
+                state,
+                target,
+                transition,
+            } = case_transition;
+            // The next state is either assigned to the register or copied to it by the register
+            let is_next_state = target == state
+                || states
+                    .copied_to
+                    .get(&target)
+                    .is_some_and(|copies| copies.contains(&state));
+            if !is_next_state || !states.registers.contains(&state) {
+                continue;
+            }
+            match by_state.iter_mut().find(|(id, _)| *id == state) {
+                Some((_, transitions)) => transitions.push(transition),
+                None => by_state.push((state, vec![transition])),
+            }
+        }
+
+        for (state, transitions) in by_state {
+            let ent = self.get_ent(state);
+            state_machines.push(StateMachine {
+                architecture: architecture.to_owned(),
+                signal: ent.designator().to_string(),
+                states: self.enum_states(state).unwrap_or_default(),
+                reset_state: states.reset_states.get(&state).cloned(),
+                transitions,
+                decl_pos: ent.decl_pos().cloned(),
+            });
+        }
+    }
+
+    fn collect_states(
+        &self,
+        statements: &mut [LabeledConcurrentStatement],
+        states: &mut ArchitectureStates,
+    ) {
+        for statement in statements.iter_mut() {
+            match statement.statement {
+                ConcurrentStatement::Process(ref mut process) => {
+                    let kind = classify_process(process);
+                    if kind == ProcessKind::Other {
+                        continue;
+                    }
+                    self.case_transitions(&process.statements, states);
+
+                    let ProcessKind::Clocked(clocked) = kind else {
+                        continue;
+                    };
+                    let mut assignments = Vec::new();
+                    process_assignment_sources(
+                        &mut process.statements,
+                        &|id| self.is_signal(id),
+                        &mut assignments,
+                    );
+                    for assignment in assignments {
+                        states.registers.insert(assignment.target);
+                        if let Some(copied) = assignment.copied {
+                            states
+                                .copied_to
+                                .entry(copied)
+                                .or_default()
+                                .push(assignment.target);
+                        }
+                    }
+                    if let Some(reset) = clocked.reset.and_then(|reset| reset.signal) {
+                        self.reset_states(&mut process.statements, reset, states);
+                    }
+                }
+                ConcurrentStatement::Block(ref mut block) => {
+                    self.collect_states(&mut block.statements, states);
+                }
+                ConcurrentStatement::ForGenerate(ref mut gen) => {
+                    self.collect_states(&mut gen.body.statements, states);
+                }
+                ConcurrentStatement::IfGenerate(ref mut gen) => {
+                    for conditional in gen.conditionals.iter_mut() {
+                        self.collect_states(&mut conditional.item.statements, states);
+                    }
+                    if let Some(ref mut else_item) = gen.else_item {
+                        self.collect_states(&mut else_item.statements, states);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                    for alternative in gen.alternatives.iter_mut() {
+                        self.collect_states(&mut alternative.item.statements, states);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The transitions of the case statements on a state register within the statements
+    /// of a process, the conditions of the statements enclosing the case statement are
+    /// those of the clock and reset and are not part of the transitions
+    fn case_transitions(
+        &self,
+        statements: &[LabeledSequentialStatement],
+        states: &mut ArchitectureStates,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                SequentialStatement::Case(ref case_stmt) => {
+                    if let Some(state) = self.state_register(&case_stmt.expression.item) {
+                        for alternative in case_stmt.alternatives.iter() {
+                            for choice in alternative.choices.iter() {
+                                let from = match choice {
+                                    Choice::Expression(expr) => expr.to_string(),
+                                    Choice::DiscreteRange(range) => range.to_string(),
+                                    Choice::Others => "others".to_owned(),
+                                };
+                                self.next_states(
+                                    state,
+                                    &from,
+                                    &alternative.item,
+                                    &mut Vec::new(),
+                                    states,
+                                );
+                            }
+                        }
+                    } else {
+                        for alternative in case_stmt.alternatives.iter() {
+                            self.case_transitions(&alternative.item, states);
+                        }
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.case_transitions(&conditional.item, states);
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.case_transitions(else_item, states);
+                    }
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    self.case_transitions(&loop_stmt.statements, states);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The states assigned within an alternative of a case statement on a state register
+    fn next_states(
+        &self,
+        state: EntityId,
+        from: &str,
+        statements: &[LabeledSequentialStatement],
+        conditions: &mut Vec<String>,
+        states: &mut ArchitectureStates,
+    ) {
+        for statement in statements.iter() {
+            let outer = conditions.len();
+            match statement.statement {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    let Some(target) = self.assigned_signal(&assign.target.item) else {
+                        continue;
+                    };
+                    let mut add = |value: &WithPos<Expression>, condition: Option<String>| {
+                        if self.enum_literal(&value.item).is_none() {
+                            return;
+                        }
+                        let condition = conditions.iter().cloned().chain(condition);
+                        states.transitions.push(CaseTransition {
+                            state,
+                            target,
+                            transition: StateTransition {
+                                from: from.to_owned(),
+                                to: value.to_string(),
+                                condition: condition.collect::<Vec<_>>().join(" and "),
+                                pos: value.pos.clone(),
+                            },
+                        });
+                    };
+                    match assign.rhs {
+                        AssignmentRightHand::Simple(ref waveform) => {
+                            if let Some(value) = single_value(waveform) {
+                                add(value, None);
+                            }
+                        }
+                        AssignmentRightHand::Conditional(ref conditionals) => {
+                            let mut previous = Vec::new();
+                            for conditional in conditionals.conditionals.iter() {
+                                if let Some(value) = single_value(&conditional.item) {
+                                    add(
+                                        value,
+                                        Some(branch_condition(
+                                            &previous,
+                                            Some(&conditional.condition),
+                                        )),
+                                    );
+                                }
+                                previous.push(&conditional.condition);
+                            }
+                            if let Some(value) =
+                                conditionals.else_item.as_ref().and_then(single_value)
+                            {
+                                add(value, Some(branch_condition(&previous, None)));
+                            }
+                        }
+                        AssignmentRightHand::Selected(..) => {}
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    let mut previous = Vec::new();
+                    for conditional in ifstmt.conditionals.iter() {
+                        conditions.push(branch_condition(&previous, Some(&conditional.condition)));
+                        self.next_states(state, from, &conditional.item, conditions, states);
+                        conditions.truncate(outer);
+                        previous.push(&conditional.condition);
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        conditions.push(branch_condition(&previous, None));
+                        self.next_states(state, from, else_item, conditions, states);
+                    }
+                }
+                SequentialStatement::Case(ref case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter() {
+                        let choices: Vec<String> = alternative
+                            .choices
+                            .iter()
+                            .map(|choice| choice.to_string())
+                            .collect();
+                        conditions.push(format!(
+                            "{} = {}",
+                            case_stmt.expression,
+                            choices.join(" | ")
+                        ));
+                        self.next_states(state, from, &alternative.item, conditions, states);
+                        conditions.truncate(outer);
+                    }
+                }
+                _ => {}
+            }
+            conditions.truncate(outer);
+        }
+    }
+
+    /// The states assigned to registers within the branches of if statements whose condition
+    /// reads the reset signal
+    fn reset_states(
+        &self,
+        statements: &mut [LabeledSequentialStatement],
+        reset: EntityId,
+        states: &mut ArchitectureStates,
+    ) {
+        for statement in statements.iter_mut() {
+            let SequentialStatement::If(ref mut ifstmt) = statement.statement else {
+                continue;
+            };
+            for conditional in ifstmt.conditionals.iter_mut() {
+                let is_reset = signal_reads(&mut conditional.condition, &|id| id == reset)
+                    .iter()
+                    .any(|(id, _)| *id == reset);
+                if !is_reset {
+                    self.reset_states(&mut conditional.item, reset, states);
+                    continue;
+                }
+                for statement in conditional.item.iter() {
+                    let SequentialStatement::SignalAssignment(ref assign) = statement.statement
+                    else {
+                        continue;
+                    };
+                    let AssignmentRightHand::Simple(ref waveform) = assign.rhs else {
+                        continue;
+                    };
+                    if let (Some(target), Some(value)) = (
+                        self.assigned_signal(&assign.target.item),
+                        single_value(waveform),
+                    ) {
+                        if self.enum_literal(&value.item).is_some() {
+                            states.reset_states.insert(target, value.to_string());
+                        }
+                    }
+                }
+            }
+            if let Some(ref mut else_item) = ifstmt.else_item {
+                self.reset_states(else_item, reset, states);
+            }
+        }
+    }
+
+    /// The signal of an expression that is the name of a signal of an enumeration type
+    /// whose literals are identifiers
+    fn state_register(&self, expr: &Expression) -> Option<EntityId> {
+        let Expression::Name(ref name) = expr else {
+            return None;
+        };
+        let Name::Designator(ref designator) = name.as_ref() else {
+            return None;
+        };
+        let id = designator.reference?;
+        self.enum_states(id)?;
+        Some(id)
+    }
+
+    /// The literals of the enumeration type of a signal
+    fn enum_states(&self, id: EntityId) -> Option<Vec<String>> {
+        let object = ObjectEnt::from_any(self.get_ent(id))?;
+        if object.class() != ObjectClass::Signal {
+            return None;
+        }
+        let base_type = object.type_mark().base_type();
+        let Type::Enum(ref literals) = base_type.kind() else {
+            return None;
+        };
+        if literals
+            .iter()
+            .any(|literal| !matches!(literal, Designator::Identifier(_)))
+        {
+            return None;
+        }
+        Some(
+            base_type
+                .implicits
+                .iter()
+                .filter(|ent| {
+                    matches!(
+                        ent.kind(),
+                        AnyEntKind::Overloaded(Overloaded::EnumLiteral(..))
+                    )
+                })
+                .map(|ent| ent.designator().to_string())
+                .collect(),
+        )
+    }
+
+    fn enum_literal(&self, expr: &Expression) -> Option<EntRef<'_>> {
+        let Expression::Name(ref name) = expr else {
+            return None;
+        };
+        let Name::Designator(ref designator) = name.as_ref() else {
+            return None;
+        };
+        let ent = self.get_ent(designator.reference?);
+        matches!(
+            ent.kind(),
+            AnyEntKind::Overloaded(Overloaded::EnumLiteral(..))
+        )
+        .then_some(ent)
+    }
+
+    /// The signal that is the whole target of an assignment
+    fn assigned_signal(&self, target: &Target) -> Option<EntityId> {
+        let Target::Name(Name::Designator(ref designator)) = target else {
+            return None;
+        };
+        designator.reference.filter(|id| self.is_signal(*id))
+    }
+
+    fn is_signal(&self, id: EntityId) -> bool {
+        ObjectEnt::from_any(self.get_ent(id))
+            .is_some_and(|object| object.class() == ObjectClass::Signal)
+    }
+}
+
+/// The value of a waveform with a single element without an after clause
+fn single_value(waveform: &Waveform) -> Option<&WithPos<Expression>> {
+    match waveform {
+        Waveform::Elements(elements) => match elements.as_slice() {
+            [element] if element.after.is_none() => Some(&element.value),
+            _ => None,
+        },
+        Waveform::Unaffected => None,
+    }
+}
+
+/// The condition of a branch of an if statement that is taken when the conditions
+/// of the previous branches do not hold
+fn branch_condition(
+    previous: &[&WithPos<Expression>],
+    condition: Option<&WithPos<Expression>>,
+) -> String {
+    previous
+        .iter()
+        .map(|previous| format!("not ({previous})"))
+        .chain(condition.map(|condition| condition.to_string()))
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
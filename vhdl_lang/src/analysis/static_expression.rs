@@ -0,0 +1,1059 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Evaluation of static expressions
+//! LRM 9.4.2 Locally static primaries
+
+use super::analyze::*;
+use super::literals::string_literal_length;
+use super::named_entity::*;
+use super::region::*;
+use super::DesignRoot;
+use crate::ast::search::*;
+use crate::ast::Range;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+/// Evaluates locally static expressions using the references resolved by the analysis.
+/// The static values and ranges of declarations are stored on their named entities
+/// when they are analyzed such that names may be evaluated through them.
+pub(crate) struct StaticEvaluator<F> {
+    lookup: F,
+    /// The values of generics known at an instantiation site
+    generics: FnvHashMap<EntityId, StaticValue>,
+}
+
+impl<'a, F: Fn(EntityId) -> EntRef<'a>> StaticEvaluator<F> {
+    pub fn new(lookup: F) -> Self {
+        StaticEvaluator {
+            lookup,
+            generics: FnvHashMap::default(),
+        }
+    }
+
+    /// Evaluate names of generics to the given values
+    pub fn with_generics(mut self, generics: FnvHashMap<EntityId, StaticValue>) -> Self {
+        self.generics = generics;
+        self
+    }
+
+    fn get(&self, reference: Reference) -> Option<EntRef<'a>> {
+        reference.map(|id| (self.lookup)(id))
+    }
+
+    /// Evaluate a locally static expression
+    /// The target type is used to resolve character literals
+    /// Returns None if the expression cannot be evaluated
+    pub fn eval(&self, ttyp: Option<TypeEnt<'a>>, expr: &Expression) -> Option<StaticValue> {
+        match expr {
+            Expression::Literal(literal) => self.eval_literal(ttyp, literal),
+            Expression::Name(name) => self.eval_name(name),
+            Expression::Qualified(qexpr) => {
+                let typ = self.type_mark(&qexpr.type_mark.item);
+                self.eval(typ.or(ttyp), &qexpr.expr.item)
+            }
+            Expression::Unary(op, operand) => {
+                if !self.is_predefined_operator(&op.item) {
+                    return None;
+                }
+                eval_unary(op.item.item, self.eval(ttyp, &operand.item)?)
+            }
+            Expression::Binary(op, left, right) => {
+                if !self.is_predefined_operator(&op.item) {
+                    return None;
+                }
+                // The operands of comparisons and logical operators may have another type than the result
+                let operand_ttyp = match op.item.item {
+                    Operator::EQ
+                    | Operator::NE
+                    | Operator::LT
+                    | Operator::LTE
+                    | Operator::GT
+                    | Operator::GTE => None,
+                    _ => ttyp,
+                };
+                let left = self.eval(operand_ttyp, &left.item);
+                let right = self.eval(operand_ttyp, &right.item);
+                eval_binary(op.item.item, left?, right?)
+            }
+            Expression::Aggregate(..) | Expression::New(..) => None,
+        }
+    }
+
+    /// Evaluate a range with locally static bounds
+    pub fn eval_range(&self, ttyp: Option<TypeEnt<'a>>, range: &Range) -> Option<StaticRange> {
+        match range {
+            Range::Range(constraint) => Some(StaticRange::new(
+                self.eval(ttyp, &constraint.left_expr.item)?,
+                self.eval(ttyp, &constraint.right_expr.item)?,
+                constraint.direction,
+            )),
+            Range::Attribute(attr) => {
+                let reverse = match attr.attr.item {
+                    AttributeDesignator::Range(RangeAttribute::Range) => false,
+                    AttributeDesignator::Range(RangeAttribute::ReverseRange) => true,
+                    _ => return None,
+                };
+                let range = self.prefix_range(attr)?;
+                if reverse {
+                    Some(StaticRange::new(
+                        range.right,
+                        range.left,
+                        match range.direction {
+                            Direction::Ascending => Direction::Descending,
+                            Direction::Descending => Direction::Ascending,
+                        },
+                    ))
+                } else {
+                    Some(range)
+                }
+            }
+        }
+    }
+
+    pub fn eval_discrete_range(
+        &self,
+        ttyp: Option<TypeEnt<'a>>,
+        range: &DiscreteRange,
+    ) -> Option<StaticRange> {
+        match range {
+            DiscreteRange::Discrete(type_mark, None) => {
+                let typ = self.type_mark(&type_mark.item)?;
+                self.ranges_of(typ.into())?.first().copied()
+            }
+            DiscreteRange::Discrete(type_mark, Some(range)) => {
+                self.eval_range(self.type_mark(&type_mark.item), range)
+            }
+            DiscreteRange::Range(range) => self.eval_range(ttyp, range),
+        }
+    }
+
+    /// The ranges of a subtype constraint, one per index for array constraints
+    pub fn eval_constraint(
+        &self,
+        typ: TypeEnt<'a>,
+        constraint: &SubtypeConstraint,
+    ) -> Option<Vec<StaticRange>> {
+        match constraint {
+            SubtypeConstraint::Range(range) => Some(vec![
+                self.as_type_range(typ, self.eval_range(Some(typ), range)?)
+            ]),
+            SubtypeConstraint::Array(ranges, _) if !ranges.is_empty() => {
+                let indexes = typ.array_type().map(|(_, indexes)| indexes);
+                ranges
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, range)| {
+                        let index_typ = indexes
+                            .and_then(|indexes| indexes.get(idx).copied().flatten())
+                            .map(TypeEnt::from);
+                        self.eval_discrete_range(index_typ, range)
+                    })
+                    .collect()
+            }
+            SubtypeConstraint::Array(..) | SubtypeConstraint::Record(..) => None,
+        }
+    }
+
+    /// The bounds of the range of a physical type are given as universal integers
+    pub fn as_type_range(&self, typ: TypeEnt<'a>, range: StaticRange) -> StaticRange {
+        if let (Type::Physical, StaticValue::Integer(left), StaticValue::Integer(right)) =
+            (typ.base_type().kind(), range.left, range.right)
+        {
+            StaticRange::new(
+                StaticValue::Physical(left),
+                StaticValue::Physical(right),
+                range.direction,
+            )
+        } else {
+            range
+        }
+    }
+
+    /// The static range of a scalar subtype or the index ranges of a constrained array
+    /// for types, subtypes and objects
+    pub fn ranges_of(&self, ent: EntRef<'a>) -> Option<&'a [StaticRange]> {
+        if let Some(ranges) = ent.static_ranges() {
+            return Some(ranges);
+        }
+
+        match ent.kind() {
+            AnyEntKind::Type(Type::Subtype(subtype)) => self.ranges_of(subtype.type_mark().into()),
+            AnyEntKind::Type(Type::Alias(typ)) => self.ranges_of((*typ).into()),
+            AnyEntKind::Object(object) => self.ranges_of(object.subtype.type_mark().into()),
+            AnyEntKind::DeferredConstant(subtype) | AnyEntKind::ElementDeclaration(subtype) => {
+                self.ranges_of(subtype.type_mark().into())
+            }
+            // The ranges of aliases of whole objects and slices are stored on the alias
+            AnyEntKind::ObjectAlias { type_mark, .. } => self.ranges_of((*type_mark).into()),
+            _ => None,
+        }
+    }
+
+    /// The ranges of an interface object where a constraint that depends on generics
+    /// is evaluated with the known values of the generics
+    pub fn interface_ranges(&self, ent: EntRef<'a>) -> Option<Vec<StaticRange>> {
+        if let Some(StaticProperty::Generic {
+            constraint: Some(ref constraint),
+            ..
+        }) = ent.static_property
+        {
+            let AnyEntKind::Object(object) = ent.kind() else {
+                return None;
+            };
+            self.eval_constraint(object.subtype.type_mark(), constraint)
+        } else {
+            self.ranges_of(ent).map(|ranges| ranges.to_vec())
+        }
+    }
+
+    fn type_mark(&self, type_mark: &TypeMark) -> Option<TypeEnt<'a>> {
+        if type_mark.attr.is_some() {
+            return None;
+        }
+        TypeEnt::from_any(self.get(type_mark.name.item.get_suffix_reference())?)
+    }
+
+    /// Returns true if the operator resolved to an implicitly declared operator
+    fn is_predefined_operator(&self, op: &WithRef<Operator>) -> bool {
+        self.get(op.reference)
+            .map(|ent| ent.is_implicit())
+            .unwrap_or(false)
+    }
+
+    fn eval_literal(&self, ttyp: Option<TypeEnt<'a>>, literal: &Literal) -> Option<StaticValue> {
+        match literal {
+            Literal::AbstractLiteral(AbstractLiteral::Integer(value)) => {
+                Some(StaticValue::Integer(i64::try_from(*value).ok()?))
+            }
+            Literal::AbstractLiteral(AbstractLiteral::Real(value)) => {
+                Some(StaticValue::Real(*value))
+            }
+            Literal::Physical(physical) => self.eval_physical_literal(physical),
+            Literal::Character(chr) => {
+                let designator = Designator::Character(*chr);
+                ttyp?
+                    .base_type()
+                    .implicits
+                    .iter()
+                    .find(|ent| ent.designator() == &designator)?
+                    .static_value()
+            }
+            Literal::String(..) | Literal::BitString(..) | Literal::Null => None,
+        }
+    }
+
+    pub fn eval_physical_literal(&self, physical: &PhysicalLiteral) -> Option<StaticValue> {
+        let StaticValue::Physical(factor) = self.get(physical.unit.reference)?.static_value()?
+        else {
+            return None;
+        };
+
+        let value = match physical.value {
+            AbstractLiteral::Integer(value) => i64::try_from(value).ok()?.checked_mul(factor)?,
+            AbstractLiteral::Real(value) => {
+                let value = (value * factor as f64).round();
+                if value.is_finite() && value.abs() < i64::MAX as f64 {
+                    value as i64
+                } else {
+                    return None;
+                }
+            }
+        };
+        Some(StaticValue::Physical(value))
+    }
+
+    fn eval_name(&self, name: &Name) -> Option<StaticValue> {
+        match name {
+            Name::Designator(..) | Name::Selected(..) => {
+                let id = name.get_suffix_reference()?;
+                if let Some(value) = self.generics.get(&id) {
+                    return Some(*value);
+                }
+                let ent = (self.lookup)(id).as_actual();
+                if let AnyEntKind::ObjectAlias { base_object, .. } = ent.kind() {
+                    base_object.static_value()
+                } else {
+                    ent.static_value()
+                }
+            }
+            Name::Attribute(attr) => self.eval_attribute(attr),
+            Name::SelectedAll(..)
+            | Name::Slice(..)
+            | Name::CallOrIndexed(..)
+            | Name::External(..) => None,
+        }
+    }
+
+    /// The range of the prefix of an array or scalar attribute for the dimension given by the
+    /// optional static expression of the attribute
+    fn prefix_range(&self, attr: &AttributeName) -> Option<StaticRange> {
+        let prefix = self.get(attr.name.item.get_suffix_reference())?.as_actual();
+        let dimension = if let Some(ref expr) = attr.expr {
+            usize::try_from(self.eval(None, &expr.item)?.as_discrete()?).ok()?
+        } else {
+            1
+        };
+        self.interface_ranges(prefix)?
+            .get(dimension.checked_sub(1)?)
+            .copied()
+    }
+
+    fn eval_attribute(&self, attr: &AttributeName) -> Option<StaticValue> {
+        match attr.attr.item {
+            AttributeDesignator::Left => Some(self.prefix_range(attr)?.left),
+            AttributeDesignator::Right => Some(self.prefix_range(attr)?.right),
+            AttributeDesignator::High => Some(self.prefix_range(attr)?.high()),
+            AttributeDesignator::Low => Some(self.prefix_range(attr)?.low()),
+            AttributeDesignator::Length => {
+                Some(StaticValue::Integer(self.prefix_range(attr)?.length()?))
+            }
+            AttributeDesignator::Ascending => Some(StaticValue::from_bool(
+                self.prefix_range(attr)?.direction == Direction::Ascending,
+            )),
+            AttributeDesignator::Descending => Some(StaticValue::from_bool(
+                self.prefix_range(attr)?.direction == Direction::Descending,
+            )),
+            AttributeDesignator::Pos
+            | AttributeDesignator::Val
+            | AttributeDesignator::Succ
+            | AttributeDesignator::Pred
+            | AttributeDesignator::LeftOf
+            | AttributeDesignator::RightOf => {
+                let typ = TypeEnt::from_any(self.get(attr.name.item.get_suffix_reference())?)?;
+                let expr = &attr.expr.as_ref()?.item;
+
+                if attr.attr.item == AttributeDesignator::Val {
+                    let position = self.eval(None, expr)?.as_discrete()?;
+                    let range = self.ranges_of(typ.into())?.first()?;
+                    return range.left.with_integer(position);
+                }
+
+                let value = self.eval(Some(typ), expr)?;
+                let position = value.as_discrete()?;
+                let is_ascending = || {
+                    self.ranges_of(typ.into())
+                        .and_then(|ranges| ranges.first())
+                        .map(|range| range.direction == Direction::Ascending)
+                };
+                let offset = match attr.attr.item {
+                    AttributeDesignator::Pos => return Some(StaticValue::Integer(position)),
+                    AttributeDesignator::Succ => 1,
+                    AttributeDesignator::Pred => -1,
+                    AttributeDesignator::RightOf => {
+                        if is_ascending()? {
+                            1
+                        } else {
+                            -1
+                        }
+                    }
+                    _ => {
+                        if is_ascending()? {
+                            -1
+                        } else {
+                            1
+                        }
+                    }
+                };
+                value.with_integer(position.checked_add(offset)?)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn eval_unary(op: Operator, operand: StaticValue) -> Option<StaticValue> {
+    use StaticValue::*;
+    match (op, operand) {
+        (Operator::Plus, Integer(_) | Real(_) | Physical(_)) => Some(operand),
+        (Operator::Minus, Integer(value)) => Some(Integer(value.checked_neg()?)),
+        (Operator::Minus, Physical(value)) => Some(Physical(value.checked_neg()?)),
+        (Operator::Minus, Real(value)) => Some(Real(-value)),
+        (Operator::Abs, Integer(value)) => Some(Integer(value.checked_abs()?)),
+        (Operator::Abs, Physical(value)) => Some(Physical(value.checked_abs()?)),
+        (Operator::Abs, Real(value)) => Some(Real(value.abs())),
+        (Operator::Not, _) => Some(StaticValue::from_bool(!operand.as_bool()?)),
+        _ => None,
+    }
+}
+
+fn eval_binary(op: Operator, left: StaticValue, right: StaticValue) -> Option<StaticValue> {
+    use std::cmp::Ordering;
+    use StaticValue::*;
+
+    let compare = || left.compare(&right);
+    let logical = |f: fn(bool, bool) -> bool| {
+        Some(StaticValue::from_bool(f(left.as_bool()?, right.as_bool()?)))
+    };
+
+    match op {
+        Operator::EQ => Some(StaticValue::from_bool(compare()? == Ordering::Equal)),
+        Operator::NE => Some(StaticValue::from_bool(compare()? != Ordering::Equal)),
+        Operator::LT => Some(StaticValue::from_bool(compare()? == Ordering::Less)),
+        Operator::LTE => Some(StaticValue::from_bool(compare()? != Ordering::Greater)),
+        Operator::GT => Some(StaticValue::from_bool(compare()? == Ordering::Greater)),
+        Operator::GTE => Some(StaticValue::from_bool(compare()? != Ordering::Less)),
+        Operator::And => logical(|l, r| l && r),
+        Operator::Or => logical(|l, r| l || r),
+        Operator::Nand => logical(|l, r| !(l && r)),
+        Operator::Nor => logical(|l, r| !(l || r)),
+        Operator::Xor => logical(|l, r| l != r),
+        Operator::Xnor => logical(|l, r| l == r),
+        _ => match (left, right) {
+            (Integer(left), Integer(right)) => Some(Integer(eval_integer(op, left, right)?)),
+            (Physical(left), Physical(right)) => match op {
+                Operator::Plus => Some(Physical(left.checked_add(right)?)),
+                Operator::Minus => Some(Physical(left.checked_sub(right)?)),
+                Operator::Div => Some(Integer(left.checked_div(right)?)),
+                _ => None,
+            },
+            (Physical(left), Integer(right)) => match op {
+                Operator::Times => Some(Physical(left.checked_mul(right)?)),
+                Operator::Div => Some(Physical(left.checked_div(right)?)),
+                _ => None,
+            },
+            (Integer(left), Physical(right)) if op == Operator::Times => {
+                Some(Physical(left.checked_mul(right)?))
+            }
+            (Real(left), Real(right)) => match op {
+                Operator::Plus => Some(Real(left + right)),
+                Operator::Minus => Some(Real(left - right)),
+                Operator::Times => Some(Real(left * right)),
+                Operator::Div if right != 0.0 => Some(Real(left / right)),
+                _ => None,
+            },
+            (Real(left), Integer(right)) if op == Operator::Pow => {
+                Some(Real(left.powi(i32::try_from(right).ok()?)))
+            }
+            _ => None,
+        },
+    }
+}
+
+fn eval_integer(op: Operator, left: i64, right: i64) -> Option<i64> {
+    match op {
+        Operator::Plus => left.checked_add(right),
+        Operator::Minus => left.checked_sub(right),
+        Operator::Times => left.checked_mul(right),
+        Operator::Div => left.checked_div(right),
+        Operator::Rem => left.checked_rem(right),
+        Operator::Mod => left.checked_rem_euclid(right).map(|rem| {
+            // The result of mod has the sign of the right operand
+            if rem != 0 && right < 0 {
+                rem + right
+            } else {
+                rem
+            }
+        }),
+        Operator::Pow => left.checked_pow(u32::try_from(right).ok()?),
+        _ => None,
+    }
+}
+
+impl<'a> AnalyzeContext<'a> {
+    pub(crate) fn static_evaluator(&self) -> StaticEvaluator<impl Fn(EntityId) -> EntRef<'a> + '_> {
+        StaticEvaluator::new(move |id| self.arena.get(id)).with_generics(self.generics.clone())
+    }
+
+    /// Evaluate a locally static expression
+    /// Returns None if the expression cannot be evaluated
+    pub fn eval_static(&self, ttyp: Option<TypeEnt<'a>>, expr: &Expression) -> Option<StaticValue> {
+        self.static_evaluator().eval(ttyp, expr)
+    }
+
+    /// Store the static value or ranges of a declaration on its named entity
+    pub(crate) fn set_static_property(&self, ent: EntRef<'a>, property: Option<StaticProperty>) {
+        if let Some(property) = property {
+            unsafe {
+                self.arena.set_static_property(ent.id(), property);
+            }
+        }
+    }
+
+    /// The static ranges of a subtype indication with a constraint
+    pub(crate) fn subtype_indication_ranges(
+        &self,
+        subtype: &Subtype<'a>,
+        subtype_indication: &SubtypeIndication,
+    ) -> Option<Vec<StaticRange>> {
+        let constraint = subtype_indication.constraint.as_ref()?;
+        self.static_evaluator()
+            .eval_constraint(subtype.type_mark(), &constraint.item)
+    }
+
+    /// The static ranges of an object alias are the ranges of the constraint of its subtype
+    /// indication if any, otherwise the ranges of the aliased object or slice
+    pub(crate) fn object_alias_ranges(
+        &self,
+        type_mark: TypeEnt<'a>,
+        name: &Name,
+        subtype_indication: Option<&SubtypeIndication>,
+    ) -> Option<Vec<StaticRange>> {
+        let evaluator = self.static_evaluator();
+        if let Some(constraint) = subtype_indication.and_then(|subtype| subtype.constraint.as_ref())
+        {
+            return evaluator.eval_constraint(type_mark, &constraint.item);
+        }
+
+        self.name_ranges(name, None)
+    }
+
+    /// Record the range of a loop or generate parameter including its direction
+    /// when the discrete range is locally static
+    pub(crate) fn set_loop_parameter_range(
+        &self,
+        index: EntRef<'a>,
+        typ: Option<BaseType<'a>>,
+        drange: &DiscreteRange,
+    ) {
+        let range = self
+            .static_evaluator()
+            .eval_discrete_range(typ.map(TypeEnt::from), drange);
+        self.set_static_property(
+            index,
+            range.map(|range| StaticProperty::Ranges(vec![range])),
+        );
+    }
+
+    /// The static ranges of the object, alias, record element or slice denoted by a name
+    /// Other names such as indexed names get the ranges of their type if it is given
+    pub(crate) fn name_ranges(
+        &self,
+        name: &Name,
+        typ: Option<TypeEnt<'a>>,
+    ) -> Option<Vec<StaticRange>> {
+        let evaluator = self.static_evaluator();
+        match name {
+            Name::Designator(..) | Name::Selected(..) => {
+                evaluator.interface_ranges(self.arena.get(name.get_suffix_reference()?))
+            }
+            Name::Slice(_, drange) => Some(vec![evaluator.eval_discrete_range(None, drange)?]),
+            _ => evaluator
+                .ranges_of(typ?.into())
+                .map(|ranges| ranges.to_vec()),
+        }
+    }
+
+    /// Check the number of elements of a one-dimensional array aggregate against the static
+    /// index range of its target. Named choices must be within the range and cover all of it
+    /// unless there is an `others` choice. Aggregates with positional elements that are
+    /// arrays themselves are not checked.
+    pub(crate) fn check_aggregate_length(
+        &self,
+        scope: &Scope<'a>,
+        typ: TypeEnt<'a>,
+        range: &StaticRange,
+        expr: &mut WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Expression::Aggregate(ref mut assocs) = expr.item else {
+            return;
+        };
+        let Some((elem_type, indexes)) = typ.array_type() else {
+            return;
+        };
+        let (Some(length), [Some(index_typ)]) = (range.length(), indexes.as_slice()) else {
+            return;
+        };
+        let index_typ = TypeEnt::from(*index_typ);
+        let evaluator = self.static_evaluator();
+
+        let mut positional = 0;
+        let mut has_others = false;
+        // The associated index intervals of the named choices if they are all static
+        let mut associated = Some(Vec::new());
+
+        for assoc in assocs.iter_mut() {
+            match assoc {
+                ElementAssociation::Positional(ref mut elem) => {
+                    let Ok(types) = self.expr_type(scope, elem, &mut NullDiagnostics) else {
+                        return;
+                    };
+                    if !self.is_possible(&types, elem_type.base())
+                        && self.is_possible(&types, typ.base())
+                    {
+                        // The number of elements of an array element is not known
+                        return;
+                    }
+                    positional += 1;
+                }
+                ElementAssociation::Named(ref choices, _) => {
+                    for choice in choices.iter() {
+                        let (pos, choice_range) = match choice {
+                            Choice::Others => {
+                                has_others = true;
+                                continue;
+                            }
+                            Choice::Expression(choice_expr) => (
+                                choice_expr.pos.clone(),
+                                evaluator
+                                    .eval(Some(index_typ), &choice_expr.item)
+                                    .map(|value| StaticRange::new(value, value, range.direction)),
+                            ),
+                            Choice::DiscreteRange(drange) => (
+                                drange.pos(),
+                                evaluator.eval_discrete_range(Some(index_typ), drange),
+                            ),
+                        };
+
+                        let bounds = choice_range.and_then(|choice_range| {
+                            if choice_range.is_null() {
+                                return Some(None);
+                            }
+                            let (low, high) = (choice_range.low(), choice_range.high());
+                            if range.contains(&low)? && range.contains(&high)? {
+                                return Some(Some((low.as_discrete()?, high.as_discrete()?)));
+                            }
+                            let outside = if range.contains(&low)? { high } else { low };
+                            diagnostics.error(
+                                &pos,
+                                format!(
+                                    "Choice {} is outside of the index range {}",
+                                    format_static_value(index_typ, outside),
+                                    format_static_range(index_typ, range)
+                                ),
+                            );
+                            None
+                        });
+
+                        match (&mut associated, bounds) {
+                            (Some(associated), Some(Some(bounds))) => associated.push(bounds),
+                            (Some(_), Some(None)) => {}
+                            (associated, _) => *associated = None,
+                        }
+                    }
+                }
+            }
+        }
+
+        if positional > 0 {
+            if positional > length || (positional < length && !has_others) {
+                diagnostics.error(
+                    &expr.pos,
+                    format!(
+                        "Too {} elements in aggregate, got {} but the index range {} has {}",
+                        if positional > length { "many" } else { "few" },
+                        positional,
+                        format_static_range(index_typ, range),
+                        length
+                    ),
+                );
+            }
+        } else if let (Some(mut associated), false) = (associated, has_others) {
+            associated.sort_unstable();
+            let mut covered = 0;
+            let mut next = i64::MIN;
+            for (low, high) in associated {
+                let low = low.max(next);
+                if low <= high {
+                    covered += high - low + 1;
+                    next = high.saturating_add(1);
+                }
+            }
+            if covered < length {
+                diagnostics.error(
+                    &expr.pos,
+                    format!(
+                        "Too few elements in aggregate, {} of the {} elements of the index range {} are not associated",
+                        length - covered,
+                        length,
+                        format_static_range(index_typ, range)
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Check that the locally static indexes of an indexed name are within the index ranges
+    /// of the prefix, which is the object, alias or record element denoted by the prefix name
+    pub(crate) fn check_static_index(
+        &self,
+        prefix: &Name,
+        prefix_typ: TypeEnt<'a>,
+        assocs: &[AssociationElement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(prefix_ent) = prefix.get_suffix_reference().map(|id| self.arena.get(id)) else {
+            return;
+        };
+        let Some((_, indexes)) = prefix_typ.array_type() else {
+            return;
+        };
+        let evaluator = self.static_evaluator();
+        let Some(ranges) = evaluator.interface_ranges(prefix_ent) else {
+            return;
+        };
+        if ranges.len() != assocs.len() || indexes.len() != assocs.len() {
+            return;
+        }
+
+        for ((assoc, range), index_typ) in assocs.iter().zip(ranges.iter()).zip(indexes.iter()) {
+            let (None, ActualPart::Expression(ref expr), Some(index_typ)) =
+                (&assoc.formal, &assoc.actual.item, index_typ)
+            else {
+                continue;
+            };
+            let index_typ = TypeEnt::from(*index_typ);
+            let Some(value) = evaluator.eval(Some(index_typ), expr) else {
+                continue;
+            };
+            if range.contains(&value) == Some(false) {
+                diagnostics.error(
+                    &assoc.actual.pos,
+                    format!(
+                        "Index {} is outside of the index range {} of {}",
+                        format_static_value(index_typ, value),
+                        format_static_range(index_typ, range),
+                        prefix_ent.describe()
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Check that the value of a static default expression is within the range of
+    /// a scalar subtype or has the length of a constrained array subtype
+    /// Aggregates are checked by the number of their elements instead
+    pub fn check_static_default(
+        &self,
+        typ: TypeEnt<'a>,
+        ranges: Option<&[StaticRange]>,
+        expr: &WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if matches!(expr.item, Expression::Aggregate(_)) && !typ.is_scalar() {
+            return;
+        }
+        if let Some(message) =
+            static_default_error(&self.static_evaluator(), typ, ranges, &expr.item)
+        {
+            diagnostics.error(&expr.pos, message);
+        }
+    }
+
+    /// The value of a physical literal in units of the primary unit.
+    /// An error is reported when the value overflows the range of the implementation.
+    pub(crate) fn physical_literal_value(
+        &self,
+        typ: TypeEnt<'a>,
+        pos: &SrcPos,
+        literal: &PhysicalLiteral,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Option<StaticValue> {
+        // The value of the unit is not known when its declaration has an error
+        self.arena.get(literal.unit.reference?).static_value()?;
+
+        let value = self.static_evaluator().eval_physical_literal(literal);
+        if value.is_none() {
+            diagnostics.error(
+                pos,
+                format!(
+                    "Physical literal {} overflows the range of {}",
+                    literal,
+                    typ.describe()
+                ),
+            );
+        }
+        value
+    }
+
+    /// Check that the value of a physical literal is within the declared range of its type
+    pub(crate) fn check_physical_literal(
+        &self,
+        typ: TypeEnt<'a>,
+        pos: &SrcPos,
+        literal: &PhysicalLiteral,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(value) = self.physical_literal_value(typ, pos, literal, diagnostics) else {
+            return;
+        };
+        let Some(range) = self
+            .static_evaluator()
+            .ranges_of(typ.into())
+            .and_then(|ranges| ranges.first())
+        else {
+            return;
+        };
+        if range.contains(&value) == Some(false) {
+            diagnostics.error(
+                pos,
+                format!(
+                    "Physical literal {} is outside of the range {} to {} of {}",
+                    literal,
+                    format_static_value(typ, range.low()),
+                    format_static_value(typ, range.high()),
+                    typ.describe()
+                ),
+            );
+        }
+    }
+
+    /// Warn when a range with static bounds such as `7 downto 8` or `0 to -1` is null
+    /// The consequence of the null range in its context is appended to the message if any
+    pub(crate) fn check_null_range(
+        &self,
+        range: &Range,
+        consequence: Option<&str>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Range::Range(ref constraint) = range else {
+            return;
+        };
+
+        if let Some(static_range) = self.static_evaluator().eval_range(None, range) {
+            if static_range.is_null() {
+                let mut message = format!("Range {constraint} is null");
+                if let Some(consequence) = consequence {
+                    message.push_str(", ");
+                    message.push_str(consequence);
+                }
+                diagnostics.push(
+                    Diagnostic::warning(range.pos(), message).with_code(ErrorCode::NullRange),
+                );
+            }
+        }
+    }
+
+    pub(crate) fn check_null_drange(
+        &self,
+        drange: &DiscreteRange,
+        consequence: Option<&str>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        match drange {
+            DiscreteRange::Range(range) | DiscreteRange::Discrete(_, Some(range)) => {
+                self.check_null_range(range, consequence, diagnostics)
+            }
+            DiscreteRange::Discrete(_, None) => {}
+        }
+    }
+
+    /// Warn about the null ranges within the constraint of a subtype declaration.
+    /// The subtypes of object declarations are not checked since a null range such as
+    /// `unsigned(0 downto 1)` is the common way to declare an empty array.
+    pub(crate) fn check_null_constraint(
+        &self,
+        constraint: &SubtypeConstraint,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        match constraint {
+            SubtypeConstraint::Range(range) => self.check_null_range(range, None, diagnostics),
+            SubtypeConstraint::Array(dranges, element) => {
+                for drange in dranges.iter() {
+                    self.check_null_drange(drange, None, diagnostics);
+                }
+                if let Some(element) = element {
+                    self.check_null_constraint(&element.item, diagnostics);
+                }
+            }
+            SubtypeConstraint::Record(constraints) => {
+                for constraint in constraints.iter() {
+                    self.check_null_constraint(&constraint.constraint.item, diagnostics);
+                }
+            }
+        }
+    }
+
+    /// Warn when a slice of an object has another direction than the index range of the object
+    /// Null slices are allowed to have any direction
+    pub(crate) fn check_slice_direction(
+        &self,
+        prefix: ObjectEnt<'a>,
+        drange: &DiscreteRange,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let evaluator = self.static_evaluator();
+        let Some(&[prefix_range]) = evaluator.interface_ranges(prefix.ent).as_deref() else {
+            return;
+        };
+        let Some(slice_range) = evaluator.eval_discrete_range(None, drange) else {
+            return;
+        };
+
+        if !slice_range.is_null() && slice_range.direction != prefix_range.direction {
+            diagnostics.push(
+                Diagnostic::warning(
+                    drange.pos(),
+                    format!(
+                        "Slice direction '{}' does not match the direction '{}' of {}",
+                        slice_range.direction,
+                        prefix_range.direction,
+                        prefix.describe_name()
+                    ),
+                )
+                .with_code(ErrorCode::SliceDirection),
+            );
+        }
+    }
+}
+
+/// The error of a default expression that is outside of the range or does not have the
+/// length of its subtype. Ranges are the ranges of the constraint of the subtype indication if any.
+pub(crate) fn static_default_error<'a>(
+    evaluator: &StaticEvaluator<impl Fn(EntityId) -> EntRef<'a>>,
+    typ: TypeEnt<'a>,
+    ranges: Option<&[StaticRange]>,
+    expr: &Expression,
+) -> Option<String> {
+    if typ.is_scalar() {
+        let range = match ranges {
+            Some(ranges) => *ranges.first()?,
+            None => *evaluator.ranges_of(typ.into())?.first()?,
+        };
+        let value = evaluator.eval(Some(typ), expr)?;
+        if range.contains(&value)? {
+            None
+        } else {
+            Some(format!(
+                "Value {} is outside of the range {} to {} of {}",
+                format_static_value(typ, value),
+                format_static_value(typ, range.low()),
+                format_static_value(typ, range.high()),
+                typ.describe()
+            ))
+        }
+    } else {
+        let (_, indexes) = typ.array_type()?;
+        if indexes.len() != 1 {
+            return None;
+        }
+        let expected = match ranges {
+            Some(ranges) => ranges.first()?.length()?,
+            None => evaluator.ranges_of(typ.into())?.first()?.length()?,
+        };
+        let length = static_length(expr)?;
+        if length == expected {
+            None
+        } else {
+            Some(format!(
+                "Value of length {length} does not match the length {expected} of the subtype"
+            ))
+        }
+    }
+}
+
+/// The entity of a name in an expression that is known to not be locally static
+pub(crate) fn non_static_reference<'a>(arena: &'a Arena, expr: &Expression) -> Option<EntRef<'a>> {
+    match expr {
+        Expression::Name(name) => {
+            let ent = arena.get(name.get_suffix_reference()?);
+            match ent.kind() {
+                // Interface constants such as generics and parameters are at most globally static
+                AnyEntKind::Object(object)
+                    if object.class != ObjectClass::Constant || object.mode.is_some() =>
+                {
+                    Some(ent)
+                }
+                AnyEntKind::LoopParameter(..) => Some(ent),
+                _ => None,
+            }
+        }
+        Expression::Qualified(qexpr) => non_static_reference(arena, &qexpr.expr.item),
+        Expression::Unary(_, operand) => non_static_reference(arena, &operand.item),
+        Expression::Binary(_, left, right) => non_static_reference(arena, &left.item)
+            .or_else(|| non_static_reference(arena, &right.item)),
+        Expression::Aggregate(..) | Expression::Literal(..) | Expression::New(..) => None,
+    }
+}
+
+/// The number of elements of a string literal, a bit string literal or a positional aggregate
+pub(crate) fn static_length(expr: &Expression) -> Option<i64> {
+    match expr {
+        Expression::Literal(literal) => i64::try_from(string_literal_length(literal)?).ok(),
+        Expression::Aggregate(assocs) => {
+            if assocs
+                .iter()
+                .all(|assoc| matches!(assoc, ElementAssociation::Positional(..)))
+            {
+                i64::try_from(assocs.len()).ok()
+            } else {
+                None
+            }
+        }
+        Expression::Qualified(qexpr) => static_length(&qexpr.expr.item),
+        _ => None,
+    }
+}
+
+/// Format a static value of a type as it would be written in VHDL
+pub fn format_static_value(typ: TypeEnt, value: StaticValue) -> String {
+    match value {
+        StaticValue::Integer(value) => value.to_string(),
+        StaticValue::Real(value) => format!("{value:?}"),
+        StaticValue::Enum(_) | StaticValue::Physical(_) => {
+            let base = typ.base_type();
+            let literal = |value| {
+                base.implicits
+                    .iter()
+                    .find(|ent| ent.static_value() == Some(value))
+            };
+            match value {
+                StaticValue::Enum(pos) => literal(value)
+                    .map(|ent| ent.designator().to_string())
+                    .unwrap_or_else(|| pos.to_string()),
+                _ => {
+                    let unit = literal(StaticValue::Physical(1));
+                    match (value, unit) {
+                        (StaticValue::Physical(value), Some(unit)) => {
+                            format!("{} {}", value, unit.designator())
+                        }
+                        (StaticValue::Physical(value), None) => value.to_string(),
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Format a static range of a type as it would be written in VHDL
+pub(crate) fn format_static_range(typ: TypeEnt, range: &StaticRange) -> String {
+    format!(
+        "{} {} {}",
+        format_static_value(typ, range.left),
+        range.direction,
+        format_static_value(typ, range.right)
+    )
+}
+
+/// Evaluates the innermost expression containing the cursor
+pub(super) struct FindStaticValue<'a> {
+    root: &'a DesignRoot,
+    source: Source,
+    cursor: Position,
+    pub result: Option<(SrcPos, Option<StaticValue>)>,
+}
+
+impl<'a> FindStaticValue<'a> {
+    pub fn new(root: &'a DesignRoot, source: &Source, cursor: Position) -> Self {
+        FindStaticValue {
+            root,
+            source: source.clone(),
+            cursor,
+            result: None,
+        }
+    }
+}
+
+impl<'a> Searcher for FindStaticValue<'a> {
+    fn search_expression(&mut self, pos: &SrcPos, expr: &Expression) -> SearchState {
+        if pos.start() <= self.cursor && self.cursor <= pos.end() {
+            let evaluator = StaticEvaluator::new(|id| self.root.get_ent(id));
+            self.result = Some((pos.clone(), evaluator.eval(None, expr)));
+        }
+        NotFinished
+    }
+
+    fn search_source(&mut self, source: &Source) -> SearchState {
+        if source == &self.source {
+            NotFinished
+        } else {
+            Finished(NotFound)
+        }
+    }
+}
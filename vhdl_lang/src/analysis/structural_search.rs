@@ -0,0 +1,392 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Structural search and replace of expressions matching a pattern with capture variables
+
+use super::root::DesignRoot;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+
+/// An expression such as `rising_edge($clk) and $rst = '0'` where the names starting with `$`
+/// are capture variables. A capture variable matches any expression or name and all occurrences
+/// of the same variable must match the same expression.
+#[derive(Clone, Debug)]
+pub struct StructuralPattern {
+    expr: WithPos<Expression>,
+}
+
+impl StructuralPattern {
+    pub(crate) fn new(expr: WithPos<Expression>) -> StructuralPattern {
+        StructuralPattern { expr }
+    }
+
+    /// The text of a pattern where the capture variables are written as extended identifiers
+    /// such that the pattern is parsed as an ordinary expression
+    pub(crate) fn escape(pattern: &str) -> Result<String, String> {
+        let mut escaped = String::with_capacity(pattern.len());
+        let mut in_string = false;
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(chr) = chars.next() {
+            if chr == '"' {
+                in_string = !in_string;
+            }
+            if chr != '$' || in_string {
+                escaped.push(chr);
+                continue;
+            }
+
+            let name = capture_name(&mut chars);
+            if name.is_empty() {
+                return Err(format!(
+                    "Expected the name of a capture variable after '$' in '{pattern}'"
+                ));
+            }
+            escaped.push_str(&format!("\\${name}\\"));
+        }
+        Ok(escaped)
+    }
+
+    /// The names of the capture variables without the `$`
+    pub fn captures(&self) -> Vec<String> {
+        let mut finder = CaptureFinder::default();
+        let _ = self.expr.clone().search(&mut finder);
+        finder.names
+    }
+
+    /// Check that a replacement only uses the capture variables of the pattern
+    pub fn check_replacement(&self, template: &str) -> Result<(), String> {
+        let captures = self.captures();
+        let mut chars = template.chars().peekable();
+        while let Some(chr) = chars.next() {
+            if chr == '$' {
+                let name = capture_name(&mut chars);
+                if !captures
+                    .iter()
+                    .any(|capture| capture.eq_ignore_ascii_case(&name))
+                {
+                    return Err(format!(
+                        "Unknown capture variable '${name}' in the replacement '{template}'"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The name of a capture variable following a `$`
+fn capture_name(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut name = String::new();
+    while let Some(chr) = chars.next_if(|chr| chr.is_ascii_alphanumeric() || *chr == '_') {
+        name.push(chr);
+    }
+    name
+}
+
+/// The name of the capture variable without the `$` if the designator is one
+fn as_capture(designator: &Designator) -> Option<String> {
+    let Designator::Identifier(sym) = designator else {
+        return None;
+    };
+    if !sym.is_extended() {
+        return None;
+    }
+    let name = sym.name_utf8();
+    let inner = name.strip_prefix("\\$")?.strip_suffix('\\')?;
+    Some(inner.to_owned())
+}
+
+#[derive(Default)]
+struct CaptureFinder {
+    names: Vec<String>,
+}
+
+impl Searcher for CaptureFinder {
+    fn search_designator_ref(
+        &mut self,
+        _pos: &mut SrcPos,
+        designator: &mut WithRef<Designator>,
+    ) -> SearchState {
+        if let Some(name) = as_capture(&designator.item) {
+            if !self.names.contains(&name) {
+                self.names.push(name);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// An expression matching a structural pattern
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructuralMatch {
+    pub pos: SrcPos,
+    /// The position of the expression or name matched by each capture variable
+    /// in the order in which they occur in the pattern
+    pub captures: Vec<(String, SrcPos)>,
+}
+
+impl StructuralMatch {
+    /// The source text of the matched expression
+    pub fn text(&self) -> String {
+        text_of(&self.pos)
+    }
+
+    /// The fix that replaces the matched expression by a template where
+    /// each `$name` is replaced by the text matched by the capture variable
+    pub fn rewrite(&self, template: &str) -> Result<Fix, String> {
+        let mut replacement = String::with_capacity(template.len());
+        let mut chars = template.chars().peekable();
+
+        while let Some(chr) = chars.next() {
+            if chr != '$' {
+                replacement.push(chr);
+                continue;
+            }
+            let name = capture_name(&mut chars);
+            let Some((_, pos)) = self
+                .captures
+                .iter()
+                .find(|(capture, _)| capture.eq_ignore_ascii_case(&name))
+            else {
+                return Err(format!(
+                    "Unknown capture variable '${name}' in the replacement '{template}'"
+                ));
+            };
+            replacement.push_str(&text_of(pos));
+        }
+
+        Ok(Fix {
+            pos: self.pos.clone(),
+            replacement,
+        })
+    }
+}
+
+fn text_of(pos: &SrcPos) -> String {
+    pos.source.contents().text(pos.range())
+}
+
+/// What a capture variable is bound to while matching
+#[derive(Clone, Copy)]
+enum Bound<'t> {
+    Expression(&'t Expression),
+    Name(&'t Name),
+}
+
+impl<'t> Bound<'t> {
+    fn as_name(&self) -> Option<&'t Name> {
+        match *self {
+            Bound::Expression(Expression::Name(ref name)) => Some(name),
+            Bound::Expression(_) => None,
+            Bound::Name(name) => Some(name),
+        }
+    }
+}
+
+type Bindings<'t> = Vec<(String, SrcPos, Bound<'t>)>;
+
+/// Bind a capture variable, a variable that is already bound must match the same expression
+fn bind<'t>(name: String, pos: &SrcPos, bound: Bound<'t>, bindings: &mut Bindings<'t>) -> bool {
+    let Some(&(_, _, previous)) = bindings
+        .iter()
+        .find(|(capture, _, _)| capture.eq_ignore_ascii_case(&name))
+    else {
+        bindings.push((name, pos.clone(), bound));
+        return true;
+    };
+
+    match (previous.as_name(), bound.as_name()) {
+        (Some(previous), Some(name)) => match_name(previous, name, pos, &mut Vec::new()),
+        _ => match (previous, bound) {
+            (Bound::Expression(previous), Bound::Expression(expr)) => {
+                match_expr(previous, expr, pos, &mut Vec::new())
+            }
+            _ => false,
+        },
+    }
+}
+
+fn match_expr<'t>(
+    pattern: &Expression,
+    target: &'t Expression,
+    pos: &SrcPos,
+    bindings: &mut Bindings<'t>,
+) -> bool {
+    if let Expression::Name(ref name) = pattern {
+        if let Name::Designator(ref designator) = name.as_ref() {
+            if let Some(capture) = as_capture(&designator.item) {
+                return bind(capture, pos, Bound::Expression(target), bindings);
+            }
+        }
+    }
+
+    match (pattern, target) {
+        (Expression::Binary(op, left, right), Expression::Binary(top, tleft, tright)) => {
+            op.item.item == top.item.item
+                && match_expr(&left.item, &tleft.item, &tleft.pos, bindings)
+                && match_expr(&right.item, &tright.item, &tright.pos, bindings)
+        }
+        (Expression::Unary(op, expr), Expression::Unary(top, texpr)) => {
+            op.item.item == top.item.item
+                && match_expr(&expr.item, &texpr.item, &texpr.pos, bindings)
+        }
+        (Expression::Name(name), Expression::Name(tname)) => match_name(name, tname, pos, bindings),
+        (Expression::Literal(lit), Expression::Literal(tlit)) => match (lit, tlit) {
+            (Literal::Physical(phys), Literal::Physical(tphys)) => {
+                phys.value == tphys.value && phys.unit.item.item == tphys.unit.item.item
+            }
+            _ => lit == tlit,
+        },
+        // Captures are not supported within aggregates, qualified expressions and allocators
+        (Expression::Aggregate(_), Expression::Aggregate(_))
+        | (Expression::Qualified(_), Expression::Qualified(_))
+        | (Expression::New(_), Expression::New(_)) => pattern.to_string() == target.to_string(),
+        _ => false,
+    }
+}
+
+fn match_name<'t>(
+    pattern: &Name,
+    target: &'t Name,
+    pos: &SrcPos,
+    bindings: &mut Bindings<'t>,
+) -> bool {
+    if let Name::Designator(ref designator) = pattern {
+        if let Some(capture) = as_capture(&designator.item) {
+            return bind(capture, pos, Bound::Name(target), bindings);
+        }
+    }
+
+    match (pattern, target) {
+        (Name::Designator(designator), Name::Designator(tdesignator)) => {
+            designator.item == tdesignator.item
+        }
+        (Name::Selected(prefix, suffix), Name::Selected(tprefix, tsuffix)) => {
+            suffix.item.item == tsuffix.item.item
+                && match_name(&prefix.item, &tprefix.item, &tprefix.pos, bindings)
+        }
+        (Name::SelectedAll(prefix), Name::SelectedAll(tprefix)) => {
+            match_name(&prefix.item, &tprefix.item, &tprefix.pos, bindings)
+        }
+        (Name::CallOrIndexed(call), Name::CallOrIndexed(tcall)) => {
+            match_name(&call.name.item, &tcall.name.item, &tcall.name.pos, bindings)
+                && match (&call.generic_map, &tcall.generic_map) {
+                    (Some(generic_map), Some(tgeneric_map)) => {
+                        match_associations(generic_map, tgeneric_map, bindings)
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+                && match_associations(&call.parameters, &tcall.parameters, bindings)
+        }
+        (Name::Attribute(attr), Name::Attribute(tattr)) => {
+            attr.attr.item == tattr.attr.item
+                && attr.signature.is_none()
+                && tattr.signature.is_none()
+                && match_name(&attr.name.item, &tattr.name.item, &tattr.name.pos, bindings)
+                && match (&attr.expr, &tattr.expr) {
+                    (Some(expr), Some(texpr)) => {
+                        match_expr(&expr.item, &texpr.item, &texpr.pos, bindings)
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+        }
+        // Captures are not supported within slices and external names
+        (Name::Slice(..), Name::Slice(..)) | (Name::External(_), Name::External(_)) => {
+            pattern.to_string() == target.to_string()
+        }
+        _ => false,
+    }
+}
+
+fn match_associations<'t>(
+    assocs: &[AssociationElement],
+    tassocs: &'t [AssociationElement],
+    bindings: &mut Bindings<'t>,
+) -> bool {
+    assocs.len() == tassocs.len()
+        && assocs
+            .iter()
+            .zip(tassocs.iter())
+            .all(|(assoc, tassoc)| match_association(assoc, tassoc, bindings))
+}
+
+fn match_association<'t>(
+    assoc: &AssociationElement,
+    tassoc: &'t AssociationElement,
+    bindings: &mut Bindings<'t>,
+) -> bool {
+    let formals_match = match (&assoc.formal, &tassoc.formal) {
+        (Some(formal), Some(tformal)) => {
+            match_name(&formal.item, &tformal.item, &tformal.pos, bindings)
+        }
+        (None, None) => true,
+        _ => false,
+    };
+
+    formals_match
+        && match (&assoc.actual.item, &tassoc.actual.item) {
+            (ActualPart::Expression(expr), ActualPart::Expression(texpr)) => {
+                match_expr(expr, texpr, &tassoc.actual.pos, bindings)
+            }
+            (ActualPart::Open, ActualPart::Open) => true,
+            _ => false,
+        }
+}
+
+/// Finds the outermost expressions matching a pattern, the expressions within a match are not searched
+struct PatternSearcher<'p> {
+    pattern: &'p StructuralPattern,
+    matches: Vec<StructuralMatch>,
+}
+
+impl Searcher for PatternSearcher<'_> {
+    fn search_expression(&mut self, pos: &SrcPos, expr: &Expression) -> SearchState {
+        let mut bindings = Vec::new();
+        if match_expr(&self.pattern.expr.item, expr, pos, &mut bindings) {
+            self.matches.push(StructuralMatch {
+                pos: pos.clone(),
+                captures: bindings
+                    .into_iter()
+                    .map(|(name, pos, _)| (name, pos))
+                    .collect(),
+            });
+            Finished(NotFound)
+        } else {
+            NotFinished
+        }
+    }
+
+    fn search_source(&mut self, source: &Source) -> SearchState {
+        if LibraryArchive::is_archive(source.file_name()) {
+            Finished(NotFound)
+        } else {
+            NotFinished
+        }
+    }
+}
+
+impl DesignRoot {
+    /// The expressions of all design units that match a structural pattern ordered by
+    /// their file name and position. A file within several libraries is only searched once.
+    pub fn structural_search(&self, pattern: &StructuralPattern) -> Vec<StructuralMatch> {
+        let mut searcher = PatternSearcher {
+            pattern,
+            matches: Vec::new(),
+        };
+        let _ = self.search(&mut searcher);
+
+        let mut matches = searcher.matches;
+        matches.sort_by(|lhs, rhs| {
+            (lhs.pos.file_name(), lhs.pos.start()).cmp(&(rhs.pos.file_name(), rhs.pos.start()))
+        });
+        matches.dedup_by(|lhs, rhs| lhs.pos == rhs.pos);
+        matches
+    }
+}
@@ -0,0 +1,237 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Lints of constructs that synthesis tools commonly do not support.
+//! The lints are not performed within testbenches and the units of simulation libraries.
+
+use super::analyze::*;
+use crate::ast::*;
+use crate::data::*;
+
+impl<'a> AnalyzeContext<'a> {
+    /// Whether any of the synthesis lints is enabled for the units of the current library.
+    /// The std and ieee libraries are provided by the synthesis tools themselves.
+    fn is_synthesized(&self) -> bool {
+        let library_name = self.work_library_name();
+        !self.root.simulation_libraries.contains(library_name)
+            && *library_name != self.std_sym
+            && library_name.name_utf8() != "ieee"
+            && ErrorCode::ALL
+                .iter()
+                .any(|code| code.is_synthesis_restriction() && self.root.is_lint_enabled(*code))
+    }
+
+    /// Report the constructs within declarations and concurrent statements that synthesis
+    /// tools commonly do not support
+    pub fn check_synthesizable(
+        &self,
+        decl: &[Declaration],
+        statements: &[LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if !self.is_synthesized() {
+            return;
+        }
+        self.check_synthesizable_declarations(decl, diagnostics);
+        self.check_synthesizable_concurrent(statements, diagnostics);
+    }
+
+    fn check_synthesizable_declarations(
+        &self,
+        decl: &[Declaration],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for declaration in decl.iter() {
+            match declaration {
+                Declaration::Object(object) => match object.class {
+                    ObjectClass::SharedVariable => {
+                        self.push_synthesis_lint(
+                            ErrorCode::SharedVariable,
+                            &object.ident.tree.pos,
+                            format!(
+                                "Shared variable '{}' is not supported by synthesis",
+                                object.ident.tree.item
+                            ),
+                            diagnostics,
+                        );
+                    }
+                    ObjectClass::Signal => {
+                        if let Some(ref expression) = object.expression {
+                            self.push_synthesis_lint(
+                                ErrorCode::SignalInitialValue,
+                                &expression.pos,
+                                format!(
+                                    "Initial value of signal '{}' is not supported by synthesis for all devices",
+                                    object.ident.tree.item
+                                ),
+                                diagnostics,
+                            );
+                        }
+                    }
+                    ObjectClass::Constant | ObjectClass::Variable => {}
+                },
+                Declaration::File(file) => {
+                    self.push_synthesis_lint(
+                        ErrorCode::FileIo,
+                        &file.ident.tree.pos,
+                        format!(
+                            "File '{}' is not supported by synthesis",
+                            file.ident.tree.item
+                        ),
+                        diagnostics,
+                    );
+                }
+                Declaration::SubprogramBody(body) => {
+                    self.check_synthesizable_declarations(&body.declarations, diagnostics);
+                    self.check_synthesizable_sequential(&body.statements, diagnostics);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_synthesizable_concurrent(
+        &self,
+        statements: &[LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                ConcurrentStatement::Process(ref process) => {
+                    self.check_synthesizable_declarations(&process.decl, diagnostics);
+                    self.check_synthesizable_sequential(&process.statements, diagnostics);
+                }
+                ConcurrentStatement::Assignment(ref assign) => {
+                    self.check_synthesizable_rhs(&assign.rhs, diagnostics);
+                }
+                ConcurrentStatement::Block(ref block) => {
+                    self.check_synthesizable_declarations(&block.decl, diagnostics);
+                    self.check_synthesizable_concurrent(&block.statements, diagnostics);
+                }
+                ConcurrentStatement::ForGenerate(ref gen) => {
+                    self.check_synthesizable_generate_body(&gen.body, diagnostics);
+                }
+                ConcurrentStatement::IfGenerate(ref gen) => {
+                    for conditional in gen.conditionals.iter() {
+                        self.check_synthesizable_generate_body(&conditional.item, diagnostics);
+                    }
+                    if let Some(ref else_item) = gen.else_item {
+                        self.check_synthesizable_generate_body(else_item, diagnostics);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref gen) => {
+                    for alternative in gen.alternatives.iter() {
+                        self.check_synthesizable_generate_body(&alternative.item, diagnostics);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_synthesizable_generate_body(
+        &self,
+        body: &GenerateBody,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Some(ref decl) = body.decl {
+            self.check_synthesizable_declarations(decl, diagnostics);
+        }
+        self.check_synthesizable_concurrent(&body.statements, diagnostics);
+    }
+
+    fn check_synthesizable_sequential(
+        &self,
+        statements: &[LabeledSequentialStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                SequentialStatement::Wait(ref wait_stmt) => {
+                    if let Some(ref timeout) = wait_stmt.item.timeout_clause {
+                        self.push_synthesis_lint(
+                            ErrorCode::TimedWait,
+                            &timeout.pos,
+                            "Wait statement with a timeout is not supported by synthesis",
+                            diagnostics,
+                        );
+                    }
+                }
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    self.check_synthesizable_rhs(&assign.rhs, diagnostics);
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.check_synthesizable_sequential(&conditional.item, diagnostics);
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.check_synthesizable_sequential(else_item, diagnostics);
+                    }
+                }
+                SequentialStatement::Case(ref case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter() {
+                        self.check_synthesizable_sequential(&alternative.item, diagnostics);
+                    }
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    self.check_synthesizable_sequential(&loop_stmt.statements, diagnostics);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_synthesizable_rhs(
+        &self,
+        rhs: &AssignmentRightHand<Waveform>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let waveforms: Vec<&Waveform> = match rhs {
+            AssignmentRightHand::Simple(waveform) => vec![waveform],
+            AssignmentRightHand::Conditional(conditionals) => conditionals
+                .conditionals
+                .iter()
+                .map(|conditional| &conditional.item)
+                .chain(conditionals.else_item.as_ref())
+                .collect(),
+            AssignmentRightHand::Selected(selection) => selection
+                .alternatives
+                .iter()
+                .map(|alternative| &alternative.item)
+                .collect(),
+        };
+
+        for waveform in waveforms {
+            // A single element with an after clause is only delayed in simulation
+            let Waveform::Elements(ref elements) = waveform else {
+                continue;
+            };
+            if elements.len() < 2 {
+                continue;
+            }
+            if let Some(after) = elements.iter().find_map(|element| element.after.as_ref()) {
+                self.push_synthesis_lint(
+                    ErrorCode::AfterClause,
+                    &after.pos,
+                    "Waveform relies on after clauses that are ignored by synthesis",
+                    diagnostics,
+                );
+            }
+        }
+    }
+
+    fn push_synthesis_lint(
+        &self,
+        code: ErrorCode,
+        pos: &SrcPos,
+        message: impl Into<String>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.root.is_lint_enabled(code) {
+            diagnostics.push(Diagnostic::warning(pos, message).with_code(code));
+        }
+    }
+}
@@ -0,0 +1,312 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Lint of testbenches that never terminate the simulation
+
+use super::analyze::*;
+use super::named_entity::*;
+use super::process::signal_reads;
+use super::region::*;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+
+impl<'a> AnalyzeContext<'a> {
+    /// Warn when the architecture of a testbench contains no way of terminating the simulation,
+    /// that is no call to std.env.finish or std.env.stop, no assertion or report of severity failure,
+    /// no call to the VUnit test_runner_cleanup procedure and the simulation does not end by
+    /// running out of events
+    pub fn check_testbench_termination(
+        &self,
+        unit: &mut ArchitectureBody,
+        entity_region: &Region<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        if !self.is_testbench(&unit.entity_name.item.item, entity_region) {
+            return Ok(());
+        }
+
+        let env_arena = match self.lookup_in_library(
+            &self.std_sym,
+            unit.pos(),
+            &Designator::Identifier(self.root.symbol_utf8("env")),
+            &mut None,
+        ) {
+            Ok(env) => Some(env.id().arena_id()),
+            Err(err) => {
+                // Without std.env only the other termination mechanisms are available
+                let _ = err.into_non_fatal()?;
+                None
+            }
+        };
+
+        let mut searcher = FindTermination {
+            arena: self.arena,
+            env_arena,
+            standard_arena: self.root.standard_pkg_id.map(|id| id.arena_id()),
+            finish: Designator::Identifier(self.root.symbol_utf8("finish")),
+            stop: Designator::Identifier(self.root.symbol_utf8("stop")),
+            failure: Designator::Identifier(self.root.symbol_utf8("failure")),
+            cleanup: Designator::Identifier(self.root.symbol_utf8("test_runner_cleanup")),
+        };
+
+        if let NotFound = unit.search(&mut searcher) {
+            if self.ends_by_event_starvation(&mut unit.statements) {
+                return Ok(());
+            }
+            diagnostics.push(
+                Diagnostic::warning(
+                    unit.pos(),
+                    format!(
+                        "Testbench '{}' never terminates the simulation, expected a call to std.env.finish or std.env.stop, a report of severity failure or a call to test_runner_cleanup",
+                        unit.entity_name.item.item
+                    ),
+                )
+                .with_code(ErrorCode::UnterminatedTestbench),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether the simulation runs out of events, that is a process suspends forever with a wait
+    /// statement without clauses and no concurrent statement keeps scheduling events, such as a
+    /// clock that is only driven until a process assigns the signal that gates it
+    fn ends_by_event_starvation(&self, statements: &mut [LabeledConcurrentStatement]) -> bool {
+        let mut suspends = false;
+        let mut assigned = Vec::new();
+        for statement in statements.iter() {
+            if let ConcurrentStatement::Process(ref process) = statement.statement {
+                suspends |= suspends_forever(&process.statements);
+                self.assigned_signals(&process.statements, &mut assigned);
+            }
+        }
+        let gates: Vec<EntityId> = assigned.iter().map(|(signal, _)| signal.id()).collect();
+
+        suspends
+            && !statements
+                .iter_mut()
+                .any(|statement| self.runs_forever(statement, &gates))
+    }
+
+    /// Whether a concurrent statement can keep scheduling events without end,
+    /// a statement that is gated by one of the signals assigned by a process is not
+    fn runs_forever(&self, statement: &mut LabeledConcurrentStatement, gates: &[EntityId]) -> bool {
+        match statement.statement {
+            ConcurrentStatement::Process(ref process) => {
+                process.sensitivity_list.is_none()
+                    && has_timeout(&process.statements)
+                    && !suspends_forever(&process.statements)
+            }
+            ConcurrentStatement::Assignment(ref mut assign) => {
+                let Some(target) = self.target_signal(&assign.target.item) else {
+                    return false;
+                };
+                self.oscillates(target.id(), &mut assign.rhs, gates)
+            }
+            // A procedure such as a clock generator may run forever
+            ConcurrentStatement::ProcedureCall(..) => true,
+            ConcurrentStatement::Block(ref mut block) => block
+                .statements
+                .iter_mut()
+                .any(|statement| self.runs_forever(statement, gates)),
+            ConcurrentStatement::ForGenerate(ref mut gen) => gen
+                .body
+                .statements
+                .iter_mut()
+                .any(|statement| self.runs_forever(statement, gates)),
+            ConcurrentStatement::IfGenerate(ref mut gen) => gen
+                .conditionals
+                .iter_mut()
+                .map(|conditional| &mut conditional.item)
+                .chain(gen.else_item.as_mut())
+                .any(|body| {
+                    body.statements
+                        .iter_mut()
+                        .any(|statement| self.runs_forever(statement, gates))
+                }),
+            ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                gen.alternatives.iter_mut().any(|alternative| {
+                    alternative
+                        .item
+                        .statements
+                        .iter_mut()
+                        .any(|statement| self.runs_forever(statement, gates))
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether a concurrent assignment with an after clause reads its own target such as
+    /// `clk <= not clk after 5 ns`, a conditional assignment whose conditions read one of the
+    /// gates only oscillates when its else branch does
+    fn oscillates(
+        &self,
+        target: EntityId,
+        rhs: &mut AssignmentRightHand<Waveform>,
+        gates: &[EntityId],
+    ) -> bool {
+        let is_signal = |id| {
+            ObjectEnt::from_any(self.arena.get(id))
+                .is_some_and(|object| object.class() == ObjectClass::Signal)
+        };
+
+        let gated = match rhs {
+            AssignmentRightHand::Simple(_) => false,
+            AssignmentRightHand::Conditional(ref mut conditionals) => conditionals
+                .conditionals
+                .iter_mut()
+                .any(|conditional| reads_any(&mut conditional.condition, gates, &is_signal)),
+            AssignmentRightHand::Selected(ref mut selection) => {
+                reads_any(&mut selection.expression, gates, &is_signal)
+            }
+        };
+        if gated {
+            return match rhs {
+                AssignmentRightHand::Conditional(ref mut conditionals) => {
+                    conditionals.else_item.as_mut().is_some_and(|waveform| {
+                        has_after(waveform) && reads_any(waveform, &[target], &is_signal)
+                    })
+                }
+                _ => false,
+            };
+        }
+
+        let delayed = match rhs {
+            AssignmentRightHand::Simple(ref waveform) => has_after(waveform),
+            AssignmentRightHand::Conditional(ref conditionals) => conditionals
+                .conditionals
+                .iter()
+                .map(|conditional| &conditional.item)
+                .chain(conditionals.else_item.as_ref())
+                .any(has_after),
+            AssignmentRightHand::Selected(ref selection) => selection
+                .alternatives
+                .iter()
+                .any(|alternative| has_after(&alternative.item)),
+        };
+        delayed && reads_any(rhs, &[target], &is_signal)
+    }
+
+    /// An entity without ports that is either named as a testbench or has a VUnit runner_cfg generic
+    pub(super) fn is_testbench(&self, entity_name: &Symbol, entity_region: &Region<'a>) -> bool {
+        let has_ports = entity_region.immediates().any(|named| {
+            matches!(
+                named.first_kind(),
+                AnyEntKind::Object(Object {
+                    class: ObjectClass::Signal,
+                    mode: Some(_),
+                    ..
+                })
+            )
+        });
+
+        if has_ports {
+            return false;
+        }
+
+        let name = entity_name.name_utf8().to_lowercase();
+        name.starts_with("tb_")
+            || name.ends_with("_tb")
+            || entity_region
+                .lookup_immediate(&Designator::Identifier(self.root.symbol_utf8("runner_cfg")))
+                .is_some()
+    }
+}
+
+/// Whether any of the signals is read within the item
+fn reads_any(
+    item: &mut impl Search,
+    ids: &[EntityId],
+    is_signal: &dyn Fn(EntityId) -> bool,
+) -> bool {
+    signal_reads(item, is_signal)
+        .iter()
+        .any(|(id, _)| ids.contains(id))
+}
+
+fn has_after(waveform: &Waveform) -> bool {
+    match waveform {
+        Waveform::Elements(elements) => elements.iter().any(|element| element.after.is_some()),
+        Waveform::Unaffected => false,
+    }
+}
+
+/// Whether the statements contain a wait statement for some time
+fn has_timeout(statements: &[LabeledSequentialStatement]) -> bool {
+    contains_wait(statements, &|wait| wait.timeout_clause.is_some())
+}
+
+/// Whether the statements contain a wait statement without clauses that suspends forever
+fn suspends_forever(statements: &[LabeledSequentialStatement]) -> bool {
+    contains_wait(statements, &|wait| {
+        wait.sensitivity_clause.is_empty()
+            && wait.condition_clause.is_none()
+            && wait.timeout_clause.is_none()
+    })
+}
+
+fn contains_wait(
+    statements: &[LabeledSequentialStatement],
+    matches: &dyn Fn(&WaitStatement) -> bool,
+) -> bool {
+    statements
+        .iter()
+        .any(|statement| match statement.statement {
+            SequentialStatement::Wait(ref wait) => matches(&wait.item),
+            SequentialStatement::If(ref ifstmt) => {
+                ifstmt
+                    .conditionals
+                    .iter()
+                    .any(|conditional| contains_wait(&conditional.item, matches))
+                    || ifstmt
+                        .else_item
+                        .as_ref()
+                        .is_some_and(|statements| contains_wait(statements, matches))
+            }
+            SequentialStatement::Case(ref case_stmt) => case_stmt
+                .alternatives
+                .iter()
+                .any(|alternative| contains_wait(&alternative.item, matches)),
+            SequentialStatement::Loop(ref loop_stmt) => {
+                contains_wait(&loop_stmt.statements, matches)
+            }
+            _ => false,
+        })
+}
+
+struct FindTermination<'b> {
+    arena: &'b Arena,
+    env_arena: Option<ArenaId>,
+    standard_arena: Option<ArenaId>,
+    finish: Designator,
+    stop: Designator,
+    failure: Designator,
+    cleanup: Designator,
+}
+
+impl<'b> FindTermination<'b> {
+    fn is_termination(&self, ent: &AnyEnt) -> bool {
+        let designator = ent.designator();
+        let arena_id = Some(ent.id().arena_id());
+
+        ((designator == &self.finish || designator == &self.stop) && arena_id == self.env_arena)
+            || (designator == &self.failure && arena_id == self.standard_arena)
+            || designator == &self.cleanup
+    }
+}
+
+impl<'b> Searcher for FindTermination<'b> {
+    fn search_pos_with_ref(&mut self, _pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        if let Some(id) = reference {
+            if self.is_termination(self.arena.get(*id)) {
+                return Finished(Found);
+            }
+        }
+        NotFinished
+    }
+}
@@ -0,0 +1,108 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn positional_aggregate_length() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s4 : bit_vector(3 downto 0);
+  signal s2 : bit_vector(1 downto 0);
+  signal ok : bit_vector(3 downto 0) := ('0', '1', '0', '1');
+begin
+  process
+    variable v : bit_vector(0 to 2);
+  begin
+    s4 <= ('0', '1', '0', '1', '1');
+    s4 <= ('0', '1', '0');
+    s4 <= ('0', '1', others => '0');
+    s4 <= ('0', '1', '0', '1', others => '0');
+    s4 <= ('0', '1', '0', '1', '0', others => '0');
+    s4 <= (s2, s2);
+    s4(1 downto 0) <= ('0', '1', '0');
+    v := ('0', '1');
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("('0', '1', '0', '1', '1')"),
+                "Too many elements in aggregate, got 5 but the index range 3 downto 0 has 4",
+            ),
+            Diagnostic::error(
+                code.s1("s4 <= ('0', '1', '0');").s1("('0', '1', '0')"),
+                "Too few elements in aggregate, got 3 but the index range 3 downto 0 has 4",
+            ),
+            Diagnostic::error(
+                code.s1("('0', '1', '0', '1', '0', others => '0')"),
+                "Too many elements in aggregate, got 5 but the index range 3 downto 0 has 4",
+            ),
+            Diagnostic::error(
+                code.s1("s4(1 downto 0) <= ('0', '1', '0')")
+                    .s1("('0', '1', '0')"),
+                "Too many elements in aggregate, got 3 but the index range 1 downto 0 has 2",
+            ),
+            Diagnostic::error(
+                code.s1("v := ('0', '1')").s1("('0', '1')"),
+                "Too few elements in aggregate, got 2 but the index range 0 to 2 has 3",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn named_aggregate_length() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c0 : bit_vector(3 downto 0) := (3 downto 1 => '0', 0 => '1');
+  constant c1 : bit_vector(3 downto 0) := (3 | 2 => '0', 0 => '1');
+  constant c2 : bit_vector(3 downto 0) := (4 => '0', others => '1');
+  constant c3 : bit_vector(3 downto 0) := (3 => '0', others => '1');
+  constant c4 : bit_vector(3 downto 0) := (5 downto 0 => '1');
+  constant c5 : bit_vector(3 downto 0) := (others => '1');
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("(3 | 2 => '0', 0 => '1')"),
+                "Too few elements in aggregate, 1 of the 4 elements of the index range 3 downto 0 are not associated",
+            ),
+            Diagnostic::error(
+                code.s1("(4 =>").s1("4"),
+                "Choice 4 is outside of the index range 3 downto 0",
+            ),
+            Diagnostic::error(
+                code.s1("5 downto 0"),
+                "Choice 5 is outside of the index range 3 downto 0",
+            ),
+        ],
+    );
+}
@@ -5,6 +5,8 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::data::ErrorCode;
+use crate::data::VHDLStandard;
 
 #[test]
 fn overloaded_name_may_not_be_assignment_target() {
@@ -176,6 +178,8 @@ end architecture;
 #[test]
 fn objects_may_be_assignment_target() {
     let mut builder = LibraryBuilder::new();
+    // Shared variables of other than protected types are allowed by VHDL-1993
+    builder.standard(VHDLStandard::VHDL1993);
     builder.code(
         "libname",
         "
@@ -548,3 +552,105 @@ end architecture;
         ],
     );
 }
+
+#[test]
+fn conditional_expressions_have_the_target_type() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    let code = builder.in_declarative_region(
+        "
+function fun(cond : boolean) return natural is
+begin
+  return 0 when cond else false;
+end function;
+
+procedure proc(cond : boolean) is
+  variable v : natural;
+begin
+  v := 0 when cond else 'c';
+  with cond select v :=
+    1 when true,
+    true when others;
+end procedure;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("false"), "'false' does not match subtype 'NATURAL'"),
+            Diagnostic::error(
+                code.s1("'c'"),
+                "character literal does not match subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1("true when others").s1("true"),
+                "'true' does not match subtype 'NATURAL'",
+            ),
+        ],
+    );
+}
+
+const CONDITIONAL_CODE: &str = "
+function fun(cond : boolean) return natural is
+begin
+  return 0 when cond else 1;
+end function;
+
+procedure proc(cond : boolean; signal s : out natural) is
+  variable v : natural;
+begin
+  v := 0 when cond else 1;
+  with cond select s <=
+    1 when true,
+    0 when others;
+end procedure;
+";
+
+#[test]
+fn conditional_expressions_depend_on_the_standard() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL1993);
+    let code = builder.in_declarative_region(CONDITIONAL_CODE);
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("return 0 when cond").s1("cond"),
+                "Conditional return statement is not allowed before VHDL-2019",
+            )
+            .with_code(ErrorCode::LaterRevision),
+            Diagnostic::error(
+                code.s1("v := 0 when cond").s1("cond"),
+                "Conditional assignment cannot be a sequential statement before VHDL-2008",
+            )
+            .with_code(ErrorCode::LaterRevision),
+            Diagnostic::error(
+                code.s1("with cond").s1("cond"),
+                "Selected assignment cannot be a sequential statement before VHDL-2008",
+            )
+            .with_code(ErrorCode::LaterRevision),
+        ],
+    );
+
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2008);
+    let code = builder.in_declarative_region(CONDITIONAL_CODE);
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("return 0 when cond").s1("cond"),
+            "Conditional return statement is not allowed before VHDL-2019",
+        )
+        .with_code(ErrorCode::LaterRevision)],
+    );
+
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    builder.in_declarative_region(CONDITIONAL_CODE);
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
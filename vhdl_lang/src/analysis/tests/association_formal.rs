@@ -417,3 +417,363 @@ end architecture;
         .search_reference(code.source(), code.s1("inport => sig").s1("sig").start())
         .is_some())
 }
+
+#[test]
+fn formal_may_not_be_associated_twice() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent2 is
+generic (
+    width : natural := 4);
+port (
+    signal inport: in natural);
+end entity;
+
+architecture a of ent2 is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+    signal sig : natural;
+begin
+    inst: entity work.ent2
+        generic map (4, width => 8)
+        port map (
+        inport => sig,
+        inport => 0
+        );
+end architecture;
+    ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("width => 8").s1("width"),
+                "Interface constant 'width' is associated more than once",
+            )
+            .related(
+                code.s1("generic map (4").s1("4"),
+                "Previously associated here",
+            ),
+            Diagnostic::error(
+                code.s("inport", 3),
+                "Interface signal 'inport' : in is associated more than once",
+            )
+            .related(code.s("inport", 2), "Previously associated here"),
+        ],
+    );
+}
+
+#[test]
+fn parts_of_formal_may_be_associated_individually() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    a : natural;
+    b : natural;
+  end record;
+end package;
+
+use work.pkg.all;
+
+entity ent2 is
+port (
+    signal rec: in rec_t;
+    signal vec: in bit_vector(1 downto 0));
+end entity;
+
+architecture a of ent2 is
+begin
+end architecture;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+    signal sig : natural;
+begin
+    inst: entity work.ent2
+        port map (
+        rec.a => sig,
+        rec.b => sig,
+        vec(0) => '0',
+        vec(1) => '1'
+        );
+
+    bad_inst: entity work.ent2
+        port map (
+        rec => (0, 0),
+        rec.b => sig,
+        vec => \"00\"
+        );
+end architecture;
+    ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("rec.b => sig,\n        vec =>").s1("rec.b"),
+            "Interface signal 'rec' : in is associated more than once",
+        )
+        .related(
+            code.s1("rec => (0, 0)").s1("rec"),
+            "Previously associated here",
+        )],
+    );
+}
+
+#[test]
+fn checks_associations_of_configuration_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent2 is
+generic (
+    width : natural);
+port (
+    signal inport: in natural);
+end entity;
+
+architecture a of ent2 is
+begin
+end architecture;
+
+configuration cfg of ent2 is
+  for a
+  end for;
+end configuration;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+    signal sig : natural;
+begin
+    good_inst: configuration work.cfg
+        generic map (width => 8)
+        port map (inport => sig);
+
+    bad_inst: configuration work.cfg
+        port map (inport => sig, missing => sig);
+end architecture;
+    ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("work.cfg", 2),
+                "No association of interface constant 'width'",
+            )
+            .related(code.s1("width"), "Defined here"),
+            Diagnostic::error(code.s1("missing"), "No declaration of 'missing'"),
+        ],
+    );
+}
+
+#[test]
+fn checks_generic_map_of_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent2 is
+generic (
+    width : natural;
+    depth : natural := 4);
+end entity;
+
+architecture a of ent2 is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+    missing_inst: entity work.ent2
+        generic map (depth => 8);
+
+    extra_inst: entity work.ent2
+        generic map (8, 4, 2);
+
+    type_inst: entity work.ent2
+        generic map (width => true);
+end architecture;
+    ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("work.ent2", 1),
+                "No association of interface constant 'width'",
+            )
+            .related(code.s1("width"), "Defined here"),
+            Diagnostic::error(code.s1(", 2)").s1("2"), "Unexpected extra argument"),
+            Diagnostic::error(code.s1("true"), "'true' does not match subtype 'NATURAL'"),
+        ],
+    );
+}
+
+#[test]
+fn conversion_of_formal_and_actual() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+    port (
+        inport: in natural;
+        outport: out natural;
+        ioport: inout natural
+    );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity ent2 is
+end entity;
+
+architecture a of ent2 is
+    function to_nat(arg : bit) return natural is
+    begin
+        return 0;
+    end function;
+
+    function to_bit(arg : natural) return bit is
+    begin
+        return '0';
+    end function;
+
+    signal b : bit;
+    signal r : real;
+begin
+    inst: entity work.ent
+        port map (
+        inport => to_nat(b),
+        to_bit(outport) => b,
+        to_bit(ioport) => to_nat(b));
+
+    inst2: entity work.ent
+        port map (
+        inport => natural(r),
+        real(outport) => r,
+        real(ioport) => natural(r));
+end architecture;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let conversions = code.s1("to_bit(ioport) => to_nat(b)");
+    assert_eq!(
+        root.search_reference_pos(code.source(), conversions.s1("to_bit").start()),
+        Some(code.s1("to_bit").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), conversions.s1("to_nat").start()),
+        Some(code.s1("to_nat").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), conversions.s1("(b)").s1("b").start()),
+        Some(code.s1("signal b").s1("b").pos())
+    );
+}
+
+#[test]
+fn illegal_conversion_of_formal_and_actual() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+    port (
+        inport: in natural;
+        outport: out natural;
+        ioport: inout natural
+    );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity ent2 is
+end entity;
+
+architecture a of ent2 is
+    function to_nat(arg : bit) return natural is
+    begin
+        return 0;
+    end function;
+
+    function to_bit(arg : natural) return bit is
+    begin
+        return '0';
+    end function;
+
+    signal b : bit;
+begin
+    inst: entity work.ent
+        port map (
+        to_bit(inport) => b,
+        outport => to_nat(b),
+        to_bit(ioport) => to_bit(b));
+
+    inst2: entity work.ent
+        port map (
+        inport => 0,
+        bit(outport) => b,
+        open);
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("to_bit(inport)"),
+                "A formal of mode in cannot be converted",
+            ),
+            Diagnostic::error(
+                code.s1("to_nat(b)"),
+                "The actual of a formal of mode out cannot be converted",
+            ),
+            Diagnostic::error(
+                code.s1("to_bit(b)").s1("to_bit"),
+                "No function 'to_bit' converting type 'BIT' to subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1("bit(outport)"),
+                "subtype 'NATURAL' cannot be converted to type 'BIT'",
+            ),
+        ],
+    );
+}
@@ -0,0 +1,113 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn all_applies_to_every_entity_of_class() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+attribute keep : boolean;
+signal sig0, sig1 : bit;
+constant const0 : bit := '0';
+attribute keep of all : signal is true;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let keep = root
+        .search_reference(code.source(), code.s1("keep").start())
+        .unwrap();
+
+    for name in ["sig0", "sig1"] {
+        let ent = root
+            .search_reference(code.source(), code.s1(name).start())
+            .unwrap();
+        assert_eq!(ent.attribute_value(keep), Some(&code.s1("true").pos()));
+    }
+
+    let ent = root
+        .search_reference(code.source(), code.s1("const0").start())
+        .unwrap();
+    assert_eq!(ent.attribute_value(keep), None);
+}
+
+#[test]
+fn others_applies_to_entities_without_value() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+attribute weight : natural;
+function f0 return natural;
+function f1 return natural;
+procedure p0;
+attribute weight of f0[return natural] : function is 1;
+attribute weight of others : function is 2;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let weight = root
+        .search_reference(code.source(), code.s1("weight").start())
+        .unwrap();
+
+    let f0 = root
+        .search_reference(code.source(), code.s1("f0").start())
+        .unwrap();
+    assert_eq!(
+        f0.attribute_value(weight),
+        Some(&code.s1("is 1").s1("1").pos())
+    );
+
+    let f1 = root
+        .search_reference(code.source(), code.s1("f1").start())
+        .unwrap();
+    assert_eq!(
+        f1.attribute_value(weight),
+        Some(&code.s1("is 2").s1("2").pos())
+    );
+
+    let p0 = root
+        .search_reference(code.source(), code.s1("p0").start())
+        .unwrap();
+    assert_eq!(p0.attribute_value(weight), None);
+}
+
+#[test]
+fn error_on_duplicate_attribute_value() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+attribute keep : boolean;
+signal sig0, sig1 : bit;
+attribute keep of sig0 : signal is true;
+attribute keep of all : signal is false;
+attribute keep of sig1 : signal is true;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("false"),
+                "Duplicate specification of attribute 'keep' for signal 'sig0'",
+            )
+            .related(code.s1("true"), "Previously specified here"),
+            Diagnostic::error(
+                code.s1("keep of sig1").s1("sig1"),
+                "Duplicate specification of attribute 'keep' for signal 'sig1'",
+            )
+            .related(code.s1("false"), "Previously specified here"),
+        ],
+    );
+}
@@ -0,0 +1,152 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::ClockDomainCrossingReport;
+
+fn crossings(report: &ClockDomainCrossingReport) -> Vec<(&str, &str, &str, &str, &str)> {
+    report
+        .crossings
+        .iter()
+        .map(|crossing| {
+            (
+                crossing.architecture.as_str(),
+                crossing.signal.as_str(),
+                crossing.source_clock.as_str(),
+                crossing.register.as_str(),
+                crossing.destination_clock.as_str(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn reports_register_reading_register_of_other_clock() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk_a, clk_b : in bit);
+end entity;
+
+architecture rtl of ent is
+  signal a, b : bit;
+begin
+  process (clk_a) begin
+    if rising_edge(clk_a) then
+      a <= not a;
+    end if;
+  end process;
+
+  process (clk_b) begin
+    if rising_edge(clk_b) then
+      b <= a;
+    end if;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let report = root.clock_domain_crossings();
+    assert_eq!(
+        crossings(&report),
+        vec![("libname.ent(rtl)", "a", "clk_a", "b", "clk_b")]
+    );
+    assert_eq!(report.crossings[0].pos, code.s1("b <= a").s1("a").pos());
+
+    let csv = report.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("architecture,signal,source clock,register,destination clock")
+    );
+    assert_eq!(lines.next(), Some("libname.ent(rtl),a,clk_a,b,clk_b"));
+}
+
+#[test]
+fn two_register_synchronizer_is_not_a_crossing() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk_a, clk_b, rst : in bit);
+end entity;
+
+architecture rtl of ent is
+  signal a, meta, sync, b : bit;
+begin
+  process (clk_a) begin
+    if rising_edge(clk_a) then
+      a <= not a;
+    end if;
+  end process;
+
+  process (clk_b, rst) begin
+    if rst = '1' then
+      meta <= '0';
+      sync <= '0';
+    elsif rising_edge(clk_b) then
+      meta <= a;
+      sync <= meta;
+    end if;
+  end process;
+
+  process (clk_b) begin
+    if rising_edge(clk_b) then
+      b <= sync and meta;
+    end if;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(crossings(&root.clock_domain_crossings()), vec![]);
+}
+
+#[test]
+fn reports_crossing_through_combinational_logic() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk_a, clk_b, en : in bit);
+end entity;
+
+architecture rtl of ent is
+  signal a, gated, meta, sync : bit;
+begin
+  process (clk_a) begin
+    if rising_edge(clk_a) then
+      a <= not a;
+    end if;
+  end process;
+
+  gated <= a and en;
+
+  process (clk_b) begin
+    if rising_edge(clk_b) then
+      meta <= gated;
+      sync <= meta;
+    end if;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        crossings(&root.clock_domain_crossings()),
+        vec![("libname.ent(rtl)", "gated", "clk_a", "meta", "clk_b")]
+    );
+}
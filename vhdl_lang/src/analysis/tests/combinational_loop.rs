@@ -0,0 +1,114 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+
+const SIGNALS: &str = "
+  signal clk, req, busy, grant, locked, sel, x, y, z, q : bit;
+  signal v : bit_vector(0 to 1);
+";
+
+#[test]
+fn concurrent_assignments_form_combinational_loop() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::CombinationalLoop);
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  grant <= req and not busy;
+  busy <= grant or locked;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::warning(
+            code.s1("grant <=").s1("grant"),
+            "Signal 'grant' depends on itself through a combinational loop",
+        )
+        .related(code.s1("busy <=").s1("busy"), "'busy' depends on 'grant'")
+        .with_code(ErrorCode::CombinationalLoop)],
+    );
+}
+
+#[test]
+fn combinational_process_conditions_form_combinational_loop() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::CombinationalLoop);
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (all)
+  begin
+    x <= '0';
+    if z = '1' then
+      x <= sel;
+    end if;
+  end process;
+
+  y <= not x;
+  z <= y when sel = '1' else '0';
+  q <= q;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::warning(
+                code.s1("x <= sel").s1("x"),
+                "Signal 'x' depends on itself through a combinational loop",
+            )
+            .related(code.s1("y <=").s1("y"), "'y' depends on 'x'")
+            .related(code.s1("z <=").s1("z"), "'z' depends on 'y'")
+            .with_code(ErrorCode::CombinationalLoop),
+            Diagnostic::warning(
+                code.s1("q <= q").s1("q"),
+                "Signal 'q' depends on itself through a combinational loop",
+            )
+            .with_code(ErrorCode::CombinationalLoop),
+        ],
+    );
+}
+
+#[test]
+fn registers_and_delays_break_combinational_loops() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::CombinationalLoop);
+    builder.in_architecture(
+        SIGNALS,
+        "
+  clk <= not clk after 5 ns;
+
+  process (clk)
+  begin
+    if rising_edge(clk) then
+      busy <= grant;
+    end if;
+  end process;
+  grant <= req and not busy;
+
+  v(1) <= v(0);
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn combinational_loops_are_opt_in() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_architecture(
+        SIGNALS,
+        "
+  grant <= req and not busy;
+  busy <= grant or locked;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
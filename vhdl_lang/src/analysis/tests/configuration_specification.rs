@@ -0,0 +1,285 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+const ENT: &str = "
+entity ent is
+  generic (
+    width : natural := 8
+  );
+  port (
+    clk : in bit;
+    q : out bit_vector(width-1 downto 0)
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
+";
+
+#[test]
+fn good_configuration_specification() {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", ENT);
+    builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    generic (
+      width : natural
+    );
+    port (
+      clk : in bit;
+      q : out bit_vector(width-1 downto 0)
+    );
+  end component;
+
+  component comp2 is
+    port (
+      clock : in bit
+    );
+  end component;
+
+  for all : comp use entity work.ent(rtl);
+  for others : comp use entity work.ent;
+  for inst2 : comp2 use entity work.ent(rtl)
+    generic map (width => 4)
+    port map (clk => clock, q => open);
+  for inst3 : comp2 use open;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn component_name_must_be_a_component() {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", ENT);
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  signal sig : bit;
+  for all : missing use entity work.ent;
+  for all : sig use entity work.ent;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("missing"), "No declaration of 'missing'"),
+            Diagnostic::error(
+                code.s1("all : sig").s1("sig"),
+                "Expected component, got signal 'sig'",
+            )
+            .related(code.s1("sig : bit").s1("sig"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn bound_entity_and_architecture_must_exist() {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", ENT);
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component ent is
+  end component;
+
+  constant c0 : natural := 0;
+
+  for inst1 : ent use entity work.missing;
+  for inst2 : ent use entity work.ent(bad);
+  for inst3 : ent use entity c0;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("missing"),
+                "No primary unit 'missing' within library 'libname'",
+            ),
+            Diagnostic::error(code.s1("bad"), "No architecture 'bad' for entity 'ent'"),
+            Diagnostic::error(
+                code.s1("entity c0").s1("c0"),
+                "Expected entity, got constant 'c0'",
+            )
+            .related(code.s1("c0"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn default_binding_checks_types_and_modes() {
+    let mut builder = LibraryBuilder::new();
+    let ent = builder.code("libname", ENT);
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    generic (
+      width : boolean
+    );
+    port (
+      clk : out bit;
+      q : out bit_vector(7 downto 0)
+    );
+  end component;
+
+  for all : comp use entity work.ent;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("work.ent"),
+                "Interface constant 'width' of type BOOLEAN does not match interface constant 'width' of type NATURAL",
+            )
+            .related(ent.s1("width"), "Defined here"),
+            Diagnostic::error(
+                code.s1("work.ent"),
+                "Interface signal 'clk' : out cannot be associated with interface signal 'clk' : in",
+            )
+            .related(ent.s1("clk"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn default_binding_requires_matching_names() {
+    let mut builder = LibraryBuilder::new();
+    let ent = builder.code("libname", ENT);
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    port (
+      clock : in bit
+    );
+  end component;
+
+  for all : comp use entity work.ent;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("work.ent"),
+                "No formal matching interface signal 'clock' : in in default binding",
+            )
+            .related(code.s1("clock"), "Defined here"),
+            Diagnostic::error(
+                code.s1("work.ent"),
+                "No association of interface signal 'clk' : in",
+            )
+            .related(ent.s1("clk"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn explicit_port_map_is_checked_against_entity() {
+    let mut builder = LibraryBuilder::new();
+    let ent = builder.code("libname", ENT);
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    port (
+      clock : in bit
+    );
+  end component;
+
+  for inst1 : comp use entity work.ent
+    port map (clk => missing);
+  for inst2 : comp use entity work.ent
+    port map (bad => clock);
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("missing"), "No declaration of 'missing'"),
+            Diagnostic::error(code.s1("bad"), "No declaration of 'bad'"),
+            Diagnostic::error(
+                code.s("work.ent", 2),
+                "No association of interface signal 'clk' : in",
+            )
+            .related(ent.s1("clk"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn search_component_in_configuration_specification() {
+    check_search_reference(
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component decl is
+  end component;
+
+  for all : decl use open;
+begin
+end architecture;
+",
+    );
+}
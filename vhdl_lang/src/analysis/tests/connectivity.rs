@@ -0,0 +1,322 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{Connection, ConnectivityReport};
+
+const CHILD: &str = "
+entity child is
+  port (
+    clk : in bit;
+    data : in bit_vector(3 downto 0);
+    en : in bit;
+    q : out bit;
+    spare : out bit;
+    flag : in boolean
+  );
+end entity;
+
+architecture rtl of child is
+begin
+end architecture;
+";
+
+fn connectivity(code: &str) -> ConnectivityReport {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", CHILD);
+    builder.code("libname", code);
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    root.connectivity("libname", "top", "a").unwrap()
+}
+
+fn rows(report: &ConnectivityReport) -> Vec<(&str, &str, &str, Connection)> {
+    report
+        .connections
+        .iter()
+        .map(|conn| {
+            (
+                conn.instance.as_str(),
+                conn.port.as_str(),
+                conn.actual.as_str(),
+                conn.connection,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn reports_connections_of_instance_ports() {
+    let report = connectivity(
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, q : bit;
+  signal bus8 : bit_vector(7 downto 0);
+  constant enabled : bit := '1';
+begin
+  inst: entity work.child
+    port map (
+      clk => clk,
+      data => bus8(3 downto 0),
+      en => enabled,
+      q => q,
+      flag => bus8(0) = '1'
+    );
+end architecture;
+",
+    );
+
+    assert_eq!(report.architecture, "libname.top(a)");
+    assert_eq!(
+        rows(&report),
+        vec![
+            ("inst", "clk", "clk", Connection::Signal),
+            ("inst", "data", "bus8(3 downto 0)", Connection::Adapted),
+            ("inst", "en", "enabled", Connection::Constant),
+            ("inst", "q", "q", Connection::Signal),
+            ("inst", "spare", "", Connection::Open),
+            ("inst", "flag", "bus8(0) = '1'", Connection::Expression),
+        ]
+    );
+    assert!(report
+        .connections
+        .iter()
+        .all(|conn| conn.unit == "entity work.child"));
+}
+
+#[test]
+fn reports_positional_and_open_associations() {
+    let report = connectivity(
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, q : bit;
+  signal data : bit_vector(3 downto 0);
+begin
+  inst: entity work.child(rtl)
+    port map (clk, data, '0', q, open, true);
+end architecture;
+",
+    );
+
+    assert_eq!(
+        rows(&report),
+        vec![
+            ("inst", "clk", "clk", Connection::Signal),
+            ("inst", "data", "data", Connection::Signal),
+            ("inst", "en", "'0'", Connection::Constant),
+            ("inst", "q", "q", Connection::Signal),
+            ("inst", "spare", "open", Connection::Open),
+            ("inst", "flag", "true", Connection::Constant),
+        ]
+    );
+    assert_eq!(report.connections[0].unit, "entity work.child(rtl)");
+    assert_eq!(report.connections[0].mode, "in");
+}
+
+#[test]
+fn reports_instances_within_generate_and_block() {
+    let report = connectivity(
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : bit;
+  signal qs : bit_vector(0 to 1);
+begin
+  gen: for i in 0 to 1 generate
+    inst: entity work.child
+      port map (clk => clk, data => \"0000\", en => '1', q => qs(i), flag => false);
+  end generate;
+
+  blk: block
+  begin
+    inst: entity work.child
+      port map (clk => clk, data => \"0000\", en => '1', q => open, flag => false);
+  end block;
+end architecture;
+",
+    );
+
+    let instances: Vec<_> = report
+        .connections
+        .iter()
+        .filter(|conn| conn.port == "q")
+        .map(|conn| (conn.instance.as_str(), conn.connection))
+        .collect();
+    assert_eq!(
+        instances,
+        vec![
+            ("gen.inst", Connection::Adapted),
+            ("blk.inst", Connection::Open)
+        ]
+    );
+}
+
+#[test]
+fn reports_component_instances() {
+    let report = connectivity(
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    port (
+      a : in bit_vector(7 downto 0);
+      b : out bit
+    );
+  end component;
+
+  type word_t is array (7 downto 0) of bit;
+  signal data : word_t;
+  signal b : bit;
+begin
+  inst: comp
+    port map (a => bit_vector(data), b => b);
+end architecture;
+",
+    );
+
+    assert_eq!(
+        rows(&report),
+        vec![
+            ("inst", "a", "bit_vector(data)", Connection::Adapted),
+            ("inst", "b", "b", Connection::Signal),
+        ]
+    );
+    assert_eq!(report.connections[0].unit, "component comp");
+}
+
+#[test]
+fn error_on_missing_architecture() {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", CHILD);
+    let (root, _) = builder.get_analyzed_root();
+    assert_eq!(
+        root.connectivity("libname", "child", "missing")
+            .unwrap_err(),
+        "No such architecture 'missing' of entity 'libname.child'"
+    );
+}
+
+#[test]
+fn formats_report_as_csv_and_html() {
+    let report = connectivity(
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, q : bit;
+  signal data : bit_vector(3 downto 0);
+begin
+  inst: entity work.child
+    port map (clk => clk, data => data, en => '1', q => q, spare => open, flag => false);
+end architecture;
+",
+    );
+
+    let csv = report.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("instance,unit,port,mode,actual,connection")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("inst,entity work.child,clk,in,clk,signal")
+    );
+    assert_eq!(
+        lines.nth(1),
+        Some("inst,entity work.child,en,in,'1',constant")
+    );
+
+    let html = report.to_html();
+    assert!(html.contains("<tr class=\"constant\">"));
+    assert!(html.contains("<tr class=\"open\">"));
+    assert!(html.contains("libname.top(a)"));
+}
+
+#[test]
+fn reports_ports_unused_at_every_instance() {
+    let mut builder = LibraryBuilder::new();
+    let child = builder.code("libname", CHILD);
+    builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component child is
+    port (
+      clk : in bit;
+      data : in bit_vector(3 downto 0);
+      en : in bit;
+      q : out bit;
+      flag : in boolean
+    );
+  end component;
+
+  signal clk, q0, q1 : bit;
+  signal flag : boolean;
+begin
+  inst0: entity work.child
+    port map (clk => clk, data => \"0000\", en => '1', q => q0, flag => false);
+
+  gen: if true generate
+    inst1: child
+      port map (clk => clk, data => \"1111\", en => open, q => q1, flag => flag);
+  end generate;
+end architecture;
+
+entity unused is
+  port (a : in bit);
+end entity;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let report = root.unused_ports();
+    let ports: Vec<_> = report
+        .ports
+        .iter()
+        .map(|port| {
+            (
+                port.entity.as_str(),
+                port.port.as_str(),
+                port.mode.as_str(),
+                port.instances,
+                port.open,
+            )
+        })
+        .collect();
+    assert_eq!(
+        ports,
+        vec![
+            ("libname.child", "data", "in", 2, 0),
+            ("libname.child", "en", "in", 2, 1),
+            ("libname.child", "spare", "out", 2, 2),
+        ]
+    );
+    assert_eq!(report.ports[2].decl_pos, Some(child.s1("spare").pos()));
+
+    let csv = report.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("entity,port,mode,instances,open,constant")
+    );
+    assert_eq!(lines.next(), Some("libname.child,data,in,2,0,2"));
+}
@@ -0,0 +1,146 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{DataflowNodeKind, ProcessDataflow};
+
+fn nodes(dataflow: &ProcessDataflow) -> Vec<(&str, DataflowNodeKind)> {
+    dataflow
+        .nodes
+        .iter()
+        .map(|node| (node.name.as_str(), node.kind))
+        .collect()
+}
+
+fn edges(dataflow: &ProcessDataflow) -> Vec<(&str, &str, bool)> {
+    dataflow
+        .edges
+        .iter()
+        .map(|edge| {
+            (
+                dataflow.nodes[edge.from].name.as_str(),
+                dataflow.nodes[edge.to].name.as_str(),
+                edge.control,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn dataflow_from_inputs_through_variables_to_outputs() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (a, b, sel : in bit; y, z : out bit);
+end entity;
+
+architecture rtl of ent is
+begin
+  comb : process (all)
+    variable v : bit;
+  begin
+    z <= '0';
+    v := a and b;
+    if sel = '1' then
+      y <= v;
+      z <= a;
+    else
+      y <= not v;
+    end if;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let dataflow = root
+        .process_dataflow(code.source(), code.s1("v := a and b").start())
+        .unwrap();
+    assert_eq!(dataflow.architecture, "libname.ent(rtl)");
+    assert_eq!(dataflow.label.as_deref(), Some("comb"));
+    assert_eq!(
+        nodes(&dataflow),
+        vec![
+            ("z", DataflowNodeKind::Output),
+            ("a", DataflowNodeKind::Input),
+            ("v", DataflowNodeKind::Variable),
+            ("b", DataflowNodeKind::Input),
+            ("y", DataflowNodeKind::Output),
+            ("sel", DataflowNodeKind::Input),
+        ]
+    );
+    assert_eq!(
+        edges(&dataflow),
+        vec![
+            ("a", "v", false),
+            ("b", "v", false),
+            ("v", "y", false),
+            ("sel", "y", true),
+            ("a", "z", false),
+            ("sel", "z", true),
+        ]
+    );
+    assert_eq!(dataflow.edges[0].pos, code.s1("v := a and b").s1("a").pos());
+}
+
+#[test]
+fn selects_process_at_cursor() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (a, b : in bit; x, y : out bit);
+end entity;
+
+architecture rtl of ent is
+begin
+  first : process (a) begin
+    x <= a;
+  end process;
+
+  blk : block is
+  begin
+    process (b) begin
+      y <= b;
+    end process;
+  end block;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let dataflow = root
+        .process_dataflow(code.source(), code.s1("first").start())
+        .unwrap();
+    assert_eq!(edges(&dataflow), vec![("a", "x", false)]);
+
+    let dataflow = root
+        .process_dataflow(code.source(), code.s1("y <= b").start())
+        .unwrap();
+    assert_eq!(dataflow.label, None);
+    assert_eq!(edges(&dataflow), vec![("b", "y", false)]);
+    assert_eq!(
+        dataflow.to_dot(),
+        "\
+digraph \"libname.ent(rtl)\" {
+  rankdir=LR;
+  n0 [label=\"b\", shape=box];
+  n1 [label=\"y\", shape=box, style=bold];
+  n0 -> n1;
+}
+"
+    );
+
+    assert_eq!(
+        root.process_dataflow(code.source(), code.s1("blk : block").start()),
+        None
+    );
+}
@@ -0,0 +1,298 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::ArchitectureSelection;
+use crate::data::ErrorCode;
+
+#[test]
+fn component_instance_references_default_bound_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    clk : in bit
+  );
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component ent is
+    port (
+      clk : in bit
+    );
+  end component;
+  signal clk : bit;
+begin
+  inst : ent port map (clk => clk);
+
+  blk : block
+  begin
+    gen : if true generate
+      inst2 : component ent port map (clk => clk);
+    end generate;
+  end block;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.find_all_references_pos(&code.s1("ent is").s1("ent").pos()),
+        vec![
+            code.s1("ent is").s1("ent").pos(),
+            code.s1("inst : ent").s1("ent").pos(),
+            code.s1("component ent port").s("ent", 2).pos(),
+        ]
+    );
+
+    // The component name still refers to the component declaration
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("inst : ent").s1("ent").start()),
+        Some(code.s1("component ent is").s("ent", 2).pos())
+    );
+}
+
+#[test]
+fn default_binding_checks_ports() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    clk : in bit
+  );
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component ent is
+    port (
+      clk : in boolean
+    );
+  end component;
+  signal clk : boolean;
+begin
+  inst : ent port map (clk => clk);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("inst : ent").s1("ent"),
+            "Interface signal 'clk' : in of type BOOLEAN does not match interface signal 'clk' : in of type BIT",
+        )
+        .related(code.s1("clk : in bit").s1("clk"), "Defined here")],
+    );
+}
+
+#[test]
+fn no_default_binding_for_explicitly_bound_instance() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    clk : in bit
+  );
+end entity;
+
+entity ent2 is
+  port (
+    clk : in boolean
+  );
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component ent is
+    port (
+      clk : in boolean
+    );
+  end component;
+  signal clk : boolean;
+
+  for inst : ent use entity work.ent2;
+begin
+  inst : ent port map (clk => clk);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn component_without_entity_is_unbound() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component vendor_primitive is
+    port (
+      clk : in bit
+    );
+  end component;
+  signal clk : bit;
+begin
+  inst : vendor_primitive port map (clk => clk);
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(root.find_all_unresolved().1, vec![]);
+}
+
+fn multiple_architectures(builder: &mut LibraryBuilder) -> Code {
+    builder.code(
+        "libname",
+        "
+entity leaf is
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+
+architecture sim of leaf is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component leaf is
+  end component;
+begin
+  inst0 : entity work.leaf;
+  inst1 : entity work.leaf(rtl);
+  inst2 : component leaf;
+end architecture;
+",
+    )
+}
+
+#[test]
+fn default_binding_of_entity_with_several_architectures() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::AmbiguousDefaultBinding);
+    let code = multiple_architectures(&mut builder);
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("work.leaf;").s1("work.leaf"),
+                "Entity 'leaf' has 2 architectures, the default binding selects 'sim'",
+            )
+            .related(code.s1("sim"), "Selected architecture 'sim'")
+            .related(code.s1("rtl"), "Other architecture 'rtl'")
+            .with_code(ErrorCode::AmbiguousDefaultBinding),
+            Diagnostic::warning(
+                code.s1("inst2 : component leaf").s1("leaf"),
+                "Entity 'leaf' has 2 architectures, the default binding selects 'sim'",
+            )
+            .related(code.s1("sim"), "Selected architecture 'sim'")
+            .related(code.s1("rtl"), "Other architecture 'rtl'")
+            .with_code(ErrorCode::AmbiguousDefaultBinding),
+        ],
+    );
+}
+
+#[test]
+fn default_binding_selects_architecture_alphabetically() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::AmbiguousDefaultBinding);
+    builder.architecture_selection(ArchitectureSelection::Alphabetical);
+    let code = multiple_architectures(&mut builder);
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("work.leaf;").s1("work.leaf"),
+                "Entity 'leaf' has 2 architectures, the default binding selects 'rtl'",
+            )
+            .related(code.s1("rtl"), "Selected architecture 'rtl'")
+            .related(code.s1("sim"), "Other architecture 'sim'")
+            .with_code(ErrorCode::AmbiguousDefaultBinding),
+            Diagnostic::warning(
+                code.s1("inst2 : component leaf").s1("leaf"),
+                "Entity 'leaf' has 2 architectures, the default binding selects 'rtl'",
+            )
+            .related(code.s1("rtl"), "Selected architecture 'rtl'")
+            .related(code.s1("sim"), "Other architecture 'sim'")
+            .with_code(ErrorCode::AmbiguousDefaultBinding),
+        ],
+    );
+}
+
+#[test]
+fn configuration_pins_architecture_of_component_instance() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::AmbiguousDefaultBinding);
+    let code = multiple_architectures(&mut builder);
+    builder.code(
+        "libname",
+        "
+configuration cfg of top is
+  for a
+    for inst2 : leaf
+      use entity work.leaf(rtl);
+    end for;
+  end for;
+end configuration;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("work.leaf;").s1("work.leaf"),
+            "Entity 'leaf' has 2 architectures, the default binding selects 'sim'",
+        )
+        .related(code.s1("sim"), "Selected architecture 'sim'")
+        .related(code.s1("rtl"), "Other architecture 'rtl'")
+        .with_code(ErrorCode::AmbiguousDefaultBinding)],
+    );
+}
+
+#[test]
+fn default_binding_is_not_checked_in_simulation_libraries() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::AmbiguousDefaultBinding);
+    builder.simulation_library("libname");
+    multiple_architectures(&mut builder);
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
@@ -150,3 +150,36 @@ end package body;
     let diagnostics = builder.analyze();
     check_diagnostics(diagnostics, vec![duplicate(&code, "a1", 2, 3)]);
 }
+
+#[test]
+fn error_on_full_constant_declaration_with_different_subtype() {
+    let mut builder = LibraryBuilder::new();
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+constant a1 : natural;
+constant b1 : integer;
+end package;
+",
+    );
+    let body = builder.code(
+        "libname",
+        "
+package body pkg is
+constant a1 : natural := 0;
+constant b1 : natural := 0;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            body.s1("b1"),
+            "Full declaration of deferred constant 'b1' has subtype 'NATURAL' which does not match integer type 'INTEGER' of the deferred constant",
+        )
+        .related(pkg.s1("b1"), "Deferred constant declared here")],
+    );
+}
@@ -0,0 +1,213 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::ResolutionExplanation;
+
+fn explain(root: &DesignRoot, code: &Code) -> ResolutionExplanation {
+    root.explain_resolution(code.source(), code.start())
+        .unwrap()
+}
+
+#[test]
+fn explains_rejected_overloads() {
+    let mut builder = LibraryBuilder::new();
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+  function fun(arg : natural) return natural;
+  function fun(arg : boolean) return natural;
+  function fun(arg : natural) return boolean;
+  function fun(a, b : natural) return natural;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := fun(0);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let explanation = explain(&root, &code.s1("fun"));
+    assert_eq!(explanation.designator.to_string(), "fun");
+    assert_eq!(explanation.use_clauses.len(), 4);
+    assert_eq!(
+        explanation.use_clauses[0].visible_pos,
+        vec![code.s1("work.pkg.all").pos()]
+    );
+
+    let rejections: Vec<_> = explanation
+        .overloads
+        .iter()
+        .map(|candidate| candidate.rejection.as_deref())
+        .collect();
+    assert_eq!(
+        rejections,
+        vec![
+            None,
+            Some("Actual 1 does not match type 'BOOLEAN', got expression with type universal_integer"),
+            Some("Return type 'BOOLEAN' does not match subtype 'NATURAL'"),
+            Some("No association of interface constant 'b'"),
+        ]
+    );
+    assert_eq!(
+        explanation.resolved.unwrap().decl_pos,
+        Some(pkg.s1("fun(arg").s1("fun").pos())
+    );
+}
+
+#[test]
+fn explains_operator_resolution() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type my_int is range 0 to 10;
+  function \"+\"(a : my_int) return my_int;
+
+  constant c : my_int := 1 + 2;
+begin
+end architecture;
+",
+    );
+
+    let (root, _) = builder.get_analyzed_root();
+    let explanation = explain(&root, &code.s1("+ 2").s1("+"));
+
+    let candidate = |description: &str| {
+        explanation
+            .overloads
+            .iter()
+            .find(|candidate| candidate.entity.description.starts_with(description))
+            .unwrap()
+    };
+    assert_eq!(
+        candidate("\"+\"[my_int return my_int]")
+            .rejection
+            .as_deref(),
+        Some("Does not take 2 operand(s)")
+    );
+    assert_eq!(
+        candidate("\"+\"[INTEGER, INTEGER return INTEGER]")
+            .rejection
+            .as_deref(),
+        Some("Return integer type 'INTEGER' does not match integer type 'my_int'")
+    );
+    assert_eq!(
+        explanation.resolved.unwrap().description,
+        "\"+\"[my_int, my_int return my_int]"
+    );
+}
+
+#[test]
+fn explains_searched_scopes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := 0;
+begin
+  process
+    constant c : natural := 1;
+    variable v : natural;
+  begin
+    v := c;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let explanation = explain(&root, &code.s1("v := c").s1("c"));
+    // The search stops at the constant declared in the process
+    assert_eq!(explanation.scopes.len(), 1);
+    assert_eq!(
+        explanation.scopes[0][0].decl_pos,
+        Some(code.s1("constant c : natural := 1").s("c", 2).pos())
+    );
+    assert!(explanation.use_clauses.is_empty());
+    assert_eq!(
+        explanation.resolved.unwrap().decl_pos,
+        Some(code.s1("constant c : natural := 1").s("c", 2).pos())
+    );
+
+    let explanation = explain(&root, &code.s1("v := c").s1("v"));
+    assert_eq!(explanation.scopes.len(), 1);
+    assert!(explanation.to_string().starts_with("Resolution of 'v'"));
+}
+
+#[test]
+fn explains_conflicting_use_clauses() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg1 is
+  constant c : natural := 0;
+end package;
+
+package pkg2 is
+  constant c : natural := 1;
+end package;
+
+use work.pkg1.all;
+use work.pkg2.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant d : natural := c;
+begin
+end architecture;
+",
+    );
+
+    let (root, _) = builder.get_analyzed_root();
+    let explanation = explain(&root, &code.s1(":= c;").s1("c"));
+
+    let visible: Vec<_> = explanation
+        .use_clauses
+        .iter()
+        .map(|candidate| candidate.visible_pos.clone())
+        .collect();
+    assert_eq!(
+        visible,
+        vec![
+            vec![code.s1("work.pkg1.all").pos()],
+            vec![code.s1("work.pkg2.all").pos()]
+        ]
+    );
+    assert_eq!(
+        explanation.error.as_deref(),
+        Some("Name 'c' is hidden by conflicting use clause")
+    );
+    assert!(explanation.resolved.is_none());
+}
@@ -0,0 +1,247 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+const HIERARCHY: &str = "
+entity leaf is
+  port (leaf_port : in bit);
+end entity;
+
+architecture a of leaf is
+  signal leaf_sig : natural;
+begin
+end architecture;
+
+entity mid is
+end entity;
+
+architecture a of mid is
+begin
+  inst : entity work.leaf port map (leaf_port => '0');
+end architecture;
+";
+
+#[test]
+fn resolves_package_path() {
+    check_search_reference_with_name(
+        "pkg_const",
+        "
+package pkg is
+  constant pkg_const : natural := 0;
+end package;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  alias c is << constant @libname.pkg.pkg_const : natural >>;
+begin
+end architecture;
+",
+    );
+}
+
+#[test]
+fn resolves_relative_path_through_instances() {
+    check_search_reference_with_name(
+        "leaf_sig",
+        &format!(
+            "{HIERARCHY}
+entity top is
+end entity;
+
+architecture a of top is
+  signal s : natural;
+begin
+  mid_inst : entity work.mid;
+  s <= << signal mid_inst.inst.leaf_sig : natural >>;
+end architecture;
+"
+        ),
+    );
+}
+
+#[test]
+fn resolves_absolute_path_to_port() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        &format!(
+            "{HIERARCHY}
+entity tb is
+end entity;
+
+architecture a of tb is
+  signal s : bit;
+begin
+  s <= << signal .mid.inst.leaf_port : bit >>;
+end architecture;
+"
+        ),
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("leaf_port", 3).end()),
+        Some(code.s1("leaf_port").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1(".mid").s1("mid").end()),
+        Some(code.s1("entity mid").s1("mid").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1(".inst").s1("inst").end()),
+        Some(code.s1("inst :").s1("inst").pos())
+    );
+}
+
+#[test]
+fn type_and_class_must_match() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        &format!(
+            "{HIERARCHY}
+entity top is
+end entity;
+
+architecture a of top is
+  signal s : bit;
+  signal b : boolean;
+begin
+  mid_inst : entity work.mid;
+  b <= << signal mid_inst.inst.leaf_port : boolean >>;
+  s <= << constant mid_inst.inst.leaf_port : bit >>;
+end architecture;
+"
+        ),
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1(": boolean >>").s1("boolean"),
+                "External name of type 'BOOLEAN' does not match signal 'leaf_port' of type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s1("mid_inst.inst.leaf_port : bit")
+                    .s1("mid_inst.inst.leaf_port"),
+                "External constant name denotes signal 'leaf_port'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn missing_declarations_in_path() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        &format!(
+            "{HIERARCHY}
+package pkg is
+end package;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal s : natural;
+begin
+  mid_inst : entity work.mid;
+
+  process
+  begin
+    s <= << signal mid_inst.missing.leaf_sig : natural >>;
+    s <= << signal mid_inst.inst.missing_sig : natural >>;
+    s <= << constant @libname.pkg.missing_const : natural >>;
+    s <= << signal .missing_ent.leaf_sig : natural >>;
+    wait;
+  end process;
+end architecture;
+"
+        ),
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("missing.").s1("missing"),
+                "No declaration of 'missing' within entity 'mid'",
+            ),
+            Diagnostic::error(
+                code.s1("missing_sig"),
+                "No declaration of 'missing_sig' within entity 'leaf'",
+            ),
+            Diagnostic::error(
+                code.s1("missing_const"),
+                "No declaration of 'missing_const' within package 'pkg'",
+            ),
+            Diagnostic::error(
+                code.s1("missing_ent"),
+                "No primary unit 'missing_ent' within library 'libname'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn paths_relative_to_enclosing_instances_are_not_resolved() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  s <= << signal ^.^.unknown.sig : natural >>;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn resolves_path_through_last_analyzed_architecture() {
+    check_search_reference_with_name(
+        "sim_sig",
+        "
+entity leaf is
+end entity;
+
+architecture rtl of leaf is
+  signal rtl_sig : natural;
+begin
+end architecture;
+
+architecture sim of leaf is
+  signal sim_sig : natural;
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal s : natural;
+begin
+  inst : entity work.leaf;
+  s <= << signal inst.sim_sig : natural >>;
+end architecture;
+",
+    );
+}
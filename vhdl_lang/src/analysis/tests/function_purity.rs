@@ -0,0 +1,197 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{ErrorCode, VHDLStandard};
+
+#[test]
+fn pure_function_may_not_reference_objects_declared_outside() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+use std.textio.all;
+
+entity ent is
+  port (inp : in bit);
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+
+  function read_sig return bit is
+  begin
+    return sig;
+  end function;
+
+  function read_port return bit is
+  begin
+    return inp;
+  end function;
+begin
+  main : process
+    variable var : natural;
+    file fil : text;
+
+    function read_var return natural is
+    begin
+      return var;
+    end function;
+
+    function read_file return boolean is
+    begin
+      return endfile(fil);
+    end function;
+  begin
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("return sig").s1("sig"),
+                "Pure function 'read_sig' cannot reference signal 'sig' declared outside of it",
+            )
+            .with_code(ErrorCode::FunctionPurity),
+            Diagnostic::error(
+                code.s1("return inp").s1("inp"),
+                "Pure function 'read_port' cannot reference signal 'inp' declared outside of it",
+            )
+            .with_code(ErrorCode::FunctionPurity),
+            Diagnostic::error(
+                code.s1("return var").s1("var"),
+                "Pure function 'read_var' cannot reference variable 'var' declared outside of it",
+            )
+            .with_code(ErrorCode::FunctionPurity),
+            Diagnostic::error(
+                code.s1("(fil)").s1("fil"),
+                "Pure function 'read_file' cannot reference file 'fil' declared outside of it",
+            )
+            .with_code(ErrorCode::FunctionPurity),
+        ],
+    );
+}
+
+#[test]
+fn pure_function_may_not_call_impure_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  impure function get_time return time is
+  begin
+    return now;
+  end function;
+
+  function time_plus_one return time is
+  begin
+    return get_time + 1 ns;
+  end function;
+
+  -- NOW is accepted within pure functions
+  function now_plus_one return time is
+  begin
+    return now + 1 ns;
+  end function;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("return get_time").s1("get_time"),
+            "Pure function 'time_plus_one' cannot call impure function 'get_time'",
+        )
+        .with_code(ErrorCode::FunctionPurity)],
+    );
+}
+
+#[test]
+fn pure_function_may_reference_its_own_objects() {
+    let mut builder = LibraryBuilder::new();
+    // Shared variables of other than protected types are allowed by VHDL-1993
+    builder.standard(VHDLStandard::VHDL1993);
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+  shared variable shared_var : natural;
+
+  procedure set(variable target : out natural; value : natural) is
+  begin
+    target := value;
+  end procedure;
+
+  function local_objects(signal s : bit; v : natural) return natural is
+    variable var : natural;
+    constant c : natural := 1;
+
+    function nested return natural is
+    begin
+      return c;
+    end function;
+  begin
+    set(target => var, value => v);
+    if s = '1' then
+      return nested;
+    end if;
+    return var;
+  end function;
+
+  impure function impure_reads return natural is
+  begin
+    if sig = '1' then
+      return shared_var;
+    end if;
+    return 0;
+  end function;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn standard_libraries_are_not_checked() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package pkg is
+  signal enable : bit;
+  function get return bit;
+end package;
+
+package body pkg is
+  function get return bit is
+  begin
+    return enable;
+  end function;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
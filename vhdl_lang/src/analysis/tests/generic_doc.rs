@@ -0,0 +1,72 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::GenericDocumentation;
+
+#[test]
+fn documents_generics_of_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    -- The width of the data bus
+    -- in bits
+    width : natural := 8;
+    depth : positive -- The number of words, \"--\" is not a comment
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  inst : entity work.ent
+    generic map (
+      width => 16,
+      depth => 4
+    );
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let width = GenericDocumentation {
+        name: "width".to_owned(),
+        subtype: "natural".to_owned(),
+        default: Some("8".to_owned()),
+        doc: Some("The width of the data bus\nin bits".to_owned()),
+    };
+    let depth = GenericDocumentation {
+        name: "depth".to_owned(),
+        subtype: "positive".to_owned(),
+        default: None,
+        doc: Some("The number of words, \"--\" is not a comment".to_owned()),
+    };
+
+    // The formal of the generic map refers to the generic of the entity
+    let ent = root
+        .search_reference(code.source(), code.s1("width => 16").start())
+        .unwrap();
+    assert_eq!(root.documentation_of_generic(ent), Some(width.clone()));
+    let ent = root
+        .search_reference(code.source(), code.s1("depth => 4").start())
+        .unwrap();
+    assert_eq!(root.documentation_of_generic(ent), Some(depth.clone()));
+
+    let ent = root
+        .search_reference(code.source(), code.s1("work.ent").s1("ent").start())
+        .unwrap();
+    assert_eq!(root.generic_documentation(ent), vec![width, depth]);
+}
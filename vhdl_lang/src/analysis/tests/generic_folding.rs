@@ -0,0 +1,240 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+const ADDER: &str = "
+entity adder is
+  generic (
+    width : natural := 8
+  );
+  port (
+    a : in bit_vector(width - 1 downto 0);
+    b : in bit_vector(width - 1 downto 0);
+    sum : out bit_vector(width downto 0)
+  );
+end entity;
+
+architecture rtl of adder is
+begin
+end architecture;
+";
+
+#[test]
+fn port_lengths_match_generic_actuals() {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", ADDER);
+    builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  constant w : natural := 4;
+  signal x, y : bit_vector(w - 1 downto 0);
+  signal s : bit_vector(w downto 0);
+  signal x8 : bit_vector(7 downto 0);
+  signal s9 : bit_vector(0 to 8);
+begin
+  inst0 : entity work.adder
+    generic map (width => w)
+    port map (a => x, b => y, sum => s);
+
+  inst1 : entity work.adder
+    port map (a => x8, b => x8, sum => s9);
+
+  inst2 : entity work.adder
+    generic map (2 * w)
+    port map (x8, x8, s9);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn port_length_mismatch_at_instance() {
+    let mut builder = LibraryBuilder::new();
+    let adder = builder.code("libname", ADDER);
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  signal x4 : bit_vector(3 downto 0);
+  signal x8 : bit_vector(7 downto 0);
+  signal s5 : bit_vector(4 downto 0);
+begin
+  inst0 : entity work.adder
+    generic map (width => 4)
+    port map (a => x4, b => x8, sum => s5);
+
+  inst1 : entity work.adder
+    port map (a => \"0000\", b => x8(3 downto 0), sum => open);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("b => x8").s1("x8"),
+                "Actual of length 8 does not match the length 4 of port 'b'",
+            )
+            .related(adder.s1("b : in").s1("b"), "Defined here"),
+            Diagnostic::error(
+                code.s1("\"0000\""),
+                "Actual of length 4 does not match the length 8 of port 'a'",
+            )
+            .related(adder.s1("a : in").s1("a"), "Defined here"),
+            Diagnostic::error(
+                code.s1("x8(3 downto 0)"),
+                "Actual of length 4 does not match the length 8 of port 'b'",
+            )
+            .related(adder.s1("b : in").s1("b"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn non_static_generic_actual_is_not_checked() {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", ADDER);
+    builder.code(
+        "libname",
+        "
+entity top is
+  generic (
+    w : natural
+  );
+end entity;
+
+architecture a of top is
+  signal x : bit_vector(3 downto 0);
+  signal s : bit_vector(4 downto 0);
+begin
+  inst : entity work.adder
+    generic map (width => w)
+    port map (a => x, b => x, sum => s);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn port_length_mismatch_at_component_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    generic (
+      width : natural
+    );
+    port (
+      d : in bit_vector(1 to width)
+    );
+  end component;
+
+  signal x : bit_vector(3 downto 0);
+begin
+  inst : comp
+    generic map (width => 3)
+    port map (d => x);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("d => x").s1("x"),
+            "Actual of length 4 does not match the length 3 of port 'd'",
+        )
+        .related(code.s1("d : in").s1("d"), "Defined here")],
+    );
+}
+
+#[test]
+fn port_default_checked_with_generics_of_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity sub is
+  generic (
+    width : natural := 4;
+    limit : natural := 3
+  );
+  port (
+    d : in bit_vector(width - 1 downto 0) := \"0000\";
+    sel : in integer range 0 to limit := 3;
+    q : out bit
+  );
+end entity;
+
+architecture rtl of sub is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal q : bit;
+begin
+  inst0 : entity work.sub
+    port map (q => q);
+
+  inst1 : entity work.sub
+    generic map (width => 8, limit => 2)
+    port map (sel => open, q => q);
+
+  inst2 : entity work.sub
+    generic map (width => 8, limit => 2)
+    port map (d => x\"00\", sel => 1, q => q);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("inst1 : entity work.sub").s1("work.sub"),
+                "Default value of port 'd' is invalid with the generics of this instance",
+            )
+            .related(
+                code.s1("\"0000\""),
+                "Value of length 4 does not match the length 8 of the subtype",
+            ),
+            Diagnostic::error(
+                code.s1("inst1 : entity work.sub").s1("work.sub"),
+                "Default value of port 'sel' is invalid with the generics of this instance",
+            )
+            .related(
+                code.s1(":= 3;").s1("3"),
+                "Value 3 is outside of the range 0 to 2 of integer type 'INTEGER'",
+            ),
+        ],
+    );
+}
@@ -0,0 +1,127 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::ast::Expression;
+use crate::data::{Symbol, WithPos};
+
+fn generics(
+    builder: &mut LibraryBuilder,
+    values: &[(&str, &str)],
+) -> Vec<(Symbol, WithPos<Expression>)> {
+    values
+        .iter()
+        .map(|(name, value)| {
+            let value = builder.snippet(value);
+            (value.symbol(name), value.expr())
+        })
+        .collect()
+}
+
+#[test]
+fn checks_architecture_with_assumed_generics() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    width : natural;
+    enable : boolean := false
+  );
+  port (
+    data : in bit_vector(width - 1 downto 0)
+  );
+end entity;
+
+architecture a of ent is
+  constant last : natural := width - 1;
+  signal b0, b1 : bit;
+begin
+  b0 <= data(7);
+  b1 <= data(last);
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let diagnostics = root
+        .analyze_with_generics(
+            "libname",
+            "ent",
+            "a",
+            generics(&mut builder, &[("width", "8")]),
+        )
+        .unwrap();
+    check_no_diagnostics(&diagnostics);
+
+    let diagnostics = root
+        .analyze_with_generics(
+            "libname",
+            "ent",
+            "a",
+            generics(&mut builder, &[("width", "4"), ("enable", "true")]),
+        )
+        .unwrap();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("data(7)").s1("7"),
+            "Index 7 is outside of the index range 3 downto 0 of interface signal 'data' : in",
+        )],
+    );
+}
+
+#[test]
+fn invalid_assumed_generics() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    width : natural
+  );
+  port (
+    data : in bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let mut error = |name: &str, value: &str| {
+        let generics = generics(&mut builder, &[(name, value)]);
+        root.analyze_with_generics("libname", "ent", "a", generics)
+            .unwrap_err()
+    };
+
+    assert_eq!(
+        error("missing", "1"),
+        "Entity 'ent' has no generic 'missing'"
+    );
+    assert_eq!(
+        error("data", "'1'"),
+        "Entity 'ent' has no generic constant 'data'"
+    );
+    assert_eq!(
+        error("width", "true"),
+        "Invalid value of generic 'width': 'true' does not match subtype 'NATURAL'"
+    );
+    assert_eq!(
+        root.analyze_with_generics("libname", "ent", "b", Vec::new())
+            .unwrap_err(),
+        "No such architecture 'b' of entity 'libname.ent'"
+    );
+}
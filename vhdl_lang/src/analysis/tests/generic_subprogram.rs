@@ -0,0 +1,274 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{ErrorCode, VHDLStandard};
+
+#[test]
+fn instantiated_subprograms_participate_in_overload_resolution() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function conv generic (type T) parameter (x : T) return T is
+begin
+  return x;
+end function;
+
+function conv is new conv generic map (T => integer);
+function conv is new conv generic map (T => boolean);
+
+constant c0 : integer := conv(1);
+constant c1 : boolean := conv(true);
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("conv(1)").start()),
+        Some(code.s("conv", 2).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("conv(true)").start()),
+        Some(code.s("conv", 4).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("conv", 3).start()),
+        Some(code.s("conv", 1).pos())
+    );
+}
+
+#[test]
+fn generic_subprogram_declaration_and_body_in_package() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  procedure swap generic (type T) parameter (variable a, b : inout T);
+end package;
+
+package body pkg is
+  procedure swap generic (type T) parameter (variable a, b : inout T) is
+    variable tmp : T;
+  begin
+    tmp := a;
+    a := b;
+    b := tmp;
+  end procedure;
+end package body;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  main : process
+    procedure swap_int is new work.pkg.swap generic map (T => integer);
+    variable x, y : integer := 0;
+  begin
+    swap_int(x, y);
+    wait;
+  end process;
+end architecture;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn uninstantiated_subprogram_cannot_be_called() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function ident generic (type T) parameter (x : T) return T;
+constant c0 : integer := ident(0);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("ident", 2),
+            "Uninstantiated subprogram 'ident' cannot be called",
+        )
+        .related(code.s1("ident"), "Must be instantiated ident[T return T]")],
+    );
+}
+
+#[test]
+fn subprogram_instance_must_denote_an_uninstantiated_subprogram() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function plain(x : natural) return natural;
+procedure gproc generic (type T) parameter (x : T);
+
+function f0 is new plain;
+function f1 is new gproc generic map (T => natural);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("plain", 2),
+                "'plain' does not denote an uninstantiated function",
+            )
+            .related(code.s1("plain"), "Found plain[NATURAL return NATURAL]"),
+            Diagnostic::error(
+                code.s("gproc", 2),
+                "'gproc' does not denote an uninstantiated function",
+            )
+            .related(code.s1("gproc"), "Found gproc[T]"),
+        ],
+    );
+}
+
+#[test]
+fn subprogram_instance_generic_map_is_checked() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function ident generic (type T) parameter (x : T) return T;
+
+function f0 is new ident;
+function f1 is new ident generic map (U => natural);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s("ident", 2), "No association of type 'T'")
+                .related(code.s1("T"), "Defined here"),
+            Diagnostic::error(code.s1("U"), "No declaration of 'U'"),
+        ],
+    );
+}
+
+#[test]
+fn generic_map_at_call_site_instantiates_the_subprogram() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  function ident generic (type T) parameter (x : T) return T is
+  begin
+    return x;
+  end function;
+
+  procedure swap generic (type T) parameter (variable a, b : inout T) is
+    variable tmp : T;
+  begin
+    tmp := a;
+    a := b;
+    b := tmp;
+  end procedure;
+begin
+  process
+    variable n0, n1 : natural;
+    variable b0 : boolean;
+  begin
+    n0 := ident generic map (T => natural) (n1) + 1;
+    b0 := ident generic map (T => boolean) parameter map (x => true);
+    swap generic map (T => natural) (n0, n1);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let call = code.s1("ident generic map (T => natural)").s1("ident");
+    assert_eq!(
+        root.search_reference_pos(code.source(), call.start()),
+        Some(code.s1("ident").pos())
+    );
+    let call = code.s1("swap generic map").s1("swap");
+    assert_eq!(
+        root.search_reference_pos(code.source(), call.start()),
+        Some(code.s1("swap").pos())
+    );
+    assert_eq!(
+        root.find_all_references_pos(&code.s1("ident").pos()).len(),
+        3
+    );
+}
+
+#[test]
+fn generic_map_at_call_site_checks_the_specialized_signature() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    let code = builder.in_declarative_region(
+        "
+function ident generic (type T) parameter (x : T) return T;
+function plain(x : natural) return natural;
+
+constant c0 : boolean := ident generic map (T => boolean) (0);
+constant c1 : boolean := ident generic map (T => natural) (0);
+constant c2 : natural := plain generic map (T => natural) (0);
+        ",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::error(
+                code.s1("(0)").s1("0"),
+                "integer literal does not match type 'BOOLEAN'",
+            ),
+            Diagnostic::error(
+                code.s1("ident generic map (T => natural)").s1("ident"),
+                "Could not resolve call to 'ident'",
+            )
+            .related(
+                code.s1("ident"),
+                "Does not match ident[NATURAL return NATURAL]",
+            ),
+            Diagnostic::error(
+                code.s("plain", 2),
+                "'plain' does not denote an uninstantiated subprogram",
+            )
+            .related(code.s1("plain"), "Found plain[NATURAL return NATURAL]"),
+        ],
+    );
+}
+
+#[test]
+fn generic_map_at_call_site_requires_vhdl2019() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2008);
+    let code = builder.in_declarative_region(
+        "
+function ident generic (type T) parameter (x : T) return T;
+constant c0 : natural := ident generic map (T => natural) (0);
+        ",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::error(
+            code.s("ident", 2),
+            "Generic map aspects of subprogram calls are not allowed before VHDL-2019",
+        )
+        .with_code(ErrorCode::LaterRevision)],
+    );
+}
@@ -0,0 +1,152 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn group_is_available_to_attribute_specifications() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+group pair is (signal, signal);
+group path is (signal, literal <>);
+type state_t is (idle, busy, done);
+signal clk, rst : bit;
+group clocking : pair (clk, rst);
+group states : path (clk, idle, busy, done);
+group no_states : path (rst);
+attribute note : string;
+attribute note of clocking : group is \"clk\";
+attribute note of others : group is \"default\";
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let note = root
+        .search_reference(code.source(), code.s1("note").start())
+        .unwrap();
+
+    let clocking = root
+        .search_reference(code.source(), code.s1("clocking").start())
+        .unwrap();
+    assert_eq!(
+        clocking.attribute_value(note),
+        Some(&code.s1("\"clk\"").pos())
+    );
+
+    for name in ["states", "no_states"] {
+        let group = root
+            .search_reference(code.source(), code.s1(name).start())
+            .unwrap();
+        assert_eq!(
+            group.attribute_value(note),
+            Some(&code.s1("\"default\"").pos())
+        );
+    }
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("pair", 2).start()),
+        Some(code.s1("pair").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("clk", 2).start()),
+        Some(code.s1("clk").pos())
+    );
+}
+
+#[test]
+fn group_constituents_must_belong_to_entity_class_of_template() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+group pair is (signal, constant);
+signal sig0, sig1 : bit;
+constant const0 : bit := '0';
+group good : pair (sig0, const0);
+group bad : pair (const0, sig1);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("(const0, sig1)").s1("const0"),
+                "constant 'const0' does not belong to entity class 'signal' of the group template",
+            ),
+            Diagnostic::error(
+                code.s1("(const0, sig1)").s1("sig1"),
+                "signal 'sig1' does not belong to entity class 'constant' of the group template",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn group_constituent_count_must_match_template() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+group pair is (signal, signal);
+group many is (signal, signal <>);
+signal sig0, sig1, sig2 : bit;
+group too_many : pair (sig0, sig1, sig2);
+group enough : many (sig0, sig1, sig2);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("pair", 2),
+            "group template 'pair' expects 2 constituents, got 3",
+        )],
+    );
+}
+
+#[test]
+fn only_last_entity_class_entry_may_have_box() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+group bad is (signal <>, constant);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("signal <>"),
+            "Only the last entity class entry of a group template may contain a box (<>)",
+        )],
+    );
+}
+
+#[test]
+fn group_template_name_must_denote_group_template() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+signal sig0 : bit;
+group bad : sig0 (sig0);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("sig0", 2),
+            "Expected group template, got signal 'sig0'",
+        )
+        .related(code.s1("sig0"), "Defined here")],
+    );
+}
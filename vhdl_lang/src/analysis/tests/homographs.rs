@@ -950,6 +950,7 @@ end package;
 package body pkg is
   function name1 return natural is
   begin
+    return 0;
   end;
 end package body;
 ",
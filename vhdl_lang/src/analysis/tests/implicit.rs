@@ -198,7 +198,9 @@ end package;
         ),
         Some(
             "\
--- TO_STRING[enum_t return STRING]
+function TO_STRING(
+  constant VALUE : in enum_t
+) return STRING;
 
 -- Implicitly defined by:
 type enum_t is (alpha, beta);
@@ -213,7 +215,7 @@ fn implicit_functions_on_physical_type() {
     check_code_with_no_diagnostics(
         "
 package pkg is
-    type time_t is range 0 to 1
+    type time_t is range 0 to 1000
     units
       small;
       big = 1000 small;
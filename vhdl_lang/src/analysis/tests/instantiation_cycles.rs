@@ -0,0 +1,169 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn error_on_instantiation_cycle() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent_a is
+end entity;
+
+entity ent_b is
+end entity;
+
+architecture rtl of ent_a is
+begin
+  inst_b : entity work.ent_b;
+end architecture;
+
+architecture rtl of ent_b is
+begin
+  blk : block
+  begin
+    inst_a : entity work.ent_a;
+  end block;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("work.ent_a"),
+            "Recursive instantiation of entity 'ent_a' without a terminating generate condition: ent_a -> ent_b -> ent_a",
+        )
+        .related(code.s1("work.ent_b"), "Instantiation of 'ent_b'")],
+    );
+}
+
+#[test]
+fn error_on_component_bound_to_itself() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+  component ent is
+  end component;
+begin
+  inst : component ent;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("inst : component ent").s1(" ent").s1("ent"),
+            "Recursive instantiation of entity 'ent' without a terminating generate condition: ent -> ent",
+        )],
+    );
+}
+
+#[test]
+fn recursion_within_generate_is_not_a_cycle() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity tree is
+  generic (depth : natural);
+end entity;
+
+architecture rtl of tree is
+begin
+  gen : if depth > 0 generate
+    left : entity work.tree generic map (depth => depth - 1);
+    right : entity work.tree generic map (depth => depth - 1);
+  end generate;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn instance_of_another_architecture_is_not_a_cycle() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity e is
+end entity;
+
+entity f is
+end entity;
+
+architecture leaf of e is
+begin
+end architecture;
+
+architecture rtl of f is
+begin
+  inst_e : entity work.e(leaf);
+end architecture;
+
+architecture top of e is
+begin
+  inst_f : entity work.f;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_cycle_through_default_binding() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity e is
+end entity;
+
+entity f is
+end entity;
+
+architecture leaf of e is
+begin
+end architecture;
+
+architecture rtl of f is
+begin
+  inst_e : entity work.e;
+end architecture;
+
+architecture top of e is
+begin
+  inst_f : entity work.f;
+end architecture;
+",
+    );
+
+    // The default binding of work.e is the most recently analyzed architecture top
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("work.f"),
+            "Recursive instantiation of entity 'f' without a terminating generate condition: f -> e -> f",
+        )
+        .related(code.s1("work.e"), "Instantiation of 'e'")],
+    );
+}
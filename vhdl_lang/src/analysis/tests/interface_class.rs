@@ -0,0 +1,94 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::VHDLStandard;
+
+#[test]
+fn function_parameters_must_be_of_mode_in() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function fun1(a : natural; signal b : natural; c : out natural) return natural;
+function fun2(signal d : inout natural; variable e : natural) return natural;
+impure function fun3(variable f : inout natural) return natural;
+procedure proc(variable g : natural; signal h : out natural);
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::error(
+                code.s1("c : out").s1("c"),
+                "Parameter 'c' of function 'fun1' must be of mode in, got out",
+            ),
+            Diagnostic::error(
+                code.s1("d : inout").s1("d"),
+                "Parameter 'd' of function 'fun2' must be of mode in, got inout",
+            ),
+            Diagnostic::error(
+                code.s1("e : natural").s1("e"),
+                "Parameter 'e' of function 'fun2' must be a constant, signal or file, got variable",
+            ),
+            Diagnostic::error(
+                code.s1("f : inout").s1("f"),
+                "Parameter 'f' of function 'fun3' must be of mode in, got inout",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn impure_function_parameters_of_any_mode_since_vhdl2019() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    let code = builder.in_declarative_region(
+        "
+impure function fun1(variable a : inout natural; b : out natural) return natural;
+function fun2(variable c : inout natural) return natural;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::error(
+            code.s1("c : inout").s1("c"),
+            "Parameter 'c' of function 'fun2' must be of mode in, got inout",
+        )],
+    );
+}
+
+#[test]
+fn hover_shows_inferred_parameter_class() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure proc(a : natural; b : out natural; c : inout natural);
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let format = |name: &str| {
+        let pos = code.s1(name).start();
+        root.format_declaration(root.search_reference(code.source(), pos).unwrap())
+    };
+
+    assert_eq!(
+        format("a : natural"),
+        Some("constant a : in natural;".to_owned())
+    );
+    assert_eq!(
+        format("b : out"),
+        Some("variable b : out natural;".to_owned())
+    );
+    assert_eq!(
+        format("c : inout"),
+        Some("variable c : inout natural;".to_owned())
+    );
+}
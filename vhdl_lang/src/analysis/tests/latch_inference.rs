@@ -0,0 +1,336 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+
+const SIGNALS: &str = "
+  signal clk, sel, a, b, q, r : bit;
+  signal v, w, x : bit_vector(0 to 1);
+";
+
+#[test]
+fn if_without_else_infers_latch() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (sel, a)
+  begin
+    if sel = '1' then
+      q <= a;
+    end if;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("q <= a").s1("q"),
+            "Latch inferred for signal 'q' which is not assigned in every branch of the process",
+        )
+        .related(code.s1("sel = '1'"), "Not assigned when no condition holds")
+        .with_code(ErrorCode::LatchInference)],
+    );
+}
+
+#[test]
+fn signal_assigned_in_all_branches_does_not_infer_latch() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_architecture(
+        SIGNALS,
+        "
+  process (sel, a, b)
+  begin
+    if sel = '1' then
+      q <= a;
+    elsif a = '1' then
+      q <= b;
+    else
+      q <= '0';
+    end if;
+
+    case sel is
+      when '0' => r <= a;
+      when others => r <= b;
+    end case;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn default_assignment_does_not_infer_latch() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_architecture(
+        SIGNALS,
+        "
+  process (all)
+  begin
+    q <= '0';
+    v(0) <= '0';
+    if sel = '1' then
+      q <= a;
+      v(0) <= b;
+    end if;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn case_alternative_without_assignment_infers_latch() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (sel, a, b)
+  begin
+    case sel is
+      when '0' =>
+        q <= a;
+        r <= a;
+      when others =>
+        if a = '1' then
+          q <= b;
+        else
+          q <= a;
+        end if;
+    end case;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("r <= a").s1("r"),
+            "Latch inferred for signal 'r' which is not assigned in every branch of the process",
+        )
+        .related(code.s1("case sel").s1("sel"), "Not assigned in this branch")
+        .with_code(ErrorCode::LatchInference)],
+    );
+}
+
+#[test]
+fn conditional_assignment_without_else_infers_latch() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (sel, a, b)
+  begin
+    q <= a when sel = '1' else b;
+    r <= a when sel = '1';
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("r <= a").s1("r"),
+            "Latch inferred for signal 'r' which is not assigned in every branch of the process",
+        )
+        .related(
+            code.s("sel = '1'", 2),
+            "Not assigned when no condition holds",
+        )
+        .with_code(ErrorCode::LatchInference)],
+    );
+}
+
+#[test]
+fn latch_inference_points_at_nested_branch_without_assignment() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (sel, a, b)
+  begin
+    if sel = '1' then
+      q <= a;
+    else
+      case a is
+        when '0' => q <= b;
+        when '1' => null;
+      end case;
+    end if;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("q <= a").s1("q"),
+            "Latch inferred for signal 'q' which is not assigned in every branch of the process",
+        )
+        .related(
+            code.s1("'1' => null").s1("'1'"),
+            "Not assigned in this branch",
+        )
+        .with_code(ErrorCode::LatchInference)],
+    );
+}
+
+#[test]
+fn clocked_process_does_not_infer_latch() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_architecture(
+        SIGNALS,
+        "
+  process (clk)
+  begin
+    if rising_edge(clk) then
+      if sel = '1' then
+        q <= a;
+      end if;
+    end if;
+  end process;
+
+  process (clk)
+  begin
+    if clk'event and clk = '1' then
+      r <= a;
+    end if;
+  end process;
+
+  process
+  begin
+    if sel = '1' then
+      b <= a;
+    end if;
+    wait on sel;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn for_loop_with_static_range_is_executed() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_architecture(
+        SIGNALS,
+        "
+  process (v)
+  begin
+    for i in 0 to 1 loop
+      w(i) <= v(1 - i);
+    end loop;
+  end process;
+
+  process (sel)
+  begin
+    for i in w'range loop
+      if sel = '1' then
+        x(i) <= '1';
+      else
+        x(i) <= '0';
+      end if;
+    end loop;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn loop_that_might_not_execute_infers_latch() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (sel, v)
+  begin
+    for i in 1 to 0 loop
+      w(i) <= v(i);
+    end loop;
+
+    for i in v'range loop
+      exit when sel = '1';
+      x(i) <= v(i);
+    end loop;
+
+    while sel = '1' loop
+      q <= a;
+    end loop;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("1 to 0"),
+                "Range 1 to 0 is null, the loop is never executed",
+            )
+            .with_code(ErrorCode::NullRange),
+            Diagnostic::warning(
+                code.s1("w(i)"),
+                "Latch inferred for signal 'w' which is not assigned in every branch of the process",
+            )
+            .with_code(ErrorCode::LatchInference),
+            Diagnostic::warning(
+                code.s1("x(i)"),
+                "Latch inferred for signal 'x' which is not assigned in every branch of the process",
+            )
+            .with_code(ErrorCode::LatchInference),
+            Diagnostic::warning(
+                code.s1("q <= a").s1("q"),
+                "Latch inferred for signal 'q' which is not assigned in every branch of the process",
+            )
+            .with_code(ErrorCode::LatchInference),
+        ],
+    );
+}
+
+#[test]
+fn missing_branch_within_executed_loop() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (sel, v)
+  begin
+    for i in v'range loop
+      if sel = '1' then
+        w(i) <= v(i);
+      end if;
+    end loop;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("w(i)"),
+            "Latch inferred for signal 'w' which is not assigned in every branch of the process",
+        )
+        .related(code.s1("sel = '1'"), "Not assigned when no condition holds")
+        .with_code(ErrorCode::LatchInference)],
+    );
+}
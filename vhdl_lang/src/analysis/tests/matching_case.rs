@@ -0,0 +1,266 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+
+#[test]
+fn matching_case_of_std_ulogic() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+type std_ulogic_vector is array (natural range <>) of std_ulogic;
+constant pattern : std_ulogic_vector(0 to 2) := \"1-0\";
+
+procedure proc(sel : std_ulogic_vector(0 to 2); bsel : bit) is
+begin
+  case? sel is
+    when \"1--\" => null;
+    when pattern => null;
+    when others => null;
+  end case?;
+
+  case? bsel is
+    when '1' => null;
+    when others => null;
+  end case?;
+end procedure;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn matching_case_selector_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure proc(sel : integer) is
+begin
+  case? sel is
+    when 0 => null;
+    when others => null;
+  end case?;
+end procedure;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("case? sel").s1("sel"),
+            "Selector of a matching case statement must be of type BIT or STD_ULOGIC or a one-dimensional array of them, got integer type 'INTEGER'",
+        )],
+    );
+}
+
+#[test]
+fn matching_case_choices_must_be_static_and_of_equal_length() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure proc(sel : bit_vector; other : bit_vector(0 to 2)) is
+begin
+  case? sel is
+    when \"100\" => null;
+    when \"10\" => null;
+    when other => null;
+    when others => null;
+  end case?;
+end procedure;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("\"10\""),
+                "Choice of length 2 does not match the length 3 of the other choices",
+            )
+            .related(code.s1("\"100\""), "Choice of length 3"),
+            Diagnostic::error(
+                code.s1("when other =>").s1("other"),
+                "Choice must be locally static, interface constant 'other' is not static",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn matching_case_choices_must_have_the_length_of_the_selector() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure proc(sel : bit_vector(0 to 2)) is
+begin
+  case? sel is
+    when \"100\" => null;
+    when \"10\" => null;
+    when others => null;
+  end case?;
+end procedure;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("\"10\""),
+            "Choice of length 2 does not match the length 3 of the selector",
+        )],
+    );
+}
+
+#[test]
+fn matching_case_choices_must_not_overlap() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+type std_ulogic_vector is array (natural range <>) of std_ulogic;
+
+procedure proc(sel : std_ulogic_vector(0 to 2); bsel : std_ulogic) is
+begin
+  case? sel is
+    when \"1-0\" => null;
+    when \"0-1\" => null;
+    when \"11-\" => null;
+    when \"X00\" => null;
+    when \"-00\" => null;
+    when others => null;
+  end case?;
+
+  case? bsel is
+    when '1' => null;
+    when 'H' => null;
+    when others => null;
+  end case?;
+end procedure;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("\"11-\""),
+                "Choice overlaps with a previous choice, both match the value \"110\"",
+            )
+            .related(code.s1("\"1-0\""), "Previous choice"),
+            Diagnostic::error(
+                code.s1("\"-00\""),
+                "Choice overlaps with a previous choice, both match the value \"100\"",
+            )
+            .related(code.s1("\"1-0\""), "Previous choice"),
+            Diagnostic::error(
+                code.s1("when 'H'").s1("'H'"),
+                "Choice overlaps with a previous choice, both match the value '1'",
+            )
+            .related(code.s1("'1' =>").s1("'1'"), "Previous choice"),
+        ],
+    );
+}
+
+#[test]
+fn dont_care_in_ordinary_case() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+type std_ulogic_vector is array (natural range <>) of std_ulogic;
+
+procedure proc(sel : std_ulogic_vector(0 to 1)) is
+begin
+  case sel is
+    when \"1-\" => null;
+    when others => null;
+  end case;
+
+  case? sel is
+    when \"1-\" => null;
+    when others => null;
+  end case?;
+end procedure;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("\"1-\""),
+            "Choice '-' only matches the '-' value in an ordinary case statement, use case? to treat it as a don't care",
+        )
+        .with_code(ErrorCode::DontCareChoice)],
+    );
+}
+
+#[test]
+fn dont_care_character_choice_in_ordinary_case() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+
+function is_dont_care(value : std_ulogic) return boolean is
+begin
+  case value is
+    when '-' => return true;
+    when others => return false;
+  end case;
+end function;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn case_generate_choices_are_checked() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  constant sel : std_ulogic_vector(0 to 1) := \"10\";
+begin
+  gen: case sel generate
+    when \"1-\" =>
+    when 0 =>
+    when others =>
+  end generate;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("\"1-\""),
+                "Choice '-' only matches the '-' value in an ordinary case statement, use case? to treat it as a don't care",
+            )
+            .with_code(ErrorCode::DontCareChoice),
+            Diagnostic::error(code.s1("when 0").s1("0"), "integer literal does not match array type 'std_ulogic_vector'",
+            ),
+        ],
+    );
+}
@@ -0,0 +1,226 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+
+fn missing_return(code: &Code, name: &str, end: Code) -> Diagnostic {
+    Diagnostic::warning(
+        end,
+        format!(
+            "Function '{name}' can reach the end of its body without executing a return statement"
+        ),
+    )
+    .related(code.s1(name), "Function declared here")
+    .with_code(ErrorCode::MissingReturn)
+}
+
+#[test]
+fn function_without_return_on_every_path() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function f_empty return natural is
+begin
+end function;
+
+function f_if(v : boolean) return natural is
+begin
+  if v then
+    return 1;
+  elsif not v then
+    return 0;
+  end if;
+end function;
+
+function f_case(v : bit) return natural is
+begin
+  case v is
+    when '0' => return 0;
+    when others => null;
+  end case;
+end function;
+
+function f_for(v : bit_vector) return natural is
+begin
+  for i in v'range loop
+    return i;
+  end loop;
+end function;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            missing_return(&code, "f_empty", code.s("end function", 1).s1("end")),
+            missing_return(&code, "f_if", code.s("end function", 2).s1("end")),
+            missing_return(&code, "f_case", code.s("end function", 3).s1("end")),
+            missing_return(&code, "f_for", code.s("end function", 4).s1("end")),
+        ],
+    );
+}
+
+#[test]
+fn function_returning_on_every_path() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+function f_if(v : boolean) return natural is
+begin
+  if v then
+    return 1;
+  else
+    return 0;
+  end if;
+end function;
+
+function f_case(v : bit) return natural is
+begin
+  case v is
+    when '0' => return 0;
+    when others => return 1;
+  end case;
+end function;
+
+function f_loop(v : natural) return natural is
+  variable i : natural := 0;
+begin
+  loop
+    if i = v then
+      return i;
+    end if;
+    i := i + 1;
+  end loop;
+end function;
+
+function f_nested_exit(v : natural) return natural is
+begin
+  loop
+    for i in 0 to v loop
+      exit;
+    end loop;
+  end loop;
+end function;
+
+procedure p is
+begin
+end procedure;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn loop_left_by_exit_statement_can_complete() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function f_exit(v : natural) return natural is
+  variable i : natural := 0;
+begin
+  loop
+    exit when i = v;
+    i := i + 1;
+  end loop;
+end function;
+
+function f_label(v : natural) return natural is
+begin
+  outer : loop
+    loop
+      exit outer;
+    end loop;
+  end loop;
+end function;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            missing_return(&code, "f_exit", code.s("end function", 1).s1("end")),
+            missing_return(&code, "f_label", code.s("end function", 2).s1("end")),
+        ],
+    );
+}
+
+#[test]
+fn function_stopping_the_simulation_does_not_complete() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+function f_report(v : natural) return natural is
+begin
+  if v = 0 then
+    return 0;
+  end if;
+  report \"unsupported value\" severity failure;
+end function;
+
+function f_assert(v : natural) return natural is
+begin
+  if v = 0 then
+    return 0;
+  end if;
+  assert false report \"unsupported value\" severity failure;
+end function;
+
+function f_while(v : natural) return natural is
+  variable i : natural := 0;
+begin
+  while true loop
+    if i = v then
+      return i;
+    end if;
+    i := i + 1;
+  end loop;
+end function;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn function_continuing_after_report_or_assert_can_complete() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function f_report return natural is
+begin
+  report \"unsupported value\" severity error;
+end function;
+
+function f_assert(v : boolean) return natural is
+begin
+  assert v severity failure;
+end function;
+
+function f_while(v : boolean) return natural is
+begin
+  while v loop
+    return 0;
+  end loop;
+end function;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            missing_return(&code, "f_report", code.s("end function", 1).s1("end")),
+            missing_return(&code, "f_assert", code.s("end function", 2).s1("end")),
+            missing_return(&code, "f_while", code.s("end function", 3).s1("end")),
+        ],
+    );
+}
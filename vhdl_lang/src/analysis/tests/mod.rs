@@ -4,25 +4,69 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
+mod aggregate_length;
 mod assignment_typecheck;
 mod association_formal;
+mod attribute_specification;
 mod circular_dependencies;
+mod clock_domain;
+mod combinational_loop;
+mod configuration_specification;
+mod connectivity;
 mod context_clause;
+mod dataflow;
+mod default_binding;
 mod deferred_constant;
+mod explain_resolution;
+mod external_names;
+mod function_purity;
+mod generic_doc;
+mod generic_folding;
+mod generic_override;
+mod generic_subprogram;
+mod group_declaration;
 mod homographs;
 mod implicit;
 mod incomplete_type;
 mod incremental_analysis;
+mod instantiation_cycles;
+mod interface_class;
+mod latch_inference;
+mod matching_case;
+mod missing_return;
+mod mode_view;
+mod multiple_drivers;
+mod null_range;
+mod operator_alias;
+mod out_mode_read;
 mod package_instance;
+mod physical_literal;
+mod port_record;
+mod pretty_print;
+mod process_classification;
 mod protected_type;
+mod psl;
+mod reset_convention;
+mod resolution_function;
 mod resolves_design_units;
 mod resolves_names;
 mod resolves_type_mark;
 mod sensitivity_list;
+mod state_machine;
+mod static_expression;
+mod static_index;
+mod static_range;
+mod structural_search;
 mod subprogram_arguments;
+mod synthesis;
+mod testbench_termination;
 mod typecheck_expression;
+mod unused;
+mod use_all_expansion;
 mod util;
 mod visibility;
+mod visible_names;
+mod wait_statement;
 
 use std::cell::RefCell;
 
@@ -0,0 +1,312 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+use crate::data::VHDLStandard;
+
+const BUS_PKG: &str = "
+package bus_pkg is
+  type bus_t is record
+    addr : bit_vector(7 downto 0);
+    wdata : bit_vector(7 downto 0);
+    rdata : bit_vector(7 downto 0);
+    valid : bit;
+  end record;
+
+  view master_view of bus_t is
+    addr, wdata, valid : out;
+    rdata : in;
+  end view;
+
+  type bus_array_t is array (natural range <>) of bus_t;
+end package;
+";
+
+fn builder_2019() -> LibraryBuilder {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    builder
+}
+
+#[test]
+fn port_with_mode_view() {
+    let mut builder = builder_2019();
+    let pkg = builder.code("libname", BUS_PKG);
+    let code = builder.code(
+        "libname",
+        "
+use work.bus_pkg.all;
+
+entity master is
+  port (
+    bus_o : view master_view;
+    buses_o : view (master_view) of bus_array_t(0 to 1)
+  );
+end entity;
+
+architecture a of master is
+begin
+  bus_o.addr <= x\"00\";
+  buses_o(0).valid <= '1';
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("master_view").start()),
+        Some(pkg.s1("master_view").pos())
+    );
+
+    let pretty = |code: &Code, name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+            .pretty()
+            .to_string()
+    };
+    assert_eq!(
+        pretty(&pkg, "master_view"),
+        "\
+view master_view of bus_t is
+  addr : out;
+  wdata : out;
+  valid : out;
+  rdata : in;
+end view;"
+    );
+    assert_eq!(pretty(&code, "bus_o"), "bus_o : view master_view;");
+    assert_eq!(
+        pretty(&code, "buses_o"),
+        "buses_o : view (master_view) of bus_array_t(0 to 1);"
+    );
+}
+
+#[test]
+fn view_element_references_record_element() {
+    let mut builder = builder_2019();
+    let code = builder.code("libname", BUS_PKG);
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("rdata", 2).start()),
+        Some(code.s1("rdata").pos())
+    );
+}
+
+#[test]
+fn every_element_must_be_given_exactly_one_mode() {
+    let mut builder = builder_2019();
+    let code = builder.in_declarative_region(
+        "
+type rec_t is record
+  a, b, c : bit;
+end record;
+
+view bad_view of rec_t is
+  a : out;
+  a, d : in;
+end view;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("a, d").s1("a"), "Duplicate mode of element 'a'")
+                .related(code.s1("a : out").s1("a"), "Previously given here"),
+            Diagnostic::error(
+                code.s1("a, d").s1("d"),
+                "No declaration of 'd' within record type 'rec_t'",
+            ),
+            Diagnostic::error(
+                code.s1("bad_view"),
+                "Element 'b' of record type 'rec_t' is not given a mode in view 'bad_view'",
+            ),
+            Diagnostic::error(
+                code.s1("bad_view"),
+                "Element 'c' of record type 'rec_t' is not given a mode in view 'bad_view'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn mode_view_must_be_of_a_record_type() {
+    let mut builder = builder_2019();
+    let code = builder.in_declarative_region(
+        "
+view bad_view of bit_vector is
+  a : out;
+end view;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("bit_vector"),
+            "The subtype of a mode view must be a record type, got array type 'BIT_VECTOR'",
+        )],
+    );
+}
+
+#[test]
+fn element_view_must_match_element_type() {
+    let mut builder = builder_2019();
+    let code = builder.in_declarative_region(
+        "
+type sub_t is record
+  x : bit;
+end record;
+
+type other_t is record
+  y : bit;
+end record;
+
+type sub_array_t is array (natural range <>) of sub_t;
+
+type rec_t is record
+  good : sub_t;
+  goods : sub_array_t(0 to 1);
+  bad : other_t;
+end record;
+
+view sub_view of sub_t is
+  x : in;
+end view;
+
+view rec_view of rec_t is
+  good : view sub_view;
+  goods : view (sub_view);
+  bad : view sub_view;
+end view;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("bad : view").s1("bad"),
+            "View 'sub_view' of record type 'sub_t' cannot be the view of element 'bad' of record type 'other_t'",
+        )],
+    );
+}
+
+#[test]
+fn port_subtype_must_match_mode_view() {
+    let mut builder = builder_2019();
+    builder.code("libname", BUS_PKG);
+    let code = builder.code(
+        "libname",
+        "
+use work.bus_pkg.all;
+
+entity ent is
+  port (
+    bus_o : view master_view of bus_array_t;
+    buses_o : view (master_view) of bus_t
+  );
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("bus_array_t"),
+                "The subtype of a port with view 'master_view' must be record type 'bus_t', got array type 'bus_array_t'",
+            ),
+            Diagnostic::error(
+                code.s1("of bus_t").s1("bus_t"),
+                "The subtype of a port with view 'master_view' must be an array of record type 'bus_t', got record type 'bus_t'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn mode_views_are_not_allowed_before_vhdl2019() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type rec_t is record
+  a : bit;
+end record;
+
+view rec_view of rec_t is
+  a : out;
+end view;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("rec_view"),
+            "Mode view declarations are not allowed before VHDL-2019",
+        )
+        .with_code(ErrorCode::LaterRevision)],
+    );
+}
+
+#[test]
+fn association_uses_mode_of_view_element() {
+    let mut builder = builder_2019();
+    builder.code("libname", BUS_PKG);
+    let code = builder.code(
+        "libname",
+        "
+use work.bus_pkg.all;
+
+entity master is
+  port (
+    bus_o : view master_view
+  );
+end entity;
+
+architecture a of master is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal addr, rdata : bit_vector(7 downto 0);
+  signal wdata : bit_vector(7 downto 0);
+  signal valid : bit;
+begin
+  inst: entity work.master
+    port map (
+      bus_o.addr => addr,
+      bus_o.wdata => bit_vector(wdata),
+      bus_o.rdata => bit_vector(rdata),
+      bus_o.valid => valid
+    );
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("bit_vector(wdata)"),
+            "The actual of a formal of mode out cannot be converted",
+        )],
+    );
+}
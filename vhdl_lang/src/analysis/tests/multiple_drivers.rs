@@ -0,0 +1,140 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+const PKG: &str = "
+package pkg is
+  function resolve_bit(values : bit_vector) return bit;
+  subtype rbit is resolve_bit bit;
+
+  type rec_t is record
+    field1 : bit;
+    field2 : bit;
+  end record;
+end package;
+";
+
+const SIGNALS: &str = "
+  use work.pkg.all;
+  signal clk, d, s : bit;
+  signal r : rbit;
+  signal v : bit_vector(0 to 3);
+  signal rec : rec_t;
+";
+
+/// The signals within an architecture that uses the package of a resolved subtype
+fn in_architecture_using_pkg(builder: &mut LibraryBuilder, statements: &str) -> Code {
+    builder.code("libname", PKG);
+    builder.in_architecture(SIGNALS, statements)
+}
+
+#[test]
+fn signal_driven_by_two_processes() {
+    let mut builder = LibraryBuilder::new();
+    let code = in_architecture_using_pkg(
+        &mut builder,
+        "
+  process (clk, d)
+  begin
+    if clk = '1' then
+      s <= d;
+    else
+      s <= '0';
+    end if;
+  end process;
+
+  process (d)
+  begin
+    s <= d;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("s <= d", 2).s1("s"),
+            "Signal 's' of unresolved type 'BIT' has multiple drivers",
+        )
+        .related(code.s1("s <= d").s1("s"), "Driven here")
+        .related(code.s("s <= d", 2).s1("s"), "Driven here")],
+    );
+}
+
+#[test]
+fn signal_driven_by_process_and_concurrent_assignment() {
+    let mut builder = LibraryBuilder::new();
+    let code = in_architecture_using_pkg(
+        &mut builder,
+        "
+  process (d)
+  begin
+    v <= (others => d);
+  end process;
+
+  v(1) <= d;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("v(1)"),
+            "Signal 'v' of unresolved type 'BIT_VECTOR' has multiple drivers",
+        )
+        .related(code.s1("v <=").s1("v"), "Driven here")
+        .related(code.s1("v(1)"), "Driven here")],
+    );
+}
+
+#[test]
+fn distinct_parts_and_resolved_signals_may_have_multiple_drivers() {
+    let mut builder = LibraryBuilder::new();
+    in_architecture_using_pkg(
+        &mut builder,
+        "
+  r <= d;
+  r <= clk;
+
+  v(0) <= d;
+  v(1) <= d;
+  v(2 to 3) <= d & d;
+
+  rec.field1 <= d;
+  rec.field2 <= d;
+
+  process (d)
+  begin
+    s <= d;
+    s <= not d;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn drivers_within_generate_statements_are_ignored() {
+    let mut builder = LibraryBuilder::new();
+    in_architecture_using_pkg(
+        &mut builder,
+        "
+  s <= d;
+
+  gen : if false generate
+    s <= clk;
+  end generate;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
@@ -0,0 +1,136 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+
+#[test]
+fn null_range_in_subtype_indication() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant width : natural := 8;
+  subtype sub_t is integer range 0 to -1;
+  subtype word_t is bit_vector(7 downto width);
+  signal sig : bit_vector(width - 1 downto 0);
+  signal ok : integer range 0 to width;
+  type matrix_t is array (natural range <>) of bit_vector;
+  subtype rows_t is matrix_t(0 to 1)(3 to 0);
+  -- Null ranges are the common way to declare empty arrays
+  constant empty : bit_vector(0 downto 1) := (others => '0');
+  constant nus : string(2 to 1) := (others => ' ');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(code.s1("0 to -1"), "Range 0 to -1 is null")
+                .with_code(ErrorCode::NullRange),
+            Diagnostic::warning(code.s1("7 downto width"), "Range 7 downto width is null")
+                .with_code(ErrorCode::NullRange),
+            Diagnostic::warning(code.s1("3 to 0"), "Range 3 to 0 is null")
+                .with_code(ErrorCode::NullRange),
+        ],
+    );
+}
+
+#[test]
+fn null_loop_and_generate_ranges() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    n : natural
+  );
+end entity;
+
+architecture a of ent is
+  type enum_t is (alpha, beta, gamma);
+begin
+  gen : for i in 3 to 0 generate
+  end generate;
+
+  dyn : for i in 0 to n - 1 generate
+  end generate;
+
+  process
+  begin
+    for e in gamma to alpha loop
+    end loop;
+
+    for i in 3 downto 0 loop
+    end loop;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("3 to 0"),
+                "Range 3 to 0 is null, nothing is generated",
+            )
+            .with_code(ErrorCode::NullRange),
+            Diagnostic::warning(
+                code.s1("gamma to alpha"),
+                "Range gamma to alpha is null, the loop is never executed",
+            )
+            .with_code(ErrorCode::NullRange),
+        ],
+    );
+}
+
+#[test]
+fn null_and_reversed_slices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal down : bit_vector(7 downto 0);
+  signal up : bit_vector(0 to 7);
+  signal s4, s5, s6 : bit_vector(3 downto 0);
+  signal s0 : bit_vector(0 downto 1);
+begin
+  s4 <= down(3 downto 0);
+  s5 <= down(0 to 3);
+  s6 <= up(4 to 7);
+  s0 <= down(1 to 0);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("0 to 3"),
+                "Slice direction 'to' does not match the direction 'downto' of signal 'down'",
+            )
+            .with_code(ErrorCode::SliceDirection),
+            Diagnostic::warning(
+                code.s1("1 to 0"),
+                "Range 1 to 0 is null, the slice is empty",
+            )
+            .with_code(ErrorCode::NullRange),
+        ],
+    );
+}
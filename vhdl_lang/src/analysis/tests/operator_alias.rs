@@ -0,0 +1,97 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn operator_alias_participates_in_overload_resolution() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type rec_t is record
+  a : natural;
+end record;
+
+function my_add(l, r : rec_t) return rec_t;
+function my_neg(v : rec_t) return rec_t;
+
+alias \"+\" is my_add[rec_t, rec_t return rec_t];
+alias \"-\" is my_neg[rec_t return rec_t];
+
+constant c0 : rec_t := (a => 0);
+constant c1 : rec_t := c0 + c0;
+constant c2 : rec_t := - c1;
+constant c3 : natural := 1 + 2;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("c0 + c0").s1("+").start()),
+        Some(code.s1("\"+\"").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("- c1").s1("-").start()),
+        Some(code.s1("\"-\"").pos())
+    );
+    assert_ne!(
+        root.search_reference_pos(code.source(), code.s1("1 + 2").s1("+").start()),
+        Some(code.s1("\"+\"").pos())
+    );
+}
+
+#[test]
+fn operator_alias_must_denote_a_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure my_proc(l, r : natural);
+alias \"*\" is my_proc[natural, natural];
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("\"*\""),
+            "Alias of operator \"*\" must denote a function, got procedure my_proc[NATURAL, NATURAL]",
+        )],
+    );
+}
+
+#[test]
+fn operator_alias_must_have_the_parameters_of_the_operator() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function unary(v : bit) return bit;
+function binary(l, r : bit) return bit;
+
+alias \"not\" is binary[bit, bit return bit];
+alias \"=\" is unary[bit return bit];
+alias \"and\" is unary[bit return bit];
+alias \"abs\" is unary[bit return bit];
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("\"not\""),
+                "Alias of operator \"not\" must denote a function with one parameter, got 2",
+            ),
+            Diagnostic::error(
+                code.s1("\"=\""),
+                "Alias of operator \"=\" must denote a function with two parameters, got 1",
+            ),
+        ],
+    );
+}
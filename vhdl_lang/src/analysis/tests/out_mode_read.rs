@@ -0,0 +1,85 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+use crate::data::VHDLStandard;
+
+const CODE: &str = "
+entity child is
+  port (
+    o : out bit);
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+entity ent is
+  port (
+    outp : out bit;
+    outv : out bit_vector(3 downto 0));
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+
+  procedure proc(variable v : out natural) is
+    variable tmp : natural;
+  begin
+    v := 0;
+    tmp := v;
+  end procedure;
+begin
+  sig <= outp;
+  outp <= '0';
+  outv(0) <= outv(1);
+  inst : entity work.child port map (o => outp);
+end architecture;
+";
+
+#[test]
+fn reading_out_mode_objects_is_an_error_before_vhdl_2008() {
+    for standard in [VHDLStandard::VHDL1993, VHDLStandard::VHDL2002] {
+        let mut builder = LibraryBuilder::new();
+        builder.standard(standard);
+        let code = builder.code("libname", CODE);
+
+        let diagnostics = builder.analyze();
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::error(
+                    code.s1("tmp := v").s1("v"),
+                    "interface variable 'v' of mode out cannot be read before VHDL-2008",
+                )
+                .with_code(ErrorCode::LaterRevision),
+                Diagnostic::error(
+                    code.s1("<= outp").s1("outp"),
+                    "interface signal 'outp' of mode out cannot be read before VHDL-2008",
+                )
+                .with_code(ErrorCode::LaterRevision),
+                Diagnostic::error(
+                    code.s1("outv(1)"),
+                    "interface signal 'outv' of mode out cannot be read before VHDL-2008",
+                )
+                .with_code(ErrorCode::LaterRevision),
+            ],
+        );
+    }
+}
+
+#[test]
+fn reading_out_mode_objects_is_allowed_in_vhdl_2008() {
+    for standard in [VHDLStandard::VHDL2008, VHDLStandard::VHDL2019] {
+        let mut builder = LibraryBuilder::new();
+        builder.standard(standard);
+        builder.code("libname", CODE);
+
+        let diagnostics = builder.analyze();
+        check_no_diagnostics(&diagnostics);
+    }
+}
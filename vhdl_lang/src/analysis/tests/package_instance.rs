@@ -158,7 +158,7 @@ package bad_pkg2 is new work.gpkg
             ),
             Diagnostic::error(
                 code.s1("natural"),
-                "subtype 'NATURAL' cannot be used in an expression",
+                "Expected value, got subtype 'NATURAL', did you mean a qualified expression NATURAL'(...) or a constant?",
             ),
             Diagnostic::error(
                 code.s1("=> work").s1("work"),
@@ -203,6 +203,99 @@ package rec_pkg is new work.gpkg
     check_no_diagnostics(&diagnostics);
 }
 
+#[test]
+fn constrained_type_actual_is_used_within_the_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t
+  );
+  signal s : type_t;
+end package;
+
+package arr_pkg is new work.gpkg
+  generic map (
+    type_t => integer_vector(0 to 3)
+  );
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal x : integer;
+begin
+  work.arr_pkg.s <= (1, 2, 3);
+  x <= work.arr_pkg.s(4);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("(1, 2, 3)"),
+                "Too few elements in aggregate, got 3 but the index range 0 to 3 has 4",
+            ),
+            Diagnostic::error(
+                code.s1("s(4)").s1("4"),
+                "Index 4 is outside of the index range 0 to 3 of signal 's'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn constraint_of_type_actual_is_checked() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+      field: integer_vector;
+  end record;
+end package;
+
+package gpkg is
+  generic (
+    type type_t
+  );
+end package;
+
+package nat_pkg is new work.gpkg
+  generic map (
+    type_t => natural(0 to 3)
+  );
+
+use work.pkg.rec_t;
+package rec_pkg is new work.gpkg
+  generic map (
+    type_t => rec_t(missing(0 to 3))
+  );
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("natural(0 to 3)").s1("natural"),
+                "Array constraint cannot be used for subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1("missing"),
+                "No declaration of 'missing' within record type 'rec_t'",
+            ),
+        ],
+    );
+}
+
 #[test]
 fn interface_subprogram() {
     let mut builder = LibraryBuilder::new();
@@ -530,3 +623,211 @@ end package;
         vec![code.s("sub_t", 1).pos(), code.s("sub_t", 3).pos(),]
     );
 }
+
+#[test]
+fn generic_map_must_associate_generics_without_default() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t;
+    constant c0 : integer;
+    constant c1 : integer := 0
+  );
+end package;
+
+package good_pkg is new work.gpkg
+  generic map (
+    type_t => integer,
+    c0 => 0
+  );
+
+package missing_pkg is new work.gpkg
+  generic map (
+    c0 => 0,
+    c1 => open
+  );
+
+package open_pkg is new work.gpkg
+  generic map (
+    type_t => integer,
+    c0 => open
+  );
+
+package no_map_pkg is new work.gpkg;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s("work.gpkg", 2), "No association of type 'type_t'")
+                .related(code.s1("type_t"), "Defined here"),
+            Diagnostic::error(
+                code.s("work.gpkg", 3),
+                "No association of interface constant 'c0'",
+            )
+            .related(code.s1("c0"), "Defined here"),
+            Diagnostic::error(code.s("work.gpkg", 4), "No association of type 'type_t'")
+                .related(code.s1("type_t"), "Defined here"),
+            Diagnostic::error(
+                code.s("work.gpkg", 4),
+                "No association of interface constant 'c0'",
+            )
+            .related(code.s1("c0"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn generic_may_not_be_associated_twice() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t;
+    constant c0 : integer
+  );
+end package;
+
+package bad_pkg is new work.gpkg
+  generic map (
+    integer,
+    c0 => 0,
+    type_t => natural,
+    c0 => 1
+  );
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("type_t => natural").s1("type_t"),
+                "Type 'type_t' is associated more than once",
+            )
+            .related(
+                code.s1("integer,").s1("integer"),
+                "Previously associated here",
+            ),
+            Diagnostic::error(
+                code.s1("c0 => 1").s1("c0"),
+                "Interface constant 'c0' is associated more than once",
+            )
+            .related(code.s1("c0 => 0").s1("c0"), "Previously associated here"),
+        ],
+    );
+}
+
+#[test]
+fn interface_subprogram_without_default_must_be_associated() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t;
+    function to_string(value : type_t) return string
+  );
+end package;
+
+package pkg is new work.gpkg generic map (type_t => integer);
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("work.gpkg", 1),
+            "No association of to_string[type_t return STRING]",
+        )
+        .related(code.s1("to_string"), "Defined here")],
+    );
+}
+
+#[test]
+fn interface_subprogram_box_default_resolves_visible_subprogram() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t;
+    function to_string(value : type_t) return string is <>;
+    function \"<\"(l, r : type_t) return boolean is <>
+  );
+end package;
+
+package pkg is
+  type rec_t is record
+    f : natural;
+  end record;
+
+  function to_string(value : rec_t) return string;
+  function to_string(value : boolean) return string;
+  function \"<\"(l, r : rec_t) return boolean;
+
+  package int_pkg is new work.gpkg generic map (type_t => natural);
+  package rec_pkg is new work.gpkg generic map (type_t => rec_t);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn interface_subprogram_box_default_reports_near_misses() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t;
+    function to_string(value : type_t) return string is <>
+  );
+end package;
+
+package pkg is
+  type rec_t is record
+    f : natural;
+  end record;
+
+  function to_string(value : boolean) return string;
+  function to_string(value : bit) return string;
+
+  package ipkg is new work.gpkg generic map (type_t => rec_t);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("work.gpkg"),
+            "No visible subprogram matches the default <> of to_string[type_t return STRING]",
+        )
+        .related(code.s1("to_string"), "Defined here")
+        .related(
+            code.s("to_string", 2),
+            "Does not match to_string[BOOLEAN return STRING]",
+        )
+        .related(
+            code.s("to_string", 3),
+            "Does not match to_string[BIT return STRING]",
+        )],
+    );
+}
@@ -0,0 +1,107 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn physical_literal_within_range() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+constant c0 : time := 1 hr;
+constant c1 : time := 2.5 ns;
+constant c2 : time := -9223 sec;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn physical_literal_outside_of_range() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type distance_t is range 0 to 1000
+units
+  mm;
+  m = 1000 mm;
+  km = 1000 m;
+end units;
+
+constant c0 : distance_t := 1 m;
+constant c1 : distance_t := 1 km;
+constant c2 : time := 10000000 hr;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::error(
+                code.s1("1 km"),
+                "Physical literal 1 km is outside of the range 0 mm to 1000 mm of physical type 'distance_t'",
+            ),
+            Diagnostic::error(
+                code.s1("10000000 hr"),
+                "Physical literal 10000000 hr overflows the range of physical type 'TIME'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn secondary_unit_must_be_positive_integer_multiple() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type distance_t is range 0 to 1000
+units
+  mm;
+  cm = 10 mm;
+  half = 0.5 cm;
+  none = 0 mm;
+end units;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::error(
+                code.s1("half"),
+                "Secondary unit 'half' must be a positive integer multiple of a previously declared unit, got 0.5 cm",
+            ),
+            Diagnostic::error(
+                code.s1("none"),
+                "Secondary unit 'none' must be a positive integer multiple of a previously declared unit, got 0 mm",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn secondary_unit_overflows_primary_unit() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type distance_t is range 0 to 1000
+units
+  mm;
+  km = 1000000 mm;
+  big = 1000000000000000 km;
+end units;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::error(
+            code.s1("big"),
+            "Physical literal 1000000000000000 km overflows the range of physical type 'distance_t'",
+        )],
+    );
+}
@@ -0,0 +1,381 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::PortGrouping;
+use crate::data::{Fix, Position};
+
+fn grouping(ports: &[&str], package_name: &str) -> PortGrouping {
+    PortGrouping {
+        library_name: "libname".to_owned(),
+        entity_name: "ent".to_owned(),
+        ports: ports.iter().map(|port| port.to_string()).collect(),
+        port_name: "inputs".to_owned(),
+        record_name: "inputs_t".to_owned(),
+        package_library: "libname".to_owned(),
+        package_name: package_name.to_owned(),
+    }
+}
+
+/// Apply fixes of a single source to its code
+fn apply(code: &str, fixes: &[Fix]) -> String {
+    let offset = |pos: Position| -> usize {
+        code.split_inclusive('\n')
+            .take(pos.line as usize)
+            .map(|line| line.len())
+            .sum::<usize>()
+            + pos.character as usize
+    };
+
+    let mut result = code.to_owned();
+    for fix in fixes.iter().rev() {
+        result.replace_range(
+            offset(fix.pos.start())..offset(fix.pos.end()),
+            &fix.replacement,
+        );
+    }
+    result
+}
+
+fn check_analyzes(code: &str) {
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", code);
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn groups_ports_of_entity_into_record() {
+    let code = "
+package pkg is
+end package;
+
+entity ent is
+  port (
+    clk : in bit;
+    -- The data bus
+    data, valid : in bit_vector(7 downto 0);
+    ready : out bit;
+    last : in bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+  ready <= data(0) and last;
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, last, ready : bit;
+  signal data, valid : bit_vector(7 downto 0);
+begin
+  inst : entity work.ent
+    port map (
+      clk => clk,
+      data => data,
+      valid => valid,
+      ready => ready,
+      last => last
+    );
+end architecture;
+";
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", code);
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let fixes = root
+        .group_ports_into_record(&grouping(&["data", "last"], "pkg"))
+        .unwrap();
+    let result = apply(code, &fixes);
+    assert_eq!(
+        result,
+        "
+package pkg is
+
+  type inputs_t is record
+    data : bit_vector(7 downto 0);
+    last : bit;
+  end record;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+  port (
+    clk : in bit;
+    -- The data bus
+    valid : in bit_vector(7 downto 0);
+    ready : out bit;
+    inputs : in inputs_t
+  );
+end entity;
+
+architecture a of ent is
+begin
+  ready <= inputs.data(0) and inputs.last;
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, last, ready : bit;
+  signal data, valid : bit_vector(7 downto 0);
+begin
+  inst : entity work.ent
+    port map (
+      clk => clk,
+      inputs.data => data,
+      valid => valid,
+      ready => ready,
+      inputs.last => last
+    );
+end architecture;
+"
+    );
+    check_analyzes(&result);
+}
+
+#[test]
+fn groups_ports_of_bound_components() {
+    let code = "
+package comps is
+  constant width : natural := 4;
+
+  component ent is
+    port (
+      a : in bit;
+      b : in bit_vector(width - 1 downto 0);
+      c : out bit
+    );
+  end component;
+end package;
+
+use work.comps.all;
+
+entity ent is
+  port (
+    a : in bit;
+    b : in bit_vector(width - 1 downto 0);
+    c : out bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+  c <= a;
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal a, c : bit;
+  signal b : bit_vector(3 downto 0);
+begin
+  inst : component work.comps.ent
+    port map (
+      a => a,
+      b => b,
+      c => c
+    );
+end architecture;
+";
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", code);
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let fixes = root
+        .group_ports_into_record(&grouping(&["a", "b"], "comps"))
+        .unwrap();
+    let result = apply(code, &fixes);
+    assert_eq!(
+        result,
+        "
+package comps is
+  constant width : natural := 4;
+
+  type inputs_t is record
+    a : bit;
+    b : bit_vector(width - 1 downto 0);
+  end record;
+
+  component ent is
+    port (
+      inputs : in inputs_t;
+      c : out bit
+    );
+  end component;
+end package;
+
+use work.comps.all;
+
+entity ent is
+  port (
+    inputs : in inputs_t;
+    c : out bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+  c <= inputs.a;
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal a, c : bit;
+  signal b : bit_vector(3 downto 0);
+begin
+  inst : component work.comps.ent
+    port map (
+      inputs.a => a,
+      inputs.b => b,
+      c => c
+    );
+end architecture;
+"
+    );
+    check_analyzes(&result);
+}
+
+#[test]
+fn ports_that_cannot_be_grouped() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type inputs_t is (alpha, beta);
+end package;
+
+package other is
+end package;
+
+entity ent is
+  generic (
+    width : natural
+  );
+  port (
+    a : in bit;
+    b : out bit;
+    c : in bit_vector(width - 1 downto 0);
+    d : in bit := '0';
+    inputs : in bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal a, b, inputs : bit;
+  signal c : bit_vector(3 downto 0);
+begin
+  inst : entity work.ent
+    generic map (width => 4)
+    port map (a, b, c, open, inputs);
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let error = |ports: &[&str], package_name: &str| {
+        root.group_ports_into_record(&grouping(ports, package_name))
+            .unwrap_err()
+    };
+
+    assert_eq!(
+        error(&["a"], "missing"),
+        "No such package 'libname.missing'"
+    );
+    assert_eq!(
+        error(&["a"], "pkg"),
+        "Package 'pkg' already declares 'inputs_t'"
+    );
+    assert_eq!(error(&["x"], "other"), "Entity 'ent' has no port 'x'");
+    assert_eq!(
+        error(&["a", "b"], "other"),
+        "Cannot group ports of different modes into a record"
+    );
+    assert_eq!(
+        error(&["d"], "other"),
+        "Cannot group port 'd' since it has a default value"
+    );
+    assert_eq!(
+        error(&["c", "inputs"], "other"),
+        "The subtype of port 'c' depends on the generic 'width'"
+    );
+    assert_eq!(
+        error(&["a"], "other"),
+        "Entity 'ent' already declares 'inputs'"
+    );
+    assert!(error(&["a", "inputs"], "other").starts_with("Cannot rewrite the positional port map"));
+}
+
+#[test]
+fn groups_ports_into_record_of_extended_package() {
+    let code = "
+package \\Pkg\\ is
+end package \\Pkg\\;
+
+entity ent is
+  port (
+    a : in bit;
+    b : in bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+";
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", code);
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let mut grouping = grouping(&["a", "b"], "\\Pkg\\");
+    grouping.port_name = "\\In Puts\\".to_owned();
+    let fixes = root.group_ports_into_record(&grouping).unwrap();
+    let result = apply(code, &fixes);
+    assert_eq!(
+        result,
+        "
+package \\Pkg\\ is
+
+  type inputs_t is record
+    a : bit;
+    b : bit;
+  end record;
+end package \\Pkg\\;
+
+use work.\\Pkg\\.all;
+
+entity ent is
+  port (
+    \\In Puts\\ : in inputs_t
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+"
+    );
+    check_analyzes(&result);
+}
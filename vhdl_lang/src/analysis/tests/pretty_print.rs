@@ -0,0 +1,243 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn pretty_prints_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta, 'c');
+  type int_t is range 10 downto 0;
+  type real_t is range 0.0 to 1.5;
+  type dist_t is range 0 to 1000000
+    units
+      um;
+      mm = 1000 um;
+      m = 1000 mm;
+    end units;
+  type arr_t is array (integer range <>, enum_t range <>) of bit;
+  type word_t is array (0 to 15) of bit;
+  subtype sub_t is integer range 2 to 9;
+  subtype byte_t is bit_vector(7 downto 0);
+  type rec_t is record
+    field : byte_t;
+    value : int_t;
+  end record;
+  type ptr_t is access rec_t;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let pretty = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+            .pretty()
+            .to_string()
+    };
+
+    assert_eq!(pretty("enum_t is"), "type enum_t is (alpha, beta, 'c');");
+    assert_eq!(pretty("int_t is"), "type int_t is range 10 downto 0;");
+    assert_eq!(pretty("real_t is"), "type real_t is range 0.0 to 1.5;");
+    assert_eq!(
+        pretty("dist_t is"),
+        "\
+type dist_t is range 0 to 1000000
+  units
+    um;
+    mm = 1000 um;
+    m = 1000000 um;
+  end units;"
+    );
+    assert_eq!(
+        pretty("arr_t is"),
+        "type arr_t is array (INTEGER range <>, enum_t range <>) of BIT;"
+    );
+    assert_eq!(
+        pretty("word_t is"),
+        "type word_t is array (0 to 15) of BIT;"
+    );
+    assert_eq!(pretty("sub_t is"), "subtype sub_t is INTEGER range 2 to 9;");
+    assert_eq!(
+        pretty("byte_t is"),
+        "subtype byte_t is BIT_VECTOR(7 downto 0);"
+    );
+    assert_eq!(
+        pretty("rec_t is"),
+        "\
+type rec_t is record
+  field : byte_t;
+  value : int_t;
+end record;"
+    );
+    assert_eq!(pretty("ptr_t is"), "type ptr_t is access rec_t;");
+}
+
+#[test]
+fn pretty_prints_objects_and_subprograms() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  constant width : natural := 2 * 4;
+  constant state : enum_t := beta;
+  constant deferred : natural;
+  signal sig : bit_vector(width - 1 downto 0);
+  signal idx : integer range 0 to width;
+  function fun(a : natural; signal s : in bit) return enum_t;
+  procedure proc(variable v : inout integer; constant c : in natural := 3);
+  alias fun_alias is fun[natural, bit return enum_t];
+end package;
+
+package body pkg is
+  constant deferred : natural := 1;
+
+  function fun(a : natural; signal s : in bit) return enum_t is
+  begin
+    return alpha;
+  end function;
+
+  procedure proc(variable v : inout integer; constant c : in natural := 3) is
+  begin
+  end procedure;
+end package body;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let pretty = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+            .pretty()
+            .to_string()
+    };
+
+    assert_eq!(pretty("width :"), "constant width : NATURAL := 8;");
+    assert_eq!(pretty("state :"), "constant state : enum_t := beta;");
+    assert_eq!(pretty("deferred :"), "constant deferred : NATURAL;");
+    assert_eq!(pretty("sig :"), "signal sig : BIT_VECTOR(7 downto 0);");
+    assert_eq!(pretty("idx :"), "signal idx : INTEGER range 0 to 8;");
+    assert_eq!(
+        pretty("fun("),
+        "\
+function fun(
+  constant a : in NATURAL;
+  signal s : in BIT
+) return enum_t;"
+    );
+    assert_eq!(
+        pretty("proc("),
+        "\
+procedure proc(
+  variable v : inout INTEGER;
+  constant c : in NATURAL := 3
+);"
+    );
+    assert_eq!(
+        pretty("fun_alias"),
+        "alias fun_alias is fun[NATURAL, BIT return enum_t];"
+    );
+}
+
+#[test]
+fn pretty_prints_entities_and_components() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    width : natural := 8;
+    init : bit := '0'
+  );
+  port (
+    clk : in bit;
+    data : out bit_vector(width - 1 downto 0) := (others => init)
+  );
+end entity;
+
+architecture a of ent is
+  component comp is
+    port (
+      d : in bit_vector(3 downto 0)
+    );
+  end component;
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ent = root
+        .search_reference(code.source(), code.s1("ent is").start())
+        .unwrap();
+    assert_eq!(
+        ent.pretty().to_string(),
+        "\
+entity ent is
+  generic (
+    width : NATURAL := 8;
+    init : BIT := '0'
+  );
+  port (
+    clk : in BIT;
+    data : out BIT_VECTOR(width - 1 downto 0) := (others => init)
+  );
+end entity;"
+    );
+    assert_eq!(
+        ent.instantiation_template("ent_inst"),
+        Some(
+            "\
+ent_inst : entity work.ent
+  generic map (
+    width => width,
+    init => init
+  )
+  port map (
+    clk => clk,
+    data => data
+  );"
+            .to_owned()
+        )
+    );
+
+    let comp = root
+        .search_reference(code.source(), code.s1("comp is").start())
+        .unwrap();
+    assert_eq!(
+        comp.pretty().to_string(),
+        "\
+component comp is
+  port (
+    d : in BIT_VECTOR(3 downto 0)
+  );
+end component;"
+    );
+    assert_eq!(
+        comp.instantiation_template("inst"),
+        Some(
+            "\
+inst : comp
+  port map (
+    d => d
+  );"
+            .to_owned()
+        )
+    );
+}
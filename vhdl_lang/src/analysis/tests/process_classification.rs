@@ -0,0 +1,134 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{
+    ActiveLevel, ClassifiedProcess, ClockEdge, ClockedProcess, ProcessKind, ProcessReset,
+    ResetStyle,
+};
+
+#[test]
+fn classify_processes_of_architecture() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, rst, d, q, r, c : bit;
+begin
+  comb : process (d)
+  begin
+    c <= d;
+  end process;
+
+  reg_async : process (clk, rst)
+  begin
+    if rst = '0' then
+      q <= '0';
+    elsif falling_edge(clk) then
+      q <= d;
+    end if;
+  end process;
+
+  blk : block
+  begin
+    gen : for i in 0 to 1 generate
+      reg_sync : process
+      begin
+        wait until clk = '1' and clk'event;
+        if rst = '1' then
+          r <= '0';
+        else
+          r <= d;
+        end if;
+      end process;
+    end generate;
+  end block;
+
+  process
+  begin
+    wait for 10 ns;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let clk = root
+        .search_reference(code.source(), code.s1("clk").start())
+        .map(|ent| ent.id());
+    let rst = root
+        .search_reference(code.source(), code.s1("rst").start())
+        .map(|ent| ent.id());
+    let d = root
+        .search_reference(code.source(), code.s1("d, q").start())
+        .map(|ent| ent.id());
+    let (clk, rst, d) = (clk.unwrap(), rst.unwrap(), d.unwrap());
+
+    assert_eq!(
+        root.classify_processes(
+            &root.symbol_utf8("libname"),
+            &root.symbol_utf8("ent"),
+            &root.symbol_utf8("a"),
+        ),
+        Some(vec![
+            ClassifiedProcess {
+                label: Some(code.s1("comb").ident()),
+                kind: ProcessKind::Combinational,
+                read_signals: vec![d],
+            },
+            ClassifiedProcess {
+                label: Some(code.s1("reg_async").ident()),
+                kind: ProcessKind::Clocked(ClockedProcess {
+                    clock: Some(clk),
+                    edge: ClockEdge::Falling,
+                    clock_pos: code.s1("falling_edge(clk)").pos(),
+                    reset: Some(ProcessReset {
+                        signal: Some(rst),
+                        style: ResetStyle::Asynchronous,
+                        level: ActiveLevel::Low,
+                        pos: code.s1("rst = '0'").pos(),
+                    }),
+                }),
+                read_signals: vec![rst, clk, d],
+            },
+            ClassifiedProcess {
+                label: Some(code.s1("reg_sync").ident()),
+                kind: ProcessKind::Clocked(ClockedProcess {
+                    clock: Some(clk),
+                    edge: ClockEdge::Rising,
+                    clock_pos: code.s1("clk = '1' and clk'event").pos(),
+                    reset: Some(ProcessReset {
+                        signal: Some(rst),
+                        style: ResetStyle::Synchronous,
+                        level: ActiveLevel::High,
+                        pos: code.s1("rst = '1'").pos(),
+                    }),
+                }),
+                read_signals: vec![clk, rst, d],
+            },
+            ClassifiedProcess {
+                label: None,
+                kind: ProcessKind::Other,
+                read_signals: vec![],
+            },
+        ])
+    );
+
+    assert_eq!(
+        root.classify_processes(
+            &root.symbol_utf8("libname"),
+            &root.symbol_utf8("ent"),
+            &root.symbol_utf8("missing"),
+        ),
+        None
+    );
+}
@@ -5,6 +5,8 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::data::ErrorCode;
+use crate::data::VHDLStandard;
 
 #[test]
 fn error_on_missing_protected_body() {
@@ -275,3 +277,307 @@ end package;",
         Some(code.s1("prot_t").pos())
     );
 }
+
+#[test]
+fn shared_variable_must_be_of_protected_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+  end protected;
+
+  shared variable prot : prot_t;
+  shared variable count : natural;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+  end protected body;
+end package body;
+
+package gpkg is
+  generic (type gen_t);
+  shared variable gen : gen_t;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("count"),
+            "Shared variable 'count' must be of a protected type, got subtype 'NATURAL'",
+        )
+        .with_code(ErrorCode::SharedVariableType)],
+    );
+}
+
+#[test]
+fn shared_variable_may_be_of_any_type_in_vhdl1993() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL1993);
+    builder.code(
+        "libname",
+        "
+package pkg is
+  shared variable count : natural;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn only_variables_may_be_of_protected_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+  end protected;
+
+  signal sig : prot_t;
+
+  procedure proc(variable var : inout prot_t; const : prot_t; signal sig_param : in prot_t);
+end package;
+
+package body pkg is
+  type prot_t is protected body
+  end protected body;
+
+  procedure proc(variable var : inout prot_t; const : prot_t; signal sig_param : in prot_t) is
+  begin
+  end procedure;
+end package body;",
+    );
+
+    let diagnostics = builder.analyze();
+    let mut expected = vec![Diagnostic::error(
+        code.s1("sig : prot_t").s1("sig"),
+        "Signal 'sig' cannot be of protected type 'prot_t', only variables may be of a protected type",
+    )];
+    for occurrence in [1, 2] {
+        expected.push(Diagnostic::error(
+            code.s("const", occurrence),
+            "Constant 'const' cannot be of protected type 'prot_t', only variables may be of a protected type",
+        ));
+        expected.push(Diagnostic::error(
+            code.s("sig_param", occurrence),
+            "Signal 'sig_param' cannot be of protected type 'prot_t', only variables may be of a protected type",
+        ));
+    }
+    check_diagnostics(diagnostics, expected);
+}
+
+#[test]
+fn file_and_access_types_may_not_be_of_protected_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+  end protected;
+
+  type file_t is file of prot_t;
+  type ptr_t is access prot_t;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+  end protected body;
+end package body;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("prot_t", 2),
+                "A file type cannot contain values of protected type 'prot_t'",
+            ),
+            Diagnostic::error(
+                code.s("prot_t", 3),
+                "An access type cannot designate protected type 'prot_t'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn access_types_may_designate_protected_type_since_vhdl2019() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+  end protected;
+
+  type ptr_t is access prot_t;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+  end protected body;
+end package body;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn resolves_overloaded_methods_of_shared_variable() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+    procedure put(value : integer);
+    procedure put(value : boolean);
+    impure function get return integer;
+  end protected;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+    procedure put(value : integer) is
+    begin
+    end procedure;
+
+    procedure put(value : boolean) is
+    begin
+    end procedure;
+
+    impure function get return integer is
+    begin
+      return 0;
+    end function;
+  end protected body;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  shared variable sv : prot_t;
+begin
+  main : process
+    variable v : integer;
+  begin
+    sv.put(value => 1);
+    sv.put(true);
+    v := sv.get;
+    sv.put(v, false);
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("sv.put(v, false)").s1("put"),
+            "Could not resolve call to 'put'",
+        )
+        .related(code.s("put", 1), "Does not match put[INTEGER]")
+        .related(code.s("put", 2), "Does not match put[BOOLEAN]")],
+    );
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("sv.put(value").s1("put").start()),
+        Some(code.s("put", 1).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("sv.put(true").s1("put").start()),
+        Some(code.s("put", 2).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("sv.get").s1("get").start()),
+        Some(code.s("get", 1).pos())
+    );
+}
+
+#[test]
+fn methods_of_protected_type_in_package_instance_use_instantiated_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (type T);
+
+  type rec_t is record
+    value : T;
+  end record;
+
+  type prot_t is protected
+    procedure put(rec : rec_t);
+    impure function get return rec_t;
+    procedure merge(variable other : inout prot_t);
+  end protected;
+end package;
+
+package body gpkg is
+  type prot_t is protected body
+    variable stored : rec_t;
+
+    procedure put(rec : rec_t) is
+    begin
+      stored := rec;
+    end procedure;
+
+    impure function get return rec_t is
+    begin
+      return stored;
+    end function;
+
+    procedure merge(variable other : inout prot_t) is
+    begin
+    end procedure;
+  end protected body;
+end package body;
+
+package ipkg is new work.gpkg generic map (T => integer);
+
+use work.ipkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  shared variable sv : prot_t;
+  shared variable sv2 : prot_t;
+begin
+  main : process
+    variable r : rec_t;
+    variable v : integer;
+  begin
+    sv.put(r);
+    r := sv.get;
+    v := sv.get.value + 1;
+    sv.merge(sv2);
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let merge = root
+        .search_reference(code.source(), code.s1("sv.merge").s1("merge").start())
+        .unwrap();
+    assert_eq!(merge.decl_pos(), Some(&code.s1("merge").pos()));
+}
@@ -0,0 +1,159 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn resolves_names_in_psl_comments_and_directives() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_psl_comments();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, req, ack : bit;
+  signal busy : boolean;
+  constant latency : natural := 2;
+  -- psl default clock is rising_edge(clk);
+begin
+  -- psl handshake : assert always (req -> next[latency] ack) report \"no ack\";
+  assume always {req} |=> (busy until ack = '1');
+  cover {req; busy[*1 to inf]; rose(ack)} @ rising_edge(clk);
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    for (name, occurence) in [
+        ("clk", 2),
+        ("req", 2),
+        ("ack", 2),
+        ("latency", 2),
+        ("busy", 2),
+        ("req", 4),
+        ("ack", 5),
+        ("clk", 3),
+    ] {
+        let decl = if name == "latency" {
+            code.s1("latency : natural").s1("latency")
+        } else {
+            code.s1(name)
+        };
+        assert_eq!(
+            root.search_reference_pos(code.source(), code.s(name, occurence).end()),
+            Some(decl.pos()),
+            "{name} {occurence}"
+        );
+    }
+}
+
+#[test]
+fn checks_psl_names_and_types() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_psl_comments();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : bit;
+  signal cnt : natural;
+  -- psl default clock is clk = '1';
+begin
+  -- psl assert always cnt -> next missing;
+  assert always (clk = '1') -> next[clk] (cnt > 0);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("always cnt").s1("cnt"),
+                "subtype 'NATURAL' cannot be implictly converted to type 'BOOLEAN'. Operator ?? is not defined for this type.",
+            ),
+            Diagnostic::error(code.s1("missing"), "No declaration of 'missing'"),
+            Diagnostic::error(code.s1("next[clk]").s1("clk"), "Expected integer type, got type 'BIT'"),
+        ],
+    );
+}
+
+#[test]
+fn plain_concurrent_assertion_is_not_a_psl_directive() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal cover : boolean;
+begin
+  assert cover report \"fail\";
+  cover <= true;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("cover", 3).end()),
+        Some(code.s1("cover").pos())
+    );
+}
+
+#[test]
+fn psl_comments_are_opt_in() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  -- psl assert always missing;
+end architecture;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn prose_comments_starting_with_psl_are_comments() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_psl_comments();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  -- PSL properties for this block are in a separate vunit
+  signal clk : bit;
+begin
+  -- psl: see the verification plan
+  clk <= not clk; -- psl assert is done elsewhere
+end architecture;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
@@ -0,0 +1,176 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{ActiveLevel, ResetConvention, ResetStyle};
+
+const SIGNALS: &str = "
+  signal clk, rst, rst_n, d, q, q1, q2, q3 : bit;
+  signal srst : boolean;
+";
+
+const PROCESSES: &str = "
+  async_high : process (clk, rst)
+  begin
+    if rst = '1' then
+      q <= '0';
+    elsif rising_edge(clk) then
+      q <= d;
+    end if;
+  end process;
+
+  sync_low : process (clk)
+  begin
+    if clk'event and clk = '1' then
+      if rst_n = '0' then
+        q1 <= '0';
+      else
+        q1 <= d;
+      end if;
+    end if;
+  end process;
+
+  sync_wait : process
+  begin
+    wait until rising_edge(clk);
+    if srst then
+      q2 <= '0';
+    else
+      q2 <= d;
+    end if;
+  end process;
+
+  no_reset : process (clk)
+  begin
+    if falling_edge(clk) then
+      q3 <= d;
+    end if;
+  end process;
+";
+
+#[test]
+fn no_convention_is_not_checked() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_architecture(SIGNALS, PROCESSES);
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn reset_style_must_follow_convention() {
+    let mut builder = LibraryBuilder::new();
+    builder.reset_convention(ResetConvention {
+        style: Some(ResetStyle::Synchronous),
+        level: None,
+        required: false,
+    });
+    let code = builder.in_architecture(SIGNALS, PROCESSES);
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("rst = '1'"),
+            "Asynchronous reset does not follow the synchronous reset convention of the project",
+        )],
+    );
+}
+
+#[test]
+fn reset_level_must_follow_convention() {
+    let mut builder = LibraryBuilder::new();
+    builder.reset_convention(ResetConvention {
+        style: None,
+        level: Some(ActiveLevel::High),
+        required: false,
+    });
+    let code = builder.in_architecture(SIGNALS, PROCESSES);
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("rst_n = '0'"),
+            "Reset is active low but the reset convention of the project is active high",
+        )],
+    );
+}
+
+#[test]
+fn missing_reset_when_required() {
+    let mut builder = LibraryBuilder::new();
+    builder.reset_convention(ResetConvention {
+        style: Some(ResetStyle::Asynchronous),
+        level: Some(ActiveLevel::Low),
+        required: true,
+    });
+    let code = builder.in_architecture(SIGNALS, PROCESSES);
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("rst = '1'"),
+                "Reset is active high but the reset convention of the project is active low",
+            ),
+            Diagnostic::warning(
+                code.s1("rst_n = '0'"),
+                "Synchronous reset does not follow the asynchronous reset convention of the project",
+            ),
+            Diagnostic::warning(
+                code.s1("if srst").s1("srst"),
+                "Synchronous reset does not follow the asynchronous reset convention of the project",
+            ),
+            Diagnostic::warning(
+                code.s1("if srst").s1("srst"),
+                "Reset is active high but the reset convention of the project is active low",
+            ),
+            Diagnostic::warning(
+                code.s1("falling_edge(clk)"),
+                "Clocked process has no reset but the reset convention of the project requires one",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn enable_is_not_a_reset() {
+    let mut builder = LibraryBuilder::new();
+    builder.reset_convention(ResetConvention {
+        style: None,
+        level: None,
+        required: true,
+    });
+    let code = builder.in_architecture(
+        SIGNALS,
+        "
+  process (clk)
+  begin
+    if rising_edge(clk) then
+      if d = '1' then
+        q <= rst;
+      end if;
+    end if;
+  end process;
+
+  process (d, rst)
+  begin
+    q1 <= d and rst;
+  end process;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("rising_edge(clk)"),
+            "Clocked process has no reset but the reset convention of the project requires one",
+        )],
+    );
+}
@@ -0,0 +1,167 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn resolves_resolution_functions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function resolve_bit(values : bit_vector) return bit;
+  function resolve_bit(value : bit) return bit;
+
+  subtype rbit_t is resolve_bit bit;
+  subtype rbits_t is (resolve_bit) bit_vector;
+
+  type rec_t is record
+    field : bit;
+    arr : bit_vector(0 to 1);
+  end record;
+  subtype rrec_t is (field resolve_bit, arr (resolve_bit)) rec_t;
+end package;
+
+package pkg2 is
+  signal s : work.pkg.resolve_bit bit;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let decl_pos = code.s1("resolve_bit").pos();
+    for occurence in 3..=7 {
+        assert_eq!(
+            root.search_reference_pos(code.source(), code.s("resolve_bit", occurence).start()),
+            Some(decl_pos.clone()),
+            "occurence {occurence}"
+        );
+    }
+}
+
+#[test]
+fn checks_resolution_functions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type bits_t is array (natural range <>) of bit;
+  subtype word_t is bit_vector(0 to 7);
+
+  function ambiguous(values : bits_t) return bit;
+  function ambiguous(values : bit_vector) return bit;
+  function wrong_return(values : bit_vector) return boolean;
+  function constrained(values : word_t) return bit;
+  function two_params(values : bit_vector; extra : bit) return bit;
+  constant not_function : bit := '0';
+
+  subtype s1_t is ambiguous bit;
+  subtype s2_t is wrong_return bit;
+  subtype s3_t is constrained bit;
+  subtype s4_t is two_params bit;
+  subtype s5_t is not_function bit;
+  subtype s6_t is (constrained) bit;
+
+  type rec_t is record
+    field : bit;
+  end record;
+  subtype s7_t is (missing wrong_return) rec_t;
+  subtype s8_t is (field wrong_return) rec_t;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("ambiguous", 3),
+                "Ambiguous resolution function 'ambiguous' for type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s("wrong_return", 2),
+                "No resolution function 'wrong_return' for type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s("constrained", 2),
+                "No resolution function 'constrained' for type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s("two_params", 2),
+                "No resolution function 'two_params' for type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s("not_function", 2),
+                "constant 'not_function' cannot be used as a resolution function",
+            ),
+            Diagnostic::error(
+                code.s("constrained", 3),
+                "Element resolution of non-array type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s1("missing"),
+                "No declaration of 'missing' within record type 'rec_t'",
+            ),
+            Diagnostic::error(
+                code.s("wrong_return", 4),
+                "No resolution function 'wrong_return' for type 'BIT'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn checks_nested_element_resolutions() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function resolve_bit(values : bit_vector) return bit;
+
+  type rec_t is record
+    field : bit;
+  end record;
+  type rec_arr_t is array (natural range <>) of rec_t;
+  type matrix_t is array (natural range <>) of bit_vector(0 to 1);
+
+  subtype good1_t is ((field resolve_bit)) rec_arr_t;
+  subtype good2_t is ((resolve_bit)) matrix_t;
+  subtype bad1_t is ((resolve_bit)) bit_vector;
+  subtype bad2_t is ((field resolve_bit)) matrix_t;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("resolve_bit", 4),
+                "Element resolution of non-array type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s1("bad2_t is ((field").s1("field"),
+                "Record element resolution of non-record array type 'BIT_VECTOR'",
+            ),
+        ],
+    );
+
+    let decl_pos = code.s1("resolve_bit").pos();
+    for occurence in 2..=3 {
+        assert_eq!(
+            root.search_reference_pos(code.source(), code.s("resolve_bit", occurence).start()),
+            Some(decl_pos.clone()),
+            "occurence {occurence}"
+        );
+    }
+}
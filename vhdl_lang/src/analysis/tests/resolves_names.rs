@@ -5,6 +5,7 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::data::{Position, VHDLStandard};
 
 #[test]
 fn resolves_names_in_object_decl_init_expressions() {
@@ -324,6 +325,7 @@ package body pkg is
      constant c5 : natural := missing'val(0);
      constant c6 : boolean := boolean'val(missing);
   begin
+    return c5;
   end;
 
 end package body;
@@ -364,6 +366,7 @@ package body pkg is
      constant c5 : string := decl'simple_name;
      constant c6 : boolean := boolean'val(decl);
   begin
+    return c;
   end;
 
 end package body;
@@ -629,7 +632,6 @@ architecture a of ent is
 begin
   main : process(missing) is
   begin
-    wait on missing until missing = 0 ns for missing;
     missing <= missing after missing;
     missing <= force missing;
     missing <= release;
@@ -638,6 +640,11 @@ begin
                   missing when others;
 
   end process;
+
+  waiting : process is
+  begin
+    wait on missing until missing = 0 ns for missing;
+  end process;
 end architecture;
 ",
     );
@@ -655,7 +662,6 @@ architecture a of ent is
 begin
   main : process (decl) is
   begin
-    wait on decl until decl = 0 ns for decl;
     decl <= decl after decl;
     decl <= force decl;
     decl <= release;
@@ -663,6 +669,11 @@ begin
        decl <= decl when decl,
                decl when others;
   end process;
+
+  waiting : process is
+  begin
+    wait on decl until decl = 0 ns for decl;
+  end process;
 end architecture;
 ",
     );
@@ -863,7 +874,14 @@ architecture a of ent is
   begin
   end;
 
-  signal decl : natural := 0;
+  type natural_array is array (natural range <>) of natural;
+  function resolved(values : natural_array) return natural is
+  begin
+    return values(values'left);
+  end;
+  subtype resolved_natural is resolved natural;
+
+  signal decl : resolved_natural := 0;
 begin
   decl <= decl;
   decl <= decl when decl = 0 else decl;
@@ -955,6 +973,28 @@ end architecture;
     );
 }
 
+#[test]
+fn search_case_generate_selector_and_choices() {
+    check_search_reference(
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant decl : natural := 0;
+  signal foo : natural;
+begin
+ gen: case decl generate
+   when decl =>
+     foo <= decl;
+   when others =>
+     foo <= decl + 1;
+ end generate;
+end architecture;
+",
+    );
+}
+
 #[test]
 fn search_generate_alternate_labels() {
     check_search_reference(
@@ -1128,6 +1168,7 @@ package body pkg is
 
   function subpgm(arg: sub_type2) return sub_type2 is
   begin
+    return arg;
   end;
 
   alias alias1 is subpgm[integer return integer];
@@ -1151,10 +1192,12 @@ end package;
 package body pkg is
   function subpgm(arg: natural) return natural is
   begin
+    return arg;
   end;
 
   function subpgm(arg: boolean) return boolean is
   begin
+    return arg;
   end;
 
   alias alias1 is subpgm[boolean return boolean];
@@ -1178,6 +1221,102 @@ end package body;
     );
 }
 
+#[test]
+fn object_alias_of_slices_and_elements() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    num : natural;
+    vec : bit_vector(0 to 3);
+  end record;
+
+  signal v : bit_vector(0 to 7);
+  signal r : rec_t;
+
+  alias slice1 is v(2 to 3);
+  alias slice2 : bit_vector(0 to 1) is r.vec(1 to 2);
+  alias elem1 : bit is v(1);
+  alias elem2 : natural is v(2);
+  alias field1 : bit is r.num;
+
+  signal b : bit;
+  signal n : natural;
+begin
+  process
+  begin
+    b <= slice1(2);
+    b <= slice2(0);
+    b <= elem1;
+    n <= slice1;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1(": natural is").s1("natural"),
+                "Subtype 'NATURAL' of alias does not match type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s1(": bit is r.num").s1("bit"),
+                "Subtype 'BIT' of alias does not match subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1("n <= slice1").s1("slice1"),
+                "array type 'BIT_VECTOR' does not match subtype 'NATURAL'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn labels_and_loop_parameters_cannot_be_aliased() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  lbl : process
+  begin
+    for i in 0 to 1 loop
+      next;
+    end loop;
+    wait;
+  end process;
+
+  blk : block
+    alias a is lbl;
+  begin
+  end block;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("is lbl").s1("lbl"),
+            "Label 'lbl' cannot be aliased",
+        )],
+    );
+}
+
 #[test]
 fn overloaded_name_can_be_selected() {
     let mut builder = LibraryBuilder::new();
@@ -1300,6 +1439,47 @@ end architecture;",
     );
 }
 
+#[test]
+fn find_all_references_until_deadline() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : natural;
+begin
+  sig <= 1;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ent = root
+        .search_reference(code.source(), code.s1("sig :").start())
+        .unwrap();
+
+    let (references, truncated) = root.find_all_references_until(
+        ent,
+        std::time::Instant::now() + std::time::Duration::from_secs(3600),
+    );
+    assert!(!truncated);
+    assert_eq_unordered(
+        &references,
+        &[
+            code.s1("sig :").s1("sig").pos(),
+            code.s1("sig <=").s1("sig").pos(),
+        ],
+    );
+
+    let (references, truncated) = root.find_all_references_until(ent, std::time::Instant::now());
+    assert!(truncated);
+    assert_eq!(references, vec![]);
+}
+
 #[test]
 fn record_subtype_can_be_selected() {
     let mut builder = LibraryBuilder::new();
@@ -1709,3 +1889,166 @@ attribute bad of ram : signal is 0;
         )],
     );
 }
+
+#[test]
+fn suggests_similar_name_when_missing() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant wr_data : natural := 0;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+  port (
+    wr_en : in bit
+  );
+end entity;
+
+architecture a of ent is
+  signal data_out : natural;
+begin
+  process
+  begin
+    if wr_enn = '1' then
+      data_out <= wrdata;
+    end if;
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("wr_enn"),
+                "No declaration of 'wr_enn', did you mean 'wr_en'?",
+            )
+            .with_fix(code.s1("wr_enn"), "wr_en"),
+            Diagnostic::error(
+                code.s1("wrdata"),
+                "No declaration of 'wrdata', did you mean 'wr_data'?",
+            )
+            .with_fix(code.s1("wrdata"), "wr_data"),
+        ],
+    );
+}
+
+#[test]
+fn image_attribute_of_object_requires_vhdl2019() {
+    let code = "
+constant c0 : natural := 0;
+constant c1 : string := c0'image;
+";
+
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2019);
+    builder.in_declarative_region(code);
+    check_no_diagnostics(&builder.analyze());
+
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL2008);
+    let code = builder.in_declarative_region(code);
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::error(
+            code.s("c0", 2),
+            "Expected type prefix for 'image attribute, got constant 'c0'",
+        )],
+    );
+}
+
+#[test]
+fn search_references_matches_search_reference_at_each_position() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    field : natural;
+  end record;
+  function fun(arg : rec_t) return natural;
+end package;
+
+package body pkg is
+  function fun(arg : rec_t) return natural is
+  begin
+    return arg.field;
+  end function;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+  port (value : out natural);
+end entity;
+
+architecture a of ent is
+  signal sig : rec_t;
+begin
+  value <= fun(sig) + sig.field;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let end = code.end();
+    let cursors: Vec<_> = (0..=end.line)
+        .flat_map(|line| (0..40).map(move |character| Position::new(line, character)))
+        .collect();
+
+    let batch = root.search_references(code.source(), &cursors);
+    assert_eq!(batch.len(), cursors.len());
+    for (cursor, ent) in cursors.iter().zip(batch) {
+        assert_eq!(
+            ent.map(|ent| ent.id()),
+            root.search_reference(code.source(), *cursor)
+                .map(|ent| ent.id()),
+            "at {cursor:?}"
+        );
+    }
+}
+
+#[test]
+fn search_references_are_in_the_order_of_the_cursors() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+constant c0 : natural := 0;
+constant c1 : natural := c0;
+        ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let cursors = [
+        code.s("c0", 2).start(),
+        code.s1("natural").start(),
+        code.s1(":=").start(),
+        code.s("c0", 2).start(),
+    ];
+    let decl_pos: Vec<_> = root
+        .search_references(code.source(), &cursors)
+        .into_iter()
+        .map(|ent| ent.and_then(|ent| ent.decl_pos().cloned()))
+        .collect();
+
+    assert_eq!(
+        decl_pos,
+        vec![
+            Some(code.s1("c0").pos()),
+            root.search_reference_pos(code.source(), code.s1("natural").start()),
+            None,
+            Some(code.s1("c0").pos()),
+        ]
+    );
+}
@@ -5,6 +5,7 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::data::VHDLStandard;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -328,6 +329,7 @@ end package;
 package body pkg is
   function bad return natural is
   begin
+    return 0;
   end function;
 
   constant err : bad := 0;
@@ -362,7 +364,7 @@ end package body;
     let diagnostics = builder.analyze();
     check_diagnostics(
         diagnostics,
-        vec![kind_error(&code, "bad", 2, 1, "type", "constant 'bad'")],
+        vec![object_as_type_mark(&code, "bad", 2, 1, "constant 'bad'")],
     );
 }
 
@@ -382,7 +384,13 @@ end package;
     let diagnostics = builder.analyze();
     check_diagnostics(
         diagnostics,
-        vec![kind_error(&code, "bad", 2, 1, "type", "object alias 'bad'")],
+        vec![object_as_type_mark(
+            &code,
+            "bad",
+            2,
+            1,
+            "object alias 'bad'",
+        )],
     );
 }
 
@@ -399,7 +407,7 @@ constant foo : natural := bar'(0);
     let diagnostics = builder.analyze();
     check_diagnostics(
         diagnostics,
-        vec![kind_error(&code, "bar", 2, 1, "type", "constant 'bar'")],
+        vec![object_as_type_mark(&code, "bar", 2, 1, "constant 'bar'")],
     );
 }
 
@@ -470,6 +478,21 @@ end package body;
     );
 }
 
+fn object_as_type_mark(
+    code: &Code,
+    name: &str,
+    occ: usize,
+    occ_decl: usize,
+    got: &str,
+) -> Diagnostic {
+    Diagnostic::error(
+        code.s(name, occ),
+        format!("Expected type, got {got}, did you mean {name}'subtype?"),
+    )
+    .related(code.s(name, occ_decl), "Defined here")
+    .with_fix(code.s(name, occ), format!("{name}'subtype"))
+}
+
 pub fn kind_error(
     code: &Code,
     name: &str,
@@ -481,3 +504,46 @@ pub fn kind_error(
     Diagnostic::error(code.s(name, occ), format!("Expected {expected}, got {got}"))
         .related(code.s(name, occ_decl), "Defined here")
 }
+
+#[test]
+fn object_as_type_mark_has_no_subtype_hint_before_vhdl2008() {
+    let mut builder = LibraryBuilder::new();
+    builder.standard(VHDLStandard::VHDL1993);
+    let code = builder.in_declarative_region(
+        "
+constant bad : natural := 0;
+constant err : bad := 0;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![kind_error(&code, "bad", 2, 1, "type", "constant 'bad'")],
+    );
+}
+
+#[test]
+fn type_mark_used_as_value() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type enum_t is (alpha, beta);
+constant c0 : natural := natural;
+constant c1 : enum_t := enum_t;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::error(
+                code.s("natural", 2),
+                "Expected value, got subtype 'NATURAL', did you mean a qualified expression NATURAL'(...) or a constant?",
+            ),
+            Diagnostic::error(
+                code.s("enum_t", 3),
+                "Expected value, got type 'enum_t', did you mean a qualified expression enum_t'(...) or a constant?",
+            ),
+        ],
+    );
+}
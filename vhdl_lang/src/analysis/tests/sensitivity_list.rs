@@ -5,6 +5,7 @@
 //! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::data::ErrorCode;
 
 #[test]
 fn must_be_object_name() {
@@ -61,7 +62,8 @@ end package body;
         vec![Diagnostic::error(
             code.s1("wait on c0").s1("c0"),
             "constant 'c0' is not a signal and cannot be in a sensitivity list",
-        )],
+        )
+        .with_code(ErrorCode::NonSignalSensitivity)],
     )
 }
 
@@ -92,3 +94,261 @@ end package body;
         )],
     )
 }
+
+#[test]
+fn duplicate_names() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, d : bit;
+  signal v : bit_vector(0 to 1);
+begin
+  process (clk, d, CLK, v(0), v(1), v(0))
+  begin
+  end process;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(code.s1("CLK"), "Duplicate 'CLK' in sensitivity list")
+                .related(code.s1("(clk").s1("clk"), "Previously listed here")
+                .with_code(ErrorCode::DuplicateSensitivity),
+            Diagnostic::warning(code.s("v(0)", 2), "Duplicate 'v(0)' in sensitivity list")
+                .related(code.s1("v(0)"), "Previously listed here")
+                .with_code(ErrorCode::DuplicateSensitivity),
+        ],
+    )
+}
+
+#[test]
+fn redundant_element_names() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    field : bit;
+    other : bit;
+  end record;
+  signal rec : rec_t;
+  signal v : bit_vector(0 to 1);
+begin
+  process (rec.field, rec, v(0), rec.other)
+  begin
+  end process;
+
+  process
+  begin
+    wait on v(1), v;
+  end process;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("rec.field"),
+                "'rec.field' is redundant since 'rec' is also in the sensitivity list",
+            )
+            .related(code.s1("rec, v(0)").s1("rec"), "Also listed here")
+            .with_code(ErrorCode::RedundantSensitivity),
+            Diagnostic::warning(
+                code.s1("rec.other"),
+                "'rec.other' is redundant since 'rec' is also in the sensitivity list",
+            )
+            .related(code.s1("rec, v(0)").s1("rec"), "Also listed here")
+            .with_code(ErrorCode::RedundantSensitivity),
+            Diagnostic::warning(
+                code.s1("v(1)"),
+                "'v(1)' is redundant since 'v' is also in the sensitivity list",
+            )
+            .related(code.s1("v(1), v").s1(", v").s1("v"), "Also listed here")
+            .with_code(ErrorCode::RedundantSensitivity),
+        ],
+    )
+}
+
+#[test]
+fn incomplete_sensitivity_of_combinational_process() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::IncompleteSensitivity);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (sel : in bit; a, b : in bit_vector(0 to 1); q, r : out bit);
+end entity;
+
+architecture a of ent is
+  signal idx : natural range 0 to 1;
+begin
+  comb : process (sel, a)
+  begin
+    if sel = '1' then
+      q <= a(idx);
+    else
+      q <= b(idx);
+    end if;
+  end process;
+
+  all_signals : process (all)
+  begin
+    r <= a(idx) and b(0);
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::warning(
+                code.s1("a(idx)").s1("idx"),
+                "Signal 'idx' is read by the process but is not in the sensitivity list",
+            )
+            .with_code(ErrorCode::IncompleteSensitivity)
+            .with_fix(code.s1("sel, a"), "sel, a, idx, b"),
+            Diagnostic::warning(
+                code.s1("b(idx)").s1("b"),
+                "Signal 'b' is read by the process but is not in the sensitivity list",
+            )
+            .with_code(ErrorCode::IncompleteSensitivity)
+            .with_fix(code.s1("sel, a"), "sel, a, idx, b"),
+        ],
+    );
+}
+
+#[test]
+fn superfluous_sensitivity_of_combinational_process() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::IncompleteSensitivity);
+    builder.enable_lint(ErrorCode::SuperfluousSensitivity);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk, a, b, c : in bit; q, r, s : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  comb : process (c, a, b)
+  begin
+    q <= a and b;
+  end process;
+
+  -- The reads after the wait statement do not belong to the sensitivity
+  waiting : process
+  begin
+    r <= a;
+    wait on a, c;
+  end process;
+
+  clocked : process (clk, a)
+  begin
+    if rising_edge(clk) then
+      s <= b;
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::warning(
+                code.s1("c, a, b").s1("c"),
+                "'c' is in the sensitivity list but is not read by the process",
+            )
+            .with_code(ErrorCode::SuperfluousSensitivity)
+            .with_fix(code.s1("c, a, b"), "a, b"),
+            Diagnostic::warning(
+                code.s1("a, c").s1("c"),
+                "'c' is in the sensitivity list but is not read by the process",
+            )
+            .with_code(ErrorCode::SuperfluousSensitivity)
+            .with_fix(code.s1("a, c"), "a"),
+        ],
+    );
+}
+
+#[test]
+fn sensitivity_completeness_lints_are_opt_in() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (a, b, c : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  comb : process (c, a)
+  begin
+    q <= a and b;
+  end process;
+end architecture;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn process_all_is_sensitive_to_read_signals() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (a, b : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+  signal v : bit_vector(0 to 1);
+begin
+  comb : process (all)
+  begin
+    v(0) <= a;
+    q <= v(1) xor b;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let id = |substr: &str| {
+        root.search_reference(code.source(), code.s1(substr).start())
+            .unwrap()
+            .id()
+    };
+    let processes = root
+        .classify_processes(
+            &root.symbol_utf8("libname"),
+            &root.symbol_utf8("ent"),
+            &root.symbol_utf8("a"),
+        )
+        .unwrap();
+    assert_eq!(
+        processes[0].read_signals,
+        vec![id("a;"), id("v(1)"), id("b;")]
+    );
+}
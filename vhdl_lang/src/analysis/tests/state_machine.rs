@@ -0,0 +1,185 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::StateMachine;
+
+fn transitions(state_machine: &StateMachine) -> Vec<(&str, &str, &str)> {
+    state_machine
+        .transitions
+        .iter()
+        .map(|transition| {
+            (
+                transition.from.as_str(),
+                transition.to.as_str(),
+                transition.condition.as_str(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn extracts_single_process_state_machine() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk, rst, start, done : in bit);
+end entity;
+
+architecture rtl of ent is
+  type state_t is (idle, busy, finished);
+  signal state : state_t;
+begin
+  process (clk) begin
+    if rising_edge(clk) then
+      if rst = '1' then
+        state <= idle;
+      else
+        case state is
+          when idle =>
+            if start = '1' then
+              state <= busy;
+            end if;
+          when busy =>
+            if done = '1' then
+              state <= finished;
+            elsif start = '1' then
+              state <= idle;
+            end if;
+          when others =>
+            state <= idle;
+        end case;
+      end if;
+    end if;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let report = root.state_machines();
+    assert_eq!(report.state_machines.len(), 1);
+    let state_machine = &report.state_machines[0];
+    assert_eq!(state_machine.architecture, "libname.ent(rtl)");
+    assert_eq!(state_machine.signal, "state");
+    assert_eq!(state_machine.states, vec!["idle", "busy", "finished"]);
+    assert_eq!(state_machine.reset_state.as_deref(), Some("idle"));
+    assert_eq!(
+        state_machine.decl_pos,
+        Some(code.s1("signal state").s1("state").pos())
+    );
+    assert_eq!(
+        transitions(state_machine),
+        vec![
+            ("idle", "busy", "start = '1'"),
+            ("busy", "finished", "done = '1'"),
+            ("busy", "idle", "not (done = '1') and start = '1'"),
+            ("others", "idle", ""),
+        ]
+    );
+    assert_eq!(
+        state_machine.transitions[0].pos,
+        code.s1("state <= busy").s1("busy").pos()
+    );
+
+    let csv = report.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("architecture,signal,reset state,from,to,condition")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("libname.ent(rtl),state,idle,idle,busy,start = '1'")
+    );
+}
+
+#[test]
+fn extracts_two_process_state_machine() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk, rst, go : in bit);
+end entity;
+
+architecture rtl of ent is
+  type state_t is (s0, s1);
+  signal state, next_state : state_t;
+begin
+  process (clk, rst) begin
+    if rst = '1' then
+      state <= s1;
+    elsif rising_edge(clk) then
+      state <= next_state;
+    end if;
+  end process;
+
+  process (all) begin
+    next_state <= state;
+    case state is
+      when s0 =>
+        next_state <= s1 when go = '1' else s0;
+      when s1 =>
+        next_state <= s0;
+    end case;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let report = root.state_machines();
+    assert_eq!(report.state_machines.len(), 1);
+    let state_machine = &report.state_machines[0];
+    assert_eq!(state_machine.signal, "state");
+    assert_eq!(state_machine.reset_state.as_deref(), Some("s1"));
+    assert_eq!(
+        transitions(state_machine),
+        vec![
+            ("s0", "s1", "go = '1'"),
+            ("s0", "s0", "not (go = '1')"),
+            ("s1", "s0", ""),
+        ]
+    );
+}
+
+#[test]
+fn case_on_signal_that_is_not_a_register_is_not_a_state_machine() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (sel : in bit; y : out bit);
+end entity;
+
+architecture rtl of ent is
+  type mode_t is (a, b);
+  signal mode : mode_t;
+begin
+  mode <= a when sel = '1' else b;
+
+  process (all) begin
+    case mode is
+      when a =>
+        mode <= b;
+        y <= '0';
+      when b =>
+        y <= '1';
+    end case;
+  end process;
+end architecture;
+",
+    );
+    let (root, _) = builder.get_analyzed_root();
+    assert_eq!(root.state_machines().state_machines, vec![]);
+}
@@ -0,0 +1,162 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{StaticRange, StaticValue};
+use crate::ast::Direction;
+
+#[test]
+fn evaluates_locally_static_constants() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta, gamma);
+  type int_t is range 10 downto 0;
+  subtype sub_t is integer range 2 to 9;
+  type arr_t is array (sub_t range 3 to 5, enum_t) of bit;
+  constant c0 : natural := 2 ** 4 - 1;
+  constant c1 : integer := c0 / 2 + sub_t'high;
+  constant c2 : integer := arr_t'length(1) * arr_t'length(2);
+  constant c3 : enum_t := enum_t'succ(alpha);
+  constant c4 : time := 2 ns + 500 ps;
+  constant c5 : integer := enum_t'pos(gamma) + int_t'pos(int_t'left);
+  constant c6 : boolean := c0 > 10 and not (c3 = alpha);
+  constant c7 : real := 1.5 * 2.0;
+  constant c8 : integer := character'pos('a');
+  constant c9 : integer := c0 mod -4;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let value_of = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+            .static_value()
+    };
+
+    assert_eq!(value_of("c0 :"), Some(StaticValue::Integer(15)));
+    assert_eq!(value_of("c1 :"), Some(StaticValue::Integer(16)));
+    assert_eq!(value_of("c2 :"), Some(StaticValue::Integer(9)));
+    assert_eq!(value_of("c3 :"), Some(StaticValue::Enum(1)));
+    assert_eq!(value_of("c4 :"), Some(StaticValue::Physical(2_500_000)));
+    assert_eq!(value_of("c5 :"), Some(StaticValue::Integer(12)));
+    assert_eq!(value_of("c6 :"), Some(StaticValue::Enum(1)));
+    assert_eq!(value_of("c7 :"), Some(StaticValue::Real(3.0)));
+    assert_eq!(value_of("c8 :"), Some(StaticValue::Integer(97)));
+    assert_eq!(value_of("c9 :"), Some(StaticValue::Integer(-1)));
+}
+
+#[test]
+fn evaluates_static_ranges_of_declarations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta, gamma);
+  subtype sub_t is enum_t range gamma downto beta;
+  type arr_t is array (natural range <>) of bit;
+  subtype word_t is arr_t(15 downto 0);
+  constant width : natural := 8;
+  signal sig : arr_t(width - 1 downto 0);
+  signal rev : arr_t(word_t'reverse_range);
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ranges_of = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+            .static_ranges()
+            .map(|ranges| ranges.to_vec())
+    };
+
+    let int_range = |left, right, direction| {
+        StaticRange::new(
+            StaticValue::Integer(left),
+            StaticValue::Integer(right),
+            direction,
+        )
+    };
+
+    assert_eq!(
+        ranges_of("enum_t is"),
+        Some(vec![StaticRange::new(
+            StaticValue::Enum(0),
+            StaticValue::Enum(2),
+            Direction::Ascending
+        )])
+    );
+    assert_eq!(
+        ranges_of("sub_t is"),
+        Some(vec![StaticRange::new(
+            StaticValue::Enum(2),
+            StaticValue::Enum(1),
+            Direction::Descending
+        )])
+    );
+    assert_eq!(ranges_of("arr_t is"), None);
+    assert_eq!(
+        ranges_of("word_t is"),
+        Some(vec![int_range(15, 0, Direction::Descending)])
+    );
+    assert_eq!(
+        ranges_of("sig :"),
+        Some(vec![int_range(7, 0, Direction::Descending)])
+    );
+    assert_eq!(
+        ranges_of("rev :"),
+        Some(vec![int_range(0, 15, Direction::Ascending)])
+    );
+}
+
+#[test]
+fn static_value_of_expression_at_cursor() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    g : natural := 3
+  );
+end entity;
+
+architecture a of ent is
+  constant c : natural := 4;
+  signal sig : bit_vector(c * 2 - 1 downto 0);
+  signal dyn : bit_vector(g * 2 - 1 downto 0);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.static_value_at(code.source(), code.s1("- 1 downto").start()),
+        Some((code.s1("c * 2 - 1").pos(), Some(StaticValue::Integer(7))))
+    );
+    assert_eq!(
+        root.static_value_at(code.source(), code.s1("* 2 -").start()),
+        Some((code.s1("c * 2").pos(), Some(StaticValue::Integer(8))))
+    );
+
+    // Generics are not locally static
+    assert_eq!(
+        root.static_value_at(code.source(), code.s1("g * 2").s1("*").start()),
+        Some((code.s1("g * 2").pos(), None))
+    );
+}
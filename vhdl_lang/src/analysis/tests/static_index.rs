@@ -0,0 +1,128 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn static_index_out_of_bounds() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant width : natural := 8;
+  type enum_t is (alpha, beta, gamma);
+  type enum_arr_t is array (enum_t range alpha to beta) of bit;
+  type matrix_t is array (0 to 3, 1 to 2) of bit;
+  type rec_t is record
+    field : bit_vector(3 downto 0);
+  end record;
+
+  signal sig : bit_vector(width - 1 downto 0);
+  constant const : bit_vector(0 to 3) := \"0000\";
+  signal enum_arr : enum_arr_t;
+  signal matrix : matrix_t;
+  signal rec : rec_t;
+  signal b : bit_vector(0 to 5);
+begin
+  b(0) <= sig(7);
+  b(1) <= sig(width);
+  b(2) <= const(-1);
+  b(3) <= enum_arr(gamma);
+  b(4) <= matrix(3, 0);
+  b(5) <= rec.field(4);
+
+  process
+    variable var : bit_vector(1 to 2);
+    variable idx : natural;
+  begin
+    var(0) := '1';
+    var(idx) := '1';
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("sig(width)").s1("width"),
+                "Index 8 is outside of the index range 7 downto 0 of signal 'sig'",
+            ),
+            Diagnostic::error(
+                code.s1("-1"),
+                "Index -1 is outside of the index range 0 to 3 of constant 'const'",
+            ),
+            Diagnostic::error(
+                code.s1("enum_arr(gamma)").s1("gamma"),
+                "Index gamma is outside of the index range alpha to beta of signal 'enum_arr'",
+            ),
+            Diagnostic::error(
+                code.s1("matrix(3, 0)").s1("0"),
+                "Index 0 is outside of the index range 1 to 2 of signal 'matrix'",
+            ),
+            Diagnostic::error(
+                code.s1("field(4)").s1("4"),
+                "Index 4 is outside of the index range 3 downto 0 of element declaration 'field'",
+            ),
+            Diagnostic::error(
+                code.s1("var(0)").s1("0"),
+                "Index 0 is outside of the index range 1 to 2 of variable 'var'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn static_index_out_of_bounds_of_alias() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit_vector(7 downto 0);
+  alias whole is sig;
+  alias low is sig(3 downto 0);
+  alias renamed : bit_vector(1 to 8) is sig;
+  signal b : bit_vector(0 to 4);
+begin
+  b(0) <= whole(8);
+  b(1) <= low(4);
+  b(2) <= low(3);
+  b(3) <= renamed(0);
+  b(4) <= renamed(8);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("whole(8)").s1("8"),
+                "Index 8 is outside of the index range 7 downto 0 of object alias 'whole'",
+            ),
+            Diagnostic::error(
+                code.s1("low(4)").s1("4"),
+                "Index 4 is outside of the index range 3 downto 0 of object alias 'low'",
+            ),
+            Diagnostic::error(
+                code.s1("renamed(0)").s1("0"),
+                "Index 0 is outside of the index range 1 to 8 of object alias 'renamed'",
+            ),
+        ],
+    );
+}
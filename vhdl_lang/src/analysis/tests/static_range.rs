@@ -0,0 +1,350 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn generic_default_within_range() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    g0 : natural := 0;
+    g1 : positive := 2 * 3 - 5;
+    g2 : integer := -2147483647;
+    g3 : natural := 2**31 - 1
+  );
+  port (
+    p0 : in natural := 17 mod 5
+  );
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn generic_default_outside_of_range() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    g0 : positive := 0;
+    g1 : natural := 1 - 2;
+    g2 : integer := 2**31
+  );
+  port (
+    p0 : in natural := -1
+  );
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1(":= 0").s1("0"),
+                "Value 0 is outside of the range 1 to 2147483647 of subtype 'POSITIVE'",
+            ),
+            Diagnostic::error(
+                code.s1("1 - 2"),
+                "Value -1 is outside of the range 0 to 2147483647 of subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1("2**31"),
+                "Value 2147483648 is outside of the range -2147483647 to 2147483647 of integer type 'INTEGER'",
+            ),
+            Diagnostic::error(
+                code.s1("-1"),
+                "Value -1 is outside of the range 0 to 2147483647 of subtype 'NATURAL'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn non_static_default_is_not_checked() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant c : integer;
+  function f return integer;
+end package;
+
+package body pkg is
+  constant c : integer := -1;
+  function f return integer is
+  begin
+    return 0;
+  end function;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+  generic (
+    g0 : natural := c;
+    g1 : positive := f - 1
+  );
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn default_outside_of_constrained_subtype() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c : integer := -1;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+  generic (
+    g0 : natural := c;
+    g1 : integer range 0 to 7 := 8;
+    g2 : character range 'a' to 'z' := 'A';
+    g3 : integer range 7 downto 0 := 7
+  );
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1(":= c").s1("c"),
+                "Value -1 is outside of the range 0 to 2147483647 of subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1(":= 8").s1("8"),
+                "Value 8 is outside of the range 0 to 7 of integer type 'INTEGER'",
+            ),
+            Diagnostic::error(
+                code.s1("'A'"),
+                "Value 'A' is outside of the range 'a' to 'z' of type 'CHARACTER'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn signal_default_outside_of_subtype() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s0 : integer range 0 to 15 := 16;
+  signal s1 : bit_vector(3 downto 0) := \"00000\";
+  signal s2 : bit_vector(3 downto 0) := ('0', '1', '0');
+  signal s3 : bit_vector(3 downto 0) := (others => '0');
+  signal s4 : bit_vector(7 downto 0) := x\"ff\";
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1(":= 16").s1("16"),
+                "Value 16 is outside of the range 0 to 15 of integer type 'INTEGER'",
+            ),
+            Diagnostic::error(
+                code.s1("\"00000\""),
+                "Value of length 5 does not match the length 4 of the subtype",
+            ),
+            Diagnostic::error(
+                code.s1("('0', '1', '0')"),
+                "Too few elements in aggregate, got 3 but the index range 3 downto 0 has 4",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn loop_over_range_attribute_dimension() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type matrix_t is array (0 to 3, 'a' to 'c') of bit;
+  signal m : matrix_t;
+begin
+  process
+    variable ch : character;
+    variable n : natural;
+  begin
+    for i in m'range(2) loop
+      ch := i;
+    end loop;
+    for i in m'reverse_range(1) loop
+      n := i;
+    end loop;
+    for i in m'range(3) loop
+    end loop;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("m'range(3)").s1("3"),
+            "Dimension 3 is out of range, the prefix of 'range has 2 dimensions",
+        )],
+    );
+}
+
+#[test]
+fn array_attribute_dimension_parameters() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    dim : natural := 1
+  );
+end entity;
+
+architecture a of ent is
+  type matrix_t is array (0 to 3, 'a' to 'c') of bit;
+  signal m : matrix_t;
+  signal row : bit_vector(m'range(1));
+  subtype col_t is character range m'reverse_range(2);
+  constant cols : natural := m'length(2);
+  constant first_col : character := m'left(2);
+  constant up : boolean := m'ascending(2);
+  constant bad_dim : natural := m'length(dim);
+  constant no_dim : natural := m'length(3);
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("m'length(dim)").s1("dim"),
+                "Dimension of 'length must be locally static, interface constant 'dim' is not static",
+            ),
+            Diagnostic::error(
+                code.s1("m'length(3)").s1("3"),
+                "Dimension 3 is out of range, the prefix of 'length has 2 dimensions",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn loop_parameter_has_direction_of_range_attribute() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal v : bit_vector(7 downto 0);
+begin
+  process
+  begin
+    for i in v'range loop
+    end loop;
+    for j in v'reverse_range loop
+    end loop;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let pretty = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+            .pretty()
+            .to_string()
+    };
+    assert_eq!(pretty("i in"), "i in 7 downto 0");
+    assert_eq!(pretty("j in"), "j in 0 to 7");
+}
+
+#[test]
+fn range_attribute_as_choice() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type char_array_t is array (character range 'a' to 'c') of bit;
+  signal v : bit_vector(0 to 3);
+  signal b : char_array_t;
+begin
+  process
+    variable n : natural;
+  begin
+    case n is
+      when v'range => null;
+      when b'range => null;
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("b'range").s1("b"),
+            "type 'CHARACTER' does not match subtype 'NATURAL'",
+        )],
+    );
+}
@@ -0,0 +1,148 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{StructuralMatch, StructuralPattern};
+use crate::data::Fix;
+
+fn pattern(builder: &mut LibraryBuilder, text: &str) -> StructuralPattern {
+    let escaped = StructuralPattern::escape(text).unwrap();
+    StructuralPattern::new(builder.snippet(&escaped).expr())
+}
+
+#[test]
+fn finds_expressions_matching_a_pattern() {
+    let mut builder = LibraryBuilder::new();
+    let arch = builder.code(
+        "libname",
+        "
+entity ent2 is
+end entity;
+
+architecture a of ent2 is
+  signal clk, rst, q0, q1, q2, q3 : bit;
+  signal clk2 : bit;
+  signal rst2 : bit_vector(0 to 1);
+begin
+  q0 <= '1' when clk'event and clk = '1' and rst = '0' else '0';
+  q1 <= '1' when (clk2'event and clk2 = '1') and rst2(0) = '0' else '0';
+  q2 <= '1' when clk'event and clk = '1' and rst = '1' else '0';
+  q3 <= '1' when clk'event and clk2 = '1' and rst = '0' else '0';
+end architecture;
+",
+    );
+
+    let pattern = pattern(&mut builder, "$clk'event and $clk = '1' and $rst = '0'");
+    assert_eq!(pattern.captures(), vec!["clk".to_owned(), "rst".to_owned()]);
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.structural_search(&pattern),
+        vec![
+            StructuralMatch {
+                pos: arch.s1("clk'event and clk = '1' and rst = '0'").pos(),
+                captures: vec![
+                    ("clk".to_owned(), arch.s1("clk'event and").s1("clk").pos()),
+                    ("rst".to_owned(), arch.s1("rst = '0'").s1("rst").pos()),
+                ],
+            },
+            StructuralMatch {
+                pos: arch
+                    .s1("(clk2'event and clk2 = '1') and rst2(0) = '0'")
+                    .pos(),
+                captures: vec![
+                    ("clk".to_owned(), arch.s("clk2", 2).pos()),
+                    ("rst".to_owned(), arch.s1("rst2(0)").pos()),
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn replaces_expressions_matching_a_pattern() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function is_high(value : bit) return boolean;
+signal clk, q : bit;
+constant c0 : boolean := clk'event and clk = '1';
+constant c1 : boolean := not (q'event and q = '1');
+        ",
+    );
+
+    let pattern = pattern(&mut builder, "$sig'event and $sig = '1'");
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let fixes: Vec<_> = root
+        .structural_search(&pattern)
+        .iter()
+        .map(|found| found.rewrite("is_high($sig)").unwrap())
+        .collect();
+    assert_eq!(
+        fixes,
+        vec![
+            Fix {
+                pos: code.s1("clk'event and clk = '1'").pos(),
+                replacement: "is_high(clk)".to_owned(),
+            },
+            Fix {
+                pos: code.s1("(q'event and q = '1')").pos(),
+                replacement: "is_high(q)".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn matches_calls_and_names_structurally() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+function fun(a, b : natural) return natural;
+constant c0 : natural := fun(1, 2);
+constant c1 : natural := FUN(b => 2, a => 1);
+constant c2 : natural := fun(c0, c0) + fun(c0, c1);
+        ",
+    );
+
+    let calls = pattern(&mut builder, "fun($x, 2)");
+    let same_args = pattern(&mut builder, "fun($x, $x)");
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let positions = |pattern: &StructuralPattern| -> Vec<_> {
+        root.structural_search(pattern)
+            .into_iter()
+            .map(|found| found.pos)
+            .collect()
+    };
+    assert_eq!(positions(&calls), vec![code.s1("fun(1, 2)").pos()]);
+    assert_eq!(positions(&same_args), vec![code.s1("fun(c0, c0)").pos()]);
+}
+
+#[test]
+fn invalid_patterns_and_replacements() {
+    assert_eq!(
+        StructuralPattern::escape("$ and b"),
+        Err("Expected the name of a capture variable after '$' in '$ and b'".to_owned())
+    );
+    assert_eq!(
+        StructuralPattern::escape("s = \"$x\""),
+        Ok("s = \"$x\"".to_owned())
+    );
+
+    let mut builder = LibraryBuilder::new();
+    let pattern = pattern(&mut builder, "not $a");
+    assert_eq!(pattern.check_replacement("$a = '0'"), Ok(()));
+    assert_eq!(
+        pattern.check_replacement("$b = '0'"),
+        Err("Unknown capture variable '$b' in the replacement '$b = '0''".to_owned())
+    );
+}
@@ -13,6 +13,7 @@ fn wrong_number_of_arguments() {
         "
 function subpgm(arg: natural) return natural
 is begin
+    return arg;
 end;
 
 signal good : natural := subpgm(0);
@@ -44,6 +45,7 @@ end entity;
 architecture a of ent is
     function subpgm(arg: natural) return natural
     is begin
+        return arg;
     end;
 
     procedure theproc(arg: natural)
@@ -83,10 +85,12 @@ fn resolve_overloaded_subprogram_by_return_type() {
         "
 function subpgm(arg: natural) return character
 is begin
+    return character'val(arg);
 end;
 
 function subpgm(arg: natural) return natural
 is begin
+    return arg;
 end;
 
 
@@ -145,10 +149,12 @@ fn resolve_overloaded_subprogram_by_argument() {
         "
 function subpgm(arg: character) return natural
 is begin
+    return character'pos(arg);
 end;
 
 function subpgm(arg: natural) return natural
 is begin
+    return arg;
 end;
 
 
@@ -176,6 +182,7 @@ fn subprogram_argument_not_associated() {
         "
 function subpgm(arg1: natural; arg2: character) return natural
 is begin
+    return arg1;
 end;
 
 signal bad : natural := subpgm(0);
@@ -205,6 +212,7 @@ fn subprogram_extra_argument_not_associated() {
         "
 function subpgm(arg1: natural) return natural
 is begin
+    return arg1;
 end;
 
 signal bad : natural := subpgm(1111, 2222);
@@ -0,0 +1,180 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+
+fn enable_synthesis_lints(builder: &mut LibraryBuilder) {
+    for code in ErrorCode::ALL.iter() {
+        if code.is_synthesis_restriction() {
+            builder.enable_lint(*code);
+        }
+    }
+}
+
+#[test]
+fn constructs_not_supported_by_synthesis() {
+    let mut builder = LibraryBuilder::new();
+    enable_synthesis_lints(&mut builder);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type counter_t is protected
+    procedure increment;
+  end protected;
+
+  signal global : bit := '1';
+end package;
+
+package body pkg is
+  type counter_t is protected body
+    variable count : natural := 0;
+
+    procedure increment is
+    begin
+      count := count + 1;
+    end procedure;
+  end protected body;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+  port (clk : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+  type int_file_t is file of integer;
+  file results : int_file_t;
+  signal s : bit := '0';
+  shared variable counter : counter_t;
+begin
+  q <= '1', '0' after 10 ns;
+
+  main : process
+  begin
+    wait for 10 ns;
+    wait until clk = '1';
+    s <= '1' after 1 ns;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::warning(
+                code.s1("global : bit := '1'").s1("'1'"),
+                "Initial value of signal 'global' is not supported by synthesis for all devices",
+            )
+            .with_code(ErrorCode::SignalInitialValue),
+            Diagnostic::warning(
+                code.s1("results"),
+                "File 'results' is not supported by synthesis",
+            )
+            .with_code(ErrorCode::FileIo),
+            Diagnostic::warning(
+                code.s1("s : bit := '0'").s1("'0'"),
+                "Initial value of signal 's' is not supported by synthesis for all devices",
+            )
+            .with_code(ErrorCode::SignalInitialValue),
+            Diagnostic::warning(
+                code.s1("shared variable counter").s1("counter"),
+                "Shared variable 'counter' is not supported by synthesis",
+            )
+            .with_code(ErrorCode::SharedVariable),
+            Diagnostic::warning(
+                code.s1("'0' after 10 ns").s1("10 ns"),
+                "Waveform relies on after clauses that are ignored by synthesis",
+            )
+            .with_code(ErrorCode::AfterClause),
+            Diagnostic::warning(
+                code.s1("wait for 10 ns").s1("10 ns"),
+                "Wait statement with a timeout is not supported by synthesis",
+            )
+            .with_code(ErrorCode::TimedWait),
+        ],
+    );
+}
+
+#[test]
+fn synthesis_lints_are_opt_in() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::TimedWait);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk : in bit);
+end entity;
+
+architecture a of ent is
+  signal s : bit := '0';
+begin
+  process
+  begin
+    wait for 10 ns;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::warning(
+            code.s1("10 ns"),
+            "Wait statement with a timeout is not supported by synthesis",
+        )
+        .with_code(ErrorCode::TimedWait)],
+    );
+}
+
+#[test]
+fn testbenches_and_simulation_libraries_are_not_checked() {
+    let mut builder = LibraryBuilder::new();
+    enable_synthesis_lints(&mut builder);
+    builder.simulation_library("sim");
+    builder.code(
+        "libname",
+        "
+entity tb_ent is
+end entity;
+
+architecture a of tb_ent is
+  signal clk : bit := '0';
+begin
+  clk <= '1', '0' after 5 ns;
+
+  main : process
+  begin
+    wait for 10 ns;
+    report \"done\" severity failure;
+  end process;
+end architecture;
+",
+    );
+    builder.code(
+        "sim",
+        "
+entity model is
+  port (clk : in bit);
+end entity;
+
+architecture a of model is
+  signal s : bit := '0';
+begin
+  process
+  begin
+    wait for 10 ns;
+  end process;
+end architecture;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
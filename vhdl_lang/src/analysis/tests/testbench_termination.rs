@@ -0,0 +1,263 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::ErrorCode;
+
+fn unterminated(code: &Code, name: &str) -> Diagnostic {
+    Diagnostic::warning(
+        code.s1(&format!("architecture a of {name}")).s1(" a ").s1("a"),
+        format!("Testbench '{name}' never terminates the simulation, expected a call to std.env.finish or std.env.stop, a report of severity failure or a call to test_runner_cleanup"),
+    )
+    .with_code(ErrorCode::UnterminatedTestbench)
+}
+
+#[test]
+fn testbench_without_termination() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity tb_ent is
+end entity;
+
+architecture a of tb_ent is
+  signal clk : bit;
+begin
+  clk <= not clk after 5 ns;
+end architecture;
+
+entity ent_tb is
+  generic (runner_cfg : string);
+end entity;
+
+architecture a of ent_tb is
+  signal clk : bit;
+begin
+  clk <= not clk after 5 ns;
+
+  main : process
+    procedure finish is
+    begin
+    end;
+  begin
+    finish;
+    wait;
+  end process;
+end architecture;
+
+entity vunit_ent is
+  generic (runner_cfg : string);
+end entity;
+
+architecture a of vunit_ent is
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            unterminated(&code, "tb_ent"),
+            unterminated(&code, "ent_tb"),
+            unterminated(&code, "vunit_ent"),
+        ],
+    );
+}
+
+#[test]
+fn testbench_with_termination() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity tb_finish is
+end entity;
+
+architecture a of tb_finish is
+begin
+  main : process
+  begin
+    std.env.finish;
+  end process;
+end architecture;
+
+use std.env.all;
+
+entity tb_stop is
+end entity;
+
+architecture a of tb_stop is
+  procedure done is
+  begin
+    stop(0);
+  end;
+begin
+  done;
+end architecture;
+
+entity tb_failure is
+end entity;
+
+architecture a of tb_failure is
+begin
+  main : process
+  begin
+    wait for 10 ns;
+    report \"done\" severity failure;
+  end process;
+end architecture;
+
+entity tb_vunit is
+  generic (runner_cfg : string);
+end entity;
+
+architecture a of tb_vunit is
+  procedure test_runner_cleanup(cfg : string) is
+  begin
+  end;
+begin
+  main : process
+  begin
+    test_runner_cleanup(runner_cfg);
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn testbench_ending_by_event_starvation() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity tb_gated is
+end entity;
+
+architecture a of tb_gated is
+  signal clk : bit;
+  signal done : boolean := false;
+begin
+  clk <= not clk after 5 ns when not done;
+
+  main : process
+  begin
+    wait for 100 ns;
+    done <= true;
+    wait;
+  end process;
+end architecture;
+
+entity tb_finite is
+end entity;
+
+architecture a of tb_finite is
+  signal clk : bit;
+begin
+  clk_gen : process
+  begin
+    for i in 1 to 10 loop
+      clk <= not clk;
+      wait for 5 ns;
+    end loop;
+    wait;
+  end process;
+
+  main : process(clk)
+  begin
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn testbench_with_clock_that_is_not_stopped() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity tb_ungated is
+end entity;
+
+architecture a of tb_ungated is
+  signal clk : bit;
+  signal enable : boolean := true;
+begin
+  clk <= not clk after 5 ns when enable;
+
+  main : process
+  begin
+    wait for 100 ns;
+    wait;
+  end process;
+end architecture;
+
+entity tb_clock_process is
+end entity;
+
+architecture a of tb_clock_process is
+  signal clk : bit;
+begin
+  clk_gen : process
+  begin
+    clk <= not clk;
+    wait for 5 ns;
+  end process;
+
+  main : process
+  begin
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            unterminated(&code, "tb_ungated"),
+            unterminated(&code, "tb_clock_process"),
+        ],
+    );
+}
+
+#[test]
+fn entities_with_ports_are_not_testbenches() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity tb_ent is
+  port (clk : in bit);
+end entity;
+
+architecture a of tb_ent is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
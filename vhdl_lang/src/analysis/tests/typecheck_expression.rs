@@ -159,9 +159,12 @@ signal bad5 : enum_vec3_t(1 to 1) := \"a\";
     check_diagnostics(
         diagnostics,
         vec![
-            Diagnostic::error(code.s1("\"2\""), "type 'BIT' does not define character '2'"),
             Diagnostic::error(
-                code.s1("\"b\""),
+                code.s1("\"2\"").s1("2"),
+                "type 'BIT' does not define character '2'",
+            ),
+            Diagnostic::error(
+                code.s1("\"b\"").s1("b"),
                 "type 'enum_t' does not define character 'b'",
             ),
             Diagnostic::error(
@@ -747,6 +750,64 @@ constant bad5 : arr0_t := x\"6\";
     );
 }
 
+#[test]
+fn check_bitstring_literal_digits_and_length() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+constant good1 : bit_vector(11 downto 0) := 12sx\"F0\";
+constant good2 : bit_vector(5 downto 0) := d\"42\";
+constant good3 : bit_vector(3 downto 0) := 4x\"0F\";
+constant good4 : bit_vector(3 downto 0) := 4sx\"F8\";
+constant good5 : bit_vector(7 downto 0) := 8d\"255\";
+type logic_t is ('0', '1', 'Z', '-');
+type logic_vector_t is array (natural range <>) of logic_t;
+constant good6 : logic_vector_t(7 downto 0) := x\"Z-\";
+constant bad1 : bit_vector := b\"102\";
+constant bad2 : bit_vector := o\"8\";
+constant bad3 : bit_vector := d\"4A\";
+constant bad4 : bit_vector := 4x\"1F\";
+constant bad5 : bit_vector := 4sx\"70\";
+constant bad6 : bit_vector := 7d\"128\";
+constant bad7 : bit_vector := x\"Z\";
+",
+    );
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("b\"102\""),
+                "Illegal digit '2' in binary bit string literal",
+            ),
+            Diagnostic::error(
+                code.s1("o\"8\""),
+                "Illegal digit '8' in octal bit string literal",
+            ),
+            Diagnostic::error(
+                code.s1("d\"4A\""),
+                "Illegal digit 'A' in decimal bit string literal",
+            ),
+            Diagnostic::error(
+                code.s1("4x\"1F\""),
+                "Truncating bit string literal to 4 bits would lose significant bits",
+            ),
+            Diagnostic::error(
+                code.s1("4sx\"70\""),
+                "Truncating bit string literal to 4 bits would lose significant bits",
+            ),
+            Diagnostic::error(
+                code.s1("7d\"128\""),
+                "Truncating bit string literal to 7 bits would lose significant bits",
+            ),
+            Diagnostic::error(
+                code.s1("x\"Z\""),
+                "element type 'BIT' of array type 'BIT_VECTOR' does not define character 'Z'",
+            ),
+        ],
+    );
+}
+
 #[test]
 fn check_null_literal() {
     let mut builder = LibraryBuilder::new();
@@ -820,6 +881,88 @@ constant bad : rec_t := (field(0) => 0);
     );
 }
 
+#[test]
+fn record_aggregate_others_and_multiple_choices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type rec_t is record
+    f0, f1 : natural;
+    f2 : character;
+end record;
+
+constant good1 : rec_t := (f0 | f1 => 0, f2 => 'a');
+constant good2 : rec_t := (f2 => 'b', others => 1);
+constant good3 : rec_t := (2, f2 => 'c', others => 3);
+constant bad1 : rec_t := (f0 | f2 => 4, f1 => 5);
+constant bad2 : rec_t := (f0 => 6, others => 'd');
+constant bad3 : rec_t := (f1 => 7, others => 'e');
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("f0 | f2").s1("f2"),
+                "Elements 'f0' and 'f2' of the same choice must have the same type, got subtype 'NATURAL' and type 'CHARACTER'",
+            ),
+            Diagnostic::error(
+                code.s1("'d'"),
+                "Elements 'f1' and 'f2' of the same choice must have the same type, got subtype 'NATURAL' and type 'CHARACTER'",
+            ),
+            Diagnostic::error(
+                code.s1("'e'"),
+                "Elements 'f0' and 'f2' of the same choice must have the same type, got subtype 'NATURAL' and type 'CHARACTER'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn record_aggregate_elements_must_be_associated_once() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+type rec_t is record
+    f0, f1 : natural;
+end record;
+
+constant bad1 : rec_t := (f0 => 0);
+constant bad2 : rec_t := (1, f0 => 2, f1 => 3);
+constant bad3 : rec_t := (f0 | f1 => 4, others => 5);
+constant bad4 : rec_t := (6, 7, 8);
+constant bad5 : rec_t := (0 to 1 => 9);
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("(f0 => 0)"), "Missing association of element 'f1'"),
+            Diagnostic::error(
+                code.s1("f0 => 2").s1("f0"),
+                "Element 'f0' is associated more than once",
+            )
+            .related(code.s1("(1,").s1("1"), "Previously associated here"),
+            Diagnostic::error(
+                code.s1("5"),
+                "Others choice does not represent any element of record type 'rec_t'",
+            ),
+            Diagnostic::error(
+                code.s1("8"),
+                "Too many elements in aggregate of record type 'rec_t'",
+            ),
+            Diagnostic::error(
+                code.s1("0 to 1"),
+                "Record aggregate choice must be a simple name",
+            ),
+        ],
+    );
+}
+
 #[test]
 fn typecheck_aggregate_element_association_expr() {
     let mut builder = LibraryBuilder::new();
@@ -1606,3 +1749,74 @@ end units;
         ],
     );
 }
+
+#[test]
+fn matching_relational_operators_of_bit() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+constant b0 : bit := '0' ?= '1';
+constant b1 : bit := bit'('0') ?< '1';
+constant b2 : bit := bit_vector'(\"01\") ?/= \"10\";
+constant b3 : boolean := '0' ?>= '1';
+constant b4 : bit := bit_vector'(\"01\") ?> \"10\";
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("'0' ?>= '1'"),
+                "type 'BIT' does not match type 'BOOLEAN'",
+            ),
+            Diagnostic::error(
+                code.s1("bit_vector'(\"01\") ?>").s1("bit_vector'(\"01\")"),
+                "array type 'BIT_VECTOR' does not match type 'BIT'",
+            ),
+            Diagnostic::error(
+                code.s1("?> \"10\"").s1("\"10\""),
+                "string literal does not match type 'BIT'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn matching_relational_operators_of_std_ulogic() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+type std_ulogic_vector is array (natural range <>) of std_ulogic;
+subtype std_logic is std_ulogic;
+
+constant s0 : std_ulogic := 'U' ?= '-';
+constant s1 : std_logic := std_logic'('1') ?<= 'H';
+constant s2 : std_ulogic := std_ulogic_vector'(\"01\") ?= \"0-\";
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn matching_relational_operators_in_condition() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+function f(a, b : bit_vector) return bit is
+begin
+  if a ?= b then
+    return '1';
+  end if;
+  return a ?/= b;
+end function;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
@@ -0,0 +1,254 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{ErrorCode, Position, SrcPos};
+
+/// The position of the whole line containing the substring
+fn line_of(code: &Code, substr: &str) -> SrcPos {
+    let line = code.s1(substr).pos().start().line;
+    code.source()
+        .pos(Position::new(line, 0), Position::new(line + 1, 0))
+}
+
+#[test]
+fn unused_parameters() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::UnusedParameter);
+    let code = builder.in_declarative_region(
+        "
+procedure proc(used : natural; unused : natural; variable ret : out natural) is
+  constant c0 : natural := used;
+begin
+  ret := c0;
+end procedure;
+
+function fun(arg : natural) return natural is
+begin
+  return 0;
+end function;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::warning(
+                code.s1("unused : natural").s1("unused"),
+                "Parameter 'unused' of procedure 'proc' is never used",
+            )
+            .related(code.s1("proc(").s1("proc"), "Parameter of procedure 'proc'")
+            .with_code(ErrorCode::UnusedParameter),
+            Diagnostic::warning(
+                code.s1("arg"),
+                "Parameter 'arg' of function 'fun' is never used",
+            )
+            .related(code.s1("fun(").s1("fun"), "Parameter of function 'fun'")
+            .with_code(ErrorCode::UnusedParameter),
+        ],
+    );
+}
+
+#[test]
+fn unused_object_lints_are_opt_in() {
+    let mut builder = LibraryBuilder::new();
+    builder.in_declarative_region(
+        "
+procedure proc(unused : natural) is
+  variable v : natural;
+begin
+  v := 0;
+end procedure;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn write_only_variables() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::WriteOnlyVariable);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : natural;
+begin
+  process
+    variable write_only : natural := 0;
+    variable arr : bit_vector(0 to 1);
+    variable counter : natural := 0;
+  begin
+    write_only := 1;
+    if sig = 0 then
+      arr(0) := '1'; arr(1) := '0';
+    end if;
+    counter := counter + 1;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::warning(
+                code.s1("write_only"),
+                "Variable 'write_only' is assigned but never read",
+            )
+            .related(code.s("write_only", 2), "Assigned here")
+            .with_code(ErrorCode::WriteOnlyVariable)
+            .with_fix(line_of(&code, "variable write_only"), "")
+            .with_fix(line_of(&code, "write_only := 1"), ""),
+            // The assignments share a line and cannot be removed on their own
+            Diagnostic::warning(code.s1("arr"), "Variable 'arr' is assigned but never read")
+                .related(code.s("arr", 2), "Assigned here")
+                .related(code.s("arr", 3), "Assigned here")
+                .with_code(ErrorCode::WriteOnlyVariable),
+        ],
+    );
+}
+
+#[test]
+fn write_only_variable_assigned_by_impure_function_is_not_removed() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::WriteOnlyVariable);
+    let code = builder.in_declarative_region(
+        "
+impure function next_count return natural is
+begin
+  return 0;
+end function;
+
+procedure proc is
+  variable v : natural;
+begin
+  v := next_count;
+end procedure;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::warning(
+            code.s1("v : natural").s1("v"),
+            "Variable 'v' is assigned but never read",
+        )
+        .related(code.s1("v :=").s1("v"), "Assigned here")
+        .with_code(ErrorCode::WriteOnlyVariable)],
+    );
+}
+
+#[test]
+fn write_only_and_unassigned_signals() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::WriteOnlySignal);
+    builder.enable_lint(ErrorCode::UnassignedSignal);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    clk : in bit;
+    q : out bit);
+end entity;
+
+architecture a of ent is
+  signal write_only : bit;
+  signal unassigned : bit;
+  signal used : bit;
+  signal unused : bit;
+  signal arr : bit_vector(0 to 1);
+begin
+  write_only <= clk;
+  q <= used;
+
+  process (clk)
+  begin
+    if clk'event and clk = '1' then
+      used <= unassigned;
+      arr(0) <= '1';
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::warning(
+                code.s1("write_only"),
+                "Signal 'write_only' is assigned but never read",
+            )
+            .related(code.s("write_only", 2), "Assigned here")
+            .with_code(ErrorCode::WriteOnlySignal),
+            Diagnostic::warning(
+                code.s1("unassigned"),
+                "Signal 'unassigned' is read but never assigned",
+            )
+            .with_code(ErrorCode::UnassignedSignal),
+            Diagnostic::warning(code.s1("arr"), "Signal 'arr' is assigned but never read")
+                .related(code.s("arr", 2), "Assigned here")
+                .with_code(ErrorCode::WriteOnlySignal),
+        ],
+    );
+}
+
+#[test]
+fn signals_connected_to_actuals_may_be_assigned() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(ErrorCode::WriteOnlySignal);
+    builder.enable_lint(ErrorCode::UnassignedSignal);
+    builder.code(
+        "libname",
+        "
+entity child is
+  port (
+    d : in bit;
+    q : out bit);
+end entity;
+
+architecture a of child is
+begin
+  q <= d;
+end architecture;
+
+entity ent is
+  port (q : out bit);
+end entity;
+
+architecture a of ent is
+  signal from_instance : bit;
+  signal from_procedure : bit;
+  signal from_alias : bit;
+  alias from_alias_a is from_alias;
+
+  procedure drive(signal target : out bit) is
+  begin
+    target <= '1';
+  end procedure;
+begin
+  inst : entity work.child
+    port map (
+      d => '0',
+      q => from_instance);
+
+  drive(from_procedure);
+  from_alias_a <= '1';
+
+  q <= from_instance and from_procedure and from_alias;
+end architecture;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
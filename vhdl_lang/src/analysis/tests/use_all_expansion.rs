@@ -0,0 +1,199 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{Position, SrcPos};
+
+#[test]
+fn lists_declarations_made_visible_by_use_all() {
+    let mut builder = LibraryBuilder::new();
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  constant c0 : natural := 0;
+  constant c1 : natural := 1;
+  function fun(arg : natural) return natural;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : enum_t;
+begin
+  process
+    use work.pkg.all;
+  begin
+    sig <= beta;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let expand = |cursor: Position| -> Option<Vec<(SrcPos, bool)>> {
+        root.expand_use_all(code.source(), cursor).map(|visible| {
+            visible
+                .into_iter()
+                .map(|(ent, referenced)| (ent.decl_pos().unwrap().clone(), referenced))
+                .collect()
+        })
+    };
+
+    // The scope of the context clause of the entity extends to its architectures
+    assert_eq!(
+        expand(code.s1("use work.pkg.all").s1("pkg").start()),
+        Some(vec![
+            (pkg.s1("enum_t").pos(), true),
+            (pkg.s1("alpha").pos(), false),
+            (pkg.s1("beta").pos(), true),
+            (pkg.s1("c0").pos(), false),
+            (pkg.s1("c1").pos(), false),
+            (pkg.s1("fun(").s1("fun").pos(), false),
+        ])
+    );
+
+    // The signal is declared before the scope of the clause within the process
+    assert_eq!(
+        expand(code.s("use work.pkg.all", 2).s1("use").start()),
+        Some(vec![
+            (pkg.s1("enum_t").pos(), false),
+            (pkg.s1("alpha").pos(), false),
+            (pkg.s1("beta").pos(), true),
+            (pkg.s1("c0").pos(), false),
+            (pkg.s1("c1").pos(), false),
+            (pkg.s1("fun(").s1("fun").pos(), false),
+        ])
+    );
+
+    assert_eq!(expand(code.s1("sig <=").start()), None);
+}
+
+#[test]
+fn referenced_only_within_the_scope_of_the_clause() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  constant c0 : natural := 0;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  use work.pkg.all;
+end entity;
+
+architecture a of ent is
+  signal sig : enum_t;
+begin
+end architecture;
+
+library libname;
+entity other is
+end entity;
+
+architecture a of other is
+  constant c1 : natural := libname.pkg.c0;
+begin
+  blk : block
+    use work.pkg.all;
+    constant c2 : natural := c0;
+  begin
+  end block;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let referenced = |cursor: Position| -> Vec<String> {
+        root.expand_use_all(code.source(), cursor)
+            .unwrap()
+            .into_iter()
+            .filter(|(_, referenced)| *referenced)
+            .map(|(ent, _)| ent.designator().to_string())
+            .collect()
+    };
+
+    // The architectures of the entity are within the scope but not those of other entities
+    assert_eq!(
+        referenced(code.s1("use work.pkg.all").start()),
+        vec!["enum_t"]
+    );
+    assert_eq!(
+        referenced(code.s("use work.pkg.all", 2).start()),
+        vec!["c0"]
+    );
+}
+
+#[test]
+fn replaces_use_all_with_explicit_imports() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  constant c0 : natural := 0;
+  function fun(arg : natural) return natural;
+  function fun(arg : enum_t) return natural;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : enum_t := beta;
+  signal num : natural := fun(0) + fun(alpha);
+begin
+end architecture;
+
+entity unused is
+  use work.pkg.all;
+end entity;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.use_all_to_explicit_imports(code.source(), code.s1("use work.pkg.all").start()),
+        Some((
+            code.s1("work.pkg.all").pos(),
+            "work.pkg.enum_t, work.pkg.alpha, work.pkg.beta, work.pkg.fun".to_owned()
+        ))
+    );
+
+    // There is nothing to import explicitly
+    assert_eq!(
+        root.use_all_to_explicit_imports(code.source(), code.s("use work.pkg.all", 2).start()),
+        None
+    );
+}
@@ -4,10 +4,11 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::DesignRoot;
+use crate::analysis::{ArchitectureSelection, DesignRoot, ResetConvention};
 use crate::data::*;
 use crate::syntax::test::*;
 use crate::syntax::Symbols;
+use fnv::FnvHashSet;
 use pretty_assertions::assert_eq;
 use std::collections::{hash_map::Entry, HashMap};
 use std::sync::Arc;
@@ -15,6 +16,11 @@ use std::sync::Arc;
 pub struct LibraryBuilder {
     code_builder: CodeBuilder,
     libraries: HashMap<Symbol, Vec<Code>>,
+    reset_convention: Option<ResetConvention>,
+    standard: VHDLStandard,
+    enabled_lints: FnvHashSet<ErrorCode>,
+    architecture_selection: ArchitectureSelection,
+    simulation_libraries: FnvHashSet<Symbol>,
 }
 
 impl LibraryBuilder {
@@ -22,9 +28,39 @@ impl LibraryBuilder {
         LibraryBuilder {
             code_builder: CodeBuilder::new(),
             libraries: HashMap::default(),
+            reset_convention: None,
+            standard: VHDLStandard::default(),
+            enabled_lints: FnvHashSet::default(),
+            architecture_selection: ArchitectureSelection::default(),
+            simulation_libraries: FnvHashSet::default(),
         }
     }
 
+    pub fn reset_convention(&mut self, convention: ResetConvention) {
+        self.reset_convention = Some(convention);
+    }
+
+    pub fn standard(&mut self, standard: VHDLStandard) {
+        self.standard = standard;
+    }
+
+    pub fn enable_lint(&mut self, code: ErrorCode) {
+        self.enabled_lints.insert(code);
+    }
+
+    pub fn architecture_selection(&mut self, selection: ArchitectureSelection) {
+        self.architecture_selection = selection;
+    }
+
+    pub fn enable_psl_comments(&mut self) {
+        self.code_builder.enable_psl_comments();
+    }
+
+    pub fn simulation_library(&mut self, library_name: &str) {
+        let library_name = self.code_builder.symbol(library_name);
+        self.simulation_libraries.insert(library_name);
+    }
+
     fn add_code(&mut self, library_name: &str, code: Code) {
         let library_name = self.code_builder.symbol(library_name);
         match self.libraries.entry(library_name) {
@@ -64,8 +100,32 @@ end architecture;"
         )
     }
 
+    /// An architecture with the declarations and the concurrent statements
+    pub fn in_architecture(&mut self, decl: &str, statements: &str) -> Code {
+        self.code(
+            "libname",
+            &format!(
+                "
+entity ent is
+end entity;
+
+architecture a of ent is
+{decl}
+begin
+{statements}
+end architecture;
+"
+            ),
+        )
+    }
+
     pub fn get_analyzed_root(&self) -> (DesignRoot, Vec<Diagnostic>) {
         let mut root = DesignRoot::new(self.code_builder.symbols.clone());
+        root.set_reset_convention(self.reset_convention.clone());
+        root.set_standard(self.standard);
+        root.set_enabled_lints(self.enabled_lints.clone());
+        root.set_architecture_selection(self.architecture_selection);
+        root.set_simulation_libraries(self.simulation_libraries.clone());
         let mut diagnostics = Vec::new();
 
         add_standard_library(self.symbols(), &mut root);
@@ -123,9 +183,8 @@ fn env_package() -> Source {
 }
 
 pub fn add_standard_library(symbols: Arc<Symbols>, root: &mut DesignRoot) {
-    let builder = CodeBuilder {
-        symbols: symbols.clone(),
-    };
+    let mut builder = CodeBuilder::new();
+    builder.symbols = symbols.clone();
     let std_standard = builder.code_from_source(standard_package());
     let std_textio = builder.code_from_source(textio_package());
     let std_env = builder.code_from_source(env_package());
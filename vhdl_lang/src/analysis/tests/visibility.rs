@@ -121,6 +121,7 @@ begin
     use work.pkg.decl;
   begin
     assert decl = 1;
+    std.env.finish;
   end process;
 end architecture;
 ",
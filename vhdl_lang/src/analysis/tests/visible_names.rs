@@ -0,0 +1,185 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{VisibleName, VisibleOrigin};
+use crate::data::Position;
+
+fn find<'n>(names: &'n [VisibleName], name: &str) -> Option<&'n VisibleName> {
+    names
+        .iter()
+        .find(|visible| visible.designator.to_string().eq_ignore_ascii_case(name))
+}
+
+#[test]
+fn names_visible_within_nested_regions() {
+    let mut builder = LibraryBuilder::new();
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+  constant pkg_const : natural := 0;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+entity ent is
+  port (clk : in bit);
+end entity;
+
+architecture a of ent is
+  signal sig1 : bit;
+begin
+  main : process
+    variable var1 : natural;
+  begin
+    var1 := pkg_const;
+    wait;
+  end process;
+
+  other : process
+    variable var2 : natural;
+  begin
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let visible_at = |cursor: Position| root.visible_names(code.source(), cursor);
+
+    let names = visible_at(code.s1("var1 :=").start());
+    let var1 = find(&names, "var1").unwrap();
+    assert_eq!(var1.kind, "variable");
+    assert_eq!(var1.decl_pos, Some(code.s1("var1").pos()));
+    assert_eq!(var1.origin, VisibleOrigin::Immediate);
+    assert_eq!(find(&names, "sig1").unwrap().kind, "signal");
+    assert_eq!(
+        find(&names, "clk").unwrap().origin,
+        VisibleOrigin::Immediate
+    );
+    assert_eq!(
+        find(&names, "ent").unwrap().origin,
+        VisibleOrigin::Immediate
+    );
+    assert!(find(&names, "var2").is_none());
+
+    let pkg_const = find(&names, "pkg_const").unwrap();
+    assert_eq!(pkg_const.decl_pos, Some(pkg.s1("pkg_const").pos()));
+    assert_eq!(
+        pkg_const.origin,
+        VisibleOrigin::UseClause(vec![code.s1("work.pkg.all").pos()])
+    );
+
+    assert_eq!(
+        find(&names, "std").unwrap().origin,
+        VisibleOrigin::Library(vec![])
+    );
+    assert_eq!(
+        find(&names, "natural").unwrap().origin,
+        VisibleOrigin::UseClause(vec![])
+    );
+
+    let names = visible_at(code.s("wait", 2).start());
+    assert!(find(&names, "var1").is_none());
+    assert!(find(&names, "var2").is_some());
+
+    // Declarations are only visible after they are declared
+    let names = visible_at(code.s1("signal sig1").start());
+    assert!(find(&names, "sig1").is_none());
+    assert!(find(&names, "clk").is_some());
+}
+
+#[test]
+fn names_visible_through_context_references() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant pkg_const : natural := 0;
+end package;
+
+context ctx is
+  library libname;
+  use libname.pkg.all;
+end context;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+context work.ctx;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let names = root.visible_names(code.source(), code.s1("end architecture").start());
+    let VisibleOrigin::UseClause(ref visible_pos) = find(&names, "pkg_const").unwrap().origin
+    else {
+        panic!("Expected a use clause");
+    };
+    assert_eq!(visible_pos.len(), 2);
+    assert_eq!(visible_pos[1], code.s1("work.ctx").pos());
+
+    let VisibleOrigin::Library(ref visible_pos) = find(&names, "libname").unwrap().origin else {
+        panic!("Expected a library clause");
+    };
+    assert_eq!(visible_pos.last(), Some(&code.s1("work.ctx").pos()));
+}
+
+#[test]
+fn names_hidden_by_conflicting_use_clauses_are_not_visible() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg1 is
+  constant dup : natural := 0;
+  constant one : natural := 1;
+end package;
+
+package pkg2 is
+  constant dup : natural := 0;
+end package;
+
+use work.pkg1.all;
+use work.pkg2.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant local : natural := one;
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let names = root.visible_names(code.source(), code.s1("end architecture").start());
+    assert!(find(&names, "one").is_some());
+    assert!(find(&names, "local").is_some());
+    assert!(find(&names, "dup").is_none());
+}
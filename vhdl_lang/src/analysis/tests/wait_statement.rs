@@ -0,0 +1,176 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn wait_in_function_or_sensitive_process() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : bit;
+
+  function fun return natural is
+  begin
+    wait for 1 ns;
+    return 0;
+  end function;
+
+  procedure proc is
+  begin
+    wait on clk;
+  end procedure;
+begin
+  sensitive : process (clk)
+  begin
+    if clk = '1' then
+      wait until clk = '0';
+    end if;
+  end process;
+
+  waiting : process
+  begin
+    wait on clk;
+  end process;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::error(
+                code.s1("wait for 1 ns;"),
+                "A function cannot contain a wait statement",
+            ),
+            Diagnostic::error(
+                code.s1("wait until clk = '0';"),
+                "A process with a sensitivity list cannot contain a wait statement",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn procedure_with_wait_called_by_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+procedure inner is
+begin
+  wait for 1 ns;
+end procedure;
+
+procedure outer is
+begin
+  inner;
+end procedure;
+
+impure function fun return natural is
+begin
+  outer;
+  return 0;
+end function;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::error(
+            code.s1("outer;").s1("outer"),
+            "A function cannot call procedure 'outer' which may execute a wait statement",
+        )
+        .related(code.s1("wait for 1 ns;"), "Wait statement")],
+    );
+}
+
+#[test]
+fn procedure_with_wait_called_by_sensitive_process() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  procedure sync(signal clk : in bit);
+  procedure no_wait(signal clk : in bit);
+end package;
+
+package body pkg is
+  procedure sync(signal clk : in bit) is
+  begin
+    if clk = '0' then
+      wait until clk = '1';
+    end if;
+  end procedure;
+
+  procedure no_wait(signal clk : in bit) is
+  begin
+  end procedure;
+end package body;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : bit;
+
+  procedure recursive(n : natural) is
+  begin
+    if n > 0 then
+      recursive(n - 1);
+    else
+      sync(clk);
+    end if;
+  end procedure;
+begin
+  sensitive : process (clk)
+  begin
+    no_wait(clk);
+    sync(clk);
+    recursive(2);
+  end process;
+
+  waiting : process
+  begin
+    sync(clk);
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    let wait = diagnostics
+        .iter()
+        .find_map(|diag| diag.related.first().map(|(pos, _)| pos.clone()))
+        .unwrap();
+    assert_eq!(wait.start().line, 10);
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("sync(clk)", 2).s1("sync"),
+                "A process with a sensitivity list cannot call procedure 'sync' which may execute a wait statement",
+            )
+            .related(&wait, "Wait statement"),
+            Diagnostic::error(
+                code.s("recursive(2)", 1).s1("recursive"),
+                "A process with a sensitivity list cannot call procedure 'recursive' which may execute a wait statement",
+            )
+            .related(&wait, "Wait statement"),
+        ],
+    );
+}
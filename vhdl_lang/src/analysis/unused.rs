@@ -0,0 +1,505 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Lints of subprogram parameters, variables and signals whose values are never used
+//! or never assigned. They are performed after all names of the subprogram body, process
+//! or architecture have been resolved and only when a severity is configured for them.
+
+use super::analyze::*;
+use super::named_entity::*;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// An assignment to a variable that is a candidate for being write-only
+struct VariableWrite {
+    /// The position of the name of the variable within the target
+    pos: SrcPos,
+    /// The line of the assignment when it can be removed without side effects
+    removable_line: Option<u32>,
+}
+
+/// The assignments to the signals of an architecture
+#[derive(Default)]
+struct SignalWrites {
+    /// The positions of the names of the signals within the targets of signal assignments
+    writes: FnvHashMap<EntityId, Vec<SrcPos>>,
+    /// Signals that may be assigned through an actual of a port map or procedure call,
+    /// or through an alias
+    maybe_written: FnvHashSet<EntityId>,
+}
+
+impl SignalWrites {
+    fn is_assigned(&self, id: EntityId) -> bool {
+        self.writes.contains_key(&id) || self.maybe_written.contains(&id)
+    }
+
+    fn add_maybe_written(&mut self, item: &mut impl Search) {
+        let mut searcher = FindReads::default();
+        let _ = item.search(&mut searcher);
+        self.maybe_written.extend(searcher.reads);
+    }
+
+    fn add_target(&mut self, target: &WithPos<Target>) {
+        if let Target::Name(ref name) = target.item {
+            if let Some((pos, id)) = written_object(&target.pos, name) {
+                self.writes.entry(id).or_default().push(pos);
+            }
+        }
+    }
+
+    fn add_declarations(&mut self, declarations: &mut [Declaration]) {
+        for decl in declarations.iter_mut() {
+            match decl {
+                Declaration::SubprogramBody(ref mut body) => {
+                    self.add_declarations(&mut body.declarations);
+                    self.add_sequential(&mut body.statements);
+                }
+                Declaration::Alias(ref mut alias) => {
+                    self.add_maybe_written(&mut alias.name);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_sequential(&mut self, statements: &mut [LabeledSequentialStatement]) {
+        for statement in statements.iter_mut() {
+            match statement.statement {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    self.add_target(&assign.target)
+                }
+                SequentialStatement::SignalForceAssignment(ref assign) => {
+                    self.add_target(&assign.target)
+                }
+                SequentialStatement::SignalReleaseAssignment(ref assign) => {
+                    self.add_target(&assign.target)
+                }
+                SequentialStatement::ProcedureCall(ref mut call) => {
+                    self.add_maybe_written(&mut call.item)
+                }
+                SequentialStatement::If(ref mut ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter_mut() {
+                        self.add_sequential(&mut conditional.item);
+                    }
+                    if let Some(ref mut else_item) = ifstmt.else_item {
+                        self.add_sequential(else_item);
+                    }
+                }
+                SequentialStatement::Case(ref mut case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter_mut() {
+                        self.add_sequential(&mut alternative.item);
+                    }
+                }
+                SequentialStatement::Loop(ref mut loop_stmt) => {
+                    self.add_sequential(&mut loop_stmt.statements);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_concurrent(&mut self, statements: &mut [LabeledConcurrentStatement]) {
+        for statement in statements.iter_mut() {
+            match statement.statement {
+                ConcurrentStatement::Process(ref mut process) => {
+                    self.add_declarations(&mut process.decl);
+                    self.add_sequential(&mut process.statements);
+                }
+                ConcurrentStatement::Assignment(ref assign) => self.add_target(&assign.target),
+                ConcurrentStatement::ProcedureCall(ref mut pcall) => {
+                    self.add_maybe_written(&mut pcall.call.item)
+                }
+                ConcurrentStatement::Instance(ref mut instance) => {
+                    self.add_maybe_written(&mut instance.port_map)
+                }
+                ConcurrentStatement::Block(ref mut block) => {
+                    self.add_maybe_written(&mut block.header.port_map);
+                    self.add_declarations(&mut block.decl);
+                    self.add_concurrent(&mut block.statements);
+                }
+                ConcurrentStatement::ForGenerate(ref mut gen) => {
+                    self.add_generate_body(&mut gen.body);
+                }
+                ConcurrentStatement::IfGenerate(ref mut gen) => {
+                    for conditional in gen.conditionals.iter_mut() {
+                        self.add_generate_body(&mut conditional.item);
+                    }
+                    if let Some(ref mut else_item) = gen.else_item {
+                        self.add_generate_body(else_item);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                    for alternative in gen.alternatives.iter_mut() {
+                        self.add_generate_body(&mut alternative.item);
+                    }
+                }
+                ConcurrentStatement::Assert(..) | ConcurrentStatement::PslDirective(..) => {}
+            }
+        }
+    }
+
+    fn add_generate_body(&mut self, body: &mut GenerateBody) {
+        if let Some(ref mut decl) = body.decl {
+            self.add_declarations(decl);
+        }
+        self.add_concurrent(&mut body.statements);
+    }
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// Warn on signals declared by an architecture that are assigned but never read
+    /// or read but never assigned. A signal connected to an actual of a port map or
+    /// procedure call may be assigned through it and is not reported as never assigned.
+    pub fn check_unused_signals(
+        &self,
+        declarations: &mut Vec<Declaration>,
+        statements: &mut Vec<LabeledConcurrentStatement>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let write_only = self.root.is_lint_enabled(ErrorCode::WriteOnlySignal);
+        let unassigned = self.root.is_lint_enabled(ErrorCode::UnassignedSignal);
+        if !write_only && !unassigned {
+            return;
+        }
+
+        let signals: Vec<_> = declarations
+            .iter()
+            .filter_map(|decl| match decl {
+                Declaration::Object(object) if object.class == ObjectClass::Signal => {
+                    Some((object.ident.decl?, object.ident.tree.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        if signals.is_empty() {
+            return;
+        }
+
+        let mut writes = SignalWrites::default();
+        writes.add_declarations(declarations);
+        writes.add_concurrent(statements);
+
+        let mut searcher = FindReads {
+            writes: writes
+                .writes
+                .values()
+                .flat_map(|writes| writes.iter().cloned())
+                .collect(),
+            ..Default::default()
+        };
+        let _ = declarations.search(&mut searcher);
+        let _ = statements.search(&mut searcher);
+
+        for (id, ident) in signals {
+            let is_read = searcher.reads.contains(&id);
+            let is_assigned = writes.is_assigned(id);
+
+            if write_only && is_assigned && !is_read {
+                let mut diagnostic = Diagnostic::warning(
+                    &ident.pos,
+                    format!("Signal '{}' is assigned but never read", ident.item),
+                )
+                .with_code(ErrorCode::WriteOnlySignal);
+                for pos in writes.writes.get(&id).into_iter().flatten() {
+                    diagnostic.add_related(pos, "Assigned here");
+                }
+                diagnostics.push(diagnostic);
+            } else if unassigned && is_read && !is_assigned {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        &ident.pos,
+                        format!("Signal '{}' is read but never assigned", ident.item),
+                    )
+                    .with_code(ErrorCode::UnassignedSignal),
+                );
+            }
+        }
+    }
+
+    /// Warn on parameters of a subprogram body that are never referenced within it
+    pub fn check_unused_parameters(
+        &self,
+        body: &mut SubprogramBody,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if !self.root.is_lint_enabled(ErrorCode::UnusedParameter) {
+            return;
+        }
+
+        let (kind, designator, parameters) = match body.specification {
+            SubprogramDeclaration::Procedure(ref proc) => {
+                ("procedure", &proc.designator, &proc.parameter_list)
+            }
+            SubprogramDeclaration::Function(ref fun) => {
+                ("function", &fun.designator, &fun.parameter_list)
+            }
+        };
+        let designator_pos = designator.tree.pos.clone();
+        let name = designator.tree.item.to_string();
+
+        let unused: Vec<_> = parameters
+            .iter()
+            .filter_map(|parameter| match parameter {
+                InterfaceDeclaration::Object(object) => Some(&object.ident),
+                InterfaceDeclaration::File(file) => Some(&file.ident),
+                _ => None,
+            })
+            .filter_map(|ident| Some((ident.decl?, ident.tree.clone())))
+            .collect();
+
+        if unused.is_empty() {
+            return;
+        }
+
+        let mut searcher = FindReads::default();
+        let _ = body.declarations.search(&mut searcher);
+        let _ = body.statements.search(&mut searcher);
+
+        for (id, ident) in unused {
+            if !searcher.reads.contains(&id) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        &ident.pos,
+                        format!(
+                            "Parameter '{}' of {kind} '{name}' is never used",
+                            ident.item
+                        ),
+                    )
+                    .related(&designator_pos, format!("Parameter of {kind} '{name}'"))
+                    .with_code(ErrorCode::UnusedParameter),
+                );
+            }
+        }
+    }
+
+    /// Warn on variables of a declarative part that are assigned by the statements
+    /// but never read. The declaration and the assignments may be removed when each
+    /// of them is on a line of its own and none of them calls an impure function.
+    pub fn check_write_only_variables(
+        &self,
+        declarations: &mut Vec<Declaration>,
+        statements: &mut Vec<LabeledSequentialStatement>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if !self.root.is_lint_enabled(ErrorCode::WriteOnlyVariable) {
+            return;
+        }
+
+        let variables: Vec<_> = declarations
+            .iter_mut()
+            .filter_map(|decl| match decl {
+                Declaration::Object(object) if object.class == ObjectClass::Variable => Some((
+                    object.ident.decl?,
+                    object.ident.tree.clone(),
+                    self.removable_declaration(object),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        if variables.is_empty() {
+            return;
+        }
+
+        let mut writes: FnvHashMap<EntityId, Vec<VariableWrite>> = FnvHashMap::default();
+        self.variable_writes(statements, &mut writes);
+
+        let mut searcher = FindReads {
+            writes: writes
+                .values()
+                .flat_map(|writes| writes.iter().map(|write| write.pos.clone()))
+                .collect(),
+            ..Default::default()
+        };
+        let _ = declarations.search(&mut searcher);
+        let _ = statements.search(&mut searcher);
+
+        for (id, ident, declaration_line) in variables {
+            let Some(writes) = writes.get(&id) else {
+                continue;
+            };
+            if searcher.reads.contains(&id) {
+                continue;
+            }
+
+            let mut diagnostic = Diagnostic::warning(
+                &ident.pos,
+                format!("Variable '{}' is assigned but never read", ident.item),
+            )
+            .with_code(ErrorCode::WriteOnlyVariable);
+            for write in writes.iter() {
+                diagnostic.add_related(&write.pos, "Assigned here");
+            }
+
+            let removable_lines: Option<Vec<u32>> = std::iter::once(declaration_line)
+                .chain(writes.iter().map(|write| write.removable_line))
+                .collect();
+            if let Some(mut lines) = removable_lines {
+                lines.sort_unstable();
+                lines.dedup();
+                for line in lines {
+                    diagnostic = diagnostic.with_fix(remove_line(&ident.pos.source, line), "");
+                }
+            }
+
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    /// The assignments to variables by the statements and the statements nested within them
+    fn variable_writes(
+        &self,
+        statements: &mut [LabeledSequentialStatement],
+        writes: &mut FnvHashMap<EntityId, Vec<VariableWrite>>,
+    ) {
+        for statement in statements.iter_mut() {
+            match statement.statement {
+                SequentialStatement::VariableAssignment(ref mut assign) => {
+                    let Target::Name(ref name) = assign.target.item else {
+                        continue;
+                    };
+                    let Some((pos, id)) = written_object(&assign.target.pos, name) else {
+                        continue;
+                    };
+                    let removable_line = if statement.label.is_none()
+                        && !self.calls_impure_function(&mut assign.target)
+                        && !self.calls_impure_function(&mut assign.rhs)
+                    {
+                        sole_statement_line(&assign.target.pos)
+                    } else {
+                        None
+                    };
+                    writes.entry(id).or_default().push(VariableWrite {
+                        pos,
+                        removable_line,
+                    });
+                }
+                SequentialStatement::If(ref mut ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter_mut() {
+                        self.variable_writes(&mut conditional.item, writes);
+                    }
+                    if let Some(ref mut else_item) = ifstmt.else_item {
+                        self.variable_writes(else_item, writes);
+                    }
+                }
+                SequentialStatement::Case(ref mut case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter_mut() {
+                        self.variable_writes(&mut alternative.item, writes);
+                    }
+                }
+                SequentialStatement::Loop(ref mut loop_stmt) => {
+                    self.variable_writes(&mut loop_stmt.statements, writes);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The line of a variable declaration when it is the only declaration on it
+    /// and its initial value does not call an impure function
+    fn removable_declaration(&self, object: &mut ObjectDeclaration) -> Option<u32> {
+        if let Some(ref mut expr) = object.expression {
+            if self.calls_impure_function(expr) {
+                return None;
+            }
+        }
+
+        let pos = &object.ident.tree.pos;
+        let line = pos.start().line;
+        let contents = pos.source.contents();
+        let text = contents.get_line(line as usize)?;
+        let rest = strip_keyword(text.trim(), "variable")?;
+        let rest = rest.strip_prefix(object.ident.tree.item.name_utf8().as_str())?;
+        (rest.trim_start().starts_with(':') && is_single_statement(text)).then_some(line)
+    }
+
+    fn calls_impure_function(&self, item: &mut impl Search) -> bool {
+        let mut searcher = FindImpureCalls { arena: self.arena };
+        matches!(item.search(&mut searcher), Found)
+    }
+}
+
+/// The position and entity of the object that is written by an assignment to the target name.
+/// Elements and slices of an object are writes of the object.
+pub(super) fn written_object(pos: &SrcPos, name: &Name) -> Option<(SrcPos, EntityId)> {
+    match name {
+        Name::Designator(designator) => Some((pos.clone(), designator.reference?)),
+        Name::Selected(prefix, _) | Name::Slice(prefix, _) => {
+            written_object(&prefix.pos, &prefix.item)
+        }
+        Name::CallOrIndexed(fcall) => written_object(&fcall.name.pos, &fcall.name.item),
+        Name::SelectedAll(..) | Name::Attribute(..) | Name::External(..) => None,
+    }
+}
+
+/// The line of a statement starting at the position when it is the only statement on it
+fn sole_statement_line(pos: &SrcPos) -> Option<u32> {
+    let line = pos.start().line;
+    let contents = pos.source.contents();
+    let text = contents.get_line(line as usize)?;
+    let start = text.chars().take_while(|chr| chr.is_whitespace()).count();
+    (start == pos.start().character as usize && is_single_statement(text)).then_some(line)
+}
+
+/// True if the line ends a single statement or declaration, ignoring a trailing comment
+fn is_single_statement(line: &str) -> bool {
+    let code = line.split("--").next().unwrap_or_default().trim_end();
+    code.ends_with(';') && code.matches(';').count() == 1 && !code.contains('"')
+}
+
+fn strip_keyword<'s>(text: &'s str, keyword: &str) -> Option<&'s str> {
+    let rest = text
+        .get(..keyword.len())
+        .filter(|word| word.eq_ignore_ascii_case(keyword))
+        .map(|_| &text[keyword.len()..])?;
+    rest.starts_with(char::is_whitespace)
+        .then(|| rest.trim_start())
+}
+
+/// The position of a whole line including its line break
+fn remove_line(source: &Source, line: u32) -> SrcPos {
+    source.pos(Position::new(line, 0), Position::new(line + 1, 0))
+}
+
+/// Finds the entities that are referenced other than by being written to
+#[derive(Default)]
+struct FindReads {
+    /// The positions of the names of variables or signals that are assigned
+    writes: FnvHashSet<SrcPos>,
+    reads: FnvHashSet<EntityId>,
+}
+
+impl Searcher for FindReads {
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        if let Some(id) = reference {
+            if !self.writes.contains(pos) {
+                self.reads.insert(*id);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Finds a call of an impure function
+struct FindImpureCalls<'b> {
+    arena: &'b Arena,
+}
+
+impl<'b> Searcher for FindImpureCalls<'b> {
+    fn search_pos_with_ref(&mut self, _pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        if let Some(id) = reference {
+            if let AnyEntKind::Overloaded(overloaded) = self.arena.get(*id).kind() {
+                if overloaded.signature().is_impure() {
+                    return Finished(Found);
+                }
+            }
+        }
+        NotFinished
+    }
+}
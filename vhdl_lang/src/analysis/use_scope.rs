@@ -0,0 +1,216 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! The part of a design unit that is within the scope of a use clause.
+//! LRM 12.2 The scope of a use clause extends from the clause to the end of
+//! the immediately enclosing declarative region.
+
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+
+/// How far the scope of a use clause extends
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub(super) enum UseScope {
+    /// The clause is not within the design unit
+    NotFound,
+    /// The scope ends within the design unit
+    Unit,
+    /// The scope extends to the secondary units of the primary unit
+    SecondaryUnits,
+}
+
+/// Whether the clause was found within a declarative part
+enum Found {
+    No,
+    /// Within a nested region that has been searched completely
+    Nested,
+    /// Directly within the declarative part, the statements of the enclosing
+    /// region still have to be searched
+    Here,
+}
+
+/// Search the part of the design unit that is within the scope of the use clause at `clause`
+pub(super) fn search_use_scope(
+    unit: &mut AnyDesignUnit,
+    clause: &SrcPos,
+    searcher: &mut impl Searcher,
+) -> UseScope {
+    let in_context_clause = unit
+        .context_clause()
+        .is_some_and(|items| items.iter().any(|item| &item.pos == clause));
+    if in_context_clause {
+        let _ = unit.search(searcher);
+        return match unit {
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(_) | AnyPrimaryUnit::Package(_)) => {
+                UseScope::SecondaryUnits
+            }
+            _ => UseScope::Unit,
+        };
+    }
+
+    match unit {
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) => {
+            match search_declarations(&mut entity.decl, clause, searcher) {
+                Found::Here => {
+                    let _ = entity.statements.search(searcher);
+                    UseScope::SecondaryUnits
+                }
+                Found::Nested => UseScope::Unit,
+                Found::No => {
+                    found_in_unit(search_concurrent(&mut entity.statements, clause, searcher))
+                }
+            }
+        }
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package)) => {
+            match search_declarations(&mut package.decl, clause, searcher) {
+                Found::Here => UseScope::SecondaryUnits,
+                Found::Nested => UseScope::Unit,
+                Found::No => UseScope::NotFound,
+            }
+        }
+        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(architecture)) => {
+            match search_declarations(&mut architecture.decl, clause, searcher) {
+                Found::Here => {
+                    let _ = architecture.statements.search(searcher);
+                    UseScope::Unit
+                }
+                Found::Nested => UseScope::Unit,
+                Found::No => found_in_unit(search_concurrent(
+                    &mut architecture.statements,
+                    clause,
+                    searcher,
+                )),
+            }
+        }
+        AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(body)) => {
+            match search_declarations(&mut body.decl, clause, searcher) {
+                Found::Here | Found::Nested => UseScope::Unit,
+                Found::No => UseScope::NotFound,
+            }
+        }
+        AnyDesignUnit::Primary(
+            AnyPrimaryUnit::Configuration(_)
+            | AnyPrimaryUnit::PackageInstance(_)
+            | AnyPrimaryUnit::Context(_),
+        ) => UseScope::NotFound,
+    }
+}
+
+fn found_in_unit(found: bool) -> UseScope {
+    if found {
+        UseScope::Unit
+    } else {
+        UseScope::NotFound
+    }
+}
+
+/// Search the declarations following the clause when it is one of the declarations
+/// or the scope of the clause when it is within a nested declarative region
+fn search_declarations(
+    decl: &mut [Declaration],
+    clause: &SrcPos,
+    searcher: &mut impl Searcher,
+) -> Found {
+    for idx in 0..decl.len() {
+        let (current, rest) = decl[idx..].split_first_mut().unwrap();
+        match current {
+            Declaration::Use(use_clause) if &use_clause.pos == clause => {
+                for declaration in rest.iter_mut() {
+                    let _ = declaration.search(searcher);
+                }
+                return Found::Here;
+            }
+            Declaration::SubprogramBody(body) => {
+                match search_declarations(&mut body.declarations, clause, searcher) {
+                    Found::Here => {
+                        let _ = body.statements.search(searcher);
+                        return Found::Nested;
+                    }
+                    Found::Nested => return Found::Nested,
+                    Found::No => {}
+                }
+            }
+            Declaration::Type(TypeDeclaration {
+                def: TypeDefinition::ProtectedBody(body),
+                ..
+            }) => match search_declarations(&mut body.decl, clause, searcher) {
+                Found::Here | Found::Nested => return Found::Nested,
+                Found::No => {}
+            },
+            _ => {}
+        }
+    }
+    Found::No
+}
+
+/// Search the scope of the clause when it is within the declarative region of a statement
+/// Returns true if the clause was found
+fn search_concurrent(
+    statements: &mut [LabeledConcurrentStatement],
+    clause: &SrcPos,
+    searcher: &mut impl Searcher,
+) -> bool {
+    for statement in statements.iter_mut() {
+        let found = match statement.statement {
+            ConcurrentStatement::Process(ref mut process) => {
+                search_region(&mut process.decl, &mut process.statements, clause, searcher)
+            }
+            ConcurrentStatement::Block(ref mut block) => {
+                search_region(&mut block.decl, &mut block.statements, clause, searcher)
+                    || search_concurrent(&mut block.statements, clause, searcher)
+            }
+            ConcurrentStatement::ForGenerate(ref mut gen) => {
+                search_generate_body(&mut gen.body, clause, searcher)
+            }
+            ConcurrentStatement::IfGenerate(ref mut gen) => gen
+                .conditionals
+                .iter_mut()
+                .map(|conditional| &mut conditional.item)
+                .chain(gen.else_item.as_mut())
+                .any(|body| search_generate_body(body, clause, searcher)),
+            ConcurrentStatement::CaseGenerate(ref mut gen) => gen
+                .alternatives
+                .iter_mut()
+                .any(|alternative| search_generate_body(&mut alternative.item, clause, searcher)),
+            _ => false,
+        };
+        if found {
+            return true;
+        }
+    }
+    false
+}
+
+fn search_generate_body(
+    body: &mut GenerateBody,
+    clause: &SrcPos,
+    searcher: &mut impl Searcher,
+) -> bool {
+    let found = match body.decl {
+        Some(ref mut decl) => search_region(decl, &mut body.statements, clause, searcher),
+        None => false,
+    };
+    found || search_concurrent(&mut body.statements, clause, searcher)
+}
+
+/// Search the scope of the clause within a declarative region with statements
+/// Returns true if the clause was found
+fn search_region<T: Search>(
+    decl: &mut [Declaration],
+    statements: &mut Vec<T>,
+    clause: &SrcPos,
+    searcher: &mut impl Searcher,
+) -> bool {
+    match search_declarations(decl, clause, searcher) {
+        Found::Here => {
+            let _ = statements.search(searcher);
+            true
+        }
+        Found::Nested => true,
+        Found::No => false,
+    }
+}
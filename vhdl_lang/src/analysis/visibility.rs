@@ -8,7 +8,7 @@ use super::region::*;
 use crate::ast::*;
 use crate::data::*;
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
 
 #[derive(Clone, Debug)]
@@ -120,6 +120,36 @@ impl<'a> Visibility<'a> {
     }
 
     /// Helper function lookup a visible declaration within the region
+    /// Add the identifiers that are potentially visible
+    pub fn visible_identifiers(&self, identifiers: &mut FnvHashSet<Symbol>) {
+        for visible_region in self.all_in_regions.iter() {
+            identifiers.extend(
+                visible_region
+                    .region
+                    .entities
+                    .keys()
+                    .filter_map(Designator::as_identifier)
+                    .cloned(),
+            );
+        }
+
+        identifiers.extend(
+            self.visible
+                .keys()
+                .filter_map(Designator::as_identifier)
+                .cloned(),
+        );
+    }
+
+    /// Add the designators that are potentially visible
+    pub fn visible_designators(&self, designators: &mut FnvHashSet<Designator>) {
+        for visible_region in self.all_in_regions.iter() {
+            designators.extend(visible_region.region.entities.keys().cloned());
+        }
+
+        designators.extend(self.visible.keys().cloned());
+    }
+
     pub fn lookup_into(&self, designator: &Designator, visible: &mut Visible<'a>) {
         for visible_region in self.all_in_regions.iter() {
             if let Some(named_entities) = visible_region.region.lookup_immediate(designator) {
@@ -173,6 +203,20 @@ impl<'a> Visible<'a> {
         };
     }
 
+    /// The visible entities together with the positions where they were made visible
+    pub fn entities(&self) -> impl Iterator<Item = (EntRef<'a>, &[Option<SrcPos>])> {
+        self.visible_entities
+            .values()
+            .map(|visible_entity| (visible_entity.entity, visible_entity.visible_pos.as_slice()))
+    }
+
+    /// The positions where the entity was made visible
+    pub fn visible_pos(&self, ent: EntRef<'a>) -> Option<&[Option<SrcPos>]> {
+        self.visible_entities
+            .get(&ent.as_actual().id())
+            .map(|visible_entity| visible_entity.visible_pos.as_slice())
+    }
+
     pub fn into_unambiguous(
         self,
         pos: &SrcPos,
@@ -0,0 +1,278 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! The names visible at a position within a design unit
+
+use super::analyze::*;
+use super::named_entity::*;
+use super::region::Scope;
+use super::root::DesignRoot;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+use std::cell::RefCell;
+
+/// How a name was made visible
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VisibleOrigin {
+    /// Declared in the region at the position or an enclosing region
+    Immediate,
+    /// Made visible by a use clause. The positions lead from the use clause to the context
+    /// references that made it visible within the design unit.
+    /// They are empty for the implicit `use std.standard.all`.
+    UseClause(Vec<SrcPos>),
+    /// A library made visible by a library clause, followed by the context references that
+    /// made it visible within the design unit. They are empty for the implicit `library std, work`.
+    Library(Vec<SrcPos>),
+}
+
+/// A named entity that is visible at a position
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VisibleName {
+    pub designator: Designator,
+    /// The kind of the named entity such as "signal" or "function"
+    pub kind: String,
+    pub decl_pos: Option<SrcPos>,
+    pub origin: VisibleOrigin,
+}
+
+impl VisibleName {
+    fn new(
+        designator: Designator,
+        ent: EntRef,
+        visible_pos: Option<Vec<Option<SrcPos>>>,
+    ) -> VisibleName {
+        let decl_pos = ent.decl_pos().cloned();
+
+        let origin = match visible_pos {
+            None => VisibleOrigin::Immediate,
+            // The name of a design unit is made visible at its own declaration
+            Some(visible_pos) if decl_pos.is_some() && visible_pos.contains(&decl_pos) => {
+                VisibleOrigin::Immediate
+            }
+            Some(visible_pos) => {
+                let visible_pos = visible_pos.into_iter().flatten().collect();
+                if matches!(ent.kind(), AnyEntKind::Library) {
+                    VisibleOrigin::Library(visible_pos)
+                } else {
+                    VisibleOrigin::UseClause(visible_pos)
+                }
+            }
+        };
+
+        VisibleName {
+            designator,
+            kind: ent.kind().describe().to_owned(),
+            decl_pos,
+            origin,
+        }
+    }
+}
+
+/// Captures the names visible at a position while a design unit is analyzed.
+/// Since the end of a declaration or statement is not known, its region is assumed
+/// to extend until the start of the next declaration or statement of the same list.
+pub(super) struct ScopeProbe {
+    pos: SrcPos,
+    state: RefCell<ProbeState>,
+}
+
+struct ProbeState {
+    /// True if the declaration or statement being analyzed contains the position
+    item_active: bool,
+    /// The number of nested lists of declarations or statements containing the position
+    depth: usize,
+    captured: Option<Captured>,
+}
+
+struct Captured {
+    depth: usize,
+    /// True if the position is before a declaration or statement of the list
+    /// rather than after all of them
+    exact: bool,
+    names: Vec<VisibleName>,
+}
+
+/// A list of declarations or statements being analyzed
+#[derive(Default)]
+pub(super) struct ProbeList {
+    active: bool,
+    captured: bool,
+    starts: Vec<Option<Position>>,
+}
+
+impl ScopeProbe {
+    pub fn new(pos: SrcPos) -> ScopeProbe {
+        ScopeProbe {
+            pos,
+            state: RefCell::new(ProbeState {
+                item_active: true,
+                depth: 0,
+                captured: None,
+            }),
+        }
+    }
+
+    fn capture(&self, scope: &Scope, exact: bool) {
+        let mut state = self.state.borrow_mut();
+
+        // The innermost list wins unless the position was already found within a sibling list
+        let replace = match state.captured {
+            Some(ref captured) => {
+                state.depth > captured.depth || (state.depth == captured.depth && !captured.exact)
+            }
+            None => true,
+        };
+
+        if replace {
+            let mut names: Vec<_> = scope
+                .visible_names(&self.pos)
+                .into_iter()
+                .map(|(designator, ent, visible_pos)| {
+                    VisibleName::new(designator, ent, visible_pos)
+                })
+                .collect();
+            names.sort_by(|a, b| {
+                (a.designator.to_string(), &a.decl_pos)
+                    .cmp(&(b.designator.to_string(), &b.decl_pos))
+            });
+
+            state.captured = Some(Captured {
+                depth: state.depth,
+                exact,
+                names,
+            });
+        }
+    }
+
+    fn into_names(self) -> Vec<VisibleName> {
+        self.state
+            .into_inner()
+            .captured
+            .map(|captured| captured.names)
+            .unwrap_or_default()
+    }
+}
+
+/// Finds the first position of a declaration or statement
+struct FindStart {
+    start: Option<Position>,
+}
+
+impl FindStart {
+    fn add(&mut self, pos: &SrcPos) {
+        let start = pos.start();
+        if self.start.is_none_or(|prev| start < prev) {
+            self.start = Some(start);
+        }
+    }
+}
+
+impl Searcher for FindStart {
+    fn search_with_pos(&mut self, pos: &SrcPos) -> SearchState {
+        self.add(pos);
+        NotFinished
+    }
+
+    fn search_decl(&mut self, decl: FoundDeclaration) -> SearchState {
+        self.add(decl.pos());
+        NotFinished
+    }
+
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, _: &mut Reference) -> SearchState {
+        self.add(pos);
+        NotFinished
+    }
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// Called before a list of declarations or statements is analyzed
+    pub(super) fn probe_list<T: Search>(&self, items: &mut [T]) -> ProbeList {
+        let Some(ref probe) = self.probe else {
+            return ProbeList::default();
+        };
+
+        let mut state = probe.state.borrow_mut();
+        let active = state.item_active;
+        if !active {
+            return ProbeList::default();
+        }
+        state.depth += 1;
+
+        ProbeList {
+            active: true,
+            captured: false,
+            starts: items
+                .iter_mut()
+                .map(|item| {
+                    let mut searcher = FindStart { start: None };
+                    let _ = item.search(&mut searcher);
+                    searcher.start
+                })
+                .collect(),
+        }
+    }
+
+    /// Called before the item at the index of the list is analyzed
+    pub(super) fn probe_item(&self, scope: &Scope<'a>, list: &mut ProbeList, idx: usize) {
+        let Some(ref probe) = self.probe else {
+            return;
+        };
+        if !list.active {
+            return;
+        }
+
+        let cursor = probe.pos.start();
+        if !list.captured && list.starts[idx].is_some_and(|start| start > cursor) {
+            probe.capture(scope, true);
+            list.captured = true;
+        }
+
+        let item_active = !list.captured
+            && list.starts[idx].is_some_and(|start| start <= cursor)
+            && list.starts[idx + 1..]
+                .iter()
+                .flatten()
+                .next()
+                .is_none_or(|next| *next > cursor);
+        probe.state.borrow_mut().item_active = item_active;
+    }
+
+    /// Called after all items of the list are analyzed
+    pub(super) fn probe_end(&self, scope: &Scope<'a>, list: ProbeList) {
+        let Some(ref probe) = self.probe else {
+            return;
+        };
+        if !list.active {
+            return;
+        }
+
+        if !list.captured {
+            probe.capture(scope, false);
+        }
+
+        let mut state = probe.state.borrow_mut();
+        state.depth -= 1;
+        // The list is within the declaration or statement containing the position
+        state.item_active = true;
+    }
+}
+
+impl DesignRoot {
+    /// The names visible at the cursor together with their kinds and how they were made visible.
+    /// Returns an empty list if the cursor is not within an analyzed design unit.
+    pub fn visible_names(&self, source: &Source, cursor: Position) -> Vec<VisibleName> {
+        let pos = SrcPos::new(source.clone(), crate::data::Range::new(cursor, cursor));
+        self.reanalyze_containing(
+            source,
+            cursor,
+            |context| context.with_probe(pos),
+            |context| context.probe.map(ScopeProbe::into_names),
+        )
+        .flatten()
+        .unwrap_or_default()
+    }
+}
@@ -0,0 +1,197 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! LRM 10.2 Wait statements within procedures called by functions or by processes with a
+//! sensitivity list. The procedure calls of each design unit are recorded during analysis
+//! and checked against the call graph once all design units have been analyzed.
+
+use super::analyze::*;
+use super::named_entity::*;
+use super::region::*;
+use super::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use fnv::FnvHashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum CallerKind {
+    Function,
+    Procedure,
+    /// A process with a sensitivity list
+    SensitiveProcess,
+}
+
+/// The wait statements and procedure calls of a subprogram body or process
+#[derive(Clone, Debug)]
+pub(super) struct Caller {
+    kind: CallerKind,
+    /// The entities of the subprogram body and its declaration that calls refer to
+    ids: Vec<EntityId>,
+    waits: Vec<SrcPos>,
+    calls: Vec<(EntityId, SrcPos)>,
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// The declaration of a subprogram body made earlier in the same declarative region
+    /// or in the package of a package body. Must be called before the body is added to the scope.
+    pub(super) fn subprogram_declaration(
+        &self,
+        scope: &Scope<'a>,
+        body: EntRef<'a>,
+    ) -> Option<EntityId> {
+        let Some(NamedEntities::Overloaded(overloaded)) = scope.lookup_immediate(body.designator())
+        else {
+            return None;
+        };
+        let body = OverloadedEnt::from_any(body).ok()?;
+        let decl = overloaded.get(&body.signature().key())?;
+        decl.is_subprogram_decl().then(|| decl.id())
+    }
+
+    /// Record the wait statements and procedure calls of a subprogram body or process
+    pub(super) fn record_caller(
+        &self,
+        kind: CallerKind,
+        ids: Vec<EntityId>,
+        statements: &[LabeledSequentialStatement],
+    ) {
+        let mut caller = Caller {
+            kind,
+            ids,
+            waits: Vec::new(),
+            calls: Vec::new(),
+        };
+        add_waits_and_calls(statements, &mut caller);
+        if !caller.calls.is_empty() || (kind == CallerKind::Procedure && !caller.waits.is_empty()) {
+            self.callers.borrow_mut().push(caller);
+        }
+    }
+}
+
+fn add_waits_and_calls(statements: &[LabeledSequentialStatement], caller: &mut Caller) {
+    for statement in statements.iter() {
+        match statement.statement {
+            SequentialStatement::Wait(ref wait) => caller.waits.push(wait.pos.clone()),
+            SequentialStatement::ProcedureCall(ref pcall) => {
+                if let Some(id) = pcall.item.name.item.get_suffix_reference() {
+                    caller.calls.push((id, pcall.item.name.pos.clone()));
+                }
+            }
+            SequentialStatement::If(ref ifstmt) => {
+                for conditional in ifstmt.conditionals.iter() {
+                    add_waits_and_calls(&conditional.item, caller);
+                }
+                if let Some(ref else_item) = ifstmt.else_item {
+                    add_waits_and_calls(else_item, caller);
+                }
+            }
+            SequentialStatement::Case(ref case_stmt) => {
+                for alternative in case_stmt.alternatives.iter() {
+                    add_waits_and_calls(&alternative.item, caller);
+                }
+            }
+            SequentialStatement::Loop(ref loop_stmt) => {
+                add_waits_and_calls(&loop_stmt.statements, caller);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The procedure calls of all design units
+#[derive(Default)]
+pub(super) struct WaitGraph {
+    callers: Vec<Caller>,
+    /// The index of the caller of each procedure entity
+    procedures: FnvHashMap<EntityId, usize>,
+}
+
+impl WaitGraph {
+    pub fn add_callers(&mut self, callers: &[Caller]) {
+        for caller in callers.iter() {
+            if caller.kind == CallerKind::Procedure {
+                for id in caller.ids.iter() {
+                    self.procedures.insert(*id, self.callers.len());
+                }
+            }
+            self.callers.push(caller.clone());
+        }
+    }
+
+    /// Report the calls of functions and processes with a sensitivity list
+    /// of procedures that directly or indirectly execute a wait statement
+    pub fn check_waits(&self, root: &DesignRoot, diagnostics: &mut dyn DiagnosticHandler) {
+        let waits = self.reachable_waits(root);
+
+        for caller in self.callers.iter() {
+            let kind = match caller.kind {
+                CallerKind::Function => "A function",
+                CallerKind::SensitiveProcess => "A process with a sensitivity list",
+                CallerKind::Procedure => continue,
+            };
+
+            for (id, pos) in caller.calls.iter() {
+                let Some(wait) = self.callee(root, *id).and_then(|idx| waits[idx].as_ref()) else {
+                    continue;
+                };
+                diagnostics.push(
+                    Diagnostic::error(
+                        pos,
+                        format!(
+                            "{kind} cannot call procedure '{}' which may execute a wait statement",
+                            root.get_ent(*id).designator()
+                        ),
+                    )
+                    .related(wait, "Wait statement"),
+                );
+            }
+        }
+    }
+
+    /// The caller index of the procedure a call refers to
+    fn callee(&self, root: &DesignRoot, id: EntityId) -> Option<usize> {
+        let ent = root.get_ent(id).as_actual();
+        let ent = match ent.related {
+            Related::InstanceOf(uninstantiated) => uninstantiated,
+            _ => ent,
+        };
+        self.procedures.get(&ent.id()).copied()
+    }
+
+    /// A wait statement that is executed by each caller either directly or through the
+    /// procedures it calls
+    fn reachable_waits(&self, root: &DesignRoot) -> Vec<Option<SrcPos>> {
+        let mut waits: Vec<Option<SrcPos>> = self
+            .callers
+            .iter()
+            .map(|caller| match caller.kind {
+                CallerKind::Procedure => caller.waits.first().cloned(),
+                // Reported where the wait statement is analyzed
+                CallerKind::Function | CallerKind::SensitiveProcess => None,
+            })
+            .collect();
+
+        // Propagate to the callers until there is no change, which terminates for recursive calls
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (idx, caller) in self.callers.iter().enumerate() {
+                if waits[idx].is_some() || caller.kind != CallerKind::Procedure {
+                    continue;
+                }
+                let wait = caller.calls.iter().find_map(|(id, _)| {
+                    self.callee(root, *id)
+                        .and_then(|callee| waits[callee].clone())
+                });
+                if wait.is_some() {
+                    waits[idx] = wait;
+                    changed = true;
+                }
+            }
+        }
+        waits
+    }
+}
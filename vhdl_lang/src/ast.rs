@@ -10,6 +10,7 @@
 #![allow(clippy::large_enum_variant)]
 
 mod display;
+mod shift;
 mod util;
 
 #[macro_use]
@@ -19,6 +20,7 @@ mod any_design_unit;
 pub mod search;
 
 pub use self::display::*;
+pub(crate) use self::shift::ShiftLines;
 pub(crate) use self::util::*;
 pub(crate) use any_design_unit::*;
 
@@ -206,6 +208,8 @@ pub enum SelectedName {
 #[derive(PartialEq, Debug, Clone)]
 pub struct CallOrIndexed {
     pub name: WithPos<Name>,
+    /// The generic map aspect of a call of an uninstantiated subprogram (VHDL-2019)
+    pub generic_map: Option<Vec<AssociationElement>>,
     pub parameters: Vec<AssociationElement>,
 }
 
@@ -370,7 +374,8 @@ pub struct RecordElementResolution {
 #[derive(PartialEq, Debug, Clone)]
 pub enum ResolutionIndication {
     FunctionName(WithPos<SelectedName>),
-    ArrayElement(WithPos<SelectedName>),
+    /// The resolution of the elements of an array such as `(resolved)` or `((resolved))`
+    ArrayElement(Box<ResolutionIndication>),
     Record(Vec<RecordElementResolution>),
     Unresolved,
 }
@@ -495,7 +500,7 @@ pub enum EntityName {
 }
 
 /// LRM 7.2 Attribute specification
-// @TODO there are more classes
+// @TODO property and sequence classes of PSL
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum EntityClass {
     Entity,
@@ -509,7 +514,12 @@ pub enum EntityClass {
     Component,
     Constant,
     Type,
+    Subtype,
     Label,
+    Literal,
+    Units,
+    Group,
+    File,
 }
 
 /// LRM 7.2 Attribute specification
@@ -528,6 +538,29 @@ pub enum Attribute {
     Declaration(AttributeDeclaration),
 }
 
+/// LRM 6.9 Group template declarations
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct EntityClassEntry {
+    pub entity_class: EntityClass,
+    /// The entry ends with a box (<>) and matches any number of trailing constituents
+    pub is_box: bool,
+}
+
+/// LRM 6.9 Group template declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupTemplateDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub entity_class_entries: Vec<WithPos<EntityClassEntry>>,
+}
+
+/// LRM 6.10 Group declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub group_template_name: WithPos<SelectedName>,
+    pub group_constituent_list: Vec<WithPos<Name>>,
+}
+
 /// LRM 5.6.2 Protected type declarations
 #[derive(PartialEq, Debug, Clone)]
 pub struct ProtectedTypeDeclaration {
@@ -631,10 +664,17 @@ pub enum SubprogramDesignator {
     OperatorSymbol(Operator),
 }
 
+/// LRM 4.2.1 Subprogram header of an uninstantiated subprogram
+#[derive(PartialEq, Debug, Clone)]
+pub struct SubprogramHeader {
+    pub generic_list: Vec<InterfaceDeclaration>,
+}
+
 /// LRM 4.2 Subprogram declaration
 #[derive(PartialEq, Debug, Clone)]
 pub struct ProcedureSpecification {
     pub designator: WithDecl<WithPos<SubprogramDesignator>>,
+    pub header: Option<SubprogramHeader>,
     pub parameter_list: Vec<InterfaceDeclaration>,
 }
 
@@ -643,6 +683,7 @@ pub struct ProcedureSpecification {
 pub struct FunctionSpecification {
     pub pure: bool,
     pub designator: WithDecl<WithPos<SubprogramDesignator>>,
+    pub header: Option<SubprogramHeader>,
     pub parameter_list: Vec<InterfaceDeclaration>,
     pub return_type: WithPos<TypeMark>,
 }
@@ -653,6 +694,8 @@ pub struct SubprogramBody {
     pub specification: SubprogramDeclaration,
     pub declarations: Vec<Declaration>,
     pub statements: Vec<LabeledSequentialStatement>,
+    /// The position of the `end` keyword of the body
+    pub end_pos: SrcPos,
 }
 
 /// LRM 4.5.3 Signatures
@@ -668,6 +711,22 @@ pub enum SubprogramDeclaration {
     Function(FunctionSpecification),
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SubprogramKind {
+    Function,
+    Procedure,
+}
+
+/// LRM 4.4 Subprogram instantiation declaration
+#[derive(PartialEq, Debug, Clone)]
+pub struct SubprogramInstantiation {
+    pub kind: SubprogramKind,
+    pub designator: WithDecl<WithPos<SubprogramDesignator>>,
+    pub subprogram_name: WithPos<SelectedName>,
+    pub signature: Option<WithPos<Signature>>,
+    pub generic_map: Option<Vec<AssociationElement>>,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct InterfaceFileDeclaration {
     pub ident: WithDecl<Ident>,
@@ -685,6 +744,51 @@ pub struct InterfaceObjectDeclaration {
     pub expression: Option<WithPos<Expression>>,
 }
 
+/// LRM 6.5.2 Interface object declarations of a port with a mode view indication
+#[derive(PartialEq, Debug, Clone)]
+pub struct InterfaceViewDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub view: ModeViewIndication,
+}
+
+/// LRM 6.5.2 Mode view indication
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewIndication {
+    pub kind: ModeViewKind,
+    pub name: WithPos<SelectedName>,
+    /// The subtype is optional for a record mode view
+    pub subtype_indication: Option<SubtypeIndication>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ModeViewKind {
+    /// view name [of record_subtype]
+    Record,
+    /// view (name) of array_subtype
+    Array,
+}
+
+/// LRM 6.5.2 Mode view declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub typ: SubtypeIndication,
+    pub elements: Vec<ModeViewElement>,
+}
+
+/// The mode of record elements within a mode view declaration
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewElement {
+    pub names: Vec<WithRef<Ident>>,
+    pub mode: ElementMode,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum ElementMode {
+    Simple(WithPos<Mode>),
+    View(ModeViewKind, WithPos<SelectedName>),
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum SubprogramDefault {
     Name(WithPos<SelectedName>),
@@ -709,6 +813,7 @@ pub struct InterfacePackageDeclaration {
 #[derive(PartialEq, Debug, Clone)]
 pub enum InterfaceDeclaration {
     Object(InterfaceObjectDeclaration),
+    View(InterfaceViewDeclaration),
     File(InterfaceFileDeclaration),
     Type(WithDecl<Ident>),
     /// LRM 6.5.4 Interface subprogram declarations
@@ -749,9 +854,15 @@ pub enum Declaration {
     Alias(AliasDeclaration),
     SubprogramDeclaration(SubprogramDeclaration),
     SubprogramBody(SubprogramBody),
+    SubprogramInstantiation(SubprogramInstantiation),
     Use(WithPos<UseClause>),
     Package(PackageInstantiation),
     Configuration(ConfigurationSpecification),
+    GroupTemplate(GroupTemplateDeclaration),
+    Group(GroupDeclaration),
+    View(ModeViewDeclaration),
+    /// The PSL clock declaration `default clock is ...`
+    PslClock(WithPos<Expression>),
 }
 
 /// LRM 10.2 Wait statement
@@ -841,6 +952,7 @@ pub struct VariableAssignment {
 
 /// LRM 10.5 Signal assignment statement
 /// LRM 10.6 Variable assignment statement
+/// LRM 10.13 Return statement
 #[derive(PartialEq, Debug, Clone)]
 pub enum AssignmentRightHand<T> {
     Simple(T),
@@ -917,13 +1029,14 @@ pub struct ExitStatement {
 /// LRM 10.13 Return statement
 #[derive(PartialEq, Debug, Clone)]
 pub struct ReturnStatement {
-    pub expression: Option<WithPos<Expression>>,
+    /// The value is a conditional expression since VHDL-2019
+    pub expression: Option<AssignmentRightHand<WithPos<Expression>>>,
 }
 
 /// LRM 10. Sequential statements
 #[derive(PartialEq, Debug, Clone)]
 pub enum SequentialStatement {
-    Wait(WaitStatement),
+    Wait(WithPos<WaitStatement>),
     Assert(AssertStatement),
     Report(ReportStatement),
     VariableAssignment(VariableAssignment),
@@ -994,6 +1107,37 @@ pub struct ConcurrentAssertStatement {
     pub statement: AssertStatement,
 }
 
+/// IEEE 1850 PSL property or sequence embedded by LRM 11.1.
+/// Only the HDL expressions that are operands of the temporal operators are kept.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct PslProperty {
+    /// Expressions of type boolean, bit or std_ulogic
+    pub booleans: Vec<WithPos<Expression>>,
+    /// Arguments of built-in functions such as prev or stable that may be of any type
+    pub values: Vec<WithPos<Expression>>,
+    /// Counts and ranges of next operators and repetitions
+    pub counts: Vec<WithPos<Expression>>,
+    /// Clock expressions of the @ operator
+    pub clocks: Vec<WithPos<Expression>>,
+}
+
+/// LRM 11.1 PSL directive
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PslDirectiveKind {
+    Assert,
+    Assume,
+    Cover,
+}
+
+/// LRM 11.1 PSL directive
+#[derive(PartialEq, Debug, Clone)]
+pub struct PslDirective {
+    pub kind: PslDirectiveKind,
+    pub property: PslProperty,
+    pub report: Option<WithPos<Expression>>,
+    pub severity: Option<WithPos<Expression>>,
+}
+
 /// 11.6 Concurrent signal assignment statements
 #[derive(PartialEq, Debug, Clone)]
 pub struct ConcurrentSignalAssignment {
@@ -1018,6 +1162,9 @@ pub struct InstantiationStatement {
     pub unit: InstantiatedUnit,
     pub generic_map: Vec<AssociationElement>,
     pub port_map: Vec<AssociationElement>,
+    /// The entity that an instantiated component is bound to by default
+    /// LRM 7.3.3 Default binding indication
+    pub default_binding: Reference,
 }
 
 /// 11.8 Generate statements
@@ -1047,6 +1194,7 @@ pub enum ConcurrentStatement {
     Block(BlockStatement),
     Process(ProcessStatement),
     Assert(ConcurrentAssertStatement),
+    PslDirective(PslDirective),
     Assignment(ConcurrentSignalAssignment),
     Instance(InstantiationStatement),
     ForGenerate(ForGenerateStatement),
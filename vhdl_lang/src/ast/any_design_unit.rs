@@ -202,6 +202,26 @@ impl SecondaryKind {
 }
 
 impl AnyDesignUnit {
+    pub fn context_clause(&self) -> Option<&ContextClause> {
+        match self {
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(unit)) => Some(&unit.context_clause),
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(unit)) => {
+                Some(&unit.context_clause)
+            }
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Package(unit)) => Some(&unit.context_clause),
+            AnyDesignUnit::Primary(AnyPrimaryUnit::PackageInstance(unit)) => {
+                Some(&unit.context_clause)
+            }
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Context(_)) => None,
+            AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(unit)) => {
+                Some(&unit.context_clause)
+            }
+            AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(unit)) => {
+                Some(&unit.context_clause)
+            }
+        }
+    }
+
     pub fn as_primary_mut(&mut self) -> Option<&mut AnyPrimaryUnit> {
         if let AnyDesignUnit::Primary(unit) = self {
             Some(unit)
@@ -218,6 +218,21 @@ impl Display for SelectedName {
 impl Display for CallOrIndexed {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, "{}", self.name)?;
+        if let Some(ref generic_map) = self.generic_map {
+            let mut first = true;
+            for elem in generic_map {
+                if first {
+                    write!(f, " generic map ({elem}")?;
+                } else {
+                    write!(f, ", {elem}")?;
+                }
+                first = false;
+            }
+            write!(f, ")")?;
+            if !self.parameters.is_empty() {
+                write!(f, " ")?;
+            }
+        }
         let mut first = true;
         for param in &self.parameters {
             if first {
@@ -241,6 +256,72 @@ impl Display for AttributeDeclaration {
     }
 }
 
+impl Display for EntityClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EntityClass::Entity => write!(f, "entity"),
+            EntityClass::Architecture => write!(f, "architecture"),
+            EntityClass::Configuration => write!(f, "configuration"),
+            EntityClass::Package => write!(f, "package"),
+            EntityClass::Signal => write!(f, "signal"),
+            EntityClass::Variable => write!(f, "variable"),
+            EntityClass::Procedure => write!(f, "procedure"),
+            EntityClass::Function => write!(f, "function"),
+            EntityClass::Component => write!(f, "component"),
+            EntityClass::Constant => write!(f, "constant"),
+            EntityClass::Type => write!(f, "type"),
+            EntityClass::Subtype => write!(f, "subtype"),
+            EntityClass::Label => write!(f, "label"),
+            EntityClass::Literal => write!(f, "literal"),
+            EntityClass::Units => write!(f, "units"),
+            EntityClass::Group => write!(f, "group"),
+            EntityClass::File => write!(f, "file"),
+        }
+    }
+}
+
+impl Display for EntityClassEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.is_box {
+            write!(f, "{} <>", self.entity_class)
+        } else {
+            write!(f, "{}", self.entity_class)
+        }
+    }
+}
+
+impl Display for GroupTemplateDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "group {} is (", self.ident)?;
+        let mut first = true;
+        for entry in &self.entity_class_entries {
+            if first {
+                write!(f, "{entry}")?;
+            } else {
+                write!(f, ", {entry}")?;
+            }
+            first = false;
+        }
+        write!(f, ");")
+    }
+}
+
+impl Display for GroupDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "group {} : {} (", self.ident, self.group_template_name)?;
+        let mut first = true;
+        for constituent in &self.group_constituent_list {
+            if first {
+                write!(f, "{constituent}")?;
+            } else {
+                write!(f, ", {constituent}")?;
+            }
+            first = false;
+        }
+        write!(f, ");")
+    }
+}
+
 impl Display for Choice {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -537,8 +618,8 @@ impl Display for ResolutionIndication {
             ResolutionIndication::FunctionName(ref name) => {
                 write!(f, "{name}")
             }
-            ResolutionIndication::ArrayElement(ref name) => {
-                write!(f, "({name})")
+            ResolutionIndication::ArrayElement(ref resolution) => {
+                write!(f, "({resolution})")
             }
             ResolutionIndication::Record(elem_resolutions) => {
                 let mut first = true;
@@ -774,12 +855,37 @@ impl Display for SubprogramDesignator {
     }
 }
 
+impl Display for SubprogramHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut first = true;
+        for generic in &self.generic_list {
+            if first {
+                write!(f, "\n  generic (\n    {generic}")?;
+            } else {
+                write!(f, ";\n    {generic}")?;
+            }
+            first = false;
+        }
+        if !first {
+            write!(f, "\n  )")
+        } else {
+            Ok(())
+        }
+    }
+}
+
 impl Display for ProcedureSpecification {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, "procedure {}", self.designator)?;
+        if let Some(header) = &self.header {
+            write!(f, "{header}")?;
+        }
         let mut first = true;
         for param in &self.parameter_list {
             if first {
+                if self.header.is_some() {
+                    write!(f, " parameter ")?;
+                }
                 write!(f, "(\n  {param}")?;
             } else {
                 write!(f, ";\n  {param}")?;
@@ -800,9 +906,15 @@ impl Display for FunctionSpecification {
             write!(f, "impure ")?;
         }
         write!(f, "function {}", self.designator)?;
+        if let Some(header) = &self.header {
+            write!(f, "{header}")?;
+        }
         let mut first = true;
         for param in &self.parameter_list {
             if first {
+                if self.header.is_some() {
+                    write!(f, " parameter ")?;
+                }
                 write!(f, "(\n  {param}")?;
             } else {
                 write!(f, ";\n  {param}")?;
@@ -816,6 +928,34 @@ impl Display for FunctionSpecification {
     }
 }
 
+impl Display for SubprogramInstantiation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.kind {
+            SubprogramKind::Function => write!(f, "function ")?,
+            SubprogramKind::Procedure => write!(f, "procedure ")?,
+        }
+        write!(f, "{} is new {}", self.designator, self.subprogram_name)?;
+        if let Some(signature) = &self.signature {
+            write!(f, " {signature}")?;
+        }
+        if let Some(assoc_list) = &self.generic_map {
+            let mut first = true;
+            for assoc in assoc_list {
+                if first {
+                    write!(f, "\n  generic map (\n    {assoc}")?;
+                } else {
+                    write!(f, ",\n    {assoc}")?;
+                }
+                first = false;
+            }
+            if !first {
+                write!(f, "\n  )")?;
+            }
+        }
+        write!(f, ";")
+    }
+}
+
 impl Display for Signature {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -896,6 +1036,50 @@ impl Display for InterfaceObjectDeclaration {
     }
 }
 
+impl Display for ModeViewIndication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.kind {
+            ModeViewKind::Record => write!(f, "view {}", self.name)?,
+            ModeViewKind::Array => write!(f, "view ({})", self.name)?,
+        }
+        match self.subtype_indication {
+            Some(ref subtype_indication) => write!(f, " of {subtype_indication}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for InterfaceViewDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} : {}", self.ident, self.view)
+    }
+}
+
+impl Display for ElementMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ElementMode::Simple(ref mode) => write!(f, "{mode}"),
+            ElementMode::View(ModeViewKind::Record, ref name) => write!(f, "view {name}"),
+            ElementMode::View(ModeViewKind::Array, ref name) => write!(f, "view ({name})"),
+        }
+    }
+}
+
+impl Display for ModeViewDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "view {} of {} is", self.ident, self.typ)?;
+        for element in &self.elements {
+            let names: Vec<_> = element
+                .names
+                .iter()
+                .map(|name| name.item.to_string())
+                .collect();
+            write!(f, "\n  {} : {};", names.join(", "), element.mode)?;
+        }
+        write!(f, "\nend view;")
+    }
+}
+
 impl Display for SubprogramDefault {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -935,6 +1119,7 @@ impl Display for InterfaceDeclaration {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
             InterfaceDeclaration::Object(ref decl) => write!(f, "{decl}"),
+            InterfaceDeclaration::View(ref decl) => write!(f, "{decl}"),
             InterfaceDeclaration::File(ref decl) => write!(f, "{decl}"),
             InterfaceDeclaration::Type(ref ident) => write!(f, "type {ident}"),
             InterfaceDeclaration::Subprogram(ref decl, ref default) => {
@@ -1137,6 +1322,21 @@ mod tests {
         assert_format("foo.bar.baz", Code::selected_name);
     }
 
+    #[test]
+    fn test_selected_name_extended_identifier() {
+        assert_format("work.\\Weird Name!\\", Code::selected_name);
+    }
+
+    #[test]
+    fn test_name_extended_identifier_with_backslash() {
+        assert_format("\\a\\\\b\\.\\C\\", Code::name);
+    }
+
+    #[test]
+    fn test_subtype_indication_with_nested_element_resolution() {
+        assert_format("((elem resolve)) rec_arr_t", Code::subtype_indication);
+    }
+
     #[test]
     fn test_name_operator_symbol() {
         assert_format("\"+\"", Code::name);
@@ -1782,6 +1982,19 @@ end units;",
         assert_format("alias 'c' is 'b';", Code::alias_decl);
     }
 
+    #[test]
+    fn test_group_template_declaration() {
+        assert_format(
+            "group pair is (signal, label <>);",
+            Code::group_template_decl,
+        );
+    }
+
+    #[test]
+    fn test_group_declaration() {
+        assert_format("group grp : pair (foo, 'a');", Code::group_decl);
+    }
+
     #[test]
     pub fn test_procedure_specification() {
         assert_format("procedure foo", Code::subprogram_decl);
@@ -1828,6 +2041,35 @@ end units;",
         );
     }
 
+    #[test]
+    pub fn test_function_specification_with_generics() {
+        assert_format(
+            "function foo
+  generic (
+    type T
+  ) parameter (
+  constant foo : in T
+) return T",
+            Code::subprogram_decl,
+        );
+    }
+
+    #[test]
+    pub fn test_subprogram_instantiation() {
+        assert_format(
+            "function foo is new lib.pkg.bar [natural return natural]
+  generic map (
+    T => natural
+  );",
+            |code| {
+                assert_matches!(
+                    code.declarative_part().remove(0),
+                    Declaration::SubprogramInstantiation(instance) => instance
+                )
+            },
+        );
+    }
+
     #[test]
     pub fn test_interface_declaration_object() {
         assert_format("signal foo : in std_logic", Code::parameter);
@@ -1850,6 +2092,25 @@ end units;",
         assert_format("constant foo : in natural := bar(0)", Code::parameter);
     }
 
+    #[test]
+    fn test_interface_declaration_view() {
+        assert_format("foo : view bus_master", Code::port);
+        assert_format("foo : view bus_master of bus_t", Code::port);
+        assert_format("foo : view (bus_master) of bus_array_t", Code::port);
+    }
+
+    #[test]
+    fn test_mode_view_declaration() {
+        assert_format(
+            "view bus_master of bus_t is
+  addr, data_out : out;
+  data_in : in;
+  subs : view (sub_view);
+end view;",
+            Code::mode_view_decl,
+        );
+    }
+
     #[test]
     fn test_interface_declaration_object_generic() {
         assert_format("foo : natural := bar(0)", Code::generic);
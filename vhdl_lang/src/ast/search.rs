@@ -13,6 +13,8 @@ use crate::analysis::DesignRoot;
 use crate::analysis::EntRef;
 pub use crate::analysis::HasEntityId;
 use crate::analysis::Related;
+use fnv::FnvHashSet;
+use std::time::Instant;
 
 #[must_use]
 pub enum SearchResult {
@@ -48,6 +50,7 @@ pub enum FoundDeclaration<'a> {
     ElementDeclaration(&'a mut ElementDeclaration),
     EnumerationLiteral(&'a mut Ident, &'a mut WithDecl<WithPos<EnumerationLiteral>>),
     InterfaceObject(&'a mut InterfaceObjectDeclaration),
+    InterfaceView(&'a mut InterfaceViewDeclaration),
     InterfaceFile(&'a mut InterfaceFileDeclaration),
     File(&'a mut FileDeclaration),
     Type(&'a mut TypeDeclaration),
@@ -57,9 +60,13 @@ pub enum FoundDeclaration<'a> {
     PhysicalTypeSecondary(&'a mut WithDecl<Ident>, &'a mut PhysicalLiteral),
     Component(&'a mut ComponentDeclaration),
     Attribute(&'a mut AttributeDeclaration),
+    GroupTemplate(&'a mut GroupTemplateDeclaration),
+    Group(&'a mut GroupDeclaration),
+    View(&'a mut ModeViewDeclaration),
     Alias(&'a mut AliasDeclaration),
     Function(&'a mut FunctionSpecification),
     Procedure(&'a mut ProcedureSpecification),
+    SubprogramInstance(&'a mut SubprogramInstantiation),
     Package(&'a mut PackageDeclaration),
     PackageInstance(&'a mut PackageInstantiation),
     Configuration(&'a mut ConfigurationDeclaration),
@@ -97,6 +104,26 @@ pub trait Searcher {
         NotFinished
     }
 
+    /// Search a use clause before the names of the clause are searched
+    fn search_use_clause(&mut self, _pos: &SrcPos, _use_clause: &UseClause) -> SearchState {
+        NotFinished
+    }
+
+    /// Search an expression before its operands and names are searched
+    fn search_expression(&mut self, _pos: &SrcPos, _expr: &Expression) -> SearchState {
+        NotFinished
+    }
+
+    /// Search an instantiation statement before its unit and association lists are searched
+    fn search_instantiation(&mut self, _instance: &InstantiationStatement) -> SearchState {
+        NotFinished
+    }
+
+    /// Search an external name before its path and subtype are searched
+    fn search_external_name(&mut self, _ename: &mut ExternalName) -> SearchState {
+        NotFinished
+    }
+
     fn search_with_pos(&mut self, _pos: &SrcPos) -> SearchState {
         NotFinished
     }
@@ -208,6 +235,18 @@ fn search_selection<T: Search>(
     NotFound
 }
 
+impl<T: Search> Search for AssignmentRightHand<T> {
+    fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
+        match self {
+            AssignmentRightHand::Simple(item) => item.search(searcher),
+            AssignmentRightHand::Conditional(conditionals) => {
+                search_conditionals(conditionals, true, searcher)
+            }
+            AssignmentRightHand::Selected(selection) => search_selection(selection, true, searcher),
+        }
+    }
+}
+
 fn search_assignment<T: Search>(
     target: &mut WithPos<Target>,
     rhs: &mut AssignmentRightHand<T>,
@@ -283,7 +322,7 @@ impl Search for LabeledSequentialStatement {
                     sensitivity_clause,
                     condition_clause,
                     timeout_clause,
-                } = wait_stmt;
+                } = &mut wait_stmt.item;
                 return_if_found!(sensitivity_clause.search(searcher));
                 return_if_found!(condition_clause.search(searcher));
                 return_if_found!(timeout_clause.search(searcher));
@@ -387,12 +426,19 @@ impl Search for GenerateBody {
 
 impl Search for InstantiationStatement {
     fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_finished!(searcher.search_instantiation(self));
         match self.unit {
             InstantiatedUnit::Entity(ref mut ent_name, _) => {
                 return_if_found!(ent_name.search(searcher));
             }
             InstantiatedUnit::Component(ref mut component_name) => {
                 return_if_found!(component_name.search(searcher));
+                // Unbound components are legal and not unresolved references
+                if self.default_binding.is_some() {
+                    return_if_found!(searcher
+                        .search_pos_with_ref(&component_name.pos, &mut self.default_binding)
+                        .or_not_found());
+                }
             }
             InstantiatedUnit::Configuration(ref mut config_name) => {
                 return_if_found!(config_name.search(searcher));
@@ -489,10 +535,37 @@ impl Search for LabeledConcurrentStatement {
                 return_if_found!(severity.search(searcher));
                 NotFound
             }
+            ConcurrentStatement::PslDirective(ref mut directive) => {
+                let PslDirective {
+                    kind: _,
+                    property,
+                    report,
+                    severity,
+                } = directive;
+                return_if_found!(property.search(searcher));
+                return_if_found!(report.search(searcher));
+                return_if_found!(severity.search(searcher));
+                NotFound
+            }
         }
     }
 }
 
+impl Search for PslProperty {
+    fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
+        let PslProperty {
+            booleans,
+            values,
+            counts,
+            clocks,
+        } = self;
+        return_if_found!(booleans.search(searcher));
+        return_if_found!(values.search(searcher));
+        return_if_found!(counts.search(searcher));
+        clocks.search(searcher)
+    }
+}
+
 impl Search for WithPos<WithRef<Designator>> {
     fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
         return_if_finished!(searcher.search_with_pos(&self.pos));
@@ -552,7 +625,9 @@ fn search_pos_name(
             NotFound
         }
         Name::External(ref mut ename) => {
-            let ExternalName { subtype, .. } = ename.as_mut();
+            return_if_finished!(searcher.search_external_name(ename));
+            let ExternalName { path, subtype, .. } = ename.as_mut();
+            return_if_found!(path.search(searcher));
             return_if_found!(subtype.search(searcher));
             NotFound
         }
@@ -566,6 +641,16 @@ impl Search for WithPos<Name> {
     }
 }
 
+impl Search for WithPos<ExternalPath> {
+    fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
+        match self.item {
+            ExternalPath::Package(ref mut name)
+            | ExternalPath::Absolute(ref mut name)
+            | ExternalPath::Relative(ref mut name, _) => name.search(searcher),
+        }
+    }
+}
+
 impl Search for ElementConstraint {
     fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
         // @TODO more
@@ -604,18 +689,34 @@ impl Search for WithPos<SubtypeConstraint> {
 
 impl Search for SubtypeIndication {
     fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
-        // @TODO more
         let SubtypeIndication {
+            resolution,
             type_mark,
             constraint,
-            ..
         } = self;
+        return_if_found!(resolution.search(searcher));
         return_if_found!(type_mark.search(searcher));
         return_if_found!(constraint.search(searcher));
         NotFound
     }
 }
 
+impl Search for ResolutionIndication {
+    fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
+        match self {
+            ResolutionIndication::FunctionName(name) => name.search(searcher),
+            ResolutionIndication::ArrayElement(resolution) => resolution.search(searcher),
+            ResolutionIndication::Record(elements) => {
+                for element in elements.iter_mut() {
+                    return_if_found!(element.resolution.search(searcher));
+                }
+                NotFound
+            }
+            ResolutionIndication::Unresolved => NotFound,
+        }
+    }
+}
+
 impl Search for WithPos<TypeMark> {
     fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
         return_if_finished!(searcher.search_with_pos(&self.pos));
@@ -772,6 +873,7 @@ fn search_pos_expr(
     searcher: &mut impl Searcher,
 ) -> SearchResult {
     return_if_finished!(searcher.search_with_pos(pos));
+    return_if_finished!(searcher.search_expression(pos, expr));
     match expr {
         Expression::Binary(ref mut op, ref mut left, ref mut right) => {
             return_if_found!(searcher
@@ -848,8 +950,13 @@ impl Search for AssociationElement {
 
 impl Search for CallOrIndexed {
     fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
-        let CallOrIndexed { name, parameters } = self;
+        let CallOrIndexed {
+            name,
+            generic_map,
+            parameters,
+        } = self;
         return_if_found!(name.search(searcher));
+        return_if_found!(generic_map.search(searcher));
         return_if_found!(parameters.search(searcher));
         NotFound
     }
@@ -922,6 +1029,9 @@ impl Search for Declaration {
             Declaration::SubprogramDeclaration(decl) => {
                 return_if_found!(decl.search(searcher));
             }
+            Declaration::SubprogramInstantiation(instance) => {
+                return_if_found!(instance.search(searcher));
+            }
             Declaration::Attribute(Attribute::Declaration(decl)) => {
                 return_if_found!(searcher
                     .search_decl(FoundDeclaration::Attribute(decl))
@@ -967,9 +1077,12 @@ impl Search for Declaration {
                 }
             }
             Declaration::Use(use_clause) => {
-                return_if_found!(searcher
-                    .search_with_pos(&use_clause.pos)
-                    .or_else(|| use_clause.item.name_list.search(searcher)));
+                return_if_found!(searcher.search_with_pos(&use_clause.pos).or_else(|| {
+                    return_if_finished!(
+                        searcher.search_use_clause(&use_clause.pos, &use_clause.item)
+                    );
+                    use_clause.item.name_list.search(searcher)
+                }));
             }
             Declaration::Component(component) => {
                 return_if_found!(searcher
@@ -1003,10 +1116,69 @@ impl Search for Declaration {
                 return_if_found!(package_instance.search(searcher));
             }
 
-            Declaration::Configuration(_) => {
-                // @TODO
+            Declaration::Configuration(ref mut config) => {
+                return_if_found!(config.search(searcher));
+            }
+
+            Declaration::GroupTemplate(template) => {
+                return_if_found!(searcher
+                    .search_decl(FoundDeclaration::GroupTemplate(template))
+                    .or_not_found());
+            }
+
+            Declaration::Group(group) => {
+                return_if_found!(searcher
+                    .search_decl(FoundDeclaration::Group(group))
+                    .or_not_found());
+                let GroupDeclaration {
+                    ident: _,
+                    group_template_name,
+                    group_constituent_list,
+                } = group;
+                return_if_found!(group_template_name.search(searcher));
+                return_if_found!(group_constituent_list.search(searcher));
+            }
+            Declaration::View(view) => {
+                return_if_found!(searcher
+                    .search_decl(FoundDeclaration::View(view))
+                    .or_not_found());
+                return_if_found!(view.typ.search(searcher));
+                for element in view.elements.iter_mut() {
+                    for name in element.names.iter_mut() {
+                        return_if_found!(searcher.search_ident_ref(name).or_not_found());
+                    }
+                    if let ElementMode::View(_, ref mut name) = element.mode {
+                        return_if_found!(name.search(searcher));
+                    }
+                }
+            }
+            Declaration::PslClock(clock) => {
+                return_if_found!(clock.search(searcher));
+            }
+        }
+        NotFound
+    }
+}
+
+impl Search for ConfigurationSpecification {
+    fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
+        let ConfigurationSpecification {
+            spec,
+            bind_ind,
+            vunit_bind_inds: _,
+        } = self;
+        return_if_found!(spec.component_name.search(searcher));
+        match bind_ind.entity_aspect {
+            Some(EntityAspect::Entity(ref mut entity_name, _)) => {
+                return_if_found!(entity_name.search(searcher));
+            }
+            Some(EntityAspect::Configuration(ref mut config_name)) => {
+                return_if_found!(config_name.search(searcher));
             }
+            Some(EntityAspect::Open) | None => {}
         }
+        return_if_found!(bind_ind.generic_map.search(searcher));
+        return_if_found!(bind_ind.port_map.search(searcher));
         NotFound
     }
 }
@@ -1021,6 +1193,13 @@ impl Search for InterfaceDeclaration {
                 return_if_found!(decl.subtype_indication.search(searcher));
                 return_if_found!(decl.expression.search(searcher));
             }
+            InterfaceDeclaration::View(ref mut decl) => {
+                return_if_found!(searcher
+                    .search_decl(FoundDeclaration::InterfaceView(decl))
+                    .or_not_found());
+                return_if_found!(decl.view.name.search(searcher));
+                return_if_found!(decl.view.subtype_indication.search(searcher));
+            }
             InterfaceDeclaration::Subprogram(ref mut decl, ref mut subpgm_default) => {
                 match decl {
                     SubprogramDeclaration::Function(f) => {
@@ -1091,6 +1270,9 @@ impl Search for ProcedureSpecification {
         return_if_found!(searcher
             .search_decl(FoundDeclaration::Procedure(self))
             .or_not_found());
+        if let Some(header) = &mut self.header {
+            return_if_found!(header.generic_list.search(searcher));
+        }
         self.parameter_list.search(searcher)
     }
 }
@@ -1100,11 +1282,27 @@ impl Search for FunctionSpecification {
         return_if_found!(searcher
             .search_decl(FoundDeclaration::Function(self))
             .or_not_found());
+        if let Some(header) = &mut self.header {
+            return_if_found!(header.generic_list.search(searcher));
+        }
         return_if_found!(self.parameter_list.search(searcher));
         self.return_type.search(searcher)
     }
 }
 
+impl Search for SubprogramInstantiation {
+    fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_found!(searcher
+            .search_decl(FoundDeclaration::SubprogramInstance(self))
+            .or_not_found());
+        return_if_found!(self.subprogram_name.search(searcher));
+        if let Some(signature) = &mut self.signature {
+            return_if_found!(signature.item.search(searcher));
+        }
+        self.generic_map.search(searcher)
+    }
+}
+
 impl Search for LibraryClause {
     fn search(&mut self, searcher: &mut impl Searcher) -> SearchResult {
         for name in self.name_list.iter_mut() {
@@ -1121,6 +1319,7 @@ impl Search for WithPos<ContextItem> {
         return_if_finished!(searcher.search_with_pos(&self.pos));
         match self.item {
             ContextItem::Use(ref mut use_clause) => {
+                return_if_finished!(searcher.search_use_clause(&self.pos, use_clause));
                 return_if_found!(use_clause.name_list.search(searcher));
             }
             ContextItem::Library(ref mut library_clause) => {
@@ -1316,6 +1515,88 @@ impl Searcher for ItemAtCursor {
     }
 }
 
+// Search for the references to declarations/definitions at many cursors in one traversal
+pub struct ItemsAtCursors {
+    source: Source,
+    // The cursors in ascending order with their index in the input
+    cursors: Vec<(Position, usize)>,
+    resolved: Vec<bool>,
+    remaining: usize,
+    // The result of each cursor in the order of the input
+    pub result: Vec<Option<EntityId>>,
+}
+
+impl ItemsAtCursors {
+    pub fn new(source: &Source, cursors: &[Position]) -> ItemsAtCursors {
+        let mut sorted: Vec<_> = cursors.iter().copied().zip(0..).collect();
+        sorted.sort();
+        ItemsAtCursors {
+            source: source.clone(),
+            cursors: sorted,
+            resolved: vec![false; cursors.len()],
+            remaining: cursors.len(),
+            result: vec![None; cursors.len()],
+        }
+    }
+
+    /// The indices of the unresolved cursors within pos,
+    /// a cursor matches the character before and after it as for ItemAtCursor
+    fn unresolved_inside(&self, pos: &SrcPos) -> impl Iterator<Item = usize> + '_ {
+        let start = self
+            .cursors
+            .partition_point(|(cursor, _)| *cursor < pos.start());
+        let end = self
+            .cursors
+            .partition_point(|(cursor, _)| *cursor <= pos.end());
+        self.cursors[start..end.max(start)]
+            .iter()
+            .map(|(_, idx)| *idx)
+            .filter(|idx| !self.resolved[*idx])
+    }
+
+    /// The first declaration or reference found at a cursor is its result
+    fn resolve(&mut self, pos: &SrcPos, id: Option<EntityId>) -> SearchState {
+        let inside: Vec<_> = self.unresolved_inside(pos).collect();
+        for idx in inside {
+            self.resolved[idx] = true;
+            self.result[idx] = id;
+            self.remaining -= 1;
+        }
+
+        if self.remaining == 0 {
+            Finished(Found)
+        } else {
+            NotFinished
+        }
+    }
+}
+
+impl Searcher for ItemsAtCursors {
+    fn search_with_pos(&mut self, pos: &SrcPos) -> SearchState {
+        if self.unresolved_inside(pos).next().is_some() {
+            NotFinished
+        } else {
+            Finished(NotFound)
+        }
+    }
+
+    fn search_decl(&mut self, decl: FoundDeclaration) -> SearchState {
+        self.resolve(decl.pos(), decl.ent_id())
+    }
+
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        self.resolve(pos, *reference)
+    }
+
+    fn search_source(&mut self, source: &Source) -> SearchState {
+        if source == &self.source && self.remaining > 0 {
+            NotFinished
+        } else {
+            Finished(NotFound)
+        }
+    }
+}
+
 // Search for a declaration/definition and format it
 pub struct FormatDeclaration<'a> {
     ent: EntRef<'a>,
@@ -1339,8 +1620,8 @@ impl<'a> Searcher for FormatDeclaration<'a> {
         if is_implicit_of(self.ent, id) {
             // Implicit
             self.result = Some(format!(
-                "-- {}\n\n-- Implicitly defined by:\n{}\n",
-                self.ent.describe(),
+                "{}\n\n-- Implicitly defined by:\n{}\n",
+                self.ent.pretty(),
                 decl,
             ));
             return Finished(Found);
@@ -1357,7 +1638,10 @@ impl<'a> Searcher for FormatDeclaration<'a> {
 pub struct FindAllReferences<'a> {
     root: &'a DesignRoot,
     ent: EntRef<'a>,
+    deadline: Option<Instant>,
     pub references: Vec<SrcPos>,
+    /// True if the search was aborted at the deadline before all references were found
+    pub truncated: bool,
 }
 
 fn is_instance_of(ent: EntRef, other: EntRef) -> bool {
@@ -1398,13 +1682,35 @@ impl<'a> FindAllReferences<'a> {
         FindAllReferences {
             root,
             ent,
+            deadline: None,
             references: Vec::new(),
+            truncated: false,
+        }
+    }
+
+    /// Stop searching for more references once the deadline has passed
+    pub fn with_deadline(mut self, deadline: Instant) -> FindAllReferences<'a> {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    fn is_past_deadline(&mut self) -> bool {
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                self.truncated = true;
+            }
         }
+        self.truncated
     }
 }
 
 impl<'a> Searcher for FindAllReferences<'a> {
     fn search_decl(&mut self, decl: FoundDeclaration) -> SearchState {
+        if self.is_past_deadline() {
+            // Found aborts the search of the remaining design units
+            return Finished(Found);
+        }
+
         if let Some(id) = decl.ent_id() {
             let other = self.root.get_ent(id);
 
@@ -1416,6 +1722,10 @@ impl<'a> Searcher for FindAllReferences<'a> {
     }
 
     fn search_pos_with_ref(&mut self, pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        if self.is_past_deadline() {
+            return Finished(Found);
+        }
+
         if let Some(id) = reference.as_ref() {
             let other = self.root.get_ent(*id);
             if is_reference(self.ent, other) {
@@ -1430,10 +1740,12 @@ impl<'a> HasEntityId for FoundDeclaration<'a> {
     fn ent_id(&self) -> Option<EntityId> {
         match self {
             FoundDeclaration::InterfaceObject(value) => value.ident.decl,
+            FoundDeclaration::InterfaceView(value) => value.ident.decl,
             FoundDeclaration::ForIndex(ident, _) => ident.decl,
             FoundDeclaration::ForGenerateIndex(_, value) => value.index_name.decl,
             FoundDeclaration::Function(value) => value.designator.decl,
             FoundDeclaration::Procedure(value) => value.designator.decl,
+            FoundDeclaration::SubprogramInstance(value) => value.designator.decl,
             FoundDeclaration::Object(value) => value.ident.decl,
             FoundDeclaration::ElementDeclaration(elem) => elem.ident.decl,
             FoundDeclaration::EnumerationLiteral(_, elem) => elem.decl,
@@ -1446,6 +1758,9 @@ impl<'a> HasEntityId for FoundDeclaration<'a> {
             FoundDeclaration::PhysicalTypeSecondary(value, _) => value.decl,
             FoundDeclaration::Component(value) => value.ident.decl,
             FoundDeclaration::Attribute(value) => value.ident.decl,
+            FoundDeclaration::GroupTemplate(value) => value.ident.decl,
+            FoundDeclaration::Group(value) => value.ident.decl,
+            FoundDeclaration::View(value) => value.ident.decl,
             FoundDeclaration::Alias(value) => value.designator.decl,
             FoundDeclaration::Package(value) => value.ident.decl,
             FoundDeclaration::PackageInstance(value) => value.ident.decl,
@@ -1463,10 +1778,12 @@ impl<'a> HasSrcPos for FoundDeclaration<'a> {
     fn pos(&self) -> &SrcPos {
         match self {
             FoundDeclaration::InterfaceObject(value) => value.ident.pos(),
+            FoundDeclaration::InterfaceView(value) => value.ident.pos(),
             FoundDeclaration::ForIndex(ident, _) => ident.pos(),
             FoundDeclaration::ForGenerateIndex(_, value) => value.index_name.pos(),
             FoundDeclaration::Function(value) => &value.designator.tree.pos,
             FoundDeclaration::Procedure(value) => &value.designator.tree.pos,
+            FoundDeclaration::SubprogramInstance(value) => &value.designator.tree.pos,
             FoundDeclaration::Object(value) => value.ident.pos(),
             FoundDeclaration::ElementDeclaration(elem) => elem.ident.pos(),
             FoundDeclaration::EnumerationLiteral(_, elem) => &elem.tree.pos,
@@ -1480,6 +1797,9 @@ impl<'a> HasSrcPos for FoundDeclaration<'a> {
             FoundDeclaration::Component(value) => value.ident.pos(),
             FoundDeclaration::Alias(value) => &value.designator.tree.pos,
             FoundDeclaration::Attribute(value) => value.ident.pos(),
+            FoundDeclaration::GroupTemplate(value) => value.ident.pos(),
+            FoundDeclaration::Group(value) => value.ident.pos(),
+            FoundDeclaration::View(value) => value.ident.pos(),
             FoundDeclaration::Package(value) => value.ident.pos(),
             FoundDeclaration::PackageInstance(value) => value.ident.pos(),
             FoundDeclaration::Configuration(value) => value.ident.pos(),
@@ -1500,6 +1820,7 @@ impl std::fmt::Display for FoundDeclaration<'_> {
                 InterfaceListType::Generic => write!(f, "generic {value};"),
                 InterfaceListType::Parameter => write!(f, "{value};"),
             },
+            FoundDeclaration::InterfaceView(ref value) => write!(f, "port {value};"),
             FoundDeclaration::ForIndex(ref ident, ref drange) => {
                 write!(f, "for {ident} in {drange} loop")
             }
@@ -1513,6 +1834,9 @@ impl std::fmt::Display for FoundDeclaration<'_> {
             FoundDeclaration::Procedure(ref value) => {
                 write!(f, "{value};")
             }
+            FoundDeclaration::SubprogramInstance(ref value) => {
+                write!(f, "{value}")
+            }
             FoundDeclaration::Object(ref value) => {
                 write!(f, "{value}")
             }
@@ -1552,6 +1876,15 @@ impl std::fmt::Display for FoundDeclaration<'_> {
             FoundDeclaration::Attribute(ref value) => {
                 write!(f, "{value}")
             }
+            FoundDeclaration::GroupTemplate(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::Group(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::View(ref value) => {
+                write!(f, "{value}")
+            }
             FoundDeclaration::Package(ref value) => {
                 write!(f, "{value}")
             }
@@ -1580,6 +1913,111 @@ impl std::fmt::Display for FoundDeclaration<'_> {
     }
 }
 
+/// Find the prefix of the `use prefix.all` clause at the cursor
+pub struct FindUseAllPrefix {
+    source: Source,
+    cursor: Position,
+    pub result: Option<EntityId>,
+    /// The position of the whole use clause
+    pub clause_pos: Option<SrcPos>,
+    /// The `prefix.all` name
+    pub name: Option<WithPos<Name>>,
+}
+
+impl FindUseAllPrefix {
+    pub fn new(source: &Source, cursor: Position) -> FindUseAllPrefix {
+        FindUseAllPrefix {
+            source: source.clone(),
+            cursor,
+            result: None,
+            clause_pos: None,
+            name: None,
+        }
+    }
+
+    fn is_inside(&self, pos: &SrcPos) -> bool {
+        pos.start() <= self.cursor && self.cursor <= pos.end()
+    }
+}
+
+impl Searcher for FindUseAllPrefix {
+    fn search_with_pos(&mut self, pos: &SrcPos) -> SearchState {
+        if self.is_inside(pos) {
+            NotFinished
+        } else {
+            Finished(NotFound)
+        }
+    }
+
+    fn search_use_clause(&mut self, pos: &SrcPos, use_clause: &UseClause) -> SearchState {
+        if !self.is_inside(pos) {
+            return Finished(NotFound);
+        }
+
+        // Prefer the name at the cursor when a clause has several names
+        let name = use_clause
+            .name_list
+            .iter()
+            .find(|name| self.is_inside(&name.pos))
+            .or_else(|| {
+                use_clause
+                    .name_list
+                    .iter()
+                    .find(|name| matches!(name.item, Name::SelectedAll(..)))
+            });
+
+        if let Some(
+            name @ WithPos {
+                item: Name::SelectedAll(prefix),
+                ..
+            },
+        ) = name
+        {
+            self.result = prefix.item.get_suffix_reference();
+            self.clause_pos = Some(pos.clone());
+            self.name = Some(name.clone());
+        }
+        Finished(Found)
+    }
+
+    // Assume source is searched first to filter out design units in other files
+    fn search_source(&mut self, source: &Source) -> SearchState {
+        if source == &mut self.source {
+            NotFinished
+        } else {
+            Finished(NotFound)
+        }
+    }
+}
+
+/// Collect the declarations referenced within the searched design units
+/// The type of an implicitly declared subprogram counts as referenced when the subprogram is referenced
+pub struct FindAllReferenced<'a> {
+    root: &'a DesignRoot,
+    pub referenced: FnvHashSet<EntityId>,
+}
+
+impl<'a> FindAllReferenced<'a> {
+    pub fn new(root: &'a DesignRoot) -> FindAllReferenced<'a> {
+        FindAllReferenced {
+            root,
+            referenced: FnvHashSet::default(),
+        }
+    }
+}
+
+impl<'a> Searcher for FindAllReferenced<'a> {
+    fn search_pos_with_ref(&mut self, _pos: &SrcPos, reference: &mut Reference) -> SearchState {
+        if let Some(id) = reference.as_ref() {
+            self.referenced.insert(*id);
+            if let Related::ImplicitOf(ent) = self.root.get_ent(*id).related {
+                self.referenced.insert(ent.id());
+            }
+        }
+        NotFinished
+    }
+}
+
 #[derive(Default)]
 pub struct FindAllUnresolved {
     pub count: usize,
@@ -0,0 +1,1572 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Moving the positions of a design unit when lines are inserted or removed above it,
+//! such that the unit can be reused when a file is parsed again
+
+use super::*;
+
+pub(crate) trait ShiftLines {
+    /// Move all positions by a number of lines, upwards when negative
+    fn shift_lines(&mut self, delta: i64);
+}
+
+impl ShiftLines for Position {
+    fn shift_lines(&mut self, delta: i64) {
+        let Position { line, character: _ } = self;
+        *line = (i64::from(*line) + delta) as u32;
+    }
+}
+
+impl ShiftLines for SrcPos {
+    fn shift_lines(&mut self, delta: i64) {
+        let SrcPos {
+            source: _,
+            range: crate::data::Range { start, end },
+        } = self;
+        start.shift_lines(delta);
+        end.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for WithPos<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        let WithPos { item, pos } = self;
+        item.shift_lines(delta);
+        pos.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for WithRef<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        let WithRef { item, reference: _ } = self;
+        item.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for WithDecl<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        let WithDecl { tree, decl: _ } = self;
+        tree.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for Vec<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        for item in self.iter_mut() {
+            item.shift_lines(delta);
+        }
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for Option<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        if let Some(item) = self {
+            item.shift_lines(delta);
+        }
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for Box<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        self.as_mut().shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines, U: ShiftLines> ShiftLines for (T, U) {
+    fn shift_lines(&mut self, delta: i64) {
+        let (first, second) = self;
+        first.shift_lines(delta);
+        second.shift_lines(delta);
+    }
+}
+
+/// Items without positions
+macro_rules! without_positions {
+    ($($typ:ty),*) => {
+        $(
+            impl ShiftLines for $typ {
+                fn shift_lines(&mut self, _delta: i64) {}
+            }
+        )*
+    };
+}
+
+without_positions!(
+    Symbol,
+    String,
+    Designator,
+    Operator,
+    AttributeDesignator,
+    EnumerationLiteral,
+    SubprogramDesignator,
+    Mode,
+    EntityClassEntry
+);
+
+impl ShiftLines for Fix {
+    fn shift_lines(&mut self, delta: i64) {
+        let Fix {
+            pos,
+            replacement: _,
+        } = self;
+        pos.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Diagnostic {
+    fn shift_lines(&mut self, delta: i64) {
+        let Diagnostic {
+            pos,
+            related,
+            fixes,
+            message: _,
+            severity: _,
+            code: _,
+        } = self;
+        pos.shift_lines(delta);
+        related.shift_lines(delta);
+        fixes.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for AttributeName {
+    fn shift_lines(&mut self, delta: i64) {
+        let AttributeName {
+            name,
+            signature,
+            attr,
+            expr,
+        } = self;
+        name.shift_lines(delta);
+        signature.shift_lines(delta);
+        attr.shift_lines(delta);
+        expr.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ExternalPath {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ExternalPath::Package(value) => value.shift_lines(delta),
+            ExternalPath::Absolute(value) => value.shift_lines(delta),
+            ExternalPath::Relative(name, _) => name.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for ExternalName {
+    fn shift_lines(&mut self, delta: i64) {
+        let ExternalName {
+            path,
+            subtype,
+            class: _,
+        } = self;
+        path.shift_lines(delta);
+        subtype.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Name {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Name::Selected(prefix, suffix) => {
+                prefix.shift_lines(delta);
+                suffix.shift_lines(delta);
+            }
+            Name::SelectedAll(value) => value.shift_lines(delta),
+            Name::Slice(prefix, drange) => {
+                prefix.shift_lines(delta);
+                drange.shift_lines(delta);
+            }
+            Name::Attribute(value) => value.shift_lines(delta),
+            Name::CallOrIndexed(value) => value.shift_lines(delta),
+            Name::External(value) => value.shift_lines(delta),
+            Name::Designator(_) => {}
+        }
+    }
+}
+
+impl ShiftLines for SelectedName {
+    fn shift_lines(&mut self, delta: i64) {
+        if let SelectedName::Selected(prefix, suffix) = self {
+            prefix.shift_lines(delta);
+            suffix.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for CallOrIndexed {
+    fn shift_lines(&mut self, delta: i64) {
+        let CallOrIndexed {
+            name,
+            generic_map,
+            parameters,
+        } = self;
+        name.shift_lines(delta);
+        generic_map.shift_lines(delta);
+        parameters.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Choice {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Choice::Expression(value) => value.shift_lines(delta),
+            Choice::DiscreteRange(value) => value.shift_lines(delta),
+            Choice::Others => {}
+        }
+    }
+}
+
+impl ShiftLines for ElementAssociation {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ElementAssociation::Positional(value) => value.shift_lines(delta),
+            ElementAssociation::Named(choices, expr) => {
+                choices.shift_lines(delta);
+                expr.shift_lines(delta);
+            }
+        }
+    }
+}
+
+impl ShiftLines for ActualPart {
+    fn shift_lines(&mut self, delta: i64) {
+        if let ActualPart::Expression(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for AssociationElement {
+    fn shift_lines(&mut self, delta: i64) {
+        let AssociationElement { formal, actual } = self;
+        formal.shift_lines(delta);
+        actual.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for PhysicalLiteral {
+    fn shift_lines(&mut self, delta: i64) {
+        let PhysicalLiteral { unit, value: _ } = self;
+        unit.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Literal {
+    fn shift_lines(&mut self, delta: i64) {
+        if let Literal::Physical(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for Allocator {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Allocator::Qualified(value) => value.shift_lines(delta),
+            Allocator::Subtype(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for QualifiedExpression {
+    fn shift_lines(&mut self, delta: i64) {
+        let QualifiedExpression { type_mark, expr } = self;
+        type_mark.shift_lines(delta);
+        expr.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Expression {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Expression::Binary(op, left, right) => {
+                op.shift_lines(delta);
+                left.shift_lines(delta);
+                right.shift_lines(delta);
+            }
+            Expression::Unary(op, expr) => {
+                op.shift_lines(delta);
+                expr.shift_lines(delta);
+            }
+            Expression::Aggregate(value) => value.shift_lines(delta),
+            Expression::Qualified(value) => value.shift_lines(delta),
+            Expression::Name(value) => value.shift_lines(delta),
+            Expression::Literal(value) => value.shift_lines(delta),
+            Expression::New(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for DiscreteRange {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            DiscreteRange::Discrete(type_mark, range) => {
+                type_mark.shift_lines(delta);
+                range.shift_lines(delta);
+            }
+            DiscreteRange::Range(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for RangeConstraint {
+    fn shift_lines(&mut self, delta: i64) {
+        let RangeConstraint {
+            left_expr,
+            right_expr,
+            direction: _,
+        } = self;
+        left_expr.shift_lines(delta);
+        right_expr.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Range {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Range::Range(value) => value.shift_lines(delta),
+            Range::Attribute(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for ElementConstraint {
+    fn shift_lines(&mut self, delta: i64) {
+        let ElementConstraint { ident, constraint } = self;
+        ident.shift_lines(delta);
+        constraint.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SubtypeConstraint {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            SubtypeConstraint::Range(value) => value.shift_lines(delta),
+            SubtypeConstraint::Array(ranges, element) => {
+                ranges.shift_lines(delta);
+                element.shift_lines(delta);
+            }
+            SubtypeConstraint::Record(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for RecordElementResolution {
+    fn shift_lines(&mut self, delta: i64) {
+        let RecordElementResolution { ident, resolution } = self;
+        ident.shift_lines(delta);
+        resolution.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ResolutionIndication {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ResolutionIndication::FunctionName(value) => value.shift_lines(delta),
+            ResolutionIndication::ArrayElement(value) => value.shift_lines(delta),
+            ResolutionIndication::Record(value) => value.shift_lines(delta),
+            ResolutionIndication::Unresolved => {}
+        }
+    }
+}
+
+impl ShiftLines for TypeMark {
+    fn shift_lines(&mut self, delta: i64) {
+        let TypeMark { name, attr: _ } = self;
+        name.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SubtypeIndication {
+    fn shift_lines(&mut self, delta: i64) {
+        let SubtypeIndication {
+            resolution,
+            type_mark,
+            constraint,
+        } = self;
+        resolution.shift_lines(delta);
+        type_mark.shift_lines(delta);
+        constraint.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ArrayIndex {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ArrayIndex::IndexSubtypeDefintion(value) => value.shift_lines(delta),
+            ArrayIndex::Discrete(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for ElementDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ElementDeclaration { ident, subtype } = self;
+        ident.shift_lines(delta);
+        subtype.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ProtectedTypeDeclarativeItem {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ProtectedTypeDeclarativeItem::Subprogram(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for AliasDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let AliasDeclaration {
+            designator,
+            subtype_indication,
+            name,
+            signature,
+        } = self;
+        designator.shift_lines(delta);
+        subtype_indication.shift_lines(delta);
+        name.shift_lines(delta);
+        signature.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for AttributeDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let AttributeDeclaration { ident, type_mark } = self;
+        ident.shift_lines(delta);
+        type_mark.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for EntityTag {
+    fn shift_lines(&mut self, delta: i64) {
+        let EntityTag {
+            designator,
+            signature,
+        } = self;
+        designator.shift_lines(delta);
+        signature.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for EntityName {
+    fn shift_lines(&mut self, delta: i64) {
+        if let EntityName::Name(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for AttributeSpecification {
+    fn shift_lines(&mut self, delta: i64) {
+        let AttributeSpecification {
+            ident,
+            entity_name,
+            expr,
+            entity_class: _,
+        } = self;
+        ident.shift_lines(delta);
+        entity_name.shift_lines(delta);
+        expr.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Attribute {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Attribute::Specification(value) => value.shift_lines(delta),
+            Attribute::Declaration(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for GroupTemplateDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let GroupTemplateDeclaration {
+            ident,
+            entity_class_entries,
+        } = self;
+        ident.shift_lines(delta);
+        entity_class_entries.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for GroupDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let GroupDeclaration {
+            ident,
+            group_template_name,
+            group_constituent_list,
+        } = self;
+        ident.shift_lines(delta);
+        group_template_name.shift_lines(delta);
+        group_constituent_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ProtectedTypeDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ProtectedTypeDeclaration { items } = self;
+        items.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ProtectedTypeBody {
+    fn shift_lines(&mut self, delta: i64) {
+        let ProtectedTypeBody {
+            decl,
+            type_reference: _,
+        } = self;
+        decl.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for PhysicalTypeDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let PhysicalTypeDeclaration {
+            range,
+            primary_unit,
+            secondary_units,
+        } = self;
+        range.shift_lines(delta);
+        primary_unit.shift_lines(delta);
+        secondary_units.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for TypeDefinition {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            TypeDefinition::Enumeration(value) => value.shift_lines(delta),
+            TypeDefinition::Numeric(value) => value.shift_lines(delta),
+            TypeDefinition::Physical(value) => value.shift_lines(delta),
+            TypeDefinition::Array(indexes, subtype) => {
+                indexes.shift_lines(delta);
+                subtype.shift_lines(delta);
+            }
+            TypeDefinition::Record(value) => value.shift_lines(delta),
+            TypeDefinition::Access(value) => value.shift_lines(delta),
+            TypeDefinition::File(value) => value.shift_lines(delta),
+            TypeDefinition::Protected(value) => value.shift_lines(delta),
+            TypeDefinition::ProtectedBody(value) => value.shift_lines(delta),
+            TypeDefinition::Subtype(value) => value.shift_lines(delta),
+            TypeDefinition::Incomplete(_) => {}
+        }
+    }
+}
+
+impl ShiftLines for TypeDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let TypeDeclaration { ident, def } = self;
+        ident.shift_lines(delta);
+        def.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ObjectDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ObjectDeclaration {
+            ident,
+            subtype_indication,
+            expression,
+            class: _,
+        } = self;
+        ident.shift_lines(delta);
+        subtype_indication.shift_lines(delta);
+        expression.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for FileDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let FileDeclaration {
+            ident,
+            subtype_indication,
+            open_info,
+            file_name,
+        } = self;
+        ident.shift_lines(delta);
+        subtype_indication.shift_lines(delta);
+        open_info.shift_lines(delta);
+        file_name.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SubprogramHeader {
+    fn shift_lines(&mut self, delta: i64) {
+        let SubprogramHeader { generic_list } = self;
+        generic_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ProcedureSpecification {
+    fn shift_lines(&mut self, delta: i64) {
+        let ProcedureSpecification {
+            designator,
+            header,
+            parameter_list,
+        } = self;
+        designator.shift_lines(delta);
+        header.shift_lines(delta);
+        parameter_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for FunctionSpecification {
+    fn shift_lines(&mut self, delta: i64) {
+        let FunctionSpecification {
+            designator,
+            header,
+            parameter_list,
+            return_type,
+            pure: _,
+        } = self;
+        designator.shift_lines(delta);
+        header.shift_lines(delta);
+        parameter_list.shift_lines(delta);
+        return_type.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SubprogramBody {
+    fn shift_lines(&mut self, delta: i64) {
+        let SubprogramBody {
+            specification,
+            declarations,
+            statements,
+            end_pos,
+        } = self;
+        specification.shift_lines(delta);
+        declarations.shift_lines(delta);
+        statements.shift_lines(delta);
+        end_pos.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Signature {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Signature::Function(args, ret) => {
+                args.shift_lines(delta);
+                ret.shift_lines(delta);
+            }
+            Signature::Procedure(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for SubprogramDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            SubprogramDeclaration::Procedure(value) => value.shift_lines(delta),
+            SubprogramDeclaration::Function(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for SubprogramInstantiation {
+    fn shift_lines(&mut self, delta: i64) {
+        let SubprogramInstantiation {
+            designator,
+            subprogram_name,
+            signature,
+            generic_map,
+            kind: _,
+        } = self;
+        designator.shift_lines(delta);
+        subprogram_name.shift_lines(delta);
+        signature.shift_lines(delta);
+        generic_map.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for InterfaceFileDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let InterfaceFileDeclaration {
+            ident,
+            subtype_indication,
+        } = self;
+        ident.shift_lines(delta);
+        subtype_indication.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for InterfaceObjectDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let InterfaceObjectDeclaration {
+            ident,
+            subtype_indication,
+            expression,
+            list_type: _,
+            class: _,
+            mode: _,
+        } = self;
+        ident.shift_lines(delta);
+        subtype_indication.shift_lines(delta);
+        expression.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for InterfaceViewDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let InterfaceViewDeclaration { ident, view } = self;
+        ident.shift_lines(delta);
+        view.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ModeViewIndication {
+    fn shift_lines(&mut self, delta: i64) {
+        let ModeViewIndication {
+            name,
+            subtype_indication,
+            kind: _,
+        } = self;
+        name.shift_lines(delta);
+        subtype_indication.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ModeViewDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ModeViewDeclaration {
+            ident,
+            typ,
+            elements,
+        } = self;
+        ident.shift_lines(delta);
+        typ.shift_lines(delta);
+        elements.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ModeViewElement {
+    fn shift_lines(&mut self, delta: i64) {
+        let ModeViewElement { names, mode } = self;
+        names.shift_lines(delta);
+        mode.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ElementMode {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ElementMode::Simple(value) => value.shift_lines(delta),
+            ElementMode::View(_, name) => name.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for SubprogramDefault {
+    fn shift_lines(&mut self, delta: i64) {
+        if let SubprogramDefault::Name(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for InterfacePackageGenericMapAspect {
+    fn shift_lines(&mut self, delta: i64) {
+        if let InterfacePackageGenericMapAspect::Map(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for InterfacePackageDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let InterfacePackageDeclaration {
+            ident,
+            package_name,
+            generic_map,
+        } = self;
+        ident.shift_lines(delta);
+        package_name.shift_lines(delta);
+        generic_map.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for InterfaceDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            InterfaceDeclaration::Object(value) => value.shift_lines(delta),
+            InterfaceDeclaration::View(value) => value.shift_lines(delta),
+            InterfaceDeclaration::File(value) => value.shift_lines(delta),
+            InterfaceDeclaration::Type(value) => value.shift_lines(delta),
+            InterfaceDeclaration::Subprogram(decl, default) => {
+                decl.shift_lines(delta);
+                default.shift_lines(delta);
+            }
+            InterfaceDeclaration::Package(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for PortClause {
+    fn shift_lines(&mut self, delta: i64) {
+        let PortClause { port_list } = self;
+        port_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ComponentDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ComponentDeclaration {
+            ident,
+            generic_list,
+            port_list,
+        } = self;
+        ident.shift_lines(delta);
+        generic_list.shift_lines(delta);
+        port_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Declaration {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Declaration::Object(value) => value.shift_lines(delta),
+            Declaration::File(value) => value.shift_lines(delta),
+            Declaration::Type(value) => value.shift_lines(delta),
+            Declaration::Component(value) => value.shift_lines(delta),
+            Declaration::Attribute(value) => value.shift_lines(delta),
+            Declaration::Alias(value) => value.shift_lines(delta),
+            Declaration::SubprogramDeclaration(value) => value.shift_lines(delta),
+            Declaration::SubprogramBody(value) => value.shift_lines(delta),
+            Declaration::SubprogramInstantiation(value) => value.shift_lines(delta),
+            Declaration::Use(value) => value.shift_lines(delta),
+            Declaration::Package(value) => value.shift_lines(delta),
+            Declaration::Configuration(value) => value.shift_lines(delta),
+            Declaration::GroupTemplate(value) => value.shift_lines(delta),
+            Declaration::Group(value) => value.shift_lines(delta),
+            Declaration::View(value) => value.shift_lines(delta),
+            Declaration::PslClock(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for WaitStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let WaitStatement {
+            sensitivity_clause,
+            condition_clause,
+            timeout_clause,
+        } = self;
+        sensitivity_clause.shift_lines(delta);
+        condition_clause.shift_lines(delta);
+        timeout_clause.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for AssertStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let AssertStatement {
+            condition,
+            report,
+            severity,
+        } = self;
+        condition.shift_lines(delta);
+        report.shift_lines(delta);
+        severity.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ReportStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let ReportStatement { report, severity } = self;
+        report.shift_lines(delta);
+        severity.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Target {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            Target::Name(value) => value.shift_lines(delta),
+            Target::Aggregate(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for WaveformElement {
+    fn shift_lines(&mut self, delta: i64) {
+        let WaveformElement { value, after } = self;
+        value.shift_lines(delta);
+        after.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for Waveform {
+    fn shift_lines(&mut self, delta: i64) {
+        if let Waveform::Elements(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for DelayMechanism {
+    fn shift_lines(&mut self, delta: i64) {
+        if let DelayMechanism::Inertial { reject } = self {
+            reject.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for SignalAssignment {
+    fn shift_lines(&mut self, delta: i64) {
+        let SignalAssignment {
+            target,
+            delay_mechanism,
+            rhs,
+        } = self;
+        target.shift_lines(delta);
+        delay_mechanism.shift_lines(delta);
+        rhs.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SignalForceAssignment {
+    fn shift_lines(&mut self, delta: i64) {
+        let SignalForceAssignment {
+            target,
+            rhs,
+            force_mode: _,
+        } = self;
+        target.shift_lines(delta);
+        rhs.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SignalReleaseAssignment {
+    fn shift_lines(&mut self, delta: i64) {
+        let SignalReleaseAssignment {
+            target,
+            force_mode: _,
+        } = self;
+        target.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for VariableAssignment {
+    fn shift_lines(&mut self, delta: i64) {
+        let VariableAssignment { target, rhs } = self;
+        target.shift_lines(delta);
+        rhs.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for AssignmentRightHand<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            AssignmentRightHand::Simple(value) => value.shift_lines(delta),
+            AssignmentRightHand::Conditional(value) => value.shift_lines(delta),
+            AssignmentRightHand::Selected(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for Conditional<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        let Conditional { condition, item } = self;
+        condition.shift_lines(delta);
+        item.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for Conditionals<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        let Conditionals {
+            conditionals,
+            else_item,
+        } = self;
+        conditionals.shift_lines(delta);
+        else_item.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for Alternative<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        let Alternative { choices, item } = self;
+        choices.shift_lines(delta);
+        item.shift_lines(delta);
+    }
+}
+
+impl<T: ShiftLines> ShiftLines for Selection<T> {
+    fn shift_lines(&mut self, delta: i64) {
+        let Selection {
+            expression,
+            alternatives,
+        } = self;
+        expression.shift_lines(delta);
+        alternatives.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for CaseStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let CaseStatement {
+            expression,
+            alternatives,
+            is_matching: _,
+        } = self;
+        expression.shift_lines(delta);
+        alternatives.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for IterationScheme {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            IterationScheme::While(value) => value.shift_lines(delta),
+            IterationScheme::For(index, drange) => {
+                index.shift_lines(delta);
+                drange.shift_lines(delta);
+            }
+        }
+    }
+}
+
+impl ShiftLines for LoopStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let LoopStatement {
+            iteration_scheme,
+            statements,
+        } = self;
+        iteration_scheme.shift_lines(delta);
+        statements.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for NextStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let NextStatement {
+            loop_label,
+            condition,
+        } = self;
+        loop_label.shift_lines(delta);
+        condition.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ExitStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let ExitStatement {
+            loop_label,
+            condition,
+        } = self;
+        loop_label.shift_lines(delta);
+        condition.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ReturnStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let ReturnStatement { expression } = self;
+        expression.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SequentialStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            SequentialStatement::Wait(value) => value.shift_lines(delta),
+            SequentialStatement::Assert(value) => value.shift_lines(delta),
+            SequentialStatement::Report(value) => value.shift_lines(delta),
+            SequentialStatement::VariableAssignment(value) => value.shift_lines(delta),
+            SequentialStatement::SignalAssignment(value) => value.shift_lines(delta),
+            SequentialStatement::SignalForceAssignment(value) => value.shift_lines(delta),
+            SequentialStatement::SignalReleaseAssignment(value) => value.shift_lines(delta),
+            SequentialStatement::ProcedureCall(value) => value.shift_lines(delta),
+            SequentialStatement::If(value) => value.shift_lines(delta),
+            SequentialStatement::Case(value) => value.shift_lines(delta),
+            SequentialStatement::Loop(value) => value.shift_lines(delta),
+            SequentialStatement::Next(value) => value.shift_lines(delta),
+            SequentialStatement::Exit(value) => value.shift_lines(delta),
+            SequentialStatement::Return(value) => value.shift_lines(delta),
+            SequentialStatement::Null => {}
+        }
+    }
+}
+
+impl ShiftLines for LabeledSequentialStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let LabeledSequentialStatement { label, statement } = self;
+        label.shift_lines(delta);
+        statement.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for BlockStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let BlockStatement {
+            guard_condition,
+            header,
+            decl,
+            statements,
+        } = self;
+        guard_condition.shift_lines(delta);
+        header.shift_lines(delta);
+        decl.shift_lines(delta);
+        statements.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for BlockHeader {
+    fn shift_lines(&mut self, delta: i64) {
+        let BlockHeader {
+            generic_clause,
+            generic_map,
+            port_clause,
+            port_map,
+        } = self;
+        generic_clause.shift_lines(delta);
+        generic_map.shift_lines(delta);
+        port_clause.shift_lines(delta);
+        port_map.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for SensitivityList {
+    fn shift_lines(&mut self, delta: i64) {
+        if let SensitivityList::Names(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for ProcessStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let ProcessStatement {
+            sensitivity_list,
+            decl,
+            statements,
+            postponed: _,
+        } = self;
+        sensitivity_list.shift_lines(delta);
+        decl.shift_lines(delta);
+        statements.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConcurrentProcedureCall {
+    fn shift_lines(&mut self, delta: i64) {
+        let ConcurrentProcedureCall { call, postponed: _ } = self;
+        call.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConcurrentAssertStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let ConcurrentAssertStatement {
+            statement,
+            postponed: _,
+        } = self;
+        statement.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for PslProperty {
+    fn shift_lines(&mut self, delta: i64) {
+        let PslProperty {
+            booleans,
+            values,
+            counts,
+            clocks,
+        } = self;
+        booleans.shift_lines(delta);
+        values.shift_lines(delta);
+        counts.shift_lines(delta);
+        clocks.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for PslDirective {
+    fn shift_lines(&mut self, delta: i64) {
+        let PslDirective {
+            property,
+            report,
+            severity,
+            kind: _,
+        } = self;
+        property.shift_lines(delta);
+        report.shift_lines(delta);
+        severity.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConcurrentSignalAssignment {
+    fn shift_lines(&mut self, delta: i64) {
+        let ConcurrentSignalAssignment {
+            target,
+            delay_mechanism,
+            rhs,
+            postponed: _,
+            guarded: _,
+        } = self;
+        target.shift_lines(delta);
+        delay_mechanism.shift_lines(delta);
+        rhs.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for InstantiatedUnit {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            InstantiatedUnit::Component(value) => value.shift_lines(delta),
+            InstantiatedUnit::Entity(name, architecture) => {
+                name.shift_lines(delta);
+                architecture.shift_lines(delta);
+            }
+            InstantiatedUnit::Configuration(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for InstantiationStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let InstantiationStatement {
+            unit,
+            generic_map,
+            port_map,
+            default_binding: _,
+        } = self;
+        unit.shift_lines(delta);
+        generic_map.shift_lines(delta);
+        port_map.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for GenerateBody {
+    fn shift_lines(&mut self, delta: i64) {
+        let GenerateBody {
+            alternative_label,
+            decl,
+            statements,
+        } = self;
+        alternative_label.shift_lines(delta);
+        decl.shift_lines(delta);
+        statements.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ForGenerateStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let ForGenerateStatement {
+            index_name,
+            discrete_range,
+            body,
+        } = self;
+        index_name.shift_lines(delta);
+        discrete_range.shift_lines(delta);
+        body.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConcurrentStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ConcurrentStatement::ProcedureCall(value) => value.shift_lines(delta),
+            ConcurrentStatement::Block(value) => value.shift_lines(delta),
+            ConcurrentStatement::Process(value) => value.shift_lines(delta),
+            ConcurrentStatement::Assert(value) => value.shift_lines(delta),
+            ConcurrentStatement::PslDirective(value) => value.shift_lines(delta),
+            ConcurrentStatement::Assignment(value) => value.shift_lines(delta),
+            ConcurrentStatement::Instance(value) => value.shift_lines(delta),
+            ConcurrentStatement::ForGenerate(value) => value.shift_lines(delta),
+            ConcurrentStatement::IfGenerate(value) => value.shift_lines(delta),
+            ConcurrentStatement::CaseGenerate(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for LabeledConcurrentStatement {
+    fn shift_lines(&mut self, delta: i64) {
+        let LabeledConcurrentStatement { label, statement } = self;
+        label.shift_lines(delta);
+        statement.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for LibraryClause {
+    fn shift_lines(&mut self, delta: i64) {
+        let LibraryClause { name_list } = self;
+        name_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for UseClause {
+    fn shift_lines(&mut self, delta: i64) {
+        let UseClause { name_list } = self;
+        name_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ContextReference {
+    fn shift_lines(&mut self, delta: i64) {
+        let ContextReference { name_list } = self;
+        name_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ContextItem {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ContextItem::Use(value) => value.shift_lines(delta),
+            ContextItem::Library(value) => value.shift_lines(delta),
+            ContextItem::Context(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for ContextDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ContextDeclaration { ident, items } = self;
+        ident.shift_lines(delta);
+        items.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for PackageInstantiation {
+    fn shift_lines(&mut self, delta: i64) {
+        let PackageInstantiation {
+            context_clause,
+            ident,
+            package_name,
+            generic_map,
+        } = self;
+        context_clause.shift_lines(delta);
+        ident.shift_lines(delta);
+        package_name.shift_lines(delta);
+        generic_map.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for InstantiationList {
+    fn shift_lines(&mut self, delta: i64) {
+        if let InstantiationList::Labels(value) = self {
+            value.shift_lines(delta);
+        }
+    }
+}
+
+impl ShiftLines for EntityAspect {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            EntityAspect::Entity(name, architecture) => {
+                name.shift_lines(delta);
+                architecture.shift_lines(delta);
+            }
+            EntityAspect::Configuration(value) => value.shift_lines(delta),
+            EntityAspect::Open => {}
+        }
+    }
+}
+
+impl ShiftLines for BindingIndication {
+    fn shift_lines(&mut self, delta: i64) {
+        let BindingIndication {
+            entity_aspect,
+            generic_map,
+            port_map,
+        } = self;
+        entity_aspect.shift_lines(delta);
+        generic_map.shift_lines(delta);
+        port_map.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ComponentSpecification {
+    fn shift_lines(&mut self, delta: i64) {
+        let ComponentSpecification {
+            instantiation_list,
+            component_name,
+        } = self;
+        instantiation_list.shift_lines(delta);
+        component_name.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for VUnitBindingIndication {
+    fn shift_lines(&mut self, delta: i64) {
+        let VUnitBindingIndication { vunit_list } = self;
+        vunit_list.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConfigurationSpecification {
+    fn shift_lines(&mut self, delta: i64) {
+        let ConfigurationSpecification {
+            spec,
+            bind_ind,
+            vunit_bind_inds,
+        } = self;
+        spec.shift_lines(delta);
+        bind_ind.shift_lines(delta);
+        vunit_bind_inds.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConfigurationDeclarativeItem {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ConfigurationDeclarativeItem::Use(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for ComponentConfiguration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ComponentConfiguration {
+            spec,
+            bind_ind,
+            vunit_bind_inds,
+            block_config,
+        } = self;
+        spec.shift_lines(delta);
+        bind_ind.shift_lines(delta);
+        vunit_bind_inds.shift_lines(delta);
+        block_config.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConfigurationItem {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            ConfigurationItem::Block(value) => value.shift_lines(delta),
+            ConfigurationItem::Component(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for BlockConfiguration {
+    fn shift_lines(&mut self, delta: i64) {
+        let BlockConfiguration {
+            block_spec,
+            use_clauses,
+            items,
+        } = self;
+        block_spec.shift_lines(delta);
+        use_clauses.shift_lines(delta);
+        items.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ConfigurationDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let ConfigurationDeclaration {
+            context_clause,
+            ident,
+            entity_name,
+            decl,
+            vunit_bind_inds,
+            block_config,
+        } = self;
+        context_clause.shift_lines(delta);
+        ident.shift_lines(delta);
+        entity_name.shift_lines(delta);
+        decl.shift_lines(delta);
+        vunit_bind_inds.shift_lines(delta);
+        block_config.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for EntityDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let EntityDeclaration {
+            context_clause,
+            ident,
+            generic_clause,
+            port_clause,
+            decl,
+            statements,
+        } = self;
+        context_clause.shift_lines(delta);
+        ident.shift_lines(delta);
+        generic_clause.shift_lines(delta);
+        port_clause.shift_lines(delta);
+        decl.shift_lines(delta);
+        statements.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for ArchitectureBody {
+    fn shift_lines(&mut self, delta: i64) {
+        let ArchitectureBody {
+            context_clause,
+            ident,
+            entity_name,
+            decl,
+            statements,
+        } = self;
+        context_clause.shift_lines(delta);
+        ident.shift_lines(delta);
+        entity_name.shift_lines(delta);
+        decl.shift_lines(delta);
+        statements.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for PackageDeclaration {
+    fn shift_lines(&mut self, delta: i64) {
+        let PackageDeclaration {
+            context_clause,
+            ident,
+            generic_clause,
+            decl,
+        } = self;
+        context_clause.shift_lines(delta);
+        ident.shift_lines(delta);
+        generic_clause.shift_lines(delta);
+        decl.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for PackageBody {
+    fn shift_lines(&mut self, delta: i64) {
+        let PackageBody {
+            context_clause,
+            ident,
+            decl,
+        } = self;
+        context_clause.shift_lines(delta);
+        ident.shift_lines(delta);
+        decl.shift_lines(delta);
+    }
+}
+
+impl ShiftLines for AnyPrimaryUnit {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            AnyPrimaryUnit::Entity(value) => value.shift_lines(delta),
+            AnyPrimaryUnit::Configuration(value) => value.shift_lines(delta),
+            AnyPrimaryUnit::Package(value) => value.shift_lines(delta),
+            AnyPrimaryUnit::PackageInstance(value) => value.shift_lines(delta),
+            AnyPrimaryUnit::Context(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for AnySecondaryUnit {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            AnySecondaryUnit::Architecture(value) => value.shift_lines(delta),
+            AnySecondaryUnit::PackageBody(value) => value.shift_lines(delta),
+        }
+    }
+}
+
+impl ShiftLines for AnyDesignUnit {
+    fn shift_lines(&mut self, delta: i64) {
+        match self {
+            AnyDesignUnit::Primary(value) => value.shift_lines(delta),
+            AnyDesignUnit::Secondary(value) => value.shift_lines(delta),
+        }
+    }
+}
@@ -26,6 +26,23 @@ impl From<WithPos<SelectedName>> for WithPos<Name> {
     }
 }
 
+impl SelectedName {
+    /// Get an already set reference of the suffix
+    pub fn get_suffix_reference(&self) -> Option<EntityId> {
+        match self {
+            SelectedName::Designator(suffix) => suffix.reference,
+            SelectedName::Selected(_, suffix) => suffix.item.reference,
+        }
+    }
+
+    pub fn suffix_reference_mut(&mut self) -> &mut Reference {
+        match self {
+            SelectedName::Designator(suffix) => &mut suffix.reference,
+            SelectedName::Selected(_, suffix) => &mut suffix.item.reference,
+        }
+    }
+}
+
 impl WithPos<SelectedName> {
     pub fn suffix_pos(&self) -> &SrcPos {
         match self.item {
@@ -386,6 +403,7 @@ impl CallOrIndexed {
         let CallOrIndexed {
             ref mut name,
             ref mut parameters,
+            ..
         } = self;
 
         let mut indexes: Vec<Index> = Vec::with_capacity(parameters.len());
@@ -403,9 +421,10 @@ impl CallOrIndexed {
     }
 
     pub fn could_be_indexed_name(&self) -> bool {
-        self.parameters
-            .iter()
-            .all(|assoc| assoc.formal.is_none() && !matches!(assoc.actual.item, ActualPart::Open))
+        self.generic_map.is_none()
+            && self.parameters.iter().all(|assoc| {
+                assoc.formal.is_none() && !matches!(assoc.actual.item, ActualPart::Open)
+            })
     }
 }
 
@@ -6,6 +6,7 @@
 
 //! Configuration of the design hierarchy and other settings
 
+use crate::analysis::{ActiveLevel, ArchitectureSelection, ResetConvention, ResetStyle};
 use crate::data::*;
 use fnv::FnvHashMap;
 use std::env;
@@ -15,16 +16,43 @@ use std::io::prelude::*;
 use std::path::Path;
 use toml::Value;
 
+/// The name of the built-in profile of the lints of constructs that synthesis does not support
+const SYNTHESIS_PROFILE: &str = "synthesis";
+
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct Config {
     // A map from library name to file name
     libraries: FnvHashMap<String, LibraryConfig>,
+    // The reset that clocked processes are expected to use
+    reset_convention: Option<ResetConvention>,
+    // The revision of the language that the design is analyzed against
+    standard: Option<VHDLStandard>,
+    // Severity of diagnostics with an error code, None means ignored
+    lint_severities: FnvHashMap<ErrorCode, Option<Severity>>,
+    // Named profiles of severities which take precedence over the lint severities when selected
+    profiles: FnvHashMap<String, FnvHashMap<ErrorCode, Option<Severity>>>,
+    // Lines of non-standard preprocessors that are filtered before parsing
+    preprocessor: PreprocessorFilter,
+    // Values of the conditional analysis identifiers in addition to the predefined ones
+    conditional_analysis: FnvHashMap<String, String>,
+    // How the default binding selects one of several architectures of an entity
+    architecture_selection: Option<ArchitectureSelection>,
+    // Whether violations of the rules that vendor tools commonly accept are reported as hints
+    strictness: Option<Strictness>,
+    // Whether comments starting with `-- psl` contain embedded PSL
+    psl_comments: Option<bool>,
 }
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct LibraryConfig {
     name: String,
     patterns: Vec<String>,
+    // Names of primary units whose diagnostics and those of their secondary units are suppressed
+    ignored_units: Vec<String>,
+    // A library of testbenches or simulation models that is not part of the production design
+    simulation: bool,
+    // Severities of diagnostics with an error code within the library, overriding the lint severities
+    lint_severities: FnvHashMap<ErrorCode, Option<Severity>>,
 }
 
 impl LibraryConfig {
@@ -122,6 +150,20 @@ impl LibraryConfig {
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
+
+    /// Returns the names of the primary units whose diagnostics are ignored
+    pub fn is_simulation(&self) -> bool {
+        self.simulation
+    }
+
+    pub fn ignored_units(&self) -> &[String] {
+        &self.ignored_units
+    }
+
+    /// Returns the severities of the error codes that are overridden within the library
+    pub fn lint_severities(&self) -> &FnvHashMap<ErrorCode, Option<Severity>> {
+        &self.lint_severities
+    }
 }
 
 impl Config {
@@ -156,16 +198,126 @@ impl Config {
                 patterns.push(path);
             }
 
+            let mut ignored_units = Vec::new();
+            if let Some(unit_arr) = lib.get("ignore_units") {
+                let unit_arr = unit_arr
+                    .as_array()
+                    .ok_or_else(|| format!("ignore_units for library {name} is not array"))?;
+                for unit in unit_arr.iter() {
+                    let unit = unit
+                        .as_str()
+                        .ok_or_else(|| format!("not a string {unit}"))?;
+                    ignored_units.push(unit.to_owned());
+                }
+            }
+
+            let simulation = if let Some(simulation) = lib.get("simulation") {
+                simulation
+                    .as_bool()
+                    .ok_or_else(|| format!("simulation for library {name} is not a boolean"))?
+            } else {
+                false
+            };
+
+            let lint_severities = if let Some(lint) = lib.get("lint") {
+                parse_severities(&format!("libraries.{name}.lint"), lint)?
+            } else {
+                FnvHashMap::default()
+            };
+
             libraries.insert(
                 name.to_owned(),
                 LibraryConfig {
                     name: name.to_owned(),
                     patterns,
+                    ignored_units,
+                    simulation,
+                    lint_severities,
                 },
             );
         }
 
-        Ok(Config { libraries })
+        let mut reset_convention = None;
+        let mut lint_severities = FnvHashMap::default();
+        if let Some(lint) = config.get("lint") {
+            let lint = lint.as_table().ok_or("lint must be a table")?;
+            for (key, value) in lint.iter() {
+                if key == "reset" {
+                    reset_convention = Some(parse_reset_convention(value)?);
+                } else {
+                    let code = ErrorCode::from_name(key)
+                        .ok_or_else(|| format!("unknown field lint.{key}"))?;
+                    lint_severities
+                        .insert(code, parse_lint_severity(&format!("lint.{key}"), value)?);
+                }
+            }
+        }
+
+        let standard = if let Some(standard) = config.get("standard") {
+            Some(parse_standard(standard)?)
+        } else {
+            None
+        };
+
+        let mut profiles = FnvHashMap::default();
+        if let Some(profile_tables) = config.get("profiles") {
+            let profile_tables = profile_tables
+                .as_table()
+                .ok_or("profiles must be a table")?;
+            for (name, profile) in profile_tables.iter() {
+                profiles.insert(
+                    name.to_owned(),
+                    parse_severities(&format!("profiles.{name}"), profile)?,
+                );
+            }
+        }
+
+        let preprocessor = if let Some(preprocessor) = config.get("preprocessor") {
+            parse_preprocessor(preprocessor)?
+        } else {
+            PreprocessorFilter::default()
+        };
+
+        let conditional_analysis = if let Some(symbols) = config.get("conditional_analysis") {
+            parse_conditional_analysis(symbols)?
+        } else {
+            FnvHashMap::default()
+        };
+
+        let architecture_selection = if let Some(selection) = config.get("architecture_selection") {
+            Some(parse_architecture_selection(selection)?)
+        } else {
+            None
+        };
+
+        let strictness = if let Some(strictness) = config.get("strictness") {
+            Some(parse_strictness(strictness)?)
+        } else {
+            None
+        };
+
+        let psl_comments = if let Some(psl_comments) = config.get("psl_comments") {
+            Some(
+                psl_comments
+                    .as_bool()
+                    .ok_or("psl_comments must be a boolean")?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Config {
+            libraries,
+            reset_convention,
+            standard,
+            lint_severities,
+            profiles,
+            preprocessor,
+            conditional_analysis,
+            architecture_selection,
+            strictness,
+            psl_comments,
+        })
     }
 
     pub fn read_file_path(file_name: &Path) -> io::Result<Config> {
@@ -186,6 +338,83 @@ impl Config {
         self.libraries.values()
     }
 
+    pub fn reset_convention(&self) -> Option<&ResetConvention> {
+        self.reset_convention.as_ref()
+    }
+
+    /// The configured revision of the language, VHDL-2008 unless configured
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard.unwrap_or_default()
+    }
+
+    /// How the default binding selects one of several architectures of an entity,
+    /// the most recently analyzed one unless configured
+    pub fn architecture_selection(&self) -> ArchitectureSelection {
+        self.architecture_selection.unwrap_or_default()
+    }
+
+    /// How strictly the rules of the LRM are enforced, strict unless configured
+    pub fn strictness(&self) -> Strictness {
+        self.strictness.unwrap_or_default()
+    }
+
+    /// Whether comments starting with `-- psl` are parsed as embedded PSL, disabled by default
+    pub fn psl_comments(&self) -> bool {
+        self.psl_comments.unwrap_or(false)
+    }
+
+    pub fn lint_severities(&self) -> &FnvHashMap<ErrorCode, Option<Severity>> {
+        &self.lint_severities
+    }
+
+    pub fn preprocessor(&self) -> &PreprocessorFilter {
+        &self.preprocessor
+    }
+
+    /// The values of the conditional analysis identifiers, including the predefined ones
+    pub fn conditional_analysis(&self) -> ConditionalAnalysis {
+        let mut analysis = ConditionalAnalysis::new(self.standard());
+        for (name, value) in self.conditional_analysis.iter() {
+            analysis.define(name, value.as_str());
+        }
+        analysis
+    }
+
+    /// The names of the diagnostics profiles in alphabetical order
+    /// including the built-in synthesis profile
+    pub fn profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+        names.push(SYNTHESIS_PROFILE);
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Apply the severities of a diagnostics profile on top of the lint severities,
+    /// such that an editor may use a light profile while CI runs a strict one
+    ///
+    /// The built-in synthesis profile warns about the constructs that synthesis tools
+    /// commonly do not support, and may be adjusted by a profile of the same name
+    pub fn select_profile(&mut self, name: &str) -> Result<(), String> {
+        if name == SYNTHESIS_PROFILE {
+            for code in ErrorCode::ALL.iter() {
+                if code.is_synthesis_restriction() {
+                    self.lint_severities.insert(*code, Some(Severity::Warning));
+                }
+            }
+        }
+        let empty = FnvHashMap::default();
+        let profile = match self.profiles.get(name) {
+            Some(profile) => profile,
+            None if name == SYNTHESIS_PROFILE => &empty,
+            None => return Err(format!("Unknown diagnostics profile '{name}'")),
+        };
+        for (code, severity) in profile.iter() {
+            self.lint_severities.insert(*code, *severity);
+        }
+        Ok(())
+    }
+
     /// Append another config to self
     ///
     /// In case of conflict the appended config takes precedence
@@ -199,15 +428,92 @@ impl Config {
                     &library.name
                 )));
             } else {
-                self.libraries.insert(
-                    library.name.clone(),
-                    LibraryConfig {
-                        name: library.name.clone(),
-                        patterns: library.patterns.clone(),
-                    },
-                );
+                self.libraries.insert(library.name.clone(), library.clone());
+            }
+        }
+
+        if config.reset_convention.is_some() {
+            self.reset_convention = config.reset_convention.clone();
+        }
+
+        if config.standard.is_some() {
+            self.standard = config.standard;
+        }
+
+        if config.architecture_selection.is_some() {
+            self.architecture_selection = config.architecture_selection;
+        }
+
+        if config.strictness.is_some() {
+            self.strictness = config.strictness;
+        }
+
+        if config.psl_comments.is_some() {
+            self.psl_comments = config.psl_comments;
+        }
+
+        for (code, severity) in config.lint_severities.iter() {
+            self.lint_severities.insert(*code, *severity);
+        }
+
+        for (name, profile) in config.profiles.iter() {
+            let severities = self.profiles.entry(name.clone()).or_default();
+            for (code, severity) in profile.iter() {
+                severities.insert(*code, *severity);
             }
         }
+
+        self.preprocessor.append(&config.preprocessor);
+
+        for (name, value) in config.conditional_analysis.iter() {
+            self.conditional_analysis
+                .insert(name.clone(), value.clone());
+        }
+    }
+
+    /// The ranges of the keys naming a library in the text of a configuration file
+    /// This is used to rename a library in the configuration without reformatting it.
+    /// Handles `[libraries.name]` tables as well as dotted keys and inline tables such as
+    /// `name.files = [...]` and `name = { files = [...] }` in the `[libraries]` table.
+    pub fn library_key_ranges(contents: &str, name: &str) -> Vec<Range> {
+        let mut ranges = Vec::new();
+        let mut table: Vec<String> = Vec::new();
+
+        for (lineno, line) in contents.lines().enumerate() {
+            let trimmed = line.trim_start();
+            let start = line.len() - trimmed.len();
+            let (keys, is_header) = if trimmed.starts_with('[') {
+                let offset = start + trimmed.len() - trimmed.trim_start_matches('[').len();
+                let Some((keys, _)) = parse_dotted_key(line, offset) else {
+                    continue;
+                };
+                table = keys.iter().map(|(key, _)| key.clone()).collect();
+                (keys, true)
+            } else {
+                match parse_dotted_key(line, start) {
+                    Some((keys, end)) if line[end..].trim_start().starts_with('=') => (keys, false),
+                    _ => continue,
+                }
+            };
+
+            // The position of the library name in the full key path
+            let depth = if is_header { 0 } else { table.len() };
+            let path: Vec<&str> = table[..depth]
+                .iter()
+                .map(String::as_str)
+                .chain(keys.iter().map(|(key, _)| key.as_str()))
+                .collect();
+            if let ["libraries", library, ..] = path.as_slice() {
+                if library.eq_ignore_ascii_case(name) && depth <= 1 {
+                    let (_, (key_start, key_end)) = &keys[1 - depth];
+                    let position = |idx: usize| {
+                        Position::new(lineno as u32, line[..idx].chars().count() as u32)
+                    };
+                    ranges.push(Range::new(position(*key_start), position(*key_end)));
+                }
+            }
+        }
+        ranges
     }
 
     /// Load configuration file from installation folder
@@ -288,6 +594,247 @@ impl Config {
     }
 }
 
+/// Parse the revision of the language
+///
+/// standard = "1993" | "2002" | "2008" | "2019"
+fn parse_standard(standard: &Value) -> Result<VHDLStandard, String> {
+    standard
+        .as_str()
+        .and_then(VHDLStandard::from_name)
+        .ok_or_else(|| format!("standard must be '1993', '2002', '2008' or '2019', got {standard}"))
+}
+
+/// Parse how strictly the rules of the LRM are enforced
+///
+/// strictness = "strict" | "tolerant"
+fn parse_strictness(strictness: &Value) -> Result<Strictness, String> {
+    strictness
+        .as_str()
+        .and_then(Strictness::from_name)
+        .ok_or_else(|| format!("strictness must be 'strict' or 'tolerant', got {strictness}"))
+}
+
+/// Parse how the default binding selects one of several architectures of an entity
+///
+/// architecture_selection = "last_analyzed" | "alphabetical"
+fn parse_architecture_selection(selection: &Value) -> Result<ArchitectureSelection, String> {
+    selection
+        .as_str()
+        .and_then(ArchitectureSelection::from_name)
+        .ok_or_else(|| {
+            format!(
+                "architecture_selection must be 'last_analyzed' or 'alphabetical', got {selection}"
+            )
+        })
+}
+
+/// Parse the [lint.reset] table
+///
+/// [lint.reset]
+/// style = "synchronous" | "asynchronous"
+/// active = "high" | "low"
+/// required = true | false
+fn parse_reset_convention(reset: &Value) -> Result<ResetConvention, String> {
+    let reset = reset.as_table().ok_or("lint.reset must be a table")?;
+    let mut convention = ResetConvention::default();
+
+    for (key, value) in reset.iter() {
+        match key.as_str() {
+            "style" => {
+                convention.style = match value.as_str() {
+                    Some("synchronous") => Some(ResetStyle::Synchronous),
+                    Some("asynchronous") => Some(ResetStyle::Asynchronous),
+                    _ => {
+                        return Err(format!(
+                            "lint.reset.style must be 'synchronous' or 'asynchronous', got {value}"
+                        ))
+                    }
+                }
+            }
+            "active" => {
+                convention.level = match value.as_str() {
+                    Some("high") => Some(ActiveLevel::High),
+                    Some("low") => Some(ActiveLevel::Low),
+                    _ => {
+                        return Err(format!(
+                            "lint.reset.active must be 'high' or 'low', got {value}"
+                        ))
+                    }
+                }
+            }
+            "required" => {
+                convention.required = value
+                    .as_bool()
+                    .ok_or_else(|| format!("lint.reset.required must be a boolean, got {value}"))?;
+            }
+            _ => return Err(format!("unknown field lint.reset.{key}")),
+        }
+    }
+
+    Ok(convention)
+}
+
+/// Parse the [preprocessor] table of line prefixes that are skipped or mapped to another line
+///
+/// [preprocessor]
+/// skip = ["`include", "`define"]
+/// map = { "`timescale" = "-- timescale" }
+fn parse_preprocessor(preprocessor: &Value) -> Result<PreprocessorFilter, String> {
+    let preprocessor = preprocessor
+        .as_table()
+        .ok_or("preprocessor must be a table")?;
+    let mut filter = PreprocessorFilter::default();
+
+    for (key, value) in preprocessor.iter() {
+        match key.as_str() {
+            "skip" => {
+                let prefixes = value
+                    .as_array()
+                    .ok_or("preprocessor.skip must be an array")?;
+                for prefix in prefixes.iter() {
+                    let prefix = prefix
+                        .as_str()
+                        .filter(|prefix| !prefix.is_empty())
+                        .ok_or_else(|| {
+                            format!(
+                                "preprocessor.skip must contain non-empty strings, got {prefix}"
+                            )
+                        })?;
+                    filter.skip(prefix);
+                }
+            }
+            "map" => {
+                let mapping = value.as_table().ok_or("preprocessor.map must be a table")?;
+                for (prefix, replacement) in mapping.iter() {
+                    if prefix.is_empty() {
+                        return Err("preprocessor.map must not contain an empty prefix".to_owned());
+                    }
+                    let replacement = replacement.as_str().ok_or_else(|| {
+                        format!("preprocessor.map.{prefix} must be a string, got {replacement}")
+                    })?;
+                    filter.map(prefix.as_str(), replacement);
+                }
+            }
+            _ => return Err(format!("unknown field preprocessor.{key}")),
+        }
+    }
+
+    Ok(filter)
+}
+
+/// Parse the [conditional_analysis] table of identifier values
+///
+/// [conditional_analysis]
+/// TOOL_TYPE = "SIMULATION"
+fn parse_conditional_analysis(symbols: &Value) -> Result<FnvHashMap<String, String>, String> {
+    let symbols = symbols
+        .as_table()
+        .ok_or("conditional_analysis must be a table")?;
+    let mut values = FnvHashMap::default();
+
+    for (name, value) in symbols.iter() {
+        let is_identifier = name.starts_with(|chr: char| chr.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|chr| chr.is_ascii_alphanumeric() || chr == '_');
+        if !is_identifier {
+            return Err(format!(
+                "conditional_analysis.{name} is not a valid identifier"
+            ));
+        }
+        let value = value
+            .as_str()
+            .ok_or_else(|| format!("conditional_analysis.{name} must be a string, got {value}"))?;
+        values.insert(name.to_owned(), value.to_owned());
+    }
+
+    Ok(values)
+}
+
+/// Parse the severity of an error code in the [lint] table or a profile
+///
+/// [lint]
+/// duplicate_sensitivity = "error" | "warning" | "info" | "hint" | "ignore"
+fn parse_lint_severity(field: &str, value: &Value) -> Result<Option<Severity>, String> {
+    match value.as_str() {
+        Some("error") => Ok(Some(Severity::Error)),
+        Some("warning") => Ok(Some(Severity::Warning)),
+        Some("info") => Ok(Some(Severity::Info)),
+        Some("hint") => Ok(Some(Severity::Hint)),
+        Some("ignore") => Ok(None),
+        _ => Err(format!(
+            "{field} must be 'error', 'warning', 'info', 'hint' or 'ignore', got {value}"
+        )),
+    }
+}
+
+/// Parse the severities of error codes of a named diagnostics profile or a library
+///
+/// [profiles.editor]
+/// redundant_sensitivity = "ignore"
+///
+/// [libraries]
+/// lib.lint.signal_initial_value = "ignore"
+fn parse_severities(
+    field: &str,
+    table: &Value,
+) -> Result<FnvHashMap<ErrorCode, Option<Severity>>, String> {
+    let table = table
+        .as_table()
+        .ok_or_else(|| format!("{field} must be a table"))?;
+
+    let mut severities = FnvHashMap::default();
+    for (key, value) in table.iter() {
+        let code =
+            ErrorCode::from_name(key).ok_or_else(|| format!("unknown field {field}.{key}"))?;
+        severities.insert(code, parse_lint_severity(&format!("{field}.{key}"), value)?);
+    }
+    Ok(severities)
+}
+
+/// A part of a dotted TOML key and the byte range of its name within the line
+type KeyPart = (String, (usize, usize));
+
+/// Parse a dotted TOML key such as `libraries."lib".files` starting at a byte offset of the line
+/// Returns the parts of the key and the offset after the key
+fn parse_dotted_key(line: &str, offset: usize) -> Option<(Vec<KeyPart>, usize)> {
+    let bytes = line.as_bytes();
+    let skip_whitespace = |mut idx: usize| {
+        while idx < bytes.len() && (bytes[idx] == b' ' || bytes[idx] == b'\t') {
+            idx += 1;
+        }
+        idx
+    };
+
+    let mut keys = Vec::new();
+    let mut idx = skip_whitespace(offset);
+    loop {
+        let (key_start, key_end, next) = match bytes.get(idx)? {
+            quote @ (b'"' | b'\'') => {
+                let len = line[idx + 1..].find(*quote as char)?;
+                (idx + 1, idx + 1 + len, idx + len + 2)
+            }
+            _ => {
+                let len = line[idx..]
+                    .find(|chr: char| !(chr.is_ascii_alphanumeric() || chr == '_' || chr == '-'))
+                    .unwrap_or(line.len() - idx);
+                if len == 0 {
+                    return None;
+                }
+                (idx, idx + len, idx + len)
+            }
+        };
+        keys.push((line[key_start..key_end].to_owned(), (key_start, key_end)));
+
+        idx = skip_whitespace(next);
+        if bytes.get(idx) == Some(&b'.') {
+            idx = skip_whitespace(idx + 1);
+        } else {
+            return Some((keys, idx));
+        }
+    }
+}
+
 /// Returns true if the pattern is a plain file name and not a glob pattern
 fn is_literal(pattern: &str) -> bool {
     for chr in pattern.chars() {
@@ -325,6 +872,38 @@ mod tests {
         assert_eq!(got, abspaths(expected).as_slice());
     }
 
+    #[test]
+    fn library_key_ranges() {
+        let contents = "\
+[libraries]
+old_lib.files = ['a.vhd']
+\"old_lib\" = { files = ['b.vhd'] }
+other.files = ['old_lib.vhd']
+files = [
+  'old_lib = x.vhd',
+]
+
+[libraries.OLD_LIB]
+files = ['c.vhd']
+
+[lint]
+old_lib = 'ignore'
+";
+
+        assert_eq!(
+            Config::library_key_ranges(contents, "old_lib"),
+            vec![
+                Range::new(Position::new(1, 0), Position::new(1, 7)),
+                Range::new(Position::new(2, 1), Position::new(2, 8)),
+                Range::new(Position::new(8, 11), Position::new(8, 18)),
+            ]
+        );
+        assert_eq!(
+            Config::library_key_ranges("libraries.old_lib.files = []", "old_lib"),
+            vec![Range::new(Position::new(0, 10), Position::new(0, 17))]
+        );
+    }
+
     #[test]
     fn test_is_literal() {
         assert!(is_literal("file.vhd"));
@@ -438,102 +1017,711 @@ lib3.files = [
     }
 
     #[test]
-    fn test_warning_on_missing_file() {
+    fn ignored_units_from_str() {
         let parent = Path::new("parent_folder");
         let config = Config::from_str(
             "
 [libraries]
-lib.files = [
-  'missing.vhd'
-]
+lib.files = []
+lib.ignore_units = ['broken', 'also_broken']
+lib2.files = []
 ",
             parent,
         )
         .unwrap();
+        assert_eq!(
+            config.get_library("lib").unwrap().ignored_units(),
+            &["broken".to_owned(), "also_broken".to_owned()]
+        );
+        assert!(config
+            .get_library("lib2")
+            .unwrap()
+            .ignored_units()
+            .is_empty());
 
-        let mut messages = vec![];
-        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
-        assert_files_eq(&file_names, &[]);
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+lib.ignore_units = 'broken'
+",
+            parent,
+        );
         assert_eq!(
-            messages,
-            vec![Message::warning(format!(
-                "File {} does not exist",
-                parent.join("missing.vhd").to_str().unwrap()
-            ))]
+            config,
+            Err("ignore_units for library lib is not array".to_owned())
         );
     }
 
     #[test]
-    fn test_file_wildcard_pattern() {
-        let tempdir = tempfile::tempdir().unwrap();
-        let parent = tempdir.path();
+    fn simulation_libraries_from_str() {
+        let parent = Path::new("parent_folder");
         let config = Config::from_str(
             "
 [libraries]
-lib.files = [
-  '*.vhd'
-]
+lib.files = []
+tb_lib.files = []
+tb_lib.simulation = true
 ",
             parent,
         )
         .unwrap();
+        assert!(!config.get_library("lib").unwrap().is_simulation());
+        assert!(config.get_library("tb_lib").unwrap().is_simulation());
 
-        let file1 = touch(parent, "file1.vhd");
-        let file2 = touch(parent, "file2.vhd");
-
-        let mut messages = vec![];
-        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
-        assert_files_eq(&file_names, &[file1, file2]);
-        assert_eq!(messages, vec![]);
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+lib.simulation = 'yes'
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err("simulation for library lib is not a boolean".to_owned())
+        );
     }
 
     #[test]
-    fn test_file_wildcard_pattern_removes_duplicates() {
-        let tempdir = tempfile::tempdir().unwrap();
-        let parent = tempdir.path();
+    fn psl_comments_from_str() {
+        let parent = Path::new("parent_folder");
         let config = Config::from_str(
             "
+psl_comments = true
+
 [libraries]
-lib.files = [
-  '*.vhd',
-  'file*.vhd'
-]
+lib.files = []
 ",
             parent,
         )
         .unwrap();
+        assert!(config.psl_comments());
 
-        let file1 = touch(parent, "file1.vhd");
-        let file2 = touch(parent, "file2.vhd");
+        let config = Config::from_str("[libraries]", parent).unwrap();
+        assert!(!config.psl_comments());
 
-        let mut messages = vec![];
-        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
-        assert_files_eq(&file_names, &[file1, file2]);
-        assert_eq!(messages, vec![]);
+        let config = Config::from_str(
+            "
+psl_comments = 'yes'
+
+[libraries]
+",
+            parent,
+        );
+        assert_eq!(config, Err("psl_comments must be a boolean".to_owned()));
     }
+
     #[test]
-    fn test_warning_on_emtpy_glob_pattern() {
+    fn strictness_from_str() {
         let parent = Path::new("parent_folder");
         let config = Config::from_str(
             "
+strictness = 'tolerant'
+
 [libraries]
-lib.files = [
-  'missing*.vhd'
-]
+lib.files = []
 ",
             parent,
         )
         .unwrap();
+        assert_eq!(config.strictness(), Strictness::Tolerant);
 
-        let mut messages = vec![];
-        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
-        assert_files_eq(&file_names, &[]);
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(config.strictness(), Strictness::Strict);
+
+        let config = Config::from_str(
+            "
+strictness = 'lenient'
+
+[libraries]
+lib.files = []
+",
+            parent,
+        );
         assert_eq!(
-            messages,
-            vec![Message::warning(format!(
-                "Pattern '{}' did not match any file",
-                parent.join("missing*.vhd").to_str().unwrap()
-            ))]
+            config,
+            Err("strictness must be 'strict' or 'tolerant', got \"lenient\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn architecture_selection_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+architecture_selection = 'alphabetical'
+
+[libraries]
+lib.files = []
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config.architecture_selection(),
+            ArchitectureSelection::Alphabetical
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config.architecture_selection(),
+            ArchitectureSelection::LastAnalyzed
+        );
+
+        let config = Config::from_str(
+            "
+architecture_selection = 'first'
+
+[libraries]
+lib.files = []
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err(
+                "architecture_selection must be 'last_analyzed' or 'alphabetical', got \"first\""
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn standard_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+standard = '1993'
+
+[libraries]
+lib.files = []
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(config.standard(), VHDLStandard::VHDL1993);
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(config.standard(), VHDLStandard::VHDL2008);
+
+        let config = Config::from_str(
+            "
+standard = '87'
+
+[libraries]
+lib.files = []
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err("standard must be '1993', '2002', '2008' or '2019', got \"87\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn reset_convention_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[lint.reset]
+style = 'asynchronous'
+active = 'low'
+required = true
+",
+            parent,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.reset_convention(),
+            Some(&ResetConvention {
+                style: Some(ResetStyle::Asynchronous),
+                level: Some(ActiveLevel::Low),
+                required: true,
+            })
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[lint.reset]
+style = 'async'
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err(
+                "lint.reset.style must be 'synchronous' or 'asynchronous', got \"async\""
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn lint_severities_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[lint]
+duplicate_sensitivity = 'error'
+redundant_sensitivity = 'ignore'
+",
+            parent,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config
+                .lint_severities()
+                .get(&ErrorCode::DuplicateSensitivity),
+            Some(&Some(Severity::Error))
+        );
+        assert_eq!(
+            config
+                .lint_severities()
+                .get(&ErrorCode::RedundantSensitivity),
+            Some(&None)
+        );
+        assert_eq!(
+            config
+                .lint_severities()
+                .get(&ErrorCode::NonSignalSensitivity),
+            None
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[lint]
+duplicate_sensitivity = 'fatal'
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err(
+                "lint.duplicate_sensitivity must be 'error', 'warning', 'info', 'hint' or 'ignore', got \"fatal\""
+                    .to_owned()
+            )
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[lint]
+missing = 'error'
+",
+            parent,
+        );
+        assert_eq!(config, Err("unknown field lint.missing".to_owned()));
+    }
+
+    #[test]
+    fn profiles_from_str() {
+        let parent = Path::new("parent_folder");
+        let mut config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[lint]
+duplicate_sensitivity = 'warning'
+redundant_sensitivity = 'warning'
+
+[profiles.editor]
+redundant_sensitivity = 'ignore'
+
+[profiles.release]
+duplicate_sensitivity = 'error'
+",
+            parent,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.profile_names(),
+            vec!["editor", "release", "synthesis"]
+        );
+        assert_eq!(
+            config.select_profile("commit"),
+            Err("Unknown diagnostics profile 'commit'".to_owned())
+        );
+
+        config.select_profile("release").unwrap();
+        assert_eq!(
+            config
+                .lint_severities()
+                .get(&ErrorCode::DuplicateSensitivity),
+            Some(&Some(Severity::Error))
+        );
+        assert_eq!(
+            config
+                .lint_severities()
+                .get(&ErrorCode::RedundantSensitivity),
+            Some(&Some(Severity::Warning))
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[profiles.editor]
+missing = 'error'
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err("unknown field profiles.editor.missing".to_owned())
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[profiles.editor]
+duplicate_sensitivity = 'fatal'
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err(
+                "profiles.editor.duplicate_sensitivity must be 'error', 'warning', 'info', 'hint' or 'ignore', got \"fatal\""
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn synthesis_profile() {
+        let parent = Path::new("parent_folder");
+        let mut config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[profiles.synthesis]
+signal_initial_value = 'ignore'
+",
+            parent,
+        )
+        .unwrap();
+
+        config.select_profile("synthesis").unwrap();
+        assert_eq!(
+            config.lint_severities().get(&ErrorCode::TimedWait),
+            Some(&Some(Severity::Warning))
+        );
+        assert_eq!(
+            config.lint_severities().get(&ErrorCode::SignalInitialValue),
+            Some(&None)
+        );
+
+        let mut config = Config::from_str("[libraries]", parent).unwrap();
+        assert_eq!(config.profile_names(), vec!["synthesis"]);
+        config.select_profile("synthesis").unwrap();
+        assert_eq!(
+            config.lint_severities().get(&ErrorCode::SharedVariable),
+            Some(&Some(Severity::Warning))
+        );
+    }
+
+    #[test]
+    fn library_lint_severities_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+lib.lint.signal_initial_value = 'ignore'
+other_lib.files = []
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .get_library("lib")
+                .unwrap()
+                .lint_severities()
+                .get(&ErrorCode::SignalInitialValue),
+            Some(&None)
+        );
+        assert!(config
+            .get_library("other_lib")
+            .unwrap()
+            .lint_severities()
+            .is_empty());
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+lib.lint.missing = 'error'
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err("unknown field libraries.lib.lint.missing".to_owned())
+        );
+    }
+
+    #[test]
+    fn appended_profiles_take_precedence() {
+        let parent = Path::new("parent_folder");
+        let mut config = Config::from_str(
+            "
+[libraries]
+
+[profiles.editor]
+duplicate_sensitivity = 'ignore'
+redundant_sensitivity = 'ignore'
+",
+            parent,
+        )
+        .unwrap();
+        let config2 = Config::from_str(
+            "
+[libraries]
+
+[profiles.editor]
+duplicate_sensitivity = 'hint'
+",
+            parent,
+        )
+        .unwrap();
+
+        let mut messages = Vec::new();
+        config.append(&config2, &mut messages);
+        config.select_profile("editor").unwrap();
+        assert_eq!(
+            config
+                .lint_severities()
+                .get(&ErrorCode::DuplicateSensitivity),
+            Some(&Some(Severity::Hint))
+        );
+        assert_eq!(
+            config
+                .lint_severities()
+                .get(&ErrorCode::RedundantSensitivity),
+            Some(&None)
+        );
+    }
+
+    #[test]
+    fn test_warning_on_missing_file() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = [
+  'missing.vhd'
+]
+",
+            parent,
+        )
+        .unwrap();
+
+        let mut messages = vec![];
+        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
+        assert_files_eq(&file_names, &[]);
+        assert_eq!(
+            messages,
+            vec![Message::warning(format!(
+                "File {} does not exist",
+                parent.join("missing.vhd").to_str().unwrap()
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_file_wildcard_pattern() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = [
+  '*.vhd'
+]
+",
+            parent,
+        )
+        .unwrap();
+
+        let file1 = touch(parent, "file1.vhd");
+        let file2 = touch(parent, "file2.vhd");
+
+        let mut messages = vec![];
+        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
+        assert_files_eq(&file_names, &[file1, file2]);
+        assert_eq!(messages, vec![]);
+    }
+
+    #[test]
+    fn test_file_wildcard_pattern_removes_duplicates() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = [
+  '*.vhd',
+  'file*.vhd'
+]
+",
+            parent,
+        )
+        .unwrap();
+
+        let file1 = touch(parent, "file1.vhd");
+        let file2 = touch(parent, "file2.vhd");
+
+        let mut messages = vec![];
+        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
+        assert_files_eq(&file_names, &[file1, file2]);
+        assert_eq!(messages, vec![]);
+    }
+    #[test]
+    fn test_warning_on_emtpy_glob_pattern() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = [
+  'missing*.vhd'
+]
+",
+            parent,
+        )
+        .unwrap();
+
+        let mut messages = vec![];
+        let file_names = config.get_library("lib").unwrap().file_names(&mut messages);
+        assert_files_eq(&file_names, &[]);
+        assert_eq!(
+            messages,
+            vec![Message::warning(format!(
+                "Pattern '{}' did not match any file",
+                parent.join("missing*.vhd").to_str().unwrap()
+            ))]
+        );
+    }
+
+    #[test]
+    fn preprocessor_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[preprocessor]
+skip = ['`include', '`define']
+map = { '`timescale' = '-- timescale' }
+",
+            parent,
+        )
+        .unwrap();
+
+        let mut expected = PreprocessorFilter::default();
+        expected.skip("`include");
+        expected.skip("`define");
+        expected.map("`timescale", "-- timescale");
+        assert_eq!(config.preprocessor(), &expected);
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[preprocessor]
+skip = ['']
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err("preprocessor.skip must contain non-empty strings, got \"\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn conditional_analysis_from_str() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+standard = '2019'
+
+[libraries]
+lib.files = []
+
+[conditional_analysis]
+TOOL_TYPE = 'SIMULATION'
+sim_mode = 'fast'
+",
+            parent,
+        )
+        .unwrap();
+
+        let analysis = config.conditional_analysis();
+        assert_eq!(analysis.value("TOOL_TYPE"), Some("SIMULATION"));
+        assert_eq!(analysis.value("SIM_MODE"), Some("fast"));
+        assert_eq!(analysis.value("VHDL_VERSION"), Some("2019"));
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = []
+
+[conditional_analysis]
+SIM_MODE = 1
+",
+            parent,
+        );
+        assert_eq!(
+            config,
+            Err("conditional_analysis.SIM_MODE must be a string, got 1".to_owned())
         );
     }
 }
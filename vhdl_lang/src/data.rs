@@ -5,16 +5,30 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
+mod archive;
+mod conditional_analysis;
 mod contents;
 mod diagnostic;
+mod error_codes;
 mod latin_1;
 mod message;
+mod origin;
+mod preprocessor;
+mod protected_region;
 mod source;
+mod standard;
 mod symbol_table;
 
+pub use archive::*;
+pub use conditional_analysis::*;
 pub use contents::*;
 pub use diagnostic::*;
+pub use error_codes::*;
 pub use latin_1::*;
 pub use message::*;
+pub use origin::*;
+pub use preprocessor::*;
+pub use protected_region::*;
 pub use source::*;
+pub use standard::*;
 pub use symbol_table::*;
@@ -0,0 +1,207 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Archives of the public interface of a library
+//!
+//! An archive holds the source text of the entities, package declarations, contexts and
+//! package instances of an analyzed library but none of its architectures and package bodies.
+//! It is listed among the files of a library such that other projects are analyzed against
+//! the interface without having the sources of the implementation.
+//!
+//! The binary format starts with the magic bytes `VHDLA` and a format version byte followed
+//! by the library name, the number of units and the name and source text of each unit.
+//! Numbers are 32-bit little endian and strings are UTF-8 prefixed by their length in bytes.
+
+use std::io;
+use std::path::Path;
+
+const MAGIC: &[u8] = b"VHDLA";
+const VERSION: u8 = 1;
+
+/// The file extension of library archives
+pub const ARCHIVE_EXTENSION: &str = "vhdla";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ArchivedUnit {
+    pub name: String,
+    pub text: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LibraryArchive {
+    pub library_name: String,
+    pub units: Vec<ArchivedUnit>,
+}
+
+impl LibraryArchive {
+    /// True if the file is a library archive by its extension
+    pub fn is_archive(file_name: &Path) -> bool {
+        file_name
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(ARCHIVE_EXTENSION))
+    }
+
+    pub fn read(file_name: &Path) -> io::Result<LibraryArchive> {
+        let bytes = std::fs::read(file_name)?;
+        Self::from_bytes(&bytes).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {err}", file_name.to_string_lossy()),
+            )
+        })
+    }
+
+    pub fn write(&self, file_name: &Path) -> io::Result<()> {
+        std::fs::write(file_name, self.to_bytes())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        write_string(&mut bytes, &self.library_name);
+        write_u32(&mut bytes, self.units.len());
+        for unit in self.units.iter() {
+            write_string(&mut bytes, &unit.name);
+            write_string(&mut bytes, &unit.text);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<LibraryArchive, String> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len()).ok() != Some(MAGIC) {
+            return Err("Not a library archive".to_owned());
+        }
+        let version = reader.take(1)?[0];
+        if version != VERSION {
+            return Err(format!(
+                "Unsupported library archive version {version}, expected {VERSION}"
+            ));
+        }
+
+        let library_name = reader.string()?;
+        let num_units = reader.u32()?;
+        let mut units = Vec::new();
+        for _ in 0..num_units {
+            units.push(ArchivedUnit {
+                name: reader.string()?,
+                text: reader.string()?,
+            });
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err("Unexpected data at the end of the library archive".to_owned());
+        }
+
+        Ok(LibraryArchive {
+            library_name,
+            units,
+        })
+    }
+
+    /// The source text of all units as a single design file
+    pub fn contents(&self) -> String {
+        let mut contents = String::new();
+        for unit in self.units.iter() {
+            contents.push_str(&unit.text);
+            contents.push_str("\n\n");
+        }
+        contents
+    }
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: usize) {
+    bytes.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+fn write_string(bytes: &mut Vec<u8>, value: &str) {
+    write_u32(bytes, value.len());
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < len {
+            return Err("Unexpected end of the library archive".to_owned());
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| "Invalid text in the library archive".to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive() -> LibraryArchive {
+        LibraryArchive {
+            library_name: "ip_lib".to_owned(),
+            units: vec![
+                ArchivedUnit {
+                    name: "pkg".to_owned(),
+                    text: "package pkg is\nend package;".to_owned(),
+                },
+                ArchivedUnit {
+                    name: "ent".to_owned(),
+                    text: "entity ent is\nend entity;".to_owned(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let archive = archive();
+        assert_eq!(LibraryArchive::from_bytes(&archive.to_bytes()), Ok(archive));
+    }
+
+    #[test]
+    fn invalid_archives() {
+        let bytes = archive().to_bytes();
+        assert_eq!(
+            LibraryArchive::from_bytes(b"entity ent is"),
+            Err("Not a library archive".to_owned())
+        );
+        assert_eq!(
+            LibraryArchive::from_bytes(&bytes[..bytes.len() - 1]),
+            Err("Unexpected end of the library archive".to_owned())
+        );
+
+        let mut other_version = bytes.clone();
+        other_version[MAGIC.len()] = VERSION + 1;
+        assert_eq!(
+            LibraryArchive::from_bytes(&other_version),
+            Err(format!(
+                "Unsupported library archive version {}, expected {VERSION}",
+                VERSION + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn contents_of_all_units() {
+        assert_eq!(
+            archive().contents(),
+            "package pkg is\nend package;\n\nentity ent is\nend entity;\n\n"
+        );
+    }
+}
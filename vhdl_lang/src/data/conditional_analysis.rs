@@ -0,0 +1,621 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Conditional analysis of VHDL-2019, LRM 24.2
+//!
+//! The tool directives `` `if ``, `` `elsif ``, `` `else `` and `` `end if `` select the lines
+//! that are analyzed depending on the values of conditional analysis identifiers such as
+//! `TOOL_TYPE`. Directive lines and the lines of inactive branches are blanked before
+//! tokenizing such that positions in the rest of the file are unchanged.
+//! Each inactive branch is reported as a hint such that editors can dim it.
+
+use fnv::FnvHashMap;
+
+use super::contents::Contents;
+use super::diagnostic::{Diagnostic, DiagnosticHandler};
+use super::error_codes::ErrorCode;
+use super::source::{Position, Source, SrcPos};
+use super::standard::VHDLStandard;
+
+/// The values of the conditional analysis identifiers
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConditionalAnalysis {
+    // Identifiers are case insensitive and stored in upper case
+    symbols: FnvHashMap<String, String>,
+}
+
+impl Default for ConditionalAnalysis {
+    fn default() -> Self {
+        Self::new(VHDLStandard::default())
+    }
+}
+
+impl ConditionalAnalysis {
+    /// The predefined identifiers of LRM 24.2.4 for a revision of the language
+    pub fn new(standard: VHDLStandard) -> Self {
+        let mut analysis = ConditionalAnalysis {
+            symbols: FnvHashMap::default(),
+        };
+        analysis.define("VHDL_VERSION", standard.as_str());
+        analysis.define("TOOL_TYPE", "");
+        analysis.define("TOOL_VENDOR", "VHDL-LS");
+        analysis.define("TOOL_NAME", "vhdl_lang");
+        analysis.define("TOOL_EDITION", "");
+        analysis.define("TOOL_VERSION", env!("CARGO_PKG_VERSION"));
+        analysis
+    }
+
+    /// Define the value of an identifier, replacing any previous value
+    pub fn define(&mut self, name: &str, value: impl Into<String>) {
+        self.symbols.insert(name.to_ascii_uppercase(), value.into());
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.symbols
+            .get(&name.to_ascii_uppercase())
+            .map(String::as_str)
+    }
+
+    /// The contents with the directive lines and the lines of inactive branches blanked.
+    /// Returns None when the contents contain no conditional analysis directives.
+    pub fn filter(
+        &self,
+        source: &Source,
+        contents: &Contents,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Option<Contents> {
+        let has_directives = (0..contents.num_lines()).any(|lineno| {
+            contents
+                .get_line(lineno)
+                .is_some_and(|line| parse_directive(line).is_some())
+        });
+        if !has_directives {
+            return None;
+        }
+
+        let line_pos = |lineno: usize, text: &str, start: usize| {
+            source.pos(
+                Position::new(lineno as u32, utf16_len(&text[..start]) as u32),
+                Position::new(lineno as u32, utf16_len(text.trim_end()) as u32),
+            )
+        };
+
+        let mut filtered = String::new();
+        let mut branches: Vec<Branch> = Vec::new();
+        // The first line of the current run of inactive lines
+        let mut inactive_start: Option<usize> = None;
+        let mut last_line = String::new();
+
+        for lineno in 0..contents.num_lines() {
+            let Some(line) = contents.get_line(lineno) else {
+                continue;
+            };
+            let text = line.trim_end_matches('\n');
+            let is_active = branches.last().is_none_or(|branch| branch.active);
+
+            let Some(directive) = parse_directive(text) else {
+                if is_active {
+                    filtered.push_str(line);
+                } else {
+                    inactive_start.get_or_insert(lineno);
+                    last_line = text.to_owned();
+                    if line.ends_with('\n') {
+                        filtered.push('\n');
+                    }
+                }
+                continue;
+            };
+
+            if let Some(start) = inactive_start.take() {
+                diagnostics.push(inactive_branch(source, start, lineno - 1, &last_line));
+            }
+            if line.ends_with('\n') {
+                filtered.push('\n');
+            }
+
+            let pos = line_pos(lineno, text, text.len() - text.trim_start().len());
+
+            match directive {
+                Directive::If(condition) => {
+                    let active = is_active && self.condition(condition, &pos, diagnostics);
+                    branches.push(Branch {
+                        pos,
+                        enclosing_active: is_active,
+                        taken: active,
+                        active,
+                        has_else: false,
+                    });
+                }
+                Directive::Elsif(condition) => {
+                    let Some(branch) = branches.last_mut() else {
+                        diagnostics.push(Diagnostic::error(pos, "`elsif without a preceding `if"));
+                        continue;
+                    };
+                    if branch.has_else {
+                        diagnostics.push(
+                            Diagnostic::error(pos, "`elsif after `else")
+                                .related(&branch.pos, "The `if is given here"),
+                        );
+                        branch.active = false;
+                    } else if branch.enclosing_active && !branch.taken {
+                        branch.active = self.condition(condition, &pos, diagnostics);
+                        branch.taken = branch.active;
+                    } else {
+                        branch.active = false;
+                    }
+                }
+                Directive::Else => {
+                    let Some(branch) = branches.last_mut() else {
+                        diagnostics.push(Diagnostic::error(pos, "`else without a preceding `if"));
+                        continue;
+                    };
+                    if branch.has_else {
+                        diagnostics.push(
+                            Diagnostic::error(pos, "Duplicate `else")
+                                .related(&branch.pos, "The `if is given here"),
+                        );
+                    }
+                    branch.active = branch.enclosing_active && !branch.taken;
+                    branch.taken = true;
+                    branch.has_else = true;
+                }
+                Directive::End => {
+                    if branches.pop().is_none() {
+                        diagnostics.push(Diagnostic::error(pos, "`end without a preceding `if"));
+                    }
+                }
+                Directive::Warning(message) => {
+                    if is_active {
+                        diagnostics.push(Diagnostic::warning(pos, message));
+                    }
+                }
+                Directive::Error(message) => {
+                    if is_active {
+                        diagnostics.push(Diagnostic::error(pos, message));
+                    }
+                }
+                Directive::Invalid => {
+                    diagnostics.push(Diagnostic::error(
+                        pos,
+                        "Invalid conditional analysis directive",
+                    ));
+                }
+            }
+        }
+
+        if let Some(start) = inactive_start {
+            diagnostics.push(inactive_branch(
+                source,
+                start,
+                contents.num_lines() - 1,
+                &last_line,
+            ));
+        }
+        for branch in branches {
+            diagnostics.push(Diagnostic::error(branch.pos, "`if without a matching `end"));
+        }
+
+        Some(Contents::from_str(&filtered))
+    }
+
+    /// The value of the condition of a directive, false when it cannot be evaluated
+    fn condition(
+        &self,
+        condition: &str,
+        pos: &SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> bool {
+        self.evaluate(condition).unwrap_or_else(|message| {
+            diagnostics.push(Diagnostic::error(pos, message));
+            false
+        })
+    }
+
+    /// Evaluate a conditional analysis expression, LRM 24.2.2
+    fn evaluate(&self, condition: &str) -> Result<bool, String> {
+        let tokens = tokenize(condition).ok_or_else(invalid_expression)?;
+        let mut parser = ExpressionParser {
+            analysis: self,
+            tokens: &tokens,
+            idx: 0,
+        };
+        let value = parser.expression()?;
+        if parser.idx == tokens.len() {
+            Ok(value)
+        } else {
+            Err(invalid_expression())
+        }
+    }
+}
+
+struct Branch {
+    // The position of the `if directive
+    pos: SrcPos,
+    enclosing_active: bool,
+    // True when a previous or the current branch is selected
+    taken: bool,
+    active: bool,
+    has_else: bool,
+}
+
+enum Directive<'a> {
+    If(&'a str),
+    Elsif(&'a str),
+    Else,
+    End,
+    Warning(String),
+    Error(String),
+    Invalid,
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(|chr| chr.len_utf16()).sum()
+}
+
+fn inactive_branch(source: &Source, start: usize, end: usize, last_line: &str) -> Diagnostic {
+    Diagnostic::hint(
+        source.pos(
+            Position::new(start as u32, 0),
+            Position::new(end as u32, utf16_len(last_line.trim_end()) as u32),
+        ),
+        "Inactive branch of conditional analysis",
+    )
+    .with_code(ErrorCode::InactiveCode)
+}
+
+fn invalid_expression() -> String {
+    "Invalid conditional analysis expression".to_owned()
+}
+
+/// The conditional analysis directive of a line, other tool directives such as
+/// `` `protect `` are not handled
+fn parse_directive(text: &str) -> Option<Directive<'_>> {
+    let rest = text.trim_start().strip_prefix('`')?.trim_start();
+    let keyword_len = rest
+        .find(|chr: char| !chr.is_ascii_alphanumeric() && chr != '_')
+        .unwrap_or(rest.len());
+    let (keyword, rest) = rest.split_at(keyword_len);
+    let rest = strip_comment(rest).trim();
+
+    let directive = match keyword.to_ascii_lowercase().as_str() {
+        "if" | "elsif" => {
+            let Some(condition) = strip_suffix_keyword(rest, "then") else {
+                return Some(Directive::Invalid);
+            };
+            if keyword.eq_ignore_ascii_case("if") {
+                Directive::If(condition)
+            } else {
+                Directive::Elsif(condition)
+            }
+        }
+        "else" if rest.is_empty() => Directive::Else,
+        "end" if rest.is_empty() || rest.eq_ignore_ascii_case("if") => Directive::End,
+        "warning" | "error" => {
+            let message = match tokenize(rest).as_deref() {
+                Some([Token::Str(message)]) => message.clone(),
+                _ => return Some(Directive::Invalid),
+            };
+            if keyword.eq_ignore_ascii_case("warning") {
+                Directive::Warning(message)
+            } else {
+                Directive::Error(message)
+            }
+        }
+        "else" | "end" => Directive::Invalid,
+        _ => return None,
+    };
+    Some(directive)
+}
+
+fn strip_comment(text: &str) -> &str {
+    // A comment cannot start within a string literal
+    let mut in_string = false;
+    for (idx, chr) in text.char_indices() {
+        if chr == '"' {
+            in_string = !in_string;
+        } else if !in_string && text[idx..].starts_with("--") {
+            return &text[..idx];
+        }
+    }
+    text
+}
+
+fn strip_suffix_keyword<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+    let split = text.len().checked_sub(keyword.len())?;
+    let (condition, suffix) = (text.get(..split)?, &text[split..]);
+    if suffix.eq_ignore_ascii_case(keyword) && condition.ends_with([' ', '\t', ')']) {
+        Some(condition.trim_end())
+    } else {
+        None
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum Token {
+    Identifier(String),
+    Str(String),
+    LeftPar,
+    RightPar,
+    Relation(&'static str),
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(chr) = chars.next() {
+        let token = match chr {
+            ' ' | '\t' | '\r' => continue,
+            '(' => Token::LeftPar,
+            ')' => Token::RightPar,
+            '=' => Token::Relation("="),
+            '/' if chars.next_if_eq(&'=').is_some() => Token::Relation("/="),
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Relation("<="),
+            '<' => Token::Relation("<"),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Relation(">="),
+            '>' => Token::Relation(">"),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        // A doubled quotation mark represents a single one
+                        '"' if chars.next_if_eq(&'"').is_some() => value.push('"'),
+                        '"' => break,
+                        chr => value.push(chr),
+                    }
+                }
+                Token::Str(value)
+            }
+            chr if chr.is_ascii_alphabetic() => {
+                let mut name = String::from(chr);
+                while let Some(chr) =
+                    chars.next_if(|chr| chr.is_ascii_alphanumeric() || *chr == '_')
+                {
+                    name.push(chr);
+                }
+                Token::Identifier(name)
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+struct ExpressionParser<'a> {
+    analysis: &'a ConditionalAnalysis,
+    tokens: &'a [Token],
+    idx: usize,
+}
+
+impl<'a> ExpressionParser<'a> {
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.idx)?;
+        self.idx += 1;
+        Some(token)
+    }
+
+    fn operator(&self) -> Option<String> {
+        match self.tokens.get(self.idx)? {
+            Token::Identifier(name) => {
+                let name = name.to_ascii_lowercase();
+                matches!(name.as_str(), "and" | "or" | "xor" | "xnor").then_some(name)
+            }
+            _ => None,
+        }
+    }
+
+    /// A sequence of relations combined by the same logical operator
+    fn expression(&mut self) -> Result<bool, String> {
+        let mut value = self.relation()?;
+        let Some(operator) = self.operator() else {
+            return Ok(value);
+        };
+        while let Some(next) = self.operator() {
+            if next != operator {
+                return Err(invalid_expression());
+            }
+            self.idx += 1;
+            let rhs = self.relation()?;
+            value = match operator.as_str() {
+                "and" => value && rhs,
+                "or" => value || rhs,
+                "xor" => value != rhs,
+                _ => value == rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    fn parenthesized(&mut self) -> Result<bool, String> {
+        if self.next() != Some(&Token::LeftPar) {
+            return Err(invalid_expression());
+        }
+        let value = self.expression()?;
+        if self.next() != Some(&Token::RightPar) {
+            return Err(invalid_expression());
+        }
+        Ok(value)
+    }
+
+    fn relation(&mut self) -> Result<bool, String> {
+        match self.tokens.get(self.idx) {
+            Some(Token::LeftPar) => self.parenthesized(),
+            Some(Token::Identifier(name)) if name.eq_ignore_ascii_case("not") => {
+                self.idx += 1;
+                Ok(!self.parenthesized()?)
+            }
+            Some(Token::Identifier(name)) => {
+                self.idx += 1;
+                let (Some(Token::Relation(relation)), Some(Token::Str(rhs))) =
+                    (self.next(), self.next())
+                else {
+                    return Err(invalid_expression());
+                };
+                let lhs = self.analysis.value(name).ok_or_else(|| {
+                    format!("No conditional analysis identifier '{name}' is defined")
+                })?;
+                Ok(match *relation {
+                    "=" => lhs == rhs,
+                    "/=" => lhs != rhs,
+                    "<" => lhs < rhs.as_str(),
+                    "<=" => lhs <= rhs.as_str(),
+                    ">" => lhs > rhs.as_str(),
+                    _ => lhs >= rhs.as_str(),
+                })
+            }
+            _ => Err(invalid_expression()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn filter(
+        analysis: &ConditionalAnalysis,
+        source: &Source,
+    ) -> (Option<String>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let contents = analysis.filter(source, &source.contents(), &mut diagnostics);
+        let text = contents.map(|contents| contents.text(contents.range()));
+        (text, diagnostics)
+    }
+
+    fn source(code: &str) -> Source {
+        Source::inline(Path::new("file.vhd"), code)
+    }
+
+    fn pos(source: &Source, line: u32, start: u32, end_line: u32, end: u32) -> SrcPos {
+        source.pos(Position::new(line, start), Position::new(end_line, end))
+    }
+
+    #[test]
+    fn blanks_inactive_branches_keeping_line_numbers() {
+        let mut analysis = ConditionalAnalysis::new(VHDLStandard::VHDL2019);
+        analysis.define("tool_type", "SIMULATION");
+
+        let source = source(
+            "\
+`if TOOL_TYPE = \"SYNTHESIS\" then
+constant a : natural := 0;
+`elsif TOOL_TYPE = \"SIMULATION\" then -- comment
+constant b : natural := 1;
+`else
+constant c : natural := 2;
+constant d : natural := 3;
+`end if
+",
+        );
+        let (text, diagnostics) = filter(&analysis, &source);
+        assert_eq!(
+            text.as_deref(),
+            Some("\n\n\nconstant b : natural := 1;\n\n\n\n\n")
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::hint(
+                    pos(&source, 1, 0, 1, 26),
+                    "Inactive branch of conditional analysis"
+                )
+                .with_code(ErrorCode::InactiveCode),
+                Diagnostic::hint(
+                    pos(&source, 5, 0, 6, 26),
+                    "Inactive branch of conditional analysis"
+                )
+                .with_code(ErrorCode::InactiveCode),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_directives_within_inactive_branches_are_not_evaluated() {
+        let analysis = ConditionalAnalysis::new(VHDLStandard::VHDL2019);
+        let source = source(
+            "\
+`if VHDL_VERSION < \"2019\" then
+`if UNDEFINED = \"1\" then
+`warning \"not shown\"
+`end
+`else
+`warning \"Built with VHDL-2019\"
+`end if
+",
+        );
+        let (text, diagnostics) = filter(&analysis, &source);
+        assert_eq!(text.as_deref(), Some("\n\n\n\n\n\n\n"));
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::warning(
+                pos(&source, 5, 0, 5, 31),
+                "Built with VHDL-2019"
+            )]
+        );
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        let mut analysis = ConditionalAnalysis::new(VHDLStandard::VHDL2008);
+        analysis.define("MODE", "fast");
+
+        assert_eq!(analysis.evaluate("VHDL_VERSION = \"2008\""), Ok(true));
+        assert_eq!(analysis.evaluate("mode /= \"fast\""), Ok(false));
+        assert_eq!(
+            analysis.evaluate("MODE = \"slow\" or not (VHDL_VERSION >= \"2019\")"),
+            Ok(true)
+        );
+        assert_eq!(
+            analysis.evaluate("(MODE = \"fast\" xor MODE = \"fast\") xnor MODE = \"slow\""),
+            Ok(true)
+        );
+        assert_eq!(
+            analysis.evaluate("MODE = \"fast\" and MODE = \"fast\" or MODE = \"slow\""),
+            Err("Invalid conditional analysis expression".to_owned())
+        );
+        assert_eq!(
+            analysis.evaluate("OTHER = \"1\""),
+            Err("No conditional analysis identifier 'OTHER' is defined".to_owned())
+        );
+    }
+
+    #[test]
+    fn unbalanced_directives() {
+        let analysis = ConditionalAnalysis::default();
+        let source = source(
+            "\
+`end if
+`if TOOL_NAME = \"vhdl_lang\" then
+`else
+`else
+",
+        );
+        let (_, diagnostics) = filter(&analysis, &source);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error(pos(&source, 0, 0, 0, 7), "`end without a preceding `if"),
+                Diagnostic::error(pos(&source, 3, 0, 3, 5), "Duplicate `else")
+                    .related(pos(&source, 1, 0, 1, 32), "The `if is given here"),
+                Diagnostic::error(pos(&source, 1, 0, 1, 32), "`if without a matching `end"),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_tool_directives_are_not_filtered() {
+        let analysis = ConditionalAnalysis::default();
+        assert_eq!(
+            filter(
+                &analysis,
+                &source("`protect begin\nentity ent is\nend entity;\n")
+            ),
+            (None, vec![])
+        );
+    }
+}
@@ -4,7 +4,7 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::SrcPos;
+use super::{ErrorCode, SrcPos};
 use std::convert::{AsRef, Into};
 
 #[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
@@ -15,6 +15,13 @@ pub enum Severity {
     Error,
 }
 
+/// A suggested replacement of the source text at a position that resolves a diagnostic
+#[derive(PartialEq, Debug, Clone, Eq, Hash)]
+pub struct Fix {
+    pub pos: SrcPos,
+    pub replacement: String,
+}
+
 #[must_use]
 #[derive(PartialEq, Debug, Clone, Eq, Hash)]
 pub struct Diagnostic {
@@ -22,6 +29,8 @@ pub struct Diagnostic {
     pub message: String,
     pub severity: Severity,
     pub related: Vec<(SrcPos, String)>,
+    pub code: Option<ErrorCode>,
+    pub fixes: Vec<Fix>,
 }
 
 impl Diagnostic {
@@ -31,6 +40,8 @@ impl Diagnostic {
             message: msg.into(),
             severity,
             related: vec![],
+            code: None,
+            fixes: vec![],
         }
     }
 
@@ -56,6 +67,8 @@ impl Diagnostic {
             pos: self.pos,
             severity: self.severity,
             related: vec![],
+            code: self.code,
+            fixes: self.fixes,
         }
     }
 
@@ -65,6 +78,22 @@ impl Diagnostic {
         diagnostic
     }
 
+    pub fn with_code(self, code: ErrorCode) -> Diagnostic {
+        Diagnostic {
+            code: Some(code),
+            ..self
+        }
+    }
+
+    pub fn with_fix(self, item: impl AsRef<SrcPos>, replacement: impl Into<String>) -> Diagnostic {
+        let mut diagnostic = self;
+        diagnostic.fixes.push(Fix {
+            pos: item.as_ref().to_owned(),
+            replacement: replacement.into(),
+        });
+        diagnostic
+    }
+
     pub fn add_related(&mut self, item: impl AsRef<SrcPos>, message: impl Into<String>) {
         self.related
             .push((item.as_ref().to_owned(), message.into()));
@@ -95,7 +124,15 @@ impl Diagnostic {
             Severity::Info => &"info",
             Severity::Hint => &"hint",
         };
-        result.push_str(&self.pos.show(&format!("{}: {}", severity, self.message)));
+        if let Some(code) = self.code {
+            result.push_str(
+                &self
+                    .pos
+                    .show(&format!("{}[{}]: {}", severity, code, self.message)),
+            );
+        } else {
+            result.push_str(&self.pos.show(&format!("{}: {}", severity, self.message)));
+        }
         result
     }
 }
@@ -195,6 +232,25 @@ error: Greetings
         );
     }
 
+    #[test]
+    fn show_code() {
+        let code = Code::new_with_file_name(Path::new("{unknown file}"), "hello\nworld\nline\n");
+        assert_eq!(
+            Diagnostic::warning(code.s1("world"), "Greetings")
+                .with_code(ErrorCode::DuplicateSensitivity)
+                .show(),
+            "\
+warning[duplicate_sensitivity]: Greetings
+  --> {unknown file}:2
+   |
+1  |  hello
+2 --> world
+   |  ~~~~~
+3  |  line
+"
+        );
+    }
+
     #[test]
     fn show_related() {
         let code = Code::new_with_file_name(Path::new("{unknown file}"), "hello\nworld\nline\n");
@@ -0,0 +1,286 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::Severity;
+
+/// Identifies a class of diagnostics whose severity can be configured
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
+pub enum ErrorCode {
+    /// A waveform with more than one element relies on after clauses that synthesis ignores
+    AfterClause,
+    /// An instance is bound by default to an entity with more than one architecture
+    AmbiguousDefaultBinding,
+    /// A signal depends on itself through concurrent assignments and combinational processes
+    CombinationalLoop,
+    /// A string choice with '-' of an ordinary case statement only matches the '-' value
+    DontCareChoice,
+    /// The same name occurs more than once in a sensitivity list
+    DuplicateSensitivity,
+    /// A file is declared in a unit that is synthesized
+    FileIo,
+    /// A pure function references an object declared outside of it or calls an impure function
+    FunctionPurity,
+    /// The lines of a branch of a conditional analysis directive that is not selected
+    InactiveCode,
+    /// A signal read by a combinational process is missing from its sensitivity list
+    IncompleteSensitivity,
+    /// A signal is not assigned on every path through a combinational process
+    LatchInference,
+    /// A construct of a later revision of the standard than the configured one
+    LaterRevision,
+    /// The execution of a function can reach the end of its body without a return statement
+    MissingReturn,
+    /// A name in a sensitivity list does not denote a signal
+    NonSignalSensitivity,
+    /// A range with static bounds such as `7 downto 8` is null
+    NullRange,
+    /// A name in a sensitivity list is covered by a prefix also in the list
+    RedundantSensitivity,
+    /// A shared variable is declared in a unit that is synthesized
+    SharedVariable,
+    /// A shared variable is not of a protected type
+    SharedVariableType,
+    /// A signal declaration has an initial value
+    SignalInitialValue,
+    /// The direction of a slice differs from the direction of its prefix
+    SliceDirection,
+    /// A signal in the sensitivity list of a combinational process is never read by it
+    SuperfluousSensitivity,
+    /// A wait statement has a timeout clause
+    TimedWait,
+    /// A `-- fmt: off` or `-- fmt: on` directive comment without its counterpart
+    UnbalancedFormatDirective,
+    /// A signal of an architecture is read but never assigned
+    UnassignedSignal,
+    /// A testbench contains no way of terminating the simulation
+    UnterminatedTestbench,
+    /// A parameter of a subprogram body is never referenced within it
+    UnusedParameter,
+    /// A signal of an architecture is assigned but its value is never read
+    WriteOnlySignal,
+    /// A variable is assigned but its value is never read
+    WriteOnlyVariable,
+}
+
+impl ErrorCode {
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::AfterClause,
+        ErrorCode::AmbiguousDefaultBinding,
+        ErrorCode::CombinationalLoop,
+        ErrorCode::DontCareChoice,
+        ErrorCode::DuplicateSensitivity,
+        ErrorCode::FileIo,
+        ErrorCode::FunctionPurity,
+        ErrorCode::InactiveCode,
+        ErrorCode::IncompleteSensitivity,
+        ErrorCode::LatchInference,
+        ErrorCode::LaterRevision,
+        ErrorCode::MissingReturn,
+        ErrorCode::NonSignalSensitivity,
+        ErrorCode::NullRange,
+        ErrorCode::RedundantSensitivity,
+        ErrorCode::SharedVariable,
+        ErrorCode::SharedVariableType,
+        ErrorCode::SignalInitialValue,
+        ErrorCode::SliceDirection,
+        ErrorCode::SuperfluousSensitivity,
+        ErrorCode::TimedWait,
+        ErrorCode::UnbalancedFormatDirective,
+        ErrorCode::UnassignedSignal,
+        ErrorCode::UnterminatedTestbench,
+        ErrorCode::UnusedParameter,
+        ErrorCode::WriteOnlySignal,
+        ErrorCode::WriteOnlyVariable,
+    ];
+
+    /// The name used for the code in configuration files and diagnostics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::AfterClause => "after_clause",
+            ErrorCode::AmbiguousDefaultBinding => "ambiguous_default_binding",
+            ErrorCode::CombinationalLoop => "combinational_loop",
+            ErrorCode::DontCareChoice => "dont_care_choice",
+            ErrorCode::DuplicateSensitivity => "duplicate_sensitivity",
+            ErrorCode::FileIo => "file_io",
+            ErrorCode::FunctionPurity => "function_purity",
+            ErrorCode::InactiveCode => "inactive_code",
+            ErrorCode::IncompleteSensitivity => "incomplete_sensitivity",
+            ErrorCode::LatchInference => "latch_inference",
+            ErrorCode::LaterRevision => "later_revision",
+            ErrorCode::MissingReturn => "missing_return",
+            ErrorCode::NonSignalSensitivity => "non_signal_sensitivity",
+            ErrorCode::NullRange => "null_range",
+            ErrorCode::RedundantSensitivity => "redundant_sensitivity",
+            ErrorCode::SharedVariable => "shared_variable",
+            ErrorCode::SharedVariableType => "shared_variable_type",
+            ErrorCode::SignalInitialValue => "signal_initial_value",
+            ErrorCode::SliceDirection => "slice_direction",
+            ErrorCode::SuperfluousSensitivity => "superfluous_sensitivity",
+            ErrorCode::TimedWait => "timed_wait",
+            ErrorCode::UnbalancedFormatDirective => "unbalanced_format_directive",
+            ErrorCode::UnassignedSignal => "unassigned_signal",
+            ErrorCode::UnterminatedTestbench => "unterminated_testbench",
+            ErrorCode::UnusedParameter => "unused_parameter",
+            ErrorCode::WriteOnlySignal => "write_only_signal",
+            ErrorCode::WriteOnlyVariable => "write_only_variable",
+        }
+    }
+
+    /// Lints that are only performed when a severity is configured for them
+    pub fn is_opt_in(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::AfterClause
+                | ErrorCode::AmbiguousDefaultBinding
+                | ErrorCode::CombinationalLoop
+                | ErrorCode::FileIo
+                | ErrorCode::IncompleteSensitivity
+                | ErrorCode::SharedVariable
+                | ErrorCode::SignalInitialValue
+                | ErrorCode::SuperfluousSensitivity
+                | ErrorCode::TimedWait
+                | ErrorCode::UnassignedSignal
+                | ErrorCode::UnusedParameter
+                | ErrorCode::WriteOnlySignal
+                | ErrorCode::WriteOnlyVariable
+        )
+    }
+
+    /// Constructs that synthesis tools commonly do not support, enabled by the synthesis profile
+    pub fn is_synthesis_restriction(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::AfterClause
+                | ErrorCode::FileIo
+                | ErrorCode::SharedVariable
+                | ErrorCode::SignalInitialValue
+                | ErrorCode::TimedWait
+        )
+    }
+
+    /// Rules of the LRM whose violations are commonly accepted by vendor tools
+    pub fn is_vendor_extension(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::LaterRevision | ErrorCode::SharedVariableType
+        )
+    }
+
+    /// An extended explanation in Markdown of what the code means and how to fix it
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            ErrorCode::AfterClause => include_str!("explanations/after_clause.md"),
+            ErrorCode::AmbiguousDefaultBinding => {
+                include_str!("explanations/ambiguous_default_binding.md")
+            }
+            ErrorCode::CombinationalLoop => include_str!("explanations/combinational_loop.md"),
+            ErrorCode::DontCareChoice => include_str!("explanations/dont_care_choice.md"),
+            ErrorCode::DuplicateSensitivity => {
+                include_str!("explanations/duplicate_sensitivity.md")
+            }
+            ErrorCode::FileIo => include_str!("explanations/file_io.md"),
+            ErrorCode::FunctionPurity => include_str!("explanations/function_purity.md"),
+            ErrorCode::InactiveCode => include_str!("explanations/inactive_code.md"),
+            ErrorCode::IncompleteSensitivity => {
+                include_str!("explanations/incomplete_sensitivity.md")
+            }
+            ErrorCode::LatchInference => include_str!("explanations/latch_inference.md"),
+            ErrorCode::LaterRevision => include_str!("explanations/later_revision.md"),
+            ErrorCode::MissingReturn => include_str!("explanations/missing_return.md"),
+            ErrorCode::NonSignalSensitivity => {
+                include_str!("explanations/non_signal_sensitivity.md")
+            }
+            ErrorCode::NullRange => include_str!("explanations/null_range.md"),
+            ErrorCode::RedundantSensitivity => {
+                include_str!("explanations/redundant_sensitivity.md")
+            }
+            ErrorCode::SharedVariable => include_str!("explanations/shared_variable.md"),
+            ErrorCode::SharedVariableType => {
+                include_str!("explanations/shared_variable_type.md")
+            }
+            ErrorCode::SignalInitialValue => {
+                include_str!("explanations/signal_initial_value.md")
+            }
+            ErrorCode::SliceDirection => include_str!("explanations/slice_direction.md"),
+            ErrorCode::SuperfluousSensitivity => {
+                include_str!("explanations/superfluous_sensitivity.md")
+            }
+            ErrorCode::TimedWait => include_str!("explanations/timed_wait.md"),
+            ErrorCode::UnbalancedFormatDirective => {
+                include_str!("explanations/unbalanced_format_directive.md")
+            }
+            ErrorCode::UnassignedSignal => include_str!("explanations/unassigned_signal.md"),
+            ErrorCode::UnterminatedTestbench => {
+                include_str!("explanations/unterminated_testbench.md")
+            }
+            ErrorCode::UnusedParameter => include_str!("explanations/unused_parameter.md"),
+            ErrorCode::WriteOnlySignal => include_str!("explanations/write_only_signal.md"),
+            ErrorCode::WriteOnlyVariable => include_str!("explanations/write_only_variable.md"),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ErrorCode> {
+        Self::ALL.iter().copied().find(|code| code.as_str() == name)
+    }
+}
+
+/// How strictly the rules of the LRM are enforced
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Strictness {
+    /// All rules are enforced with the severity of their diagnostics
+    #[default]
+    Strict,
+    /// Violations of the rules that vendor tools commonly accept are reported as hints
+    Tolerant,
+}
+
+impl Strictness {
+    pub fn from_name(name: &str) -> Option<Strictness> {
+        match name {
+            "strict" => Some(Strictness::Strict),
+            "tolerant" => Some(Strictness::Tolerant),
+            _ => None,
+        }
+    }
+
+    /// The severity of a diagnostic with the code when no severity is configured for it
+    pub fn severity(&self, code: ErrorCode, severity: Severity) -> Severity {
+        match self {
+            Strictness::Tolerant if code.is_vendor_extension() => Severity::Hint,
+            _ => severity,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_round_trip() {
+        for code in ErrorCode::ALL.iter() {
+            assert_eq!(ErrorCode::from_name(code.as_str()), Some(*code));
+        }
+        assert_eq!(ErrorCode::from_name("missing"), None);
+    }
+
+    #[test]
+    fn explanations_are_titled_by_the_name() {
+        for code in ErrorCode::ALL.iter() {
+            assert!(
+                code.explanation()
+                    .starts_with(&format!("# {}\n\n", code.as_str())),
+                "{code}"
+            );
+        }
+    }
+}
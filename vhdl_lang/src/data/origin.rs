@@ -0,0 +1,212 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Origins of generated VHDL files
+//!
+//! A code generator may write a sidecar file named `<file>.origin` next to the generated
+//! file `<file>` to map its lines back to the template or specification they were generated
+//! from. Each line of the sidecar file that is neither empty nor a `#` comment has the form
+//!
+//! ```text
+//! <generated line> <original file>:<original line>
+//! ```
+//!
+//! Line numbers are one-based. The lines of the generated file from the generated line up to
+//! the next mapping originate from consecutive lines of the original file starting at the
+//! original line. Lines before the first mapping have no origin. A relative original file
+//! name is relative to the directory of the sidecar file.
+
+use super::source::{Position, Range};
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The location in an original file that a range of a generated file originates from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Origin {
+    pub file_name: PathBuf,
+    pub range: Range,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Mapping {
+    /// Zero-based line of the generated file
+    generated_line: u32,
+    file_name: PathBuf,
+    /// Zero-based line of the original file
+    original_line: u32,
+}
+
+/// Maps the lines of a generated file to their origin
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct OriginMap {
+    /// Sorted by the generated line
+    mappings: Vec<Mapping>,
+}
+
+impl OriginMap {
+    /// The name of the sidecar file of a generated file
+    pub fn sidecar_file_name(file_name: &Path) -> PathBuf {
+        let mut sidecar = OsString::from(file_name.as_os_str());
+        sidecar.push(".origin");
+        PathBuf::from(sidecar)
+    }
+
+    /// Read the sidecar file of a generated file, None if there is no sidecar file
+    pub fn read_for(file_name: &Path) -> io::Result<Option<OriginMap>> {
+        let sidecar = Self::sidecar_file_name(file_name);
+        if !sidecar.is_file() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&sidecar)?;
+        let directory = sidecar.parent().unwrap_or_else(|| Path::new(""));
+        Self::parse(directory, &contents)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Parse the contents of a sidecar file with relative file names resolved from the directory
+    pub fn parse(directory: &Path, contents: &str) -> Result<OriginMap, String> {
+        let mut mappings = Vec::new();
+
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                format!(
+                    "Invalid origin mapping on line {}, expected '<generated line> <file>:<line>'",
+                    idx + 1
+                )
+            };
+
+            let (generated_line, origin) =
+                line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+            let (file_name, original_line) = origin.trim().rsplit_once(':').ok_or_else(invalid)?;
+            if file_name.is_empty() {
+                return Err(invalid());
+            }
+
+            mappings.push(Mapping {
+                generated_line: parse_line_number(generated_line).ok_or_else(invalid)?,
+                file_name: directory.join(file_name),
+                original_line: parse_line_number(original_line).ok_or_else(invalid)?,
+            });
+        }
+
+        mappings.sort_by_key(|mapping| mapping.generated_line);
+        Ok(OriginMap { mappings })
+    }
+
+    /// The origin of a range of the generated file.
+    /// The range is mapped by the mapping of its start line and keeps its columns.
+    pub fn origin(&self, range: Range) -> Option<Origin> {
+        let idx = self
+            .mappings
+            .partition_point(|mapping| mapping.generated_line <= range.start.line);
+        let mapping = self.mappings.get(idx.checked_sub(1)?)?;
+        let map_line = |line: u32| mapping.original_line + (line - mapping.generated_line);
+
+        Some(Origin {
+            file_name: mapping.file_name.clone(),
+            range: Range::new(
+                Position::new(map_line(range.start.line), range.start.character),
+                Position::new(map_line(range.end.line), range.end.character),
+            ),
+        })
+    }
+}
+
+/// A one-based line number as a zero-based line
+fn parse_line_number(text: &str) -> Option<u32> {
+    text.trim().parse::<u32>().ok()?.checked_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start_line: u32, start_char: u32, end_line: u32, end_char: u32) -> Range {
+        Range::new(
+            Position::new(start_line, start_char),
+            Position::new(end_line, end_char),
+        )
+    }
+
+    #[test]
+    fn maps_lines_from_the_preceding_mapping() {
+        let map = OriginMap::parse(
+            Path::new("/gen"),
+            "
+# Generated by a template engine
+3 templates/top.vhd.tpl:10
+8 /abs/regs.yaml:2
+",
+        )
+        .unwrap();
+
+        assert_eq!(map.origin(range(0, 0, 0, 4)), None);
+        assert_eq!(
+            map.origin(range(2, 4, 3, 7)),
+            Some(Origin {
+                file_name: PathBuf::from("/gen/templates/top.vhd.tpl"),
+                range: range(9, 4, 10, 7),
+            })
+        );
+        assert_eq!(
+            map.origin(range(6, 0, 6, 1)),
+            Some(Origin {
+                file_name: PathBuf::from("/gen/templates/top.vhd.tpl"),
+                range: range(13, 0, 13, 1),
+            })
+        );
+        assert_eq!(
+            map.origin(range(9, 2, 9, 5)),
+            Some(Origin {
+                file_name: PathBuf::from("/abs/regs.yaml"),
+                range: range(3, 2, 3, 5),
+            })
+        );
+    }
+
+    #[test]
+    fn file_names_may_contain_colons() {
+        let map = OriginMap::parse(Path::new(""), "1 C:/spec/regs.yaml:4").unwrap();
+        assert_eq!(
+            map.origin(range(0, 0, 0, 1)).map(|origin| origin.file_name),
+            Some(PathBuf::from("C:/spec/regs.yaml"))
+        );
+    }
+
+    #[test]
+    fn invalid_mappings() {
+        for contents in ["3", "3 file.tpl", "x file.tpl:1", "3 file.tpl:0", "3 :4"] {
+            assert_eq!(
+                OriginMap::parse(Path::new(""), contents),
+                Err(
+                    "Invalid origin mapping on line 1, expected '<generated line> <file>:<line>'"
+                        .to_owned()
+                ),
+                "{contents}"
+            );
+        }
+    }
+
+    #[test]
+    fn reads_sidecar_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let file_name = tempdir.path().join("top.vhd");
+        assert_eq!(OriginMap::read_for(&file_name).unwrap(), None);
+
+        std::fs::write(tempdir.path().join("top.vhd.origin"), "1 top.vhd.tpl:1\n").unwrap();
+        let map = OriginMap::read_for(&file_name).unwrap().unwrap();
+        assert_eq!(
+            map.origin(range(0, 0, 0, 1)).map(|origin| origin.file_name),
+            Some(tempdir.path().join("top.vhd.tpl"))
+        );
+    }
+}
@@ -0,0 +1,200 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Lines of non-standard preprocessors that are filtered before tokenizing
+//!
+//! Code converted by other tools may contain lines such as `` `include "regs.vhd" `` or
+//! `` `define WIDTH 8 `` that are not VHDL. A line whose first non-whitespace characters are
+//! a configured prefix is either skipped or mapped to a replacement line such that the
+//! rest of the file is analyzed instead of cascading syntax errors.
+//! Filtered lines keep their line numbers so positions in the rest of the file are unchanged.
+
+use super::contents::Contents;
+use super::diagnostic::{Diagnostic, DiagnosticHandler};
+use super::source::{Position, Source};
+
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct PreprocessorFilter {
+    rules: Vec<PreprocessorRule>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct PreprocessorRule {
+    prefix: String,
+    // The line that replaces a matching line, None if the line is skipped
+    replacement: Option<String>,
+}
+
+impl PreprocessorFilter {
+    /// Skip lines starting with the prefix
+    pub fn skip(&mut self, prefix: impl Into<String>) {
+        self.add_rule(prefix.into(), None);
+    }
+
+    /// Replace lines starting with the prefix by another line
+    pub fn map(&mut self, prefix: impl Into<String>, replacement: impl Into<String>) {
+        self.add_rule(prefix.into(), Some(replacement.into()));
+    }
+
+    fn add_rule(&mut self, prefix: String, replacement: Option<String>) {
+        self.rules.retain(|rule| rule.prefix != prefix);
+        self.rules.push(PreprocessorRule {
+            prefix,
+            replacement,
+        });
+        // The longest prefix takes precedence
+        self.rules
+            .sort_by_key(|rule| std::cmp::Reverse(rule.prefix.len()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Append the rules of another filter, which take precedence for the same prefix
+    pub fn append(&mut self, other: &PreprocessorFilter) {
+        for rule in other.rules.iter() {
+            self.add_rule(rule.prefix.clone(), rule.replacement.clone());
+        }
+    }
+
+    /// The contents of a source file with the matching lines skipped or replaced.
+    /// Each filtered line is recorded as a hint such that the skipped spans are visible.
+    /// Returns None when no line matches.
+    pub fn filter(
+        &self,
+        source: &Source,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Option<Contents> {
+        if self.rules.is_empty() {
+            return None;
+        }
+
+        let contents = source.contents();
+        let mut filtered = String::new();
+        let mut any_filtered = false;
+
+        for lineno in 0..contents.num_lines() {
+            let Some(line) = contents.get_line(lineno) else {
+                continue;
+            };
+            let text = line.trim_end_matches('\n');
+            let trimmed = text.trim_start();
+
+            let Some(rule) = self
+                .rules
+                .iter()
+                .find(|rule| trimmed.starts_with(rule.prefix.as_str()))
+            else {
+                filtered.push_str(line);
+                continue;
+            };
+
+            let utf16_len = |text: &str| text.chars().map(|chr| chr.len_utf16()).sum::<usize>();
+            let start = utf16_len(&text[..text.len() - trimmed.len()]);
+            let end = utf16_len(text.trim_end());
+            let pos = source.pos(
+                Position::new(lineno as u32, start as u32),
+                Position::new(lineno as u32, end as u32),
+            );
+
+            if let Some(ref replacement) = rule.replacement {
+                diagnostics.push(Diagnostic::hint(
+                    pos,
+                    format!("Preprocessor line replaced by '{replacement}'"),
+                ));
+                // The replacement keeps the indentation of the line
+                filtered.push_str(&text[..text.len() - trimmed.len()]);
+                filtered.push_str(replacement);
+            } else {
+                diagnostics.push(Diagnostic::hint(pos, "Preprocessor line skipped"));
+            }
+            if line.ends_with('\n') {
+                filtered.push('\n');
+            }
+            any_filtered = true;
+        }
+
+        if any_filtered {
+            Some(Contents::from_str(&filtered))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn filter(filter: &PreprocessorFilter, source: &Source) -> (Option<String>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let contents = filter.filter(source, &mut diagnostics);
+        let text = contents.map(|contents| contents.text(contents.range()));
+        (text, diagnostics)
+    }
+
+    fn source(code: &str) -> Source {
+        Source::inline(Path::new("file.vhd"), code)
+    }
+
+    #[test]
+    fn skips_and_maps_lines_keeping_line_numbers() {
+        let mut preprocessor = PreprocessorFilter::default();
+        preprocessor.skip("`include");
+        preprocessor.map("`define SIM", "constant SIM : boolean := true;");
+
+        let source = source(
+            "\
+`include \"regs.vhd\"
+entity ent is
+end entity;
+  `define SIM
+",
+        );
+        let (text, diagnostics) = filter(&preprocessor, &source);
+        assert_eq!(
+            text.as_deref(),
+            Some("\nentity ent is\nend entity;\n  constant SIM : boolean := true;\n")
+        );
+
+        let pos = |line: u32, start: u32, end: u32| {
+            source.pos(Position::new(line, start), Position::new(line, end))
+        };
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::hint(pos(0, 0, 19), "Preprocessor line skipped"),
+                Diagnostic::hint(
+                    pos(3, 2, 13),
+                    "Preprocessor line replaced by 'constant SIM : boolean := true;'"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn longest_prefix_takes_precedence() {
+        let mut preprocessor = PreprocessorFilter::default();
+        preprocessor.map("`define SIM", "constant SIM : boolean := true;");
+        preprocessor.skip("`define");
+
+        let (text, _) = filter(&preprocessor, &source("`define SIM\n`define OTHER\n"));
+        assert_eq!(text.as_deref(), Some("constant SIM : boolean := true;\n\n"));
+    }
+
+    #[test]
+    fn unfiltered_source_is_not_copied() {
+        let mut preprocessor = PreprocessorFilter::default();
+        preprocessor.skip("`include");
+
+        assert_eq!(
+            filter(&preprocessor, &source("entity ent is\nend entity;\n")),
+            (None, vec![])
+        );
+    }
+}
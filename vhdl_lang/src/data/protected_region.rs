@@ -0,0 +1,203 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Regions of a source file that are protected from formatting and automatic fixes
+//!
+//! A region starts with a line containing only the comment `-- fmt: off` and ends with a
+//! line containing only the comment `-- fmt: on`. Both directive lines belong to the region.
+//! A region that is not ended extends to the end of the file.
+
+use super::diagnostic::{Diagnostic, DiagnosticHandler};
+use super::error_codes::ErrorCode;
+use super::source::{Position, Range, Source, SrcPos};
+
+const FMT_OFF: &str = "fmt: off";
+const FMT_ON: &str = "fmt: on";
+
+/// The protected regions of a source file as inclusive ranges of lines
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ProtectedRegions {
+    lines: Vec<(u32, u32)>,
+}
+
+impl ProtectedRegions {
+    /// Find the protected regions of a source file and warn about unbalanced directives
+    pub fn parse(source: &Source, diagnostics: &mut dyn DiagnosticHandler) -> ProtectedRegions {
+        let contents = source.contents();
+        let directive_pos = |line: usize, text: &str| {
+            let start = text.len() - text.trim_start().len();
+            source.pos(
+                Position::new(line as u32, start as u32),
+                Position::new(line as u32, text.trim_end().len() as u32),
+            )
+        };
+
+        let mut lines = Vec::new();
+        let mut off: Option<(u32, SrcPos)> = None;
+
+        for line in 0..contents.num_lines() {
+            let Some(text) = contents.get_line(line) else {
+                continue;
+            };
+            let Some(directive) = directive(text) else {
+                continue;
+            };
+            let pos = directive_pos(line, text);
+
+            if directive == FMT_OFF {
+                if let Some((_, ref previous)) = off {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            pos,
+                            format!("Repeated '-- {FMT_OFF}' before '-- {FMT_ON}'"),
+                        )
+                        .related(previous, format!("Previous '-- {FMT_OFF}'"))
+                        .with_code(ErrorCode::UnbalancedFormatDirective),
+                    );
+                } else {
+                    off = Some((line as u32, pos));
+                }
+            } else if let Some((start, _)) = off.take() {
+                lines.push((start, line as u32));
+            } else {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        pos,
+                        format!("'-- {FMT_ON}' is not preceded by '-- {FMT_OFF}'"),
+                    )
+                    .with_code(ErrorCode::UnbalancedFormatDirective),
+                );
+            }
+        }
+
+        if let Some((start, pos)) = off {
+            diagnostics.push(
+                Diagnostic::warning(
+                    pos,
+                    format!(
+                        "'-- {FMT_OFF}' is not followed by '-- {FMT_ON}', the rest of the file is protected"
+                    ),
+                )
+                .with_code(ErrorCode::UnbalancedFormatDirective),
+            );
+            lines.push((start, u32::MAX));
+        }
+
+        ProtectedRegions { lines }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// True if the range overlaps a protected region.
+    /// A range that ends at the start of a line does not overlap that line.
+    pub fn overlaps(&self, range: &Range) -> bool {
+        let start = range.start.line;
+        let end = if range.end.character == 0 && range.end.line > start {
+            range.end.line - 1
+        } else {
+            range.end.line
+        };
+        self.lines
+            .iter()
+            .any(|&(first, last)| start <= last && end >= first)
+    }
+}
+
+/// The directive of a line that contains only a `fmt: off` or `fmt: on` comment
+fn directive(text: &str) -> Option<&'static str> {
+    let comment = text.trim().strip_prefix("--")?.trim();
+    if comment == FMT_OFF {
+        Some(FMT_OFF)
+    } else if comment == FMT_ON {
+        Some(FMT_ON)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::*;
+
+    fn lines(start_line: u32, end_line: u32) -> Range {
+        Range::new(Position::new(start_line, 0), Position::new(end_line, 0))
+    }
+
+    #[test]
+    fn regions_between_directives() {
+        let code = Code::new(
+            "\
+constant a : natural := 0;
+-- fmt: off
+constant rom : rom_t := (
+  x\"00\", x\"01\",
+  x\"10\", x\"11\");
+  --   fmt: on
+constant b : natural := 0;
+",
+        );
+        let mut diagnostics = Vec::new();
+        let regions = ProtectedRegions::parse(code.source(), &mut diagnostics);
+        check_no_diagnostics(&diagnostics);
+
+        assert!(!regions.overlaps(&lines(0, 1)));
+        assert!(regions.overlaps(&lines(1, 2)));
+        assert!(regions.overlaps(&code.s1("x\"10\"").pos().range()));
+        assert!(regions.overlaps(&lines(5, 6)));
+        assert!(!regions.overlaps(&lines(6, 7)));
+    }
+
+    #[test]
+    fn unbalanced_directives() {
+        let code = Code::new(
+            "\
+-- fmt: on
+-- fmt: off
+constant a : natural := 0;
+-- fmt: off
+constant b : natural := 0;
+",
+        );
+        let mut diagnostics = Vec::new();
+        let regions = ProtectedRegions::parse(code.source(), &mut diagnostics);
+
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::warning(
+                    code.s1("-- fmt: on"),
+                    "'-- fmt: on' is not preceded by '-- fmt: off'",
+                )
+                .with_code(ErrorCode::UnbalancedFormatDirective),
+                Diagnostic::warning(
+                    code.s("-- fmt: off", 2),
+                    "Repeated '-- fmt: off' before '-- fmt: on'",
+                )
+                .related(code.s1("-- fmt: off"), "Previous '-- fmt: off'")
+                .with_code(ErrorCode::UnbalancedFormatDirective),
+                Diagnostic::warning(
+                    code.s1("-- fmt: off"),
+                    "'-- fmt: off' is not followed by '-- fmt: on', the rest of the file is protected",
+                )
+                .with_code(ErrorCode::UnbalancedFormatDirective),
+            ],
+        );
+        assert!(!regions.overlaps(&lines(0, 1)));
+        assert!(regions.overlaps(&code.s1("constant b").pos().range()));
+    }
+
+    #[test]
+    fn directives_must_be_on_a_line_of_their_own() {
+        let code = Code::new("constant a : natural := 0; -- fmt: off\n");
+        let mut diagnostics = Vec::new();
+        let regions = ProtectedRegions::parse(code.source(), &mut diagnostics);
+        check_no_diagnostics(&diagnostics);
+        assert!(regions.is_empty());
+    }
+}
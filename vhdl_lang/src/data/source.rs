@@ -167,6 +167,12 @@ impl Source {
         }
     }
 
+    /// True if the contents are shared with the other source, as they are by its clones,
+    /// such that a change of one is a change of the other
+    pub fn shares_contents(&self, other: &Source) -> bool {
+        Arc::ptr_eq(&self.source, &other.source)
+    }
+
     pub fn change(&self, range: Option<&Range>, content: &str) {
         let mut contents = self.source.contents.write();
         if let Some(range) = range {
@@ -177,6 +183,26 @@ impl Source {
     }
 }
 
+/// The absolute name of a file with symbolic links and junctions resolved and the case of
+/// the file system on case-insensitive file systems, such that a file reached through
+/// different paths has a single name. The folder is resolved for a file that does not exist,
+/// and the name is returned unchanged when the folder does not exist either.
+pub fn canonical_file_name(file_name: &Path) -> PathBuf {
+    if let Ok(canonical) = dunce::canonicalize(file_name) {
+        return canonical;
+    }
+
+    match (file_name.parent(), file_name.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+            match dunce::canonicalize(parent) {
+                Ok(parent) => parent.join(name),
+                Err(_) => file_name.to_owned(),
+            }
+        }
+        _ => file_name.to_owned(),
+    }
+}
+
 /// A lexical position (line, column) in a source.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug, Default)]
 pub struct Position {
@@ -589,6 +615,34 @@ mod tests {
         fun(CodeBuilder::new().code_from_source(Source::from_latin1_file(&file_name).unwrap()))
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn canonical_file_name_resolves_symbolic_links() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+        std::fs::create_dir(root.join("rtl")).unwrap();
+        std::fs::write(root.join("rtl").join("file.vhd"), "").unwrap();
+        std::os::unix::fs::symlink(root.join("rtl"), root.join("link")).unwrap();
+
+        assert_eq!(
+            canonical_file_name(&root.join("link").join("file.vhd")),
+            root.join("rtl").join("file.vhd")
+        );
+        assert_eq!(
+            canonical_file_name(&root.join("link").join("..").join("rtl").join("file.vhd")),
+            root.join("rtl").join("file.vhd")
+        );
+        // The folder of a file that does not exist is resolved
+        assert_eq!(
+            canonical_file_name(&root.join("link").join("new.vhd")),
+            root.join("rtl").join("new.vhd")
+        );
+        assert_eq!(
+            canonical_file_name(&root.join("missing").join("new.vhd")),
+            root.join("missing").join("new.vhd")
+        );
+    }
+
     #[test]
     fn code_context_pos_from_filename() {
         with_code_from_file("hello\nworld\n", |code: Code| {
@@ -0,0 +1,67 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+/// The revision of the VHDL language that designs are analyzed against
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum VHDLStandard {
+    VHDL1993,
+    VHDL2002,
+    #[default]
+    VHDL2008,
+    VHDL2019,
+}
+
+impl VHDLStandard {
+    pub const ALL: &'static [VHDLStandard] = &[
+        VHDLStandard::VHDL1993,
+        VHDLStandard::VHDL2002,
+        VHDLStandard::VHDL2008,
+        VHDLStandard::VHDL2019,
+    ];
+
+    /// The name used for the standard in configuration files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VHDLStandard::VHDL1993 => "1993",
+            VHDLStandard::VHDL2002 => "2002",
+            VHDLStandard::VHDL2008 => "2008",
+            VHDLStandard::VHDL2019 => "2019",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<VHDLStandard> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|standard| standard.as_str() == name)
+    }
+}
+
+impl std::fmt::Display for VHDLStandard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VHDL-{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_round_trip() {
+        for standard in VHDLStandard::ALL.iter() {
+            assert_eq!(VHDLStandard::from_name(standard.as_str()), Some(*standard));
+        }
+        assert_eq!(VHDLStandard::from_name("87"), None);
+    }
+
+    #[test]
+    fn standards_are_ordered_by_revision() {
+        assert!(VHDLStandard::VHDL1993 < VHDLStandard::VHDL2002);
+        assert!(VHDLStandard::VHDL2002 < VHDLStandard::VHDL2008);
+        assert!(VHDLStandard::VHDL2008 < VHDLStandard::VHDL2019);
+    }
+}
@@ -43,9 +43,23 @@ impl Symbol {
     }
 
     /// Returns the name of the symbol as a UTF-8 string.
+    ///
+    /// Extended identifiers include the surrounding backslashes but backslashes
+    /// within them are not escaped, use the `Display` implementation for the source form.
     pub fn name_utf8(&self) -> String {
         self.name.to_string()
     }
+
+    /// Returns true if the symbol is an extended identifier such as `\Weird Name!\`
+    pub fn is_extended(&self) -> bool {
+        self.name.bytes.first() == Some(&b'\\')
+    }
+
+    /// Returns true if the symbol is the basic identifier `name`.
+    /// The comparison is case-insensitive and never matches an extended identifier.
+    pub fn eq_basic(&self, name: &str) -> bool {
+        !self.is_extended() && self.name_utf8().eq_ignore_ascii_case(name)
+    }
 }
 
 impl PartialEq for Symbol {
@@ -56,11 +70,37 @@ impl PartialEq for Symbol {
 }
 
 impl std::fmt::Display for Symbol {
+    /// Formats the symbol as it is written in VHDL source,
+    /// backslashes within extended identifiers are doubled
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.name_utf8())
+        let name = self.name_utf8();
+        if self.is_extended() {
+            let inner = &name[1..name.len() - 1];
+            write!(f, "\\{}\\", inner.replace('\\', "\\\\"))
+        } else {
+            write!(f, "{name}")
+        }
     }
 }
 
+/// Returns the name of an extended identifier written in VHDL source such as `\a\\b\`
+/// in the form it is stored in a symbol, where the doubled backslashes are unescaped.
+/// Returns None if the text is not an extended identifier.
+pub fn unescape_extended_identifier(text: &str) -> Option<String> {
+    let inner = text.strip_prefix('\\')?.strip_suffix('\\')?;
+    let mut name = String::from('\\');
+    let mut chars = inner.chars();
+    while let Some(chr) = chars.next() {
+        if chr == '\\' && chars.next() != Some('\\') {
+            // A single backslash would end the identifier
+            return None;
+        }
+        name.push(chr);
+    }
+    name.push('\\');
+    Some(name)
+}
+
 impl std::hash::Hash for Symbol {
     fn hash<H: std::hash::Hasher>(&self, hasher: &mut H) {
         self.id.hash(hasher);
@@ -83,9 +123,15 @@ pub struct SymbolTable {
 }
 
 impl SymbolTable {
+    /// Inserts a basic or extended identifier as written in VHDL source
     pub fn insert_utf8(&self, name: &str) -> Symbol {
-        let name = Latin1String::from_utf8(name).unwrap();
-        self.insert(&name)
+        if let Some(extended) = unescape_extended_identifier(name) {
+            let name = Latin1String::from_utf8(&extended).unwrap();
+            self.insert_extended(&name)
+        } else {
+            let name = Latin1String::from_utf8(name).unwrap();
+            self.insert(&name)
+        }
     }
 
     #[cfg(test)]
@@ -214,6 +260,42 @@ mod tests {
         assert_eq!(sym2.name_utf8(), "heLLo");
     }
 
+    #[test]
+    fn extended_identifiers_are_escaped_when_displayed() {
+        let symtab = SymbolTable::default();
+        let sym = symtab.insert_extended_utf8("\\a\\b\\");
+        assert!(sym.is_extended());
+        assert_eq!(sym.name_utf8(), "\\a\\b\\");
+        assert_eq!(sym.to_string(), "\\a\\\\b\\");
+
+        let sym = symtab.insert_utf8("Basic");
+        assert!(!sym.is_extended());
+        assert_eq!(sym.to_string(), "Basic");
+    }
+
+    #[test]
+    fn insert_utf8_unescapes_extended_identifiers() {
+        let symtab = SymbolTable::default();
+        assert_eq!(
+            symtab.insert_utf8("\\a\\\\b\\"),
+            symtab.insert_extended_utf8("\\a\\b\\")
+        );
+        assert_ne!(symtab.insert_utf8("\\hello\\"), symtab.insert_utf8("hello"));
+        assert_ne!(
+            symtab.insert_utf8("\\hello\\"),
+            symtab.insert_utf8("\\HELLO\\")
+        );
+        assert_eq!(unescape_extended_identifier("\\a\\b\\"), None);
+        assert_eq!(unescape_extended_identifier("hello"), None);
+    }
+
+    #[test]
+    fn eq_basic_is_case_insensitive_for_basic_identifiers_only() {
+        let symtab = SymbolTable::default();
+        assert!(symtab.insert_utf8("TRUE").eq_basic("true"));
+        assert!(!symtab.insert_utf8("\\true\\").eq_basic("true"));
+    }
+
     #[test]
     fn extended_identifiers_symbols_are_case_sensitive() {
         let symtab = SymbolTable::default();
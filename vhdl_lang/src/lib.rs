@@ -17,11 +17,23 @@ mod data;
 mod project;
 mod syntax;
 
+pub use crate::analysis::{
+    classify_process, ActiveLevel, AnyEntKind, ArchitectureSelection, ClassifiedProcess,
+    ClockDomainCrossing, ClockDomainCrossingReport, ClockEdge, ClockedProcess, Connection,
+    ConnectivityReport, DataflowEdge, DataflowNode, DataflowNodeKind, Design, ExplainedEntity,
+    GenericDocumentation, OverloadCandidate, PortConnection, PortGrouping, PrettyEnt,
+    ProcessDataflow, ProcessKind, ProcessReset, ResetConvention, ResetStyle, ResolutionExplanation,
+    StateMachine, StateMachineReport, StateTransition, StaticRange, StaticValue, StructuralMatch,
+    StructuralPattern, UnusedPort, UnusedPortReport, UseClauseCandidate, VisibleName,
+    VisibleOrigin,
+};
 pub use crate::config::Config;
 pub use crate::data::{
-    Diagnostic, Latin1String, Message, MessageHandler, MessagePrinter, MessageType,
-    NullDiagnostics, NullMessages, Position, Range, Severity, Source, SrcPos,
+    canonical_file_name, ArchivedUnit, Diagnostic, ErrorCode, Fix, Latin1String, LibraryArchive,
+    Message, MessageHandler, MessagePrinter, MessageType, NullDiagnostics, NullMessages, Origin,
+    OriginMap, Position, ProtectedRegions, Range, Severity, Source, SrcPos, Strictness,
+    VHDLStandard, ARCHIVE_EXTENSION,
 };
 
-pub use crate::project::{Project, SourceFile};
+pub use crate::project::{FileSetChange, Project, SourceFile};
 pub use crate::syntax::{ParserResult, VHDLParser};
@@ -9,14 +9,20 @@
 // Track here: https://github.com/rust-lang/rust-clippy/issues/1981
 #![allow(clippy::ptr_arg)]
 
-use clap::Parser;
-use std::path::Path;
+use clap::{ArgGroup, Parser, ValueEnum};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use vhdl_lang::{Config, Diagnostic, MessagePrinter, NullMessages, Project};
+use vhdl_lang::{
+    Config, Diagnostic, ErrorCode, Fix, Latin1String, MessagePrinter, NullMessages, Position,
+    Project, ARCHIVE_EXTENSION,
+};
 
 /// Run vhdl analysis
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(group(ArgGroup::new("writes_output").args(["export_library", "connectivity", "dataflow", "unused_ports", "clock_domain_crossings", "fsm"])))]
+#[command(group(ArgGroup::new("report").args(["connectivity", "unused_ports", "clock_domain_crossings", "fsm"])))]
 struct Args {
     /// The number of threads to use. By default the maximum is selected based on process cores
     #[arg(short = 'p', long)]
@@ -31,8 +37,8 @@ struct Args {
     bench: bool,
 
     /// Config file in TOML format containing libraries and settings
-    #[arg(short, long)]
-    config: String,
+    #[arg(short, long, required_unless_present = "explain")]
+    config: Option<String>,
 
     /// Dump items that are not resolved into an unique reference
     /// This is used for development to test where the language server is blind
@@ -43,10 +49,86 @@ struct Args {
     /// This is used for development to test where the language server is blind
     #[arg(long)]
     count_unresolved: bool,
+
+    /// Analyze an architecture given as library.entity(architecture)
+    /// with the values of the generics given by --generic
+    #[arg(long)]
+    architecture: Option<String>,
+
+    /// The value of a generic as name=value when analyzing an architecture
+    #[arg(short = 'g', long = "generic", requires = "architecture")]
+    generics: Vec<String>,
+
+    /// The diagnostics profile of the config file to use, such as editor, commit or release
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Export the public interface of a library as an archive that other projects
+    /// list among the files of a library of the same name
+    #[arg(long)]
+    export_library: Option<String>,
+
+    /// The file name of the exported archive, by default the library name with a .vhdla extension,
+    /// or of the report, by default printed
+    #[arg(short, long, requires = "writes_output")]
+    output: Option<String>,
+
+    /// Report the signals connected to the ports of all instances within an architecture
+    /// given as library.entity(architecture)
+    #[arg(long)]
+    connectivity: Option<String>,
+
+    /// Report the ports of entities that are left open or tied to a constant at every instance
+    #[arg(long, default_value_t = false)]
+    unused_ports: bool,
+
+    /// Report the signals read by a register of another clock domain without a synchronizer
+    #[arg(long, default_value_t = false)]
+    clock_domain_crossings: bool,
+
+    /// Report the state machines of an enumeration type register with a case statement on it
+    #[arg(long, default_value_t = false)]
+    fsm: bool,
+
+    /// Export the dataflow of the process at a line given as file:line as a graph in the DOT language
+    #[arg(long)]
+    dataflow: Option<String>,
+
+    /// The format of the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Csv, requires = "report")]
+    format: ReportFormat,
+
+    /// Print the expressions matching a structural pattern such as `rising_edge($clk) and $rst = '0'`
+    /// where the names starting with `$` are capture variables that match any expression
+    #[arg(long)]
+    search: Option<String>,
+
+    /// Print how the expressions matching the pattern of --search are rewritten by a template
+    /// such as `$rst = '1'` where each capture variable is replaced by the text it matched
+    #[arg(long, requires = "search")]
+    replace: Option<String>,
+
+    /// Write the rewrites of --replace to the files in place instead of printing them
+    #[arg(long, default_value_t = false, requires = "replace")]
+    write: bool,
+
+    /// Print an extended explanation of a diagnostic code such as null_range
+    #[arg(long)]
+    explain: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ReportFormat {
+    Csv,
+    Html,
 }
 
 fn main() {
     let args = Args::parse();
+    if let Some(ref name) = args.explain {
+        explain(name);
+    }
+
     rayon::ThreadPoolBuilder::new()
         .num_threads(args.num_threads.unwrap_or(0))
         .build_global()
@@ -56,9 +138,16 @@ fn main() {
     let mut msg_printer = MessagePrinter::default();
     config.load_external_config(&mut msg_printer);
     config.append(
-        &Config::read_file_path(Path::new(&args.config)).expect("Failed to read config file"),
+        &Config::read_file_path(Path::new(args.config.as_ref().unwrap()))
+            .expect("Failed to read config file"),
         &mut msg_printer,
     );
+    if let Some(ref profile) = args.profile {
+        if let Err(message) = config.select_profile(profile) {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
 
     let start = SystemTime::now();
 
@@ -77,7 +166,110 @@ fn main() {
     let mut project = Project::from_config(&config, &mut msg_printer);
     let diagnostics = project.analyse();
     let duration = start.elapsed().unwrap() / iterations;
-    show_diagnostics(&diagnostics);
+
+    if let Some(ref architecture) = args.architecture {
+        match analyze_architecture(&project, architecture, &args.generics) {
+            Ok(diagnostics) => show_diagnostics(&diagnostics),
+            Err(message) => {
+                eprintln!("{message}");
+                std::process::exit(1);
+            }
+        }
+    } else {
+        show_diagnostics(&diagnostics);
+    }
+
+    if let Some(ref library_name) = args.export_library {
+        let output = args
+            .output
+            .clone()
+            .unwrap_or_else(|| format!("{library_name}.{ARCHIVE_EXTENSION}"));
+        let result = project.export_library(library_name).and_then(|archive| {
+            archive
+                .write(Path::new(&output))
+                .map_err(|err| format!("Failed to write {output}: {err}"))
+                .map(|_| archive.units.len())
+        });
+        match result {
+            Ok(num_units) => {
+                println!("Exported {num_units} units of library {library_name} to {output}")
+            }
+            Err(message) => {
+                eprintln!("{message}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(ref architecture) = args.connectivity {
+        let result = parse_architecture(architecture)
+            .and_then(|(library, entity, arch)| project.connectivity(library, entity, arch));
+        let result = result.and_then(|report| {
+            let contents = match args.format {
+                ReportFormat::Csv => report.to_csv(),
+                ReportFormat::Html => report.to_html(),
+            };
+            write_report(args.output.as_deref(), contents)
+        });
+        if let Err(message) = result {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
+
+    if args.unused_ports {
+        let report = project.unused_ports();
+        let contents = match args.format {
+            ReportFormat::Csv => report.to_csv(),
+            ReportFormat::Html => report.to_html(),
+        };
+        if let Err(message) = write_report(args.output.as_deref(), contents) {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
+
+    if args.clock_domain_crossings {
+        let report = project.clock_domain_crossings();
+        let contents = match args.format {
+            ReportFormat::Csv => report.to_csv(),
+            ReportFormat::Html => report.to_html(),
+        };
+        if let Err(message) = write_report(args.output.as_deref(), contents) {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
+
+    if args.fsm {
+        let report = project.state_machines();
+        let contents = match args.format {
+            ReportFormat::Csv => report.to_csv(),
+            ReportFormat::Html => report.to_html(),
+        };
+        if let Err(message) = write_report(args.output.as_deref(), contents) {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(ref location) = args.dataflow {
+        let result = process_dataflow(&project, location)
+            .and_then(|contents| write_report(args.output.as_deref(), contents));
+        if let Err(message) = result {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(ref pattern) = args.search {
+        if let Err(message) =
+            structural_search(&project, pattern, args.replace.as_deref(), args.write)
+        {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
 
     if args.perf || args.bench {
         let mut num_files = 0;
@@ -114,6 +306,162 @@ fn main() {
     std::process::exit(0);
 }
 
+/// Print the explanation of a diagnostic code and exit
+fn explain(name: &str) -> ! {
+    if let Some(code) = ErrorCode::from_name(name) {
+        print!("{}", code.explanation());
+        std::process::exit(0);
+    }
+
+    let names: Vec<_> = ErrorCode::ALL.iter().map(|code| code.as_str()).collect();
+    eprintln!(
+        "Unknown diagnostic code '{name}', expected one of: {}",
+        names.join(", ")
+    );
+    std::process::exit(1);
+}
+
+/// Analyze an architecture given as library.entity(architecture) with generics given as name=value
+fn analyze_architecture(
+    project: &Project,
+    architecture: &str,
+    generics: &[String],
+) -> Result<Vec<Diagnostic>, String> {
+    let (library_name, entity_name, architecture_name) = parse_architecture(architecture)?;
+
+    let generics = generics
+        .iter()
+        .map(|generic| {
+            generic
+                .split_once('=')
+                .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+                .ok_or_else(|| format!("Expected a generic as name=value, got '{generic}'"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    project.analyze_with_generics(library_name, entity_name, architecture_name, &generics)
+}
+
+/// Print the expressions matching a structural pattern or rewrite them when a template is given.
+/// The rewrites are only printed unless they are written to the files.
+fn structural_search(
+    project: &Project,
+    pattern: &str,
+    template: Option<&str>,
+    write: bool,
+) -> Result<(), String> {
+    let pattern = project.structural_pattern(pattern)?;
+
+    let Some(template) = template else {
+        for found in project.structural_search(&pattern) {
+            let start = found.pos.start();
+            println!(
+                "{}:{}:{}: {}",
+                found.pos.file_name().to_string_lossy(),
+                start.line + 1,
+                start.character + 1,
+                found.text()
+            );
+        }
+        return Ok(());
+    };
+
+    let fixes = project.structural_replace(&pattern, template)?;
+    let num_fixes = fixes.len();
+    if !write {
+        for fix in fixes.iter() {
+            let start = fix.pos.start();
+            let contents = fix.pos.source.contents();
+            println!(
+                "{}:{}:{}:",
+                fix.pos.file_name().to_string_lossy(),
+                start.line + 1,
+                start.character + 1,
+            );
+            println!("- {}", contents.text(fix.pos.range()));
+            println!("+ {}", fix.replacement);
+        }
+        println!("Would replace {num_fixes} expressions, pass --write to rewrite the files");
+        return Ok(());
+    }
+    let num_files = apply_fixes(fixes)?;
+    println!("Replaced {num_fixes} expressions in {num_files} files");
+    Ok(())
+}
+
+/// Apply non-overlapping fixes to the files they are within and return the number of changed files
+fn apply_fixes(fixes: Vec<Fix>) -> Result<usize, String> {
+    let mut by_file: BTreeMap<PathBuf, Vec<Fix>> = BTreeMap::new();
+    for fix in fixes {
+        by_file
+            .entry(fix.pos.file_name().to_owned())
+            .or_default()
+            .push(fix);
+    }
+
+    for (file_name, fixes) in by_file.iter_mut() {
+        // Apply the last fix first such that the positions of the others remain valid
+        fixes.sort_by_key(|fix| std::cmp::Reverse(fix.pos.start()));
+        let source = fixes[0].pos.source.clone();
+        for fix in fixes.iter() {
+            source.change(Some(&fix.pos.range()), &fix.replacement);
+        }
+
+        let contents = source.contents();
+        let text = Latin1String::from_utf8(&contents.text(contents.range()))
+            .map_err(|_| format!("Cannot encode {} as latin-1", file_name.display()))?;
+        std::fs::write(file_name, text.bytes)
+            .map_err(|err| format!("Failed to write {}: {err}", file_name.display()))?;
+    }
+    Ok(by_file.len())
+}
+
+/// Write a report to the output file or print it when no file is given
+fn write_report(output: Option<&str>, contents: String) -> Result<(), String> {
+    match output {
+        Some(output) => std::fs::write(output, contents)
+            .map_err(|err| format!("Failed to write {output}: {err}")),
+        None => {
+            print!("{contents}");
+            Ok(())
+        }
+    }
+}
+
+/// The dataflow of the process at a line given as file:line as a graph in the DOT language
+fn process_dataflow(project: &Project, location: &str) -> Result<String, String> {
+    let invalid = || format!("Expected file:line, got '{location}'");
+    let (file_name, line) = location.rsplit_once(':').ok_or_else(invalid)?;
+    let line = line
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|line| *line > 0)
+        .ok_or_else(invalid)?;
+    let source = project
+        .get_source(Path::new(file_name))
+        .ok_or_else(|| format!("File {file_name} is not part of the project"))?;
+    project
+        .process_dataflow(&source, Position::new(line - 1, 0))
+        .map(|dataflow| dataflow.to_dot())
+        .ok_or_else(|| format!("No process at line {line} of {file_name}"))
+}
+
+/// Split an architecture given as library.entity(architecture) into its names
+fn parse_architecture(architecture: &str) -> Result<(&str, &str, &str), String> {
+    let invalid = || format!("Expected library.entity(architecture), got '{architecture}'");
+    let (library_name, rest) = architecture.split_once('.').ok_or_else(invalid)?;
+    let (entity_name, architecture_name) = rest
+        .strip_suffix(')')
+        .and_then(|rest| rest.split_once('('))
+        .ok_or_else(invalid)?;
+    Ok((
+        library_name.trim(),
+        entity_name.trim(),
+        architecture_name.trim(),
+    ))
+}
+
 fn show_diagnostics(diagnostics: &[Diagnostic]) {
     for diagnostic in diagnostics {
         println!("{}", diagnostic.show());
@@ -4,20 +4,63 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::{AnyEnt, DesignRoot, EntRef};
+use crate::analysis::{
+    AnyEnt, ClassifiedProcess, ClockDomainCrossingReport, ConnectivityReport, DesignRoot, EntRef,
+    GenericDocumentation, PortGrouping, ProcessDataflow, ResolutionExplanation, StateMachineReport,
+    StaticValue, StructuralMatch, StructuralPattern, UnusedPortReport, VisibleName,
+};
 use crate::ast::DesignFile;
-use crate::config::Config;
+use crate::config::{Config, LibraryConfig};
 use crate::data::*;
-use crate::syntax::VHDLParser;
+use crate::syntax::{ParsedDesignFile, VHDLParser};
 use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 pub struct Project {
     parser: VHDLParser,
     root: DesignRoot,
     files: FnvHashMap<PathBuf, SourceFile>,
     empty_libraries: FnvHashSet<Symbol>,
+    lint_severities: FnvHashMap<ErrorCode, Option<Severity>>,
+    strictness: Strictness,
+    /// The libraries of the configuration whose file patterns are resolved again when files change
+    library_configs: Vec<LibraryConfig>,
+    /// The files loaded from the configuration and their libraries
+    configured_files: FnvHashMap<PathBuf, FnvHashSet<Symbol>>,
+    file_set_changes: Vec<FileSetChange>,
+}
+
+/// A change of the files matched by the library patterns of the configuration
+/// that was detected when the patterns were resolved again
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileSetChange {
+    /// A file loaded from the configuration no longer exists and is not analyzed
+    Removed(PathBuf),
+    /// A file that was not loaded from the configuration now matches a pattern and is analyzed
+    Added(PathBuf),
+}
+
+impl FileSetChange {
+    pub fn file_name(&self) -> &Path {
+        match self {
+            FileSetChange::Removed(file_name) | FileSetChange::Added(file_name) => file_name,
+        }
+    }
+
+    pub fn message(&self) -> Message {
+        match self {
+            FileSetChange::Removed(file_name) => Message::warning(format!(
+                "File {} was removed since the configuration was loaded",
+                file_name.to_string_lossy()
+            )),
+            FileSetChange::Added(file_name) => Message::warning(format!(
+                "File {} was added since the configuration was loaded",
+                file_name.to_string_lossy()
+            )),
+        }
+    }
 }
 
 impl Project {
@@ -27,6 +70,11 @@ impl Project {
             root: DesignRoot::new(parser.symbols.clone()),
             files: FnvHashMap::default(),
             empty_libraries: FnvHashSet::default(),
+            lint_severities: FnvHashMap::default(),
+            strictness: Strictness::default(),
+            library_configs: Vec::new(),
+            configured_files: FnvHashMap::default(),
+            file_set_changes: Vec::new(),
             parser,
         }
     }
@@ -35,9 +83,23 @@ impl Project {
     /// Files referred by configuration are parsed into corresponding libraries.
     pub fn from_config(config: &Config, messages: &mut dyn MessageHandler) -> Project {
         let mut project = Project::new();
+        project.parser.preprocessor = config.preprocessor().clone();
+        project.parser.conditional_analysis = config.conditional_analysis();
+        project.parser.psl_comments = config.psl_comments();
+        project
+            .root
+            .set_reset_convention(config.reset_convention().cloned());
+        project.root.set_standard(config.standard());
+        project.root.set_enabled_lints(enabled_lints(config));
+        project
+            .root
+            .set_architecture_selection(config.architecture_selection());
+        project.lint_severities = config.lint_severities().clone();
+        project.strictness = config.strictness();
 
         let files = project.load_files_from_config(config, messages);
         project.parse_and_add_files(files, messages);
+        project.update_configured_files();
 
         project
     }
@@ -47,7 +109,18 @@ impl Project {
     /// kept and parsed from in-memory source (required for incremental document updates).
     pub fn update_config(&mut self, config: &Config, messages: &mut dyn MessageHandler) {
         self.parser = VHDLParser::default();
+        self.parser.preprocessor = config.preprocessor().clone();
+        self.parser.conditional_analysis = config.conditional_analysis();
+        self.parser.psl_comments = config.psl_comments();
         self.root = DesignRoot::new(self.parser.symbols.clone());
+        self.root
+            .set_reset_convention(config.reset_convention().cloned());
+        self.root.set_standard(config.standard());
+        self.root.set_enabled_lints(enabled_lints(config));
+        self.root
+            .set_architecture_selection(config.architecture_selection());
+        self.lint_severities = config.lint_severities().clone();
+        self.strictness = config.strictness();
 
         // Reset library associations for known files,
         // all project files are added to the corresponding libraries later on.
@@ -66,6 +139,7 @@ impl Project {
             if let Some(source_file) = self.files.get_mut(&file_name) {
                 source_file.parser_diagnostics.clear();
                 source_file.library_names = library_names;
+                source_file.parsed = None;
                 source_file.design_file = self
                     .parser
                     .parse_design_source(&source_file.source, &mut source_file.parser_diagnostics);
@@ -73,24 +147,41 @@ impl Project {
         }
 
         self.parse_and_add_files(new_files, messages);
+        self.update_configured_files();
     }
 
     fn load_files_from_config(
         &mut self,
         config: &Config,
         messages: &mut dyn MessageHandler,
+    ) -> FnvHashMap<PathBuf, FnvHashSet<Symbol>> {
+        self.library_configs = config.iter_libraries().cloned().collect();
+        self.resolve_files(messages)
+    }
+
+    /// Resolve the file patterns of the libraries of the configuration
+    fn resolve_files(
+        &mut self,
+        messages: &mut dyn MessageHandler,
     ) -> FnvHashMap<PathBuf, FnvHashSet<Symbol>> {
         let mut files: FnvHashMap<PathBuf, FnvHashSet<Symbol>> = FnvHashMap::default();
+        let mut compile_order = FnvHashMap::default();
+        let mut simulation_libraries = FnvHashSet::default();
         self.empty_libraries.clear();
 
-        for library in config.iter_libraries() {
+        for library in self.library_configs.iter() {
             let library_name =
                 Latin1String::from_utf8(library.name()).expect("Library name not latin-1 encoded");
             let library_name = self.parser.symbol(&library_name);
 
+            if library.is_simulation() {
+                simulation_libraries.insert(library_name.clone());
+            }
+
             let mut empty_library = true;
-            for file_name in library.file_names(messages) {
+            for (idx, file_name) in library.file_names(messages).into_iter().enumerate() {
                 empty_library = false;
+                compile_order.entry(file_name.clone()).or_insert(idx);
 
                 match files.entry(file_name.clone()) {
                     Entry::Occupied(mut entry) => {
@@ -108,6 +199,9 @@ impl Project {
                 self.empty_libraries.insert(library_name);
             }
         }
+
+        self.root.set_compile_order(compile_order);
+        self.root.set_simulation_libraries(simulation_libraries);
         files
     }
 
@@ -146,13 +240,19 @@ impl Project {
                     library_names,
                     parser_diagnostics,
                     design_file,
+                    parsed: None,
                 },
             );
         }
     }
 
+    /// The source of a file, which may be given by another path to the same file such as
+    /// through a symbolic link or with a different case on a case-insensitive file system
     pub fn get_source(&self, file_name: &Path) -> Option<Source> {
-        self.files.get(file_name).map(|file| file.source.clone())
+        self.files
+            .get(file_name)
+            .or_else(|| self.files.get(&canonical_file_name(file_name)))
+            .map(|file| file.source.clone())
     }
 
     pub fn update_source(&mut self, source: &Source) {
@@ -178,17 +278,107 @@ impl Project {
                     library_names,
                     parser_diagnostics: vec![],
                     design_file: DesignFile::default(),
+                    parsed: None,
                 }
             }
         };
         source_file.parser_diagnostics.clear();
-        source_file.design_file = self
-            .parser
-            .parse_design_source(source, &mut source_file.parser_diagnostics);
+        (source_file.design_file, source_file.parsed) = self.parser.reparse_design_source(
+            source,
+            source_file.parsed.take(),
+            &mut source_file.parser_diagnostics,
+        );
         self.files
             .insert(source.file_name().to_owned(), source_file);
     }
 
+    /// Remember which of the files resolved from the configuration were loaded
+    fn update_configured_files(&mut self) {
+        self.configured_files = self
+            .files
+            .iter()
+            .filter(|(_, source_file)| !source_file.library_names.is_empty())
+            .map(|(file_name, source_file)| (file_name.clone(), source_file.library_names.clone()))
+            .collect();
+    }
+
+    /// Resolve the file patterns of the configuration again such that files removed or added
+    /// since the configuration was loaded are not analyzed from a stale set of files.
+    /// The analysis does not resolve the patterns itself, this is called when files are
+    /// known to have been created or deleted.
+    pub fn refresh_configured_files(&mut self) {
+        if self.library_configs.is_empty() {
+            return;
+        }
+
+        let files = self.resolve_files(&mut NullMessages);
+
+        let mut removed: Vec<PathBuf> = self
+            .configured_files
+            .keys()
+            .filter(|file_name| !files.contains_key(*file_name))
+            .cloned()
+            .collect();
+        removed.sort();
+        for file_name in removed {
+            if let Some(source_file) = self.files.remove(&file_name) {
+                for library_name in source_file.library_names.iter() {
+                    self.root
+                        .remove_source(library_name.clone(), &source_file.source);
+                }
+            }
+            self.configured_files.remove(&file_name);
+            self.file_set_changes
+                .push(FileSetChange::Removed(file_name));
+        }
+
+        let added: FnvHashMap<PathBuf, FnvHashSet<Symbol>> = files
+            .into_iter()
+            .filter(|(file_name, _)| !self.configured_files.contains_key(file_name))
+            .collect();
+        if added.is_empty() {
+            return;
+        }
+
+        let mut added_file_names: Vec<PathBuf> = added.keys().cloned().collect();
+        added_file_names.sort();
+
+        // Files opened in an editor that were not part of the configuration are
+        // moved to the libraries of the configuration and keep their contents
+        let (known_files, new_files): (FnvHashMap<_, _>, FnvHashMap<_, _>) = added
+            .into_iter()
+            .partition(|(file_name, _)| self.files.contains_key(file_name));
+
+        for (file_name, library_names) in known_files {
+            if let Some(source_file) = self.files.get_mut(&file_name) {
+                for library_name in source_file.library_names.iter() {
+                    self.root
+                        .remove_source(library_name.clone(), &source_file.source);
+                }
+                source_file.parser_diagnostics.clear();
+                source_file.library_names = library_names;
+                source_file.parsed = None;
+                source_file.design_file = self
+                    .parser
+                    .parse_design_source(&source_file.source, &mut source_file.parser_diagnostics);
+            }
+        }
+        self.parse_and_add_files(new_files, &mut NullMessages);
+
+        for file_name in added_file_names {
+            if let Some(source_file) = self.files.get(&file_name) {
+                self.configured_files
+                    .insert(file_name.clone(), source_file.library_names.clone());
+                self.file_set_changes.push(FileSetChange::Added(file_name));
+            }
+        }
+    }
+
+    /// The changes of the files matched by the configuration detected since the last call
+    pub fn take_file_set_changes(&mut self) -> Vec<FileSetChange> {
+        std::mem::take(&mut self.file_set_changes)
+    }
+
     pub fn analyse(&mut self) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -212,7 +402,77 @@ impl Project {
         }
 
         self.root.analyze(&mut diagnostics);
+        let diagnostics = self
+            .root
+            .remove_ignored_unit_diagnostics(&self.ignored_units(), diagnostics);
+        let mut diagnostics = remove_protected_fixes(self.apply_lint_severities(diagnostics));
+        // The interface of an archived library was checked when it was exported and its
+        // declarations may be completed by bodies that are not part of the archive
+        diagnostics.retain(|diagnostic| !LibraryArchive::is_archive(diagnostic.pos.file_name()));
+        diagnostics
+    }
+
+    /// The library and primary unit names of the units whose diagnostics are ignored
+    fn ignored_units(&self) -> Vec<(Symbol, Symbol)> {
+        let mut ignored = Vec::new();
+        for library in self.library_configs.iter() {
+            for unit in library.ignored_units() {
+                ignored.push((
+                    self.root.symbol_utf8(library.name()),
+                    self.root.symbol_utf8(unit),
+                ));
+            }
+        }
+        ignored
+    }
+
+    /// Apply the severities configured for error codes, dropping ignored diagnostics.
+    /// The strictness decides the severity of codes without a configured severity.
+    fn apply_lint_severities(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let has_library_severities = self
+            .library_configs
+            .iter()
+            .any(|library| !library.lint_severities().is_empty());
+        if self.lint_severities.is_empty()
+            && self.strictness == Strictness::Strict
+            && !has_library_severities
+        {
+            return diagnostics;
+        }
+
         diagnostics
+            .into_iter()
+            .filter_map(|mut diagnostic| {
+                if let Some(code) = diagnostic.code {
+                    let severity = self
+                        .library_lint_severity(&diagnostic.pos, code)
+                        .or_else(|| self.lint_severities.get(&code));
+                    if let Some(severity) = severity {
+                        diagnostic.severity = (*severity)?;
+                    } else if code.is_opt_in() {
+                        // The lint is only enabled by the libraries of other files
+                        return None;
+                    } else {
+                        diagnostic.severity = self.strictness.severity(code, diagnostic.severity);
+                    }
+                }
+                Some(diagnostic)
+            })
+            .collect()
+    }
+
+    /// The severity of an error code configured by the library of the file of a position
+    fn library_lint_severity(&self, pos: &SrcPos, code: ErrorCode) -> Option<&Option<Severity>> {
+        let source_file = self.files.get(pos.file_name())?;
+        self.library_configs
+            .iter()
+            .filter(|library| {
+                source_file
+                    .library_names
+                    .iter()
+                    .any(|name| name.name_utf8() == library.name())
+            })
+            .find_map(|library| library.lint_severities().get(&code))
     }
 
     /// Search for reference at position
@@ -226,27 +486,359 @@ impl Project {
         self.root.search_reference(source, cursor)
     }
 
+    /// Search for the references at many positions of a source in a single traversal,
+    /// the results are in the order of the cursors
+    pub fn search_references<'a>(
+        &'a self,
+        source: &Source,
+        cursors: &[Position],
+    ) -> Vec<Option<EntRef<'a>>> {
+        self.root.search_references(source, cursors)
+    }
+
+    /// The declarations made visible by the `use prefix.all` clause at the cursor
+    /// and whether each of them is referenced within the scope of the clause
+    pub fn expand_use_all<'a>(
+        &'a self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<Vec<(EntRef<'a>, bool)>> {
+        self.root.expand_use_all(source, cursor)
+    }
+
+    /// The replacement of the `prefix.all` name of the use clause at the cursor
+    /// by the names that are referenced within the scope of the clause
+    pub fn use_all_to_explicit_imports(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<(SrcPos, String)> {
+        self.root.use_all_to_explicit_imports(source, cursor)
+    }
+
+    /// The names visible at the cursor with their kinds and how they were made visible
+    pub fn visible_names(&self, source: &Source, cursor: Position) -> Vec<VisibleName> {
+        self.root.visible_names(source, cursor)
+    }
+
+    /// Explain how the name at the cursor was resolved
+    pub fn explain_resolution(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<ResolutionExplanation> {
+        self.root.explain_resolution(source, cursor)
+    }
+
     /// Search for the declaration at decl_pos and format it
     pub fn format_declaration(&self, ent: &AnyEnt) -> Option<String> {
         self.root.format_declaration(ent)
     }
 
+    /// The documentation table of the generics of an entity or component
+    pub fn generic_documentation(&self, ent: &AnyEnt) -> Vec<GenericDocumentation> {
+        self.root.generic_documentation(ent)
+    }
+
+    /// The documentation of a generic of an entity or component
+    pub fn documentation_of_generic(&self, ent: &AnyEnt) -> Option<GenericDocumentation> {
+        self.root.documentation_of_generic(ent)
+    }
+
     /// Search for all references to the declaration at decl_pos
     pub fn find_all_references(&self, ent: &AnyEnt) -> Vec<SrcPos> {
         self.root.find_all_references(ent)
     }
 
+    /// Search for all references to the declaration within a time budget
+    /// Returns the references found so far and true if the budget was exceeded
+    pub fn find_all_references_within(
+        &self,
+        ent: &AnyEnt,
+        budget: Duration,
+    ) -> (Vec<SrcPos>, bool) {
+        self.root
+            .find_all_references_until(ent, Instant::now() + budget)
+    }
+
+    /// The value of the innermost locally static expression at the cursor
+    pub fn static_value_at(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<(SrcPos, Option<StaticValue>)> {
+        self.root.static_value_at(source, cursor)
+    }
+
     /// Get source positions that are not resolved to a declaration
     /// This is used for development to test where the language server is blind
     pub fn find_all_unresolved(&self) -> (usize, Vec<SrcPos>) {
         self.root.find_all_unresolved()
     }
 
+    /// Classify the processes of an architecture as combinational or clocked
+    /// Returns None if the architecture does not exist or the project has not been analyzed
+    pub fn classify_processes(
+        &self,
+        library_name: &str,
+        entity_name: &str,
+        architecture_name: &str,
+    ) -> Option<Vec<ClassifiedProcess>> {
+        self.root.classify_processes(
+            &self.root.symbol_utf8(library_name),
+            &self.root.symbol_utf8(entity_name),
+            &self.root.symbol_utf8(architecture_name),
+        )
+    }
+
+    /// The dataflow of the process at the cursor from the signals it reads through
+    /// its variables to the signals it assigns
+    pub fn process_dataflow(&self, source: &Source, cursor: Position) -> Option<ProcessDataflow> {
+        self.root.process_dataflow(source, cursor)
+    }
+
+    /// The positions to replace with the new name to rename a library in all library clauses,
+    /// use clauses and selected names. References through `work` are not affected.
+    /// Returns an error if the new name is not a valid library name or conflicts with
+    /// an existing library.
+    pub fn rename_library(&self, old_name: &str, new_name: &str) -> Result<Vec<SrcPos>, String> {
+        let old_sym = self.root.symbol_utf8(old_name);
+        if !self.root.has_library(&old_sym) {
+            return Err(format!("No such library '{old_name}'"));
+        }
+        if old_sym == self.root.symbol_utf8("std") {
+            return Err("The library 'std' cannot be renamed".to_owned());
+        }
+
+        let new_sym = self
+            .parser
+            .basic_identifier(new_name)
+            .ok_or_else(|| format!("'{new_name}' is not a valid library name"))?;
+        if new_sym == old_sym {
+            return Ok(Vec::new());
+        }
+        if new_sym == self.root.symbol_utf8("work") {
+            return Err("A library cannot be named 'work'".to_owned());
+        }
+        if self.root.has_library(&new_sym) {
+            return Err(format!(
+                "Cannot rename library '{old_name}' to '{new_name}' since the library already exists"
+            ));
+        }
+
+        Ok(self
+            .root
+            .find_library_references(&old_sym)
+            .unwrap_or_default())
+    }
+
+    /// The position of the reference to a library by its own name at the cursor
+    /// Returns None if there is no such reference, such as for a reference through `work`
+    pub fn library_reference_at(&self, source: &Source, cursor: Position) -> Option<SrcPos> {
+        let ent = self.search_reference(source, cursor)?;
+        if !ent.is_library() {
+            return None;
+        }
+        self.root
+            .find_library_references(&self.root.symbol_utf8(&ent.designator().to_string()))?
+            .into_iter()
+            .find(|pos| pos.source() == source && pos.start() <= cursor && cursor <= pos.end())
+    }
+
+    /// The names of the libraries that a file is part of
+    pub fn library_names_of(&self, file_name: &Path) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .files
+            .get(file_name)
+            .map(|source_file| {
+                source_file
+                    .library_names
+                    .iter()
+                    .map(|name| name.name_utf8())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// The public interface of an analyzed library as an archive of its entities, package
+    /// declarations, contexts and package instances. Other projects analyze against the
+    /// interface by listing the archive among the files of a library of the same name.
+    pub fn export_library(&self, library_name: &str) -> Result<LibraryArchive, String> {
+        let units = self
+            .root
+            .interface_units(&self.root.symbol_utf8(library_name))
+            .ok_or_else(|| format!("No such library '{library_name}'"))?;
+
+        Ok(LibraryArchive {
+            library_name: library_name.to_owned(),
+            units: units
+                .into_iter()
+                .map(|(name, text)| ArchivedUnit {
+                    name: name.name_utf8(),
+                    text,
+                })
+                .collect(),
+        })
+    }
+
+    /// The text edits to group ports of an entity into a new record type declared in a package.
+    /// Returns an error if the names are not valid identifiers or the ports cannot be grouped.
+    pub fn group_ports_into_record(&self, grouping: &PortGrouping) -> Result<Vec<Fix>, String> {
+        for name in [&grouping.library_name, &grouping.package_library] {
+            if self.parser.basic_identifier(name).is_none() {
+                return Err(format!("'{name}' is not a valid library name"));
+            }
+        }
+        let names = [
+            &grouping.entity_name,
+            &grouping.package_name,
+            &grouping.port_name,
+            &grouping.record_name,
+        ];
+        for name in names.into_iter().chain(grouping.ports.iter()) {
+            if self.parser.identifier(name).is_none() {
+                return Err(format!("'{name}' is not a valid identifier"));
+            }
+        }
+        let fixes = self.root.group_ports_into_record(grouping)?;
+        let mut regions = FnvHashMap::default();
+        if let Some(fix) = fixes
+            .iter()
+            .find(|fix| is_protected(&mut regions, &fix.pos))
+        {
+            return Err(format!(
+                "Cannot group the ports since {}:{} is protected by '-- fmt: off'",
+                fix.pos.file_name().to_string_lossy(),
+                fix.pos.start().line + 1
+            ));
+        }
+        Ok(fixes)
+    }
+
+    /// Parse a structural pattern such as `rising_edge($clk) and $rst = '0'`
+    /// where the names starting with `$` are capture variables
+    pub fn structural_pattern(&self, pattern: &str) -> Result<StructuralPattern, String> {
+        let escaped = StructuralPattern::escape(pattern)?;
+        self.parser
+            .expression(&escaped)
+            .map(StructuralPattern::new)
+            .ok_or_else(|| format!("'{pattern}' is not a valid expression pattern"))
+    }
+
+    /// The expressions of the workspace matching a structural pattern
+    pub fn structural_search(&self, pattern: &StructuralPattern) -> Vec<StructuralMatch> {
+        self.root.structural_search(pattern)
+    }
+
+    /// The text edits that replace the expressions matching a structural pattern by a template
+    /// where each `$name` is replaced by the text matched by the capture variable.
+    /// Matches within protected regions and the files of the standard libraries are not replaced.
+    pub fn structural_replace(
+        &self,
+        pattern: &StructuralPattern,
+        template: &str,
+    ) -> Result<Vec<Fix>, String> {
+        pattern.check_replacement(template)?;
+        let mut regions = FnvHashMap::default();
+        let mut fixes = Vec::new();
+        for found in self.root.structural_search(pattern) {
+            if !self.is_standard_library_file(found.pos.file_name())
+                && !is_protected(&mut regions, &found.pos)
+            {
+                fixes.push(found.rewrite(template)?);
+            }
+        }
+        Ok(fixes)
+    }
+
+    /// Whether the file belongs to the std or ieee library that are installed with the tools
+    fn is_standard_library_file(&self, file_name: &Path) -> bool {
+        self.files.get(file_name).is_some_and(|source_file| {
+            source_file
+                .library_names
+                .iter()
+                .any(|library_name| matches!(library_name.name_utf8().as_str(), "std" | "ieee"))
+        })
+    }
+
+    /// The diagnostics of an architecture analyzed with assumed values of the generics of its
+    /// entity, given as pairs of a generic name and a VHDL expression such as `("width", "8")`.
+    /// Returns an error if a name or value is invalid.
+    pub fn analyze_with_generics(
+        &self,
+        library_name: &str,
+        entity_name: &str,
+        architecture_name: &str,
+        generics: &[(String, String)],
+    ) -> Result<Vec<Diagnostic>, String> {
+        let mut values = Vec::with_capacity(generics.len());
+        for (name, value) in generics.iter() {
+            let name_sym = self
+                .parser
+                .identifier(name)
+                .ok_or_else(|| format!("'{name}' is not a valid identifier"))?;
+            let expr = self
+                .parser
+                .expression(value)
+                .ok_or_else(|| format!("'{value}' is not a valid value of generic '{name}'"))?;
+            values.push((name_sym, expr));
+        }
+        let diagnostics = self.root.analyze_with_generics(
+            library_name,
+            entity_name,
+            architecture_name,
+            values,
+        )?;
+        Ok(self.apply_lint_severities(diagnostics))
+    }
+
+    /// The port connections of all instances within an architecture
+    pub fn connectivity(
+        &self,
+        library_name: &str,
+        entity_name: &str,
+        architecture_name: &str,
+    ) -> Result<ConnectivityReport, String> {
+        self.root
+            .connectivity(library_name, entity_name, architecture_name)
+    }
+
+    /// The ports of instantiated entities that are left open or tied to a constant at every instance
+    pub fn unused_ports(&self) -> UnusedPortReport {
+        self.root.unused_ports()
+    }
+
+    /// The signals that cross between clock domains without a synchronizer
+    pub fn clock_domain_crossings(&self) -> ClockDomainCrossingReport {
+        self.root.clock_domain_crossings()
+    }
+
+    /// The finite state machines of an enumeration type register and a case statement on it
+    pub fn state_machines(&self) -> StateMachineReport {
+        self.root.state_machines()
+    }
+
     pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
         self.files.values()
     }
 }
 
+/// The opt-in lints that have a severity configured globally or for any library
+fn enabled_lints(config: &Config) -> FnvHashSet<ErrorCode> {
+    let library_severities = config
+        .iter_libraries()
+        .flat_map(|library| library.lint_severities().iter());
+    config
+        .lint_severities()
+        .iter()
+        .chain(library_severities)
+        .filter(|(code, severity)| code.is_opt_in() && severity.is_some())
+        .map(|(code, _)| *code)
+        .collect()
+}
+
 /// Multiply clonable value by cloning
 /// Avoid clone for n=1
 fn multiply<T: Clone>(value: T, n: usize) -> Vec<T> {
@@ -270,11 +862,32 @@ impl Default for Project {
     }
 }
 
+/// Drop the fixes of diagnostics that would change a region protected by `-- fmt: off`
+fn remove_protected_fixes(mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    let mut regions = FnvHashMap::default();
+    for diagnostic in diagnostics.iter_mut() {
+        diagnostic
+            .fixes
+            .retain(|fix| !is_protected(&mut regions, &fix.pos));
+    }
+    diagnostics
+}
+
+/// True if the position is within a protected region of its source file
+fn is_protected(regions: &mut FnvHashMap<PathBuf, ProtectedRegions>, pos: &SrcPos) -> bool {
+    regions
+        .entry(pos.file_name().to_owned())
+        .or_insert_with(|| ProtectedRegions::parse(&pos.source, &mut NullDiagnostics))
+        .overlaps(&pos.range())
+}
+
 pub struct SourceFile {
     library_names: FnvHashSet<Symbol>,
     source: Source,
     design_file: DesignFile,
     parser_diagnostics: Vec<Diagnostic>,
+    /// The design units of the last change of the file that are reused by the next change
+    parsed: Option<ParsedDesignFile>,
 }
 
 impl SourceFile {
@@ -324,101 +937,747 @@ lib.files = ['file.vhd']
     }
 
     #[test]
-    fn unmapped_libraries_are_analyzed() {
-        let mut messages = Vec::new();
-        let mut project = Project::from_config(&Config::default(), &mut messages);
-        assert_eq!(messages, vec![]);
-        let diagnostics = project.analyse();
-        check_no_diagnostics(&diagnostics);
-
+    fn preprocessor_lines_are_filtered() {
         let root = tempfile::tempdir().unwrap();
-        let vhdl_file_path = root.path().join("file.vhd");
         std::fs::write(
-            &vhdl_file_path,
+            root.path().join("file.vhd"),
             "
+`include \"regs.vhd\"
 entity ent is
-end ent;
+end entity;
 
-architecture rtl of ent is
+architecture a of ent is
+  `define SIM
 begin
+  inst : component sim_model;
 end architecture;
+",
+        )
+        .unwrap();
 
-architecture rtl of ent is
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+
+[preprocessor]
+skip = ['`include']
+map = { '`define SIM' = 'component sim_model is end component;' }
+        ";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut project = Project::from_config(&config, &mut Vec::new());
+        let diagnostics: Vec<_> = project
+            .analyse()
+            .into_iter()
+            .map(|diag| (diag.severity, diag.pos.start().line, diag.message))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (Severity::Hint, 1, "Preprocessor line skipped".to_owned()),
+                (
+                    Severity::Hint,
+                    6,
+                    "Preprocessor line replaced by 'component sim_model is end component;'"
+                        .to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn conditional_analysis_skips_inactive_branches() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("file.vhd"),
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+`if TOOL_TYPE = \"SIMULATION\" then
+  component sim_model is end component;
+`else
+  this is not analyzed
+`end if
 begin
+  inst : component sim_model;
 end architecture;
 ",
         )
         .unwrap();
-        let source = Source::from_latin1_file(&vhdl_file_path).unwrap();
 
-        project.update_source(&source);
-        let diagnostics = project.analyse();
-        assert_eq!(diagnostics.len(), 1);
-        let diag = diagnostics.first().unwrap();
-        assert_eq!(diag.message, "Duplicate architecture 'rtl' of entity 'ent'")
+        let config_str = "
+standard = '2019'
+
+[libraries]
+lib.files = ['file.vhd']
+
+[conditional_analysis]
+TOOL_TYPE = 'SIMULATION'
+        ";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut project = Project::from_config(&config, &mut Vec::new());
+        let diagnostics: Vec<_> = project
+            .analyse()
+            .into_iter()
+            .map(|diag| (diag.code, diag.pos.start().line, diag.pos.end().line))
+            .collect();
+        assert_eq!(diagnostics, vec![(Some(ErrorCode::InactiveCode), 8, 8)]);
     }
 
-    /// Test that the same file can be added to several libraries
     #[test]
-    fn test_same_file_in_multiple_libraries() {
+    fn rename_library() {
         let root = tempfile::tempdir().unwrap();
-        let vhdl_file_path1 = root.path().join("file.vhd");
         std::fs::write(
-            vhdl_file_path1,
+            root.path().join("pkg.vhd"),
             "
 package pkg is
 end package;
-        ",
+
+use work.pkg.all;
+
+entity inner is
+end entity;
+",
         )
         .unwrap();
-
-        let vhdl_file_path2 = root.path().join("use_file.vhd");
         std::fs::write(
-            vhdl_file_path2,
+            root.path().join("top.vhd"),
             "
-library lib1;
-use lib1.pkg.all;
-
-package use_pkg1 is
-end package;
+library Old_Lib;
+use old_lib.pkg.all;
 
-library lib2;
-use lib2.pkg.all;
+entity top is
+end entity;
 
-package use_pkg2 is
-end package;
-        ",
+architecture a of top is
+  use OLD_LIB.pkg.all;
+begin
+  inst : entity old_lib.inner;
+end architecture;
+",
         )
         .unwrap();
 
         let config_str = "
 [libraries]
-lib1.files = ['file.vhd']
-lib2.files = ['file.vhd']
-use_lib.files = ['use_file.vhd']
+old_lib.files = ['pkg.vhd']
+other.files = ['top.vhd']
         ";
-
         let config = Config::from_str(config_str, root.path()).unwrap();
         let mut messages = Vec::new();
         let mut project = Project::from_config(&config, &mut messages);
-        assert_eq!(messages, vec![]);
         check_no_diagnostics(&project.analyse());
-    }
 
-    fn update(project: &mut Project, source: &mut Source, contents: &str) {
-        std::fs::write(std::path::Path::new(source.file_name()), contents).unwrap();
-        *source = Source::from_latin1_file(source.file_name()).unwrap();
-        project.update_source(source);
+        let positions = project.rename_library("old_lib", "new_lib").unwrap();
+        let renamed: Vec<_> = positions
+            .iter()
+            .map(|pos| {
+                (
+                    pos.file_name().file_name().unwrap().to_owned(),
+                    pos.start().line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            renamed,
+            [1, 2, 8, 10].map(|line| (std::ffi::OsString::from("top.vhd"), line))
+        );
+
+        assert_eq!(
+            project.rename_library("missing", "new_lib"),
+            Err("No such library 'missing'".to_owned())
+        );
+        assert_eq!(
+            project.rename_library("old_lib", "other"),
+            Err(
+                "Cannot rename library 'old_lib' to 'other' since the library already exists"
+                    .to_owned()
+            )
+        );
+        for invalid in ["entity", "new lib", "new__lib", "1lib", "\\ext\\"] {
+            assert_eq!(
+                project.rename_library("old_lib", invalid),
+                Err(format!("'{invalid}' is not a valid library name"))
+            );
+        }
+        assert_eq!(
+            project.rename_library("old_lib", "work"),
+            Err("A library cannot be named 'work'".to_owned())
+        );
+
+        let top = project.get_source(&root.path().join("top.vhd")).unwrap();
+        let reference = project
+            .library_reference_at(&top, Position::new(2, "use old_".len() as u32))
+            .unwrap();
+        assert_eq!(
+            reference.range(),
+            Range::new(Position::new(2, 4), Position::new(2, 11))
+        );
+        assert_eq!(
+            project.library_reference_at(&top, Position::new(2, "use old_lib.p".len() as u32)),
+            None
+        );
+        let pkg = project.get_source(&root.path().join("pkg.vhd")).unwrap();
+        assert_eq!(
+            project.library_reference_at(&pkg, Position::new(4, "use wo".len() as u32)),
+            None
+        );
     }
 
-    /// Test that the same file can be added to several libraries
     #[test]
-    fn test_re_analyze_after_update() {
-        let tempdir = tempfile::tempdir().unwrap();
-        let root = dunce::canonicalize(tempdir.path()).unwrap();
-
-        let path1 = root.join("file1.vhd");
-        let path2 = root.join("file2.vhd");
+    fn unmapped_libraries_are_analyzed() {
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&Config::default(), &mut messages);
+        assert_eq!(messages, vec![]);
+        let diagnostics = project.analyse();
+        check_no_diagnostics(&diagnostics);
+
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            &vhdl_file_path,
+            "
+entity ent is
+end ent;
+
+architecture rtl of ent is
+begin
+end architecture;
+
+architecture rtl of ent is
+begin
+end architecture;
+",
+        )
+        .unwrap();
+        let source = Source::from_latin1_file(&vhdl_file_path).unwrap();
+
+        project.update_source(&source);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        let diag = diagnostics.first().unwrap();
+        assert_eq!(diag.message, "Duplicate architecture 'rtl' of entity 'ent'")
+    }
+
+    #[test]
+    fn lint_severities_are_applied() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            &vhdl_file_path,
+            "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+  signal clk : bit;
+  type rec_t is record
+    f : bit;
+  end record;
+  signal rec : rec_t;
+begin
+  process (clk, clk, rec, rec.f)
+  begin
+  end process;
+end architecture;
+",
+        )
+        .unwrap();
+
+        std::fs::write(
+            root.path().join("standard.vhd"),
+            include_bytes!("../../vhdl_libraries/std/standard.vhd"),
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+std.files = ['standard.vhd']
+lib.files = ['file.vhd']
+
+[lint]
+duplicate_sensitivity = 'error'
+redundant_sensitivity = 'ignore'
+";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        let diag = diagnostics.first().unwrap();
+        assert_eq!(diag.code, Some(ErrorCode::DuplicateSensitivity));
+        assert_eq!(diag.severity, Severity::Error);
+    }
+
+    #[test]
+    fn library_lint_severities_are_applied() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["rtl1", "rtl2"] {
+            std::fs::write(
+                root.path().join(format!("{name}.vhd")),
+                format!(
+                    "
+entity {name} is
+end entity;
+
+architecture rtl of {name} is
+  signal s : bit := '0';
+begin
+end architecture;
+"
+                ),
+            )
+            .unwrap();
+        }
+
+        std::fs::write(
+            root.path().join("standard.vhd"),
+            include_bytes!("../../vhdl_libraries/std/standard.vhd"),
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+std.files = ['standard.vhd']
+lib1.files = ['rtl1.vhd']
+lib1.lint.signal_initial_value = 'error'
+lib2.files = ['rtl2.vhd']
+";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        let diag = diagnostics.first().unwrap();
+        assert_eq!(diag.code, Some(ErrorCode::SignalInitialValue));
+        assert_eq!(diag.severity, Severity::Error);
+        assert!(diag.pos.file_name().ends_with("rtl1.vhd"));
+    }
+
+    #[test]
+    fn tolerant_strictness_reports_vendor_extensions_as_hints() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("file.vhd"),
+            "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+  shared variable counter : natural;
+  signal s : missing_t;
+begin
+end architecture;
+",
+        )
+        .unwrap();
+
+        std::fs::write(
+            root.path().join("standard.vhd"),
+            include_bytes!("../../vhdl_libraries/std/standard.vhd"),
+        )
+        .unwrap();
+
+        let severities = |config_str: &str| {
+            let config = Config::from_str(config_str, root.path()).unwrap();
+            let mut messages = Vec::new();
+            let mut project = Project::from_config(&config, &mut messages);
+            assert_eq!(messages, vec![]);
+            let mut severities: Vec<_> = project
+                .analyse()
+                .into_iter()
+                .map(|diag| (diag.code, diag.severity))
+                .collect();
+            severities.sort_by_key(|(code, _)| code.map(|code| code.as_str()));
+            severities
+        };
+
+        let libraries = "
+[libraries]
+std.files = ['standard.vhd']
+lib.files = ['file.vhd']
+";
+        assert_eq!(
+            severities(libraries),
+            vec![
+                (None, Severity::Error),
+                (Some(ErrorCode::SharedVariableType), Severity::Error)
+            ]
+        );
+        assert_eq!(
+            severities(&format!("strictness = 'tolerant'\n{libraries}")),
+            vec![
+                (None, Severity::Error),
+                (Some(ErrorCode::SharedVariableType), Severity::Hint)
+            ]
+        );
+        // A configured severity takes precedence over the strictness
+        assert_eq!(
+            severities(&format!(
+                "strictness = 'tolerant'\n{libraries}\n[lint]\nshared_variable_type = 'warning'"
+            )),
+            vec![
+                (None, Severity::Error),
+                (Some(ErrorCode::SharedVariableType), Severity::Warning)
+            ]
+        );
+    }
+
+    #[test]
+    fn diagnostics_of_ignored_units_are_removed() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("vendor.vhd");
+        std::fs::write(
+            &vhdl_file_path,
+            "
+entity good is
+end entity;
+
+library missing_lib;
+use missing_lib.pkg.all;
+
+entity broken is
+end entity;
+
+architecture rtl of broken is
+  signal s : missing_t;
+begin
+end architecture;
+
+architecture rtl of good is
+  signal s : missing_t;
+begin
+end architecture;
+",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+vendor.files = ['vendor.vhd']
+vendor.ignore_units = ['Broken']
+";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        let diag = diagnostics.first().unwrap();
+        assert_eq!(diag.message, "No declaration of 'missing_t'");
+        assert_eq!(diag.pos.start().line, 16);
+    }
+
+    #[test]
+    fn fixes_within_protected_regions_are_removed() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("file.vhd"),
+            "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+  signal counter : natural;
+  -- fmt: off
+  signal copy0 : natural := countr;
+  -- fmt: on
+  signal copy1 : natural := countr;
+begin
+end architecture;
+",
+        )
+        .unwrap();
+
+        std::fs::write(
+            root.path().join("standard.vhd"),
+            include_bytes!("../../vhdl_libraries/std/standard.vhd"),
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+std.files = ['standard.vhd']
+lib.files = ['file.vhd']
+";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let diagnostics = project.analyse();
+        let fixed_lines: Vec<_> = diagnostics
+            .iter()
+            .map(|diag| (diag.pos.start().line, diag.fixes.len()))
+            .collect();
+        assert_eq!(fixed_lines, vec![(7, 0), (9, 1)]);
+    }
+
+    #[test]
+    fn invalid_names_of_port_grouping_are_errors() {
+        let project = Project::new();
+        let grouping = PortGrouping {
+            library_name: "lib".to_owned(),
+            entity_name: "ent".to_owned(),
+            ports: vec!["a".to_owned(), "b".to_owned()],
+            port_name: "bus_port".to_owned(),
+            record_name: "bus_t".to_owned(),
+            package_library: "lib".to_owned(),
+            package_name: "pkg".to_owned(),
+        };
+        assert_eq!(
+            project.group_ports_into_record(&grouping),
+            Err("No such entity 'lib.ent'".to_owned())
+        );
+
+        for name in [
+            "signal",
+            "my port",
+            "p\u{f6}rt\u{2713}",
+            "\\p\u{2713}\\",
+            "",
+        ] {
+            for update in [
+                |grouping: &mut PortGrouping, name: &str| grouping.port_name = name.to_owned(),
+                |grouping: &mut PortGrouping, name: &str| grouping.record_name = name.to_owned(),
+                |grouping: &mut PortGrouping, name: &str| grouping.package_name = name.to_owned(),
+                |grouping: &mut PortGrouping, name: &str| grouping.ports[1] = name.to_owned(),
+            ] {
+                let mut invalid = grouping.clone();
+                update(&mut invalid, name);
+                assert_eq!(
+                    project.group_ports_into_record(&invalid),
+                    Err(format!("'{name}' is not a valid identifier"))
+                );
+            }
+        }
+
+        let mut invalid = grouping.clone();
+        invalid.package_library = "\\lib\\".to_owned();
+        assert_eq!(
+            project.group_ports_into_record(&invalid),
+            Err("'\\lib\\' is not a valid library name".to_owned())
+        );
+    }
+
+    #[test]
+    fn standard_library_files_are_not_replaced() {
+        let root = tempfile::tempdir().unwrap();
+        let code = "
+package pkg is
+  constant c : boolean := not true;
+end package;
+";
+        std::fs::write(root.path().join("file.vhd"), code).unwrap();
+        std::fs::write(root.path().join("std_pkg.vhd"), code).unwrap();
+        std::fs::write(
+            root.path().join("standard.vhd"),
+            include_bytes!("../../vhdl_libraries/std/standard.vhd"),
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+std.files = ['standard.vhd', 'std_pkg.vhd']
+lib.files = ['file.vhd']
+";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+        project.analyse();
+
+        let pattern = project.structural_pattern("not $x").unwrap();
+        assert_eq!(project.structural_search(&pattern).len(), 2);
+        let fixed_files: Vec<_> = project
+            .structural_replace(&pattern, "$x")
+            .unwrap()
+            .iter()
+            .map(|fix| fix.pos.file_name().file_name().unwrap().to_owned())
+            .collect();
+        assert_eq!(fixed_files, vec!["file.vhd"]);
+    }
+
+    #[test]
+    fn exported_library_is_analyzed_against_without_sources() {
+        let ip_root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            ip_root.path().join("standard.vhd"),
+            include_bytes!("../../vhdl_libraries/std/standard.vhd"),
+        )
+        .unwrap();
+        std::fs::write(
+            ip_root.path().join("ip.vhd"),
+            "
+package pkg is
+  constant width : natural;
+end package;
+
+package body pkg is
+  constant width : natural := 8;
+end package body;
+
+library std;
+use work.pkg.all;
+
+entity ent is
+  port (d : in natural range 0 to width);
+end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
+",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+std.files = ['standard.vhd']
+ip.files = ['ip.vhd']
+";
+        let config = Config::from_str(config_str, ip_root.path()).unwrap();
+        let mut project = Project::from_config(&config, &mut Vec::new());
+        assert_eq!(project.analyse(), vec![]);
+
+        let archive = project.export_library("ip").unwrap();
+        let names: Vec<_> = archive
+            .units
+            .iter()
+            .map(|unit| unit.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["pkg", "ent"]);
+        assert_eq!(
+            archive.units[1].text,
+            "library std;
+use work.pkg.all;
+
+entity ent is
+  port (d : in natural range 0 to width);
+end entity;"
+        );
+        assert_eq!(
+            project.export_library("missing"),
+            Err("No such library 'missing'".to_owned())
+        );
+
+        let root = tempfile::tempdir().unwrap();
+        archive.write(&root.path().join("ip.vhdla")).unwrap();
+        std::fs::write(
+            root.path().join("standard.vhd"),
+            include_bytes!("../../vhdl_libraries/std/standard.vhd"),
+        )
+        .unwrap();
+        std::fs::write(
+            root.path().join("top.vhd"),
+            "
+library ip;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  signal d : natural range 0 to ip.pkg.width;
+begin
+  inst : entity ip.ent port map (d => d);
+  bad : entity ip.ent port map (q => d);
+end architecture;
+",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+std.files = ['standard.vhd']
+ip.files = ['ip.vhdla']
+lib.files = ['top.vhd']
+";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let diagnostics = project.analyse();
+        let messages: Vec<_> = diagnostics
+            .iter()
+            .map(|diag| (diag.pos.start().line, diag.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (10, "No declaration of 'q'"),
+                (10, "No association of interface signal 'd' : in"),
+            ]
+        );
+    }
+
+    /// Test that the same file can be added to several libraries
+    #[test]
+    fn test_same_file_in_multiple_libraries() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path1 = root.path().join("file.vhd");
+        std::fs::write(
+            vhdl_file_path1,
+            "
+package pkg is
+end package;
+        ",
+        )
+        .unwrap();
+
+        let vhdl_file_path2 = root.path().join("use_file.vhd");
+        std::fs::write(
+            vhdl_file_path2,
+            "
+library lib1;
+use lib1.pkg.all;
+
+package use_pkg1 is
+end package;
+
+library lib2;
+use lib2.pkg.all;
+
+package use_pkg2 is
+end package;
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib1.files = ['file.vhd']
+lib2.files = ['file.vhd']
+use_lib.files = ['use_file.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+        check_no_diagnostics(&project.analyse());
+    }
+
+    fn update(project: &mut Project, source: &mut Source, contents: &str) {
+        std::fs::write(std::path::Path::new(source.file_name()), contents).unwrap();
+        *source = Source::from_latin1_file(source.file_name()).unwrap();
+        project.update_source(source);
+    }
+
+    /// Test that the same file can be added to several libraries
+    #[test]
+    fn test_re_analyze_after_update() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+
+        let path1 = root.join("file1.vhd");
+        let path2 = root.join("file2.vhd");
         std::fs::write(
             &path1,
             "
@@ -507,6 +1766,104 @@ end package;
         check_no_diagnostics(&project.analyse());
     }
 
+    /// Test that changing one design unit of a file reuses the other units
+    /// without affecting the analysis
+    #[test]
+    fn test_re_analyze_after_change_of_one_unit() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+
+        let path = root.join("file.vhd");
+        std::fs::write(
+            &path,
+            "\
+package pkg1 is
+end package;
+
+use work.pkg1.all;
+
+entity ent is
+end entity;
+",
+        )
+        .unwrap();
+        let source = Source::from_latin1_file(&path).unwrap();
+
+        let config = Config::from_str("[libraries]\nlib.files = ['file.vhd']", &root).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+        check_no_diagnostics(&project.analyse());
+
+        // Rename the package
+        source.change(
+            Some(&Range::new(Position::new(0, 8), Position::new(0, 12))),
+            "pkg2",
+        );
+        project.update_source(&source);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pos.range().start, Position::new(3, 9));
+
+        // Use the renamed package
+        source.change(
+            Some(&Range::new(Position::new(3, 9), Position::new(3, 13))),
+            "pkg2",
+        );
+        project.update_source(&source);
+        check_no_diagnostics(&project.analyse());
+    }
+
+    /// Test that a file reached through a symbolic link is analyzed once
+    #[test]
+    #[cfg(unix)]
+    fn test_same_file_through_symbolic_link() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+        std::fs::create_dir(root.join("rtl")).unwrap();
+        std::os::unix::fs::symlink(root.join("rtl"), root.join("link")).unwrap();
+
+        let path = root.join("rtl").join("file.vhd");
+        std::fs::write(
+            &path,
+            "
+package pkg is
+end package;
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['rtl/file.vhd', 'link/*.vhd']
+        ";
+
+        let config = Config::from_str(config_str, &root).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+        check_no_diagnostics(&project.analyse());
+
+        let source = project
+            .get_source(&root.join("link").join("file.vhd"))
+            .unwrap();
+        assert_eq!(source.file_name(), path);
+
+        source.change(
+            None,
+            "
+package pkg is
+end package;
+
+package pkg2 is
+end package;
+        ",
+        );
+        project.update_source(&source);
+        check_no_diagnostics(&project.analyse());
+        assert_eq!(project.files().count(), 1);
+    }
+
     /// Test that the configuration can be updated
     #[test]
     fn test_config_update() {
@@ -573,4 +1930,86 @@ lib.files = ['file2.vhd']
         assert_eq!(diagnostics[0].pos.source, source2); // No such library
         assert_eq!(diagnostics[1].pos.source, source2); // No declaration
     }
+
+    #[test]
+    fn file_set_changes_are_detected_when_files_are_resolved_again() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+
+        let pkg_path = root.join("pkg.vhd");
+        let ent_path = root.join("ent.vhd");
+        let new_path = root.join("new.vhd");
+        std::fs::write(
+            &pkg_path,
+            "
+package pkg is
+end package;
+        ",
+        )
+        .unwrap();
+        std::fs::write(
+            &ent_path,
+            "
+use work.pkg.all;
+
+entity ent is
+end entity;
+        ",
+        )
+        .unwrap();
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = ['*.vhd']
+        ",
+            &root,
+        )
+        .unwrap();
+
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+        check_no_diagnostics(&project.analyse());
+        assert_eq!(project.take_file_set_changes(), vec![]);
+
+        // The package is moved to another file after the configuration was loaded
+        std::fs::remove_file(&pkg_path).unwrap();
+        std::fs::write(
+            &new_path,
+            "
+package pkg is
+end package;
+        ",
+        )
+        .unwrap();
+
+        // The files are not resolved again until the project is told that files changed
+        check_no_diagnostics(&project.analyse());
+        assert_eq!(project.take_file_set_changes(), vec![]);
+        assert!(project.get_source(&new_path).is_none());
+
+        project.refresh_configured_files();
+        check_no_diagnostics(&project.analyse());
+        assert_eq!(
+            project.take_file_set_changes(),
+            vec![
+                FileSetChange::Removed(pkg_path.clone()),
+                FileSetChange::Added(new_path.clone())
+            ]
+        );
+        assert!(project.get_source(&pkg_path).is_none());
+        assert!(project.get_source(&new_path).is_some());
+
+        // The package is removed
+        std::fs::remove_file(&new_path).unwrap();
+        project.refresh_configured_files();
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pos.file_name(), ent_path);
+        assert_eq!(
+            project.take_file_set_changes(),
+            vec![FileSetChange::Removed(new_path)]
+        );
+    }
 }
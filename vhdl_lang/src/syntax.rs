@@ -17,19 +17,22 @@ mod context;
 mod declarative_part;
 mod design_unit;
 mod expression;
+mod group_declaration;
 mod interface_declaration;
 mod names;
 mod object_declaration;
 mod parser;
+mod psl;
 mod range;
 mod sequential_statement;
 mod subprogram;
 mod subtype_indication;
 mod type_declaration;
+mod view_declaration;
 mod waveform;
 
 #[cfg(test)]
 pub mod test;
 
-pub use parser::{ParserResult, VHDLParser};
+pub use parser::{ParsedDesignFile, ParserResult, VHDLParser};
 pub use tokens::Symbols;
@@ -14,7 +14,7 @@ use crate::ast::{
     EntityTag, WithRef,
 };
 
-fn parse_entity_class(stream: &mut TokenStream) -> ParseResult<EntityClass> {
+pub fn parse_entity_class(stream: &mut TokenStream) -> ParseResult<EntityClass> {
     let token = stream.expect()?;
     Ok(try_token_kind!(
         token,
@@ -29,7 +29,12 @@ fn parse_entity_class(stream: &mut TokenStream) -> ParseResult<EntityClass> {
         Component => EntityClass::Component,
         Constant => EntityClass::Constant,
         Type => EntityClass::Type,
-        Label => EntityClass::Label
+        Subtype => EntityClass::Subtype,
+        Label => EntityClass::Label,
+        Literal => EntityClass::Literal,
+        Units => EntityClass::Units,
+        Group => EntityClass::Group,
+        File => EntityClass::File
     ))
 }
 
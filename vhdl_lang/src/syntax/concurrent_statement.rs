@@ -14,6 +14,9 @@ use super::names::{
     expression_to_ident, into_selected_name, parse_association_list, parse_name_initial_token,
     parse_selected_name,
 };
+use super::psl::{
+    parse_concurrent_assert_or_psl_directive, parse_psl_directive_known_kind, psl_directive_kind,
+};
 use super::range::parse_discrete_range;
 use super::sequential_statement::{
     parse_assert_statement_known_keyword, parse_labeled_sequential_statements, parse_selection,
@@ -230,6 +233,7 @@ fn to_procedure_call(
             call: WithPos::from(
                 CallOrIndexed {
                     name: WithPos::from(name, target.pos.clone()),
+                    generic_map: None,
                     parameters: vec![],
                 },
                 target.pos,
@@ -345,6 +349,7 @@ pub fn parse_instantiation_statement(
         unit,
         generic_map: generic_map.unwrap_or_default(),
         port_map: port_map.unwrap_or_default(),
+        default_binding: None,
     };
     stream.expect_kind(SemiColon)?;
     Ok(inst)
@@ -588,7 +593,7 @@ pub fn parse_concurrent_statement(
             For => ConcurrentStatement::ForGenerate(parse_for_generate_statement(stream, diagnostics)?),
             If => ConcurrentStatement::IfGenerate(parse_if_generate_statement(stream, diagnostics)?),
             Case => ConcurrentStatement::CaseGenerate(parse_case_generate_statement(stream, diagnostics)?),
-            Assert => ConcurrentStatement::Assert(parse_concurrent_assert_statement(stream, false)?),
+            Assert => parse_concurrent_assert_or_psl_directive(stream)?,
             Postponed => {
                 let token = stream.expect()?;
                 match token.kind {
@@ -604,6 +609,9 @@ pub fn parse_concurrent_statement(
             },
             With => ConcurrentStatement::Assignment(parse_selected_signal_assignment(stream, false)?),
             Identifier => {
+                if let Some(kind) = psl_directive_kind(stream, &token)? {
+                    return Ok(ConcurrentStatement::PslDirective(parse_psl_directive_known_kind(stream, kind)?));
+                }
                 let name = parse_name_initial_token(stream, token)?;
                 let token = stream.peek_expect()?;
                 match token.kind {
@@ -666,6 +674,13 @@ pub fn parse_labeled_concurrent_statement_initial_token(
     diagnostics: &mut dyn DiagnosticHandler,
 ) -> ParseResult<LabeledConcurrentStatement> {
     if token.kind == Identifier {
+        if let Some(kind) = psl_directive_kind(stream, &token)? {
+            let directive = parse_psl_directive_known_kind(stream, kind)?;
+            return Ok(LabeledConcurrentStatement {
+                label: None,
+                statement: ConcurrentStatement::PslDirective(directive),
+            });
+        }
         let name = parse_name_initial_token(stream, token)?;
         let token = stream.peek_expect()?;
         if token.kind == Colon {
@@ -1214,6 +1229,7 @@ inst: component lib.foo.bar;
             unit: InstantiatedUnit::Component(code.s1("lib.foo.bar").selected_name()),
             generic_map: vec![],
             port_map: vec![],
+            default_binding: None,
         };
         let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
         assert_eq!(stmt.label, Some(code.s1("inst").decl_ident()));
@@ -1232,6 +1248,7 @@ inst: configuration lib.foo.bar;
             unit: InstantiatedUnit::Configuration(code.s1("lib.foo.bar").selected_name()),
             generic_map: vec![],
             port_map: vec![],
+            default_binding: None,
         };
         let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
         assert_eq!(stmt.label, Some(code.s1("inst").decl_ident()));
@@ -1250,6 +1267,7 @@ inst: entity lib.foo.bar;
             unit: InstantiatedUnit::Entity(code.s1("lib.foo.bar").selected_name(), None),
             generic_map: vec![],
             port_map: vec![],
+            default_binding: None,
         };
         let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
         assert_eq!(stmt.label, Some(code.s1("inst").decl_ident()));
@@ -1271,6 +1289,7 @@ inst: entity lib.foo.bar(arch);
             ),
             generic_map: vec![],
             port_map: vec![],
+            default_binding: None,
         };
         let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
         assert_eq!(stmt.label, Some(code.s1("inst").decl_ident()));
@@ -1303,6 +1322,7 @@ inst: component lib.foo.bar
    clk => clk_foo
   )")
                 .association_list(),
+            default_binding: None,
         };
         let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
         assert_eq!(stmt.label, Some(code.s1("inst").decl_ident()));
@@ -1328,6 +1348,7 @@ inst: lib.foo.bar
    clk => clk_foo
   )")
                 .association_list(),
+            default_binding: None,
         };
         let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
         assert_eq!(stmt.label, Some(code.s1("inst").decl_ident()));
@@ -1353,6 +1374,7 @@ inst: lib.foo.bar
   )")
                 .association_list(),
             port_map: vec![],
+            default_binding: None,
         };
         let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
         assert_eq!(stmt.label, Some(code.s1("inst").decl_ident()));
@@ -10,11 +10,14 @@ use super::common::ParseResult;
 use super::component_declaration::parse_component_declaration;
 use super::configuration::parse_configuration_specification;
 use super::context::parse_use_clause;
+use super::group_declaration::parse_group_declaration;
 use super::names::{parse_association_list, parse_selected_name};
 use super::object_declaration::{parse_file_declaration, parse_object_declaration};
+use super::psl::parse_psl_clock_declaration;
 use super::subprogram::parse_subprogram;
 use super::tokens::{Kind::*, *};
 use super::type_declaration::parse_type_declaration;
+use super::view_declaration::parse_mode_view_declaration;
 use crate::ast::{ContextClause, Declaration, PackageInstantiation};
 use crate::data::DiagnosticHandler;
 
@@ -50,13 +53,14 @@ pub fn is_declarative_part(stream: &mut TokenStream, begin_is_end: bool) -> Pars
 fn check_declarative_part(token: &Token, may_end: bool, may_begin: bool) -> ParseResult<()> {
     match token.kind {
         Use | Type | Subtype | Shared | Constant | Signal | Variable | File | Component
-        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For => Ok(()),
+        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For | Group
+        | View | Default => Ok(()),
         Begin if may_begin => Ok(()),
         End if may_end => Ok(()),
         _ => {
             let decl_kinds = [
                 Use, Type, Subtype, Shared, Constant, Signal, Variable, File, Component, Attribute,
-                Alias, Impure, Pure, Function, Procedure, Package, For,
+                Alias, Impure, Pure, Function, Procedure, Package, For, Group,
             ];
 
             Err(token.kinds_error(&decl_kinds))
@@ -100,13 +104,17 @@ pub fn parse_declarative_part_leave_end_token(
                 | Attribute
                 | Use
                 | Alias
+                | Group
+                | View
+                | Default
         )
     }
 
     while let Some(token) = stream.peek()? {
         match token.kind {
             Begin | End => break,
-            Type | Subtype | Component | Impure | Pure | Function | Procedure | Package | For => {
+            Type | Subtype | Component | Impure | Pure | Function | Procedure | Package | For
+            | View => {
                 let decl = match token.kind {
                     Type | Subtype => {
                         parse_type_declaration(stream, diagnostics).map(Declaration::Type)?
@@ -118,6 +126,9 @@ pub fn parse_declarative_part_leave_end_token(
                     For => {
                         parse_configuration_specification(stream).map(Declaration::Configuration)?
                     }
+                    View => {
+                        parse_mode_view_declaration(stream, diagnostics).map(Declaration::View)?
+                    }
                     _ => unreachable!(),
                 };
                 declarations.push(decl);
@@ -142,10 +153,12 @@ pub fn parse_declarative_part_leave_end_token(
                 }
             }
 
-            Use | Alias => {
+            Use | Alias | Group | Default => {
                 let decl: ParseResult<Declaration> = match token.kind {
                     Use => parse_use_clause(stream).map(Declaration::Use),
                     Alias => parse_alias_declaration(stream).map(Declaration::Alias),
+                    Group => parse_group_declaration(stream),
+                    Default => parse_psl_clock_declaration(stream).map(Declaration::PslClock),
                     _ => unreachable!(),
                 };
                 match decl.or_recover_until(stream, diagnostics, is_recover_token) {
@@ -160,7 +173,7 @@ pub fn parse_declarative_part_leave_end_token(
             _ => {
                 diagnostics.push(token.kinds_error(&[
                     Type, Subtype, Component, Impure, Pure, Function, Procedure, Package, For,
-                    File, Shared, Constant, Signal, Variable, Attribute, Use, Alias,
+                    File, Shared, Constant, Signal, Variable, Attribute, Use, Alias, Group,
                 ]));
                 stream.skip_until(is_recover_token)?;
                 continue;
@@ -249,7 +262,7 @@ constant x: natural := 5;
                 "Expected 'type', 'subtype', 'component', 'impure', 'pure', \
                  'function', 'procedure', 'package', 'for', 'file', \
                  'shared', 'constant', 'signal', 'variable', 'attribute', \
-                 'use' or 'alias'"
+                 'use', 'alias' or 'group'"
             )]
         );
     }
@@ -4,7 +4,7 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::tokens::{Kind::*, TokenStream};
+use super::tokens::{Kind::*, Token, TokenStream};
 
 use super::common::error_on_end_identifier_mismatch;
 use super::common::ParseResult;
@@ -166,113 +166,209 @@ fn context_item_message(context_item: &ContextItem, message: impl AsRef<str>) ->
     format!("{} {}", prefix, message.as_ref())
 }
 
+/// A design unit together with the part of the file it was parsed from
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParsedUnit {
+    pub unit: AnyDesignUnit,
+    /// The end of the previous unit, where parsing of the unit started
+    pub start: Position,
+    /// The start of the first token of the unit or of its context clause
+    pub first: Position,
+    /// The end of the last token of the unit
+    pub end: Position,
+    /// The diagnostics of the unit and of its context clause
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ShiftLines for ParsedUnit {
+    fn shift_lines(&mut self, delta: i64) {
+        let ParsedUnit {
+            unit,
+            start,
+            first,
+            end,
+            diagnostics,
+        } = self;
+        unit.shift_lines(delta);
+        start.shift_lines(delta);
+        first.shift_lines(delta);
+        end.shift_lines(delta);
+        diagnostics.shift_lines(delta);
+    }
+}
+
+/// The design units parsed from the tokens of a file until a position
+#[derive(Default)]
+pub struct ParsedUnits {
+    pub units: Vec<ParsedUnit>,
+    /// The start of the token at which parsing stopped, None at the end of the file
+    pub next: Option<Position>,
+    /// The context clause after the last unit when parsing stopped before the end of the file
+    pub context_clause: ContextClause,
+    /// The diagnostics after the last unit
+    pub diagnostics: Vec<Diagnostic>,
+}
+
 pub fn parse_design_file(
     stream: &mut TokenStream,
     diagnostics: &mut dyn DiagnosticHandler,
 ) -> ParseResult<DesignFile> {
+    let parsed = parse_design_units(stream, None)?;
+    let mut design_units = Vec::with_capacity(parsed.units.len());
+    for unit in parsed.units {
+        diagnostics.append(unit.diagnostics);
+        design_units.push(unit.unit);
+    }
+    diagnostics.append(parsed.diagnostics);
+    Ok(DesignFile { design_units })
+}
+
+/// Parse the design units until the first token that starts at or after the position,
+/// or until the end of the file when no position is given
+pub fn parse_design_units(
+    stream: &mut TokenStream,
+    until: Option<Position>,
+) -> ParseResult<ParsedUnits> {
     let mut context_clause = vec![];
-    let mut design_units = vec![];
+    let mut parsed = ParsedUnits::default();
+    let mut start = stream.state().pos();
+    let mut first = None;
 
     while let Some(token) = stream.peek()? {
-        try_init_token_kind!(
-            token,
-            Library => {
-                match parse_library_clause(stream) {
-                    Ok(library) => {
-                        context_clause.push(library.map_into(ContextItem::Library));
-                    },
-                    Err(diagnostic) => diagnostics.push(diagnostic),
-                }
-            },
-            Use => {
-                match parse_use_clause(stream) {
-                    Ok(use_clause) => {
-                        context_clause.push(use_clause.map_into(ContextItem::Use));
-                    },
-                    Err(diagnostic) => diagnostics.push(diagnostic),
-                }
-            },
-            Context => match parse_context(stream, diagnostics) {
-                Ok(DeclarationOrReference::Declaration(context_decl)) => {
-                    if !context_clause.is_empty() {
-                        let mut diagnostic = Diagnostic::error(&context_decl.ident, "Context declaration may not be preceeded by a context clause");
-
-                        for context_item in context_clause.iter() {
-                            diagnostic.add_related(context_item, context_item_message(&context_item.item, "may not come before context declaration"));
-                        }
-
-                        diagnostics.push(diagnostic);
-                        context_clause.clear();
-                    }
+        if until.is_some_and(|until| token.pos.start() >= until) {
+            parsed.next = Some(token.pos.start());
+            break;
+        }
+        let unit_first = *first.get_or_insert(token.pos.start());
+
+        if let Some(unit) =
+            parse_design_unit(stream, &token, &mut context_clause, &mut parsed.diagnostics)?
+        {
+            let end = stream.state().pos();
+            parsed.units.push(ParsedUnit {
+                unit,
+                start,
+                first: unit_first,
+                end,
+                diagnostics: std::mem::take(&mut parsed.diagnostics),
+            });
+            start = end;
+            first = None;
+        }
+    }
 
-                    design_units.push(AnyDesignUnit::Primary(AnyPrimaryUnit::Context(context_decl)));
-                }
-                Ok(DeclarationOrReference::Reference(context_ref)) => {
-                    context_clause.push(context_ref.map_into(ContextItem::Context));
-                }
+    if parsed.next.is_some() {
+        parsed.context_clause = context_clause;
+    } else {
+        for context_item in context_clause {
+            parsed.diagnostics.push(Diagnostic::warning(
+                &context_item,
+                context_item_message(&context_item.item, "not associated with any design unit"),
+            ));
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parse a design unit or an item of the context clause of the next design unit
+fn parse_design_unit(
+    stream: &mut TokenStream,
+    token: &Token,
+    context_clause: &mut ContextClause,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<Option<AnyDesignUnit>> {
+    try_init_token_kind!(
+        token,
+        Library => {
+            match parse_library_clause(stream) {
+                Ok(library) => {
+                    context_clause.push(library.map_into(ContextItem::Library));
+                },
                 Err(diagnostic) => diagnostics.push(diagnostic),
-            },
-            Entity => match parse_entity_declaration(stream, diagnostics) {
-                Ok(mut entity) => {
-                    entity.context_clause = take_context_clause(&mut context_clause);
-                    design_units.push(AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)));
-                }
+            }
+        },
+        Use => {
+            match parse_use_clause(stream) {
+                Ok(use_clause) => {
+                    context_clause.push(use_clause.map_into(ContextItem::Use));
+                },
                 Err(diagnostic) => diagnostics.push(diagnostic),
-            },
+            }
+        },
+        Context => match parse_context(stream, diagnostics) {
+            Ok(DeclarationOrReference::Declaration(context_decl)) => {
+                if !context_clause.is_empty() {
+                    let mut diagnostic = Diagnostic::error(&context_decl.ident, "Context declaration may not be preceeded by a context clause");
+
+                    for context_item in context_clause.iter() {
+                        diagnostic.add_related(context_item, context_item_message(&context_item.item, "may not come before context declaration"));
+                    }
 
-            Architecture => match parse_architecture_body(stream, diagnostics) {
-                Ok(mut architecture) => {
-                    architecture.context_clause = take_context_clause(&mut context_clause);
-                    design_units.push(AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(architecture)));
+                    diagnostics.push(diagnostic);
+                    context_clause.clear();
                 }
-                Err(diagnostic) => diagnostics.push(diagnostic),
-            },
 
-            Configuration => match parse_configuration_declaration(stream, diagnostics) {
-                Ok(mut configuration) => {
-                    configuration.context_clause = take_context_clause(&mut context_clause);
-                    design_units.push(AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(configuration)));
-                }
-                Err(diagnostic) => diagnostics.push(diagnostic),
-            },
-            Package => {
-                if stream.next_kinds_are(&[Package, Body])? {
-                    match parse_package_body(stream, diagnostics) {
-                        Ok(mut package_body) => {
-                            package_body.context_clause = take_context_clause(&mut context_clause);
-                            design_units.push(AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(package_body)));
-                        }
-                        Err(diagnostic) => diagnostics.push(diagnostic),
-                    };
-                } else if stream.next_kinds_are(&[Package, Identifier, Is, New])? {
-                    match parse_package_instantiation(stream) {
-                        Ok(mut inst) => {
-                            inst.context_clause = take_context_clause(&mut context_clause);
-                            design_units.push(AnyDesignUnit::Primary(AnyPrimaryUnit::PackageInstance(inst)))
-                        },
-                        Err(diagnostic) => diagnostics.push(diagnostic),
+                return Ok(Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Context(context_decl))));
+            }
+            Ok(DeclarationOrReference::Reference(context_ref)) => {
+                context_clause.push(context_ref.map_into(ContextItem::Context));
+            }
+            Err(diagnostic) => diagnostics.push(diagnostic),
+        },
+        Entity => match parse_entity_declaration(stream, diagnostics) {
+            Ok(mut entity) => {
+                entity.context_clause = take_context_clause(context_clause);
+                return Ok(Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity))));
+            }
+            Err(diagnostic) => diagnostics.push(diagnostic),
+        },
+
+        Architecture => match parse_architecture_body(stream, diagnostics) {
+            Ok(mut architecture) => {
+                architecture.context_clause = take_context_clause(context_clause);
+                return Ok(Some(AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(architecture))));
+            }
+            Err(diagnostic) => diagnostics.push(diagnostic),
+        },
+
+        Configuration => match parse_configuration_declaration(stream, diagnostics) {
+            Ok(mut configuration) => {
+                configuration.context_clause = take_context_clause(context_clause);
+                return Ok(Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(configuration))));
+            }
+            Err(diagnostic) => diagnostics.push(diagnostic),
+        },
+        Package => {
+            if stream.next_kinds_are(&[Package, Body])? {
+                match parse_package_body(stream, diagnostics) {
+                    Ok(mut package_body) => {
+                        package_body.context_clause = take_context_clause(context_clause);
+                        return Ok(Some(AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(package_body))));
                     }
-                } else {
-                    match parse_package_declaration(stream, diagnostics) {
-                        Ok(mut package) => {
-                            package.context_clause = take_context_clause(&mut context_clause);
-                            design_units.push(AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package)))
-                        }
-                        Err(diagnostic) => diagnostics.push(diagnostic),
-                    };
+                    Err(diagnostic) => diagnostics.push(diagnostic),
+                };
+            } else if stream.next_kinds_are(&[Package, Identifier, Is, New])? {
+                match parse_package_instantiation(stream) {
+                    Ok(mut inst) => {
+                        inst.context_clause = take_context_clause(context_clause);
+                        return Ok(Some(AnyDesignUnit::Primary(AnyPrimaryUnit::PackageInstance(inst))));
+                    },
+                    Err(diagnostic) => diagnostics.push(diagnostic),
                 }
+            } else {
+                match parse_package_declaration(stream, diagnostics) {
+                    Ok(mut package) => {
+                        package.context_clause = take_context_clause(context_clause);
+                        return Ok(Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package))));
+                    }
+                    Err(diagnostic) => diagnostics.push(diagnostic),
+                };
             }
-        );
-    }
-
-    for context_item in context_clause {
-        diagnostics.push(Diagnostic::warning(
-            &context_item,
-            context_item_message(&context_item.item, "not associated with any design unit"),
-        ));
-    }
+        }
+    );
 
-    Ok(DesignFile { design_units })
+    Ok(None)
 }
 
 #[cfg(test)]
@@ -5,7 +5,7 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::common::ParseResult;
-use super::names::{parse_name_initial_token, parse_type_mark};
+use super::names::{parse_generic_call, parse_name_initial_token, parse_type_mark};
 use super::subtype_indication::parse_subtype_constraint;
 use super::tokens::{Kind, Kind::*, Token, TokenStream};
 use crate::ast;
@@ -363,6 +363,7 @@ fn parse_primary_initial_token(
     match token.kind {
         Identifier | LtLt => {
             let name = parse_name_initial_token(stream, token)?;
+            let name = parse_generic_call(stream, name)?;
             if stream.skip_if_kind(Tick)? {
                 let lpar = stream.expect_kind(LeftPar)?;
                 let expr = parse_expression_or_aggregate(stream)?.combine_pos_with(&lpar);
@@ -380,10 +381,10 @@ fn parse_primary_initial_token(
         }
         BitString => Ok(token
             .expect_bit_string()?
-            .map_into(|bs| Expression::Literal(Literal::BitString(bs)))),
+            .map_into(|bs| Expression::Literal(ast::Literal::BitString(bs)))),
         Character => Ok(token
             .expect_character()?
-            .map_into(|chr| Expression::Literal(Literal::Character(chr)))),
+            .map_into(|chr| Expression::Literal(ast::Literal::Character(chr)))),
         StringLiteral => {
             if stream.peek_kind()? == Some(LeftPar) {
                 // Probably an function call via operator symbol "foo"()
@@ -392,11 +393,11 @@ fn parse_primary_initial_token(
             } else {
                 Ok(token
                     .expect_string()?
-                    .map_into(|string| Expression::Literal(Literal::String(string))))
+                    .map_into(|string| Expression::Literal(ast::Literal::String(string))))
             }
         }
         Null => Ok(WithPos {
-            item: Expression::Literal(Literal::Null),
+            item: Expression::Literal(ast::Literal::Null),
             pos: token.pos,
         }),
         New => {
@@ -419,11 +420,12 @@ fn parse_primary_initial_token(
                     unit: WithRef::new(unit),
                 };
                 Ok(WithPos {
-                    item: Expression::Literal(Literal::Physical(physical)),
+                    item: Expression::Literal(ast::Literal::Physical(physical)),
                     pos,
                 })
             } else {
-                Ok(value.map_into(|value| Expression::Literal(Literal::AbstractLiteral(value))))
+                Ok(value
+                    .map_into(|value| Expression::Literal(ast::Literal::AbstractLiteral(value))))
             }
         }
 
@@ -535,7 +537,7 @@ mod tests {
         assert_eq!(
             code.with_stream(parse_expression),
             WithPos {
-                item: Expression::Literal(Literal::Character(b'a')),
+                item: Expression::Literal(ast::Literal::Character(b'a')),
                 pos: code.pos()
             }
         );
@@ -547,7 +549,9 @@ mod tests {
         assert_eq!(
             code.with_stream(parse_expression),
             WithPos {
-                item: Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(71))),
+                item: Expression::Literal(ast::Literal::AbstractLiteral(AbstractLiteral::Integer(
+                    71
+                ))),
                 pos: code.pos()
             }
         );
@@ -559,7 +563,9 @@ mod tests {
         assert_eq!(
             code.with_stream(parse_expression),
             WithPos {
-                item: Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Real(7.1))),
+                item: Expression::Literal(ast::Literal::AbstractLiteral(AbstractLiteral::Real(
+                    7.1
+                ))),
                 pos: code.pos()
             }
         );
@@ -571,7 +577,7 @@ mod tests {
         assert_eq!(
             code.with_stream(parse_expression),
             WithPos {
-                item: Expression::Literal(Literal::String(Latin1String::from_utf8_unchecked(
+                item: Expression::Literal(ast::Literal::String(Latin1String::from_utf8_unchecked(
                     "string"
                 ))),
                 pos: code.pos()
@@ -607,7 +613,7 @@ mod tests {
         assert_eq!(
             code.with_stream(parse_expression),
             WithPos {
-                item: Expression::Literal(Literal::Null),
+                item: Expression::Literal(ast::Literal::Null),
                 pos: code.pos()
             }
         );
@@ -801,7 +807,7 @@ mod tests {
     fn parses_physical_unit_expression() {
         let code = Code::new("1 ns");
         let expr = WithPos {
-            item: Expression::Literal(Literal::Physical(PhysicalLiteral {
+            item: Expression::Literal(ast::Literal::Physical(PhysicalLiteral {
                 value: AbstractLiteral::Integer(1),
                 unit: code.s1("ns").ident().into_ref(),
             })),
@@ -815,7 +821,7 @@ mod tests {
     fn parses_physical_unit_expression_real() {
         let code = Code::new("1.0 ns");
         let expr = WithPos {
-            item: Expression::Literal(Literal::Physical(PhysicalLiteral {
+            item: Expression::Literal(ast::Literal::Physical(PhysicalLiteral {
                 value: AbstractLiteral::Real(1.0),
                 unit: code.s1("ns").ident().into_ref(),
             })),
@@ -829,7 +835,7 @@ mod tests {
     fn parses_physical_unit_expression_binary() {
         let code = Code::new("2 * 1 ns");
         let time_expr = WithPos {
-            item: Expression::Literal(Literal::Physical(PhysicalLiteral {
+            item: Expression::Literal(ast::Literal::Physical(PhysicalLiteral {
                 value: AbstractLiteral::Integer(1),
                 unit: code.s1("ns").ident().into_ref(),
             })),
@@ -854,7 +860,7 @@ mod tests {
     fn parses_physical_unit_expression_unary() {
         let code = Code::new("- 1 ns");
         let time_expr = WithPos {
-            item: Expression::Literal(Literal::Physical(PhysicalLiteral {
+            item: Expression::Literal(ast::Literal::Physical(PhysicalLiteral {
                 value: AbstractLiteral::Integer(1),
                 unit: code.s1("ns").ident().into_ref(),
             })),
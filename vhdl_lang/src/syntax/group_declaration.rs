@@ -0,0 +1,150 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
+
+use super::attributes::parse_entity_class;
+use super::common::ParseResult;
+use super::names::{parse_name, parse_selected_name};
+use super::tokens::{Kind::*, TokenStream};
+use crate::ast::{Declaration, EntityClassEntry, GroupDeclaration, GroupTemplateDeclaration};
+use crate::data::WithPos;
+
+/// LRM 6.9 Group template declarations
+fn parse_entity_class_entry(stream: &mut TokenStream) -> ParseResult<WithPos<EntityClassEntry>> {
+    let pos = stream.peek_expect()?.pos;
+    let entity_class = parse_entity_class(stream)?;
+
+    Ok(if let Some(box_token) = stream.pop_if_kind(BOX)? {
+        WithPos::new(
+            EntityClassEntry {
+                entity_class,
+                is_box: true,
+            },
+            pos.combine(&box_token.pos),
+        )
+    } else {
+        WithPos::new(
+            EntityClassEntry {
+                entity_class,
+                is_box: false,
+            },
+            pos,
+        )
+    })
+}
+
+/// LRM 6.9 Group template declarations
+/// LRM 6.10 Group declarations
+pub fn parse_group_declaration(stream: &mut TokenStream) -> ParseResult<Declaration> {
+    stream.expect_kind(Group)?;
+    let ident = stream.expect_ident()?;
+    let token = stream.expect()?;
+
+    Ok(try_token_kind!(
+        token,
+        Is => {
+            stream.expect_kind(LeftPar)?;
+            let mut entity_class_entries = vec![parse_entity_class_entry(stream)?];
+            while stream.skip_if_kind(Comma)? {
+                entity_class_entries.push(parse_entity_class_entry(stream)?);
+            }
+            stream.expect_kind(RightPar)?;
+            stream.expect_kind(SemiColon)?;
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: ident.into(),
+                entity_class_entries,
+            })
+        },
+        Colon => {
+            let group_template_name = parse_selected_name(stream)?;
+            stream.expect_kind(LeftPar)?;
+            let mut group_constituent_list = vec![parse_name(stream)?];
+            while stream.skip_if_kind(Comma)? {
+                group_constituent_list.push(parse_name(stream)?);
+            }
+            stream.expect_kind(RightPar)?;
+            stream.expect_kind(SemiColon)?;
+            Declaration::Group(GroupDeclaration {
+                ident: ident.into(),
+                group_template_name,
+                group_constituent_list,
+            })
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::EntityClass;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn parse_group_template_declaration() {
+        let code = Code::new("group pair is (signal, signal);");
+        assert_eq!(
+            code.with_stream(parse_group_declaration),
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: code.s1("pair").decl_ident(),
+                entity_class_entries: vec![
+                    WithPos::new(
+                        EntityClassEntry {
+                            entity_class: EntityClass::Signal,
+                            is_box: false,
+                        },
+                        code.s("signal", 1).pos()
+                    ),
+                    WithPos::new(
+                        EntityClassEntry {
+                            entity_class: EntityClass::Signal,
+                            is_box: false,
+                        },
+                        code.s("signal", 2).pos()
+                    )
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_group_template_declaration_with_box() {
+        let code = Code::new("group path is (label, literal <>);");
+        assert_eq!(
+            code.with_stream(parse_group_declaration),
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: code.s1("path").decl_ident(),
+                entity_class_entries: vec![
+                    WithPos::new(
+                        EntityClassEntry {
+                            entity_class: EntityClass::Label,
+                            is_box: false,
+                        },
+                        code.s1("label").pos()
+                    ),
+                    WithPos::new(
+                        EntityClassEntry {
+                            entity_class: EntityClass::Literal,
+                            is_box: true,
+                        },
+                        code.s1("literal <>").pos()
+                    )
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_group_declaration_with_constituents() {
+        let code = Code::new("group grp : lib.pkg.pair (sig, 'a');");
+        assert_eq!(
+            code.with_stream(parse_group_declaration),
+            Declaration::Group(GroupDeclaration {
+                ident: code.s1("grp").decl_ident(),
+                group_template_name: code.s1("lib.pkg.pair").selected_name(),
+                group_constituent_list: vec![code.s1("sig").name(), code.s1("'a'").name()],
+            })
+        );
+    }
+}
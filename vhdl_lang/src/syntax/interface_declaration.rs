@@ -10,6 +10,7 @@ use super::object_declaration::{parse_file_declaration_no_semi, parse_optional_a
 use super::subprogram::parse_subprogram_declaration_no_semi;
 use super::subtype_indication::parse_subtype_indication;
 use super::tokens::{Kind::*, *};
+use super::view_declaration::parse_mode_view_indication;
 /// LRM 6.5 Interface declarations
 use crate::ast::*;
 use crate::data::*;
@@ -93,6 +94,10 @@ fn parse_interface_object_declaration(
 
     stream.expect_kind(Colon)?;
 
+    if stream.peek_kind()? == Some(View) {
+        return parse_interface_view_declaration(stream, list_type, explicit_object_class, idents);
+    }
+
     let mode = parse_optional_mode(stream)?;
 
     let mode_pos = match mode {
@@ -155,6 +160,46 @@ fn parse_interface_object_declaration(
         .collect())
 }
 
+/// A port with a mode view indication instead of a mode and subtype
+fn parse_interface_view_declaration(
+    stream: &mut TokenStream,
+    list_type: InterfaceListType,
+    explicit_object_class: Option<ObjectClass>,
+    idents: Vec<Ident>,
+) -> ParseResult<Vec<InterfaceDeclaration>> {
+    let view_token = stream.peek_expect()?;
+    if list_type != InterfaceListType::Port {
+        return Err(Diagnostic::error(
+            view_token,
+            "Only ports may have a mode view indication",
+        ));
+    }
+    if !matches!(explicit_object_class, None | Some(ObjectClass::Signal)) {
+        return Err(Diagnostic::error(
+            view_token,
+            "Port list only allows signal object class",
+        ));
+    }
+
+    let view = parse_mode_view_indication(stream)?;
+    if let Some(token) = stream.pop_if_kind(ColonEq)? {
+        return Err(Diagnostic::error(
+            token,
+            "A port with a mode view indication cannot have a default value",
+        ));
+    }
+
+    Ok(idents
+        .into_iter()
+        .map(|ident| {
+            InterfaceDeclaration::View(InterfaceViewDeclaration {
+                ident: ident.into(),
+                view: view.clone(),
+            })
+        })
+        .collect())
+}
+
 fn parse_subprogram_default(stream: &mut TokenStream) -> ParseResult<Option<SubprogramDefault>> {
     if stream.skip_if_kind(Is)? {
         let token = stream.peek_expect()?;
@@ -117,7 +117,7 @@ fn expression_to_name(expr: WithPos<Expression>) -> ParseResult<WithPos<Name>> {
             item: *name,
             pos: expr.pos,
         }),
-        Expression::Literal(Literal::String(val)) => {
+        Expression::Literal(ast::Literal::String(val)) => {
             if let Some(op) = Operator::from_latin1(val) {
                 Ok(WithPos {
                     item: Name::Designator(Designator::OperatorSymbol(op).into_ref()),
@@ -127,7 +127,7 @@ fn expression_to_name(expr: WithPos<Expression>) -> ParseResult<WithPos<Name>> {
                 Err(Diagnostic::error(expr.pos, "Invalid operator symbol"))
             }
         }
-        Expression::Literal(Literal::Character(val)) => Ok(WithPos {
+        Expression::Literal(ast::Literal::Character(val)) => Ok(WithPos {
             item: Name::Designator(Designator::Character(val).into_ref()),
             pos: expr.pos,
         }),
@@ -189,6 +189,13 @@ pub fn parse_association_list(stream: &mut TokenStream) -> ParseResult<Vec<Assoc
 pub fn parse_association_list_no_leftpar(
     stream: &mut TokenStream,
 ) -> ParseResult<Vec<AssociationElement>> {
+    Ok(parse_association_list_rightpar(stream)?.0)
+}
+
+/// The association elements after the left parenthesis and the right parenthesis ending them
+fn parse_association_list_rightpar(
+    stream: &mut TokenStream,
+) -> ParseResult<(Vec<AssociationElement>, Token)> {
     let mut association_elements = Vec::with_capacity(1);
     loop {
         association_elements.push(parse_association_element(stream)?);
@@ -197,12 +204,54 @@ pub fn parse_association_list_no_leftpar(
             token,
             Comma => {},
             RightPar => {
-                return Ok(association_elements);
+                return Ok((association_elements, token));
             }
         )
     }
 }
 
+/// LRM 9.3.4 A call of an uninstantiated subprogram with a generic map aspect (VHDL-2019)
+/// where the parameters may be preceded by the optional `parameter map` keywords.
+/// The name is returned unchanged when it is not followed by a generic map aspect.
+pub fn parse_generic_call(
+    stream: &mut TokenStream,
+    name: WithPos<Name>,
+) -> ParseResult<WithPos<Name>> {
+    if !stream.next_kinds_are(&[Generic, Map])? {
+        return Ok(name);
+    }
+    stream.expect_kind(Generic)?;
+    stream.expect_kind(Map)?;
+    stream.expect_kind(LeftPar)?;
+    let (generic_map, mut end_token) = parse_association_list_rightpar(stream)?;
+
+    let has_parameter_map = if stream.skip_if_kind(Parameter)? {
+        stream.expect_kind(Map)?;
+        true
+    } else {
+        false
+    };
+
+    let parameters = if has_parameter_map || stream.peek_kind()? == Some(LeftPar) {
+        stream.expect_kind(LeftPar)?;
+        let (parameters, rpar_token) = parse_association_list_rightpar(stream)?;
+        end_token = rpar_token;
+        parameters
+    } else {
+        Vec::new()
+    };
+
+    let pos = end_token.pos.combine_into(&name);
+    Ok(WithPos::new(
+        Name::CallOrIndexed(Box::new(CallOrIndexed {
+            name,
+            generic_map: Some(generic_map),
+            parameters,
+        })),
+        pos,
+    ))
+}
+
 fn parse_function_call(
     stream: &mut TokenStream,
     prefix: WithPos<Name>,
@@ -222,6 +271,7 @@ fn parse_function_call(
                 return Ok(WithPos {
                     item: Name::CallOrIndexed(Box::new(CallOrIndexed {
                         name: prefix,
+                        generic_map: None,
                         parameters: association_elements})),
                     pos,
                 });
@@ -383,9 +433,10 @@ pub fn parse_name_initial_token(
             }
             LeftSquare => {
                 let state = stream.state();
-                let signature = Some(parse_signature(stream)?);
-                if !stream.skip_if_kind(Tick)? {
+                let signature = parse_signature(stream).ok();
+                if signature.is_none() || !stream.skip_if_kind(Tick)? {
                     // Alias may have prefix[signature] without tick
+                    // and PSL may have a repetition such as prefix[*2]
                     stream.set_state(state);
                     break;
                 }
@@ -437,6 +488,7 @@ pub fn parse_name_initial_token(
                             Ok(range) => Name::Slice(Box::new(name), Box::new(DiscreteRange::Range(range))),
                             Err(assoc) => Name::CallOrIndexed(Box::new(CallOrIndexed {
                                 name,
+                                generic_map: None,
                                 parameters: vec![assoc],
                             })),
                         };
@@ -922,6 +974,7 @@ mod tests {
         let foo_0 = WithPos {
             item: Name::CallOrIndexed(Box::new(CallOrIndexed {
                 name: foo,
+                generic_map: None,
                 parameters: vec![AssociationElement {
                     formal: None,
                     actual: code.s1("0").expr().map_into(ActualPart::Expression),
@@ -945,6 +998,7 @@ mod tests {
         let prefix_index = WithPos {
             item: Name::CallOrIndexed(Box::new(CallOrIndexed {
                 name: prefix,
+                generic_map: None,
                 parameters: vec![
                     AssociationElement {
                         formal: None,
@@ -962,6 +1016,7 @@ mod tests {
         let prefix_index_3 = WithPos {
             item: Name::CallOrIndexed(Box::new(CallOrIndexed {
                 name: prefix_index,
+                generic_map: None,
                 parameters: vec![AssociationElement {
                     formal: None,
                     actual: code.s1("3").expr().map_into(ActualPart::Expression),
@@ -1006,6 +1061,7 @@ mod tests {
         let foo_call = WithPos {
             item: Name::CallOrIndexed(Box::new(CallOrIndexed {
                 name: foo,
+                generic_map: None,
                 parameters: vec![assoc_elem],
             })),
             pos: code.s1("foo(arg => 0)").pos(),
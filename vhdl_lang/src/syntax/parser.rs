@@ -4,9 +4,11 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::design_unit::parse_design_file;
-use super::tokens::{Symbols, TokenStream, Tokenizer};
-use crate::ast::DesignFile;
+use super::common::ParseResult;
+use super::design_unit::{parse_design_file, parse_design_units, ParsedUnit, ParsedUnits};
+use super::expression::parse_expression;
+use super::tokens::{Kind, Symbols, TokenStream, Tokenizer, Value};
+use crate::ast::{DesignFile, Expression, ShiftLines};
 use crate::data::*;
 use std::io;
 use std::sync::Arc;
@@ -14,22 +16,113 @@ use std::sync::Arc;
 #[derive(Default)]
 pub struct VHDLParser {
     pub symbols: Arc<Symbols>,
+    /// Lines of non-standard preprocessors that are filtered before tokenizing
+    pub preprocessor: PreprocessorFilter,
+    /// The identifiers of the conditional analysis directives of VHDL-2019
+    pub conditional_analysis: ConditionalAnalysis,
+    /// Whether comments starting with `-- psl` contain embedded PSL
+    pub psl_comments: bool,
 }
 
 pub type ParserResult = Result<(Source, DesignFile), io::Error>;
 
+/// The design units of a file together with the lines they were parsed from, such that the
+/// units that a change of the file does not touch are reused when it is parsed again
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParsedDesignFile {
+    source: Source,
+    lines: Vec<String>,
+    units: Vec<ParsedUnit>,
+    /// The diagnostics after the last design unit
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ParsedDesignFile {
+    fn design_file(&self) -> DesignFile {
+        DesignFile {
+            design_units: self.units.iter().map(|unit| unit.unit.clone()).collect(),
+        }
+    }
+
+    fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.units
+            .iter()
+            .flat_map(|unit| unit.diagnostics.iter())
+            .chain(self.diagnostics.iter())
+    }
+}
+
 impl VHDLParser {
     pub fn symbol(&self, name: &Latin1String) -> Symbol {
         self.symbols.symtab().insert(name)
     }
 
+    /// The symbol of a name if it is a single basic identifier that is not a reserved word
+    pub fn basic_identifier(&self, name: &str) -> Option<Symbol> {
+        // Basic identifiers start with a letter and do not have adjacent or trailing underscores
+        if !name.starts_with(|chr: char| chr.is_ascii_alphabetic())
+            || name.ends_with('_')
+            || name.contains("__")
+        {
+            return None;
+        }
+        self.single_identifier(name)
+    }
+
+    /// The symbol of a name if it is a single basic identifier that is not a reserved word
+    /// or a single extended identifier such as `\Weird Name!\`
+    pub fn identifier(&self, name: &str) -> Option<Symbol> {
+        if name.starts_with('\\') {
+            self.single_identifier(name)
+        } else {
+            self.basic_identifier(name)
+        }
+    }
+
+    fn single_identifier(&self, name: &str) -> Option<Symbol> {
+        let source = Source::inline(Path::new("{identifier}"), name);
+        let contents = source.contents();
+        let mut tokenizer = Tokenizer::new(&self.symbols, &source, ContentReader::new(&contents));
+        let token = tokenizer.pop().ok()??;
+        if tokenizer.pop().ok()?.is_some() {
+            return None;
+        }
+        match (token.kind, token.value) {
+            (Kind::Identifier, Value::Identifier(symbol)) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// Parse a single expression such as the value of a generic given on the command line
+    pub fn expression(&self, text: &str) -> Option<WithPos<Expression>> {
+        let source = Source::inline(Path::new("{expression}"), text);
+        let contents = source.contents();
+        let tokenizer = Tokenizer::new(&self.symbols, &source, ContentReader::new(&contents));
+        let mut stream = TokenStream::new(tokenizer);
+        let expr = parse_expression(&mut stream).ok()?;
+        if stream.peek().ok()?.is_some() {
+            return None;
+        }
+        Some(expr)
+    }
+
     pub fn parse_design_source(
         &self,
         source: &Source,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> DesignFile {
+        // Only the directives are checked, the regions are looked up when fixes are collected
+        ProtectedRegions::parse(source, diagnostics);
+
+        let filtered = self.preprocessor.filter(source, diagnostics);
         let contents = source.contents();
-        let tokenizer = Tokenizer::new(&self.symbols, source, ContentReader::new(&contents));
+        let contents = filtered.as_ref().unwrap_or(&contents);
+        let analyzed = self
+            .conditional_analysis
+            .filter(source, contents, diagnostics);
+        let reader = ContentReader::new(analyzed.as_ref().unwrap_or(contents));
+        let tokenizer =
+            Tokenizer::new(&self.symbols, source, reader).with_psl_comments(self.psl_comments);
         let mut stream = TokenStream::new(tokenizer);
 
         match parse_design_file(&mut stream, diagnostics) {
@@ -41,13 +134,347 @@ impl VHDLParser {
         }
     }
 
+    /// Parse a source again after it was changed, reusing the design units of the previous parse
+    /// that are not within the changed lines. The positions of the units after the changed lines
+    /// are moved by the number of lines that were inserted or removed.
+    /// Returns the previous parse for the next change of a file with several design units,
+    /// contents that are filtered by the preprocessor or conditional analysis are always
+    /// parsed as a whole.
+    pub fn reparse_design_source(
+        &self,
+        source: &Source,
+        previous: Option<ParsedDesignFile>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> (DesignFile, Option<ParsedDesignFile>) {
+        ProtectedRegions::parse(source, diagnostics);
+
+        let filtered = self.preprocessor.filter(source, diagnostics);
+        let contents = source.contents();
+        let contents = filtered.as_ref().unwrap_or(&contents);
+        let analyzed = self
+            .conditional_analysis
+            .filter(source, contents, diagnostics);
+        if filtered.is_some() || analyzed.is_some() {
+            let contents = analyzed.as_ref().unwrap_or(contents);
+            let reader = ContentReader::new(contents);
+            let mut stream = TokenStream::new(
+                Tokenizer::new(&self.symbols, source, reader).with_psl_comments(self.psl_comments),
+            );
+            let design_file = match parse_design_file(&mut stream, diagnostics) {
+                Ok(design_file) => design_file,
+                Err(diagnostic) => {
+                    diagnostics.push(diagnostic);
+                    DesignFile::default()
+                }
+            };
+            return (design_file, None);
+        }
+
+        let parsed = previous
+            .and_then(|previous| self.reuse_units(source, contents, previous))
+            .unwrap_or_else(|| self.parse_units_of_file(source, contents));
+        for diagnostic in parsed.diagnostics() {
+            diagnostics.push(diagnostic.clone());
+        }
+        let design_file = parsed.design_file();
+        (
+            design_file,
+            Some(parsed).filter(|parsed| parsed.units.len() > 1),
+        )
+    }
+
+    fn parse_units_of_file(&self, source: &Source, contents: &Contents) -> ParsedDesignFile {
+        let lines = contents_lines(contents);
+        match self.parse_units(source, contents, Position::default(), None) {
+            Ok(parsed) => ParsedDesignFile {
+                source: source.clone(),
+                lines,
+                units: parsed.units,
+                diagnostics: parsed.diagnostics,
+            },
+            Err(diagnostic) => ParsedDesignFile {
+                source: source.clone(),
+                lines,
+                units: Vec::new(),
+                diagnostics: vec![diagnostic],
+            },
+        }
+    }
+
+    /// Parse the lines between the units of the previous parse that are not changed,
+    /// None when no unit can be reused or the tokens after the changed lines are not
+    /// those that the following units were parsed from, such as when a comment was opened
+    fn reuse_units(
+        &self,
+        source: &Source,
+        contents: &Contents,
+        mut previous: ParsedDesignFile,
+    ) -> Option<ParsedDesignFile> {
+        // The positions of the units refer to the contents they were parsed from
+        if !previous.source.shares_contents(source) {
+            return None;
+        }
+        let lines = contents_lines(contents);
+        let prefix = lines
+            .iter()
+            .zip(previous.lines.iter())
+            .take_while(|(line, old_line)| line == old_line)
+            .count();
+        if prefix == lines.len() && prefix == previous.lines.len() {
+            return Some(previous);
+        }
+        let before = previous
+            .units
+            .iter()
+            .take_while(|unit| (unit.end.line as usize) < prefix)
+            .count();
+
+        let suffix = lines[prefix..]
+            .iter()
+            .rev()
+            .zip(previous.lines[prefix..].iter().rev())
+            .take_while(|(line, old_line)| line == old_line)
+            .count();
+        let first_unchanged = previous.lines.len() - suffix;
+        let after = previous.units[before..]
+            .iter()
+            .rev()
+            .take_while(|unit| unit.start.line as usize >= first_unchanged)
+            .count();
+
+        // The units after the changed lines move with the lines that were inserted or removed
+        let delta = lines.len() as i64 - previous.lines.len() as i64;
+        if delta != 0 {
+            let first_after = previous.units.len() - after;
+            for unit in previous.units[first_after..].iter_mut() {
+                unit.shift_lines(delta);
+            }
+        }
+        self.reparse_between(source, contents, lines, previous, before, after)
+    }
+
+    /// Parse the contents between the first units and the last units of the previous parse
+    fn reparse_between(
+        &self,
+        source: &Source,
+        contents: &Contents,
+        lines: Vec<String>,
+        mut previous: ParsedDesignFile,
+        before: usize,
+        after: usize,
+    ) -> Option<ParsedDesignFile> {
+        if before == 0 && after == 0 {
+            return None;
+        }
+
+        let reused_after = previous.units.split_off(previous.units.len() - after);
+        let mut units = previous.units;
+        units.truncate(before);
+        let start = units.last().map(|unit| unit.end).unwrap_or_default();
+        let parsed = self
+            .parse_units(
+                source,
+                contents,
+                start,
+                reused_after.first().map(|unit| unit.start),
+            )
+            .ok()?;
+        units.extend(parsed.units);
+
+        let mut reused_after = reused_after.into_iter();
+        let diagnostics = if let Some(mut next) = reused_after.next() {
+            if parsed.next != Some(next.first) || !parsed.context_clause.is_empty() {
+                return None;
+            }
+            // Tokens that could not be parsed into a unit are reported with the next unit
+            if !parsed.diagnostics.is_empty() {
+                next.start = units.last().map(|unit| unit.end).unwrap_or(start);
+                next.diagnostics = parsed
+                    .diagnostics
+                    .into_iter()
+                    .chain(next.diagnostics)
+                    .collect();
+            }
+            units.push(next);
+            units.extend(reused_after);
+            previous.diagnostics
+        } else {
+            parsed.diagnostics
+        };
+
+        Some(ParsedDesignFile {
+            source: previous.source,
+            lines,
+            units,
+            diagnostics,
+        })
+    }
+
+    fn parse_units(
+        &self,
+        source: &Source,
+        contents: &Contents,
+        start: Position,
+        until: Option<Position>,
+    ) -> ParseResult<ParsedUnits> {
+        let mut reader = ContentReader::new(contents);
+        reader.seek_pos(start);
+        let mut stream = TokenStream::new(
+            Tokenizer::new(&self.symbols, source, reader).with_psl_comments(self.psl_comments),
+        );
+        parse_design_units(&mut stream, until)
+    }
+
     pub fn parse_design_file(
         &self,
         file_name: &Path,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> ParserResult {
-        let source = Source::from_latin1_file(file_name)?;
+        let source = if LibraryArchive::is_archive(file_name) {
+            Source::inline(file_name, &LibraryArchive::read(file_name)?.contents())
+        } else {
+            Source::from_latin1_file(file_name)?
+        };
         let design_file = self.parse_design_source(&source, diagnostics);
         Ok((source, design_file))
     }
 }
+
+fn contents_lines(contents: &Contents) -> Vec<String> {
+    (0..contents.num_lines())
+        .filter_map(|lineno| contents.get_line(lineno))
+        .map(str::to_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = "\
+entity ent1 is
+end entity;
+
+entity ent2 is
+end entity;
+
+-- The last entity
+entity ent3 is -- */
+end entity;
+";
+
+    /// Parse the code after a change both reusing the units of the previous parse and as a whole
+    fn reparse(
+        range: Range,
+        text: &str,
+    ) -> (Option<ParsedDesignFile>, ParsedDesignFile, ParsedDesignFile) {
+        let parser = VHDLParser::default();
+        let source = Source::inline(Path::new("file.vhd"), CODE);
+        let previous = parser.parse_units_of_file(&source, &source.contents());
+        source.change(Some(&range), text);
+        let contents = source.contents();
+        (
+            parser.reuse_units(&source, &contents, previous.clone()),
+            parser.parse_units_of_file(&source, &contents),
+            previous,
+        )
+    }
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+        Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+    }
+
+    #[test]
+    fn reuses_units_before_and_after_changed_line() {
+        let (reused, parsed, previous) = reparse(range((3, 7), (3, 11)), "ent22");
+        let reused = reused.unwrap();
+        assert_eq!(reused, parsed);
+        assert_eq!(reused.units[0], previous.units[0]);
+        assert_eq!(reused.units[2], previous.units[2]);
+    }
+
+    #[test]
+    fn reuses_units_before_changed_line_when_lines_are_added() {
+        let (reused, parsed, previous) = reparse(range((4, 0), (4, 0)), "  port (a : bit);\n");
+        let reused = reused.unwrap();
+        assert_eq!(reused, parsed);
+        assert_eq!(reused.units[0], previous.units[0]);
+        assert_eq!(reused.design_file().design_units.len(), 3);
+    }
+
+    #[test]
+    fn reuses_units_after_changed_line_when_lines_are_added() {
+        let parser = VHDLParser::default();
+        let source = Source::inline(Path::new("file.vhd"), CODE);
+        let mut previous = parser.parse_units_of_file(&source, &source.contents());
+        // Only a unit that is reused keeps the diagnostic
+        let hint = Diagnostic::hint(SrcPos::new(source.clone(), range((7, 0), (7, 6))), "reused");
+        previous.units[2].diagnostics.push(hint.clone());
+
+        source.change(Some(&range((1, 0), (1, 0))), "  port (a : bit);\n");
+        let contents = source.contents();
+        let reused = parser
+            .reuse_units(&source, &contents, previous.clone())
+            .unwrap();
+        let parsed = parser.parse_units_of_file(&source, &contents);
+
+        let mut moved = hint;
+        moved.shift_lines(1);
+        assert_eq!(reused.units[2].diagnostics, vec![moved]);
+        assert_eq!(reused.units[2].unit, parsed.units[2].unit);
+        assert_eq!(reused.units[2].first, parsed.units[2].first);
+        assert_eq!(reused.design_file(), parsed.design_file());
+    }
+
+    #[test]
+    fn moves_else_branch_of_reused_conditional_assignment() {
+        let parser = VHDLParser::default();
+        let code = "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s, t : bit;
+begin
+  s <= '1' when t = '1' else '0';
+end architecture;
+";
+        let source = Source::inline(Path::new("file.vhd"), code);
+        let previous = parser.parse_units_of_file(&source, &source.contents());
+
+        source.change(Some(&range((1, 0), (1, 0))), "  port (a : bit);\n");
+        let contents = source.contents();
+        let reused = parser.reuse_units(&source, &contents, previous).unwrap();
+        let parsed = parser.parse_units_of_file(&source, &contents);
+        assert_eq!(reused.units[1].unit, parsed.units[1].unit);
+    }
+
+    #[test]
+    fn reuses_units_after_changed_lines_when_lines_are_removed() {
+        let (reused, parsed, previous) = reparse(range((2, 0), (5, 0)), "");
+        let reused = reused.unwrap();
+        assert_eq!(reused, parsed);
+        assert_eq!(reused.units[1].first.line, previous.units[2].first.line - 3);
+        assert_eq!(reused.design_file().design_units.len(), 2);
+    }
+
+    #[test]
+    fn reports_syntax_error_of_changed_unit() {
+        let (reused, parsed, _) = reparse(range((4, 10), (4, 10)), " ent9");
+        let reused = reused.unwrap();
+        assert_eq!(reused, parsed);
+        assert_eq!(reused.design_file().design_units.len(), 3);
+        assert_eq!(
+            reused.diagnostics().collect::<Vec<_>>(),
+            parsed.diagnostics().collect::<Vec<_>>()
+        );
+        assert!(reused.diagnostics().next().is_some());
+    }
+
+    #[test]
+    fn does_not_reuse_units_within_opened_comment() {
+        let (reused, parsed, _) = reparse(range((4, 11), (4, 11)), " /*");
+        assert_eq!(reused, None);
+        assert_ne!(parsed.design_file().design_units.len(), 3);
+    }
+}
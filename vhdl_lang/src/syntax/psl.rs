@@ -0,0 +1,382 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! LRM 11.1 PSL directives and declarations of IEEE 1850 embedded in VHDL
+//!
+//! Only the HDL expressions of a property are kept which is what is analyzed. The temporal
+//! operators are therefore parsed without regard to their precedence. PSL keywords that are
+//! not reserved words of VHDL are identifiers to the tokenizer.
+
+use super::common::{parse_optional, ParseResult};
+use super::concurrent_statement::parse_concurrent_assert_statement;
+use super::expression::parse_expression;
+use super::tokens::{Kind::*, Token, TokenStream, Value};
+use crate::ast::*;
+use crate::data::*;
+
+/// Built-in functions whose arguments may be of any type
+const BUILTIN_FUNCTIONS: &[&str] = &[
+    "rose",
+    "fell",
+    "stable",
+    "prev",
+    "onehot",
+    "onehot0",
+    "isunknown",
+    "countones",
+];
+
+fn is_psl_keyword(token: &Token, keywords: &[&str]) -> bool {
+    match token.value {
+        Value::Identifier(ref sym) if token.kind == Identifier => {
+            keywords.iter().any(|keyword| sym.eq_basic(keyword))
+        }
+        _ => false,
+    }
+}
+
+fn skip_if_psl_keyword(stream: &mut TokenStream, keywords: &[&str]) -> ParseResult<bool> {
+    if let Some(token) = stream.peek()? {
+        if is_psl_keyword(&token, keywords) {
+            stream.move_after(&token);
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// The kind of directive an identifier starts if it is assume or cover and not
+/// the start of a procedure call or signal assignment
+pub fn psl_directive_kind(
+    stream: &mut TokenStream,
+    token: &Token,
+) -> ParseResult<Option<PslDirectiveKind>> {
+    let kind = if is_psl_keyword(token, &["assume"]) {
+        PslDirectiveKind::Assume
+    } else if is_psl_keyword(token, &["cover"]) {
+        PslDirectiveKind::Cover
+    } else {
+        return Ok(None);
+    };
+
+    match stream.peek_kind()? {
+        Some(Colon | SemiColon | LTE | Dot | Tick | LeftPar | LeftSquare | Generic | Port)
+        | None => Ok(None),
+        Some(_) => Ok(Some(kind)),
+    }
+}
+
+/// PSL clock declaration `default clock is ...;`
+pub fn parse_psl_clock_declaration(stream: &mut TokenStream) -> ParseResult<WithPos<Expression>> {
+    stream.expect_kind(Default)?;
+    let token = stream.expect()?;
+    if !is_psl_keyword(&token, &["clock"]) {
+        return Err(Diagnostic::error(&token.pos, "Expected 'clock'"));
+    }
+    stream.expect_kind(Is)?;
+    let clock = parse_expression(stream)?;
+    stream.expect_kind(SemiColon)?;
+    Ok(clock)
+}
+
+/// A concurrent assertion statement or a PSL assert directive when the condition is a property
+pub fn parse_concurrent_assert_or_psl_directive(
+    stream: &mut TokenStream,
+) -> ParseResult<ConcurrentStatement> {
+    let state = stream.state();
+    let err = match parse_concurrent_assert_statement(stream, false) {
+        Ok(assert) => return Ok(ConcurrentStatement::Assert(assert)),
+        Err(err) => err,
+    };
+    let err_state = stream.state();
+    stream.set_state(state);
+
+    match parse_psl_directive_known_kind(stream, PslDirectiveKind::Assert) {
+        Ok(directive) => Ok(ConcurrentStatement::PslDirective(directive)),
+        // Report the error of the parse that got the furthest
+        Err(psl_err) if psl_err.pos.start() > err.pos.start() => Err(psl_err),
+        Err(_) => {
+            stream.set_state(err_state);
+            Err(err)
+        }
+    }
+}
+
+/// LRM 11.1 PSL directive after the assert, assume or cover keyword
+pub fn parse_psl_directive_known_kind(
+    stream: &mut TokenStream,
+    kind: PslDirectiveKind,
+) -> ParseResult<PslDirective> {
+    let mut property = PslProperty::default();
+    parse_property(stream, &mut property)?;
+    let report = parse_optional(stream, Report, parse_expression)?;
+    let severity = parse_optional(stream, Severity, parse_expression)?;
+    stream.expect_kind(SemiColon)?;
+
+    Ok(PslDirective {
+        kind,
+        property,
+        report,
+        severity,
+    })
+}
+
+fn parse_property(stream: &mut TokenStream, property: &mut PslProperty) -> ParseResult<()> {
+    loop {
+        parse_operand(stream, property)?;
+        if !skip_if_binary_operator(stream)? {
+            return Ok(());
+        }
+    }
+}
+
+fn skip_if_binary_operator(stream: &mut TokenStream) -> ParseResult<bool> {
+    let Some(token) = stream.peek()? else {
+        return Ok(false);
+    };
+
+    match token.kind {
+        // Logical implication -> and <-> as well as and, or and within of properties and sequences
+        Implies | Iff | And | Or => {
+            stream.move_after(&token);
+        }
+        // Suffix implication |-> and |=> or sequence disjunction |
+        Bar => {
+            stream.move_after(&token);
+            if !stream.skip_if_kind(Implies)? {
+                stream.skip_if_kind(RightArrow)?;
+            }
+        }
+        // Sequence conjunction & and &&
+        Concat => {
+            stream.move_after(&token);
+            stream.skip_if_kind(Concat)?;
+        }
+        Until => {
+            stream.move_after(&token);
+            stream.skip_if_kind(Bang)?;
+        }
+        _ => {
+            if !skip_if_psl_keyword(
+                stream,
+                &[
+                    "until_",
+                    "before",
+                    "before_",
+                    "within",
+                    "abort",
+                    "async_abort",
+                    "sync_abort",
+                ],
+            )? {
+                return Ok(false);
+            }
+            stream.skip_if_kind(Bang)?;
+        }
+    }
+    Ok(true)
+}
+
+fn parse_operand(stream: &mut TokenStream, property: &mut PslProperty) -> ParseResult<()> {
+    let token = stream.peek_expect()?;
+
+    match token.kind {
+        Next => {
+            stream.move_after(&token);
+            stream.skip_if_kind(Bang)?;
+            if stream.skip_if_kind(LeftSquare)? {
+                parse_count(stream, property)?;
+                stream.expect_kind(RightSquare)?;
+            }
+            parse_operand(stream, property)?;
+        }
+        LeftBrace => {
+            stream.move_after(&token);
+            parse_sere(stream, property)?;
+            stream.skip_if_kind(Bang)?;
+        }
+        LeftPar => parse_parenthesized(stream, property)?,
+        _ if is_psl_keyword(&token, &["always", "never", "eventually"]) => {
+            stream.move_after(&token);
+            stream.skip_if_kind(Bang)?;
+            parse_operand(stream, property)?;
+        }
+        _ if is_psl_keyword(&token, &["next_a", "next_e"]) => {
+            stream.move_after(&token);
+            stream.skip_if_kind(Bang)?;
+            stream.expect_kind(LeftSquare)?;
+            parse_count(stream, property)?;
+            stream.expect_kind(RightSquare)?;
+            parse_operand(stream, property)?;
+        }
+        _ if is_psl_keyword(&token, &["next_event", "next_event_a", "next_event_e"]) => {
+            stream.move_after(&token);
+            stream.skip_if_kind(Bang)?;
+            stream.expect_kind(LeftPar)?;
+            property.booleans.push(parse_expression(stream)?);
+            stream.expect_kind(RightPar)?;
+            if stream.skip_if_kind(LeftSquare)? {
+                parse_count(stream, property)?;
+                stream.expect_kind(RightSquare)?;
+            }
+            parse_operand(stream, property)?;
+        }
+        _ if is_psl_keyword(&token, BUILTIN_FUNCTIONS)
+            && stream.next_kinds_are(&[Identifier, LeftPar])? =>
+        {
+            stream.move_after(&token);
+            stream.expect_kind(LeftPar)?;
+            loop {
+                property.values.push(parse_expression(stream)?);
+                if !stream.skip_if_kind(Comma)? {
+                    break;
+                }
+            }
+            stream.expect_kind(RightPar)?;
+        }
+        _ => property.booleans.push(parse_expression(stream)?),
+    }
+
+    parse_suffixes(stream, property)
+}
+
+/// A parenthesized HDL expression or property
+fn parse_parenthesized(stream: &mut TokenStream, property: &mut PslProperty) -> ParseResult<()> {
+    let state = stream.state();
+    if let Ok(expr) = parse_expression(stream) {
+        property.booleans.push(expr);
+        return Ok(());
+    }
+    stream.set_state(state);
+
+    stream.expect_kind(LeftPar)?;
+    parse_property(stream, property)?;
+    stream.expect_kind(RightPar)?;
+    Ok(())
+}
+
+/// The sequences of a braced SERE that are concatenated with ; or fused with :
+fn parse_sere(stream: &mut TokenStream, property: &mut PslProperty) -> ParseResult<()> {
+    loop {
+        parse_property(stream, property)?;
+        if !(stream.skip_if_kind(SemiColon)? || stream.skip_if_kind(Colon)?) {
+            break;
+        }
+    }
+    stream.expect_kind(RightBrace)?;
+    Ok(())
+}
+
+/// Repetitions such as [*2 to 3], [+], [=2] or [->] and clocking with @
+fn parse_suffixes(stream: &mut TokenStream, property: &mut PslProperty) -> ParseResult<()> {
+    loop {
+        if stream.next_kinds_are(&[LeftSquare, Plus, RightSquare])? {
+            for _ in 0..3 {
+                stream.expect()?;
+            }
+        } else if stream.next_kinds_are(&[LeftSquare, Times])?
+            || stream.next_kinds_are(&[LeftSquare, EQ])?
+            || stream.next_kinds_are(&[LeftSquare, Implies])?
+        {
+            stream.expect()?;
+            stream.expect()?;
+            if !stream.skip_if_kind(RightSquare)? {
+                parse_count(stream, property)?;
+                stream.expect_kind(RightSquare)?;
+            }
+        } else if stream.skip_if_kind(CommAt)? {
+            property.clocks.push(parse_expression(stream)?);
+        } else {
+            return Ok(());
+        }
+    }
+}
+
+/// A count `n` or a range `n to m` or `n to inf`
+fn parse_count(stream: &mut TokenStream, property: &mut PslProperty) -> ParseResult<()> {
+    property.counts.push(parse_expression(stream)?);
+    if stream.skip_if_kind(To)? && !skip_if_psl_keyword(stream, &["inf"])? {
+        property.counts.push(parse_expression(stream)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    fn directive(code: &Code, kind: PslDirectiveKind) -> PslDirective {
+        code.with_stream(|stream| {
+            stream.expect()?;
+            parse_psl_directive_known_kind(stream, kind)
+        })
+    }
+
+    #[test]
+    fn parse_clock_declaration() {
+        let code = Code::new("default clock is rising_edge(clk);");
+        assert_eq!(
+            code.with_stream(parse_psl_clock_declaration),
+            code.s1("rising_edge(clk)").expr()
+        );
+    }
+
+    #[test]
+    fn parse_assert_directive() {
+        let code =
+            Code::new("assert always (req -> next[2] ack) @ rising_edge(clk) report \"fail\";");
+        assert_eq!(
+            directive(&code, PslDirectiveKind::Assert),
+            PslDirective {
+                kind: PslDirectiveKind::Assert,
+                property: PslProperty {
+                    booleans: vec![code.s1("req").expr(), code.s1("ack").expr()],
+                    values: vec![],
+                    counts: vec![code.s1("2").expr()],
+                    clocks: vec![code.s1("rising_edge(clk)").expr()],
+                },
+                report: Some(code.s1("\"fail\"").expr()),
+                severity: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_cover_sequence() {
+        let code = Code::new("cover {start; busy[*1 to inf]; rose(done)} report \"done\";");
+        assert_eq!(
+            directive(&code, PslDirectiveKind::Cover),
+            PslDirective {
+                kind: PslDirectiveKind::Cover,
+                property: PslProperty {
+                    booleans: vec![code.s1("start").expr(), code.s1("busy").expr()],
+                    values: vec![code.s1("done").expr()],
+                    counts: vec![code.s1("1").expr()],
+                    clocks: vec![],
+                },
+                report: Some(code.s1("\"done\"").expr()),
+                severity: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_suffix_implication_and_until() {
+        let code = Code::new("assume always {req} |=> (busy until! done);");
+        assert_eq!(
+            directive(&code, PslDirectiveKind::Assume).property,
+            PslProperty {
+                booleans: vec![
+                    code.s1("req").expr(),
+                    code.s1("busy").expr(),
+                    code.s1("done").expr()
+                ],
+                ..PslProperty::default()
+            }
+        );
+    }
+}
@@ -7,7 +7,7 @@
 use super::common::parse_optional;
 use super::common::ParseResult;
 use super::expression::{parse_aggregate_leftpar_known, parse_choices, parse_expression};
-use super::names::{parse_name, parse_name_initial_token};
+use super::names::{parse_generic_call, parse_name, parse_name_initial_token};
 use super::range::parse_discrete_range;
 use super::tokens::{Kind::*, Token, TokenStream};
 use super::waveform::{parse_delay_mechanism, parse_waveform};
@@ -15,7 +15,10 @@ use crate::ast::*;
 use crate::data::*;
 
 /// LRM 10.2 Wait statement
-fn parse_wait_statement_known_keyword(stream: &mut TokenStream) -> ParseResult<WaitStatement> {
+fn parse_wait_statement_known_keyword(
+    initial: Token,
+    stream: &mut TokenStream,
+) -> ParseResult<WithPos<WaitStatement>> {
     let mut sensitivity_clause = vec![];
     if stream.skip_if_kind(On)? {
         loop {
@@ -29,12 +32,15 @@ fn parse_wait_statement_known_keyword(stream: &mut TokenStream) -> ParseResult<W
     let condition_clause = parse_optional(stream, Until, parse_expression)?;
     let timeout_clause = parse_optional(stream, For, parse_expression)?;
 
-    stream.expect_kind(SemiColon)?;
-    Ok(WaitStatement {
-        sensitivity_clause,
-        condition_clause,
-        timeout_clause,
-    })
+    let semi = stream.expect_kind(SemiColon)?;
+    Ok(WithPos::new(
+        WaitStatement {
+            sensitivity_clause,
+            condition_clause,
+            timeout_clause,
+        },
+        initial.pos.combine_into(&semi.pos),
+    ))
 }
 
 /// LRM 10.3 Assertion statement
@@ -271,7 +277,16 @@ fn parse_return_statement_known_keyword(
         if stream.peek_kind()? == Some(SemiColon) {
             None
         } else {
-            Some(parse_expression(stream)?)
+            let expr = parse_expression(stream)?;
+            if stream.skip_if_kind(When)? {
+                Some(AssignmentRightHand::Conditional(parse_conditonals(
+                    stream,
+                    expr,
+                    parse_expression,
+                )?))
+            } else {
+                Some(AssignmentRightHand::Simple(expr))
+            }
         }
     };
     let semi = stream.expect_kind(SemiColon)?;
@@ -308,8 +323,9 @@ where
     match_token_kind!(
         token,
         When => {
-            Ok(AssignmentRightHand::Conditional(parse_conditonals(stream, item, parse_item)?))
-
+            let conditionals = parse_conditonals(stream, item, parse_item)?;
+            stream.expect_kind(SemiColon)?;
+            Ok(AssignmentRightHand::Conditional(conditionals))
         },
         SemiColon => {
             Ok(AssignmentRightHand::Simple(item))
@@ -335,15 +351,16 @@ where
     let mut else_item = None;
 
     loop {
-        let token = stream.expect()?;
+        let token = stream.peek_expect()?;
         try_token_kind!(
             token,
             SemiColon => {
                 break;
             },
             Else => {
+                stream.move_after(&token);
                 let item = parse_item(stream)?;
-                let token = stream.expect()?;
+                let token = stream.peek_expect()?;
                 try_token_kind!(
                     token,
                     SemiColon =>  {
@@ -351,6 +368,7 @@ where
                         break;
                     },
                     When => {
+                        stream.move_after(&token);
                         let condition = parse_expression(stream)?;
                         let conditional = Conditional {
                             condition,
@@ -469,6 +487,7 @@ fn parse_assignment_or_procedure_call(
                     SequentialStatement::ProcedureCall(
                         WithPos::new(CallOrIndexed {
                             name: WithPos::from(name, target.pos.clone()),
+                            generic_map: None,
                             parameters: vec![]
                         }, target.pos))
                 }
@@ -487,7 +506,8 @@ fn parse_target_initial_token(
     if token.kind == LeftPar {
         Ok(parse_aggregate_leftpar_known(stream)?.map_into(Target::Aggregate))
     } else {
-        Ok(parse_name_initial_token(stream, token)?.map_into(Target::Name))
+        let name = parse_name_initial_token(stream, token)?;
+        Ok(parse_generic_call(stream, name)?.map_into(Target::Name))
     }
 }
 
@@ -536,7 +556,7 @@ fn parse_unlabeled_sequential_statement(
     let statement = {
         try_init_token_kind!(
             token,
-            Wait => SequentialStatement::Wait(parse_wait_statement_known_keyword(stream)?),
+            Wait => SequentialStatement::Wait(parse_wait_statement_known_keyword(token, stream)?),
             Assert => SequentialStatement::Assert(parse_assert_statement_known_keyword(stream)?),
             Report => SequentialStatement::Report(parse_report_statement_known_keyword(stream)?),
             If => SequentialStatement::If(parse_if_statement_known_keyword(stream, diagnostics)?),
@@ -580,6 +600,7 @@ pub fn parse_sequential_statement_initial_token(
 ) -> ParseResult<LabeledSequentialStatement> {
     if token.kind == Identifier {
         let name = parse_name_initial_token(stream, token)?;
+        let name = parse_generic_call(stream, name)?;
         let token = stream.expect()?;
         if token.kind == Colon {
             let label = Some(WithDecl::new(to_simple_name(name)?));
@@ -629,16 +650,19 @@ mod tests {
 
     #[test]
     fn parse_simple_wait_statement() {
-        let (_, statement) = parse("wait;");
+        let (code, statement) = parse("wait;");
         assert_eq!(
             statement,
             with_label(
                 None,
-                SequentialStatement::Wait(WaitStatement {
-                    sensitivity_clause: vec![],
-                    condition_clause: None,
-                    timeout_clause: None
-                })
+                SequentialStatement::Wait(WithPos::new(
+                    WaitStatement {
+                        sensitivity_clause: vec![],
+                        condition_clause: None,
+                        timeout_clause: None
+                    },
+                    code.pos()
+                ))
             )
         );
     }
@@ -650,11 +674,14 @@ mod tests {
             statement,
             with_label(
                 Some(code.s1("foo").decl_ident()),
-                SequentialStatement::Wait(WaitStatement {
-                    sensitivity_clause: vec![],
-                    condition_clause: None,
-                    timeout_clause: None
-                })
+                SequentialStatement::Wait(WithPos::new(
+                    WaitStatement {
+                        sensitivity_clause: vec![],
+                        condition_clause: None,
+                        timeout_clause: None
+                    },
+                    code.s1("wait;").pos()
+                ))
             )
         );
     }
@@ -666,11 +693,14 @@ mod tests {
             statement,
             with_label(
                 None,
-                SequentialStatement::Wait(WaitStatement {
-                    sensitivity_clause: vec![code.s1("foo").name(), code.s1("bar").name()],
-                    condition_clause: None,
-                    timeout_clause: None
-                })
+                SequentialStatement::Wait(WithPos::new(
+                    WaitStatement {
+                        sensitivity_clause: vec![code.s1("foo").name(), code.s1("bar").name()],
+                        condition_clause: None,
+                        timeout_clause: None
+                    },
+                    code.pos()
+                ))
             )
         );
     }
@@ -682,11 +712,14 @@ mod tests {
             statement,
             with_label(
                 None,
-                SequentialStatement::Wait(WaitStatement {
-                    sensitivity_clause: vec![],
-                    condition_clause: Some(code.s1("a = b").expr()),
-                    timeout_clause: None
-                })
+                SequentialStatement::Wait(WithPos::new(
+                    WaitStatement {
+                        sensitivity_clause: vec![],
+                        condition_clause: Some(code.s1("a = b").expr()),
+                        timeout_clause: None
+                    },
+                    code.pos()
+                ))
             )
         );
     }
@@ -698,11 +731,14 @@ mod tests {
             statement,
             with_label(
                 None,
-                SequentialStatement::Wait(WaitStatement {
-                    sensitivity_clause: vec![],
-                    condition_clause: None,
-                    timeout_clause: Some(code.s1("2 ns").expr())
-                })
+                SequentialStatement::Wait(WithPos::new(
+                    WaitStatement {
+                        sensitivity_clause: vec![],
+                        condition_clause: None,
+                        timeout_clause: Some(code.s1("2 ns").expr())
+                    },
+                    code.pos()
+                ))
             )
         );
     }
@@ -714,11 +750,14 @@ mod tests {
             statement,
             with_label(
                 None,
-                SequentialStatement::Wait(WaitStatement {
-                    sensitivity_clause: vec![code.s1("foo").name()],
-                    condition_clause: Some(code.s1("bar").expr()),
-                    timeout_clause: Some(code.s1("2 ns").expr())
-                })
+                SequentialStatement::Wait(WithPos::new(
+                    WaitStatement {
+                        sensitivity_clause: vec![code.s1("foo").name()],
+                        condition_clause: Some(code.s1("bar").expr()),
+                        timeout_clause: Some(code.s1("2 ns").expr())
+                    },
+                    code.pos()
+                ))
             )
         );
     }
@@ -1222,6 +1261,47 @@ with x(0) + 1 select
         );
     }
 
+    #[test]
+    fn parse_procedure_call_with_generic_map() {
+        let (code, statement) = parse("swap generic map (T => natural) parameter map (a, b);");
+
+        assert_eq!(
+            statement,
+            with_label(
+                None,
+                SequentialStatement::ProcedureCall(WithPos::new(
+                    CallOrIndexed {
+                        name: code.s1("swap").name(),
+                        generic_map: Some(code.s1("(T => natural)").association_list()),
+                        parameters: code.s1("(a, b)").association_list(),
+                    },
+                    code.s1("swap generic map (T => natural) parameter map (a, b)")
+                        .pos()
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn parse_procedure_call_with_generic_map_and_no_parameters() {
+        let (code, statement) = parse("report_type generic map (T => natural);");
+
+        assert_eq!(
+            statement,
+            with_label(
+                None,
+                SequentialStatement::ProcedureCall(WithPos::new(
+                    CallOrIndexed {
+                        name: code.s1("report_type").name(),
+                        generic_map: Some(code.s1("(T => natural)").association_list()),
+                        parameters: vec![],
+                    },
+                    code.s1("report_type generic map (T => natural)").pos()
+                ))
+            )
+        );
+    }
+
     #[test]
     fn parse_simple_if_statement() {
         let (code, statement) = parse(
@@ -1685,7 +1765,31 @@ end loop;
                 None,
                 SequentialStatement::Return(WithPos::new(
                     ReturnStatement {
-                        expression: Some(code.s1("1 + 2").expr()),
+                        expression: Some(AssignmentRightHand::Simple(code.s1("1 + 2").expr())),
+                    },
+                    code.pos()
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn parse_conditional_return_statement() {
+        let code = Code::new("return 1 when cond else 2;");
+        let statement = parse_stmt(&code);
+        assert_eq!(
+            statement,
+            with_label(
+                None,
+                SequentialStatement::Return(WithPos::new(
+                    ReturnStatement {
+                        expression: Some(AssignmentRightHand::Conditional(Conditionals {
+                            conditionals: vec![Conditional {
+                                condition: code.s1("cond").expr(),
+                                item: code.s1("1").expr(),
+                            }],
+                            else_item: Some(code.s1("2").expr()),
+                        })),
                     },
                     code.pos()
                 ))
@@ -6,8 +6,8 @@
 
 use super::common::ParseResult;
 use super::declarative_part::parse_declarative_part;
-use super::interface_declaration::parse_parameter_interface_list;
-use super::names::parse_type_mark;
+use super::interface_declaration::{parse_generic_interface_list, parse_parameter_interface_list};
+use super::names::{parse_association_list, parse_selected_name, parse_type_mark};
 use super::sequential_statement::parse_labeled_sequential_statements;
 use super::tokens::{Kind::*, TokenStream};
 use crate::ast::*;
@@ -102,8 +102,18 @@ pub fn parse_subprogram_declaration_no_semi(
 
     let designator = parse_designator(stream)?;
 
+    let header = {
+        if stream.skip_if_kind(Generic)? {
+            Some(SubprogramHeader {
+                generic_list: parse_generic_interface_list(stream, diagnostics)?,
+            })
+        } else {
+            None
+        }
+    };
+
     let parameter_list = {
-        if stream.peek_kind()? == Some(LeftPar) {
+        if stream.skip_if_kind(Parameter)? || stream.peek_kind()? == Some(LeftPar) {
             parse_parameter_interface_list(stream, diagnostics)?
         } else {
             Vec::new()
@@ -116,12 +126,14 @@ pub fn parse_subprogram_declaration_no_semi(
         Ok(SubprogramDeclaration::Function(FunctionSpecification {
             pure: is_pure,
             designator: designator.into(),
+            header,
             parameter_list,
             return_type,
         }))
     } else {
         Ok(SubprogramDeclaration::Procedure(ProcedureSpecification {
             designator: designator.into(),
+            header,
             parameter_list,
         }))
     }
@@ -151,6 +163,7 @@ pub fn parse_subprogram_body(
     let declarations = parse_declarative_part(stream, diagnostics, true)?;
 
     let (statements, end_token) = parse_labeled_sequential_statements(stream, diagnostics)?;
+    let end_pos = end_token.pos.clone();
     try_token_kind!(
         end_token,
         End => {
@@ -164,6 +177,45 @@ pub fn parse_subprogram_body(
         specification,
         declarations,
         statements,
+        end_pos,
+    })
+}
+
+/// LRM 4.4 Subprogram instantiation declarations
+/// The leading `function` or `procedure` keyword has already been consumed
+fn parse_subprogram_instantiation(
+    stream: &mut TokenStream,
+    kind: SubprogramKind,
+) -> ParseResult<SubprogramInstantiation> {
+    let designator = parse_designator(stream)?;
+    stream.expect_kind(Is)?;
+    stream.expect_kind(New)?;
+    let subprogram_name = parse_selected_name(stream)?;
+    let signature = {
+        if stream.peek_kind()? == Some(LeftSquare) {
+            Some(parse_signature(stream)?)
+        } else {
+            None
+        }
+    };
+
+    let token = stream.expect()?;
+    let generic_map = try_token_kind!(
+        token,
+        Generic => {
+            stream.expect_kind(Map)?;
+            let association_list = parse_association_list(stream)?;
+            stream.expect_kind(SemiColon)?;
+            Some(association_list)
+        },
+        SemiColon => None);
+
+    Ok(SubprogramInstantiation {
+        kind,
+        designator: designator.into(),
+        subprogram_name,
+        signature,
+        generic_map,
     })
 }
 
@@ -171,6 +223,20 @@ pub fn parse_subprogram(
     stream: &mut TokenStream,
     diagnostics: &mut dyn DiagnosticHandler,
 ) -> ParseResult<Declaration> {
+    for (kind, keyword) in [
+        (SubprogramKind::Function, Function),
+        (SubprogramKind::Procedure, Procedure),
+    ] {
+        if stream.next_kinds_are(&[keyword, Identifier, Is, New])?
+            || stream.next_kinds_are(&[keyword, StringLiteral, Is, New])?
+        {
+            stream.expect_kind(keyword)?;
+            return Ok(Declaration::SubprogramInstantiation(
+                parse_subprogram_instantiation(stream, kind)?,
+            ));
+        }
+    }
+
     let specification = parse_subprogram_declaration_no_semi(stream, diagnostics)?;
     match_token_kind!(
         stream.expect()?,
@@ -204,6 +270,7 @@ procedure foo;
                     .ident()
                     .map_into(SubprogramDesignator::Identifier)
                     .into(),
+                header: None,
                 parameter_list: Vec::new(),
             })
         );
@@ -225,6 +292,7 @@ function foo return lib.foo.natural;
                     .ident()
                     .map_into(SubprogramDesignator::Identifier)
                     .into(),
+                header: None,
                 parameter_list: Vec::new(),
                 return_type: code.s1("lib.foo.natural").type_mark()
             })
@@ -247,6 +315,7 @@ function \"+\" return lib.foo.natural;
                     pos: code.s1("\"+\"").pos()
                 }
                 .into(),
+                header: None,
                 parameter_list: Vec::new(),
                 return_type: code.s1("lib.foo.natural").type_mark()
             })
@@ -269,6 +338,7 @@ impure function foo return lib.foo.natural;
                     .ident()
                     .map_into(SubprogramDesignator::Identifier)
                     .into(),
+                header: None,
                 parameter_list: Vec::new(),
                 return_type: code.s1("lib.foo.natural").type_mark()
             })
@@ -290,6 +360,7 @@ pure function foo return lib.foo.natural;
                     .ident()
                     .map_into(SubprogramDesignator::Identifier)
                     .into(),
+                header: None,
                 parameter_list: Vec::new(),
                 return_type: code.s1("lib.foo.natural").type_mark()
             })
@@ -310,6 +381,7 @@ procedure foo(foo : natural);
                     .ident()
                     .map_into(SubprogramDesignator::Identifier)
                     .into(),
+                header: None,
                 parameter_list: vec![code.s1("foo : natural").parameter()],
             })
         );
@@ -331,12 +403,61 @@ function foo(foo : natural) return lib.foo.natural;
                     .ident()
                     .map_into(SubprogramDesignator::Identifier)
                     .into(),
+                header: None,
                 parameter_list: vec![code.s1("foo : natural").parameter()],
                 return_type: code.s1("lib.foo.natural").type_mark()
             })
         );
     }
 
+    #[test]
+    pub fn parses_function_specification_with_generics() {
+        let code = Code::new(
+            "\
+function foo generic (type T) parameter (foo : T) return T;
+",
+        );
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_subprogram_declaration),
+            SubprogramDeclaration::Function(FunctionSpecification {
+                pure: true,
+                designator: code
+                    .s1("foo")
+                    .ident()
+                    .map_into(SubprogramDesignator::Identifier)
+                    .into(),
+                header: Some(SubprogramHeader {
+                    generic_list: vec![code.s1("type T").generic()],
+                }),
+                parameter_list: vec![code.s1("foo : T").parameter()],
+                return_type: code.s("T", 3).type_mark()
+            })
+        );
+    }
+
+    #[test]
+    pub fn parses_procedure_specification_with_generics() {
+        let code = Code::new(
+            "\
+procedure foo generic (constant N : natural) (foo : natural);
+",
+        );
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_subprogram_declaration),
+            SubprogramDeclaration::Procedure(ProcedureSpecification {
+                designator: code
+                    .s1("foo")
+                    .ident()
+                    .map_into(SubprogramDesignator::Identifier)
+                    .into(),
+                header: Some(SubprogramHeader {
+                    generic_list: vec![code.s1("constant N : natural").generic()],
+                }),
+                parameter_list: vec![code.s1("foo : natural").parameter()],
+            })
+        );
+    }
+
     #[test]
     pub fn parses_function_signature_only_return() {
         let code = Code::new("[return bar.type_mark]");
@@ -438,6 +559,7 @@ end function;
             specification,
             declarations,
             statements,
+            end_pos: code.s1("end").pos(),
         };
         assert_eq!(
             code.with_stream_no_diagnostics(parse_subprogram),
@@ -460,4 +582,58 @@ function foo(arg : natural) return natural;
             Declaration::SubprogramDeclaration(specification)
         );
     }
+
+    #[test]
+    pub fn parses_function_instantiation() {
+        let code = Code::new(
+            "\
+function foo is new lib.pkg.bar;
+",
+        );
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_subprogram),
+            Declaration::SubprogramInstantiation(SubprogramInstantiation {
+                kind: SubprogramKind::Function,
+                designator: code
+                    .s1("foo")
+                    .ident()
+                    .map_into(SubprogramDesignator::Identifier)
+                    .into(),
+                subprogram_name: code.s1("lib.pkg.bar").selected_name(),
+                signature: None,
+                generic_map: None,
+            })
+        );
+    }
+
+    #[test]
+    pub fn parses_procedure_instantiation_with_signature_and_generic_map() {
+        let code = Code::new(
+            "\
+procedure foo is new bar [natural]
+  generic map (
+    T => natural
+  );
+",
+        );
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_subprogram),
+            Declaration::SubprogramInstantiation(SubprogramInstantiation {
+                kind: SubprogramKind::Procedure,
+                designator: code
+                    .s1("foo")
+                    .ident()
+                    .map_into(SubprogramDesignator::Identifier)
+                    .into(),
+                subprogram_name: code.s1("bar").selected_name(),
+                signature: Some(code.s1("[natural]").signature()),
+                generic_map: Some(
+                    code.s1("(
+    T => natural
+  )")
+                        .association_list()
+                ),
+            })
+        );
+    }
 }
@@ -131,15 +131,24 @@ pub fn parse_element_resolution_indication(
 ) -> ParseResult<ResolutionIndication> {
     stream.expect_kind(LeftPar)?;
 
+    if stream.peek_kind()? == Some(LeftPar) {
+        // Resolution of array elements which are composite
+        let resolution = parse_element_resolution_indication(stream)?;
+        stream.expect_kind(RightPar)?;
+        return Ok(ResolutionIndication::ArrayElement(Box::new(resolution)));
+    }
+
+    let state = stream.state();
     let first_ident = stream.expect_ident()?;
     let token = stream.peek_expect()?;
 
     Ok(try_token_kind!(
         token,
         Dot | RightPar => {
-            let selected_name = first_ident.map_into(|sym| SelectedName::Designator(Designator::Identifier(sym).into_ref()));
+            stream.set_state(state);
+            let selected_name = parse_selected_name(stream)?;
             stream.expect_kind(RightPar)?;
-            ResolutionIndication::ArrayElement(selected_name)
+            ResolutionIndication::ArrayElement(Box::new(ResolutionIndication::FunctionName(selected_name)))
         },
         Identifier | LeftPar => {
             // Record
@@ -249,13 +258,53 @@ mod tests {
         assert_eq!(
             code.with_stream(parse_subtype_indication),
             SubtypeIndication {
-                resolution: ResolutionIndication::ArrayElement(code.s1("resolve").selected_name()),
+                resolution: ResolutionIndication::ArrayElement(Box::new(
+                    ResolutionIndication::FunctionName(code.s1("resolve").selected_name())
+                )),
+                type_mark: code.s1("integer_vector").type_mark(),
+                constraint: None
+            }
+        );
+    }
+
+    #[test]
+    fn parse_subtype_indication_with_selected_array_element_resolution_function() {
+        let code = Code::new("(lib.pkg.resolve) integer_vector");
+        assert_eq!(
+            code.with_stream(parse_subtype_indication),
+            SubtypeIndication {
+                resolution: ResolutionIndication::ArrayElement(Box::new(
+                    ResolutionIndication::FunctionName(code.s1("lib.pkg.resolve").selected_name())
+                )),
                 type_mark: code.s1("integer_vector").type_mark(),
                 constraint: None
             }
         );
     }
 
+    #[test]
+    fn parse_subtype_indication_with_nested_element_resolution() {
+        let code = Code::new("((elem resolve)) rec_array_t");
+
+        let elem_resolution = RecordElementResolution {
+            ident: code.s1("elem").ident(),
+            resolution: Box::new(ResolutionIndication::FunctionName(
+                code.s1("resolve").selected_name(),
+            )),
+        };
+
+        assert_eq!(
+            code.with_stream(parse_subtype_indication),
+            SubtypeIndication {
+                resolution: ResolutionIndication::ArrayElement(Box::new(
+                    ResolutionIndication::Record(vec![elem_resolution])
+                )),
+                type_mark: code.s1("rec_array_t").type_mark(),
+                constraint: None
+            }
+        );
+    }
+
     #[test]
     fn parse_subtype_indication_with_record_element_resolution_function() {
         let code = Code::new("(elem resolve) rec_t");
@@ -284,9 +333,9 @@ mod tests {
 
         let elem1_resolution = RecordElementResolution {
             ident: code.s1("elem1").ident(),
-            resolution: Box::new(ResolutionIndication::ArrayElement(
-                code.s1("resolve1").selected_name(),
-            )),
+            resolution: Box::new(ResolutionIndication::ArrayElement(Box::new(
+                ResolutionIndication::FunctionName(code.s1("resolve1").selected_name()),
+            ))),
         };
 
         let elem2_resolution = RecordElementResolution {
@@ -12,6 +12,7 @@ use super::context::{parse_library_clause, parse_use_clause};
 use super::declarative_part::parse_declarative_part_leave_end_token;
 use super::design_unit::{parse_design_file, parse_entity_declaration};
 use super::expression::{parse_aggregate, parse_choices, parse_expression};
+use super::group_declaration::parse_group_declaration;
 use super::interface_declaration::{parse_generic, parse_parameter, parse_port};
 use super::names::{
     parse_association_list, parse_designator, parse_name, parse_selected_name, parse_type_mark,
@@ -23,6 +24,7 @@ use super::subprogram::{parse_signature, parse_subprogram_declaration_no_semi};
 use super::subtype_indication::parse_subtype_indication;
 use super::tokens::{Comment, Symbols, Token, TokenStream, Tokenizer};
 use super::type_declaration::parse_type_declaration;
+use super::view_declaration::parse_mode_view_declaration;
 use super::waveform::parse_waveform;
 use crate::ast;
 use crate::ast::*;
@@ -37,15 +39,22 @@ use std::sync::Arc;
 
 pub struct CodeBuilder {
     pub symbols: Arc<Symbols>,
+    psl_comments: bool,
 }
 
 impl CodeBuilder {
     pub fn new() -> CodeBuilder {
         CodeBuilder {
             symbols: Arc::new(Symbols::default()),
+            psl_comments: false,
         }
     }
 
+    /// Tokenize comments with embedded PSL as code
+    pub fn enable_psl_comments(&mut self) {
+        self.psl_comments = true;
+    }
+
     pub fn code_from_source(&self, source: Source) -> Code {
         let contents = source.contents();
 
@@ -54,6 +63,7 @@ impl CodeBuilder {
         let code = Code {
             symbols: self.symbols.clone(),
             pos,
+            psl_comments: self.psl_comments,
         };
 
         // Ensure symbol table is populated
@@ -81,6 +91,7 @@ impl CodeBuilder {
 pub struct Code {
     pub symbols: Arc<Symbols>,
     pos: SrcPos,
+    psl_comments: bool,
 }
 
 impl Code {
@@ -96,6 +107,7 @@ impl Code {
         Code {
             symbols: self.symbols.clone(),
             pos: SrcPos::new(self.pos.source.clone(), range),
+            psl_comments: self.psl_comments,
         }
     }
 
@@ -145,7 +157,8 @@ impl Code {
         {
             let contents = self.pos.source.contents();
             let reader = ContentReader::new(&contents);
-            let mut tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader);
+            let mut tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader)
+                .with_psl_comments(self.psl_comments);
             loop {
                 let token = tokenizer.pop();
 
@@ -181,7 +194,8 @@ impl Code {
         );
         let contents = source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &source, reader);
+        let tokenizer =
+            Tokenizer::new(&self.symbols, &source, reader).with_psl_comments(self.psl_comments);
         let mut stream = TokenStream::new(tokenizer);
         forward(&mut stream, self.pos.start());
         parse_fun(&mut stream)
@@ -206,7 +220,8 @@ impl Code {
     {
         let contents = self.pos.source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader);
+        let tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader)
+            .with_psl_comments(self.psl_comments);
         let mut stream = TokenStream::new(tokenizer);
         parse_fun(&mut stream)
     }
@@ -367,10 +382,28 @@ impl Code {
         self.parse_ok(parse_alias_declaration)
     }
 
+    pub fn group_template_decl(&self) -> GroupTemplateDeclaration {
+        match self.parse_ok(parse_group_declaration) {
+            Declaration::GroupTemplate(decl) => decl,
+            decl => panic!("Expected group template declaration, got {decl:?}"),
+        }
+    }
+
+    pub fn group_decl(&self) -> GroupDeclaration {
+        match self.parse_ok(parse_group_declaration) {
+            Declaration::Group(decl) => decl,
+            decl => panic!("Expected group declaration, got {decl:?}"),
+        }
+    }
+
     pub fn component_decl(&self) -> ComponentDeclaration {
         self.with_stream_no_diagnostics(parse_component_declaration)
     }
 
+    pub fn mode_view_decl(&self) -> ModeViewDeclaration {
+        self.with_stream_no_diagnostics(parse_mode_view_declaration)
+    }
+
     pub fn entity_decl(&self) -> EntityDeclaration {
         self.with_stream_no_diagnostics(parse_entity_declaration)
     }
@@ -400,6 +433,7 @@ impl Code {
                 WithPos::new(
                     CallOrIndexed {
                         name,
+                        generic_map: None,
                         parameters: vec![],
                     },
                     pos,
@@ -38,6 +38,7 @@ pub enum Kind {
     Default,
     Port,
     Attribute,
+    Group,
     Begin,
     If,
     Loop,
@@ -87,6 +88,7 @@ pub enum Kind {
     Subtype,
     Access,
     Units,
+    Literal,
     New,
     Array,
     Protected,
@@ -95,6 +97,8 @@ pub enum Kind {
     Function,
     Procedure,
     Vunit,
+    View,
+    Parameter,
 
     // Unary operators
     Abs,
@@ -164,6 +168,13 @@ pub enum Kind {
     Comma,
     ColonEq,
     RightArrow,
+
+    // PSL
+    LeftBrace,
+    RightBrace,
+    Bang,
+    Implies,
+    Iff,
 }
 use self::Kind::*;
 
@@ -278,6 +289,7 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Default => "default",
         Port => "port",
         Attribute => "attribute",
+        Group => "group",
         Begin => "begin",
         If => "if",
         Loop => "loop",
@@ -327,6 +339,7 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Subtype => "subtype",
         Access => "access",
         Units => "units",
+        Literal => "literal",
         New => "new",
         Array => "array",
         Protected => "protected",
@@ -335,6 +348,8 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Function => "function",
         Procedure => "procedure",
         Vunit => "vunit",
+        View => "view",
+        Parameter => "parameter",
 
         // Unary operators
         Abs => "abs",
@@ -404,6 +419,12 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Comma => ",",
         ColonEq => ":=",
         RightArrow => "=>",
+
+        LeftBrace => "{",
+        RightBrace => "}",
+        Bang => "!",
+        Implies => "->",
+        Iff => "<->",
     }
 }
 
@@ -787,6 +808,8 @@ fn parse_exponent(reader: &mut ContentReader) -> Result<i32, TokenError> {
 pub struct TokenState {
     last_token_kind: Option<Kind>,
     start: ReaderState,
+    // The number of open braces within a PSL directive or declaration up to its semicolon
+    psl: Option<u32>,
 }
 
 impl TokenState {
@@ -794,13 +817,27 @@ impl TokenState {
         TokenState {
             last_token_kind: None,
             start,
+            psl: None,
         }
     }
 
+    /// The position after the last token
+    pub fn pos(&self) -> Position {
+        self.start.pos()
+    }
+
     /// Set state to after token
     pub fn set_after(&mut self, token: &Token) {
         self.last_token_kind = Some(token.kind);
         self.start = token.next_state;
+        self.psl = match (token.kind, self.psl) {
+            (SemiColon, Some(0)) => None,
+            (LeftBrace, Some(braces)) => Some(braces + 1),
+            (RightBrace, Some(braces)) => Some(braces.saturating_sub(1)),
+            (Assert | Default, None) => Some(0),
+            (Identifier, None) if starts_psl(token) => Some(0),
+            (_, psl) => psl,
+        };
     }
 }
 
@@ -883,6 +920,62 @@ fn parse_comment(reader: &mut ContentReader) -> Comment {
     }
 }
 
+/// The words that start a PSL directive or declaration
+const PSL_KEYWORDS: &[&str] = &[
+    "default", "assert", "assume", "cover", "property", "sequence", "restrict",
+];
+
+/// An identifier that starts a PSL directive or declaration which is not a VHDL keyword
+fn starts_psl(token: &Token) -> bool {
+    match token.value {
+        Value::Identifier(ref sym) => PSL_KEYWORDS.iter().any(|keyword| sym.eq_basic(keyword)),
+        _ => false,
+    }
+}
+
+/// Pop a word of letters, digits and underscores in lower case
+fn pop_word(reader: &mut ContentReader) -> String {
+    let mut word = String::new();
+    while let Some(chr) = reader.peek_char() {
+        if !(chr.is_ascii_alphanumeric() || chr == '_') {
+            break;
+        }
+        reader.skip();
+        word.push(chr.to_ascii_lowercase());
+    }
+    word
+}
+
+/// True if a PSL keyword follows, optionally after a label
+fn starts_psl_directive(reader: &mut ContentReader) -> bool {
+    let state = reader.state();
+    skip_whitespace_in_line(reader);
+    let mut word = pop_word(reader);
+    skip_whitespace_in_line(reader);
+    if reader.peek_char() == Some(':') {
+        reader.skip();
+        skip_whitespace_in_line(reader);
+        word = pop_word(reader);
+    }
+    reader.set_state(state);
+    PSL_KEYWORDS.contains(&word.as_str())
+}
+
+/// Assume -- has been consumed. A comment starting with the word psl followed by a PSL
+/// directive or declaration, or continuing one, contains embedded PSL which is tokenized
+/// as code after skipping the psl prefix.
+fn skip_psl_prefix(reader: &mut ContentReader, continues: bool) -> bool {
+    let state = reader.state();
+    skip_whitespace_in_line(reader);
+    let is_psl = pop_word(reader) == "psl"
+        && matches!(reader.peek_char(), Some(' ' | '\t'))
+        && (continues || starts_psl_directive(reader));
+    if !is_psl {
+        reader.set_state(state);
+    }
+    is_psl
+}
+
 /// Assume /* has been consumed
 fn parse_multi_line_comment(reader: &mut ContentReader) -> Result<Comment, TokenError> {
     let start_pos = reader.pos().prev_char().prev_char();
@@ -1178,7 +1271,13 @@ fn parse_character_literal(
     }
 }
 
-fn get_leading_comments(reader: &mut ContentReader) -> Result<Vec<Comment>, TokenError> {
+/// Comments before the next token, where comments with embedded PSL are skipped when
+/// `psl_comments` is enabled, `psl` is true within a PSL directive or declaration
+fn get_leading_comments(
+    reader: &mut ContentReader,
+    psl_comments: bool,
+    psl: bool,
+) -> Result<Vec<Comment>, TokenError> {
     let mut comments: Vec<Comment> = Vec::new();
 
     loop {
@@ -1202,6 +1301,9 @@ fn get_leading_comments(reader: &mut ContentReader) -> Result<Vec<Comment>, Toke
             }
             b'-' => {
                 if reader.pop()? == Some(b'-') {
+                    if psl_comments && skip_psl_prefix(reader, psl) {
+                        continue;
+                    }
                     comments.push(parse_comment(reader));
                 } else {
                     reader.set_state(state);
@@ -1251,14 +1353,7 @@ fn get_trailing_comment(reader: &mut ContentReader) -> Result<Option<Comment>, T
     skip_whitespace_in_line(reader);
 
     match reader.pop()? {
-        Some(b'-') => {
-            if reader.pop()? == Some(b'-') {
-                Ok(Some(parse_comment(reader)))
-            } else {
-                reader.set_state(state);
-                Ok(None)
-            }
-        }
+        Some(b'-') if reader.pop()? == Some(b'-') => Ok(Some(parse_comment(reader))),
         _ => {
             reader.set_state(state);
             Ok(None)
@@ -1315,6 +1410,7 @@ impl std::default::Default for Symbols {
             ("default", Default),
             ("port", Port),
             ("attribute", Attribute),
+            ("group", Group),
             ("begin", Begin),
             ("end", End),
             ("if", If),
@@ -1364,6 +1460,7 @@ impl std::default::Default for Symbols {
             ("subtype", Subtype),
             ("access", Access),
             ("units", Units),
+            ("literal", Literal),
             ("new", New),
             ("array", Array),
             ("protected", Protected),
@@ -1388,6 +1485,8 @@ impl std::default::Default for Symbols {
             ("mod", Mod),
             ("rem", Rem),
             ("vunit", Vunit),
+            ("view", View),
+            ("parameter", Parameter),
         ];
 
         let attributes = [
@@ -1495,6 +1594,8 @@ pub struct Tokenizer<'a> {
     source: &'a Source,
     reader: ContentReader<'a>,
     final_comments: Option<Vec<Comment>>,
+    // Comments starting with `-- psl` contain embedded PSL
+    psl_comments: bool,
 }
 
 impl<'a> Tokenizer<'a> {
@@ -1510,9 +1611,16 @@ impl<'a> Tokenizer<'a> {
             source,
             reader,
             final_comments: None,
+            psl_comments: false,
         }
     }
 
+    /// Tokenize comments starting with `-- psl` and a PSL directive or declaration as code
+    pub fn with_psl_comments(mut self, psl_comments: bool) -> Tokenizer<'a> {
+        self.psl_comments = psl_comments;
+        self
+    }
+
     pub fn state(&self) -> TokenState {
         self.state
     }
@@ -1596,7 +1704,11 @@ impl<'a> Tokenizer<'a> {
             }
             b'-' => {
                 self.reader.skip();
-                (Minus, Value::NoValue)
+                if self.state.psl.is_some() && self.reader.skip_if(b'>')? {
+                    (Implies, Value::NoValue)
+                } else {
+                    (Minus, Value::NoValue)
+                }
             }
             b'"' => {
                 self.reader.skip();
@@ -1654,6 +1766,16 @@ impl<'a> Tokenizer<'a> {
                         self.reader.skip();
                         (LtLt, Value::NoValue)
                     }
+                    Some(b'-') if self.state.psl.is_some() => {
+                        let state = self.reader.state();
+                        self.reader.skip();
+                        if self.reader.skip_if(b'>')? {
+                            (Iff, Value::NoValue)
+                        } else {
+                            self.reader.set_state(state);
+                            (LT, Value::NoValue)
+                        }
+                    }
                     _ => (LT, Value::NoValue),
                 }
             }
@@ -1747,6 +1869,19 @@ impl<'a> Tokenizer<'a> {
                 self.reader.skip();
                 (RightSquare, Value::NoValue)
             }
+            // PSL tokens are only recognized within PSL, `!` is otherwise illegal
+            b'{' if self.state.psl.is_some() => {
+                self.reader.skip();
+                (LeftBrace, Value::NoValue)
+            }
+            b'}' if self.state.psl.is_some() => {
+                self.reader.skip();
+                (RightBrace, Value::NoValue)
+            }
+            b'!' if self.state.psl.is_some() => {
+                self.reader.skip();
+                (Bang, Value::NoValue)
+            }
             b'\\' => {
                 self.reader.skip();
                 // LRM 15.4.3 Extended identifers
@@ -1764,7 +1899,11 @@ impl<'a> Tokenizer<'a> {
 
     fn pop_raw(&mut self) -> Result<Option<Token>, TokenError> {
         let prev_pos = self.reader.state().pos();
-        let leading_comments = get_leading_comments(&mut self.reader)?;
+        let leading_comments = get_leading_comments(
+            &mut self.reader,
+            self.psl_comments,
+            self.state.psl.is_some(),
+        )?;
         self.state.start = self.reader.state();
 
         match self.parse_token()? {
@@ -1823,7 +1962,7 @@ impl<'a> Tokenizer<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::syntax::test::Code;
+    use crate::syntax::test::{Code, CodeBuilder};
     use pretty_assertions::assert_eq;
 
     fn state_at_end(code: &Code) -> ReaderState {
@@ -2528,6 +2667,69 @@ my_other_ident",
         assert_eq!(kinds_tokenize("[]"), vec![LeftSquare, RightSquare]);
     }
 
+    #[test]
+    fn tokenize_psl_operators_within_psl() {
+        assert_eq!(
+            kinds_tokenize("cover {}!-><->a<-1; a<->b"),
+            vec![
+                Identifier,
+                LeftBrace,
+                RightBrace,
+                Bang,
+                Implies,
+                Iff,
+                Identifier,
+                LT,
+                Minus,
+                AbstractLiteral,
+                SemiColon,
+                Identifier,
+                LT,
+                Minus,
+                GT,
+                Identifier
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_psl_comments_as_code() {
+        let code = "
+-- psl default clock is clk;
+-- PSL properties are in a separate vunit
+1 -- psl assert always a;
+-- psl check : assert always
+-- psl   {a} -> b;
+-- psl {a}
+-- pslx assert
+";
+        assert_eq!(kinds_tokenize(code), vec![AbstractLiteral]);
+
+        let mut builder = CodeBuilder::new();
+        builder.enable_psl_comments();
+        assert_eq!(
+            kinds(&builder.code(code).tokenize()),
+            vec![
+                Default,
+                Identifier,
+                Is,
+                Identifier,
+                SemiColon,
+                AbstractLiteral,
+                Identifier,
+                Colon,
+                Assert,
+                Identifier,
+                LeftBrace,
+                Identifier,
+                RightBrace,
+                Implies,
+                Identifier,
+                SemiColon
+            ]
+        );
+    }
+
     #[test]
     fn tokenize_ignores_comments() {
         assert_eq!(
@@ -2662,7 +2864,7 @@ comment
                     next_state: state_at_end(&code.s1("begin")),
                     comments: None,
                 }),
-                Err(Diagnostic::error(&code.s1("!"), "Illegal token")),
+                Err(Diagnostic::error(code.s1("!"), "Illegal token")),
                 Ok(Token {
                     kind: End,
                     value: Value::NoValue,
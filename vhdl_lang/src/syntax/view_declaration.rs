@@ -0,0 +1,213 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::common::error_on_end_identifier_mismatch;
+use super::common::ParseResult;
+use super::names::{parse_identifier_list, parse_selected_name};
+use super::subtype_indication::parse_subtype_indication;
+use super::tokens::{Kind::*, TokenStream};
+use crate::ast::*;
+use crate::data::{DiagnosticHandler, WithPos};
+
+/// The name of a mode view either as `name` or `(name)` for an array of records
+fn parse_view_name(stream: &mut TokenStream) -> ParseResult<(ModeViewKind, WithPos<SelectedName>)> {
+    if stream.skip_if_kind(LeftPar)? {
+        let name = parse_selected_name(stream)?;
+        stream.expect_kind(RightPar)?;
+        Ok((ModeViewKind::Array, name))
+    } else {
+        Ok((ModeViewKind::Record, parse_selected_name(stream)?))
+    }
+}
+
+fn parse_element_mode(stream: &mut TokenStream) -> ParseResult<ElementMode> {
+    let token = stream.expect()?;
+    let mode = try_token_kind!(
+        token,
+        In => Mode::In,
+        Out => Mode::Out,
+        InOut => Mode::InOut,
+        Buffer => Mode::Buffer,
+        Linkage => Mode::Linkage,
+        View => {
+            let (kind, name) = parse_view_name(stream)?;
+            return Ok(ElementMode::View(kind, name));
+        }
+    );
+    Ok(ElementMode::Simple(WithPos::new(mode, token.pos)))
+}
+
+/// LRM 6.5.2 Mode view declarations
+pub fn parse_mode_view_declaration(
+    stream: &mut TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<ModeViewDeclaration> {
+    stream.expect_kind(View)?;
+    let ident = WithDecl::new(stream.expect_ident()?);
+    stream.expect_kind(Of)?;
+    let typ = parse_subtype_indication(stream)?;
+    stream.expect_kind(Is)?;
+
+    let mut elements = Vec::new();
+    while !stream.skip_if_kind(End)? {
+        let names = parse_identifier_list(stream)?
+            .into_iter()
+            .map(WithRef::new)
+            .collect();
+        stream.expect_kind(Colon)?;
+        let mode = parse_element_mode(stream)?;
+        stream.expect_kind(SemiColon)?;
+        elements.push(ModeViewElement { names, mode });
+    }
+
+    stream.expect_kind(View)?;
+    if let Some(token) = stream.pop_if_kind(Identifier)? {
+        diagnostics.push_some(error_on_end_identifier_mismatch(
+            &ident.tree,
+            &Some(token.expect_ident()?),
+        ));
+    }
+    stream.expect_kind(SemiColon)?;
+
+    Ok(ModeViewDeclaration {
+        ident,
+        typ,
+        elements,
+    })
+}
+
+/// LRM 6.5.2 Mode view indication of a port
+pub fn parse_mode_view_indication(stream: &mut TokenStream) -> ParseResult<ModeViewIndication> {
+    stream.expect_kind(View)?;
+    let (kind, name) = parse_view_name(stream)?;
+    let subtype_indication = if kind == ModeViewKind::Array {
+        stream.expect_kind(Of)?;
+        Some(parse_subtype_indication(stream)?)
+    } else if stream.skip_if_kind(Of)? {
+        Some(parse_subtype_indication(stream)?)
+    } else {
+        None
+    };
+
+    Ok(ModeViewIndication {
+        kind,
+        name,
+        subtype_indication,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn parses_mode_view_declaration() {
+        let code = Code::new(
+            "\
+view bus_master of bus_t is
+  addr, data_out : out;
+  data_in : in;
+  sub : view sub_view;
+  subs : view (sub_view);
+end view bus_master;
+",
+        );
+        let view = code.with_stream_no_diagnostics(parse_mode_view_declaration);
+        assert_eq!(
+            view,
+            ModeViewDeclaration {
+                ident: code.s1("bus_master").decl_ident(),
+                typ: code.s1("bus_t").subtype_indication(),
+                elements: vec![
+                    ModeViewElement {
+                        names: vec![
+                            WithRef::new(code.s1("addr").ident()),
+                            WithRef::new(code.s1("data_out").ident())
+                        ],
+                        mode: ElementMode::Simple(WithPos::new(
+                            Mode::Out,
+                            code.s1(": out").s1("out").pos()
+                        )),
+                    },
+                    ModeViewElement {
+                        names: vec![WithRef::new(code.s1("data_in").ident())],
+                        mode: ElementMode::Simple(WithPos::new(
+                            Mode::In,
+                            code.s1("in;").s1("in").pos()
+                        )),
+                    },
+                    ModeViewElement {
+                        names: vec![WithRef::new(code.s1("sub ").s1("sub").ident())],
+                        mode: ElementMode::View(
+                            ModeViewKind::Record,
+                            code.s1("sub_view").selected_name()
+                        ),
+                    },
+                    ModeViewElement {
+                        names: vec![WithRef::new(code.s1("subs").ident())],
+                        mode: ElementMode::View(
+                            ModeViewKind::Array,
+                            code.s("sub_view", 2).selected_name()
+                        ),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn mode_view_declaration_end_identifier_mismatch() {
+        let code = Code::new(
+            "\
+view bus_master of bus_t is
+  addr : out;
+end view other;
+",
+        );
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_mode_view_declaration);
+        assert_eq!(
+            diagnostics,
+            vec![crate::data::Diagnostic::error(
+                code.s1("other"),
+                "End identifier mismatch, expected bus_master"
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_mode_view_indications() {
+        let code = Code::new("view bus_master");
+        assert_eq!(
+            code.with_stream(parse_mode_view_indication),
+            ModeViewIndication {
+                kind: ModeViewKind::Record,
+                name: code.s1("bus_master").selected_name(),
+                subtype_indication: None,
+            }
+        );
+
+        let code = Code::new("view bus_master of bus_t");
+        assert_eq!(
+            code.with_stream(parse_mode_view_indication),
+            ModeViewIndication {
+                kind: ModeViewKind::Record,
+                name: code.s1("bus_master").selected_name(),
+                subtype_indication: Some(code.s1("bus_t").subtype_indication()),
+            }
+        );
+
+        let code = Code::new("view (bus_master) of bus_array_t(0 to 1)");
+        assert_eq!(
+            code.with_stream(parse_mode_view_indication),
+            ModeViewIndication {
+                kind: ModeViewKind::Array,
+                name: code.s1("bus_master").selected_name(),
+                subtype_indication: Some(code.s1("bus_array_t(0 to 1)").subtype_indication()),
+            }
+        );
+    }
+}
@@ -10,6 +10,8 @@ extern crate log;
 
 mod rpc_channel;
 mod stdio_server;
+mod tcp_server;
 mod vhdl_server;
 pub use crate::stdio_server::start;
+pub use crate::tcp_server::start_tcp;
 pub use crate::vhdl_server::VHDLServerSettings;
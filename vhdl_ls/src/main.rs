@@ -5,6 +5,7 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use clap::Parser;
+use std::time::Duration;
 use vhdl_ls::VHDLServerSettings;
 
 #[derive(Parser)]
@@ -18,6 +19,26 @@ struct Args {
     /// This will silence all window/showMessage and only use window/logMessage
     #[arg(long, default_value_t = false)]
     silent: bool,
+
+    /// Time budget in milliseconds of find references across all libraries
+    /// Partial results are returned when the budget is exceeded
+    #[arg(long)]
+    query_timeout_ms: Option<u64>,
+
+    /// Listen for clients on a TCP address such as 127.0.0.1:5007 instead of using stdio
+    /// Multiple clients may connect at the same time and share the analyzed project
+    #[arg(long)]
+    tcp: Option<String>,
+
+    /// The diagnostics profile of the vhdl_ls.toml config to use, such as editor
+    /// Clients may select another profile with the profile initialization option
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// The number of diagnostics published for a file after which the others are summarized
+    /// Clients may select another maximum with the maxDiagnosticsPerFile initialization option
+    #[arg(long)]
+    max_diagnostics_per_file: Option<usize>,
 }
 
 fn main() {
@@ -25,8 +46,20 @@ fn main() {
 
     env_logger::init();
     log::info!("Starting language server");
-    vhdl_ls::start(VHDLServerSettings {
+    let settings = VHDLServerSettings {
         no_lint: args.no_lint,
         silent: args.silent,
-    });
+        query_timeout: args.query_timeout_ms.map(Duration::from_millis),
+        profile: args.profile,
+        max_diagnostics_per_file: args.max_diagnostics_per_file.filter(|max| *max > 0),
+    };
+
+    if let Some(addr) = args.tcp {
+        if let Err(err) = vhdl_ls::start_tcp(&addr, settings) {
+            log::error!("Cannot listen on {}: {}", addr, err);
+            std::process::exit(1);
+        }
+    } else {
+        vhdl_ls::start(settings);
+    }
 }
@@ -15,8 +15,8 @@ use serde_json::Value;
 use std::{cell::RefCell, rc::Rc};
 
 use crate::rpc_channel::{RpcChannel, SharedRpcChannel};
-use crate::vhdl_server::VHDLServer;
 use crate::vhdl_server::VHDLServerSettings;
+use crate::vhdl_server::{ClockDomainCrossingsRequest, StateMachinesRequest, VHDLServer};
 
 /// Set up the IO channel for `stdio` and start the VHDL language server.
 pub fn start(settings: VHDLServerSettings) {
@@ -86,9 +86,11 @@ impl ConnectionRpcChannel {
         while let Ok(message) = self.connection.receiver.recv() {
             trace!("Received message: {:?}", message);
             match message {
-                lsp_server::Message::Request(request) => self.handle_request(&mut server, request),
+                lsp_server::Message::Request(request) => {
+                    self.send_response(handle_request(&mut server, request))
+                }
                 lsp_server::Message::Notification(notification) => {
-                    self.handle_notification(&mut server, notification);
+                    handle_notification(&mut server, notification);
                 }
                 lsp_server::Message::Response(response) => {
                     self.handle_response(&mut server, response)
@@ -103,118 +105,6 @@ impl ConnectionRpcChannel {
         self.connection.sender.send(response.into()).unwrap();
     }
 
-    /// Handle incoming requests from the client.
-    fn handle_request(&self, server: &mut VHDLServer, request: lsp_server::Request) {
-        fn extract<R>(
-            request: lsp_server::Request,
-        ) -> Result<(lsp_server::RequestId, R::Params), lsp_server::Request>
-        where
-            R: request::Request,
-            R::Params: serde::de::DeserializeOwned,
-        {
-            request.extract(R::METHOD).map_err(|e| match e {
-                ExtractError::MethodMismatch(r) => r,
-                err @ ExtractError::JsonError { .. } => {
-                    panic!("{err:?}");
-                }
-            })
-        }
-
-        trace!("Handling request: {:?}", request);
-        let request = match extract::<request::GotoDeclaration>(request) {
-            Ok((id, params)) => {
-                let result =
-                    server.text_document_declaration(&params.text_document_position_params);
-                self.send_response(lsp_server::Response::new_ok(id, result));
-                return;
-            }
-            Err(request) => request,
-        };
-        let request = match extract::<request::GotoDefinition>(request) {
-            Ok((id, params)) => {
-                let result = server.text_document_definition(&params.text_document_position_params);
-                self.send_response(lsp_server::Response::new_ok(id, result));
-                return;
-            }
-            Err(request) => request,
-        };
-        let request = match extract::<request::HoverRequest>(request) {
-            Ok((id, params)) => {
-                let result = server.text_document_hover(&params.text_document_position_params);
-                self.send_response(lsp_server::Response::new_ok(id, result));
-                return;
-            }
-            Err(request) => request,
-        };
-        let request = match extract::<request::References>(request) {
-            Ok((id, params)) => {
-                let result = server.text_document_references(&params);
-                self.send_response(lsp_server::Response::new_ok(id, result));
-                return;
-            }
-            Err(request) => request,
-        };
-        let request = match extract::<request::Shutdown>(request) {
-            Ok((id, _params)) => {
-                server.shutdown_server();
-                self.send_response(lsp_server::Response::new_ok(id, ()));
-                return;
-            }
-            Err(request) => request,
-        };
-
-        debug!("Unhandled request: {:?}", request);
-        self.send_response(lsp_server::Response::new_err(
-            request.id,
-            lsp_server::ErrorCode::MethodNotFound as i32,
-            "Unknown request".to_string(),
-        ));
-    }
-
-    /// Handle incoming notifications from the client.
-    fn handle_notification(&self, server: &mut VHDLServer, notification: lsp_server::Notification) {
-        fn extract<N>(
-            notification: lsp_server::Notification,
-        ) -> Result<N::Params, lsp_server::Notification>
-        where
-            N: notification::Notification,
-            N::Params: serde::de::DeserializeOwned,
-        {
-            notification.extract(N::METHOD).map_err(|e| match e {
-                ExtractError::MethodMismatch(n) => n,
-                err @ ExtractError::JsonError { .. } => {
-                    panic!("{err:?}");
-                }
-            })
-        }
-
-        trace!("Handling notification: {:?}", notification);
-        // textDocument/didChange
-        let notification = match extract::<notification::DidChangeTextDocument>(notification) {
-            Ok(params) => return server.text_document_did_change_notification(&params),
-            Err(notification) => notification,
-        };
-        // textDocument/didOpen
-        let notification = match extract::<notification::DidOpenTextDocument>(notification) {
-            Ok(params) => return server.text_document_did_open_notification(&params),
-            Err(notification) => notification,
-        };
-        // workspace.didChangeWatchedFiles
-        let notification = match extract::<notification::DidChangeWatchedFiles>(notification) {
-            Ok(params) => return server.workspace_did_change_watched_files(&params),
-            Err(notification) => notification,
-        };
-        // exit
-        let notification = match extract::<notification::Exit>(notification) {
-            Ok(_params) => return server.exit_notification(),
-            Err(notification) => notification,
-        };
-
-        if !notification.method.starts_with("$/") {
-            debug!("Unhandled notification: {:?}", notification);
-        }
-    }
-
     /// Handle incoming responses (to requests sent by us) from the client.
     fn handle_response(&self, _server: &mut VHDLServer, response: lsp_server::Response) {
         trace!("Handling response: {:?}", response);
@@ -222,3 +112,218 @@ impl ConnectionRpcChannel {
         // outgoing requests do not require confirmation by the client.
     }
 }
+
+/// The parameters of a request from the client that cannot be deserialized
+#[derive(Debug)]
+pub(crate) struct InvalidParams {
+    id: RequestId,
+    error: ExtractError<lsp_server::Request>,
+}
+
+impl InvalidParams {
+    /// The error response to the request
+    pub(crate) fn into_response(self) -> lsp_server::Response {
+        lsp_server::Response::new_err(
+            self.id,
+            lsp_server::ErrorCode::InvalidParams as i32,
+            self.error.to_string(),
+        )
+    }
+}
+
+/// Handle incoming requests from the client and return the response to send back.
+pub(crate) fn handle_request(
+    server: &mut VHDLServer,
+    request: lsp_server::Request,
+) -> lsp_server::Response {
+    try_handle_request(server, request).unwrap_or_else(|err| panic!("{err:?}"))
+}
+
+/// Handle incoming requests from the client and return the response to send back
+/// or the error when the parameters of the request cannot be deserialized.
+pub(crate) fn try_handle_request(
+    server: &mut VHDLServer,
+    request: lsp_server::Request,
+) -> Result<lsp_server::Response, InvalidParams> {
+    fn extract<R>(
+        request: lsp_server::Request,
+    ) -> Result<Result<(lsp_server::RequestId, R::Params), lsp_server::Request>, InvalidParams>
+    where
+        R: request::Request,
+        R::Params: serde::de::DeserializeOwned,
+    {
+        let id = request.id.clone();
+        match request.extract(R::METHOD) {
+            Ok(extracted) => Ok(Ok(extracted)),
+            Err(ExtractError::MethodMismatch(r)) => Ok(Err(r)),
+            Err(error @ ExtractError::JsonError { .. }) => Err(InvalidParams { id, error }),
+        }
+    }
+
+    trace!("Handling request: {:?}", request);
+    let request = match extract::<request::GotoDeclaration>(request)? {
+        Ok((id, params)) => {
+            let result = server.text_document_declaration(&params.text_document_position_params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::GotoDefinition>(request)? {
+        Ok((id, params)) => {
+            let result = server.text_document_definition(&params.text_document_position_params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::HoverRequest>(request)? {
+        Ok((id, params)) => {
+            let result = server.text_document_hover(&params.text_document_position_params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::References>(request)? {
+        Ok((id, params)) => {
+            let result = server.text_document_references(&params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::PrepareRenameRequest>(request)? {
+        Ok((id, params)) => {
+            let result = server.text_document_prepare_rename(&params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::Rename>(request)? {
+        Ok((id, params)) => {
+            let result = server.text_document_rename(&params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::CodeActionRequest>(request)? {
+        Ok((id, params)) => {
+            let result = server.text_document_code_action(&params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::ExecuteCommand>(request)? {
+        Ok((id, params)) => {
+            let result = server.workspace_execute_command(&params);
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<ClockDomainCrossingsRequest>(request)? {
+        Ok((id, _params)) => {
+            let result = server.clock_domain_crossings();
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<StateMachinesRequest>(request)? {
+        Ok((id, _params)) => {
+            let result = server.state_machines();
+            return Ok(lsp_server::Response::new_ok(id, result));
+        }
+        Err(request) => request,
+    };
+    let request = match extract::<request::Shutdown>(request)? {
+        Ok((id, _params)) => {
+            server.shutdown_server();
+            return Ok(lsp_server::Response::new_ok(id, ()));
+        }
+        Err(request) => request,
+    };
+
+    debug!("Unhandled request: {:?}", request);
+    Ok(lsp_server::Response::new_err(
+        request.id,
+        lsp_server::ErrorCode::MethodNotFound as i32,
+        "Unknown request".to_string(),
+    ))
+}
+
+/// Handle incoming notifications from the client.
+pub(crate) fn handle_notification(server: &mut VHDLServer, notification: lsp_server::Notification) {
+    try_handle_notification(server, notification).unwrap_or_else(|err| panic!("{err:?}"))
+}
+
+/// Handle incoming notifications from the client
+/// or return the error when the parameters of the notification cannot be deserialized.
+pub(crate) fn try_handle_notification(
+    server: &mut VHDLServer,
+    notification: lsp_server::Notification,
+) -> Result<(), ExtractError<lsp_server::Notification>> {
+    fn extract<N>(
+        notification: lsp_server::Notification,
+    ) -> Result<Result<N::Params, lsp_server::Notification>, ExtractError<lsp_server::Notification>>
+    where
+        N: notification::Notification,
+        N::Params: serde::de::DeserializeOwned,
+    {
+        match notification.extract(N::METHOD) {
+            Ok(params) => Ok(Ok(params)),
+            Err(ExtractError::MethodMismatch(n)) => Ok(Err(n)),
+            Err(err) => Err(err),
+        }
+    }
+
+    trace!("Handling notification: {:?}", notification);
+    // textDocument/didChange
+    let notification = match extract::<notification::DidChangeTextDocument>(notification)? {
+        Ok(params) => {
+            server.text_document_did_change_notification(&params);
+            return Ok(());
+        }
+        Err(notification) => notification,
+    };
+    // textDocument/didOpen
+    let notification = match extract::<notification::DidOpenTextDocument>(notification)? {
+        Ok(params) => {
+            server.text_document_did_open_notification(&params);
+            return Ok(());
+        }
+        Err(notification) => notification,
+    };
+    // textDocument/didClose
+    let notification = match extract::<notification::DidCloseTextDocument>(notification)? {
+        Ok(params) => {
+            server.text_document_did_close_notification(&params);
+            return Ok(());
+        }
+        Err(notification) => notification,
+    };
+    // workspace.didChangeWatchedFiles
+    let notification = match extract::<notification::DidChangeWatchedFiles>(notification)? {
+        Ok(params) => {
+            server.workspace_did_change_watched_files(&params);
+            return Ok(());
+        }
+        Err(notification) => notification,
+    };
+    // workspace/didChangeConfiguration
+    let notification = match extract::<notification::DidChangeConfiguration>(notification)? {
+        Ok(params) => {
+            server.workspace_did_change_configuration(&params);
+            return Ok(());
+        }
+        Err(notification) => notification,
+    };
+    // exit
+    let notification = match extract::<notification::Exit>(notification)? {
+        Ok(_params) => {
+            server.exit_notification();
+            return Ok(());
+        }
+        Err(notification) => notification,
+    };
+
+    if !notification.method.starts_with("$/") {
+        debug!("Unhandled notification: {:?}", notification);
+    }
+    Ok(())
+}
@@ -0,0 +1,451 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! This module handles setting up `VHDLServer` for communication over TCP.
+//! Multiple clients may be connected at the same time and share a single analyzed project.
+//! The first client to initialize loads the project, later clients join it.
+//! Messages of all clients are handled in order by a single event loop.
+//! A document stays open in the shared project until the last client that opened it closes it
+//! or disconnects.
+
+use lsp_server::{ExtractError, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::Notification as _;
+use lsp_types::{
+    notification, request, DidCloseTextDocumentParams, DidOpenTextDocumentParams, InitializeParams,
+    TextDocumentIdentifier, Url,
+};
+use serde_json::Value;
+
+use std::cell::RefCell;
+use std::io::{self, BufReader};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+use fnv::{FnvHashMap, FnvHashSet};
+
+use crate::rpc_channel::{RpcChannel, SharedRpcChannel};
+use crate::stdio_server::{try_handle_notification, try_handle_request};
+use crate::vhdl_server::{VHDLServer, VHDLServerSettings};
+
+type ClientId = usize;
+
+enum Event {
+    Connected(ClientId, Sender<Message>),
+    Received(ClientId, Message),
+    Disconnected(ClientId),
+}
+
+/// Listen for clients on a TCP socket and start the VHDL language server.
+/// Returns when the socket cannot be bound.
+pub fn start_tcp(addr: impl ToSocketAddrs, settings: VHDLServerSettings) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    info!("Listening for clients on {}", listener.local_addr()?);
+
+    let (events, receiver) = channel();
+    thread::spawn(move || accept_clients(listener, events));
+
+    let clients = Rc::new(Clients::default());
+    let rpc = SharedRpcChannel::new(clients.clone());
+    let server = VHDLServer::new_settings(rpc, settings);
+    main_event_loop(&clients, server, receiver);
+    Ok(())
+}
+
+/// Accept clients and start a reader and a writer thread for each of them
+fn accept_clients(listener: TcpListener, events: Sender<Event>) {
+    for (client_id, stream) in listener.incoming().enumerate() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!("Failed to accept client: {}", err);
+                continue;
+            }
+        };
+        info!("Client {} connected", client_id);
+
+        let reader = match stream.try_clone() {
+            Ok(reader) => reader,
+            Err(err) => {
+                error!("Failed to set up client {}: {}", client_id, err);
+                continue;
+            }
+        };
+
+        let (sender, outgoing) = channel();
+        if events.send(Event::Connected(client_id, sender)).is_err() {
+            return;
+        }
+        thread::spawn(move || write_messages(stream, outgoing));
+
+        let events = events.clone();
+        thread::spawn(move || read_messages(client_id, reader, events));
+    }
+}
+
+fn read_messages(client_id: ClientId, stream: TcpStream, events: Sender<Event>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        match Message::read(&mut reader) {
+            Ok(Some(message)) => {
+                if events.send(Event::Received(client_id, message)).is_err() {
+                    return;
+                }
+            }
+            Ok(None) => break,
+            Err(err) => {
+                error!("Failed to read from client {}: {}", client_id, err);
+                break;
+            }
+        }
+    }
+    let _ = events.send(Event::Disconnected(client_id));
+}
+
+/// Write messages to the client until the sender of the client is dropped
+fn write_messages(mut stream: TcpStream, outgoing: Receiver<Message>) {
+    for message in outgoing {
+        if let Err(err) = message.write(&mut stream) {
+            error!("Failed to write to client: {}", err);
+            return;
+        }
+    }
+}
+
+fn main_event_loop(clients: &Clients, mut server: VHDLServer, events: Receiver<Event>) {
+    for event in events {
+        match event {
+            Event::Connected(client_id, sender) => clients.connect(client_id, sender),
+            Event::Received(client_id, message) => {
+                trace!("Received message from client {}: {:?}", client_id, message);
+                clients.set_current(Some(client_id));
+                handle_message(clients, &mut server, client_id, message);
+                clients.set_current(None);
+            }
+            Event::Disconnected(client_id) => {
+                info!("Client {} disconnected", client_id);
+                disconnect(clients, &mut server, client_id);
+            }
+        }
+    }
+}
+
+fn handle_message(
+    clients: &Clients,
+    server: &mut VHDLServer,
+    client_id: ClientId,
+    message: Message,
+) {
+    match message {
+        Message::Request(request) => {
+            let response = handle_client_request(clients, server, client_id, request);
+            clients.send(client_id, response.into());
+        }
+        Message::Notification(notification) => {
+            handle_client_notification(clients, server, client_id, notification)
+        }
+        Message::Response(response) => {
+            // We currently can ignore incoming responses as the implemented
+            // outgoing requests do not require confirmation by the client.
+            trace!("Handling response: {:?}", response);
+        }
+    }
+}
+
+/// Requests that affect the lifetime of the shared server are handled per client,
+/// all other requests are handled by the shared server
+fn handle_client_request(
+    clients: &Clients,
+    server: &mut VHDLServer,
+    client_id: ClientId,
+    request: Request,
+) -> Response {
+    match request.method.as_str() {
+        <request::Initialize as request::Request>::METHOD => {
+            let result = if server.is_initialized() {
+                info!("Client {} joined the shared project", client_id);
+                server.join_request()
+            } else {
+                match serde_json::from_value::<InitializeParams>(request.params) {
+                    Ok(params) => server.initialize_request(params),
+                    Err(err) => {
+                        return Response::new_err(
+                            request.id,
+                            lsp_server::ErrorCode::InvalidParams as i32,
+                            err.to_string(),
+                        )
+                    }
+                }
+            };
+            clients.initialize(client_id);
+            Response::new_ok(request.id, result)
+        }
+        // The server keeps running for the other clients
+        <request::Shutdown as request::Request>::METHOD => Response::new_ok(request.id, ()),
+        // A request with invalid parameters must not stop the server for the other clients
+        _ => try_handle_request(server, request).unwrap_or_else(|err| {
+            error!("Client {} sent an invalid request: {:?}", client_id, err);
+            err.into_response()
+        }),
+    }
+}
+
+fn handle_client_notification(
+    clients: &Clients,
+    server: &mut VHDLServer,
+    client_id: ClientId,
+    notification: Notification,
+) {
+    match notification.method.as_str() {
+        <notification::Initialized as notification::Notification>::METHOD => {
+            if clients.is_first(client_id) {
+                server.initialized_notification();
+            } else {
+                server.joined_notification();
+            }
+        }
+        <notification::Exit as notification::Notification>::METHOD => {
+            // Dropping the sender of the client ends its writer thread
+            disconnect(clients, server, client_id);
+        }
+        notification::DidOpenTextDocument::METHOD => {
+            match notification
+                .extract::<DidOpenTextDocumentParams>(notification::DidOpenTextDocument::METHOD)
+            {
+                Ok(params) => {
+                    clients.open_document(client_id, params.text_document.uri.clone());
+                    server.text_document_did_open_notification(&params);
+                }
+                Err(err) => invalid_notification(client_id, err),
+            }
+        }
+        notification::DidCloseTextDocument::METHOD => {
+            match notification
+                .extract::<DidCloseTextDocumentParams>(notification::DidCloseTextDocument::METHOD)
+            {
+                Ok(params) => {
+                    if clients.close_document(client_id, &params.text_document.uri) {
+                        server.text_document_did_close_notification(&params);
+                    }
+                }
+                Err(err) => invalid_notification(client_id, err),
+            }
+        }
+        // A notification with invalid parameters is dropped
+        _ => {
+            if let Err(err) = try_handle_notification(server, notification) {
+                invalid_notification(client_id, err);
+            }
+        }
+    }
+}
+
+fn invalid_notification(client_id: ClientId, err: ExtractError<Notification>) {
+    error!("Client {} sent an invalid notification: {}", client_id, err);
+}
+
+/// Remove the client and close the documents that no other client has open
+fn disconnect(clients: &Clients, server: &mut VHDLServer, client_id: ClientId) {
+    for uri in clients.disconnect(client_id) {
+        server.text_document_did_close_notification(&DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier { uri },
+        });
+    }
+}
+
+struct Client {
+    sender: Sender<Message>,
+    initialized: bool,
+    open_documents: FnvHashSet<Url>,
+}
+
+/// The connected clients of the server
+/// Notifications are sent to all initialized clients, requests to the client being handled
+#[derive(Default)]
+struct Clients {
+    clients: RefCell<FnvHashMap<ClientId, Client>>,
+    /// The first client to initialize the server
+    first: RefCell<Option<ClientId>>,
+    /// The client whose message is being handled
+    current: RefCell<Option<ClientId>>,
+    next_outgoing_request_id: RefCell<i32>,
+}
+
+impl Clients {
+    fn connect(&self, client_id: ClientId, sender: Sender<Message>) {
+        self.clients.borrow_mut().insert(
+            client_id,
+            Client {
+                sender,
+                initialized: false,
+                open_documents: FnvHashSet::default(),
+            },
+        );
+    }
+
+    /// Remove the client and return the documents it had open that no other client has open
+    fn disconnect(&self, client_id: ClientId) -> Vec<Url> {
+        let Some(client) = self.clients.borrow_mut().remove(&client_id) else {
+            return Vec::new();
+        };
+        client
+            .open_documents
+            .into_iter()
+            .filter(|uri| !self.is_open(uri))
+            .collect()
+    }
+
+    fn open_document(&self, client_id: ClientId, uri: Url) {
+        if let Some(client) = self.clients.borrow_mut().get_mut(&client_id) {
+            client.open_documents.insert(uri);
+        }
+    }
+
+    /// Close the document for the client and return true if no other client has it open
+    fn close_document(&self, client_id: ClientId, uri: &Url) -> bool {
+        if let Some(client) = self.clients.borrow_mut().get_mut(&client_id) {
+            client.open_documents.remove(uri);
+        }
+        !self.is_open(uri)
+    }
+
+    fn is_open(&self, uri: &Url) -> bool {
+        self.clients
+            .borrow()
+            .values()
+            .any(|client| client.open_documents.contains(uri))
+    }
+
+    fn initialize(&self, client_id: ClientId) {
+        if let Some(client) = self.clients.borrow_mut().get_mut(&client_id) {
+            client.initialized = true;
+        }
+        self.first.borrow_mut().get_or_insert(client_id);
+    }
+
+    fn is_first(&self, client_id: ClientId) -> bool {
+        *self.first.borrow() == Some(client_id)
+    }
+
+    fn set_current(&self, client_id: Option<ClientId>) {
+        *self.current.borrow_mut() = client_id;
+    }
+
+    fn send(&self, client_id: ClientId, message: Message) {
+        trace!("Sending message to client {}: {:?}", client_id, message);
+        if let Some(client) = self.clients.borrow().get(&client_id) {
+            // A client that disconnected is removed when its disconnect event is handled
+            let _ = client.sender.send(message);
+        }
+    }
+}
+
+impl RpcChannel for Clients {
+    /// Send notification to all initialized clients.
+    fn send_notification(&self, method: String, params: Value) {
+        let notification = Notification { method, params };
+        for (client_id, client) in self.clients.borrow().iter() {
+            if client.initialized {
+                trace!(
+                    "Sending notification to client {}: {:?}",
+                    client_id,
+                    notification
+                );
+                let _ = client.sender.send(notification.clone().into());
+            }
+        }
+    }
+
+    /// Send request to the client whose message is being handled.
+    fn send_request(&self, method: String, params: Value) {
+        let Some(client_id) = *self.current.borrow() else {
+            return;
+        };
+        let request_id = self.next_outgoing_request_id.replace_with(|&mut id| id + 1);
+        let request = Request::new(RequestId::from(request_id), method, params);
+        self.send(client_id, request.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect(clients: &Clients, client_id: ClientId) -> Receiver<Message> {
+        let (sender, receiver) = channel();
+        clients.connect(client_id, sender);
+        receiver
+    }
+
+    #[test]
+    fn notifications_are_sent_to_all_initialized_clients() {
+        let clients = Clients::default();
+        let first = connect(&clients, 0);
+        let second = connect(&clients, 1);
+        let uninitialized = connect(&clients, 2);
+        clients.initialize(0);
+        clients.initialize(1);
+
+        clients.send_notification("window/logMessage".to_owned(), Value::Null);
+
+        for receiver in [&first, &second] {
+            assert!(matches!(
+                receiver.try_recv(),
+                Ok(Message::Notification(Notification { ref method, .. })) if method == "window/logMessage"
+            ));
+        }
+        assert!(uninitialized.try_recv().is_err());
+        assert!(clients.is_first(0));
+        assert!(!clients.is_first(1));
+    }
+
+    #[test]
+    fn requests_are_sent_to_the_current_client() {
+        let clients = Clients::default();
+        let first = connect(&clients, 0);
+        let second = connect(&clients, 1);
+        clients.initialize(0);
+        clients.initialize(1);
+
+        clients.set_current(Some(1));
+        clients.send_request("client/registerCapability".to_owned(), Value::Null);
+        clients.set_current(None);
+        clients.send_request("client/registerCapability".to_owned(), Value::Null);
+
+        assert!(first.try_recv().is_err());
+        assert!(matches!(second.try_recv(), Ok(Message::Request(..))));
+        assert!(second.try_recv().is_err());
+    }
+
+    #[test]
+    fn documents_stay_open_until_closed_by_all_clients() {
+        let clients = Clients::default();
+        let _first = connect(&clients, 0);
+        let _second = connect(&clients, 1);
+        let uri = Url::parse("file:///ent.vhd").unwrap();
+        let other = Url::parse("file:///pkg.vhd").unwrap();
+        clients.open_document(0, uri.clone());
+        clients.open_document(1, uri.clone());
+        clients.open_document(1, other.clone());
+
+        assert!(!clients.close_document(1, &uri));
+        assert_eq!(clients.disconnect(1), vec![other]);
+        assert!(clients.close_document(0, &uri));
+        assert_eq!(clients.disconnect(0), vec![]);
+    }
+
+    #[test]
+    fn disconnected_clients_receive_nothing() {
+        let clients = Clients::default();
+        let receiver = connect(&clients, 0);
+        clients.initialize(0);
+        clients.disconnect(0);
+
+        clients.send_notification("window/logMessage".to_owned(), Value::Null);
+        assert!(receiver.try_recv().is_err());
+    }
+}
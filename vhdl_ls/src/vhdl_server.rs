@@ -12,12 +12,95 @@ use std::collections::hash_map::Entry;
 use crate::rpc_channel::SharedRpcChannel;
 use std::io;
 use std::path::{Path, PathBuf};
-use vhdl_lang::{Config, Diagnostic, Message, MessageHandler, Project, Severity, Source, SrcPos};
+use std::time::Duration;
+use vhdl_lang::{
+    canonical_file_name, AnyEntKind, Config, Design, Diagnostic, ErrorCode, ExplainedEntity, Fix,
+    GenericDocumentation, Message, MessageHandler, Origin, OriginMap, PortGrouping, Project,
+    Severity, Source, SrcPos,
+};
+
+/// Lists the declarations made visible by the `use prefix.all` clause at a position
+/// and whether they are referenced, the argument is a TextDocumentPositionParams
+const EXPAND_USE_CLAUSE_COMMAND: &str = "vhdl_ls.expandUseClause";
+
+/// Renders an instantiation of the entity or component referenced at a position
+/// with all generics and ports associated by name, the argument is a TextDocumentPositionParams
+const INSTANTIATION_TEMPLATE_COMMAND: &str = "vhdl_ls.instantiationTemplate";
+
+/// Groups ports of the entity referenced at a position into a new record type declared in a package.
+/// The arguments are a TextDocumentPositionParams and an object with the `ports` to group,
+/// the `portName` of the new port, the `recordName` and the `package` either as `library.package`
+/// or within the library of the entity. Returns the WorkspaceEdit of the refactoring.
+const GROUP_PORTS_COMMAND: &str = "vhdl_ls.groupPortsIntoRecord";
+
+/// Explains how the name at a position was resolved: the scopes that were searched,
+/// the use clauses that made candidates visible and why overloaded candidates were rejected.
+/// The argument is a TextDocumentPositionParams
+const EXPLAIN_RESOLUTION_COMMAND: &str = "vhdl_ls.explainResolution";
+
+/// Lists the entity referenced or declared at each of many positions of a document,
+/// such as those of a viewport, in a single search. The arguments are a TextDocumentIdentifier
+/// and an array of Positions. Returns an array in the order of the positions.
+const ENTITIES_AT_POSITIONS_COMMAND: &str = "vhdl_ls.entitiesAtPositions";
+
+/// Finds the expressions of the workspace matching a structural pattern such as
+/// `rising_edge($clk) and $rst = '0'` where the names starting with `$` are capture variables.
+/// The argument is an object with the `pattern` and an optional `replacement` template.
+/// Returns the locations and captures of the matches or the WorkspaceEdit of the replacement.
+const STRUCTURAL_SEARCH_COMMAND: &str = "vhdl_ls.structuralSearch";
+
+/// Exports the dataflow of the process at a position as a graph in the DOT language, from the
+/// signals it reads through its variables to the signals it assigns, where the edges of
+/// conditions are dashed. The argument is a TextDocumentPositionParams
+const PROCESS_DATAFLOW_COMMAND: &str = "vhdl_ls.processDataflow";
+
+/// The commands registered with the client, each is dispatched by workspace_execute_command
+const COMMANDS: [&str; 7] = [
+    EXPAND_USE_CLAUSE_COMMAND,
+    INSTANTIATION_TEMPLATE_COMMAND,
+    GROUP_PORTS_COMMAND,
+    EXPLAIN_RESOLUTION_COMMAND,
+    ENTITIES_AT_POSITIONS_COMMAND,
+    STRUCTURAL_SEARCH_COMMAND,
+    PROCESS_DATAFLOW_COMMAND,
+];
+
+/// Lists the signals of the workspace that are read by a register of another clock domain
+/// without a synchronizer. The parameters are ignored. Returns an array with the architecture,
+/// the signal and register with their clocks and the location where the register reads the signal.
+pub enum ClockDomainCrossingsRequest {}
+
+impl request::Request for ClockDomainCrossingsRequest {
+    type Params = serde_json::Value;
+    type Result = serde_json::Value;
+    const METHOD: &'static str = "vhdl_ls/clockDomainCrossings";
+}
+
+/// Lists the state machines of the workspace. The parameters are ignored. Returns an array with
+/// the architecture, the state signal with its location, its states, the reset state and the
+/// transitions with the location of the next state of each.
+pub enum StateMachinesRequest {}
+
+impl request::Request for StateMachinesRequest {
+    type Params = serde_json::Value;
+    type Result = serde_json::Value;
+    const METHOD: &'static str = "vhdl_ls/stateMachines";
+}
 
 #[derive(Default, Clone)]
 pub struct VHDLServerSettings {
     pub no_lint: bool,
     pub silent: bool,
+    /// Time budget of find references after which the results found so far are returned.
+    /// Completion is not provided by the server and needs no budget.
+    pub query_timeout: Option<Duration>,
+    /// The diagnostics profile of the config to use unless the client selects one
+    /// with the profile initialization option
+    pub profile: Option<String>,
+    /// The number of diagnostics published for a file after which the others are summarized
+    /// by a single diagnostic, unless the client selects another maximum with the
+    /// maxDiagnosticsPerFile initialization option or setting
+    pub max_diagnostics_per_file: Option<usize>,
 }
 
 pub struct VHDLServer {
@@ -26,9 +109,15 @@ pub struct VHDLServer {
     // To have well defined unit tests that are not affected by environment
     use_external_config: bool,
     project: Project,
-    files_with_notifications: FnvHashMap<Url, ()>,
+    /// The diagnostics last published for each file
+    published_diagnostics: FnvHashMap<Url, Vec<lsp_types::Diagnostic>>,
+    /// The version of each document opened by the client
+    document_versions: FnvHashMap<Url, i32>,
+    document_uris: DocumentUris,
     init_params: Option<InitializeParams>,
     config_file: Option<PathBuf>,
+    /// The workspace root configuration file while it is opened by the client
+    config_document: Option<Source>,
 }
 
 impl VHDLServer {
@@ -38,9 +127,12 @@ impl VHDLServer {
             settings,
             use_external_config: true,
             project: Project::new(),
-            files_with_notifications: FnvHashMap::default(),
+            published_diagnostics: FnvHashMap::default(),
+            document_versions: FnvHashMap::default(),
+            document_uris: DocumentUris::default(),
             init_params: None,
             config_file: None,
+            config_document: None,
         }
     }
 
@@ -51,9 +143,12 @@ impl VHDLServer {
             settings: Default::default(),
             use_external_config,
             project: Project::new(),
-            files_with_notifications: FnvHashMap::default(),
+            published_diagnostics: FnvHashMap::default(),
+            document_versions: FnvHashMap::default(),
+            document_uris: DocumentUris::default(),
             init_params: None,
             config_file: None,
+            config_document: None,
         }
     }
 
@@ -99,15 +194,54 @@ impl VHDLServer {
             }
         };
 
+        if let Some(profile) = self.profile() {
+            if let Err(err) = config.select_profile(&profile) {
+                self.message(Message::error(err));
+            }
+        }
+
         config
     }
 
+    /// The diagnostics profile selected by the client or else by the server settings
+    fn profile(&self) -> Option<String> {
+        self.init_params
+            .as_ref()
+            .and_then(|init_params| init_params.initialization_options.as_ref())
+            .and_then(|options| options.get("profile"))
+            .and_then(|profile| profile.as_str())
+            .map(str::to_owned)
+            .or_else(|| self.settings.profile.clone())
+    }
+
     pub fn initialize_request(&mut self, init_params: InitializeParams) -> InitializeResult {
         self.config_file = self.root_uri_config_file(&init_params);
+        if let Some(ref options) = init_params.initialization_options {
+            self.update_client_settings(options);
+        }
+        self.init_params = Some(init_params);
         let config = self.load_config();
         self.project = Project::from_config(&config, &mut self.message_filter());
-        self.init_params = Some(init_params);
+        self.initialize_result()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.init_params.is_some()
+    }
+
+    /// Respond to the initialize request of a client joining an already initialized server
+    /// The project loaded for the first client is shared and its analysis is reused
+    pub fn join_request(&self) -> InitializeResult {
+        self.initialize_result()
+    }
+
+    /// Publish the diagnostics of the shared project such that a joining client receives them
+    pub fn joined_notification(&mut self) {
+        self.published_diagnostics.clear();
+        self.publish_diagnostics();
+    }
 
+    fn initialize_result(&self) -> InitializeResult {
         let capabilities = ServerCapabilities {
             text_document_sync: Some(TextDocumentSyncCapability::Kind(
                 TextDocumentSyncKind::INCREMENTAL,
@@ -116,6 +250,15 @@ impl VHDLServer {
             definition_provider: Some(OneOf::Left(true)),
             hover_provider: Some(HoverProviderCapability::Simple(true)),
             references_provider: Some(OneOf::Left(true)),
+            rename_provider: Some(OneOf::Right(RenameOptions {
+                prepare_provider: Some(true),
+                work_done_progress_options: Default::default(),
+            })),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: COMMANDS.map(str::to_owned).to_vec(),
+                work_done_progress_options: Default::default(),
+            }),
             ..Default::default()
         };
 
@@ -130,7 +273,7 @@ impl VHDLServer {
         match params.root_uri.clone() {
             Some(root_uri) => root_uri
                 .to_file_path()
-                .map(|root_path| root_path.join("vhdl_ls.toml"))
+                .map(|root_path| canonical_file_name(&root_path.join("vhdl_ls.toml")))
                 .map_err(|_| {
                     self.message(Message::error(format!(
                         "{} {} {:?} ",
@@ -162,14 +305,10 @@ impl VHDLServer {
 
     /// Register capabilities on the client side:
     /// - watch workspace config file for changes
+    /// - watch for created and deleted files that the config file patterns may match
     fn register_capabilities(&mut self) {
         if self.client_supports_did_change_watched_files() {
-            let register_options = DidChangeWatchedFilesRegistrationOptions {
-                watchers: vec![FileSystemWatcher {
-                    glob_pattern: "**/vhdl_ls.toml".to_owned(),
-                    kind: None,
-                }],
-            };
+            let register_options = watched_files_registration_options();
             let params = RegistrationParams {
                 registrations: vec![Registration {
                     id: "workspace/didChangeWatchedFiles".to_owned(),
@@ -188,7 +327,20 @@ impl VHDLServer {
 
     pub fn text_document_did_change_notification(&mut self, params: &DidChangeTextDocumentParams) {
         let file_name = uri_to_file_name(&params.text_document.uri);
-        if let Some(source) = self.project.get_source(&file_name) {
+        self.document_versions.insert(
+            params.text_document.uri.clone(),
+            params.text_document.version,
+        );
+        if let Some(source) = self
+            .config_document
+            .as_ref()
+            .filter(|_| self.is_config_file(&file_name))
+        {
+            for content_change in params.content_changes.iter() {
+                let range = content_change.range.map(from_lsp_range);
+                source.change(range.as_ref(), &content_change.text);
+            }
+        } else if let Some(source) = self.project.get_source(&file_name) {
             for content_change in params.content_changes.iter() {
                 let range = content_change.range.map(from_lsp_range);
                 source.change(range.as_ref(), &content_change.text);
@@ -204,9 +356,17 @@ impl VHDLServer {
     }
 
     pub fn text_document_did_open_notification(&mut self, params: &DidOpenTextDocumentParams) {
-        let TextDocumentItem { uri, text, .. } = &params.text_document;
+        let TextDocumentItem {
+            uri, text, version, ..
+        } = &params.text_document;
         let file_name = uri_to_file_name(uri);
-        if let Some(source) = self.project.get_source(&file_name) {
+        self.document_versions.insert(uri.clone(), *version);
+        self.document_uris
+            .uris
+            .insert(file_name.clone(), uri.clone());
+        if self.is_config_file(&file_name) {
+            self.config_document = Some(Source::inline(&file_name, text));
+        } else if let Some(source) = self.project.get_source(&file_name) {
             source.change(None, text);
             self.project.update_source(&source);
             self.publish_diagnostics();
@@ -221,6 +381,19 @@ impl VHDLServer {
         }
     }
 
+    pub fn text_document_did_close_notification(&mut self, params: &DidCloseTextDocumentParams) {
+        let file_name = uri_to_file_name(&params.text_document.uri);
+        self.document_versions.remove(&params.text_document.uri);
+        if self.is_config_file(&file_name) {
+            self.config_document = None;
+        }
+        self.document_uris.uris.remove(&file_name);
+    }
+
+    fn is_config_file(&self, file_name: &Path) -> bool {
+        self.config_file.as_deref() == Some(file_name)
+    }
+
     pub fn workspace_did_change_watched_files(&mut self, params: &DidChangeWatchedFilesParams) {
         if let Some(config_file) = &self.config_file {
             let config_file_has_changed = params
@@ -236,8 +409,39 @@ impl VHDLServer {
                 self.project
                     .update_config(&config, &mut self.message_filter());
                 self.publish_diagnostics();
+                return;
             }
         }
+
+        let files_created_or_deleted = params.changes.iter().any(|change| {
+            matches!(
+                change.typ,
+                FileChangeType::CREATED | FileChangeType::DELETED
+            )
+        });
+        if files_created_or_deleted {
+            self.project.refresh_configured_files();
+            self.publish_diagnostics();
+        }
+    }
+
+    pub fn workspace_did_change_configuration(&mut self, params: &DidChangeConfigurationParams) {
+        if self.update_client_settings(&params.settings) && self.is_initialized() {
+            self.publish_diagnostics();
+        }
+    }
+
+    /// Apply the settings of the client that override those of the server,
+    /// returns true if the diagnostics need to be published again
+    fn update_client_settings(&mut self, settings: &serde_json::Value) -> bool {
+        let Some(max) = settings.get("maxDiagnosticsPerFile") else {
+            return false;
+        };
+        // Zero or null removes the maximum
+        let max = max.as_u64().filter(|max| *max > 0).map(|max| max as usize);
+        let has_changed = self.settings.max_diagnostics_per_file != max;
+        self.settings.max_diagnostics_per_file = max;
+        has_changed
     }
 
     fn client_supports_related_information(&self) -> bool {
@@ -254,6 +458,20 @@ impl VHDLServer {
         try_fun().unwrap_or(false)
     }
 
+    fn client_supports_document_changes(&self) -> bool {
+        let try_fun = || {
+            self.init_params
+                .as_ref()?
+                .capabilities
+                .workspace
+                .as_ref()?
+                .workspace_edit
+                .as_ref()?
+                .document_changes
+        };
+        try_fun().unwrap_or(false)
+    }
+
     fn client_supports_did_change_watched_files(&self) -> bool {
         let try_fun = || {
             self.init_params
@@ -275,6 +493,9 @@ impl VHDLServer {
 
         let supports_related_information = self.client_supports_related_information();
         let diagnostics = self.project.analyse();
+        for change in self.project.take_file_set_changes() {
+            self.message(change.message());
+        }
         let diagnostics = {
             if supports_related_information {
                 diagnostics
@@ -283,28 +504,49 @@ impl VHDLServer {
             }
         };
 
-        let mut files_with_notifications = std::mem::take(&mut self.files_with_notifications);
-        for (file_uri, diagnostics) in diagnostics_by_uri(diagnostics).into_iter() {
-            let mut lsp_diagnostics = Vec::new();
-            for diagnostic in diagnostics {
-                lsp_diagnostics.push(to_lsp_diagnostic(diagnostic));
+        let mut origins = OriginMaps::default();
+        let diagnostics_by_uri = diagnostics_by_uri(
+            diagnostics,
+            &self.document_uris,
+            &mut origins,
+            supports_related_information,
+        );
+        for message in origins.errors {
+            self.message(message);
+        }
+
+        let mut published_diagnostics = std::mem::take(&mut self.published_diagnostics);
+        for (file_uri, mut lsp_diagnostics) in diagnostics_by_uri.into_iter() {
+            if let Some(max) = self.settings.max_diagnostics_per_file {
+                limit_diagnostics(&mut lsp_diagnostics, max);
+            }
+            // Fixes are only valid for the version of the document they were computed for
+            let version = self.document_versions.get(&file_uri).copied();
+            for lsp_diagnostic in lsp_diagnostics.iter_mut() {
+                if let Some(edits) = lsp_diagnostic.data.take() {
+                    lsp_diagnostic.data = Some(serde_json::json!({
+                        "version": version,
+                        "edits": edits,
+                    }));
+                }
             }
 
             let publish_diagnostics = PublishDiagnosticsParams {
                 uri: file_uri.clone(),
-                diagnostics: lsp_diagnostics,
+                diagnostics: lsp_diagnostics.clone(),
                 version: None,
             };
 
             self.rpc
                 .send_notification("textDocument/publishDiagnostics", publish_diagnostics);
 
-            self.files_with_notifications.insert(file_uri.clone(), ());
+            self.published_diagnostics
+                .insert(file_uri.clone(), lsp_diagnostics);
         }
 
-        for (file_uri, _) in files_with_notifications.drain() {
+        for (file_uri, _) in published_diagnostics.drain() {
             // File has no longer any diagnosics, publish empty notification to clear them
-            if !self.files_with_notifications.contains_key(&file_uri) {
+            if !self.published_diagnostics.contains_key(&file_uri) {
                 let publish_diagnostics = PublishDiagnosticsParams {
                     uri: file_uri.clone(),
                     diagnostics: vec![],
@@ -328,7 +570,7 @@ impl VHDLServer {
         let ent = self
             .project
             .search_reference(&source, from_lsp_pos(params.position))?;
-        Some(srcpos_to_location(ent.decl_pos()?))
+        Some(self.document_uris.srcpos_to_location(ent.decl_pos()?))
     }
 
     // Copy goto-declaration for now
@@ -339,7 +581,47 @@ impl VHDLServer {
         self.text_document_declaration(params)
     }
 
+    /// The declaration of the entity at the position followed by the explanations
+    /// of the codes of the diagnostics at the position
     pub fn text_document_hover(&mut self, params: &TextDocumentPositionParams) -> Option<Hover> {
+        let mut sections: Vec<String> = self.declaration_hover(params).into_iter().collect();
+        sections.extend(self.code_explanations(&params.text_document.uri, params.position));
+        if sections.is_empty() {
+            return None;
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: sections.join("\n\n---\n\n"),
+            }),
+            range: None,
+        })
+    }
+
+    /// The explanations of the codes of the published diagnostics at a position
+    fn code_explanations(&self, uri: &Url, position: lsp_types::Position) -> Vec<String> {
+        let mut codes = Vec::new();
+        for diagnostic in self.published_diagnostics.get(uri).into_iter().flatten() {
+            if !(diagnostic.range.start <= position && position <= diagnostic.range.end) {
+                continue;
+            }
+            let Some(NumberOrString::String(ref name)) = diagnostic.code else {
+                continue;
+            };
+            if let Some(code) = ErrorCode::from_name(name) {
+                if !codes.contains(&code) {
+                    codes.push(code);
+                }
+            }
+        }
+        codes
+            .into_iter()
+            .map(|code| code.explanation().to_owned())
+            .collect()
+    }
+
+    fn declaration_hover(&self, params: &TextDocumentPositionParams) -> Option<String> {
         let source = self
             .project
             .get_source(&uri_to_file_name(&params.text_document.uri))?;
@@ -348,14 +630,23 @@ impl VHDLServer {
             .search_reference(&source, from_lsp_pos(params.position))?;
 
         let value = self.project.format_declaration(ent)?;
+        let mut value = format!("```vhdl\n{value}\n```");
 
-        Some(Hover {
-            contents: HoverContents::Markup(MarkupContent {
-                kind: MarkupKind::Markdown,
-                value: format!("```vhdl\n{value}\n```"),
-            }),
-            range: None,
-        })
+        // Generics are documented by the comments of their declaration
+        if let Some(doc) = self
+            .project
+            .documentation_of_generic(ent)
+            .and_then(|generic| generic.doc)
+        {
+            value.push_str(&format!("\n\n{doc}"));
+        }
+
+        let generics = self.project.generic_documentation(ent);
+        if !generics.is_empty() {
+            value.push_str("\n\n");
+            value.push_str(&generic_table(&generics));
+        }
+        Some(value)
     }
 
     pub fn text_document_references(&mut self, params: &ReferenceParams) -> Vec<Location> {
@@ -371,15 +662,538 @@ impl VHDLServer {
                 )
             });
 
-        if let Some(ent) = ent {
+        let Some(ent) = ent else {
+            return Vec::new();
+        };
+
+        let references = if let Some(timeout) = self.settings.query_timeout {
+            let (references, truncated) = self.project.find_all_references_within(ent, timeout);
+            if truncated {
+                self.message(Message::warning(format!(
+                    "Find references of '{}' exceeded the time budget of {} ms, the results are truncated",
+                    ent.designator(),
+                    timeout.as_millis()
+                )));
+            }
+            references
+        } else {
+            self.project.find_all_references(ent)
+        };
+
+        references
+            .iter()
+            .map(|pos| self.document_uris.srcpos_to_location(pos))
+            .collect()
+    }
+
+    /// The range of the library name at the position, positions of anything else
+    /// are rejected since only libraries can be renamed
+    pub fn text_document_prepare_rename(
+        &mut self,
+        params: &TextDocumentPositionParams,
+    ) -> Option<PrepareRenameResponse> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let pos = self
+            .project
+            .library_reference_at(&source, from_lsp_pos(params.position))?;
+        Some(PrepareRenameResponse::Range(to_lsp_range(pos.range())))
+    }
+
+    /// Rename the library at the position in all design files and the workspace root
+    /// configuration file. The configuration file is edited as opened by the client
+    /// when it has unsaved changes. Only libraries can be renamed.
+    pub fn text_document_rename(&mut self, params: &RenameParams) -> Option<WorkspaceEdit> {
+        let position = &params.text_document_position;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let ent = self
+            .project
+            .search_reference(&source, from_lsp_pos(position.position))?;
+
+        if !ent.is_library() {
+            self.message(Message::error(format!(
+                "Cannot rename {}, only libraries can be renamed",
+                ent.describe()
+            )));
+            return None;
+        }
+
+        let old_name = ent.designator().to_string();
+        let positions = match self.project.rename_library(&old_name, &params.new_name) {
+            Ok(positions) => positions,
+            Err(err) => {
+                self.message(Message::error(err));
+                return None;
+            }
+        };
+
+        let mut changes: FnvHashMap<Url, Vec<TextEdit>> = Default::default();
+        for pos in positions {
+            changes
+                .entry(self.document_uris.file_name_to_uri(pos.file_name()))
+                .or_default()
+                .push(TextEdit::new(
+                    to_lsp_range(pos.range()),
+                    params.new_name.clone(),
+                ));
+        }
+
+        if let Some(ref config_file) = self.config_file {
+            let contents = match self.config_document {
+                Some(ref document) => {
+                    let contents = document.contents();
+                    Ok(contents.text(contents.range()))
+                }
+                None => std::fs::read_to_string(config_file),
+            };
+            if let Ok(contents) = contents {
+                let edits: Vec<_> = Config::library_key_ranges(&contents, &old_name)
+                    .into_iter()
+                    .map(|range| TextEdit::new(to_lsp_range(range), params.new_name.clone()))
+                    .collect();
+                if !edits.is_empty() {
+                    changes.insert(self.document_uris.file_name_to_uri(config_file), edits);
+                }
+            }
+        }
+
+        Some(self.workspace_edit(changes))
+    }
+
+    /// An edit of several documents where each open document is identified by its
+    /// current version, such that a client that supports document changes rejects
+    /// the whole edit rather than applying it to a document that has changed since
+    fn workspace_edit(&self, changes: FnvHashMap<Url, Vec<TextEdit>>) -> WorkspaceEdit {
+        if !self.client_supports_document_changes() {
+            return WorkspaceEdit {
+                changes: Some(changes.into_iter().collect()),
+                ..Default::default()
+            };
+        }
+
+        let mut changes: Vec<_> = changes.into_iter().collect();
+        changes.sort_by(|(uri, _), (other, _)| uri.as_str().cmp(other.as_str()));
+        let edits = changes
+            .into_iter()
+            .map(|(uri, edits)| TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier {
+                    version: self.document_versions.get(&uri).copied(),
+                    uri,
+                },
+                edits: edits.into_iter().map(OneOf::Left).collect(),
+            })
+            .collect();
+        WorkspaceEdit {
+            document_changes: Some(DocumentChanges::Edits(edits)),
+            ..Default::default()
+        }
+    }
+
+    /// Quick fixes of the diagnostics within the range from the edits attached to their data
+    /// and the replacement of a `use prefix.all` clause at the start of the range
+    /// with the names it makes visible that are referenced
+    pub fn text_document_code_action(
+        &mut self,
+        params: &CodeActionParams,
+    ) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let mut actions = Vec::new();
+        for diagnostic in params.context.diagnostics.iter() {
+            for edit in self.quick_fix_edits(uri, diagnostic, params.range) {
+                let mut changes = FnvHashMap::default();
+                changes.insert(uri.clone(), vec![edit.clone()]);
+
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Replace with '{}'", edit.new_text),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(self.workspace_edit(changes)),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        let explicit_imports = self
+            .project
+            .get_source(&uri_to_file_name(uri))
+            .and_then(|source| {
+                self.project
+                    .use_all_to_explicit_imports(&source, from_lsp_pos(params.range.start))
+            });
+        if let Some((pos, replacement)) = explicit_imports {
+            let mut changes = FnvHashMap::default();
+            changes.insert(
+                uri.clone(),
+                vec![TextEdit::new(to_lsp_range(pos.range()), replacement)],
+            );
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Replace with explicit imports".to_owned(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(self.workspace_edit(changes)),
+                ..Default::default()
+            }));
+        }
+        actions
+    }
+
+    /// The edits attached to a diagnostic when they were computed for the current version
+    /// of the document. Otherwise the edits of the same diagnostic as last published,
+    /// which are computed for the current version, within the range of the code action
+    fn quick_fix_edits(
+        &self,
+        uri: &Url,
+        diagnostic: &lsp_types::Diagnostic,
+        range: Range,
+    ) -> Vec<TextEdit> {
+        let fix_data = |diagnostic: &lsp_types::Diagnostic| {
+            let data = diagnostic.data.as_ref()?;
+            let version =
+                serde_json::from_value::<Option<i32>>(data.get("version")?.clone()).ok()?;
+            let edits = serde_json::from_value::<Vec<TextEdit>>(data.get("edits")?.clone()).ok()?;
+            Some((version, edits))
+        };
+
+        let Some((version, edits)) = fix_data(diagnostic) else {
+            return Vec::new();
+        };
+        if version == self.document_versions.get(uri).copied() {
+            return edits;
+        }
+
+        self.published_diagnostics
+            .get(uri)
+            .into_iter()
+            .flatten()
+            .find(|published| {
+                published.code == diagnostic.code
+                    && published.message == diagnostic.message
+                    && published.range.start <= range.end
+                    && range.start <= published.range.end
+            })
+            .and_then(fix_data)
+            .map(|(_, edits)| edits)
+            .unwrap_or_default()
+    }
+
+    pub fn clock_domain_crossings(&self) -> serde_json::Value {
+        serde_json::Value::Array(
             self.project
-                .find_all_references(ent)
+                .clock_domain_crossings()
+                .crossings
                 .iter()
-                .map(srcpos_to_location)
-                .collect()
-        } else {
-            Vec::new()
+                .map(|crossing| {
+                    serde_json::json!({
+                        "architecture": crossing.architecture,
+                        "signal": crossing.signal,
+                        "sourceClock": crossing.source_clock,
+                        "register": crossing.register,
+                        "destinationClock": crossing.destination_clock,
+                        "location": self.document_uris.srcpos_to_location(&crossing.pos),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn state_machines(&self) -> serde_json::Value {
+        serde_json::Value::Array(
+            self.project
+                .state_machines()
+                .state_machines
+                .iter()
+                .map(|state_machine| {
+                    let transitions: Vec<serde_json::Value> = state_machine
+                        .transitions
+                        .iter()
+                        .map(|transition| {
+                            serde_json::json!({
+                                "from": transition.from,
+                                "to": transition.to,
+                                "condition": transition.condition,
+                                "location": self.document_uris.srcpos_to_location(&transition.pos),
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "architecture": state_machine.architecture,
+                        "signal": state_machine.signal,
+                        "states": state_machine.states,
+                        "resetState": state_machine.reset_state,
+                        "location": state_machine
+                            .decl_pos
+                            .as_ref()
+                            .map(|pos| self.document_uris.srcpos_to_location(pos)),
+                        "transitions": transitions,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn workspace_execute_command(
+        &mut self,
+        params: &ExecuteCommandParams,
+    ) -> Option<serde_json::Value> {
+        let arguments = params.arguments.as_slice();
+        match params.command.as_str() {
+            EXPAND_USE_CLAUSE_COMMAND => self.expand_use_clause(arguments),
+            INSTANTIATION_TEMPLATE_COMMAND => self.instantiation_template(arguments),
+            GROUP_PORTS_COMMAND => self.group_ports_into_record(arguments),
+            EXPLAIN_RESOLUTION_COMMAND => self.explain_resolution(arguments),
+            ENTITIES_AT_POSITIONS_COMMAND => self.entities_at_positions(arguments),
+            STRUCTURAL_SEARCH_COMMAND => self.structural_search(arguments),
+            PROCESS_DATAFLOW_COMMAND => self.process_dataflow(arguments),
+            command => {
+                self.message(Message::error(format!("Unknown command '{command}'")));
+                None
+            }
+        }
+    }
+
+    /// The source and position of a command whose first argument is a TextDocumentPositionParams
+    fn command_position(
+        &self,
+        arguments: &[serde_json::Value],
+    ) -> Option<(Source, vhdl_lang::Position)> {
+        let position = arguments.first().and_then(|arg| {
+            serde_json::from_value::<TextDocumentPositionParams>(arg.clone()).ok()
+        })?;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        Some((source, from_lsp_pos(position.position)))
+    }
+
+    /// The edit of the workspace that applies the fixes
+    fn fixes_to_workspace_edit(&self, fixes: Vec<Fix>) -> WorkspaceEdit {
+        let mut changes: FnvHashMap<Url, Vec<TextEdit>> = Default::default();
+        for fix in fixes {
+            changes
+                .entry(self.document_uris.file_name_to_uri(fix.pos.file_name()))
+                .or_default()
+                .push(TextEdit::new(
+                    to_lsp_range(fix.pos.range()),
+                    fix.replacement,
+                ));
+        }
+        self.workspace_edit(changes)
+    }
+
+    fn structural_search(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let options = arguments.first()?;
+        let pattern = options.get("pattern")?.as_str()?;
+        let pattern = match self.project.structural_pattern(pattern) {
+            Ok(pattern) => pattern,
+            Err(err) => {
+                self.message(Message::error(err));
+                return None;
+            }
+        };
+
+        let Some(template) = options.get("replacement").and_then(|value| value.as_str()) else {
+            return Some(serde_json::Value::Array(
+                self.project
+                    .structural_search(&pattern)
+                    .into_iter()
+                    .map(|found| {
+                        let captures: serde_json::Map<_, _> = found
+                            .captures
+                            .iter()
+                            .map(|(name, pos)| {
+                                (
+                                    name.clone(),
+                                    serde_json::json!(self.document_uris.srcpos_to_location(pos)),
+                                )
+                            })
+                            .collect();
+                        serde_json::json!({
+                            "location": self.document_uris.srcpos_to_location(&found.pos),
+                            "text": found.text(),
+                            "captures": captures,
+                        })
+                    })
+                    .collect(),
+            ));
+        };
+
+        let fixes = match self.project.structural_replace(&pattern, template) {
+            Ok(fixes) => fixes,
+            Err(err) => {
+                self.message(Message::error(err));
+                return None;
+            }
+        };
+        serde_json::to_value(self.fixes_to_workspace_edit(fixes)).ok()
+    }
+
+    fn entities_at_positions(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let document = arguments
+            .first()
+            .and_then(|arg| serde_json::from_value::<TextDocumentIdentifier>(arg.clone()).ok())?;
+        let positions = arguments
+            .get(1)
+            .and_then(|arg| serde_json::from_value::<Vec<lsp_types::Position>>(arg.clone()).ok())?;
+        let source = self.project.get_source(&uri_to_file_name(&document.uri))?;
+        let cursors: Vec<_> = positions.into_iter().map(from_lsp_pos).collect();
+
+        Some(serde_json::Value::Array(
+            self.project
+                .search_references(&source, &cursors)
+                .into_iter()
+                .map(|ent| {
+                    ent.map_or(serde_json::Value::Null, |ent| {
+                        serde_json::json!({
+                            "name": ent.designator().to_string(),
+                            "kind": ent.kind().describe(),
+                            "location": ent.decl_pos().map(|pos| self.document_uris.srcpos_to_location(pos)),
+                        })
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    fn instantiation_template(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let (source, cursor) = self.command_position(arguments)?;
+        let ent = self.project.search_reference(&source, cursor)?;
+        let label = format!("{}_inst", ent.designator());
+        ent.instantiation_template(&label)
+            .map(serde_json::Value::String)
+    }
+
+    fn process_dataflow(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let (source, cursor) = self.command_position(arguments)?;
+        self.project
+            .process_dataflow(&source, cursor)
+            .map(|dataflow| serde_json::Value::String(dataflow.to_dot()))
+    }
+
+    fn group_ports_into_record(
+        &self,
+        arguments: &[serde_json::Value],
+    ) -> Option<serde_json::Value> {
+        let (source, cursor) = self.command_position(arguments)?;
+        let ent = self.project.search_reference(&source, cursor)?;
+        if !matches!(ent.kind(), AnyEntKind::Design(Design::Entity(..))) {
+            self.message(Message::error(format!(
+                "Cannot group the ports of {}, only the ports of entities can be grouped",
+                ent.describe()
+            )));
+            return None;
         }
+
+        let library_names = ent
+            .decl_pos()
+            .map(|pos| self.project.library_names_of(pos.file_name()))
+            .unwrap_or_default();
+        let [library_name] = library_names.as_slice() else {
+            self.message(Message::error(format!(
+                "Cannot group the ports of {} since it is not part of exactly one library",
+                ent.describe()
+            )));
+            return None;
+        };
+
+        let options = arguments.get(1)?;
+        let text = |key: &str| options.get(key)?.as_str().map(str::to_owned);
+        let package = text("package")?;
+        let (package_library, package_name) = match package.split_once('.') {
+            Some((library, package)) if !library.eq_ignore_ascii_case("work") => {
+                (library.to_owned(), package.to_owned())
+            }
+            Some((_, package)) => (library_name.clone(), package.to_owned()),
+            None => (library_name.clone(), package.clone()),
+        };
+        let grouping = PortGrouping {
+            library_name: library_name.clone(),
+            entity_name: ent.designator().to_string(),
+            ports: options
+                .get("ports")?
+                .as_array()?
+                .iter()
+                .filter_map(|port| port.as_str().map(str::to_owned))
+                .collect(),
+            port_name: text("portName")?,
+            record_name: text("recordName")?,
+            package_library,
+            package_name,
+        };
+
+        let fixes = match self.project.group_ports_into_record(&grouping) {
+            Ok(fixes) => fixes,
+            Err(err) => {
+                self.message(Message::error(err));
+                return None;
+            }
+        };
+
+        serde_json::to_value(self.fixes_to_workspace_edit(fixes)).ok()
+    }
+
+    fn explain_resolution(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let (source, cursor) = self.command_position(arguments)?;
+        let explanation = self.project.explain_resolution(&source, cursor)?;
+        let entity = |ent: &ExplainedEntity| {
+            serde_json::json!({
+                "description": ent.description,
+                "location": ent.decl_pos.as_ref().map(|pos| self.document_uris.srcpos_to_location(pos)),
+            })
+        };
+
+        Some(serde_json::json!({
+            "name": explanation.designator.to_string(),
+            "explanation": explanation.to_string(),
+            "scopes": explanation
+                .scopes
+                .iter()
+                .map(|declared| declared.iter().map(entity).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            "useClauses": explanation
+                .use_clauses
+                .iter()
+                .map(|candidate| {
+                    let mut value = entity(&candidate.entity);
+                    value["useClause"] =
+                        serde_json::json!(candidate.visible_pos.first().map(|pos| self.document_uris.srcpos_to_location(pos)));
+                    value
+                })
+                .collect::<Vec<_>>(),
+            "candidates": explanation
+                .overloads
+                .iter()
+                .map(|candidate| {
+                    let mut value = entity(&candidate.entity);
+                    value["rejection"] = serde_json::json!(candidate.rejection);
+                    value
+                })
+                .collect::<Vec<_>>(),
+            "resolved": explanation.resolved.as_ref().map(entity),
+            "error": explanation.error,
+        }))
+    }
+
+    fn expand_use_clause(&self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let (source, cursor) = self.command_position(arguments)?;
+        let visible = self.project.expand_use_all(&source, cursor)?;
+
+        Some(serde_json::Value::Array(
+            visible
+                .into_iter()
+                .map(|(ent, referenced)| {
+                    serde_json::json!({
+                        "name": ent.designator().to_string(),
+                        "kind": ent.kind().describe(),
+                        "declaration": ent.pretty().to_string(),
+                        "location": ent.decl_pos().map(|pos| self.document_uris.srcpos_to_location(pos)),
+                        "referenced": referenced,
+                    })
+                })
+                .collect(),
+        ))
     }
 
     fn message_filter(&self) -> MessageFilter {
@@ -394,6 +1208,21 @@ impl VHDLServer {
     }
 }
 
+fn watched_files_registration_options() -> DidChangeWatchedFilesRegistrationOptions {
+    DidChangeWatchedFilesRegistrationOptions {
+        watchers: vec![
+            FileSystemWatcher {
+                glob_pattern: GlobPattern::String("**/vhdl_ls.toml".to_owned()),
+                kind: None,
+            },
+            FileSystemWatcher {
+                glob_pattern: GlobPattern::String("**/*".to_owned()),
+                kind: Some(WatchKind::Create | WatchKind::Delete),
+            },
+        ],
+    }
+}
+
 struct MessageFilter {
     silent: bool,
     rpc: SharedRpcChannel,
@@ -435,11 +1264,27 @@ fn to_lsp_message_type(message_type: &vhdl_lang::MessageType) -> MessageType {
     }
 }
 
-fn srcpos_to_location(pos: &SrcPos) -> Location {
-    let uri = file_name_to_uri(pos.source.file_name());
-    Location {
-        uri,
-        range: to_lsp_range(pos.range()),
+/// The URIs that the client opened documents with by their canonical file name.
+/// Locations within a document that the client reached through a symbolic link or with
+/// a different case refer to the URI of the opened document.
+#[derive(Default)]
+struct DocumentUris {
+    uris: FnvHashMap<PathBuf, Url>,
+}
+
+impl DocumentUris {
+    fn file_name_to_uri(&self, file_name: &Path) -> Url {
+        self.uris
+            .get(file_name)
+            .cloned()
+            .unwrap_or_else(|| file_name_to_uri(file_name))
+    }
+
+    fn srcpos_to_location(&self, pos: &SrcPos) -> Location {
+        Location {
+            uri: self.file_name_to_uri(pos.source.file_name()),
+            range: to_lsp_range(pos.range()),
+        }
     }
 }
 
@@ -471,28 +1316,130 @@ fn from_lsp_range(range: lsp_types::Range) -> vhdl_lang::Range {
     }
 }
 
-fn diagnostics_by_uri(diagnostics: Vec<Diagnostic>) -> FnvHashMap<Url, Vec<Diagnostic>> {
-    let mut map: FnvHashMap<Url, Vec<Diagnostic>> = FnvHashMap::default();
+/// Render the generics of an entity or component as a markdown table
+fn generic_table(generics: &[GenericDocumentation]) -> String {
+    // Table cells are single line and may not contain the column separator
+    fn cell(text: &str) -> String {
+        text.replace('|', "\\|").replace('\n', " ")
+    }
 
-    for diagnostic in diagnostics {
-        let uri = file_name_to_uri(diagnostic.pos.source.file_name());
-        match map.entry(uri) {
-            Entry::Occupied(mut entry) => entry.get_mut().push(diagnostic),
-            Entry::Vacant(entry) => {
-                let vec = vec![diagnostic];
-                entry.insert(vec);
-            }
-        }
+    let mut table = "| Generic | Type | Default | Description |\n|---|---|---|---|\n".to_owned();
+    for generic in generics.iter() {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            cell(&generic.name),
+            cell(&generic.subtype),
+            cell(generic.default.as_deref().unwrap_or_default()),
+            cell(generic.doc.as_deref().unwrap_or_default()),
+        ));
     }
+    table
+}
 
-    map
+/// The origin maps of generated files, read once per publication of diagnostics
+#[derive(Default)]
+struct OriginMaps {
+    maps: FnvHashMap<PathBuf, Option<OriginMap>>,
+    errors: Vec<Message>,
 }
 
-fn flatten_related(diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
-    let mut flat_diagnostics = Vec::new();
-    for mut diagnostic in diagnostics {
-        flat_diagnostics.extend(diagnostic.drain_related());
-        flat_diagnostics.push(diagnostic);
+impl OriginMaps {
+    /// The origin of a position within a generated file that has an origin map
+    fn origin_of(&mut self, pos: &SrcPos) -> Option<Origin> {
+        let file_name = pos.source.file_name();
+        let errors = &mut self.errors;
+        let map =
+            self.maps.entry(file_name.to_owned()).or_insert_with(|| {
+                match OriginMap::read_for(file_name) {
+                    Ok(map) => map,
+                    Err(err) => {
+                        errors.push(Message::file_error(
+                            format!("Failed to read origin map: {err}"),
+                            &OriginMap::sidecar_file_name(file_name),
+                        ));
+                        None
+                    }
+                }
+            });
+        map.as_ref()?
+            .origin(pos.range())
+            .filter(|origin| origin.file_name.is_absolute())
+    }
+}
+
+/// Diagnostics within a generated file that has an origin map are published for the
+/// original file, with the generated location as related information
+fn diagnostics_by_uri(
+    diagnostics: Vec<Diagnostic>,
+    uris: &DocumentUris,
+    origins: &mut OriginMaps,
+    supports_related_information: bool,
+) -> FnvHashMap<Url, Vec<lsp_types::Diagnostic>> {
+    let mut map: FnvHashMap<Url, Vec<lsp_types::Diagnostic>> = FnvHashMap::default();
+
+    for diagnostic in diagnostics {
+        let origin = origins.origin_of(&diagnostic.pos);
+        let generated = uris.srcpos_to_location(&diagnostic.pos);
+        let mut lsp_diagnostic = to_lsp_diagnostic(diagnostic, uris);
+
+        let uri = if let Some(origin) = origin {
+            lsp_diagnostic.range = to_lsp_range(origin.range);
+            // Fixes edit the generated file rather than the original file
+            lsp_diagnostic.data = None;
+            if supports_related_information {
+                lsp_diagnostic
+                    .related_information
+                    .get_or_insert_with(Vec::new)
+                    .insert(
+                        0,
+                        DiagnosticRelatedInformation {
+                            location: generated,
+                            message: "Generated here".to_owned(),
+                        },
+                    );
+            }
+            uris.file_name_to_uri(&origin.file_name)
+        } else {
+            generated.uri
+        };
+
+        match map.entry(uri) {
+            Entry::Occupied(mut entry) => entry.get_mut().push(lsp_diagnostic),
+            Entry::Vacant(entry) => {
+                entry.insert(vec![lsp_diagnostic]);
+            }
+        }
+    }
+
+    map
+}
+
+/// Keep the most severe diagnostics of a file up to the maximum and summarize the others
+/// by a single diagnostic at the start of the file
+fn limit_diagnostics(diagnostics: &mut Vec<lsp_types::Diagnostic>, max: usize) {
+    if diagnostics.len() <= max {
+        return;
+    }
+    // The sort is stable such that diagnostics of the same severity keep their order
+    diagnostics.sort_by_key(|diagnostic| diagnostic.severity.unwrap_or(DiagnosticSeverity::ERROR));
+    let suppressed = diagnostics.len() - max;
+    diagnostics.truncate(max);
+    diagnostics.push(lsp_types::Diagnostic {
+        range: Range::default(),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        source: Some("vhdl ls".to_owned()),
+        message: format!(
+            "{suppressed} more problems suppressed, at most {max} diagnostics are published per file"
+        ),
+        ..Default::default()
+    });
+}
+
+fn flatten_related(diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    let mut flat_diagnostics = Vec::new();
+    for mut diagnostic in diagnostics {
+        flat_diagnostics.extend(diagnostic.drain_related());
+        flat_diagnostics.push(diagnostic);
     }
     flat_diagnostics
 }
@@ -502,12 +1449,14 @@ fn file_name_to_uri(file_name: &Path) -> Url {
     Url::from_file_path(file_name).unwrap()
 }
 
+/// The canonical file name of a document such that a file reached through different paths
+/// is a single source of the project
 fn uri_to_file_name(uri: &Url) -> PathBuf {
     // @TODO return error to client
-    uri.to_file_path().unwrap()
+    canonical_file_name(&uri.to_file_path().unwrap())
 }
 
-fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
+fn to_lsp_diagnostic(diagnostic: Diagnostic, uris: &DocumentUris) -> lsp_types::Diagnostic {
     let severity = match diagnostic.severity {
         Severity::Error => DiagnosticSeverity::ERROR,
         Severity::Warning => DiagnosticSeverity::WARNING,
@@ -518,7 +1467,7 @@ fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
     let related_information = if !diagnostic.related.is_empty() {
         let mut related_information = Vec::new();
         for (pos, msg) in diagnostic.related {
-            let uri = file_name_to_uri(pos.source.file_name());
+            let uri = uris.file_name_to_uri(pos.source.file_name());
             related_information.push(DiagnosticRelatedInformation {
                 location: Location {
                     uri: uri.to_owned(),
@@ -532,13 +1481,40 @@ fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
         None
     };
 
+    // Fixes are sent as the data of the diagnostic to be turned into code actions
+    let edits: Vec<TextEdit> = diagnostic
+        .fixes
+        .iter()
+        .filter(|fix| fix.pos.source == diagnostic.pos.source)
+        .map(|fix| TextEdit {
+            range: to_lsp_range(fix.pos.range()),
+            new_text: fix.replacement.clone(),
+        })
+        .collect();
+    let data = if edits.is_empty() {
+        None
+    } else {
+        serde_json::to_value(edits).ok()
+    };
+
+    // Inactive branches of conditional analysis are dimmed by the editor
+    let tags = if diagnostic.code == Some(ErrorCode::InactiveCode) {
+        Some(vec![DiagnosticTag::UNNECESSARY])
+    } else {
+        None
+    };
+
     lsp_types::Diagnostic {
         range: to_lsp_range(diagnostic.pos.range()),
         severity: Some(severity),
-        code: None,
+        code: diagnostic
+            .code
+            .map(|code| NumberOrString::String(code.as_str().to_owned())),
         source: Some("vhdl ls".to_owned()),
         message: diagnostic.message,
         related_information,
+        tags,
+        data,
         ..Default::default()
     }
 }
@@ -551,14 +1527,34 @@ mod tests {
     use crate::rpc_channel::test_support::*;
 
     fn initialize_server(server: &mut VHDLServer, root_uri: Url) {
-        let capabilities = ClientCapabilities::default();
+        initialize_server_with_options(server, root_uri, None);
+    }
+
+    fn initialize_server_with_options(
+        server: &mut VHDLServer,
+        root_uri: Url,
+        initialization_options: Option<serde_json::Value>,
+    ) {
+        initialize_server_with_capabilities(
+            server,
+            root_uri,
+            initialization_options,
+            ClientCapabilities::default(),
+        );
+    }
 
+    fn initialize_server_with_capabilities(
+        server: &mut VHDLServer,
+        root_uri: Url,
+        initialization_options: Option<serde_json::Value>,
+        capabilities: ClientCapabilities,
+    ) {
         #[allow(deprecated)]
         let initialize_params = InitializeParams {
             process_id: None,
             root_path: None,
             root_uri: Some(root_uri),
-            initialization_options: None,
+            initialization_options,
             capabilities,
             trace: None,
             workspace_folders: None,
@@ -717,6 +1713,86 @@ end entity ent;
         server.text_document_did_change_notification(&did_change);
     }
 
+    #[test]
+    fn did_open_generated_file_publishes_diagnostics_at_origin() {
+        let (mock, mut server) = setup_server();
+
+        let (_tempdir, root_uri) = temp_root_uri();
+        expect_missing_config_messages(&mock);
+        initialize_server(&mut server, root_uri.clone());
+
+        write_file(&root_uri, "ent.vhd.origin", "2 templates/ent.vhd.tpl:10\n");
+        let root = root_uri.to_file_path().unwrap();
+        let file_url = Url::from_file_path(root.join("ent.vhd")).unwrap();
+        let template_url = Url::from_file_path(root.join("templates").join("ent.vhd.tpl")).unwrap();
+        let code = "
+entity ent is
+end entity ent2;
+"
+        .to_owned();
+
+        let did_open = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: file_url.clone(),
+                language_id: "vhdl".to_owned(),
+                version: 0,
+                text: code,
+            },
+        };
+
+        let publish_diagnostics = PublishDiagnosticsParams {
+            uri: template_url.clone(),
+            diagnostics: vec![lsp_types::Diagnostic {
+                range: Range {
+                    start: lsp_types::Position {
+                        line: 10,
+                        character: "end entity ".len() as u32,
+                    },
+                    end: lsp_types::Position {
+                        line: 10,
+                        character: "end entity ent2".len() as u32,
+                    },
+                },
+                code: None,
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("vhdl ls".to_owned()),
+                message: "End identifier mismatch, expected ent".to_owned(),
+                ..Default::default()
+            }],
+            version: None,
+        };
+
+        mock.expect_warning_contains("is not part of the project");
+
+        mock.expect_notification("textDocument/publishDiagnostics", publish_diagnostics);
+        server.text_document_did_open_notification(&did_open);
+
+        let did_change = DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: file_url,
+                version: 1,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "
+entity ent is
+end entity ent;
+"
+                .to_owned(),
+            }],
+        };
+
+        let publish_diagnostics = PublishDiagnosticsParams {
+            uri: template_url,
+            diagnostics: vec![],
+            version: None,
+        };
+
+        mock.expect_notification("textDocument/publishDiagnostics", publish_diagnostics);
+        server.text_document_did_change_notification(&did_change);
+    }
+
     fn write_file(root_uri: &Url, file_name: impl AsRef<str>, contents: impl AsRef<str>) -> Url {
         let path = root_uri.to_file_path().unwrap().join(file_name.as_ref());
         std::fs::write(&path, contents.as_ref()).unwrap();
@@ -782,6 +1858,84 @@ lib.files = [
         initialize_server(&mut server, root_uri);
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn did_open_through_symbolic_link_publishes_diagnostics_for_opened_uri() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let root = root_uri.to_file_path().unwrap();
+        std::fs::create_dir(root.join("rtl")).unwrap();
+        std::os::unix::fs::symlink(root.join("rtl"), root.join("link")).unwrap();
+        let code = "\
+entity ent is
+end entity ent2;
+";
+        let file_uri = write_file(&root_uri, "rtl/file.vhd", code);
+        let link_uri = Url::from_file_path(root.join("link").join("file.vhd")).unwrap();
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['rtl/file.vhd']
+",
+        );
+
+        let diagnostic = lsp_types::Diagnostic {
+            range: Range {
+                start: lsp_types::Position {
+                    line: 1,
+                    character: "end entity ".len() as u32,
+                },
+                end: lsp_types::Position {
+                    line: 1,
+                    character: "end entity ent2".len() as u32,
+                },
+            },
+            code: None,
+            severity: Some(DiagnosticSeverity::ERROR),
+            source: Some("vhdl ls".to_owned()),
+            message: "End identifier mismatch, expected ent".to_owned(),
+            ..Default::default()
+        };
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: file_uri.clone(),
+                diagnostics: vec![diagnostic.clone()],
+                version: None,
+            },
+        );
+        initialize_server(&mut server, root_uri);
+
+        // The opened document is the file of the project rather than another file of work
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: link_uri.clone(),
+                diagnostics: vec![diagnostic],
+                version: None,
+            },
+        );
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: file_uri,
+                diagnostics: vec![],
+                version: None,
+            },
+        );
+        server.text_document_did_open_notification(&DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: link_uri,
+                language_id: "vhdl".to_owned(),
+                version: 0,
+                text: code.to_owned(),
+            },
+        });
+    }
+
     #[test]
     fn initialize_with_bad_config() {
         let (mock, mut server) = setup_server();
@@ -798,6 +1952,111 @@ lib.files = [
         initialize_server(&mut server, root_uri);
     }
 
+    #[test]
+    fn initialize_with_unknown_profile() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+
+[profiles.editor]
+duplicate_sensitivity = 'ignore'
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_error_contains("Unknown diagnostics profile 'release'");
+        initialize_server_with_options(
+            &mut server,
+            root_uri,
+            Some(serde_json::json!({ "profile": "release" })),
+        );
+    }
+
+    #[test]
+    fn maximum_diagnostics_per_file() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent1 is
+end entity bad1;
+entity ent2 is
+end entity bad2;
+entity ent3 is
+end entity bad3;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['ent.vhd']
+",
+        );
+
+        let mismatch = |line: u32| lsp_types::Diagnostic {
+            range: Range::new(
+                lsp_types::Position::new(line, "end entity ".len() as u32),
+                lsp_types::Position::new(line, "end entity bad1".len() as u32),
+            ),
+            severity: Some(DiagnosticSeverity::ERROR),
+            source: Some("vhdl ls".to_owned()),
+            message: format!("End identifier mismatch, expected ent{}", line / 2 + 1),
+            ..Default::default()
+        };
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: file_uri.clone(),
+                diagnostics: vec![
+                    mismatch(1),
+                    mismatch(3),
+                    lsp_types::Diagnostic {
+                        range: Range::default(),
+                        severity: Some(DiagnosticSeverity::INFORMATION),
+                        source: Some("vhdl ls".to_owned()),
+                        message: "1 more problems suppressed, at most 2 diagnostics are published per file"
+                            .to_owned(),
+                        ..Default::default()
+                    },
+                ],
+                version: None,
+            },
+        );
+        initialize_server_with_options(
+            &mut server,
+            root_uri,
+            Some(serde_json::json!({ "maxDiagnosticsPerFile": 2 })),
+        );
+
+        // Zero removes the maximum
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: file_uri,
+                diagnostics: vec![mismatch(1), mismatch(3), mismatch(5)],
+                version: None,
+            },
+        );
+        server.workspace_did_change_configuration(&DidChangeConfigurationParams {
+            settings: serde_json::json!({ "maxDiagnosticsPerFile": 0 }),
+        });
+
+        // Unrelated settings do not publish the diagnostics again
+        server.workspace_did_change_configuration(&DidChangeConfigurationParams {
+            settings: serde_json::json!({ "other": true }),
+        });
+    }
+
     #[test]
     fn initialize_with_config_missing_files() {
         let (mock, mut server) = setup_server();
@@ -898,56 +2157,1272 @@ lib.files = [
     }
 
     #[test]
-    fn client_register_capability() {
+    fn text_document_rename_library() {
         let (mock, mut server) = setup_server();
         let (_tempdir, root_uri) = temp_root_uri();
 
+        write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+end package;
+",
+        );
+        let top_uri = write_file(
+            &root_uri,
+            "top.vhd",
+            "\
+library old_lib;
+use old_lib.pkg.all;
+package top is
+end package;
+",
+        );
         let config_uri = write_config(
             &root_uri,
             "
 [libraries]
-        ",
+old_lib.files = ['pkg.vhd']
+other.files = ['top.vhd']
+",
         );
 
-        let register_options = DidChangeWatchedFilesRegistrationOptions {
-            watchers: vec![FileSystemWatcher {
-                glob_pattern: "**/vhdl_ls.toml".to_owned(),
-                kind: None,
-            }],
-        };
-        let register_capability = RegistrationParams {
-            registrations: vec![Registration {
-                id: "workspace/didChangeWatchedFiles".to_owned(),
-                method: "workspace/didChangeWatchedFiles".to_owned(),
-                register_options: serde_json::to_value(register_options).ok(),
-            }],
-        };
-
         expect_loaded_config_messages(&mock, &config_uri);
-        mock.expect_request("client/registerCapability", register_capability);
+        initialize_server(&mut server, root_uri);
 
-        let capabilities = ClientCapabilities {
-            workspace: Some(WorkspaceClientCapabilities {
-                did_change_watched_files: Some(DynamicRegistrationClientCapabilities {
-                    dynamic_registration: Some(true),
-                }),
-                ..WorkspaceClientCapabilities::default()
-            }),
-            ..ClientCapabilities::default()
+        let rename = |line: u32, character: u32, new_name: &str| RenameParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: top_uri.clone(),
+                },
+                position: lsp_types::Position { line, character },
+            },
+            new_name: new_name.to_owned(),
+            work_done_progress_params: Default::default(),
         };
-        #[allow(deprecated)]
-        let initialize_params = InitializeParams {
-            root_uri: Some(root_uri),
-            capabilities,
-            ..Default::default()
+        let edit = |line: u32, start: usize, new_name: &str| {
+            TextEdit::new(
+                Range {
+                    start: lsp_types::Position::new(line, start as u32),
+                    end: lsp_types::Position::new(line, (start + "old_lib".len()) as u32),
+                },
+                new_name.to_owned(),
+            )
         };
 
-        server.initialize_request(initialize_params);
-        server.initialized_notification();
-    }
-
-    #[test]
-    fn update_config_file() {
+        let response = server.text_document_rename(&rename(0, 10, "new_lib"));
+        let mut expected = std::collections::HashMap::new();
+        expected.insert(
+            top_uri.clone(),
+            vec![
+                edit(0, "library ".len(), "new_lib"),
+                edit(1, "use ".len(), "new_lib"),
+            ],
+        );
+        expected.insert(config_uri, vec![edit(2, 0, "new_lib")]);
+        assert_eq!(
+            response,
+            Some(WorkspaceEdit {
+                changes: Some(expected),
+                ..Default::default()
+            })
+        );
+
+        mock.expect_error_contains("the library already exists");
+        assert_eq!(server.text_document_rename(&rename(0, 10, "other")), None);
+
+        mock.expect_error_contains("only libraries can be renamed");
+        assert_eq!(
+            server.text_document_rename(&rename(1, "use old_lib.p".len() as u32, "new_pkg")),
+            None
+        );
+
+        let mut prepare_rename = |line: u32, character: u32| {
+            server.text_document_prepare_rename(&rename(line, character, "").text_document_position)
+        };
+        assert_eq!(
+            prepare_rename(1, 6),
+            Some(PrepareRenameResponse::Range(
+                edit(1, "use ".len(), "").range
+            ))
+        );
+        assert_eq!(prepare_rename(1, "use old_lib.p".len() as u32), None);
+        assert_eq!(prepare_rename(2, 0), None);
+    }
+
+    #[test]
+    fn text_document_rename_library_in_open_config_file() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+end package;
+",
+        );
+        let top_uri = write_file(
+            &root_uri,
+            "top.vhd",
+            "\
+library old_lib;
+package top is
+end package;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+old_lib.files = ['pkg.vhd']
+other.files = ['top.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        // The unsaved changes move the library key to another line
+        server.text_document_did_open_notification(&DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: config_uri.clone(),
+                language_id: "toml".to_owned(),
+                version: 1,
+                text: "\n[libraries]\nold_lib.files = ['pkg.vhd']\n".to_owned(),
+            },
+        });
+        server.text_document_did_change_notification(&DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: config_uri.clone(),
+                version: 2,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(Range {
+                    start: lsp_types::Position::new(1, 0),
+                    end: lsp_types::Position::new(1, 0),
+                }),
+                range_length: None,
+                text: "other.files = ['top.vhd']\n".to_owned(),
+            }],
+        });
+
+        let response = server.text_document_rename(&RenameParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: top_uri.clone(),
+                },
+                position: lsp_types::Position::new(0, 10),
+            },
+            new_name: "new_lib".to_owned(),
+            work_done_progress_params: Default::default(),
+        });
+
+        let edit = |line: u32, start: usize| {
+            TextEdit::new(
+                Range {
+                    start: lsp_types::Position::new(line, start as u32),
+                    end: lsp_types::Position::new(line, (start + "old_lib".len()) as u32),
+                },
+                "new_lib".to_owned(),
+            )
+        };
+        let mut expected = std::collections::HashMap::new();
+        expected.insert(top_uri, vec![edit(0, "library ".len())]);
+        expected.insert(config_uri.clone(), vec![edit(3, 0)]);
+        assert_eq!(
+            response,
+            Some(WorkspaceEdit {
+                changes: Some(expected),
+                ..Default::default()
+            })
+        );
+
+        server.text_document_did_close_notification(&DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier { uri: config_uri },
+        });
+        assert!(server.config_document.is_none());
+    }
+
+    fn document_changes_capabilities() -> ClientCapabilities {
+        ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                workspace_edit: Some(WorkspaceEditClientCapabilities {
+                    document_changes: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn text_document_rename_library_with_document_versions() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+end package;
+",
+        );
+        let code = "\
+library old_lib;
+package top is
+end package;
+";
+        let top_uri = write_file(&root_uri, "top.vhd", code);
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+old_lib.files = ['pkg.vhd']
+other.files = ['top.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server_with_capabilities(
+            &mut server,
+            root_uri,
+            None,
+            document_changes_capabilities(),
+        );
+        server.text_document_did_open_notification(&DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: top_uri.clone(),
+                language_id: "vhdl".to_owned(),
+                version: 3,
+                text: code.to_owned(),
+            },
+        });
+
+        let response = server.text_document_rename(&RenameParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: top_uri.clone(),
+                },
+                position: lsp_types::Position::new(0, 10),
+            },
+            new_name: "new_lib".to_owned(),
+            work_done_progress_params: Default::default(),
+        });
+
+        let edit = |line: u32, start: usize| {
+            OneOf::Left(TextEdit::new(
+                Range {
+                    start: lsp_types::Position::new(line, start as u32),
+                    end: lsp_types::Position::new(line, (start + "old_lib".len()) as u32),
+                },
+                "new_lib".to_owned(),
+            ))
+        };
+        let mut expected = vec![
+            TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier {
+                    uri: top_uri,
+                    version: Some(3),
+                },
+                edits: vec![edit(0, "library ".len())],
+            },
+            TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier {
+                    uri: config_uri,
+                    version: None,
+                },
+                edits: vec![edit(2, 0)],
+            },
+        ];
+        expected.sort_by(|edit, other| {
+            edit.text_document
+                .uri
+                .as_str()
+                .cmp(other.text_document.uri.as_str())
+        });
+        assert_eq!(
+            response,
+            Some(WorkspaceEdit {
+                document_changes: Some(DocumentChanges::Edits(expected)),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn text_document_code_action_recomputes_stale_fixes() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let code = "\
+package pkg is
+  procedure update;
+end package;
+
+package body pkg is
+  procedure update is
+  begin
+    updat;
+  end procedure;
+end package body;
+";
+        let pkg_uri = write_file(&root_uri, "pkg.vhd", code);
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['pkg.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        for _ in 0..3 {
+            mock.expect_notification_contains(
+                "textDocument/publishDiagnostics",
+                "did you mean 'update'?",
+            );
+        }
+        initialize_server_with_capabilities(
+            &mut server,
+            root_uri,
+            None,
+            document_changes_capabilities(),
+        );
+        server.text_document_did_open_notification(&DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: pkg_uri.clone(),
+                language_id: "vhdl".to_owned(),
+                version: 1,
+                text: code.to_owned(),
+            },
+        });
+        let diagnostic = server.published_diagnostics[&pkg_uri][0].clone();
+
+        let code_action = |diagnostic: &lsp_types::Diagnostic| CodeActionParams {
+            text_document: TextDocumentIdentifier {
+                uri: pkg_uri.clone(),
+            },
+            range: diagnostic.range,
+            context: CodeActionContext {
+                diagnostics: vec![diagnostic.clone()],
+                only: None,
+                trigger_kind: None,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let fix = |line: u32, version: i32| {
+            let start = "    ".len() as u32;
+            Some(WorkspaceEdit {
+                document_changes: Some(DocumentChanges::Edits(vec![TextDocumentEdit {
+                    text_document: OptionalVersionedTextDocumentIdentifier {
+                        uri: pkg_uri.clone(),
+                        version: Some(version),
+                    },
+                    edits: vec![OneOf::Left(TextEdit::new(
+                        Range {
+                            start: lsp_types::Position::new(line, start),
+                            end: lsp_types::Position::new(line, start + "updat".len() as u32),
+                        },
+                        "update".to_owned(),
+                    ))],
+                }])),
+                ..Default::default()
+            })
+        };
+        let edit_of = |actions: Vec<CodeActionOrCommand>| match actions.as_slice() {
+            [CodeActionOrCommand::CodeAction(action)] => action.edit.clone(),
+            _ => panic!("Expected a single code action, got {actions:?}"),
+        };
+
+        assert_eq!(
+            edit_of(server.text_document_code_action(&code_action(&diagnostic))),
+            fix(7, 1)
+        );
+
+        // The diagnostic of the client was published before a line was inserted
+        server.text_document_did_change_notification(&DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: pkg_uri.clone(),
+                version: 2,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(Range {
+                    start: lsp_types::Position::new(0, 0),
+                    end: lsp_types::Position::new(0, 0),
+                }),
+                range_length: None,
+                text: "\n".to_owned(),
+            }],
+        });
+        let mut stale = diagnostic;
+        stale.range.start.line += 1;
+        stale.range.end.line += 1;
+        assert_eq!(
+            edit_of(server.text_document_code_action(&code_action(&stale))),
+            fix(8, 2)
+        );
+    }
+
+    #[test]
+    fn text_document_code_action_replaces_use_all_with_explicit_imports() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+package pkg is
+  component comp is
+  end component;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  inst : component comp;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['ent.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let range = Range {
+            start: lsp_types::Position::new(5, 0),
+            end: lsp_types::Position::new(5, 0),
+        };
+        let actions = server.text_document_code_action(&CodeActionParams {
+            text_document: TextDocumentIdentifier {
+                uri: file_uri.clone(),
+            },
+            range,
+            context: CodeActionContext {
+                diagnostics: Vec::new(),
+                only: None,
+                trigger_kind: None,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+
+        let mut changes = std::collections::HashMap::new();
+        changes.insert(
+            file_uri,
+            vec![TextEdit::new(
+                Range {
+                    start: lsp_types::Position::new(5, 4),
+                    end: lsp_types::Position::new(5, 16),
+                },
+                "work.pkg.comp".to_owned(),
+            )],
+        );
+        assert_eq!(
+            actions,
+            vec![CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Replace with explicit imports".to_owned(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })]
+        );
+    }
+
+    #[test]
+    fn text_document_hover_shows_generic_table() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+  generic (
+    -- The width of the bus
+    width : natural := default_width
+  );
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  inst : entity work.ent
+    generic map (width => bus_width);
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['ent.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        // The std library is not available
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "No declaration of 'natural'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let mut hover = |line: u32, character: u32| {
+            let hover = server.text_document_hover(&TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: file_uri.clone(),
+                },
+                position: lsp_types::Position { line, character },
+            });
+            match hover.map(|hover| hover.contents) {
+                Some(HoverContents::Markup(markup)) => markup.value,
+                other => panic!("Unexpected hover {other:?}"),
+            }
+        };
+
+        let value = hover(12, "  inst : entity work.e".len() as u32);
+        assert!(
+            value.ends_with(
+                "| Generic | Type | Default | Description |\n\
+                 |---|---|---|---|\n\
+                 | width | natural | default_width | The width of the bus |\n"
+            ),
+            "{value}"
+        );
+    }
+
+    #[test]
+    fn text_document_hover_explains_diagnostic_codes() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+-- fmt: on
+entity ent is
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['ent.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "unbalanced_format_directive",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let mut hover = |line: u32, character: u32| {
+            server
+                .text_document_hover(&TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: file_uri.clone(),
+                    },
+                    position: lsp_types::Position { line, character },
+                })
+                .map(|hover| match hover.contents {
+                    HoverContents::Markup(markup) => markup.value,
+                    other => panic!("Unexpected hover {other:?}"),
+                })
+        };
+
+        assert_eq!(
+            hover(0, 3).as_deref(),
+            Some(ErrorCode::UnbalancedFormatDirective.explanation())
+        );
+        assert_eq!(
+            hover(1, 8).as_deref(),
+            Some("```vhdl\nentity ent is\nend entity;\n```")
+        );
+        assert_eq!(hover(2, 0), None);
+    }
+
+    #[test]
+    fn workspace_execute_command_dispatches_registered_commands() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+        ",
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let execute = |server: &mut VHDLServer, command: &str| {
+            server.workspace_execute_command(&ExecuteCommandParams {
+                command: command.to_owned(),
+                arguments: vec![],
+                work_done_progress_params: Default::default(),
+            })
+        };
+
+        // Each registered command is dispatched and ignores the missing arguments
+        for command in COMMANDS {
+            assert_eq!(execute(&mut server, command), None);
+        }
+
+        mock.expect_error_contains("Unknown command 'vhdl_ls.missing'");
+        assert_eq!(execute(&mut server, "vhdl_ls.missing"), None);
+    }
+
+    #[test]
+    fn workspace_execute_command_group_ports() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let pkg_uri = write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+end package;
+",
+        );
+        let ent_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+  port (
+    a : in bit;
+    b : in bit
+  );
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['pkg.vhd', 'ent.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        // The std library is not available
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "No declaration of 'bit'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let group = |uri: &Url, line: u32, character: u32| ExecuteCommandParams {
+            command: GROUP_PORTS_COMMAND.to_owned(),
+            arguments: vec![
+                serde_json::to_value(TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: uri.clone() },
+                    position: lsp_types::Position { line, character },
+                })
+                .unwrap(),
+                serde_json::json!({
+                    "ports": ["a", "b"],
+                    "portName": "inputs",
+                    "recordName": "inputs_t",
+                    "package": "work.pkg",
+                }),
+            ],
+            work_done_progress_params: Default::default(),
+        };
+        let edit = |start: (u32, u32), end: (u32, u32), new_text: &str| {
+            TextEdit::new(
+                Range {
+                    start: lsp_types::Position::new(start.0, start.1),
+                    end: lsp_types::Position::new(end.0, end.1),
+                },
+                new_text.to_owned(),
+            )
+        };
+
+        let response = server
+            .workspace_execute_command(&group(&ent_uri, 0, "entity e".len() as u32))
+            .and_then(|value| serde_json::from_value::<WorkspaceEdit>(value).ok());
+        let mut expected = std::collections::HashMap::new();
+        expected.insert(
+            pkg_uri.clone(),
+            vec![edit(
+                (1, 0),
+                (1, 0),
+                "\n  type inputs_t is record\n    a : bit;\n    b : bit;\n  end record;\n",
+            )],
+        );
+        expected.insert(
+            ent_uri.clone(),
+            vec![
+                edit((0, 0), (0, 0), "use work.pkg.all;\n\n"),
+                edit((2, 4), (3, 14), "inputs : in inputs_t"),
+            ],
+        );
+        assert_eq!(
+            response,
+            Some(WorkspaceEdit {
+                changes: Some(expected),
+                ..Default::default()
+            })
+        );
+
+        mock.expect_error_contains("only the ports of entities can be grouped");
+        assert_eq!(
+            server.workspace_execute_command(&group(&pkg_uri, 0, "package p".len() as u32)),
+            None
+        );
+    }
+
+    #[test]
+    fn workspace_execute_command_explain_resolution() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let pkg_uri = write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+  type enum_t is (alpha, beta);
+  function fun(arg : enum_t) return enum_t;
+  function fun(a, b : enum_t) return enum_t;
+  constant c : enum_t := fun(alpha);
+end package;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = ['pkg.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let response = server
+            .workspace_execute_command(&ExecuteCommandParams {
+                command: EXPLAIN_RESOLUTION_COMMAND.to_owned(),
+                arguments: vec![serde_json::to_value(TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: pkg_uri.clone(),
+                    },
+                    position: lsp_types::Position::new(
+                        4,
+                        "  constant c : enum_t := f".len() as u32,
+                    ),
+                })
+                .unwrap()],
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+
+        assert_eq!(response["name"], "fun");
+        assert_eq!(
+            response["candidates"][1]["rejection"],
+            "No association of interface constant 'b'"
+        );
+        assert_eq!(
+            response["resolved"]["location"],
+            serde_json::to_value(Location::new(
+                pkg_uri,
+                Range::new(
+                    lsp_types::Position::new(2, 11),
+                    lsp_types::Position::new(2, 14)
+                )
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn workspace_execute_command_process_dataflow() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let ent_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+  port (a, b, sel : in bit; y : out bit);
+end entity;
+
+architecture rtl of ent is
+begin
+  mux : process (all)
+    variable v : bit;
+  begin
+    v := a and b;
+    if sel = '1' then
+      y <= v;
+    else
+      y <= a;
+    end if;
+  end process;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let response = server.workspace_execute_command(&ExecuteCommandParams {
+            command: PROCESS_DATAFLOW_COMMAND.to_owned(),
+            arguments: vec![serde_json::to_value(TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: ent_uri },
+                position: lsp_types::Position::new(10, 0),
+            })
+            .unwrap()],
+            work_done_progress_params: Default::default(),
+        });
+
+        assert_eq!(
+            response,
+            Some(serde_json::Value::String(
+                "\
+digraph \"lib.ent(rtl).mux\" {
+  rankdir=LR;
+  n0 [label=\"a\", shape=box];
+  n1 [label=\"v\", shape=ellipse];
+  n2 [label=\"b\", shape=box];
+  n3 [label=\"y\", shape=box, style=bold];
+  n4 [label=\"sel\", shape=box];
+  n0 -> n1;
+  n2 -> n1;
+  n1 -> n3;
+  n4 -> n3 [style=dashed];
+  n0 -> n3;
+}
+"
+                .to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn workspace_execute_command_entities_at_positions() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let pkg_uri = write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+  type enum_t is (alpha, beta);
+  constant c : enum_t := alpha;
+end package;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = ['pkg.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let response = server
+            .workspace_execute_command(&ExecuteCommandParams {
+                command: ENTITIES_AT_POSITIONS_COMMAND.to_owned(),
+                arguments: vec![
+                    serde_json::to_value(TextDocumentIdentifier {
+                        uri: pkg_uri.clone(),
+                    })
+                    .unwrap(),
+                    serde_json::to_value(vec![
+                        lsp_types::Position::new(2, "  constant c : enum_t := a".len() as u32),
+                        lsp_types::Position::new(0, 0),
+                        lsp_types::Position::new(2, "  constant c : e".len() as u32),
+                    ])
+                    .unwrap(),
+                ],
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+
+        assert_eq!(response[0]["name"], "alpha");
+        assert_eq!(response[1], serde_json::Value::Null);
+        assert_eq!(response[2]["name"], "enum_t");
+        assert_eq!(
+            response[2]["location"],
+            serde_json::to_value(Location::new(
+                pkg_uri,
+                Range::new(
+                    lsp_types::Position::new(1, 7),
+                    lsp_types::Position::new(1, 13)
+                )
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn workspace_execute_command_structural_search() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let pkg_uri = write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+  constant c0 : boolean := not (true and false);
+  constant c1 : boolean := false and false;
+end package;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = ['pkg.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let search = |options: serde_json::Value| ExecuteCommandParams {
+            command: STRUCTURAL_SEARCH_COMMAND.to_owned(),
+            arguments: vec![options],
+            work_done_progress_params: Default::default(),
+        };
+        let location = |line: u32, start: u32, end: u32| {
+            serde_json::to_value(Location::new(
+                pkg_uri.clone(),
+                Range::new(
+                    lsp_types::Position::new(line, start),
+                    lsp_types::Position::new(line, end),
+                ),
+            ))
+            .unwrap()
+        };
+
+        let response = server
+            .workspace_execute_command(&search(serde_json::json!({
+                "pattern": "$a and false",
+            })))
+            .unwrap();
+        assert_eq!(response[0]["text"], "(true and false)");
+        assert_eq!(response[0]["location"], location(1, 31, 47));
+        assert_eq!(response[0]["captures"]["a"], location(1, 32, 36));
+        assert_eq!(response[1]["text"], "false and false");
+
+        let response = server
+            .workspace_execute_command(&search(serde_json::json!({
+                "pattern": "$a and false",
+                "replacement": "$a",
+            })))
+            .and_then(|value| serde_json::from_value::<WorkspaceEdit>(value).ok());
+        let mut expected = std::collections::HashMap::new();
+        expected.insert(
+            pkg_uri.clone(),
+            vec![
+                TextEdit::new(
+                    Range::new(
+                        lsp_types::Position::new(1, 31),
+                        lsp_types::Position::new(1, 47),
+                    ),
+                    "true".to_owned(),
+                ),
+                TextEdit::new(
+                    Range::new(
+                        lsp_types::Position::new(2, 27),
+                        lsp_types::Position::new(2, 42),
+                    ),
+                    "false".to_owned(),
+                ),
+            ],
+        );
+        assert_eq!(
+            response,
+            Some(WorkspaceEdit {
+                changes: Some(expected),
+                ..Default::default()
+            })
+        );
+
+        mock.expect_error_contains("Unknown capture variable '$b'");
+        assert_eq!(
+            server.workspace_execute_command(&search(serde_json::json!({
+                "pattern": "$a and false",
+                "replacement": "$b",
+            }))),
+            None
+        );
+    }
+
+    #[test]
+    fn clock_domain_crossings_request() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let ent_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+  port (clk_a, clk_b : in bit);
+end entity;
+
+architecture rtl of ent is
+  signal a, b : bit;
+begin
+  process (clk_a) begin
+    if rising_edge(clk_a) then
+      a <= not a;
+    end if;
+  end process;
+
+  process (clk_b) begin
+    if rising_edge(clk_b) then
+      b <= a;
+    end if;
+  end process;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        assert_eq!(
+            server.clock_domain_crossings(),
+            serde_json::json!([{
+                "architecture": "lib.ent(rtl)",
+                "signal": "a",
+                "sourceClock": "clk_a",
+                "register": "b",
+                "destinationClock": "clk_b",
+                "location": Location::new(
+                    ent_uri,
+                    Range::new(
+                        lsp_types::Position::new(15, 11),
+                        lsp_types::Position::new(15, 12),
+                    ),
+                ),
+            }])
+        );
+    }
+
+    #[test]
+    fn state_machines_request() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let ent_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+  port (clk, go : in bit);
+end entity;
+
+architecture rtl of ent is
+  type state_t is (idle, busy);
+  signal state : state_t;
+begin
+  process (clk) begin
+    if rising_edge(clk) then
+      case state is
+        when idle =>
+          if go = '1' then
+            state <= busy;
+          end if;
+        when busy =>
+          state <= idle;
+      end case;
+    end if;
+  end process;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let location = |line, start, end| {
+            Location::new(
+                ent_uri.clone(),
+                Range::new(
+                    lsp_types::Position::new(line, start),
+                    lsp_types::Position::new(line, end),
+                ),
+            )
+        };
+        assert_eq!(
+            server.state_machines(),
+            serde_json::json!([{
+                "architecture": "lib.ent(rtl)",
+                "signal": "state",
+                "states": ["idle", "busy"],
+                "resetState": null,
+                "location": location(6, 9, 14),
+                "transitions": [
+                    {
+                        "from": "idle",
+                        "to": "busy",
+                        "condition": "go = '1'",
+                        "location": location(13, 21, 25),
+                    },
+                    {
+                        "from": "busy",
+                        "to": "idle",
+                        "condition": "",
+                        "location": location(16, 19, 23),
+                    },
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn client_register_capability() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+        ",
+        );
+
+        let register_options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: vec![
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String("**/vhdl_ls.toml".to_owned()),
+                    kind: None,
+                },
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String("**/*".to_owned()),
+                    kind: Some(WatchKind::Create | WatchKind::Delete),
+                },
+            ],
+        };
+        let register_capability = RegistrationParams {
+            registrations: vec![Registration {
+                id: "workspace/didChangeWatchedFiles".to_owned(),
+                method: "workspace/didChangeWatchedFiles".to_owned(),
+                register_options: serde_json::to_value(register_options).ok(),
+            }],
+        };
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_request("client/registerCapability", register_capability);
+
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                did_change_watched_files: Some(DynamicRegistrationClientCapabilities {
+                    dynamic_registration: Some(true),
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        #[allow(deprecated)]
+        let initialize_params = InitializeParams {
+            root_uri: Some(root_uri),
+            capabilities,
+            ..Default::default()
+        };
+
+        server.initialize_request(initialize_params);
+        server.initialized_notification();
+    }
+
+    #[test]
+    fn update_config_file() {
         let (mock, mut server) = setup_server();
         let (_tempdir, root_uri) = temp_root_uri();
         let file1_uri = write_file(
@@ -1036,4 +3511,85 @@ lib.files = [
             }],
         });
     }
+
+    #[test]
+    fn created_file_is_analyzed_when_matched_by_config_file() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file1_uri = write_file(
+            &root_uri,
+            "file1.vhd",
+            "\
+architecture rtl of ent is
+begin
+end;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['*.vhd']
+",
+        );
+
+        let publish_diagnostics = PublishDiagnosticsParams {
+            uri: file1_uri.clone(),
+            diagnostics: vec![lsp_types::Diagnostic {
+                range: Range {
+                    start: lsp_types::Position {
+                        line: 0,
+                        character: "architecture rtl of ".len() as u32,
+                    },
+                    end: lsp_types::Position {
+                        line: 0,
+                        character: "architecture rtl of ent".len() as u32,
+                    },
+                },
+                code: None,
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("vhdl ls".to_owned()),
+                message: "No primary unit \'ent\' within library \'lib\'".to_owned(),
+                ..Default::default()
+            }],
+            version: None,
+        };
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification("textDocument/publishDiagnostics", publish_diagnostics);
+        initialize_server(&mut server, root_uri.clone());
+
+        let file2_uri = write_file(
+            &root_uri,
+            "file2.vhd",
+            "\
+entity ent is
+end entity;
+",
+        );
+
+        // Changed files do not affect the files matched by the config file
+        server.workspace_did_change_watched_files(&DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                typ: FileChangeType::CHANGED,
+                uri: file1_uri.clone(),
+            }],
+        });
+
+        mock.expect_warning_contains("file2.vhd was added since the configuration was loaded");
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: file1_uri,
+                diagnostics: vec![],
+                version: None,
+            },
+        );
+        server.workspace_did_change_watched_files(&DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                typ: FileChangeType::CREATED,
+                uri: file2_uri,
+            }],
+        });
+    }
 }